@@ -0,0 +1,161 @@
+//! 搜索框输入热路径基准：SearchInputChar / Backspace 交替输入
+//!
+//! 运行：`cargo bench --bench reducer_input`
+//! 启动时会先打印每次按键的分配次数（计数分配器），随后由 criterion 测量耗时。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use netease_ratui::app::{App, AppSnapshot, View};
+use netease_ratui::core::CoreEffects;
+use netease_ratui::core::infra::{IdAllocator, NextSongCacheManager, RequestKey, RequestTracker};
+use netease_ratui::domain::model::Song;
+use netease_ratui::features::search::handle_search_command;
+use netease_ratui::messages::app::AppCommand;
+
+struct CountingAlloc;
+
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static ALLOC_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        ALLOC_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCS.fetch_add(1, Ordering::Relaxed);
+        ALLOC_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const QUEUE_LEN: usize = 2000;
+const SEARCH_RESULTS: usize = 30;
+
+fn song(id: usize) -> Song {
+    Song {
+        id: id as i64,
+        name: format!("song-{id}"),
+        artists: format!("artist-{}", id % 97),
+        duration_ms: Some(200_000),
+        ..Default::default()
+    }
+}
+
+/// 典型场景：搜索页、播放队列较长、已有一页搜索结果
+fn bench_app() -> App {
+    let mut app = App {
+        view: View::Search,
+        logged_in: true,
+        ..Default::default()
+    };
+    app.play_queue
+        .set_songs((0..QUEUE_LEN).map(song).collect(), Some(0));
+    app.search_results = (0..SEARCH_RESULTS).map(song).collect();
+    app
+}
+
+struct Harness {
+    rt: tokio::runtime::Runtime,
+    app: App,
+    req_ids: IdAllocator,
+    tracker: RequestTracker<RequestKey>,
+    titles: HashMap<i64, String>,
+    next_song_cache: NextSongCacheManager,
+    step: usize,
+}
+
+impl Harness {
+    fn new() -> Self {
+        Self {
+            rt: tokio::runtime::Builder::new_current_thread()
+                .build()
+                .expect("tokio runtime"),
+            app: bench_app(),
+            req_ids: IdAllocator::default(),
+            tracker: RequestTracker::new(),
+            titles: HashMap::new(),
+            next_song_cache: NextSongCacheManager::default(),
+            step: 0,
+        }
+    }
+
+    /// 合成命令流：输入 8 个字符后删掉 8 个，循环往复
+    fn next_command(&mut self) -> AppCommand {
+        let cmd = if self.step % 16 < 8 {
+            AppCommand::SearchInputChar { c: '歌' }
+        } else {
+            AppCommand::SearchInputBackspace
+        };
+        self.step += 1;
+        cmd
+    }
+
+    fn keystroke(&mut self) -> CoreEffects {
+        let cmd = self.next_command();
+        let mut effects = CoreEffects::default();
+        let handled = self.rt.block_on(handle_search_command(
+            cmd,
+            &mut self.app,
+            &self.req_ids,
+            &mut self.tracker,
+            &mut self.titles,
+            &mut self.next_song_cache,
+            &mut effects,
+        ));
+        assert!(handled);
+        effects
+    }
+}
+
+fn report_allocations() {
+    const KEYSTROKES: usize = 1000;
+    let mut h = Harness::new();
+    // 预热：让 runtime 等一次性分配不计入统计
+    for _ in 0..16 {
+        drop(h.keystroke());
+    }
+
+    let allocs = ALLOCS.load(Ordering::Relaxed);
+    let bytes = ALLOC_BYTES.load(Ordering::Relaxed);
+    for _ in 0..KEYSTROKES {
+        drop(black_box(h.keystroke()));
+    }
+    let allocs = ALLOCS.load(Ordering::Relaxed) - allocs;
+    let bytes = ALLOC_BYTES.load(Ordering::Relaxed) - bytes;
+    println!(
+        "reducer_input: 队列 {QUEUE_LEN} 首 / 搜索结果 {SEARCH_RESULTS} 首，每次按键 {} 次分配，{} 字节",
+        allocs / KEYSTROKES,
+        bytes / KEYSTROKES
+    );
+}
+
+fn bench_reducer_input(c: &mut Criterion) {
+    report_allocations();
+
+    let mut h = Harness::new();
+    c.bench_function("search_input_keystroke", |b| {
+        b.iter(|| black_box(h.keystroke()))
+    });
+
+    let app = bench_app();
+    c.bench_function("snapshot_from_app", |b| {
+        b.iter(|| black_box(AppSnapshot::from_app(black_box(&app))))
+    });
+}
+
+criterion_group!(benches, bench_reducer_input);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! 音频焦点：其它程序开始播放声音（视频会议、其它播放器）时降低音量或暂停
+//!
+//! [`AudioFocusPolicy`] 为设置项；[`AudioFocus`] 记录本程序因焦点让出而处于的状态，
+//! 其它声音结束时据此恢复。平台监听见 `crate::audio_focus`，状态切换见
+//! `features::player::audio_focus`。
+
+/// 「音频焦点策略」设置
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AudioFocusPolicy {
+    /// 不理会其它声音
+    #[default]
+    Ignore,
+    /// 按 `audio_focus_duck_percent` 降低音量
+    Duck,
+    /// 暂停，其它声音结束后自动继续
+    Pause,
+}
+
+impl AudioFocusPolicy {
+    pub fn cycle(self, dir: i32) -> Self {
+        const OPTIONS: [AudioFocusPolicy; 3] = [
+            AudioFocusPolicy::Ignore,
+            AudioFocusPolicy::Duck,
+            AudioFocusPolicy::Pause,
+        ];
+        let pos = OPTIONS.iter().position(|p| *p == self).unwrap_or(0);
+        let next = if dir > 0 {
+            (pos + 1) % OPTIONS.len()
+        } else {
+            (pos + OPTIONS.len() - 1) % OPTIONS.len()
+        };
+        OPTIONS[next]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ignore => "忽略",
+            Self::Duck => "降低音量",
+            Self::Pause => "暂停",
+        }
+    }
+}
+
+/// 因其它声音而让出的焦点，其它声音结束时恢复
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AudioFocus {
+    #[default]
+    Held,
+    /// 已降低输出音量（`App::volume` 保持用户设置的值）
+    Ducked,
+    /// 已自动暂停；仅当 `App::auto_paused` 仍为 true 时自动继续
+    Paused,
+}
+
+/// 降低音量的幅度（百分比）范围与调整步长
+pub const DUCK_PERCENT_MIN: u8 = 10;
+pub const DUCK_PERCENT_MAX: u8 = 90;
+pub const DUCK_PERCENT_STEP: u8 = 10;
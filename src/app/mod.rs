@@ -1,8 +1,28 @@
+pub mod audio_focus;
+pub mod notifications;
+pub mod pane;
 pub mod parsers;
+pub mod pinyin;
 pub mod play_queue;
+pub mod playlist_filter;
+pub mod playlist_stats;
+pub mod queue_groups;
+pub mod song_failures;
 pub mod state;
+pub mod tasks;
+pub mod track_filter;
+pub mod track_order;
 
+pub use audio_focus::{AudioFocus, AudioFocusPolicy};
+pub use pane::PaneRatios;
 #[allow(unused_imports)]
 pub use parsers::{parse_search_songs, parse_user_playlists};
 pub use play_queue::PlayQueue;
+pub use playlist_filter::PlaylistFilter;
+pub use playlist_stats::PlaylistStats;
+pub use song_failures::{SongFailureKind, SongFailures};
 pub use state::*;
+pub use tasks::{
+    BackgroundTask, QUIT_WAIT_TIMEOUT, QuitConfirm, TaskCancel, TaskKind, TaskRegistry, TaskStatus,
+};
+pub use track_order::{TrackOrder, TrackOrderKeys};
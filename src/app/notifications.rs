@@ -0,0 +1,111 @@
+//! 通知历史：Toast 只在状态栏显示几秒、且长消息会被截断，这里保留完整文本
+//!
+//! 每条消息最多保留 [`MAX_MESSAGE_BYTES`] 字节，历史最多 [`MAX_NOTIFICATIONS`] 条，
+//! 避免异常长的错误文本（如整段响应体）长期占用内存。
+
+use super::state::ToastLevel;
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// 保留的通知条数
+pub const MAX_NOTIFICATIONS: usize = 50;
+/// 单条通知保留的最大字节数
+pub const MAX_MESSAGE_BYTES: usize = 8 * 1024;
+
+/// 一条历史通知
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+    pub level: ToastLevel,
+    pub at: Instant,
+}
+
+/// 最近的通知（最新的在前）
+#[derive(Debug, Clone, Default)]
+pub struct NotificationLog {
+    entries: VecDeque<Notification>,
+}
+
+impl NotificationLog {
+    pub fn push(&mut self, level: ToastLevel, message: &str) {
+        self.entries.push_front(Notification {
+            message: cap_message(message),
+            level,
+            at: Instant::now(),
+        });
+        self.entries.truncate(MAX_NOTIFICATIONS);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Notification> {
+        self.entries.get(index)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Notification> {
+        self.entries.iter()
+    }
+}
+
+/// 超过 [`MAX_MESSAGE_BYTES`] 时在字符边界处截断，并注明原始长度
+pub fn cap_message(message: &str) -> String {
+    if message.len() <= MAX_MESSAGE_BYTES {
+        return message.to_owned();
+    }
+    let mut end = MAX_MESSAGE_BYTES;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!(
+        "{}…（已截断，原长 {} 字节）",
+        &message[..end],
+        message.len()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_messages_are_kept_verbatim() {
+        assert_eq!(cap_message("网络错误"), "网络错误");
+        let exact = "a".repeat(MAX_MESSAGE_BYTES);
+        assert_eq!(cap_message(&exact), exact);
+    }
+
+    #[test]
+    fn long_messages_are_capped_on_a_char_boundary() {
+        // 「错」占 3 字节，8192 不是 3 的倍数，截断点落在字符中间
+        let long = "错".repeat(5_000);
+        let capped = cap_message(&long);
+        let kept = capped.split('…').next().unwrap();
+        assert!(kept.len() <= MAX_MESSAGE_BYTES);
+        assert_eq!(kept.len(), MAX_MESSAGE_BYTES / 3 * 3);
+        assert!(capped.ends_with("（已截断，原长 15000 字节）"));
+    }
+
+    #[test]
+    fn log_keeps_newest_first_and_drops_the_oldest() {
+        let mut log = NotificationLog::default();
+        for i in 0..MAX_NOTIFICATIONS + 5 {
+            log.push(ToastLevel::Info, &format!("第 {i} 条"));
+        }
+        assert_eq!(log.len(), MAX_NOTIFICATIONS);
+        assert_eq!(
+            log.get(0).map(|n| n.message.as_str()),
+            Some(format!("第 {} 条", MAX_NOTIFICATIONS + 4).as_str())
+        );
+        assert_eq!(
+            log.entries().last().map(|n| n.message.as_str()),
+            Some("第 5 条")
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! 主体三栏（左：歌单/分组，中：内容，右：信息）的宽度比例
+
+/// 终端宽度低于该值时隐藏右栏
+pub const NARROW_BODY_WIDTH: u16 = 120;
+
+/// 左栏比例可调范围（百分比）
+const LEFT_MIN: u16 = 10;
+const LEFT_MAX: u16 = 40;
+/// 中栏最少占比（百分比）
+const CENTER_MIN: u16 = 30;
+/// 右栏最少占比（百分比）
+const RIGHT_MIN: u16 = 15;
+
+/// 三栏宽度百分比；右栏占剩余部分
+///
+/// 默认值在 122 列（原固定布局宽度）下恰好得到 24 / 58 / 40 列。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneRatios {
+    pub left: u16,
+    pub center: u16,
+}
+
+impl Default for PaneRatios {
+    fn default() -> Self {
+        Self {
+            left: 20,
+            center: 47,
+        }
+    }
+}
+
+impl PaneRatios {
+    pub fn right(self) -> u16 {
+        100u16.saturating_sub(self.left + self.center)
+    }
+
+    /// 从设置中的 `[左, 中, 右]` 还原；不合法（总和不为 100 或超出范围）时回退默认值
+    pub fn from_percentages(p: [u16; 3]) -> Self {
+        let ratios = Self {
+            left: p[0],
+            center: p[1],
+        };
+        if p.iter().map(|&v| u32::from(v)).sum::<u32>() == 100 && ratios.is_valid() {
+            ratios
+        } else {
+            Self::default()
+        }
+    }
+
+    pub fn to_percentages(self) -> [u16; 3] {
+        [self.left, self.center, self.right()]
+    }
+
+    /// 移动左/中栏分界线（正数向右）；右栏不变。到达边界时返回 false
+    pub fn nudge(&mut self, delta: i16) -> bool {
+        let left = (i32::from(self.left) + i32::from(delta))
+            .clamp(i32::from(LEFT_MIN), i32::from(LEFT_MAX)) as u16;
+        let next = Self {
+            left,
+            center: (self.left + self.center).saturating_sub(left),
+        };
+        if next == *self || !next.is_valid() {
+            return false;
+        }
+        *self = next;
+        true
+    }
+
+    fn is_valid(self) -> bool {
+        (LEFT_MIN..=LEFT_MAX).contains(&self.left)
+            && self.center >= CENTER_MIN
+            && self.left + self.center <= 100 - RIGHT_MIN
+    }
+}
+
+/// 终端宽度足够时才显示右栏
+pub fn right_pane_visible(width: u16) -> bool {
+    width >= NARROW_BODY_WIDTH
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nudge_moves_left_center_boundary_within_bounds() {
+        let mut r = PaneRatios::default();
+        assert!(r.nudge(2));
+        assert_eq!(r.to_percentages(), [22, 45, 33]);
+        assert!(r.nudge(-4));
+        assert_eq!(r.to_percentages(), [18, 49, 33]);
+
+        for _ in 0..10 {
+            r.nudge(-2);
+        }
+        assert_eq!(r.left, LEFT_MIN);
+        assert!(!r.nudge(-2), "左栏已到最小值");
+
+        let mut r = PaneRatios::default();
+        for _ in 0..20 {
+            r.nudge(2);
+        }
+        assert_eq!(r.to_percentages(), [36, 31, 33], "中栏不小于最小占比");
+        assert!(!r.nudge(2));
+    }
+
+    #[test]
+    fn invalid_percentages_fall_back_to_default() {
+        assert_eq!(
+            PaneRatios::from_percentages([25, 50, 25]).to_percentages(),
+            [25, 50, 25]
+        );
+        for bad in [[20, 47, 30], [5, 60, 35], [30, 20, 50], [30, 65, 5]] {
+            assert_eq!(PaneRatios::from_percentages(bad), PaneRatios::default());
+        }
+    }
+}
@@ -40,6 +40,7 @@ pub fn parse_search_songs(v: &Value) -> Vec<Song> {
                 name,
                 artists,
                 duration_ms,
+                ..Default::default()
             })
         })
         .collect()
@@ -56,11 +57,17 @@ pub fn parse_user_playlists(v: &Value) -> Vec<Playlist> {
             let name = p.get("name")?.as_str()?.to_owned();
             let track_count = p.get("trackCount").and_then(|x| x.as_i64()).unwrap_or(0);
             let special_type = p.get("specialType").and_then(|x| x.as_i64()).unwrap_or(0);
+            let subscribed = p
+                .get("subscribed")
+                .and_then(|x| x.as_bool())
+                .unwrap_or(false);
             Some(Playlist {
                 id,
                 name,
                 track_count,
                 special_type,
+                subscribed,
+                updated_at_ms: None,
             })
         })
         .collect()
@@ -0,0 +1,166 @@
+//! 拼音匹配：输入 ASCII 关键词时按全拼或首字母匹配中文歌名、歌手与歌单名
+//!
+//! 例如 `zjl`、`zhoujielun` 都能匹配「周杰伦」。文本中的 ASCII 字母与数字原样保留
+//! （「G.E.M.邓紫棋」的首字母为 `gemdzq`），标点与空白忽略；多音字取常用读音。
+
+use std::collections::HashMap;
+
+use pinyin::ToPinyin;
+
+use crate::domain::model::Song;
+
+/// 一段文本的拼音（全拼与首字母，均为小写且不含空白）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinyinKey {
+    full: String,
+    initials: String,
+}
+
+impl PinyinKey {
+    /// 文本中没有可转换的汉字时为 None（纯 ASCII 文本按原文匹配即可）
+    pub fn new(text: &str) -> Option<Self> {
+        let mut full = String::new();
+        let mut initials = String::new();
+        let mut has_hanzi = false;
+        for c in text.chars() {
+            if let Some(p) = c.to_pinyin() {
+                has_hanzi = true;
+                full.push_str(p.plain());
+                initials.push_str(p.first_letter());
+            } else if c.is_ascii_alphanumeric() {
+                let c = c.to_ascii_lowercase();
+                full.push(c);
+                initials.push(c);
+            }
+        }
+        has_hanzi.then_some(Self { full, initials })
+    }
+
+    /// `query` 须为 `pinyin_query` 的结果
+    fn matches(&self, query: &str) -> bool {
+        self.full.contains(query) || self.initials.contains(query)
+    }
+}
+
+/// 可用于拼音匹配的关键词：只含 ASCII 字母、数字与空白时返回去掉空白的小写形式
+pub fn pinyin_query(query: &str) -> Option<String> {
+    let compact: String = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    (!compact.is_empty() && compact.chars().all(|c| c.is_ascii_alphanumeric())).then_some(compact)
+}
+
+/// `text` 的拼音是否匹配关键词（不检查原文包含）
+pub fn matches(text: &str, query: &str) -> bool {
+    pinyin_query(query).is_some_and(|q| PinyinKey::new(text).is_some_and(|key| key.matches(&q)))
+}
+
+/// 排序用的键：汉字换成拼音，其余字符转为小写，使中英文混排的名称按字母顺序排列
+pub fn sort_key(text: &str) -> String {
+    let mut key = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c.to_pinyin() {
+            Some(p) => key.push_str(p.plain()),
+            None => key.extend(c.to_lowercase()),
+        }
+    }
+    key
+}
+
+/// 歌曲名、译名与歌手的拼音缓存（按歌曲 id），避免每次输入都重新转换整个歌单
+#[derive(Debug, Clone, Default)]
+pub struct SongPinyinCache {
+    keys: HashMap<i64, Vec<PinyinKey>>,
+}
+
+impl SongPinyinCache {
+    /// 为尚未缓存的歌曲计算拼音
+    pub fn fill(&mut self, songs: &[Song]) {
+        for song in songs {
+            self.keys
+                .entry(song.id)
+                .or_insert_with(|| song_keys(song).collect());
+        }
+    }
+
+    /// 歌曲的拼音是否匹配关键词；`query` 须为 `pinyin_query` 的结果，未缓存的歌曲即时计算
+    pub fn song_matches(&self, song: &Song, query: &str) -> bool {
+        match self.keys.get(&song.id) {
+            Some(keys) => keys.iter().any(|k| k.matches(query)),
+            None => song_keys(song).any(|k| k.matches(query)),
+        }
+    }
+}
+
+fn song_keys(song: &Song) -> impl Iterator<Item = PinyinKey> + '_ {
+    [
+        Some(song.name.as_str()),
+        song.translated.as_deref(),
+        Some(song.artists.as_str()),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(PinyinKey::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_full_pinyin_and_initials() {
+        assert!(matches("周杰伦", "zjl"));
+        assert!(matches("周杰伦", "ZhouJieLun"));
+        assert!(matches("周杰伦", "jie lun"));
+        assert!(matches("晴天", "qt"));
+        assert!(!matches("周杰伦", "zjk"));
+        assert!(!matches("Coldplay", "cold"), "纯 ASCII 文本不做拼音匹配");
+        assert!(!matches("周杰伦", "周"), "非 ASCII 关键词只按原文匹配");
+    }
+
+    #[test]
+    fn multi_character_surnames() {
+        assert!(matches("欧阳娜娜", "oynn"));
+        assert!(matches("欧阳娜娜", "ouyang"));
+        assert!(matches("司马懿", "smy"));
+        assert!(matches("诸葛亮", "zhugeliang"));
+        assert!(!matches("欧阳娜娜", "oyy"));
+    }
+
+    #[test]
+    fn mixed_ascii_and_cjk() {
+        assert!(matches("G.E.M.邓紫棋", "gemdzq"));
+        assert!(matches("G.E.M.邓紫棋", "dengziqi"));
+        assert!(matches("夜空中最亮的星（Live 版）", "live b"));
+        assert!(matches("2002年的第一场雪", "2002n"));
+        assert!(matches("2002年的第一场雪", "dycx"));
+        assert_eq!(pinyin_query("a-b"), None);
+        assert_eq!(pinyin_query("  "), None);
+    }
+
+    #[test]
+    fn sort_key_spells_out_hanzi() {
+        assert_eq!(sort_key("晴天"), "qingtian");
+        assert_eq!(sort_key("G.E.M.邓紫棋"), "g.e.m.dengziqi");
+        assert_eq!(sort_key("Fix You"), "fix you");
+    }
+
+    #[test]
+    fn cache_matches_name_translation_and_artists() {
+        let mut tracks = vec![
+            Song::titled(1, "晴天", "周杰伦"),
+            Song::titled(2, "Yellow", "Coldplay"),
+        ];
+        tracks[1].translated = Some("黄色".to_owned());
+        let mut cache = SongPinyinCache::default();
+        cache.fill(&tracks);
+        assert!(cache.song_matches(&tracks[0], "zjl"));
+        assert!(cache.song_matches(&tracks[0], "qingtian"));
+        assert!(cache.song_matches(&tracks[1], "hs"));
+        assert!(!cache.song_matches(&tracks[1], "zjl"));
+        // 未缓存的歌曲即时计算
+        assert!(cache.song_matches(&Song::titled(3, "十年", "陈奕迅"), "cyx"));
+    }
+}
@@ -1,15 +1,31 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+
 use rand::seq::SliceRandom;
 
 use crate::domain::model::Song;
 
 use super::PlayMode;
 
+/// 全局递增的修改序号：队列被整体替换（如恢复状态）后序号也不会与旧值重复
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
 #[derive(Debug, Clone)]
 pub struct PlayQueue {
     songs: Vec<Song>,
     order: Vec<usize>,
     cursor: Option<usize>,
     mode: PlayMode,
+    /// 按播放顺序排列的歌曲（快照共享用），songs/order 变化时失效
+    ordered: OnceLock<Arc<[Song]>>,
+    /// 用户为此队列禁用了淡入淡出（更换队列时重置）
+    no_fade: bool,
+    /// 队列来源（如 `playlist:<id>`），随 queue.json 持久化
+    origin: Option<String>,
+    /// 每首歌的来源（与 `songs` 一一对应），同一来源共享一个 `Arc`
+    entry_origins: Vec<Option<Arc<str>>>,
+    /// 歌曲、顺序、游标或模式每次变化都会更新，用于检测需要自动保存的修改
+    revision: u64,
 }
 
 impl PlayQueue {
@@ -19,16 +35,54 @@ impl PlayQueue {
             order: Vec::new(),
             cursor: None,
             mode,
+            ordered: OnceLock::new(),
+            no_fade: false,
+            origin: None,
+            entry_origins: Vec::new(),
+            revision: 0,
         }
     }
 
+    /// 切换播放模式，始终保持当前歌曲不变
+    ///
+    /// - 非随机 → 随机：当前歌曲排在新顺序的第一位（游标为 0），其余歌曲随机排在其后；
+    ///   没有当前歌曲时整体打乱，游标保持为空
+    /// - 随机 → 非随机：恢复原始顺序，游标移到当前歌曲在原始顺序中的位置，
+    ///   「下一首」从它的自然后继继续；没有当前歌曲时游标保持为空
+    /// - 非随机模式之间切换：顺序与游标都不变
+    /// - 空队列：只记录模式
     pub fn set_mode(&mut self, mode: PlayMode) {
         if self.mode == mode {
             return;
         }
+        let was_shuffle = self.mode == PlayMode::Shuffle;
         let current = self.current_index();
         self.mode = mode;
-        self.rebuild_order(current);
+        self.touch();
+        if self.songs.is_empty() {
+            return;
+        }
+
+        match (was_shuffle, mode == PlayMode::Shuffle) {
+            (false, true) => {
+                self.invalidate_ordered();
+                let mut rest: Vec<usize> = (0..self.songs.len())
+                    .filter(|&i| Some(i) != current)
+                    .collect();
+                rest.shuffle(&mut rand::thread_rng());
+                self.order.clear();
+                self.order.extend(current);
+                self.order.extend(rest);
+                self.cursor = current.map(|_| 0);
+            }
+            (true, false) => {
+                self.invalidate_ordered();
+                self.order.clear();
+                self.order.extend(0..self.songs.len());
+                self.cursor = current;
+            }
+            _ => {}
+        }
     }
 
     /// 设置播放队列的歌曲列表
@@ -36,14 +90,115 @@ impl PlayQueue {
     /// 返回旧的 songs 向量，允许调用方重用或丢弃
     pub fn set_songs(&mut self, songs: Vec<Song>, start_index: Option<usize>) -> Vec<Song> {
         let old = std::mem::replace(&mut self.songs, songs);
+        self.no_fade = false;
+        self.origin = None;
+        self.entry_origins = vec![None; self.songs.len()];
         self.rebuild_order(start_index);
         old
     }
 
+    /// 在队尾追加歌曲（歌单分批加载时使用），游标仍指向原来的歌曲
+    ///
+    /// 新歌曲的来源记为队列当前的来源。随机模式下新歌曲与尚未播放的部分一起重新打乱，
+    /// 已播放的部分保持不变。
+    pub fn extend(&mut self, songs: impl IntoIterator<Item = Song>) {
+        let origin = self.origin.clone();
+        self.extend_from(songs, origin.as_deref());
+    }
+
+    /// 追加来自 `origin` 的歌曲（队列视图按来源分组显示）
+    pub fn extend_from(&mut self, songs: impl IntoIterator<Item = Song>, origin: Option<&str>) {
+        let start = self.songs.len();
+        self.songs.extend(songs);
+        if self.songs.len() == start {
+            return;
+        }
+        let tag = origin.map(|o| self.shared_origin(o));
+        self.entry_origins.resize(self.songs.len(), tag);
+        self.invalidate_ordered();
+        self.order.extend(start..self.songs.len());
+        if matches!(self.mode, PlayMode::Shuffle) {
+            let unplayed = self.cursor.map_or(0, |pos| pos + 1);
+            self.order[unplayed..].shuffle(&mut rand::thread_rng());
+        }
+    }
+
+    /// 换回之前保存的队列（撤销队列替换），沿用当前的播放模式
+    pub fn restore_from(&mut self, mut saved: PlayQueue) {
+        saved.set_mode(self.mode);
+        saved.touch();
+        *self = saved;
+    }
+
     pub fn clear(&mut self) {
+        self.no_fade = false;
+        self.origin = None;
+        self.entry_origins.clear();
         self.songs.clear();
         self.order.clear();
         self.cursor = None;
+        self.invalidate_ordered();
+    }
+
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// 记录队列来源；需在 `set_songs` 之后调用（更换队列会清空来源）
+    ///
+    /// 尚无来源的歌曲同时记为该来源。
+    pub fn set_origin(&mut self, origin: impl Into<String>) {
+        let origin = origin.into();
+        let tag = self.shared_origin(&origin);
+        self.entry_origins.resize(self.songs.len(), None);
+        for entry in self.entry_origins.iter_mut().filter(|e| e.is_none()) {
+            *entry = Some(tag.clone());
+        }
+        self.origin = Some(origin);
+        self.touch();
+    }
+
+    /// 第 `index` 首歌（`songs` 下标）的来源
+    pub fn entry_origin(&self, index: usize) -> Option<&Arc<str>> {
+        self.entry_origins.get(index).and_then(Option::as_ref)
+    }
+
+    /// 恢复保存的每首歌来源（长度不符时忽略），应在 `restore` 之后、`set_origin` 之前调用
+    pub fn restore_entry_origins(&mut self, origins: &[Option<String>]) {
+        if origins.len() != self.songs.len() {
+            return;
+        }
+        self.entry_origins.clear();
+        for origin in origins {
+            let tag = origin.as_deref().map(|o| self.shared_origin(o));
+            self.entry_origins.push(tag);
+        }
+    }
+
+    /// 各歌曲来源是否都与队列来源相同（此时无需单独保存）
+    pub fn entry_origins_uniform(&self) -> bool {
+        self.entry_origins
+            .iter()
+            .all(|e| e.as_deref() == self.origin.as_deref())
+    }
+
+    /// 复用已有的同名来源，使同一来源的歌曲可以按指针比较
+    fn shared_origin(&self, origin: &str) -> Arc<str> {
+        self.entry_origins
+            .iter()
+            .rev()
+            .flatten()
+            .find(|o| &***o == origin)
+            .cloned()
+            .unwrap_or_else(|| Arc::from(origin))
+    }
+
+    pub fn mode(&self) -> PlayMode {
+        self.mode
+    }
+
+    pub fn revision(&self) -> u64 {
+        self.revision
     }
 
     pub fn is_empty(&self) -> bool {
@@ -58,11 +213,18 @@ impl PlayQueue {
         &self.order
     }
 
-    pub fn ordered_songs(&self) -> Vec<Song> {
-        self.order
-            .iter()
-            .filter_map(|&idx| self.songs.get(idx).cloned())
-            .collect()
+    /// 按播放顺序排列的歌曲
+    ///
+    /// 结果会被缓存并以 `Arc` 共享：只移动游标时多次调用不会重复克隆整个队列。
+    pub fn ordered_songs(&self) -> Arc<[Song]> {
+        self.ordered
+            .get_or_init(|| {
+                self.order
+                    .iter()
+                    .filter_map(|&idx| self.songs.get(idx).cloned())
+                    .collect()
+            })
+            .clone()
     }
 
     pub fn current_index(&self) -> Option<usize> {
@@ -83,18 +245,164 @@ impl PlayQueue {
         }
         if let Some(pos) = self.order.iter().position(|&i| i == index) {
             self.cursor = Some(pos);
+            self.touch();
             true
         } else {
             false
         }
     }
 
+    /// 查找正在播放歌曲所在的位置（跳转到正在播放时使用）
+    ///
+    /// 同一首歌可能在队列中出现多次，此时优先返回游标所在的位置，
+    /// 只有游标不指向该歌曲时才退回到第一次出现的位置。
+    pub fn index_of_playing(&self, song_id: i64) -> Option<usize> {
+        if let Some(idx) = self.current_index()
+            && self.songs.get(idx).is_some_and(|s| s.id == song_id)
+        {
+            return Some(idx);
+        }
+        self.songs.iter().position(|s| s.id == song_id)
+    }
+
+    /// 按位置移除歌曲，保持游标指向原来的歌曲
+    ///
+    /// 如果移除的就是当前歌曲，游标停留在同一播放位置（即原来的下一首）。
+    pub fn remove(&mut self, index: usize) -> Option<Song> {
+        if index >= self.songs.len() {
+            return None;
+        }
+        self.invalidate_ordered();
+        let removed = self.songs.remove(index);
+        if index < self.entry_origins.len() {
+            self.entry_origins.remove(index);
+        }
+        let removed_pos = self.order.iter().position(|&i| i == index);
+        if let Some(pos) = removed_pos {
+            self.order.remove(pos);
+        }
+        for i in &mut self.order {
+            if *i > index {
+                *i -= 1;
+            }
+        }
+
+        self.cursor = match (self.cursor, removed_pos) {
+            (Some(cur), Some(pos)) if pos < cur => Some(cur - 1),
+            (Some(cur), _) if cur >= self.order.len() => None,
+            (cursor, _) => cursor,
+        };
+        Some(removed)
+    }
+
+    /// 把歌曲插到当前歌曲之后（没有当前歌曲时插到最前），返回它在播放顺序中的位置
+    ///
+    /// 随机模式下歌曲追加到原始顺序末尾，只在随机顺序中排到当前歌曲之后；
+    /// 其他模式直接插入原始顺序。游标仍指向当前歌曲。
+    pub fn insert_after_cursor(&mut self, song: Song, origin: Option<&str>) -> usize {
+        let pos = self.cursor.map_or(0, |cur| cur + 1);
+        let tag = self.entry_tag(origin);
+        self.invalidate_ordered();
+        if self.mode == PlayMode::Shuffle {
+            self.songs.push(song);
+            self.entry_origins.push(tag);
+            self.order.insert(pos, self.songs.len() - 1);
+        } else {
+            let idx = self.order.get(pos).copied().unwrap_or(self.songs.len());
+            self.songs.insert(idx, song);
+            self.entry_origins.insert(idx, tag);
+            for i in &mut self.order {
+                if *i >= idx {
+                    *i += 1;
+                }
+            }
+            self.order.insert(pos, idx);
+        }
+        pos
+    }
+
+    /// 把歌曲追加到播放顺序末尾（随机模式也不重新打乱），返回它在播放顺序中的位置
+    pub fn push_back(&mut self, song: Song, origin: Option<&str>) -> usize {
+        let tag = self.entry_tag(origin);
+        self.invalidate_ordered();
+        self.songs.push(song);
+        self.entry_origins.push(tag);
+        self.order.push(self.songs.len() - 1);
+        self.order.len() - 1
+    }
+
+    /// 单首插入歌曲的来源；之前未记录逐首来源时先补齐为队列来源
+    fn entry_tag(&mut self, origin: Option<&str>) -> Option<Arc<str>> {
+        let fill = self.origin.clone().map(|o| self.shared_origin(&o));
+        self.entry_origins.resize(self.songs.len(), fill);
+        origin.map(|o| self.shared_origin(o))
+    }
+
+    /// 把播放顺序中 `pos` 处的歌曲与相邻一首交换（`up` 为向前），返回它的新位置
+    ///
+    /// 随机模式只交换随机顺序，歌曲原始顺序不变；其他模式顺序与原始顺序一致，
+    /// 直接交换歌曲（连同来源）。游标始终跟随当前歌曲。
+    pub fn move_adjacent(&mut self, pos: usize, up: bool) -> Option<usize> {
+        let other = if up { pos.checked_sub(1)? } else { pos + 1 };
+        if pos >= self.order.len() || other >= self.order.len() {
+            return None;
+        }
+        self.invalidate_ordered();
+        if self.mode == PlayMode::Shuffle {
+            self.order.swap(pos, other);
+        } else {
+            let (a, b) = (self.order[pos], self.order[other]);
+            self.songs.swap(a, b);
+            if a < self.entry_origins.len() && b < self.entry_origins.len() {
+                self.entry_origins.swap(a, b);
+            }
+        }
+        self.cursor = match self.cursor {
+            Some(cur) if cur == pos => Some(other),
+            Some(cur) if cur == other => Some(pos),
+            cursor => cursor,
+        };
+        Some(other)
+    }
+
+    pub fn no_fade(&self) -> bool {
+        self.no_fade
+    }
+
+    /// 切换「此队列禁用淡入淡出」，返回切换后的状态
+    pub fn toggle_no_fade(&mut self) -> bool {
+        self.no_fade = !self.no_fade;
+        self.no_fade
+    }
+
+    /// 当前歌曲与播放顺序中的上一首来自同一专辑（连续专辑/现场录音，不应淡入淡出）
+    ///
+    /// 随机模式下相邻曲目只是碰巧同专辑，不算连续。
+    pub fn continues_album(&self) -> bool {
+        if self.mode == PlayMode::Shuffle {
+            return false;
+        }
+        let Some(pos) = self.cursor.filter(|&pos| pos > 0) else {
+            return false;
+        };
+        let album_at = |pos: usize| {
+            self.order
+                .get(pos)
+                .and_then(|&idx| self.songs.get(idx))
+                .and_then(|s| s.album_id)
+        };
+        matches!((album_at(pos - 1), album_at(pos)), (Some(a), Some(b)) if a == b)
+    }
+
     pub fn clear_cursor(&mut self) {
         self.cursor = None;
+        self.touch();
     }
 
     pub fn restore(&mut self, songs: Vec<Song>, order: Vec<usize>, cursor: Option<usize>) -> bool {
+        self.invalidate_ordered();
         self.songs = songs;
+        self.entry_origins = vec![None; self.songs.len()];
         let len = self.songs.len();
         if len == 0 {
             self.order.clear();
@@ -138,8 +446,17 @@ impl PlayQueue {
         }
     }
 
+    /// 「下一首」将要播放的歌曲，不移动游标
+    ///
+    /// 与 [`Self::next_index`] 的结果一致：单曲循环为当前歌曲，随机模式按已打乱的顺序，
+    /// 顺序播放到末尾或没有当前歌曲时为 None。
+    pub fn peek_next(&self) -> Option<&Song> {
+        self.peek_next_index().and_then(|idx| self.songs.get(idx))
+    }
+
     pub fn next_index(&mut self) -> Option<usize> {
         let pos = self.cursor?;
+        self.touch();
         let len = self.order.len();
         if len == 0 {
             return None;
@@ -171,6 +488,7 @@ impl PlayQueue {
 
     pub fn prev_index(&mut self) -> Option<usize> {
         let pos = self.cursor?;
+        self.touch();
         let len = self.order.len();
         if len == 0 {
             return None;
@@ -191,6 +509,7 @@ impl PlayQueue {
     }
 
     fn rebuild_order(&mut self, start_index: Option<usize>) {
+        self.invalidate_ordered();
         let len = self.songs.len();
         self.order.clear();
         if len == 0 {
@@ -206,6 +525,15 @@ impl PlayQueue {
         self.cursor = Some(pos);
     }
 
+    fn invalidate_ordered(&mut self) {
+        self.ordered.take();
+        self.touch();
+    }
+
+    fn touch(&mut self) {
+        self.revision = NEXT_REVISION.fetch_add(1, Ordering::Relaxed);
+    }
+
     fn is_valid_order(order: &[usize], len: usize) -> bool {
         if order.len() != len {
             return false;
@@ -221,3 +549,508 @@ impl PlayQueue {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 40 首歌，id=7 同时出现在位置 2 与 30
+    fn songs_with_duplicate() -> Vec<Song> {
+        (0..40)
+            .map(|i| Song::fixture(if i == 30 { 7 } else { i as i64 + 5 }))
+            .collect()
+    }
+
+    #[test]
+    fn duplicate_song_keeps_cursor_on_selected_position() {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs(songs_with_duplicate(), Some(30));
+        assert_eq!(q.songs()[2].id, 7);
+        assert_eq!(q.current_index(), Some(30));
+
+        assert!(q.set_current_index(30));
+        assert_eq!(q.current_index(), Some(30));
+    }
+
+    #[test]
+    fn duplicate_song_next_prev_move_from_cursor_position() {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs(songs_with_duplicate(), Some(30));
+
+        assert_eq!(q.next_index(), Some(31));
+        assert_eq!(q.prev_index(), Some(30));
+        assert_eq!(q.prev_index(), Some(29));
+
+        q.set_current_index(2);
+        assert_eq!(q.next_index(), Some(3));
+        assert_eq!(q.prev_index(), Some(2));
+    }
+
+    #[test]
+    fn duplicate_song_jump_prefers_cursor_index() {
+        let mut q = PlayQueue::new(PlayMode::ListLoop);
+        q.set_songs(songs_with_duplicate(), Some(30));
+        assert_eq!(q.index_of_playing(7), Some(30));
+
+        q.set_current_index(2);
+        assert_eq!(q.index_of_playing(7), Some(2));
+
+        // 游标不在该歌曲上时退回第一次出现的位置
+        q.set_current_index(10);
+        assert_eq!(q.index_of_playing(7), Some(2));
+    }
+
+    #[test]
+    fn duplicate_song_restore_uses_saved_positions() {
+        let mut q = PlayQueue::new(PlayMode::Shuffle);
+        let order: Vec<usize> = (0..40).rev().collect();
+        let cursor = order.iter().position(|&i| i == 30);
+        assert!(q.restore(songs_with_duplicate(), order, cursor));
+
+        assert_eq!(q.current_index(), Some(30));
+        assert_eq!(q.index_of_playing(7), Some(30));
+    }
+
+    #[test]
+    fn removing_one_duplicate_keeps_cursor_on_other() {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs(songs_with_duplicate(), Some(30));
+
+        let removed = q.remove(2).expect("removed");
+        assert_eq!(removed.id, 7);
+        assert_eq!(q.songs().len(), 39);
+        assert_eq!(q.current_index(), Some(29));
+        assert_eq!(q.current().map(|s| s.id), Some(7));
+        assert_eq!(q.next_index(), Some(30));
+    }
+
+    #[test]
+    fn removing_current_duplicate_advances_to_next_position() {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs(songs_with_duplicate(), Some(30));
+        let next_id = q.songs()[31].id;
+
+        q.remove(30);
+        assert_eq!(q.current_index(), Some(30));
+        assert_eq!(q.current().map(|s| s.id), Some(next_id));
+        assert_eq!(q.index_of_playing(7), Some(2));
+    }
+
+    #[test]
+    fn insert_after_cursor_plays_next_in_every_mode() {
+        for mode in [PlayMode::Sequential, PlayMode::ListLoop, PlayMode::Shuffle] {
+            let mut q = PlayQueue::new(mode);
+            q.set_songs((1..=5).map(Song::fixture).collect(), Some(2));
+            let current = q.current().map(|s| s.id);
+            let cursor = q.cursor_pos().expect("cursor");
+
+            // 允许重复：插入一首已在队列中的歌曲
+            let pos = q.insert_after_cursor(Song::fixture(1), Some("search"));
+            assert_eq!(pos, cursor + 1, "{mode:?}");
+            assert_eq!(q.current().map(|s| s.id), current, "{mode:?}");
+            assert_eq!(q.peek_next().map(|s| s.id), Some(1), "{mode:?}");
+            assert_eq!(q.songs().len(), 6);
+            assert_permutation(&q);
+            let idx = q.order()[pos];
+            assert_eq!(q.entry_origin(idx).map(|o| &**o), Some("search"));
+        }
+
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs((1..=3).map(Song::fixture).collect(), Some(1));
+        q.insert_after_cursor(Song::fixture(9), None);
+        assert_eq!(ordered_ids(&q), vec![1, 2, 9, 3], "非随机模式插入原始顺序");
+        assert_eq!(
+            q.songs().iter().map(|s| s.id).collect::<Vec<_>>(),
+            vec![1, 2, 9, 3]
+        );
+
+        let mut empty = PlayQueue::new(PlayMode::Sequential);
+        assert_eq!(empty.insert_after_cursor(Song::fixture(1), None), 0);
+        assert_eq!(empty.cursor_pos(), None);
+        assert_eq!(ordered_ids(&empty), vec![1]);
+    }
+
+    #[test]
+    fn push_back_appends_to_play_order_without_reshuffling() {
+        let mut q = PlayQueue::new(PlayMode::Shuffle);
+        q.set_songs((1..=6).map(Song::fixture).collect(), Some(0));
+        q.set_origin("playlist:7");
+        let before = ordered_ids(&q);
+        let current = q.current().map(|s| s.id);
+
+        assert_eq!(q.push_back(Song::fixture(9), None), 6);
+        let mut expected = before;
+        expected.push(9);
+        assert_eq!(ordered_ids(&q), expected);
+        assert_eq!(q.current().map(|s| s.id), current);
+        assert_permutation(&q);
+        assert_eq!(q.entry_origin(6), None);
+        assert_eq!(q.entry_origin(0).map(|o| &**o), Some("playlist:7"));
+    }
+
+    #[test]
+    fn move_adjacent_keeps_cursor_on_current_song() {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs((1..=4).map(Song::fixture).collect(), Some(1));
+
+        assert_eq!(q.move_adjacent(1, false), Some(2));
+        assert_eq!(ordered_ids(&q), vec![1, 3, 2, 4]);
+        assert_eq!(q.current().map(|s| s.id), Some(2));
+        assert_eq!(q.cursor_pos(), Some(2));
+        assert_eq!(q.peek_next().map(|s| s.id), Some(4));
+
+        // 把当前歌曲之前的一首移到它后面：游标前移一位
+        assert_eq!(q.move_adjacent(1, false), Some(2));
+        assert_eq!(ordered_ids(&q), vec![1, 2, 3, 4]);
+        assert_eq!(q.cursor_pos(), Some(1));
+
+        assert_eq!(q.move_adjacent(0, true), None);
+        assert_eq!(q.move_adjacent(3, false), None);
+    }
+
+    #[test]
+    fn move_adjacent_in_shuffle_only_swaps_play_order() {
+        let mut q = PlayQueue::new(PlayMode::Shuffle);
+        q.set_songs((1..=6).map(Song::fixture).collect(), Some(0));
+        let before = ordered_ids(&q);
+        let current = q.current().map(|s| s.id);
+        let pos = q.cursor_pos().expect("cursor");
+        let target = if pos == 0 { 1 } else { pos - 1 };
+
+        q.move_adjacent(pos, pos != 0);
+        let mut expected = before.clone();
+        expected.swap(pos, target);
+        assert_eq!(ordered_ids(&q), expected);
+        assert_eq!(q.current().map(|s| s.id), current);
+        assert_eq!(q.cursor_pos(), Some(target));
+        assert_eq!(
+            q.songs().iter().map(|s| s.id).collect::<Vec<_>>(),
+            vec![1, 2, 3, 4, 5, 6],
+            "随机模式不改变原始顺序"
+        );
+        assert_permutation(&q);
+    }
+
+    fn ordered_ids(q: &PlayQueue) -> Vec<i64> {
+        q.ordered_songs().iter().map(|s| s.id).collect()
+    }
+
+    #[test]
+    fn ordered_songs_shared_until_queue_changes() {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs((1..=5).map(Song::fixture).collect(), Some(0));
+        let first = q.ordered_songs();
+        q.next_index();
+        assert!(
+            Arc::ptr_eq(&first, &q.ordered_songs()),
+            "仅移动游标应复用缓存"
+        );
+
+        q.remove(0);
+        assert_eq!(ordered_ids(&q), vec![2, 3, 4, 5]);
+        assert!(!Arc::ptr_eq(&first, &q.ordered_songs()));
+
+        q.set_mode(PlayMode::Shuffle);
+        let expected: Vec<i64> = q.order().iter().map(|&i| q.songs()[i].id).collect();
+        assert_eq!(ordered_ids(&q), expected);
+
+        assert!(q.restore(
+            vec![Song::fixture(8), Song::fixture(9)],
+            vec![1, 0],
+            Some(0)
+        ));
+        assert_eq!(ordered_ids(&q), vec![9, 8]);
+
+        q.set_songs(vec![Song::fixture(6)], Some(0));
+        assert_eq!(ordered_ids(&q), vec![6]);
+
+        q.clear();
+        assert!(q.ordered_songs().is_empty());
+    }
+
+    #[test]
+    fn continues_album_requires_adjacent_tracks_from_same_album() {
+        let with_album = |id: i64, album_id: Option<i64>| Song {
+            album_id,
+            ..Song::fixture(id)
+        };
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs(
+            vec![
+                with_album(1, Some(10)),
+                with_album(2, Some(10)),
+                with_album(3, Some(20)),
+                with_album(4, None),
+                with_album(5, None),
+            ],
+            Some(0),
+        );
+        assert!(!q.continues_album(), "第一首没有上一首");
+        q.next_index();
+        assert!(q.continues_album());
+        q.next_index();
+        assert!(!q.continues_album(), "换专辑");
+        q.next_index();
+        q.next_index();
+        assert!(!q.continues_album(), "没有专辑 id 不算同专辑");
+
+        q.set_mode(PlayMode::Shuffle);
+        q.set_songs(
+            vec![with_album(1, Some(10)), with_album(2, Some(10))],
+            Some(0),
+        );
+        q.next_index();
+        assert!(!q.continues_album(), "随机模式不算连续");
+    }
+
+    #[test]
+    fn no_fade_override_resets_with_new_queue() {
+        let mut q = PlayQueue::new(PlayMode::ListLoop);
+        q.set_songs(vec![Song::fixture(1), Song::fixture(2)], Some(0));
+        assert!(q.toggle_no_fade());
+        q.next_index();
+        assert!(q.no_fade(), "切歌不影响队列设置");
+        q.set_songs(vec![Song::fixture(3)], Some(0));
+        assert!(!q.no_fade());
+        assert!(q.toggle_no_fade());
+        q.clear();
+        assert!(!q.no_fade());
+    }
+
+    #[test]
+    fn revision_tracks_mutations_only() {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs(
+            vec![Song::fixture(1), Song::fixture(2), Song::fixture(3)],
+            Some(0),
+        );
+        q.set_origin("playlist:7");
+        let rev = q.revision();
+        let _ = q.ordered_songs();
+        let _ = q.peek_next_index();
+        assert_eq!(q.revision(), rev, "只读不改变序号");
+
+        q.next_index();
+        let after_next = q.revision();
+        assert_ne!(after_next, rev);
+        q.set_mode(PlayMode::ListLoop);
+        assert_ne!(q.revision(), after_next);
+
+        let cloned = q.clone();
+        let mut fresh = PlayQueue::new(PlayMode::Sequential);
+        fresh.restore(vec![Song::fixture(1)], vec![0], Some(0));
+        assert_ne!(fresh.revision(), cloned.revision(), "替换队列后序号不重复");
+
+        assert_eq!(q.origin(), Some("playlist:7"));
+        q.set_songs(vec![Song::fixture(4)], Some(0));
+        assert_eq!(q.origin(), None, "更换队列清空来源");
+    }
+
+    #[test]
+    fn extend_keeps_cursor_on_current_song() {
+        let mut q = PlayQueue::new(PlayMode::ListLoop);
+        q.set_songs((1..=3).map(Song::fixture).collect(), Some(1));
+        q.set_origin("playlist:1");
+        q.extend((4..=5).map(Song::fixture));
+
+        assert_eq!(q.current().map(|s| s.id), Some(2));
+        assert_eq!(ordered_ids(&q), [1, 2, 3, 4, 5]);
+        assert_eq!(q.origin(), Some("playlist:1"));
+        assert_eq!(q.next_index().map(|i| q.songs()[i].id), Some(3));
+    }
+
+    #[test]
+    fn extend_in_shuffle_only_reorders_unplayed_tail() {
+        let mut q = PlayQueue::new(PlayMode::Shuffle);
+        q.set_songs((1..=4).map(Song::fixture).collect(), Some(2));
+        let before = q.order()[..=q.cursor_pos().unwrap()].to_vec();
+        q.extend((5..=20).map(Song::fixture));
+
+        assert_eq!(q.current().map(|s| s.id), Some(3));
+        let pos = q.cursor_pos().unwrap();
+        assert_eq!(q.order()[..=pos], before[..]);
+        let mut all = q.order().to_vec();
+        all.sort_unstable();
+        assert_eq!(all, (0..20).collect::<Vec<_>>());
+    }
+
+    const ALL_MODES: [PlayMode; 4] = [
+        PlayMode::Sequential,
+        PlayMode::ListLoop,
+        PlayMode::SingleLoop,
+        PlayMode::Shuffle,
+    ];
+
+    fn assert_permutation(q: &PlayQueue) {
+        let mut order = q.order().to_vec();
+        order.sort_unstable();
+        assert_eq!(order, (0..q.songs().len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn set_mode_keeps_current_song_across_all_transitions() {
+        for from in ALL_MODES {
+            for to in ALL_MODES.into_iter().filter(|&m| m != from) {
+                let mut q = PlayQueue::new(from);
+                q.set_songs((1..=10).map(Song::fixture).collect(), Some(6));
+                let order_before = q.order().to_vec();
+                let cursor_before = q.cursor_pos();
+                q.set_mode(to);
+
+                let case = format!("{from:?} -> {to:?}");
+                assert_eq!(q.current().map(|s| s.id), Some(7), "{case}");
+                assert_permutation(&q);
+                match (from, to) {
+                    (_, PlayMode::Shuffle) => {
+                        assert_eq!(q.cursor_pos(), Some(0), "{case}: 当前歌曲排在第一位");
+                    }
+                    (PlayMode::Shuffle, _) => {
+                        assert_eq!(q.order(), (0..10).collect::<Vec<_>>(), "{case}");
+                        assert_eq!(q.cursor_pos(), Some(6), "{case}");
+                    }
+                    _ => {
+                        assert_eq!(q.order(), order_before, "{case}: 顺序不变");
+                        assert_eq!(q.cursor_pos(), cursor_before, "{case}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set_mode_without_current_song_keeps_cursor_empty() {
+        for from in ALL_MODES {
+            for to in ALL_MODES.into_iter().filter(|&m| m != from) {
+                let mut q = PlayQueue::new(from);
+                q.set_songs((1..=10).map(Song::fixture).collect(), Some(0));
+                q.clear_cursor();
+                q.set_mode(to);
+
+                let case = format!("{from:?} -> {to:?}");
+                assert_eq!(q.cursor_pos(), None, "{case}");
+                assert_permutation(&q);
+                if to != PlayMode::Shuffle {
+                    assert_eq!(q.order(), (0..10).collect::<Vec<_>>(), "{case}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn set_mode_on_empty_queue_only_records_mode() {
+        for from in ALL_MODES {
+            for to in ALL_MODES.into_iter().filter(|&m| m != from) {
+                let mut q = PlayQueue::new(from);
+                q.set_mode(to);
+                assert!(q.is_empty());
+                assert!(q.order().is_empty());
+                assert_eq!(q.cursor_pos(), None);
+                assert_eq!(q.next_index(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn shuffle_round_trip_continues_from_natural_successor() {
+        let mut q = PlayQueue::new(PlayMode::ListLoop);
+        q.set_songs((1..=10).map(Song::fixture).collect(), Some(3));
+        q.set_mode(PlayMode::Shuffle);
+        assert_eq!(q.current().map(|s| s.id), Some(4));
+
+        // 随机播放两首后关闭随机
+        q.next_index();
+        q.next_index();
+        let playing = q.current().map(|s| s.id).unwrap();
+        q.set_mode(PlayMode::ListLoop);
+
+        assert_eq!(q.current().map(|s| s.id), Some(playing));
+        assert_eq!(ordered_ids(&q), (1..=10).collect::<Vec<_>>());
+        let next = q.next_index().map(|i| q.songs()[i].id);
+        assert_eq!(next, Some(playing % 10 + 1));
+    }
+
+    /// 各模式下 `peek_next` 都应与在克隆队列上实际执行 `next_index` 的结果一致，且不修改队列
+    fn assert_peek_matches_next(q: &PlayQueue, case: &str) {
+        let rev = q.revision();
+        let cursor = q.cursor_pos();
+        let peeked = q.peek_next_index();
+
+        let mut advanced = q.clone();
+        let played = advanced.next_index();
+        assert_eq!(peeked, played, "{case}");
+        assert_eq!(
+            q.peek_next().map(|s| s.id),
+            played.map(|idx| advanced.songs()[idx].id),
+            "{case}"
+        );
+        assert_eq!(q.cursor_pos(), cursor, "{case}: peek 不移动游标");
+        assert_eq!(q.revision(), rev, "{case}: peek 不改变序号");
+    }
+
+    #[test]
+    fn peek_next_agrees_with_next_in_every_mode_and_position() {
+        for mode in ALL_MODES {
+            for len in 0..=5 {
+                let mut q = PlayQueue::new(mode);
+                q.set_songs((1..=len).map(Song::fixture).collect(), Some(0));
+                for pos in 0..len as usize {
+                    q.set_current_index(q.order()[pos]);
+                    assert_peek_matches_next(&q, &format!("{mode:?} len={len} pos={pos}"));
+                }
+                q.clear_cursor();
+                assert_peek_matches_next(&q, &format!("{mode:?} len={len} 无当前歌曲"));
+            }
+        }
+    }
+
+    #[test]
+    fn peek_next_follows_queue_through_a_full_run() {
+        for mode in ALL_MODES {
+            let mut q = PlayQueue::new(mode);
+            q.set_songs((1..=6).map(Song::fixture).collect(), Some(2));
+            // 播放过程中切换模式、追加与移除歌曲，每一步都校验
+            for step in 0..20 {
+                let case = format!("{mode:?} step={step}");
+                assert_peek_matches_next(&q, &case);
+                match step {
+                    5 => q.extend((7..=9).map(Song::fixture)),
+                    9 => q.set_mode(PlayMode::Shuffle),
+                    13 => {
+                        q.remove(0);
+                    }
+                    16 => q.set_mode(mode),
+                    _ => {}
+                }
+                assert_peek_matches_next(&q, &case);
+                if q.next_index().is_none() {
+                    q.set_current_index(0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn peek_next_edge_cases() {
+        let ids = |q: &PlayQueue| q.peek_next().map(|s| s.id);
+
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs((1..=3).map(Song::fixture).collect(), Some(2));
+        assert_eq!(ids(&q), None, "顺序播放到末尾");
+
+        q.set_mode(PlayMode::ListLoop);
+        assert_eq!(ids(&q), Some(1), "列表循环回到开头");
+
+        q.set_mode(PlayMode::SingleLoop);
+        assert_eq!(ids(&q), Some(3), "单曲循环为当前歌曲");
+
+        q.set_mode(PlayMode::Shuffle);
+        let second = q.songs()[q.order()[1]].id;
+        assert_eq!(ids(&q), Some(second), "随机模式按打乱后的顺序");
+
+        let mut single = PlayQueue::new(PlayMode::ListLoop);
+        single.set_songs(vec![Song::fixture(9)], Some(0));
+        assert_eq!(ids(&single), Some(9));
+
+        assert_eq!(ids(&PlayQueue::new(PlayMode::ListLoop)), None);
+    }
+}
@@ -0,0 +1,129 @@
+//! 歌单列表的归属筛选（全部 / 我创建的 / 收藏的）
+//!
+//! `App::playlists` 始终保存完整列表，`playlists_selected` 是完整列表中的下标；
+//! 筛选只影响展示与上下移动，快照中的歌单列表与选中位置按筛选结果换算。
+
+use crate::domain::model::Playlist;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlaylistFilter {
+    #[default]
+    All,
+    Mine,
+    Subscribed,
+}
+
+impl PlaylistFilter {
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::Mine,
+            Self::Mine => Self::Subscribed,
+            Self::Subscribed => Self::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::All => "全部",
+            Self::Mine => "我的",
+            Self::Subscribed => "收藏",
+        }
+    }
+
+    pub fn matches(self, p: &Playlist) -> bool {
+        match self {
+            Self::All => true,
+            Self::Mine => !p.subscribed,
+            Self::Subscribed => p.subscribed,
+        }
+    }
+}
+
+/// 筛选后可见歌单在完整列表中的下标
+pub fn visible_indices(playlists: &[Playlist], filter: PlaylistFilter) -> Vec<usize> {
+    playlists
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| filter.matches(p))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// 切换筛选后的选中项（完整列表下标）
+///
+/// 原选中歌单仍可见时按 id 保持选中，否则选中第一个可见歌单；没有可见歌单时保持不变。
+pub fn remap_selection(playlists: &[Playlist], selected: usize, filter: PlaylistFilter) -> usize {
+    let selected_id = playlists.get(selected).map(|p| p.id);
+    let visible = visible_indices(playlists, filter);
+    visible
+        .iter()
+        .copied()
+        .find(|&i| Some(playlists[i].id) == selected_id)
+        .or_else(|| visible.first().copied())
+        .unwrap_or(selected)
+}
+
+/// `(我创建的, 收藏的)` 数量
+pub fn ownership_counts(playlists: &[Playlist]) -> (usize, usize) {
+    let subscribed = playlists.iter().filter(|p| p.subscribed).count();
+    (playlists.len() - subscribed, subscribed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playlist(id: i64, subscribed: bool) -> Playlist {
+        Playlist {
+            id,
+            name: format!("p{id}"),
+            subscribed,
+            ..Default::default()
+        }
+    }
+
+    fn sample() -> Vec<Playlist> {
+        vec![
+            playlist(10, false),
+            playlist(11, true),
+            playlist(12, false),
+            playlist(13, true),
+        ]
+    }
+
+    #[test]
+    fn filter_cycles_and_selects_by_ownership() {
+        let list = sample();
+        assert_eq!(
+            PlaylistFilter::All.next().next().next(),
+            PlaylistFilter::All
+        );
+        assert_eq!(
+            visible_indices(&list, PlaylistFilter::All),
+            vec![0, 1, 2, 3]
+        );
+        assert_eq!(visible_indices(&list, PlaylistFilter::Mine), vec![0, 2]);
+        assert_eq!(
+            visible_indices(&list, PlaylistFilter::Subscribed),
+            vec![1, 3]
+        );
+        assert_eq!(ownership_counts(&list), (2, 2));
+    }
+
+    #[test]
+    fn selection_remaps_by_id_across_filter_changes() {
+        let list = sample();
+        // 选中的歌单在新筛选下仍可见：保持
+        assert_eq!(remap_selection(&list, 2, PlaylistFilter::Mine), 2);
+        assert_eq!(remap_selection(&list, 3, PlaylistFilter::Subscribed), 3);
+        // 被隐藏：回到第一个可见歌单
+        assert_eq!(remap_selection(&list, 2, PlaylistFilter::Subscribed), 1);
+        assert_eq!(remap_selection(&list, 1, PlaylistFilter::Mine), 0);
+        // 没有可见歌单时保持原样
+        let mine_only = vec![playlist(1, false)];
+        assert_eq!(
+            remap_selection(&mine_only, 0, PlaylistFilter::Subscribed),
+            0
+        );
+    }
+}
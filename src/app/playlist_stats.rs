@@ -0,0 +1,125 @@
+//! 歌单统计（总时长、歌手数、歌曲最多的歌手）
+//!
+//! 在歌单歌曲加载完成时计算一次：打开歌单时存入 `App::playlist_tracks_stats`，
+//! 预加载完成时随 `PlaylistPreload` 缓存；歌单刷新或被编辑时清空，下次打开重新计算。
+
+use crate::domain::model::Song;
+use std::collections::HashMap;
+
+/// 统计中展示的歌手数
+pub const TOP_ARTISTS: usize = 3;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PlaylistStats {
+    /// 已知时长之和
+    pub total_ms: u64,
+    /// 缺少时长的歌曲数（不计入 `total_ms`）
+    pub unknown_duration: usize,
+    /// 不同歌手数
+    pub artist_count: usize,
+    /// 歌曲数最多的歌手（降序；数量相同时按在歌单中首次出现的顺序）
+    pub top_artists: Vec<(String, usize)>,
+}
+
+impl PlaylistStats {
+    pub fn compute(songs: &[Song]) -> Self {
+        let mut total_ms = 0u64;
+        let mut unknown_duration = 0usize;
+        // 歌手名 -> (首次出现顺序, 歌曲数)
+        let mut artists: HashMap<&str, (usize, usize)> = HashMap::new();
+
+        for song in songs {
+            match song.duration_ms {
+                Some(ms) => total_ms = total_ms.saturating_add(ms),
+                None => unknown_duration += 1,
+            }
+            let mut seen = Vec::new();
+            for name in song.artists.split('/').map(str::trim) {
+                // 同一首歌重复列出的歌手只计一次
+                if name.is_empty() || seen.contains(&name) {
+                    continue;
+                }
+                seen.push(name);
+                let order = artists.len();
+                artists.entry(name).or_insert((order, 0)).1 += 1;
+            }
+        }
+
+        let mut ranked: Vec<(&str, usize, usize)> = artists
+            .into_iter()
+            .map(|(name, (order, count))| (name, order, count))
+            .collect();
+        ranked.sort_by(|a, b| b.2.cmp(&a.2).then(a.1.cmp(&b.1)));
+
+        Self {
+            total_ms,
+            unknown_duration,
+            artist_count: ranked.len(),
+            top_artists: ranked
+                .into_iter()
+                .take(TOP_ARTISTS)
+                .map(|(name, _, count)| (name.to_owned(), count))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top(stats: &PlaylistStats) -> Vec<(&str, usize)> {
+        stats
+            .top_artists
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn counts_duration_and_artists() {
+        let stats = PlaylistStats::compute(&[
+            Song::titled(0, "", "A/B").lasting_ms(60_000),
+            Song::titled(0, "", "A").lasting_ms(120_000),
+            Song::titled(0, "", "C").lasting_ms(30_000),
+        ]);
+        assert_eq!(stats.total_ms, 210_000);
+        assert_eq!(stats.unknown_duration, 0);
+        assert_eq!(stats.artist_count, 3);
+        assert_eq!(top(&stats), [("A", 2), ("B", 1), ("C", 1)]);
+    }
+
+    #[test]
+    fn ties_keep_first_appearance_order() {
+        let stats = PlaylistStats::compute(&[
+            Song::titled(0, "", "D"),
+            Song::titled(0, "", "C"),
+            Song::titled(0, "", "B"),
+            Song::titled(0, "", "A"),
+            Song::titled(0, "", "A"),
+            Song::titled(0, "", "B"),
+            Song::titled(0, "", "C"),
+            Song::titled(0, "", "D"),
+        ]);
+        assert_eq!(stats.artist_count, 4);
+        assert_eq!(top(&stats), [("D", 2), ("C", 2), ("B", 2)]);
+    }
+
+    #[test]
+    fn missing_durations_and_artists_are_skipped() {
+        let stats = PlaylistStats::compute(&[
+            Song::titled(0, "", ""),
+            Song::titled(0, "", "A / A").lasting_ms(1_000),
+            Song::titled(0, "", "A"),
+        ]);
+        assert_eq!(stats.total_ms, 1_000);
+        assert_eq!(stats.unknown_duration, 2);
+        assert_eq!(stats.artist_count, 1);
+        assert_eq!(top(&stats), [("A", 2)]);
+    }
+
+    #[test]
+    fn empty_playlist_has_empty_stats() {
+        assert_eq!(PlaylistStats::compute(&[]), PlaylistStats::default());
+    }
+}
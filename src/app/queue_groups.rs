@@ -0,0 +1,324 @@
+//! 播放队列按来源分组显示
+//!
+//! 播放顺序中相邻且来源相同的歌曲组成一段，每段带标题（`来自: 叶惠美 (10)`）并可折叠。
+//! 分组只影响队列面板的显示与选择移动：`PlayQueue` 的顺序与游标保持扁平，播放不受影响。
+//! 随机模式下相邻歌曲的来源是随机的，不分组。
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::domain::model::{Playlist, Song};
+
+use super::{FM_QUEUE_ORIGIN, PlayMode, PlayQueue, RECOMMEND_ORIGIN};
+
+/// 播放顺序中来源相同的一段歌曲
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueGroup {
+    pub origin: Option<Arc<str>>,
+    /// 播放顺序中的位置范围
+    pub range: Range<usize>,
+}
+
+/// 队列面板中的一个分组（快照用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueueGroupView {
+    /// 来源显示名
+    pub label: String,
+    pub range: Range<usize>,
+    pub collapsed: bool,
+}
+
+impl QueueGroupView {
+    pub fn header(&self) -> String {
+        format!("来自: {} ({})", self.label, self.range.len())
+    }
+}
+
+/// 折叠状态按来源记录；没有来源的歌曲共用空字符串
+pub fn collapse_key(origin: Option<&str>) -> &str {
+    origin.unwrap_or("")
+}
+
+/// 把队列按播放顺序切分为来源相同的连续段；随机模式下返回空
+pub fn group_runs(queue: &PlayQueue) -> Vec<QueueGroup> {
+    if queue.mode() == PlayMode::Shuffle {
+        return Vec::new();
+    }
+    let mut groups: Vec<QueueGroup> = Vec::new();
+    for (pos, &idx) in queue.order().iter().enumerate() {
+        let origin = queue.entry_origin(idx);
+        match groups.last_mut() {
+            Some(last) if last.origin.as_ref() == origin => last.range.end = pos + 1,
+            _ => groups.push(QueueGroup {
+                origin: origin.cloned(),
+                range: pos..pos + 1,
+            }),
+        }
+    }
+    groups
+}
+
+/// 来源的显示名：歌单名、专辑名、歌手名等
+pub fn origin_label(origin: Option<&str>, first: Option<&Song>, playlists: &[Playlist]) -> String {
+    let Some(origin) = origin else {
+        return "其他".to_owned();
+    };
+    if origin == FM_QUEUE_ORIGIN {
+        return "私人FM".to_owned();
+    }
+    if origin == RECOMMEND_ORIGIN {
+        return "每日推荐".to_owned();
+    }
+    let (kind, id) = origin.split_once(':').unwrap_or((origin, ""));
+    match kind {
+        "playlist" => id
+            .parse::<i64>()
+            .ok()
+            .and_then(|id| playlists.iter().find(|p| p.id == id))
+            .map_or_else(|| "歌单".to_owned(), |p| p.name.clone()),
+        "album" => first
+            .map(|s| s.album.clone())
+            .filter(|a| !a.is_empty())
+            .unwrap_or_else(|| "专辑".to_owned()),
+        "artist" => first
+            .and_then(|s| s.artists.split('/').next())
+            .map(str::trim)
+            .filter(|a| !a.is_empty())
+            .map_or_else(|| "歌手".to_owned(), str::to_owned),
+        _ => origin.to_owned(),
+    }
+}
+
+/// 面板中的一行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueRow {
+    /// 第 n 个分组的标题
+    Header(usize),
+    /// 播放顺序中的位置
+    Entry(usize),
+}
+
+/// 分组后的行布局与行/队列位置之间的映射
+#[derive(Debug, Clone, Default)]
+pub struct QueueLayout {
+    rows: Vec<QueueRow>,
+    /// 每个分组：位置范围与是否折叠
+    groups: Vec<(Range<usize>, bool)>,
+}
+
+impl QueueLayout {
+    /// 按分组生成行；折叠的分组只显示标题，但正在播放的歌曲（`current`）始终显示
+    pub fn new(groups: &[QueueGroupView], current: Option<usize>) -> Self {
+        let mut rows = Vec::new();
+        for (g, group) in groups.iter().enumerate() {
+            rows.push(QueueRow::Header(g));
+            if group.collapsed {
+                rows.extend(
+                    current
+                        .filter(|c| group.range.contains(c))
+                        .map(QueueRow::Entry),
+                );
+            } else {
+                rows.extend(group.range.clone().map(QueueRow::Entry));
+            }
+        }
+        Self {
+            rows,
+            groups: groups
+                .iter()
+                .map(|g| (g.range.clone(), g.collapsed))
+                .collect(),
+        }
+    }
+
+    pub fn rows(&self) -> &[QueueRow] {
+        &self.rows
+    }
+
+    /// 位置所在的分组
+    pub fn group_of(&self, pos: usize) -> Option<usize> {
+        self.groups
+            .iter()
+            .position(|(range, _)| range.contains(&pos))
+    }
+
+    /// 位置对应的行：所在分组折叠时为分组标题（选择停在标题上）
+    pub fn row_of(&self, pos: usize) -> Option<usize> {
+        let g = self.group_of(pos)?;
+        let target = if self.groups[g].1 {
+            QueueRow::Header(g)
+        } else {
+            QueueRow::Entry(pos)
+        };
+        self.rows.iter().position(|&r| r == target)
+    }
+
+    /// 可停留的行：展开分组中的歌曲与折叠分组的标题
+    fn is_stop(&self, row: QueueRow) -> bool {
+        match row {
+            QueueRow::Header(g) => self.groups[g].1,
+            QueueRow::Entry(pos) => self.group_of(pos).is_some_and(|g| !self.groups[g].1),
+        }
+    }
+
+    /// 行对应的位置；折叠分组的标题对应该组第一首
+    fn pos_of(&self, row: QueueRow) -> usize {
+        match row {
+            QueueRow::Header(g) => self.groups[g].0.start,
+            QueueRow::Entry(pos) => pos,
+        }
+    }
+
+    /// 从 `pos` 移动 `delta` 个可停留的行（跳过折叠的歌曲），到头时停在两端
+    pub fn step(&self, pos: usize, delta: isize) -> Option<usize> {
+        let stops: Vec<QueueRow> = self
+            .rows
+            .iter()
+            .copied()
+            .filter(|&r| self.is_stop(r))
+            .collect();
+        if stops.is_empty() {
+            return None;
+        }
+        let here = self.row_of(pos).map(|row| self.rows[row]);
+        let at = here
+            .and_then(|r| stops.iter().position(|&s| s == r))
+            .unwrap_or(0);
+        let next = at.saturating_add_signed(delta).min(stops.len() - 1);
+        Some(self.pos_of(stops[next]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn songs(ids: Range<i64>) -> Vec<Song> {
+        ids.map(|id| Song::titled(id, "", "周杰伦/费玉清").in_album("叶惠美"))
+            .collect()
+    }
+
+    /// 专辑 3 首 + 手动 2 首 + 歌单 4 首
+    fn mixed_queue() -> PlayQueue {
+        let mut q = PlayQueue::new(PlayMode::Sequential);
+        q.set_songs(songs(0..3), Some(0));
+        q.set_origin("album:1");
+        q.extend_from(songs(3..5), None);
+        q.extend_from(songs(5..9), Some("playlist:7"));
+        q
+    }
+
+    fn views(q: &PlayQueue, collapsed: &[&str]) -> Vec<QueueGroupView> {
+        group_runs(q)
+            .into_iter()
+            .map(|g| {
+                let key = collapse_key(g.origin.as_deref()).to_owned();
+                QueueGroupView {
+                    label: key.clone(),
+                    collapsed: collapsed.contains(&key.as_str()),
+                    range: g.range,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn runs_follow_entry_origins_in_play_order() {
+        let mut q = mixed_queue();
+        let groups = group_runs(&q);
+        let ranges: Vec<_> = groups.iter().map(|g| g.range.clone()).collect();
+        assert_eq!(ranges, [0..3, 3..5, 5..9]);
+        assert_eq!(groups[0].origin.as_deref(), Some("album:1"));
+        assert_eq!(groups[1].origin, None);
+        q.set_mode(PlayMode::Shuffle);
+        assert!(group_runs(&q).is_empty());
+    }
+
+    #[test]
+    fn collapsed_group_keeps_only_header_and_playing_row() {
+        let q = mixed_queue();
+        let layout = QueueLayout::new(&views(&q, &["playlist:7"]), Some(6));
+        assert_eq!(
+            &layout.rows()[7..],
+            [QueueRow::Header(2), QueueRow::Entry(6)],
+            "折叠分组中正在播放的歌曲仍然显示"
+        );
+
+        let layout = QueueLayout::new(&views(&q, &["playlist:7"]), Some(0));
+        assert_eq!(layout.rows().last(), Some(&QueueRow::Header(2)));
+    }
+
+    #[test]
+    fn selection_on_hidden_row_maps_to_group_header() {
+        let q = mixed_queue();
+        let layout = QueueLayout::new(&views(&q, &[""]), None);
+        // 位置 4 属于折叠的第二组：选择显示在标题上
+        assert_eq!(
+            layout.rows()[layout.row_of(4).unwrap()],
+            QueueRow::Header(1)
+        );
+        // 从隐藏位置继续移动按标题所在的位置计算
+        assert_eq!(layout.step(4, 1), Some(5));
+        assert_eq!(layout.step(4, -1), Some(2));
+    }
+
+    #[test]
+    fn navigation_skips_collapsed_entries() {
+        let q = mixed_queue();
+        let layout = QueueLayout::new(&views(&q, &["album:1"]), Some(1));
+        // 第一组折叠：只有标题可停留（正在播放的行仅显示）
+        assert_eq!(layout.step(0, 0), Some(0));
+        assert_eq!(layout.step(0, 1), Some(3));
+        assert_eq!(layout.step(3, -1), Some(0));
+        assert_eq!(layout.step(3, -5), Some(0), "到头停在第一行");
+        assert_eq!(layout.step(8, 1), Some(8), "到尾停在最后一行");
+
+        // 展开时逐首移动
+        let layout = QueueLayout::new(&views(&q, &[]), Some(1));
+        assert_eq!(layout.step(0, 1), Some(1));
+        assert_eq!(layout.step(2, 1), Some(3));
+    }
+
+    #[test]
+    fn removing_a_whole_section_drops_its_header() {
+        let mut q = mixed_queue();
+        for _ in 3..5 {
+            q.remove(3);
+        }
+        let views = views(&q, &[]);
+        let ranges: Vec<_> = views.iter().map(|g| g.range.clone()).collect();
+        assert_eq!(ranges, [0..3, 3..7]);
+
+        let layout = QueueLayout::new(&views, Some(0));
+        assert_eq!(
+            layout
+                .rows()
+                .iter()
+                .filter(|r| matches!(r, QueueRow::Header(_)))
+                .count(),
+            2
+        );
+        // 原来选在被删除分组上的位置落到后一组
+        assert_eq!(layout.group_of(3), Some(1));
+        assert_eq!(layout.step(3, 0), Some(3));
+        assert_eq!(layout.row_of(9), None);
+    }
+
+    #[test]
+    fn labels_resolve_names_per_origin_kind() {
+        let first = Song::titled(1, "", "周杰伦/费玉清").in_album("叶惠美");
+        let playlists = [Playlist {
+            id: 7,
+            name: "通勤".to_owned(),
+            ..Default::default()
+        }];
+        let label = |o| origin_label(o, Some(&first), &playlists);
+        assert_eq!(label(Some("album:1")), "叶惠美");
+        assert_eq!(label(Some("artist:6452")), "周杰伦");
+        assert_eq!(label(Some("playlist:7")), "通勤");
+        assert_eq!(label(Some("playlist:8")), "歌单");
+        assert_eq!(label(Some(RECOMMEND_ORIGIN)), "每日推荐");
+        assert_eq!(label(Some(FM_QUEUE_ORIGIN)), "私人FM");
+        assert_eq!(label(None), "其他");
+    }
+}
@@ -0,0 +1,115 @@
+//! 单曲播放失败记录
+//!
+//! 仅在本次运行内有效：记录每首歌最近一次失败的类型、累计次数与时间，
+//! 供歌曲列表标记（⚠）、右侧详情与自动跳过使用。
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crate::error::{AudioErrorVariant, MessageError};
+
+/// 累计失败达到该次数后在歌曲列表中显示 ⚠
+pub const SONG_FAILURE_MARK_THRESHOLD: u32 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SongFailureKind {
+    /// 没有可用的播放链接
+    Unavailable,
+    /// 下载/缓存失败
+    Download,
+    /// 打开或解码音频失败
+    Decode,
+    /// 其他播放错误
+    Other,
+}
+
+impl SongFailureKind {
+    pub fn from_error(e: &MessageError) -> Self {
+        match e {
+            MessageError::Audio(
+                AudioErrorVariant::Download(_)
+                | AudioErrorVariant::Cache(_)
+                | AudioErrorVariant::FileNotFound(_),
+            ) => Self::Download,
+            MessageError::Audio(
+                AudioErrorVariant::OpenFile { .. } | AudioErrorVariant::Decode { .. },
+            ) => Self::Decode,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Unavailable => "无可用链接",
+            Self::Download => "下载失败",
+            Self::Decode => "解码失败",
+            Self::Other => "播放错误",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SongFailure {
+    /// 最近一次失败的类型
+    pub kind: SongFailureKind,
+    /// 本次运行内累计失败次数（含重试）
+    pub count: u32,
+    pub last_at: Instant,
+}
+
+/// 歌曲 id → 失败记录
+#[derive(Debug, Clone, Default)]
+pub struct SongFailures(HashMap<i64, SongFailure>);
+
+impl SongFailures {
+    /// 记录一次失败，返回该歌曲的累计失败次数
+    pub fn record(&mut self, song_id: i64, kind: SongFailureKind) -> u32 {
+        let now = Instant::now();
+        let entry = self.0.entry(song_id).or_insert(SongFailure {
+            kind,
+            count: 0,
+            last_at: now,
+        });
+        entry.kind = kind;
+        entry.count = entry.count.saturating_add(1);
+        entry.last_at = now;
+        entry.count
+    }
+
+    pub fn get(&self, song_id: i64) -> Option<&SongFailure> {
+        self.0.get(&song_id)
+    }
+
+    /// 累计失败次数（没有记录时为 0）
+    pub fn count(&self, song_id: i64) -> u32 {
+        self.get(song_id).map_or(0, |f| f.count)
+    }
+
+    /// 是否需要在列表中标记 ⚠
+    pub fn is_marked(&self, song_id: i64) -> bool {
+        self.count(song_id) >= SONG_FAILURE_MARK_THRESHOLD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker_appears_from_second_failure() {
+        let mut failures = SongFailures::default();
+        assert!(!failures.is_marked(1));
+
+        assert_eq!(failures.record(1, SongFailureKind::Download), 1);
+        assert!(!failures.is_marked(1), "一次失败不标记");
+
+        assert_eq!(failures.record(1, SongFailureKind::Unavailable), 2);
+        assert!(failures.is_marked(1));
+        assert_eq!(
+            failures.get(1).map(|f| f.kind),
+            Some(SongFailureKind::Unavailable),
+            "记录最近一次失败的类型"
+        );
+        assert!(!failures.is_marked(2), "记录按歌曲区分");
+    }
+}
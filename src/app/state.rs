@@ -1,19 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
-use super::PlayQueue;
+use super::audio_focus::{AudioFocus, AudioFocusPolicy};
+use super::notifications::{Notification, NotificationLog};
+use super::pinyin::{self, SongPinyinCache};
+use super::playlist_filter::{self, PlaylistFilter};
+use super::playlist_stats::PlaylistStats;
+use super::queue_groups::{self, QueueGroupView};
+use super::song_failures::SongFailures;
+use super::tasks::{BackgroundTask, QuitConfirm, TaskRegistry};
+use super::track_filter;
+use super::track_order::{TrackOrder, TrackOrderKeys};
+use super::{PaneRatios, PlayQueue};
 use crate::audio_worker::AudioStreamHint;
-use crate::domain::model::LyricLine;
+use crate::domain::model::{LyricLine, SongPreview};
+use crate::features::settings::registry as settings_registry;
 use crate::keybindings::{KeyBindings, SharedKeyBindings};
 
-pub use crate::domain::model::{Playlist, Song};
+pub use crate::domain::model::{Album, Artist, Playlist, Song, SongNameMode};
 
+/// 歌曲内的播放进度：扣除暂停后实际经过的时长按倍速折算
+///
+/// 倍速改变时调用方需重新对齐计时起点（见 [`App::set_play_speed`]），否则之前经过的时长也会按新倍速折算。
 pub fn playback_elapsed_ms(
     started_at: Option<Instant>,
     paused: bool,
     paused_at: Option<Instant>,
     paused_accum_ms: u64,
+    speed: f32,
 ) -> u64 {
     let Some(started_at) = started_at else {
         return 0;
@@ -25,12 +41,22 @@ pub fn playback_elapsed_ms(
         Instant::now()
     };
 
-    u64::try_from(
-        now.duration_since(started_at)
-            .as_millis()
-            .saturating_sub(paused_accum_ms as u128),
-    )
-    .unwrap_or(u64::MAX)
+    let wall_ms = now
+        .duration_since(started_at)
+        .as_millis()
+        .saturating_sub(paused_accum_ms as u128);
+    if speed == 1.0 {
+        return u64::try_from(wall_ms).unwrap_or(u64::MAX);
+    }
+    (wall_ms as f64 * f64::from(speed)).round() as u64
+}
+
+/// 歌曲内的一段进度在 `speed` 倍速下实际经过的时长
+pub fn playback_wall_ms(position_ms: u64, speed: f32) -> u64 {
+    if speed == 1.0 {
+        return position_ms;
+    }
+    (position_ms as f64 / f64::from(speed)).round() as u64
 }
 
 /// 默认操作菜单选项
@@ -43,8 +69,175 @@ pub fn default_menu_items() -> Vec<String> {
     ]
 }
 
+/// 私人 FM 播放队列的来源标记（见 [`PlayQueue::origin`]）
+pub const FM_QUEUE_ORIGIN: &str = "fm";
+/// 每日推荐作为播放队列时的来源标记
+pub const RECOMMEND_ORIGIN: &str = "recommend:daily";
+
+/// 私人 FM 状态
+///
+/// FM 期间播放队列只记录本次已播放的 FM 歌曲，下一首从 `buffer` 取；用户原来的队列暂存在 `saved_queue`。
+#[derive(Debug, Clone)]
+pub struct FmState {
+    /// 尚未播放的 FM 歌曲
+    pub buffer: VecDeque<Song>,
+    /// 进入 FM 前的播放队列，退出时恢复
+    pub saved_queue: PlayQueue,
+    /// 缓冲区为空，补充到歌曲后立即播放
+    pub play_when_ready: bool,
+}
+
+/// 被整体替换掉的播放队列（按 `u` 撤销替换时恢复）
+#[derive(Debug, Clone)]
+pub struct ReplacedQueue {
+    /// 替换前的队列（歌曲、顺序、游标与来源）
+    pub queue: PlayQueue,
+    /// 替换时正在播放的歌曲
+    pub playing_song_id: Option<i64>,
+}
+
+/// 启动时恢复的歌曲正在加载（见 `resume_restored_track`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StartupResume {
+    pub song_id: i64,
+    /// 开始播放后立即暂停（`resume_on_start` 关闭）
+    pub paused: bool,
+}
+
+/// 不可用歌曲及找到的可替换版本
+#[derive(Debug, Clone)]
+pub struct SongSubstitution {
+    pub original: Song,
+    pub candidate: Song,
+}
+
+/// 「检查不可用歌曲」的审阅框：逐条确认替换或跳过
+#[derive(Debug, Clone, Default)]
+pub struct UnavailableReview {
+    pub playlist_id: i64,
+    pub playlist_name: String,
+    /// 尚未处理的替换建议
+    pub items: Vec<SongSubstitution>,
+    pub selected: usize,
+}
+
+/// 手机号登录表单中的输入框
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PhoneLoginField {
+    #[default]
+    Phone,
+    /// 密码或短信验证码
+    Secret,
+}
+
+/// 手机号登录表单（密码 / 短信验证码）
+///
+/// 密码只保存在 core 中，提交后立即清空；UI 快照里只有掩码。
+#[derive(Clone, Default)]
+pub struct PhoneLoginForm {
+    /// 手机号；其他地区以 `+区号 ` 开头，如 `+852 91234567`
+    pub phone: String,
+    /// 密码或短信验证码
+    pub secret: String,
+    /// true 为短信验证码登录
+    pub captcha: bool,
+    pub field: PhoneLoginField,
+}
+
+impl std::fmt::Debug for PhoneLoginForm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PhoneLoginForm")
+            .field("phone", &self.phone)
+            .field("secret", &"<已隐藏>")
+            .field("captcha", &self.captcha)
+            .field("field", &self.field)
+            .finish()
+    }
+}
+
+impl PhoneLoginForm {
+    /// 解析手机号输入，返回 `(区号, 手机号)`；不带 `+区号` 时为中国大陆（86）
+    pub fn parse_phone(&self) -> Option<(String, String)> {
+        let input = self.phone.trim();
+        let (country_code, number) = match input.strip_prefix('+') {
+            Some(rest) => {
+                let split = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                (&rest[..split], &rest[split..])
+            }
+            None => ("86", input),
+        };
+        let number: String = number.chars().filter(char::is_ascii_digit).collect();
+        (!country_code.is_empty() && !number.is_empty()).then(|| (country_code.to_owned(), number))
+    }
+
+    fn snapshot(&self) -> PhoneLoginSnapshot {
+        PhoneLoginSnapshot {
+            phone: self.phone.clone(),
+            secret: if self.captcha {
+                self.secret.clone()
+            } else {
+                "•".repeat(self.secret.chars().count())
+            },
+            captcha: self.captcha,
+            field: self.field,
+        }
+    }
+}
+
+/// 手机号登录表单的快照；密码已替换为掩码
+#[derive(Debug, Clone, Default)]
+pub struct PhoneLoginSnapshot {
+    pub phone: String,
+    /// 验证码原样显示，密码显示为等长的 `•`
+    pub secret: String,
+    pub captcha: bool,
+    pub field: PhoneLoginField,
+}
+
+/// 「添加到歌单」选择框
+#[derive(Debug, Clone, Default)]
+pub struct PlaylistPicker {
+    /// 要添加的歌曲（搜索结果中选中的一首，或标记的多首）
+    pub songs: Vec<Song>,
+    /// 可选的目标歌单（仅自己创建的）
+    pub playlists: Vec<Playlist>,
+    /// 输入的筛选词，按歌单名模糊匹配（ASCII 筛选词也按拼音匹配）
+    pub filter: String,
+    /// 在筛选后列表中的位置
+    pub selected: usize,
+}
+
+impl PlaylistPicker {
+    /// 与筛选词匹配的歌单（保持原有顺序）
+    pub fn visible(&self) -> Vec<&Playlist> {
+        self.playlists
+            .iter()
+            .filter(|p| {
+                fuzzy_match(&p.name, &self.filter) || pinyin::matches(&p.name, &self.filter)
+            })
+            .collect()
+    }
+
+    /// 当前选中的歌单
+    pub fn selected_playlist(&self) -> Option<&Playlist> {
+        self.visible().get(self.selected).copied()
+    }
+}
+
+/// 模糊匹配：`query` 的字符（忽略大小写与空白）按顺序出现在 `text` 中即可，不要求相邻
+pub fn fuzzy_match(text: &str, query: &str) -> bool {
+    let mut chars = text.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|q| chars.any(|c| c == q))
+}
+
 /// Toast 通知级别
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ToastLevel {
     Error, // 红色，8秒自动消失
     #[allow(dead_code)]
@@ -93,6 +286,15 @@ impl Toast {
         Self::new(message, ToastLevel::Info)
     }
 
+    /// 专注模式下只显示错误 Toast，其余只写日志（返回 None 表示不显示）
+    pub fn filter_for_focus_mode(self, focus_mode: bool) -> Option<Self> {
+        if focus_mode && self.level != ToastLevel::Error {
+            tracing::info!(message = %self.message, "专注模式：隐藏 Toast");
+            return None;
+        }
+        Some(self)
+    }
+
     /// 检查是否已过期
     pub fn is_expired(&self) -> bool {
         if let Some(duration) = self.level.duration_ms() {
@@ -103,13 +305,15 @@ impl Toast {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum View {
     Login,
     Playlists,
     Search,
     Lyrics,
     Settings,
+    /// 播放队列：按播放顺序查看、调整与移除
+    Queue,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -120,13 +324,82 @@ pub enum UiFocus {
     BodyRight,
 }
 
-/// 标签页配置：统一管理标题与对应的 View
+/// 已登录启动（ClientReady）时进入的页面
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupView {
+    /// 上次退出时所在的页面（依赖状态恢复，无记录时回退到歌单）
+    Last,
+    Playlists,
+    Search,
+    /// 正在播放（歌词页）
+    NowPlaying,
+}
+
+/// 歌词页在原文下方显示的副行（`t` 循环切换）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LyricsDisplay {
+    /// 只显示原文
+    Original,
+    /// 原文 + 翻译
+    #[default]
+    Translation,
+    /// 原文 + 罗马音
+    Roman,
+}
+
+impl LyricsDisplay {
+    /// 循环顺序：原文 → 原文+翻译 → 原文+罗马音
+    pub fn next(self) -> Self {
+        match self {
+            LyricsDisplay::Original => LyricsDisplay::Translation,
+            LyricsDisplay::Translation => LyricsDisplay::Roman,
+            LyricsDisplay::Roman => LyricsDisplay::Original,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LyricsDisplay::Original => "原文",
+            LyricsDisplay::Translation => "原文+翻译",
+            LyricsDisplay::Roman => "原文+罗马音",
+        }
+    }
+
+    /// 该行在当前显示方式下的副行；没有对应内容时为 None
+    pub fn secondary(self, line: &LyricLine) -> Option<&str> {
+        let text = match self {
+            LyricsDisplay::Original => None,
+            LyricsDisplay::Translation => line.translation.as_deref(),
+            LyricsDisplay::Roman => line.roman.as_deref(),
+        };
+        text.filter(|t| !t.trim().is_empty())
+    }
+}
+
+/// 标签页配置：统一管理标题、对应的 View 与可聚焦区域
 #[derive(Debug, Clone, Copy)]
 pub struct TabConfig {
     pub title: &'static str,
     pub view: View,
+    /// 切换到该页的功能键编号（F1–F4 依次对应前四页，队列页为 F8）
+    pub fkey: u8,
+    /// Tab / Shift+Tab 循环的区域（按顺序）；不在其中的面板只展示信息
+    pub focus_order: &'static [UiFocus],
+    /// 首次进入该页面时的焦点
+    pub default_focus: UiFocus,
 }
 
+const LOGIN_FOCUS: &[UiFocus] = &[UiFocus::BodyCenter, UiFocus::BodyRight];
+const PLAYLISTS_FOCUS: &[UiFocus] = &[UiFocus::BodyLeft, UiFocus::BodyCenter, UiFocus::BodyRight];
+const SEARCH_FOCUS: &[UiFocus] = &[
+    UiFocus::HeaderSearch,
+    UiFocus::BodyCenter,
+    UiFocus::BodyRight,
+];
+const LYRICS_FOCUS: &[UiFocus] = &[UiFocus::BodyCenter, UiFocus::BodyRight];
+const SETTINGS_FOCUS: &[UiFocus] = &[UiFocus::BodyLeft, UiFocus::BodyCenter, UiFocus::BodyRight];
+const QUEUE_FOCUS: &[UiFocus] = &[UiFocus::BodyCenter, UiFocus::BodyRight];
+
 /// 获取当前登录状态下的标签页配置
 pub fn tab_configs(logged_in: bool) -> &'static [TabConfig] {
     if logged_in {
@@ -134,18 +407,37 @@ pub fn tab_configs(logged_in: bool) -> &'static [TabConfig] {
             TabConfig {
                 title: "歌单",
                 view: View::Playlists,
+                fkey: 1,
+                focus_order: PLAYLISTS_FOCUS,
+                default_focus: UiFocus::BodyCenter,
             },
             TabConfig {
                 title: "搜索",
                 view: View::Search,
+                fkey: 2,
+                focus_order: SEARCH_FOCUS,
+                default_focus: UiFocus::HeaderSearch,
             },
             TabConfig {
                 title: "歌词",
                 view: View::Lyrics,
+                fkey: 3,
+                focus_order: LYRICS_FOCUS,
+                default_focus: UiFocus::BodyCenter,
             },
             TabConfig {
                 title: "设置",
                 view: View::Settings,
+                fkey: 4,
+                focus_order: SETTINGS_FOCUS,
+                default_focus: UiFocus::BodyCenter,
+            },
+            TabConfig {
+                title: "队列",
+                view: View::Queue,
+                fkey: 8,
+                focus_order: QUEUE_FOCUS,
+                default_focus: UiFocus::BodyCenter,
             },
         ]
     } else {
@@ -153,18 +445,37 @@ pub fn tab_configs(logged_in: bool) -> &'static [TabConfig] {
             TabConfig {
                 title: "登录",
                 view: View::Login,
+                fkey: 1,
+                focus_order: LOGIN_FOCUS,
+                default_focus: UiFocus::BodyCenter,
             },
             TabConfig {
                 title: "搜索",
                 view: View::Search,
+                fkey: 2,
+                focus_order: SEARCH_FOCUS,
+                default_focus: UiFocus::HeaderSearch,
             },
             TabConfig {
                 title: "歌词",
                 view: View::Lyrics,
+                fkey: 3,
+                focus_order: LYRICS_FOCUS,
+                default_focus: UiFocus::BodyCenter,
             },
             TabConfig {
                 title: "设置",
                 view: View::Settings,
+                fkey: 4,
+                focus_order: SETTINGS_FOCUS,
+                default_focus: UiFocus::BodyCenter,
+            },
+            TabConfig {
+                title: "队列",
+                view: View::Queue,
+                fkey: 8,
+                focus_order: QUEUE_FOCUS,
+                default_focus: UiFocus::BodyCenter,
             },
         ]
     }
@@ -175,16 +486,75 @@ pub fn tab_index_for_view(view: View, logged_in: bool) -> Option<usize> {
     tab_configs(logged_in).iter().position(|c| c.view == view)
 }
 
+fn tab_config_for_view(view: View) -> &'static TabConfig {
+    tab_configs(true)
+        .iter()
+        .chain(tab_configs(false))
+        .find(|c| c.view == view)
+        .expect("每个 View 都有标签页配置")
+}
+
+/// 页面可聚焦的区域（按 Tab 循环顺序）
+pub fn focus_order(view: View) -> &'static [UiFocus] {
+    tab_config_for_view(view).focus_order
+}
+
+/// 首次进入页面时的焦点
+pub fn default_focus(view: View) -> UiFocus {
+    tab_config_for_view(view).default_focus
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlaylistMode {
     List,
     Tracks,
 }
 
+/// 搜索类型（`t` 切换）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SearchKind {
+    #[default]
+    Songs,
+    Albums,
+    Artists,
+}
+
+/// 搜索页中已打开的专辑或歌手：曲目放在 `search_results` 中
+#[derive(Debug, Clone)]
+pub enum SearchOpened {
+    Album(Album),
+    Artist(Artist),
+}
+
+impl SearchOpened {
+    /// 播放时写入队列的来源标记
+    pub fn origin(&self) -> String {
+        match self {
+            Self::Album(a) => format!("album:{}", a.id),
+            Self::Artist(a) => format!("artist:{}", a.id),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PlaylistPreload {
     pub status: PreloadStatus,
     pub songs: Vec<Song>,
+    /// 请求的歌曲数与实际返回数之差（地区限制等原因详情接口不返回的歌曲）
+    #[serde(default)]
+    pub missing_count: usize,
+    /// 歌单统计；完成时计算，歌单被编辑后清空（打开时重新计算）
+    #[serde(default)]
+    pub stats: Option<PlaylistStats>,
+    /// 预加载完成时间（毫秒时间戳），用于显示缓存新旧
+    #[serde(default)]
+    pub completed_at_ms: Option<i64>,
+    /// 歌曲加入歌单的时间（id -> 毫秒时间戳），用于「最近添加」排序
+    #[serde(default)]
+    pub added_at: HashMap<i64, i64>,
+    /// 本次加载所请求歌曲 id 列表（含顺序）的指纹，用于识别同一份歌单内容的重复加载
+    #[serde(default)]
+    pub track_ids_fingerprint: Option<u64>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -212,25 +582,77 @@ pub enum PlayMode {
 pub struct App {
     pub view: View,
     pub ui_focus: UiFocus,
+    /// 各页面离开时的焦点（切回时恢复）
+    pub view_focus: HashMap<View, UiFocus>,
     pub help_visible: bool,
     pub toast: Option<Toast>,
+    /// 专注模式（仅本次运行有效，不写入设置）：隐藏非错误 Toast
+    pub focus_mode: bool,
+    /// 专注模式下状态栏用「♪ 播放中」代替歌名
+    pub focus_mask_titles: bool,
 
     pub menu_visible: bool,
     pub menu_selected: usize,
     pub menu_items: Vec<String>,
+    pub tasks: TaskRegistry,
+    pub tasks_visible: bool,
+    pub tasks_selected: usize,
+    /// 通知历史（F7 打开，Enter 查看完整消息）
+    pub notifications: NotificationLog,
+    pub notifications_visible: bool,
+    pub notifications_selected: usize,
+    /// 正在查看详情的通知（`notifications` 中的下标）
+    pub notification_detail: Option<usize>,
+    /// 详情面板的滚动行数
+    pub notification_detail_scroll: usize,
+    /// 启动时发现过旧的播放状态，等待用户选择是否恢复队列（弹窗文案）
+    pub restore_prompt: Option<String>,
+    /// 「添加到歌单」选择框（打开时捕获按键）
+    pub playlist_picker: Option<PlaylistPicker>,
+    /// 「检查不可用歌曲」的审阅框（打开时捕获按键）
+    pub unavailable_review: Option<UnavailableReview>,
+    /// 有下载进行中时的退出确认弹窗
+    pub quit_confirm: Option<QuitConfirm>,
+    /// 等待中的下载已全部结束，UI 收到后退出
+    pub exit_requested: bool,
 
     pub login_qr_url: Option<String>,
     pub login_qr_ascii: Option<String>,
     pub login_unikey: Option<String>,
+    /// 当前二维码 key 的生成时间，用于显示剩余有效期
+    pub login_qr_created_at: Option<Instant>,
+    /// 本次扫码登录中二维码过期后已自动重新生成的次数（按 l 手动生成时清零）
+    pub login_qr_auto_regenerations: u8,
     pub login_status: String,
     pub logged_in: bool,
     pub login_cookie_input: String,
     pub login_cookie_input_visible: bool,
+    /// 手机号登录表单（按 p 打开）
+    pub login_phone_form: Option<PhoneLoginForm>,
 
     pub search_input: String,
     pub search_results: Vec<Song>,
     pub search_selected: usize,
     pub search_status: String,
+    /// 最近一次提交的关键词（翻页时沿用，不受输入框后续修改影响）
+    pub search_query: String,
+    /// 上一页结果是满页，可能还有更多结果
+    pub search_has_more: bool,
+    pub search_kind: SearchKind,
+    /// 专辑搜索结果
+    pub search_albums: Vec<Album>,
+    /// 歌手搜索结果
+    pub search_artists: Vec<Artist>,
+    /// 已打开的专辑/歌手：曲目放在 `search_results` 中，`search_selected` 指向曲目
+    pub search_opened: Option<SearchOpened>,
+    /// 打开前专辑/歌手列表的选中项（返回时恢复）
+    pub search_collection_selected: usize,
+    /// 搜索结果预览的会话缓存：歌曲 id → 详情
+    pub search_previews: HashMap<i64, SongPreview>,
+    /// 最近一次预览的歌曲是否已在本地音频缓存中：`(歌曲 id, 已缓存)`
+    pub search_preview_cached: Option<(i64, bool)>,
+    /// 用 `v` 标记的歌曲（按标记顺序），可跨专辑/歌手结果一起添加到歌单；新搜索时清空
+    pub search_marked: Vec<Song>,
 
     pub now_playing: Option<String>,
     pub play_status: String,
@@ -238,36 +660,154 @@ pub struct App {
     pub play_started_at: Option<Instant>,
     pub play_total_ms: Option<u64>,
     pub play_stream_hint: Option<AudioStreamHint>,
+    /// 上次刷新缓冲进度状态的时间（节流用）
+    pub play_buffering_status_at: Option<Instant>,
     pub play_paused_at: Option<Instant>,
     pub play_paused_accum_ms: u64,
+    /// 播放倍速
+    pub play_speed: f32,
     pub pending_seek_ms: Option<u64>,
+    /// 启动时恢复的歌曲加载中，开始播放后按此处理（跳到恢复的进度后是否暂停）
+    pub startup_resume: Option<StartupResume>,
+    /// 音频引擎最近一次上报的实际播放位置（`AudioEvent::Position`）
+    pub play_position_ms: Option<u64>,
+    /// 最近一次发出 Seek 的时间；此后短时间内的位置上报可能早于 Seek，不用于校正
+    pub play_seek_at: Option<Instant>,
     pub play_id: Option<u64>,
     pub play_queue: PlayQueue,
+    /// 队列面板中折叠的来源分组（按来源标记，见 [`queue_groups::collapse_key`]）
+    pub queue_collapsed: HashSet<String>,
+    /// 队列面板中选中的位置（播放顺序）；未选择时跟随当前歌曲
+    pub queue_selected: Option<usize>,
+    /// 最近一次被替换掉的播放队列（仅本次运行，只保留一层）
+    pub queue_undo: Option<ReplacedQueue>,
+    /// 私人 FM（开启时播放队列不决定下一首）
+    pub fm: Option<FmState>,
     pub play_mode: PlayMode,
+    /// 用户设置的音量；静音时保持不变，取消静音后恢复
     pub volume: f32,
+    /// 静音（仅本次运行有效，不写入设置）
+    pub muted: bool,
+    /// 当前歌曲播完后停止，不推进队列（仅本次运行有效）
+    pub stop_after_current: bool,
+    /// A-B 循环的 A / B 点（播放位置，毫秒）；换歌时清除
+    pub loop_a_ms: Option<u64>,
+    pub loop_b_ms: Option<u64>,
+    /// `Alt+↑/↓` 每次调整的音量
+    pub volume_step: f32,
     pub play_song_id: Option<i64>,
     pub play_error_count: u32,
+    /// 本次运行内各歌曲的播放失败记录
+    pub song_failures: SongFailures,
     pub play_br: i64,
+    /// 当前歌曲实际拿到的音质档位（SongUrl 返回），可能低于 play_br
+    pub play_actual_br: Option<i64>,
     pub crossfade_ms: u64,
+    /// 音量均衡：按歌曲的响度增益调整音量
+    pub normalize_volume: bool,
+    /// 低音/高音增益（dB）
+    pub eq_bass_db: i8,
+    pub eq_treble_db: i8,
+    /// 本次运行中音频缓存超出上限时自动清理的文件数与字节数
+    pub cache_evicted_files: usize,
+    pub cache_evicted_bytes: u64,
+    /// 音频缓存当前的文件数与总字节数（进入设置页时查询）
+    pub cache_usage: Option<(usize, u64)>,
+    /// 歌曲列表中已缓存的歌曲（按当前音质查询）
+    pub cached_song_ids: HashSet<i64>,
+    /// 本次运行使用的音频缓存目录（启动时解析 `--cache-dir` / `audio_cache_dir` 后确定）
+    pub audio_cache_dir: PathBuf,
+    /// 缓存目录被改到别处时为数据目录下的默认位置，「移动现有缓存」从这里搬运
+    pub audio_cache_previous_dir: Option<PathBuf>,
+    /// Ctrl+←/→ 的 Seek 步长
+    pub seek_step_small_ms: u64,
+    /// 歌词页 PageUp/PageDown 的 Seek 步长
+    pub seek_step_large_ms: u64,
+    /// 终端挂起（Ctrl+Z）恢复后保持暂停
+    pub pause_on_suspend: bool,
+    /// 挂起前自动暂停了播放；恢复时据此决定是否继续
+    pub suspend_paused: bool,
+    /// 终端失去焦点时自动暂停，重新获得焦点时继续
+    pub pause_on_focus_lost: bool,
+    /// 由程序自动暂停（挂起恢复、音频焦点、终端失焦）；用户手动播放/暂停时清除
+    pub auto_paused: bool,
+    /// 其它程序播放声音时的处理方式
+    pub audio_focus_policy: AudioFocusPolicy,
+    /// 「降低音量」策略下降低的百分比
+    pub audio_focus_duck_percent: u8,
+    /// 当前因其它声音让出焦点的状态
+    pub audio_focus: AudioFocus,
+    /// 新歌开始播放时显示桌面通知
+    pub notifications_enabled: bool,
+    /// 在底部状态栏显示当前歌词行
+    pub status_lyrics: bool,
+    /// 已登录启动时进入的页面
+    pub startup_view: StartupView,
+    /// 歌曲名的显示方式（原名 / 译名优先 / 原名(译名)）
+    pub song_name_mode: SongNameMode,
+    /// 单色显示（设置项；`NO_COLOR` / `--no-color` 由 UI 另行处理）
+    pub no_color: bool,
+    /// 启动时恢复上次的播放队列与进度
+    pub restore_queue_on_startup: bool,
+    /// 启动时恢复的歌曲加载后直接继续播放（关闭时停在暂停状态）
+    pub resume_on_start: bool,
+    /// 状态恢复得到的上次所在页面（供 StartupView::Last 使用）
+    pub last_view: Option<View>,
+    /// 主体三栏宽度比例（Ctrl+Shift+←/→ 调整）
+    pub pane_ratios: PaneRatios,
+    /// 终端宽度是否足以显示右栏（由 UI 在尺寸变化时上报）
+    pub right_pane_visible: bool,
 
     pub account_uid: Option<i64>,
     pub account_nickname: Option<String>,
+    /// 登录 cookie（MUSIC_U）的过期时间（Unix 秒），未知时为 None
+    pub login_expires_at: Option<i64>,
+    /// 「我喜欢」中的歌曲 id（登录后拉取，收藏切换时乐观更新）
+    pub liked_song_ids: HashSet<i64>,
+    /// 「我喜欢」预加载未完成时发生的收藏变更，预加载完成后补应用（true 为收藏）
+    pub liked_pending: Vec<(Song, bool)>,
     pub playlists: Vec<Playlist>,
+    /// `playlists` 中的下标（不受筛选影响）
     pub playlists_selected: usize,
+    pub playlist_filter: PlaylistFilter,
     pub playlist_mode: PlaylistMode,
     pub playlist_tracks: Vec<Song>,
     pub playlist_tracks_selected: usize,
+    pub playlist_tracks_missing: usize,
+    /// 已打开歌单的统计（歌曲加载完成时计算）
+    pub playlist_tracks_stats: Option<PlaylistStats>,
+    /// 歌曲列表来自每日推荐（而非 `playlists_selected` 指向的歌单）
+    pub playlist_tracks_recommend: bool,
+    /// 已打开歌单的原始顺序与添加时间，用于切换显示顺序
+    pub playlist_tracks_order_keys: TrackOrderKeys,
+    /// 各歌单选择的显示顺序（本次运行内有效；未记录的为原始顺序）
+    pub playlist_track_orders: HashMap<i64, TrackOrder>,
+    /// 歌单内搜索的关键词（为空时不筛选）
+    pub playlist_filter_input: String,
+    /// 歌单内搜索输入框已打开
+    pub playlist_filter_editing: bool,
+    /// 歌曲名与歌手的拼音缓存（歌单内搜索时按需填充）
+    pub song_pinyin: SongPinyinCache,
     pub playlists_status: String,
 
     pub playlist_preloads: HashMap<i64, PlaylistPreload>,
     pub preload_summary: String,
+    /// 预加载因接口错误过多已暂停（手动重新预加载或重新登录后恢复）
+    pub preload_suspended: bool,
 
     pub lyrics_song_id: Option<i64>,
-    pub lyrics: Vec<LyricLine>,
+    /// 歌词行；用 Arc 共享给歌词页与状态栏快照，避免每次快照复制
+    pub lyrics: Arc<[LyricLine]>,
     pub lyrics_status: String,
     pub lyrics_follow: bool,
     pub lyrics_selected: usize,
     pub lyrics_offset_ms: i64,
+    /// 当前歌词所属歌曲单独保存的 offset（覆盖全局 offset）
+    pub lyrics_song_offset_ms: Option<i64>,
+    /// 歌词页原文下方显示翻译还是罗马音
+    pub lyrics_display: LyricsDisplay,
+    /// 单首歌词的行数上限（超出时截断）
+    pub lyrics_max_lines: usize,
 
     pub settings_selected: usize,
     pub settings_group_selected: usize,
@@ -282,57 +822,146 @@ impl Default for App {
         Self {
             view: View::Login,
             ui_focus: UiFocus::BodyCenter,
+            view_focus: HashMap::new(),
             help_visible: false,
+            focus_mode: false,
+            focus_mask_titles: false,
             toast: None,
             menu_visible: false,
             menu_selected: 0,
             menu_items: default_menu_items(),
+            tasks: TaskRegistry::default(),
+            tasks_visible: false,
+            tasks_selected: 0,
+            notifications: NotificationLog::default(),
+            notifications_visible: false,
+            notifications_selected: 0,
+            notification_detail: None,
+            notification_detail_scroll: 0,
+            restore_prompt: None,
+            playlist_picker: None,
+            unavailable_review: None,
+            quit_confirm: None,
+            exit_requested: false,
             login_qr_url: None,
             login_qr_ascii: None,
             login_unikey: None,
+            login_qr_created_at: None,
+            login_qr_auto_regenerations: 0,
             login_status: "按 l 生成二维码；q 退出；Ctrl+Tab 切换页面".to_owned(),
             logged_in: false,
             login_cookie_input: String::new(),
             login_cookie_input_visible: false,
+            login_phone_form: None,
             search_input: String::new(),
             search_results: Vec::new(),
             search_selected: 0,
             search_status: "输入关键词，回车搜索".to_owned(),
+            search_query: String::new(),
+            search_has_more: false,
+            search_kind: SearchKind::Songs,
+            search_albums: Vec::new(),
+            search_artists: Vec::new(),
+            search_opened: None,
+            search_collection_selected: 0,
+            search_previews: HashMap::new(),
+            search_preview_cached: None,
+            search_marked: Vec::new(),
             now_playing: None,
             play_status: "未播放".to_owned(),
             paused: false,
             play_started_at: None,
             play_total_ms: None,
             play_stream_hint: None,
+            play_buffering_status_at: None,
             play_paused_at: None,
             play_paused_accum_ms: 0,
+            play_speed: 1.0,
             pending_seek_ms: None,
+            startup_resume: None,
+            play_position_ms: None,
+            play_seek_at: None,
             play_id: None,
             play_queue: PlayQueue::new(PlayMode::ListLoop),
+            queue_collapsed: HashSet::new(),
+            queue_selected: None,
+            queue_undo: None,
+            fm: None,
             play_mode: PlayMode::ListLoop,
             volume: 1.0,
+            muted: false,
+            stop_after_current: false,
+            loop_a_ms: None,
+            loop_b_ms: None,
+            volume_step: 0.05,
             play_song_id: None,
             play_error_count: 0,
+            song_failures: SongFailures::default(),
             play_br: 999_000,
+            play_actual_br: None,
             crossfade_ms: 300,
+            normalize_volume: false,
+            eq_bass_db: 0,
+            eq_treble_db: 0,
+            cache_evicted_files: 0,
+            cache_evicted_bytes: 0,
+            cache_usage: None,
+            cached_song_ids: HashSet::new(),
+            audio_cache_dir: PathBuf::new(),
+            audio_cache_previous_dir: None,
+            seek_step_small_ms: 5_000,
+            seek_step_large_ms: 30_000,
+            pause_on_suspend: false,
+            suspend_paused: false,
+            pause_on_focus_lost: false,
+            auto_paused: false,
+            audio_focus_policy: AudioFocusPolicy::Ignore,
+            audio_focus_duck_percent: 50,
+            audio_focus: AudioFocus::Held,
+            notifications_enabled: false,
+            status_lyrics: true,
+            startup_view: StartupView::Playlists,
+            song_name_mode: SongNameMode::Original,
+            no_color: false,
+            restore_queue_on_startup: true,
+            resume_on_start: false,
+            last_view: None,
+            pane_ratios: PaneRatios::default(),
+            right_pane_visible: true,
             account_uid: None,
             account_nickname: None,
+            login_expires_at: None,
+            liked_song_ids: HashSet::new(),
+            liked_pending: Vec::new(),
             playlists: Vec::new(),
             playlists_selected: 0,
+            playlist_filter: PlaylistFilter::All,
             playlist_mode: PlaylistMode::List,
             playlist_tracks: Vec::new(),
             playlist_tracks_selected: 0,
+            playlist_tracks_missing: 0,
+            playlist_tracks_stats: None,
+            playlist_tracks_recommend: false,
+            playlist_tracks_order_keys: TrackOrderKeys::default(),
+            playlist_track_orders: HashMap::new(),
+            playlist_filter_input: String::new(),
+            playlist_filter_editing: false,
+            song_pinyin: SongPinyinCache::default(),
             playlists_status: "等待登录后加载歌单".to_owned(),
 
             playlist_preloads: HashMap::new(),
             preload_summary: String::new(),
+            preload_suspended: false,
 
             lyrics_song_id: None,
-            lyrics: Vec::new(),
+            lyrics: Arc::from([]),
             lyrics_status: "暂无歌词".to_owned(),
             lyrics_follow: true,
+            lyrics_display: LyricsDisplay::default(),
             lyrics_selected: 0,
             lyrics_offset_ms: 0,
+            lyrics_song_offset_ms: None,
+            lyrics_max_lines: crate::settings::DEFAULT_LYRICS_MAX_LINES,
 
             settings_selected: 0,
             settings_group_selected: 0,
@@ -344,6 +973,172 @@ impl Default for App {
 }
 
 impl App {
+    /// 当前页面可聚焦的区域（右栏被隐藏时不含右栏）
+    pub fn focus_regions(&self) -> impl Iterator<Item = UiFocus> + '_ {
+        focus_order(self.view)
+            .iter()
+            .copied()
+            .filter(|f| *f != UiFocus::BodyRight || self.right_pane_visible)
+    }
+
+    /// 切换页面：记住离开页面的焦点，恢复目标页面上次的焦点（首次进入用默认焦点）
+    pub fn switch_view(&mut self, view: View) {
+        if view == self.view {
+            return;
+        }
+        self.view_focus.insert(self.view, self.ui_focus);
+        self.view = view;
+        let focus = self
+            .view_focus
+            .get(&view)
+            .copied()
+            .unwrap_or_else(|| default_focus(view));
+        self.ui_focus = if self.focus_regions().any(|f| f == focus) {
+            focus
+        } else {
+            default_focus(view)
+        };
+    }
+
+    /// 实际交给音频线程的音量：静音时为 0，音频焦点降低音量时按比例减小，`volume` 本身不变
+    pub fn output_volume(&self) -> f32 {
+        if self.muted {
+            0.0
+        } else if self.audio_focus == AudioFocus::Ducked {
+            self.volume * f32::from(100 - self.audio_focus_duck_percent.min(100)) / 100.0
+        } else {
+            self.volume
+        }
+    }
+
+    /// 私人 FM 正在接管播放（用户换了播放队列后 FM 自动失效）
+    pub fn fm_active(&self) -> bool {
+        self.fm.is_some() && self.play_queue.origin() == Some(FM_QUEUE_ORIGIN)
+    }
+
+    /// 状态栏与歌词页的「下一首」：与按下「下一首」实际播放的歌曲一致
+    ///
+    /// 没有正在播放的歌曲（或 FM 缓冲区为空）时为 None，不显示；顺序播放到末尾为「无」。
+    pub fn up_next_title(&self) -> Option<String> {
+        if self.fm_active() {
+            let fm = self.fm.as_ref()?;
+            return fm
+                .buffer
+                .front()
+                .map(|s| s.display_title(self.song_name_mode));
+        }
+        self.play_queue.current_index()?;
+        Some(match self.play_queue.peek_next() {
+            Some(song) => song.display_title(self.song_name_mode),
+            None => "无".to_owned(),
+        })
+    }
+
+    /// 用户的播放队列：FM 期间为进入 FM 前暂存的队列（保存状态时使用）
+    /// 队列面板的来源分组；不足两组时为空
+    pub fn queue_group_views(&self) -> Arc<[QueueGroupView]> {
+        let groups = queue_groups::group_runs(&self.play_queue);
+        if groups.len() < 2 {
+            return Arc::from([]);
+        }
+        groups
+            .into_iter()
+            .map(|g| {
+                let first = self
+                    .play_queue
+                    .order()
+                    .get(g.range.start)
+                    .and_then(|&idx| self.play_queue.songs().get(idx));
+                QueueGroupView {
+                    label: queue_groups::origin_label(g.origin.as_deref(), first, &self.playlists),
+                    collapsed: self
+                        .queue_collapsed
+                        .contains(queue_groups::collapse_key(g.origin.as_deref())),
+                    range: g.range,
+                }
+            })
+            .collect()
+    }
+
+    pub fn user_queue(&self) -> &PlayQueue {
+        match &self.fm {
+            Some(fm) if self.fm_active() => &fm.saved_queue,
+            _ => &self.play_queue,
+        }
+    }
+
+    /// 搜索页当前显示的是专辑列表（而非歌曲或专辑曲目）
+    pub fn search_showing_albums(&self) -> bool {
+        self.search_kind == SearchKind::Albums && self.search_opened.is_none()
+    }
+
+    /// 搜索页当前显示的是歌手列表
+    pub fn search_showing_artists(&self) -> bool {
+        self.search_kind == SearchKind::Artists && self.search_opened.is_none()
+    }
+
+    /// 已打开的歌单（每日推荐不算）
+    pub fn open_playlist_id(&self) -> Option<i64> {
+        if self.playlist_tracks_recommend {
+            return None;
+        }
+        self.playlists.get(self.playlists_selected).map(|p| p.id)
+    }
+
+    /// 歌曲列表当前的显示顺序
+    pub fn playlist_tracks_order(&self) -> TrackOrder {
+        self.open_playlist_id()
+            .and_then(|id| self.playlist_track_orders.get(&id).copied())
+            .unwrap_or_default()
+    }
+
+    /// 歌单内搜索筛选后可见歌曲在 `playlist_tracks` 中的下标
+    pub fn visible_playlist_tracks(&self) -> Vec<usize> {
+        track_filter::visible_indices(
+            &self.playlist_tracks,
+            &self.playlist_filter_input,
+            &self.song_pinyin,
+        )
+    }
+
+    /// 关闭歌单内搜索并清空关键词（打开其他歌单或返回列表时调用）
+    pub fn clear_playlist_filter(&mut self) {
+        self.playlist_filter_input.clear();
+        self.playlist_filter_editing = false;
+    }
+
+    /// 记入通知历史；新条目插在最前，已选中/正在查看的条目下标随之后移
+    pub fn record_notification(&mut self, level: ToastLevel, message: &str) {
+        self.notifications.push(level, message);
+        if self.notifications_visible {
+            self.notifications_selected =
+                (self.notifications_selected + 1).min(self.notifications.len() - 1);
+        }
+        self.notification_detail = self
+            .notification_detail
+            .map(|i| i + 1)
+            .filter(|&i| i < self.notifications.len());
+    }
+
+    /// 切换专注模式；开启时收起正在显示的非错误 Toast
+    pub fn toggle_focus_mode(&mut self) {
+        self.focus_mode = !self.focus_mode;
+        if let Some(toast) = self.toast.take() {
+            self.toast = toast.filter_for_focus_mode(self.focus_mode);
+        }
+    }
+
+    /// 搜索页当前列表的长度（`search_selected` 的范围）
+    pub fn search_list_len(&self) -> usize {
+        if self.search_showing_albums() {
+            self.search_albums.len()
+        } else if self.search_showing_artists() {
+            self.search_artists.len()
+        } else {
+            self.search_results.len()
+        }
+    }
+
     pub fn can_seek(&self) -> bool {
         self.play_total_ms.is_some()
             && self
@@ -359,8 +1154,37 @@ impl App {
             self.paused,
             self.play_paused_at,
             self.play_paused_accum_ms,
+            self.play_speed,
         )
     }
+
+    /// 当前歌词实际使用的 offset：歌曲单独保存的优先，否则为全局 offset
+    pub fn lyrics_effective_offset_ms(&self) -> i64 {
+        self.lyrics_song_offset_ms.unwrap_or(self.lyrics_offset_ms)
+    }
+
+    /// 把播放进度对齐到 `position_ms`：重设计时起点并清空暂停累计
+    pub fn set_playback_position(&mut self, position_ms: u64) {
+        let now = Instant::now();
+        let wall_ms = playback_wall_ms(position_ms, self.play_speed);
+        self.play_started_at = Some(
+            now.checked_sub(std::time::Duration::from_millis(wall_ms))
+                .unwrap_or(now),
+        );
+        self.play_paused_at = self.paused.then_some(now);
+        self.play_paused_accum_ms = 0;
+    }
+
+    /// 改变播放倍速：按旧倍速结算当前进度，之后按新倍速计时
+    pub fn set_play_speed(&mut self, speed: f32) {
+        if self.play_started_at.is_some() {
+            let position_ms = self.playback_elapsed_ms();
+            self.play_speed = speed;
+            self.set_playback_position(position_ms);
+        } else {
+            self.play_speed = speed;
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -370,13 +1194,47 @@ pub struct AppSnapshot {
     pub ui_focus: UiFocus,
     pub help_visible: bool,
     pub toast: Option<Toast>,
+    pub focus_mode: bool,
     pub menu_visible: bool,
     pub menu_selected: usize,
     pub menu_items: Vec<String>,
+    pub tasks_visible: bool,
+    pub tasks_selected: usize,
+    /// 后台任务列表（仅在面板可见时填充）
+    pub tasks: Vec<BackgroundTask>,
+    pub notifications_visible: bool,
+    pub notifications_selected: usize,
+    /// 通知历史（仅在面板可见时填充）
+    pub notifications: Vec<Notification>,
+    pub notification_detail: Option<usize>,
+    pub notification_detail_scroll: usize,
+    pub restore_prompt: Option<String>,
+    pub playlist_picker: Option<PlaylistPicker>,
+    pub unavailable_review: Option<UnavailableReview>,
+    pub quit_confirm: Option<QuitConfirm>,
+    pub exit_requested: bool,
+    /// 进行中、退出前需要确认的任务数
+    pub quit_blockers: usize,
+    pub pane_ratios: PaneRatios,
     pub search_input: String,
     pub player: PlayerSnapshot,
-    pub queue: Vec<Song>,
+    /// 按播放顺序排列的队列（与 PlayQueue 共享，不随每次快照克隆）
+    pub queue: Arc<[Song]>,
     pub queue_pos: Option<usize>,
+    /// 此队列禁用了淡入淡出
+    pub queue_no_fade: bool,
+    /// 队列的来源分组；来源只有一种（或随机模式）时为空，按扁平列表显示
+    pub queue_groups: Arc<[QueueGroupView]>,
+    /// 队列面板中选中的位置（播放顺序）
+    pub queue_selected: Option<usize>,
+    /// 私人 FM 正在接管播放
+    pub fm_active: bool,
+    /// 列表中歌曲名的显示方式
+    pub song_name_mode: SongNameMode,
+    /// 设置中开启了单色显示
+    pub no_color: bool,
+    /// 歌曲播放失败记录（列表 ⚠ 标记与详情）
+    pub song_failures: SongFailures,
     pub view_state: AppViewSnapshot,
     pub keybindings: SharedKeyBindings,
 }
@@ -391,12 +1249,42 @@ pub struct PlayerSnapshot {
     pub play_stream_hint: Option<AudioStreamHint>,
     pub play_paused_at: Option<Instant>,
     pub play_paused_accum_ms: u64,
+    pub play_speed: f32,
     pub play_mode: PlayMode,
     pub volume: f32,
+    pub muted: bool,
+    pub stop_after_current: bool,
+    pub loop_a_ms: Option<u64>,
+    pub loop_b_ms: Option<u64>,
     pub play_br: i64,
+    pub play_actual_br: Option<i64>,
+    /// 当前歌曲是否在「我喜欢」中（未播放或未登录时为 None）
+    pub liked: Option<bool>,
+    pub seek_step_small_ms: u64,
+    pub seek_step_large_ms: u64,
+    /// 状态栏歌词（设置关闭或当前歌曲没有歌词时为 None）
+    pub status_lyrics: Option<StatusLyricsSnapshot>,
+    /// 专注模式下隐藏状态栏中的歌名与歌词
+    pub mask_title: bool,
+    /// 当前歌曲的歌词（不论是否开启状态栏歌词），用于进度条的段落标记
+    pub track_lyrics: Option<StatusLyricsSnapshot>,
+    /// 「下一首」预告，见 [`App::up_next_title`]
+    pub up_next: Option<String>,
+}
+
+/// 状态栏歌词所需的数据：与 App 共享同一份歌词，不随快照复制
+#[derive(Debug, Clone)]
+pub struct StatusLyricsSnapshot {
+    pub lines: Arc<[LyricLine]>,
+    pub offset_ms: i64,
 }
 
 impl PlayerSnapshot {
+    /// 服务端降级时返回实际音质（低于设置的 play_br）
+    pub fn downgraded_br(&self) -> Option<i64> {
+        self.play_actual_br.filter(|&br| br < self.play_br)
+    }
+
     pub fn can_seek(&self) -> bool {
         self.play_total_ms.is_some()
             && self
@@ -412,6 +1300,7 @@ impl PlayerSnapshot {
             self.paused,
             self.play_paused_at,
             self.play_paused_accum_ms,
+            self.play_speed,
         )
     }
 }
@@ -423,41 +1312,117 @@ pub enum AppViewSnapshot {
     Search(SearchSnapshot),
     Lyrics(LyricsSnapshot),
     Settings(SettingsSnapshot),
+    Queue(QueueSnapshot),
 }
 
 #[derive(Debug, Clone)]
 pub struct LoginSnapshot {
     pub login_qr_url: Option<String>,
     pub login_qr_ascii: Option<String>,
+    pub login_qr_created_at: Option<Instant>,
     pub login_status: String,
     pub login_cookie_input: String,
     pub login_cookie_input_visible: bool,
+    pub login_phone_form: Option<PhoneLoginSnapshot>,
 }
 
 #[derive(Debug, Clone)]
 pub struct SearchSnapshot {
+    pub search_kind: SearchKind,
     pub search_results: Vec<Song>,
+    /// 专辑列表（仅在显示专辑列表时填充）
+    pub search_albums: Vec<Album>,
+    /// 歌手列表（仅在显示歌手列表时填充）
+    pub search_artists: Vec<Artist>,
+    pub search_opened: Option<SearchOpened>,
     pub search_selected: usize,
     pub search_status: String,
+    /// 已标记歌曲的 id
+    pub search_marked: HashSet<i64>,
+    /// 选中结果的详情预览（尚未加载时为 None）
+    pub preview: Option<SongPreview>,
+    pub preview_cached: Option<bool>,
+    /// 正在播放的歌曲（列表中以 ▶ 标出）
+    pub play_song_id: Option<i64>,
+}
+
+impl SearchSnapshot {
+    /// 是否正在显示专辑列表（专辑搜索且未打开专辑）
+    pub fn showing_albums(&self) -> bool {
+        self.search_kind == SearchKind::Albums && self.search_opened.is_none()
+    }
+
+    /// 是否正在显示歌手列表（歌手搜索且未打开歌手）
+    pub fn showing_artists(&self) -> bool {
+        self.search_kind == SearchKind::Artists && self.search_opened.is_none()
+    }
+
+    /// 中间列表的长度：专辑/歌手列表或歌曲结果
+    pub fn list_len(&self) -> usize {
+        if self.showing_albums() {
+            self.search_albums.len()
+        } else if self.showing_artists() {
+            self.search_artists.len()
+        } else {
+            self.search_results.len()
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct PlaylistsSnapshot {
     pub playlist_mode: PlaylistMode,
+    /// 筛选后可见的歌单
     pub playlists: Vec<Playlist>,
+    /// `playlists`（筛选后）中的位置
     pub playlists_selected: usize,
+    pub playlist_filter: PlaylistFilter,
+    /// 歌单内搜索筛选后可见的歌曲
     pub playlist_tracks: Vec<Song>,
+    /// `playlist_tracks`（筛选后）中的位置
     pub playlist_tracks_selected: usize,
+    /// `playlist_tracks` 各项在完整歌曲列表中的下标（用于显示原序号）
+    pub playlist_tracks_index: Vec<usize>,
+    /// 完整歌曲列表的数量（筛选前）
+    pub playlist_tracks_total: usize,
+    /// 歌单内搜索关键词（为空时不筛选）
+    pub playlist_filter_input: String,
+    /// 歌单内搜索输入框已打开
+    pub playlist_filter_editing: bool,
+    pub playlist_tracks_missing: usize,
+    /// 已打开歌单的名称（歌曲模式下的标题）
+    pub playlist_tracks_name: String,
+    pub playlist_tracks_order: TrackOrder,
+    pub playlist_tracks_stats: Option<PlaylistStats>,
+    /// 已打开歌单的最近更新时间（毫秒时间戳）
+    pub playlist_tracks_updated_at: Option<i64>,
+    /// 已打开歌单的预加载完成时间（毫秒时间戳）；歌曲来自预加载缓存时显示
+    pub playlist_tracks_cached_at: Option<i64>,
+    /// 已完成预加载中存在不可用歌曲的歌单（playlist_id -> 不可用数）
+    pub playlist_missing: HashMap<i64, usize>,
+    /// 已完成预加载的歌单（playlist_id -> 预加载完成时间，毫秒时间戳）
+    pub playlist_cached_at: HashMap<i64, i64>,
+    /// 歌曲列表中已在音频缓存中的歌曲
+    pub playlist_tracks_cached: HashSet<i64>,
     pub playlists_status: String,
+    /// 正在播放的歌曲（列表中以 ▶ 标出）
+    pub play_song_id: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
 pub struct LyricsSnapshot {
-    pub lyrics: Vec<LyricLine>,
+    pub lyrics: Arc<[LyricLine]>,
     pub lyrics_status: String,
     pub lyrics_follow: bool,
     pub lyrics_selected: usize,
     pub lyrics_offset_ms: i64,
+    pub lyrics_display: LyricsDisplay,
+}
+
+/// 队列页的状态；歌曲列表与选中位置见 [`AppSnapshot::queue`] 等共享字段
+#[derive(Debug, Clone)]
+pub struct QueueSnapshot {
+    pub queue_status: String,
 }
 
 #[derive(Debug, Clone)]
@@ -465,6 +1430,10 @@ pub struct SettingsSnapshot {
     pub settings_selected: usize,
     pub settings_group_selected: usize,
     pub settings_status: String,
+    /// 全部分组标题（来自设置注册表）
+    pub settings_groups: Vec<&'static str>,
+    /// 选中分组的设置项文本
+    pub settings_items: Vec<String>,
     pub lyrics_offset_ms: i64,
     pub crossfade_ms: u64,
 }
@@ -504,6 +1473,9 @@ impl AppSnapshot {
     ///   - 典型场景：搜索结果 30 首，歌单 200 首
     ///   - Song 结构：约 50-100 字节
     ///   - 总开销：可接受范围
+    /// - 播放队列可能有上千首，改为共享 `PlayQueue` 缓存的 `Arc<[Song]>`，
+    ///   搜索框输入等不改动队列的操作不再克隆队列
+    ///   （见 `benches/reducer_input.rs`）
     ///
     /// ## 使用示例
     ///
@@ -517,6 +1489,13 @@ impl AppSnapshot {
     /// }
     /// ```
     pub fn from_app(app: &App) -> Self {
+        let track_lyrics = (!app.lyrics.is_empty()
+            && app.lyrics_song_id.is_some()
+            && app.lyrics_song_id == app.play_song_id)
+            .then(|| StatusLyricsSnapshot {
+                lines: app.lyrics.clone(),
+                offset_ms: app.lyrics_effective_offset_ms(),
+            });
         let player = PlayerSnapshot {
             now_playing: app.now_playing.clone(),
             play_status: app.play_status.clone(),
@@ -526,50 +1505,174 @@ impl AppSnapshot {
             play_stream_hint: app.play_stream_hint.clone(),
             play_paused_at: app.play_paused_at,
             play_paused_accum_ms: app.play_paused_accum_ms,
+            play_speed: app.play_speed,
             play_mode: app.play_mode,
             volume: app.volume,
+            muted: app.muted,
+            stop_after_current: app.stop_after_current,
+            loop_a_ms: app.loop_a_ms,
+            loop_b_ms: app.loop_b_ms,
             play_br: app.play_br,
+            play_actual_br: app.play_actual_br,
+            liked: app
+                .play_song_id
+                .filter(|_| app.logged_in)
+                .map(|id| app.liked_song_ids.contains(&id)),
+            seek_step_small_ms: app.seek_step_small_ms,
+            seek_step_large_ms: app.seek_step_large_ms,
+            status_lyrics: track_lyrics.clone().filter(|_| app.status_lyrics),
+            mask_title: app.focus_mode && app.focus_mask_titles,
+            track_lyrics,
+            up_next: app.up_next_title(),
         };
 
+        let visible_playlists = if matches!(app.view, View::Playlists) {
+            playlist_filter::visible_indices(&app.playlists, app.playlist_filter)
+        } else {
+            Vec::new()
+        };
+        let visible_tracks = if matches!(app.view, View::Playlists)
+            && matches!(app.playlist_mode, PlaylistMode::Tracks)
+        {
+            app.visible_playlist_tracks()
+        } else {
+            Vec::new()
+        };
         let view_state = match app.view {
             View::Login => AppViewSnapshot::Login(LoginSnapshot {
                 login_qr_url: app.login_qr_url.clone(),
                 login_qr_ascii: app.login_qr_ascii.clone(),
+                login_qr_created_at: app.login_qr_created_at,
                 login_status: app.login_status.clone(),
                 login_cookie_input: app.login_cookie_input.clone(),
                 login_cookie_input_visible: app.login_cookie_input_visible,
+                login_phone_form: app.login_phone_form.as_ref().map(PhoneLoginForm::snapshot),
             }),
             View::Playlists => AppViewSnapshot::Playlists(PlaylistsSnapshot {
                 playlist_mode: app.playlist_mode,
-                playlists: app.playlists.clone(),
-                playlists_selected: app.playlists_selected,
-                playlist_tracks: if matches!(app.playlist_mode, PlaylistMode::Tracks) {
-                    app.playlist_tracks.clone()
+                playlists: visible_playlists
+                    .iter()
+                    .map(|&i| app.playlists[i].clone())
+                    .collect(),
+                playlists_selected: visible_playlists
+                    .iter()
+                    .position(|&i| i == app.playlists_selected)
+                    .unwrap_or(0),
+                playlist_filter: app.playlist_filter,
+                playlist_tracks: visible_tracks
+                    .iter()
+                    .map(|&i| app.playlist_tracks[i].clone())
+                    .collect(),
+                playlist_tracks_selected: visible_tracks
+                    .iter()
+                    .position(|&i| i == app.playlist_tracks_selected)
+                    .unwrap_or(0),
+                playlist_tracks_index: visible_tracks,
+                playlist_tracks_total: app.playlist_tracks.len(),
+                playlist_filter_input: app.playlist_filter_input.clone(),
+                playlist_filter_editing: app.playlist_filter_editing,
+                playlist_tracks_missing: app.playlist_tracks_missing,
+                playlist_tracks_name: if app.playlist_tracks_recommend {
+                    "每日推荐".to_owned()
                 } else {
-                    Vec::new()
+                    app.playlists
+                        .get(app.playlists_selected)
+                        .map(|p| p.name.clone())
+                        .unwrap_or_default()
                 },
-                playlist_tracks_selected: app.playlist_tracks_selected,
+                playlist_tracks_order: app.playlist_tracks_order(),
+                playlist_tracks_stats: app.playlist_tracks_stats.clone(),
+                playlist_tracks_updated_at: if app.playlist_tracks_recommend {
+                    None
+                } else {
+                    app.playlists
+                        .get(app.playlists_selected)
+                        .and_then(|p| p.updated_at_ms)
+                },
+                playlist_tracks_cached_at: if app.playlist_tracks_recommend {
+                    None
+                } else {
+                    app.playlists
+                        .get(app.playlists_selected)
+                        .and_then(|p| app.playlist_preloads.get(&p.id))
+                        .filter(|p| matches!(p.status, PreloadStatus::Completed))
+                        .and_then(|p| p.completed_at_ms)
+                },
+                playlist_missing: app
+                    .playlist_preloads
+                    .iter()
+                    .filter(|(_, p)| {
+                        matches!(p.status, PreloadStatus::Completed) && p.missing_count > 0
+                    })
+                    .map(|(id, p)| (*id, p.missing_count))
+                    .collect(),
+                playlist_cached_at: app
+                    .playlist_preloads
+                    .iter()
+                    .filter(|(_, p)| matches!(p.status, PreloadStatus::Completed))
+                    .filter_map(|(id, p)| p.completed_at_ms.map(|at| (*id, at)))
+                    .collect(),
+                playlist_tracks_cached: app.cached_song_ids.clone(),
                 playlists_status: app.playlists_status.clone(),
+                play_song_id: app.play_song_id,
             }),
-            View::Search => AppViewSnapshot::Search(SearchSnapshot {
-                search_results: app.search_results.clone(),
-                search_selected: app.search_selected,
-                search_status: app.search_status.clone(),
-            }),
+            View::Search => {
+                let selected_id = if app.search_showing_albums() || app.search_showing_artists() {
+                    None
+                } else {
+                    app.search_results.get(app.search_selected).map(|s| s.id)
+                };
+                AppViewSnapshot::Search(SearchSnapshot {
+                    search_kind: app.search_kind,
+                    search_results: app.search_results.clone(),
+                    search_albums: if app.search_showing_albums() {
+                        app.search_albums.clone()
+                    } else {
+                        Vec::new()
+                    },
+                    search_artists: if app.search_showing_artists() {
+                        app.search_artists.clone()
+                    } else {
+                        Vec::new()
+                    },
+                    search_opened: app.search_opened.clone(),
+                    search_selected: app.search_selected,
+                    search_status: app.search_status.clone(),
+                    search_marked: app.search_marked.iter().map(|s| s.id).collect(),
+                    preview: selected_id.and_then(|id| app.search_previews.get(&id).cloned()),
+                    preview_cached: app
+                        .search_preview_cached
+                        .filter(|(id, _)| Some(*id) == selected_id)
+                        .map(|(_, cached)| cached),
+                    play_song_id: app.play_song_id,
+                })
+            }
             View::Lyrics => AppViewSnapshot::Lyrics(LyricsSnapshot {
                 lyrics: app.lyrics.clone(),
                 lyrics_status: app.lyrics_status.clone(),
                 lyrics_follow: app.lyrics_follow,
                 lyrics_selected: app.lyrics_selected,
-                lyrics_offset_ms: app.lyrics_offset_ms,
+                lyrics_offset_ms: app.lyrics_effective_offset_ms(),
+                lyrics_display: app.lyrics_display,
             }),
             View::Settings => AppViewSnapshot::Settings(SettingsSnapshot {
                 settings_selected: app.settings_selected,
                 settings_group_selected: app.settings_group_selected,
                 settings_status: app.settings_status.clone(),
+                settings_groups: settings_registry::groups()
+                    .iter()
+                    .map(|g| g.title)
+                    .collect(),
+                settings_items: settings_registry::groups()
+                    .get(app.settings_group_selected)
+                    .map(|g| g.items.iter().map(|item| (item.label)(app)).collect())
+                    .unwrap_or_default(),
                 lyrics_offset_ms: app.lyrics_offset_ms,
                 crossfade_ms: app.crossfade_ms,
             }),
+            View::Queue => AppViewSnapshot::Queue(QueueSnapshot {
+                queue_status: queue_status(&app.play_queue),
+            }),
         };
 
         Self {
@@ -578,15 +1681,58 @@ impl AppSnapshot {
             ui_focus: app.ui_focus,
             help_visible: app.help_visible,
             toast: app.toast.clone(),
+            focus_mode: app.focus_mode,
             menu_visible: app.menu_visible,
             menu_selected: app.menu_selected,
             menu_items: app.menu_items.clone(),
+            tasks_visible: app.tasks_visible,
+            tasks_selected: app.tasks_selected,
+            tasks: if app.tasks_visible {
+                app.tasks.tasks().to_vec()
+            } else {
+                Vec::new()
+            },
+            notifications_visible: app.notifications_visible,
+            notifications_selected: app.notifications_selected,
+            notifications: if app.notifications_visible {
+                app.notifications.entries().cloned().collect()
+            } else {
+                Vec::new()
+            },
+            notification_detail: app.notification_detail,
+            notification_detail_scroll: app.notification_detail_scroll,
+            restore_prompt: app.restore_prompt.clone(),
+            playlist_picker: app.playlist_picker.clone(),
+            unavailable_review: app.unavailable_review.clone(),
+            quit_confirm: app.quit_confirm,
+            exit_requested: app.exit_requested,
+            quit_blockers: app.tasks.quit_blockers(),
+            pane_ratios: app.pane_ratios,
             search_input: app.search_input.clone(),
             player,
             queue: app.play_queue.ordered_songs(),
             queue_pos: app.play_queue.cursor_pos(),
+            queue_no_fade: app.play_queue.no_fade(),
+            queue_groups: app.queue_group_views(),
+            queue_selected: app.queue_selected,
+            fm_active: app.fm_active(),
+            song_name_mode: app.song_name_mode,
+            no_color: app.no_color,
+            song_failures: app.song_failures.clone(),
             view_state,
             keybindings: app.keybindings.clone(),
         }
     }
 }
+
+/// 队列页状态栏：歌曲数与当前位置
+fn queue_status(queue: &PlayQueue) -> String {
+    let total = queue.order().len();
+    if total == 0 {
+        return "队列为空".to_owned();
+    }
+    match queue.cursor_pos() {
+        Some(pos) => format!("队列 {total} 首 · 正在播放第 {} 首", pos + 1),
+        None => format!("队列 {total} 首"),
+    }
+}
@@ -0,0 +1,360 @@
+use std::time::{Duration, Instant};
+
+/// 已结束任务在面板中的保留时长
+pub const TASK_RETENTION: Duration = Duration::from_secs(5 * 60);
+/// 「等待完成后退出」的最长等待时间，超时后直接退出
+pub const QUIT_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 后台长耗时操作的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskKind {
+    /// 歌单预加载
+    PlaylistPreload { playlist_id: i64 },
+    /// 音频下载（边下边播 / 写入缓存）
+    AudioDownload { song_id: i64 },
+    /// 导出歌单 CSV（补全歌曲详情并写盘）
+    CsvExport,
+    /// 下载歌曲到下载目录（获取链接、缓存并复制出去）
+    TrackExport { song_id: i64 },
+    /// 把旧缓存目录中的文件移到当前缓存目录
+    CacheMigration,
+}
+
+/// 取消任务时需要路由到的具体机制
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskCancel {
+    /// 取消指定歌单的预加载（PreloadManager::cancel_playlist）
+    Preload { playlist_id: i64 },
+    /// 停止当前播放请求（AudioCommand::Stop 会取消下载）
+    StopAudio,
+    /// 放弃进行中的 CSV 导出
+    Export,
+    /// 放弃下载到下载目录（AudioCommand::CancelExport，已开始复制的仍会完成）
+    TrackExport { song_id: i64 },
+    /// 停止移动缓存（AudioCommand::CancelCacheMigration，已移动的文件保留在新位置）
+    CacheMigration,
+}
+
+impl TaskKind {
+    pub fn cancel_action(self) -> TaskCancel {
+        match self {
+            TaskKind::PlaylistPreload { playlist_id } => TaskCancel::Preload { playlist_id },
+            TaskKind::AudioDownload { .. } => TaskCancel::StopAudio,
+            TaskKind::CsvExport => TaskCancel::Export,
+            TaskKind::TrackExport { song_id } => TaskCancel::TrackExport { song_id },
+            TaskKind::CacheMigration => TaskCancel::CacheMigration,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskKind::PlaylistPreload { .. } => "预加载",
+            TaskKind::AudioDownload { .. } => "下载",
+            TaskKind::CsvExport => "导出",
+            TaskKind::TrackExport { .. } => "下载到本地",
+            TaskKind::CacheMigration => "移动缓存",
+        }
+    }
+
+    /// 退出前需要确认的任务：中断会丢掉已下载的数据或没写出的导出；预加载只在内存中，随时可以放弃
+    pub fn blocks_quit(self) -> bool {
+        matches!(
+            self,
+            TaskKind::AudioDownload { .. } | TaskKind::CsvExport | TaskKind::TrackExport { .. }
+        )
+    }
+}
+
+/// 退出确认弹窗（有下载进行中时按 q 打开）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuitConfirm {
+    /// 打开弹窗时进行中的下载数
+    pub downloads: usize,
+    /// 选择「等待完成后退出」后的截止时间
+    pub wait_deadline: Option<Instant>,
+}
+
+impl QuitConfirm {
+    pub fn message(&self) -> String {
+        if self.wait_deadline.is_some() {
+            format!(
+                "等待 {} 个下载/导出完成后退出（最多 {} 秒）… q 立即退出 / n 取消",
+                self.downloads,
+                QUIT_WAIT_TIMEOUT.as_secs()
+            )
+        } else {
+            format!(
+                "有 {} 个下载/导出进行中，确认退出？(y/n) · w 等待完成后退出",
+                self.downloads
+            )
+        }
+    }
+
+    /// 等待已超时
+    pub fn wait_expired(&self, now: Instant) -> bool {
+        self.wait_deadline.is_some_and(|at| now >= at)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskStatus {
+    Running,
+    Completed,
+    Failed(String),
+    Cancelled,
+}
+
+#[derive(Debug, Clone)]
+pub struct BackgroundTask {
+    pub id: u64,
+    pub kind: TaskKind,
+    pub title: String,
+    pub done: u64,
+    pub total: Option<u64>,
+    pub status: TaskStatus,
+    pub started_at: Instant,
+    pub finished_at: Option<Instant>,
+}
+
+impl BackgroundTask {
+    pub fn is_running(&self) -> bool {
+        matches!(self.status, TaskStatus::Running)
+    }
+}
+
+/// 后台任务登记表
+///
+/// 长耗时操作在开始时登记，随后推送进度并在结束时标记完成/失败/取消。
+/// 已结束的任务保留 [`TASK_RETENTION`] 供面板回看，之后被清理。
+#[derive(Debug, Clone, Default)]
+pub struct TaskRegistry {
+    next_id: u64,
+    tasks: Vec<BackgroundTask>,
+}
+
+impl TaskRegistry {
+    /// 登记任务；同类型的任务仍在运行时复用其 id
+    pub fn register(&mut self, kind: TaskKind, title: impl Into<String>) -> u64 {
+        self.register_at(kind, title, Instant::now())
+    }
+
+    fn register_at(&mut self, kind: TaskKind, title: impl Into<String>, now: Instant) -> u64 {
+        let title = title.into();
+        if let Some(task) = self
+            .tasks
+            .iter_mut()
+            .find(|t| t.kind == kind && t.is_running())
+        {
+            task.title = title;
+            return task.id;
+        }
+
+        self.prune(now);
+        self.next_id = self.next_id.wrapping_add(1).max(1);
+        self.tasks.push(BackgroundTask {
+            id: self.next_id,
+            kind,
+            title,
+            done: 0,
+            total: None,
+            status: TaskStatus::Running,
+            started_at: now,
+            finished_at: None,
+        });
+        self.next_id
+    }
+
+    pub fn running_id(&self, kind: TaskKind) -> Option<u64> {
+        self.tasks
+            .iter()
+            .find(|t| t.kind == kind && t.is_running())
+            .map(|t| t.id)
+    }
+
+    #[cfg(test)]
+    pub fn get(&self, id: u64) -> Option<&BackgroundTask> {
+        self.tasks.iter().find(|t| t.id == id)
+    }
+
+    pub fn tasks(&self) -> &[BackgroundTask] {
+        &self.tasks
+    }
+
+    #[cfg(test)]
+    pub fn running_count(&self) -> usize {
+        self.tasks.iter().filter(|t| t.is_running()).count()
+    }
+
+    /// 进行中、退出前需要确认的任务数（见 [`TaskKind::blocks_quit`]）
+    pub fn quit_blockers(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|t| t.is_running() && t.kind.blocks_quit())
+            .count()
+    }
+
+    pub fn update_progress(&mut self, id: u64, done: u64, total: Option<u64>) {
+        if let Some(task) = self.running_mut(id) {
+            task.done = done;
+            task.total = total;
+        }
+    }
+
+    pub fn complete(&mut self, id: u64) {
+        self.finish(id, TaskStatus::Completed, Instant::now());
+    }
+
+    pub fn fail(&mut self, id: u64, message: impl Into<String>) {
+        self.finish(id, TaskStatus::Failed(message.into()), Instant::now());
+    }
+
+    pub fn cancel(&mut self, id: u64) {
+        self.finish(id, TaskStatus::Cancelled, Instant::now());
+    }
+
+    /// 清理结束超过 [`TASK_RETENTION`] 的任务
+    pub fn prune(&mut self, now: Instant) {
+        self.tasks.retain(|t| {
+            t.finished_at
+                .is_none_or(|at| now.saturating_duration_since(at) < TASK_RETENTION)
+        });
+    }
+
+    fn running_mut(&mut self, id: u64) -> Option<&mut BackgroundTask> {
+        self.tasks.iter_mut().find(|t| t.id == id && t.is_running())
+    }
+
+    fn finish(&mut self, id: u64, status: TaskStatus, now: Instant) {
+        if let Some(task) = self.running_mut(id) {
+            if matches!(status, TaskStatus::Completed)
+                && let Some(total) = task.total
+            {
+                task.done = total;
+            }
+            task.status = status;
+            task.finished_at = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn register_update_complete_lifecycle() {
+        let mut reg = TaskRegistry::default();
+        let kind = TaskKind::PlaylistPreload { playlist_id: 7 };
+        let id = reg.register(kind, "我喜欢的音乐");
+
+        assert_eq!(reg.running_id(kind), Some(id));
+        assert_eq!(
+            reg.register(kind, "我喜欢的音乐"),
+            id,
+            "运行中的同类任务应复用"
+        );
+
+        reg.update_progress(id, 30, Some(100));
+        let task = reg.get(id).expect("task");
+        assert_eq!((task.done, task.total), (30, Some(100)));
+
+        reg.complete(id);
+        let task = reg.get(id).expect("task");
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert_eq!(task.done, 100);
+        assert!(task.finished_at.is_some());
+        assert_eq!(reg.running_count(), 0);
+
+        // 已结束后再次登记应生成新任务
+        let id2 = reg.register(kind, "我喜欢的音乐");
+        assert_ne!(id2, id);
+    }
+
+    #[test]
+    fn finished_task_ignores_later_updates() {
+        let mut reg = TaskRegistry::default();
+        let id = reg.register(TaskKind::AudioDownload { song_id: 1 }, "A");
+        reg.cancel(id);
+        reg.update_progress(id, 10, Some(20));
+        reg.fail(id, "boom");
+
+        let task = reg.get(id).expect("task");
+        assert_eq!(task.status, TaskStatus::Cancelled);
+        assert_eq!(task.done, 0);
+    }
+
+    #[test]
+    fn prune_removes_tasks_finished_over_five_minutes_ago() {
+        let mut reg = TaskRegistry::default();
+        let t0 = Instant::now();
+        let old = reg.register_at(TaskKind::AudioDownload { song_id: 1 }, "old", t0);
+        let running = reg.register_at(TaskKind::AudioDownload { song_id: 2 }, "running", t0);
+        let recent = reg.register_at(TaskKind::AudioDownload { song_id: 3 }, "recent", t0);
+        reg.finish(old, TaskStatus::Completed, t0);
+        reg.finish(
+            recent,
+            TaskStatus::Failed("x".to_owned()),
+            t0 + Duration::from_secs(120),
+        );
+
+        reg.prune(t0 + TASK_RETENTION + Duration::from_secs(1));
+
+        assert!(reg.get(old).is_none(), "超过 5 分钟的任务应被清理");
+        assert!(reg.get(running).is_some(), "运行中的任务不应被清理");
+        assert!(reg.get(recent).is_some());
+    }
+
+    #[test]
+    fn cancel_action_maps_to_operation_specific_mechanism() {
+        assert_eq!(
+            TaskKind::PlaylistPreload { playlist_id: 42 }.cancel_action(),
+            TaskCancel::Preload { playlist_id: 42 }
+        );
+        assert_eq!(
+            TaskKind::AudioDownload { song_id: 1 }.cancel_action(),
+            TaskCancel::StopAudio
+        );
+    }
+
+    #[test]
+    fn only_running_downloads_and_exports_block_quit() {
+        let mut reg = TaskRegistry::default();
+        reg.register(TaskKind::PlaylistPreload { playlist_id: 1 }, "歌单");
+        assert_eq!(reg.quit_blockers(), 0, "预加载不阻止退出");
+
+        let a = reg.register(TaskKind::AudioDownload { song_id: 1 }, "A");
+        reg.register(TaskKind::AudioDownload { song_id: 2 }, "B");
+        assert_eq!(reg.quit_blockers(), 2);
+
+        reg.complete(a);
+        assert_eq!(reg.quit_blockers(), 1);
+
+        reg.register(TaskKind::CsvExport, "歌单.csv");
+        assert_eq!(
+            reg.quit_blockers(),
+            2,
+            "导出还在补拉歌曲详情时退出会丢掉导出"
+        );
+    }
+
+    #[test]
+    fn quit_wait_expires_after_deadline() {
+        let now = Instant::now();
+        let confirm = QuitConfirm {
+            downloads: 2,
+            wait_deadline: None,
+        };
+        assert_eq!(
+            confirm.message(),
+            "有 2 个下载/导出进行中，确认退出？(y/n) · w 等待完成后退出"
+        );
+        assert!(!confirm.wait_expired(now));
+
+        let waiting = QuitConfirm {
+            wait_deadline: Some(now + QUIT_WAIT_TIMEOUT),
+            ..confirm
+        };
+        assert!(!waiting.wait_expired(now));
+        assert!(waiting.wait_expired(now + QUIT_WAIT_TIMEOUT));
+    }
+}
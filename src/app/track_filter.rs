@@ -0,0 +1,92 @@
+//! 歌单内搜索：按关键词筛选已打开歌单的歌曲
+//!
+//! `App::playlist_tracks` 始终保存完整列表，`playlist_tracks_selected` 是完整列表中的下标；
+//! 筛选只影响展示与上下移动，播放时队列仍由完整列表构建。
+//! ASCII 关键词还按拼音匹配（见 `pinyin` 模块），如 `zjl` 匹配「周杰伦」。
+
+use super::pinyin::{self, SongPinyinCache};
+use crate::domain::model::Song;
+
+/// 歌名（含译名）或歌手包含关键词；`query` 已转为小写
+fn song_matches(song: &Song, query: &str) -> bool {
+    song.name.to_lowercase().contains(query)
+        || song
+            .translated
+            .as_deref()
+            .is_some_and(|a| a.to_lowercase().contains(query))
+        || song.artists.to_lowercase().contains(query)
+}
+
+/// 筛选后可见歌曲在完整列表中的下标（不区分大小写；空关键词匹配全部）
+pub fn visible_indices(tracks: &[Song], query: &str, cache: &SongPinyinCache) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    let pinyin_query = pinyin::pinyin_query(&query);
+    tracks
+        .iter()
+        .enumerate()
+        .filter(|(_, s)| {
+            query.is_empty()
+                || song_matches(s, &query)
+                || pinyin_query
+                    .as_deref()
+                    .is_some_and(|q| cache.song_matches(s, q))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// 修改关键词后的选中项（完整列表下标）
+///
+/// 原选中歌曲仍可见时保持选中，否则选中第一个可见歌曲；没有可见歌曲时保持不变。
+pub fn remap_selection(
+    tracks: &[Song],
+    selected: usize,
+    query: &str,
+    cache: &SongPinyinCache,
+) -> usize {
+    let visible = visible_indices(tracks, query, cache);
+    if visible.contains(&selected) {
+        return selected;
+    }
+    visible.first().copied().unwrap_or(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Song> {
+        vec![
+            Song::titled(1, "Yellow", "Coldplay"),
+            Song::titled(2, "晴天", "周杰伦"),
+            Song::titled(3, "Fix You", "Coldplay"),
+            Song::titled(4, "夜曲", "周杰伦"),
+        ]
+    }
+
+    #[test]
+    fn matches_name_or_artist_case_insensitively() {
+        let list = sample();
+        let cache = SongPinyinCache::default();
+        assert_eq!(visible_indices(&list, "", &cache), vec![0, 1, 2, 3]);
+        assert_eq!(visible_indices(&list, "COLD", &cache), vec![0, 2]);
+        assert_eq!(visible_indices(&list, "you", &cache), vec![2]);
+        assert_eq!(visible_indices(&list, "周杰", &cache), vec![1, 3]);
+        assert_eq!(visible_indices(&list, "曲", &cache), vec![3]);
+        assert!(visible_indices(&list, "不存在", &cache).is_empty());
+        assert_eq!(visible_indices(&list, " 晴 ", &cache), vec![1]);
+        // ASCII 关键词按拼音匹配，原文包含仍然有效
+        assert_eq!(visible_indices(&list, "zjl", &cache), vec![1, 3]);
+        assert_eq!(visible_indices(&list, "yequ", &cache), vec![3]);
+        assert_eq!(visible_indices(&list, "y", &cache), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn selection_stays_when_visible_otherwise_first_match() {
+        let list = sample();
+        let cache = SongPinyinCache::default();
+        assert_eq!(remap_selection(&list, 2, "coldplay", &cache), 2);
+        assert_eq!(remap_selection(&list, 1, "coldplay", &cache), 0);
+        assert_eq!(remap_selection(&list, 1, "不存在", &cache), 1);
+    }
+}
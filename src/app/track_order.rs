@@ -0,0 +1,246 @@
+//! 歌单歌曲的显示顺序（原始顺序 / 最近添加优先 / 按歌名 / 按歌手）
+//!
+//! `playlist_detail` 的 `trackIds` 顺序即歌单的原始顺序（创建者的自定义排序），每项的 `at`
+//! 为加入歌单的时间。打开歌单时把两者记为 [`TrackOrderKeys`]，再按该歌单选择的
+//! [`TrackOrder`] 重排 `App::playlist_tracks`；从歌曲列表开始播放时，队列沿用显示顺序。
+
+use super::pinyin;
+use crate::domain::model::Song;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TrackOrder {
+    /// 服务端返回的原始顺序
+    #[default]
+    Canonical,
+    /// 最近添加的在前；没有添加时间的歌曲排在最后
+    RecentlyAdded,
+    /// 按歌名（汉字按拼音）
+    ByName,
+    /// 按歌手，同一歌手按歌名
+    ByArtist,
+}
+
+impl TrackOrder {
+    /// `t`：原始顺序 ↔ 最近添加（按歌名 / 歌手排序时切到最近添加）
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::RecentlyAdded => Self::Canonical,
+            _ => Self::RecentlyAdded,
+        }
+    }
+
+    /// `S`：原始顺序 → 按歌名 → 按歌手 → 原始顺序（最近添加时切到按歌名）
+    pub fn cycle_sort(self) -> Self {
+        match self {
+            Self::ByName => Self::ByArtist,
+            Self::ByArtist => Self::Canonical,
+            Self::Canonical | Self::RecentlyAdded => Self::ByName,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Canonical => "原始顺序",
+            Self::RecentlyAdded => "最近添加",
+            Self::ByName => "按歌名",
+            Self::ByArtist => "按歌手",
+        }
+    }
+}
+
+/// 已打开歌单的排序依据
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackOrderKeys {
+    /// 歌曲 id -> 在原始顺序中出现的位置（重复出现的歌曲有多个）
+    positions: HashMap<i64, Vec<usize>>,
+    /// 歌曲 id -> 加入歌单的时间（毫秒时间戳）
+    added_at: HashMap<i64, i64>,
+}
+
+impl TrackOrderKeys {
+    pub fn new(ids: impl IntoIterator<Item = i64>, added_at: HashMap<i64, i64>) -> Self {
+        let mut positions: HashMap<i64, Vec<usize>> = HashMap::new();
+        for (i, id) in ids.into_iter().enumerate() {
+            positions.entry(id).or_default().push(i);
+        }
+        Self {
+            positions,
+            added_at,
+        }
+    }
+
+    /// 是否有可用于「最近添加」排序的添加时间
+    pub fn has_added_at(&self) -> bool {
+        !self.added_at.is_empty()
+    }
+
+    /// 按 `order` 重排 `songs`，返回原先 `selected` 处的歌曲的新位置
+    ///
+    /// 同一首歌重复出现时，第 n 次出现对应原始顺序中的第 n 个位置；排序是稳定的，
+    /// 不在原始顺序中的歌曲（如之后新加的）保持相对顺序排在最后。
+    pub fn sort(&self, songs: &mut Vec<Song>, order: TrackOrder, selected: usize) -> usize {
+        let mut seen: HashMap<i64, usize> = HashMap::new();
+        let mut keyed: Vec<(usize, usize, Song)> = songs
+            .drain(..)
+            .enumerate()
+            .map(|(i, song)| {
+                let nth = seen.entry(song.id).or_default();
+                let position = self
+                    .positions
+                    .get(&song.id)
+                    .and_then(|p| p.get(*nth))
+                    .copied()
+                    .unwrap_or(usize::MAX);
+                *nth += 1;
+                (i, position, song)
+            })
+            .collect();
+
+        match order {
+            TrackOrder::Canonical => keyed.sort_by_key(|&(_, position, _)| position),
+            TrackOrder::RecentlyAdded => keyed.sort_by_key(|(_, position, song)| {
+                let at = self.added_at.get(&song.id).copied();
+                (at.is_none(), Reverse(at), *position)
+            }),
+            // 原文作为次级键、原始位置作为最后的键，保证结果确定
+            TrackOrder::ByName => keyed.sort_by_cached_key(|(_, position, song)| {
+                (pinyin::sort_key(&song.name), song.name.clone(), *position)
+            }),
+            TrackOrder::ByArtist => keyed.sort_by_cached_key(|(_, position, song)| {
+                (
+                    pinyin::sort_key(&song.artists),
+                    pinyin::sort_key(&song.name),
+                    song.artists.clone(),
+                    song.name.clone(),
+                    *position,
+                )
+            }),
+        }
+
+        let mut new_selected = 0;
+        for (new, (old, _, song)) in keyed.into_iter().enumerate() {
+            if old == selected {
+                new_selected = new;
+            }
+            songs.push(song);
+        }
+        new_selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn songs(ids: &[i64]) -> Vec<Song> {
+        ids.iter()
+            .map(|&id| Song {
+                id,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn ids(songs: &[Song]) -> Vec<i64> {
+        songs.iter().map(|s| s.id).collect()
+    }
+
+    #[test]
+    fn recently_added_first_then_back_to_canonical() {
+        // 4 没有添加时间，排在最后；1 与 3 时间相同，按原始顺序
+        let keys = TrackOrderKeys::new([1, 2, 3, 4], HashMap::from([(1, 100), (2, 300), (3, 100)]));
+        let mut list = songs(&[1, 2, 3, 4]);
+
+        let selected = keys.sort(&mut list, TrackOrder::RecentlyAdded, 2);
+        assert_eq!(ids(&list), vec![2, 1, 3, 4]);
+        assert_eq!(list[selected].id, 3);
+
+        let selected = keys.sort(&mut list, TrackOrder::Canonical, selected);
+        assert_eq!(ids(&list), vec![1, 2, 3, 4]);
+        assert_eq!(selected, 2);
+    }
+
+    #[test]
+    fn duplicates_return_to_their_own_positions() {
+        let keys = TrackOrderKeys::new([7, 1, 7], HashMap::from([(1, 500), (7, 100)]));
+        let mut list = songs(&[7, 1, 7]);
+
+        let selected = keys.sort(&mut list, TrackOrder::RecentlyAdded, 2);
+        assert_eq!(ids(&list), vec![1, 7, 7]);
+        assert_eq!(selected, 2);
+
+        let selected = keys.sort(&mut list, TrackOrder::Canonical, selected);
+        assert_eq!(ids(&list), vec![7, 1, 7]);
+        assert_eq!(selected, 2);
+    }
+
+    fn named(id: i64, name: &str, artists: &str) -> Song {
+        Song {
+            id,
+            name: name.to_owned(),
+            artists: artists.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sort_by_name_and_artist_mixes_cjk_and_ascii() {
+        let mut list = vec![
+            named(1, "晴天", "周杰伦"),
+            named(2, "Yellow", "Coldplay"),
+            named(3, "安静", "周杰伦"),
+            named(4, "beautiful", "陈奕迅"),
+            named(5, "Fix You", "Coldplay"),
+            named(6, "富士山下", "陈奕迅"),
+        ];
+        let keys = TrackOrderKeys::new(ids(&list), HashMap::new());
+
+        // 安静(anjing) beautiful Fix You 富士山下(fushishanxia) 晴天(qingtian) Yellow
+        let selected = keys.sort(&mut list, TrackOrder::ByName, 0);
+        assert_eq!(ids(&list), vec![3, 4, 5, 6, 1, 2]);
+        assert_eq!(list[selected].id, 1);
+
+        // 陈奕迅(chenyixun) < Coldplay < 周杰伦(zhoujielun)，同一歌手按歌名
+        let selected = keys.sort(&mut list, TrackOrder::ByArtist, selected);
+        assert_eq!(ids(&list), vec![4, 6, 5, 2, 3, 1]);
+        assert_eq!(list[selected].id, 1);
+
+        let selected = keys.sort(&mut list, TrackOrder::Canonical, selected);
+        assert_eq!(ids(&list), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(selected, 0);
+    }
+
+    #[test]
+    fn sort_by_name_is_deterministic_for_equal_keys() {
+        // 同名歌曲按原始位置；拼音相同的不同汉字按原文排序
+        let mut list = vec![
+            named(1, "十年", "b"),
+            named(2, "十年", "a"),
+            named(3, "时年", "c"),
+        ];
+        let keys = TrackOrderKeys::new(ids(&list), HashMap::new());
+        keys.sort(&mut list, TrackOrder::ByName, 0);
+        let first = ids(&list);
+        list.reverse();
+        keys.sort(&mut list, TrackOrder::ByName, 0);
+        assert_eq!(ids(&list), first);
+        assert_eq!(&first[..2], &[1, 2]);
+    }
+
+    #[test]
+    fn toggle_and_empty_list() {
+        assert_eq!(TrackOrder::Canonical.toggle(), TrackOrder::RecentlyAdded);
+        assert_eq!(TrackOrder::RecentlyAdded.toggle(), TrackOrder::Canonical);
+        assert_eq!(TrackOrder::ByArtist.toggle(), TrackOrder::RecentlyAdded);
+        assert_eq!(
+            TrackOrder::Canonical.cycle_sort().cycle_sort().cycle_sort(),
+            TrackOrder::Canonical
+        );
+        assert_eq!(TrackOrder::RecentlyAdded.cycle_sort(), TrackOrder::ByName);
+        let keys = TrackOrderKeys::default();
+        assert!(!keys.has_added_at());
+        assert_eq!(keys.sort(&mut Vec::new(), TrackOrder::RecentlyAdded, 3), 0);
+    }
+}
@@ -0,0 +1,147 @@
+//! Audio focus: notice when other programs start playing sound (`audio-focus` feature).
+//!
+//! On Linux with PulseAudio or PipeWire (via `pipewire-pulse`) a background task
+//! follows `pactl subscribe` and, on every sink-input event, re-reads
+//! `pactl list sink-inputs`. Whenever "some other process has an uncorked output
+//! stream" flips, it sends `AppCommand::AudioFocusChanged` to the app actor; the
+//! reducer decides what to do from the `音频焦点策略` setting.
+//!
+//! The `pactl` output parsing is compiled (and tested) without the feature; builds
+//! without it, or on other platforms, start nothing.
+
+#![cfg_attr(
+    not(all(feature = "audio-focus", target_os = "linux")),
+    allow(dead_code)
+)]
+
+#[cfg(all(feature = "audio-focus", target_os = "linux"))]
+mod pulse;
+
+#[cfg(all(feature = "audio-focus", target_os = "linux"))]
+pub use pulse::start_audio_focus;
+
+/// `pactl list sink-inputs` 中的一条输出流
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SinkInput {
+    pub index: u32,
+    /// 已暂停（corked）的流不出声
+    pub corked: bool,
+    pub pid: Option<u32>,
+}
+
+impl SinkInput {
+    /// 其它进程正在出声的流
+    pub fn is_active_other(&self, own_pid: u32) -> bool {
+        !self.corked && self.pid != Some(own_pid)
+    }
+}
+
+/// `pactl subscribe` 的一行是否为输出流的新增/变化/移除事件
+pub fn is_sink_input_event(line: &str) -> bool {
+    line.starts_with("Event '") && line.contains(" on sink-input #")
+}
+
+/// 解析 `LC_ALL=C pactl list sink-inputs` 的输出
+pub fn parse_sink_inputs(text: &str) -> Vec<SinkInput> {
+    let mut inputs = Vec::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(index) = line.strip_prefix("Sink Input #") {
+            inputs.push(SinkInput {
+                index: index.trim().parse().unwrap_or_default(),
+                corked: false,
+                pid: None,
+            });
+            continue;
+        }
+        let Some(current) = inputs.last_mut() else {
+            continue;
+        };
+        if let Some(corked) = line.strip_prefix("Corked:") {
+            current.corked = corked.trim() == "yes";
+        } else if let Some(pid) = line.strip_prefix("application.process.id = ") {
+            current.pid = pid.trim().trim_matches('"').parse().ok();
+        }
+    }
+    inputs
+}
+
+/// 是否有其它进程正在播放声音
+pub fn others_playing(inputs: &[SinkInput], own_pid: u32) -> bool {
+    inputs.iter().any(|input| input.is_active_other(own_pid))
+}
+
+/// 其它程序播放状态变化时发给应用的命令
+pub fn focus_command(others_playing: bool) -> crate::messages::app::AppCommand {
+    crate::messages::app::AppCommand::AudioFocusChanged { others_playing }
+}
+
+/// Builds without the `audio-focus` feature (or outside Linux) do not listen.
+#[cfg(not(all(feature = "audio-focus", target_os = "linux")))]
+pub fn start_audio_focus(_tx_cmd: tokio::sync::mpsc::Sender<crate::messages::app::AppCommand>) {
+    tracing::trace!("当前构建未启用 audio-focus 特性（或非 Linux），不监听其它程序的声音");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = "\
+Sink Input #41
+\tDriver: protocol-native.c
+\tCorked: no
+\tProperties:
+\t\tapplication.name = \"netease-ratui\"
+\t\tapplication.process.id = \"100\"
+
+Sink Input #42
+\tDriver: protocol-native.c
+\tCorked: yes
+\tProperties:
+\t\tapplication.name = \"Firefox\"
+\t\tapplication.process.id = \"200\"
+
+Sink Input #43
+\tCorked: no
+\tProperties:
+\t\tapplication.name = \"Zoom\"
+\t\tapplication.process.id = \"300\"
+";
+
+    #[test]
+    fn parses_corked_state_and_pid() {
+        let inputs = parse_sink_inputs(LIST);
+        assert_eq!(
+            inputs,
+            vec![
+                SinkInput {
+                    index: 41,
+                    corked: false,
+                    pid: Some(100),
+                },
+                SinkInput {
+                    index: 42,
+                    corked: true,
+                    pid: Some(200),
+                },
+                SinkInput {
+                    index: 43,
+                    corked: false,
+                    pid: Some(300),
+                },
+            ]
+        );
+        assert!(others_playing(&inputs, 100));
+        // 只有自己和一个已暂停的流
+        assert!(!others_playing(&inputs[..2], 100));
+        assert!(!others_playing(&[], 100));
+    }
+
+    #[test]
+    fn recognizes_sink_input_events_only() {
+        assert!(is_sink_input_event("Event 'new' on sink-input #43"));
+        assert!(is_sink_input_event("Event 'remove' on sink-input #43"));
+        assert!(!is_sink_input_event("Event 'change' on sink #1"));
+        assert!(!is_sink_input_event("Event 'new' on client #7"));
+    }
+}
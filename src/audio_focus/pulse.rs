@@ -0,0 +1,81 @@
+//! `pactl`-based listener (`audio-focus` feature, Linux).
+//!
+//! Works with PulseAudio and with PipeWire through `pipewire-pulse`. Without `pactl`
+//! or a running sound server the task logs a warning and exits; playback is never
+//! affected.
+
+use super::{focus_command, is_sink_input_event, others_playing, parse_sink_inputs};
+use crate::messages::app::AppCommand;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+/// 在后台任务中监听其它程序的输出流；应用命令通道关闭时结束
+pub fn start_audio_focus(tx_cmd: mpsc::Sender<AppCommand>) {
+    tokio::spawn(async move {
+        if let Err(e) = run(tx_cmd).await {
+            tracing::warn!("音频焦点监听不可用（需要 pactl 与 PulseAudio/PipeWire）: {e}");
+        }
+    });
+}
+
+async fn run(tx_cmd: mpsc::Sender<AppCommand>) -> std::io::Result<()> {
+    let own_pid = std::process::id();
+    let mut child = Command::new("pactl")
+        .arg("subscribe")
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()?;
+    let Some(stdout) = child.stdout.take() else {
+        return Ok(());
+    };
+    let mut lines = BufReader::new(stdout).lines();
+
+    // 启动前已在播放的其它声音只作为基准，不触发动作
+    let mut playing = query_others_playing(own_pid).await?;
+    tracing::debug!(others_playing = playing, "🎵 [AudioFocus] 开始监听输出流");
+
+    while let Some(line) = lines.next_line().await? {
+        if !is_sink_input_event(&line) {
+            continue;
+        }
+        let now = match query_others_playing(own_pid).await {
+            Ok(now) => now,
+            Err(e) => {
+                tracing::debug!("pactl list sink-inputs 失败: {e}");
+                continue;
+            }
+        };
+        if now == playing {
+            continue;
+        }
+        playing = now;
+        tracing::debug!(others_playing = now, "🎵 [AudioFocus] 其它程序播放状态变化");
+        if tx_cmd.send(focus_command(now)).await.is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+async fn query_others_playing(own_pid: u32) -> std::io::Result<bool> {
+    let output = Command::new("pactl")
+        .args(["list", "sink-inputs"])
+        .env("LC_ALL", "C")
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "pactl 退出码 {}",
+            output.status
+        )));
+    }
+    let inputs = parse_sink_inputs(&String::from_utf8_lossy(&output.stdout));
+    Ok(others_playing(&inputs, own_pid))
+}
@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use tempfile::NamedTempFile;
 
 use super::download::{clear_dir_files, now_ms};
+use super::transfer::CacheKey;
 use crate::error::CacheError;
 
 #[derive(Debug, Serialize, Deserialize, Default)]
@@ -30,13 +31,16 @@ pub struct AudioCache {
     dirty: bool,
 }
 
+/// 默认的音频缓存目录名（位于数据目录下，可由 `audio_cache_dir` / `--cache-dir` 改到别处）
+pub const AUDIO_CACHE_DIR: &str = "audio_cache";
+
 impl AudioCache {
-    pub fn new_with_config(data_dir: &Path, max_mb: usize) -> Self {
+    pub fn new_with_config(cache_dir: &Path, max_mb: usize) -> Self {
         const INDEX_VERSION: u32 = 2;
 
         let max_bytes = (max_mb as u64).saturating_mul(1024).saturating_mul(1024);
 
-        let dir = data_dir.join("audio_cache");
+        let dir = cache_dir.to_path_buf();
         if let Err(e) = fs::create_dir_all(&dir) {
             tracing::warn!(dir = %dir.display(), err = %e, "创建音频缓存目录失败，将禁用缓存");
             return Self {
@@ -98,6 +102,44 @@ impl AudioCache {
         Some(path)
     }
 
+    /// 先按实际音质查找；未命中时按旧键（请求的音质）再查一次，命中则改名迁移到新键
+    ///
+    /// 旧版本以请求的音质作为缓存键，而非会员拿到的往往是降级后的文件，
+    /// 迁移后旧键不再存在，因此回退查找对每个文件只会发生一次。
+    pub fn lookup_path_or_migrate(
+        &mut self,
+        song_id: i64,
+        br: i64,
+        legacy_br: Option<i64>,
+    ) -> Option<PathBuf> {
+        if let Some(path) = self.lookup_path(song_id, br) {
+            return Some(path);
+        }
+        let legacy_br = legacy_br.filter(|&b| b != br)?;
+        let dir = self.dir.clone()?;
+
+        let legacy_key = cache_key(song_id, legacy_br);
+        let legacy_path = dir.join(format!("{legacy_key}.bin"));
+        if !legacy_path.exists() {
+            return None;
+        }
+
+        let key = cache_key(song_id, br);
+        let file_name = format!("{key}.bin");
+        let path = dir.join(&file_name);
+        if let Err(e) = fs::rename(&legacy_path, &path) {
+            tracing::warn!(song_id, legacy_br, br, err = %e, "迁移旧音质缓存键失败");
+            return None;
+        }
+        tracing::info!(song_id, legacy_br, br, "旧音质缓存键已迁移");
+
+        self.index.entries.remove(&legacy_key);
+        self.touch(&key, &file_name, &path);
+        self.dirty = true;
+        self.persist_index_if_dirty();
+        Some(path)
+    }
+
     pub fn commit_tmp_file(
         &mut self,
         song_id: i64,
@@ -118,7 +160,6 @@ impl AudioCache {
             .map_err(|e| CacheError::CommitTmp(format!("重命名临时文件失败: {e}")))?;
 
         self.touch(&key, &file_name, &final_path);
-        self.cleanup(Some(&final_path));
         self.dirty = true;
         self.persist_index_if_dirty();
 
@@ -137,10 +178,126 @@ impl AudioCache {
         );
     }
 
-    fn cleanup(&mut self, keep: Option<&Path>) {
+    /// 标记开始播放：刷新最近访问时间（预缓存的文件可能很久之前就下载好了）
+    pub fn touch_key(&mut self, song_id: i64, br: i64) {
         let Some(dir) = self.dir.as_ref() else {
             return;
         };
+        let key = cache_key(song_id, br);
+        let file_name = format!("{key}.bin");
+        let path = dir.join(&file_name);
+        if path.exists() {
+            self.touch(&key, &file_name, &path);
+            self.dirty = true;
+        }
+    }
+
+    /// 缓存总大小超过上限时按最近访问时间从旧到新删除，直到不超过上限
+    ///
+    /// `protected` 中的条目（正在播放、正在下载）不会被删除。返回删除的文件数与字节数。
+    pub fn evict_to_limit(&mut self, protected: &[CacheKey]) -> (usize, u64) {
+        let protected = protected
+            .iter()
+            .map(|k| cache_key(k.song_id, k.br))
+            .collect::<HashSet<_>>();
+        let evicted = self.evict_lru(|key, _| protected.contains(key));
+        if evicted.0 > 0 {
+            self.dirty = true;
+            self.persist_index_if_dirty();
+        }
+        evicted
+    }
+
+    /// 缓存中的文件数与总字节数（先丢掉文件已不存在的条目）
+    pub fn stats(&mut self) -> (usize, u64) {
+        let Some(dir) = self.dir.as_ref() else {
+            return (0, 0);
+        };
+        let before = self.index.entries.len();
+        self.index
+            .entries
+            .retain(|_, ent| dir.join(&ent.file_name).exists());
+        if self.index.entries.len() != before {
+            self.dirty = true;
+            self.persist_index_if_dirty();
+        }
+        let bytes = self.index.entries.values().map(|e| e.size_bytes).sum();
+        (self.index.entries.len(), bytes)
+    }
+
+    /// 把目录中尚未登记的缓存文件加入索引（移动旧缓存之后调用），以文件修改时间作为最近访问时间
+    pub fn adopt_untracked(&mut self) -> usize {
+        let Some(dir) = self.dir.as_ref() else {
+            return 0;
+        };
+        let Ok(rd) = fs::read_dir(dir) else {
+            return 0;
+        };
+        let mut adopted = 0;
+        for ent in rd.flatten() {
+            let Some(file_name) = ent.file_name().to_str().map(str::to_owned) else {
+                continue;
+            };
+            let Some(key) = file_name
+                .strip_suffix(".bin")
+                .filter(|k| parse_cache_key(k).is_some())
+            else {
+                continue;
+            };
+            if self.index.entries.contains_key(key) {
+                continue;
+            }
+            let Ok(md) = ent.metadata() else {
+                continue;
+            };
+            let last_access_ms = md
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0);
+            self.index.entries.insert(
+                key.to_owned(),
+                CacheEntry {
+                    file_name: file_name.clone(),
+                    size_bytes: md.len(),
+                    last_access_ms,
+                },
+            );
+            adopted += 1;
+        }
+        if adopted > 0 {
+            self.dirty = true;
+            self.persist_index_if_dirty();
+        }
+        adopted
+    }
+
+    /// `song_ids` 中已缓存的歌曲（任一不高于 `max_br` 的音质），只查索引不访问磁盘
+    pub fn cached_among(&self, song_ids: &[i64], max_br: i64) -> Vec<i64> {
+        let cached = self
+            .index
+            .entries
+            .keys()
+            .filter_map(|key| parse_cache_key(key))
+            .filter(|(_, br)| *br <= max_br)
+            .map(|(sid, _)| sid)
+            .collect::<HashSet<_>>();
+        song_ids
+            .iter()
+            .copied()
+            .filter(|id| cached.contains(id))
+            .collect()
+    }
+
+    fn cleanup(&mut self, keep: Option<&Path>) {
+        self.evict_lru(|_, path| keep.is_some_and(|kp| kp == path));
+    }
+
+    fn evict_lru(&mut self, is_kept: impl Fn(&str, &Path) -> bool) -> (usize, u64) {
+        let Some(dir) = self.dir.as_ref() else {
+            return (0, 0);
+        };
 
         // remove missing
         self.index
@@ -149,7 +306,7 @@ impl AudioCache {
 
         let mut total: u64 = self.index.entries.values().map(|e| e.size_bytes).sum();
         if total <= self.max_bytes {
-            return;
+            return (0, 0);
         }
 
         let mut entries = self
@@ -167,18 +324,34 @@ impl AudioCache {
             .collect::<Vec<_>>();
         entries.sort_by_key(|(_, ts, _, _)| *ts);
 
+        let (mut files, mut bytes) = (0, 0);
         for (k, _ts, file_name, size) in entries {
             if total <= self.max_bytes {
                 break;
             }
             let p = dir.join(&file_name);
-            if keep.is_some_and(|kp| kp == p.as_path()) {
+            if is_kept(&k, &p) {
+                continue;
+            }
+            if let Err(e) = fs::remove_file(&p) {
+                tracing::debug!(path = %p.display(), err = %e, "删除缓存文件失败");
                 continue;
             }
-            let _ = fs::remove_file(&p);
             self.index.entries.remove(&k);
             total = total.saturating_sub(size);
+            files += 1;
+            bytes += size;
+        }
+        if files > 0 {
+            tracing::info!(
+                files,
+                bytes,
+                total,
+                max_bytes = self.max_bytes,
+                "音频缓存超出上限，已按 LRU 清理"
+            );
         }
+        (files, bytes)
     }
 
     fn persist_index(&self) {
@@ -232,7 +405,8 @@ impl AudioCache {
         (files, bytes)
     }
 
-    pub fn purge_not_br(&mut self, keep_br: i64, keep: Option<&Path>) {
+    /// 清理音质高于 max_br 的缓存（服务端降级得到的低音质文件保留）
+    pub fn purge_above_br(&mut self, max_br: i64, keep: Option<&Path>) {
         let Some(dir) = self.dir.as_ref() else {
             return;
         };
@@ -245,7 +419,7 @@ impl AudioCache {
             let Some((_song_id, br)) = parse_cache_key(&key) else {
                 continue;
             };
-            if br == keep_br {
+            if br <= max_br {
                 continue;
             }
 
@@ -307,6 +481,44 @@ impl Drop for AudioCache {
     }
 }
 
+/// 音频缓存中是否有该歌曲（任意音质），不经过缓存索引直接查看文件
+pub fn has_cached_song(cache_dir: &Path, song_id: i64) -> bool {
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return false;
+    };
+    entries.flatten().any(|e| {
+        e.file_name()
+            .to_str()
+            .and_then(|name| name.strip_suffix(".bin"))
+            .and_then(parse_cache_key)
+            .is_some_and(|(sid, _)| sid == song_id)
+    })
+}
+
+/// 音频缓存中所有歌曲的 id（批量判断时只读一次目录）
+pub fn cached_song_ids(cache_dir: &Path) -> HashSet<i64> {
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return HashSet::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|e| {
+            e.file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(".bin"))
+                .and_then(parse_cache_key)
+                .map(|(sid, _)| sid)
+        })
+        .collect()
+}
+
+/// 是否为缓存音频文件名（`{song_id}_{br}.bin`）
+pub(super) fn is_cache_file_name(name: &str) -> bool {
+    name.strip_suffix(".bin")
+        .and_then(parse_cache_key)
+        .is_some()
+}
+
 fn cache_key(song_id: i64, br: i64) -> String {
     format!("{song_id}_{br}")
 }
@@ -325,7 +537,7 @@ mod tests {
     #[test]
     fn test_cache_new_with_dirty_flag() {
         let temp_dir = TempDir::new().unwrap();
-        let cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         assert!(!cache.dirty, "new cache should not be dirty");
     }
@@ -333,7 +545,7 @@ mod tests {
     #[test]
     fn test_lookup_path_sets_dirty_on_hit() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Create a test cache file
         let cache_dir = cache.cache_dir().unwrap();
@@ -352,7 +564,7 @@ mod tests {
     #[test]
     fn test_lookup_path_sets_dirty_on_miss() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Reset dirty flag
         cache.dirty = false;
@@ -366,7 +578,7 @@ mod tests {
     #[test]
     fn test_persist_index_if_dirty() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Set dirty flag
         cache.dirty = true;
@@ -382,7 +594,7 @@ mod tests {
     #[test]
     fn test_persist_index_if_dirty_when_not_dirty() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Don't set dirty flag
         assert!(!cache.dirty);
@@ -395,7 +607,7 @@ mod tests {
     #[test]
     fn test_commit_tmp_file_persists_immediately() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Create a temp file
         let tmp_file = temp_dir.path().join("tmp.bin");
@@ -423,7 +635,7 @@ mod tests {
     #[test]
     fn test_invalidate_persists_immediately() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Create a test cache file
         let cache_dir = cache.cache_dir().unwrap();
@@ -449,7 +661,7 @@ mod tests {
     #[test]
     fn test_clear_all_persists_immediately() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Create a test cache file
         let cache_dir = cache.cache_dir().unwrap();
@@ -475,7 +687,7 @@ mod tests {
     #[test]
     fn test_multiple_lookups_before_persist() {
         let temp_dir = TempDir::new().unwrap();
-        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
 
         // Create multiple test cache files
         let cache_dir = cache.cache_dir().unwrap();
@@ -504,4 +716,177 @@ mod tests {
         cache.persist_index_if_dirty();
         assert!(!cache.dirty, "dirty flag should be cleared after persist");
     }
+
+    #[test]
+    fn test_lookup_falls_back_to_requested_br_key_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
+
+        // 旧版本以请求的音质 320k 为键缓存了实际 128k 的文件
+        let tmp_file = temp_dir.path().join("tmp.bin");
+        fs::write(&tmp_file, b"legacy").unwrap();
+        cache.commit_tmp_file(1, 320_000, &tmp_file).unwrap();
+
+        let path = cache
+            .lookup_path_or_migrate(1, 128_000, Some(320_000))
+            .expect("应回退找到旧键文件");
+        assert!(path.ends_with("1_128000.bin"));
+        assert_eq!(fs::read(&path).unwrap(), b"legacy");
+
+        let cache_dir = cache.cache_dir().unwrap().to_path_buf();
+        assert!(!cache_dir.join("1_320000.bin").exists(), "旧键应已迁移");
+        assert!(cache.index.entries.contains_key("1_128000"));
+        assert!(!cache.index.entries.contains_key("1_320000"));
+
+        // 之后按新键直接命中；会员拿到真正的 320k 时不会再误用这份文件
+        assert!(cache.lookup_path_or_migrate(1, 128_000, None).is_some());
+        assert!(
+            cache
+                .lookup_path_or_migrate(1, 320_000, Some(320_000))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_purge_above_br_keeps_downgraded_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
+        for (id, br) in [(1, 128_000), (2, 320_000), (3, 999_000)] {
+            let tmp_file = temp_dir.path().join("tmp.bin");
+            fs::write(&tmp_file, b"x").unwrap();
+            cache.commit_tmp_file(id, br, &tmp_file).unwrap();
+        }
+
+        cache.purge_above_br(320_000, None);
+
+        assert!(cache.lookup_path(1, 128_000).is_some());
+        assert!(cache.lookup_path(2, 320_000).is_some());
+        assert!(cache.lookup_path(3, 999_000).is_none());
+    }
+
+    #[test]
+    fn test_has_cached_song_matches_any_br() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(
+            !has_cached_song(&temp_dir.path().join(AUDIO_CACHE_DIR), 1),
+            "缓存目录不存在"
+        );
+
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 100);
+        let tmp_file = temp_dir.path().join("tmp.bin");
+        fs::write(&tmp_file, b"x").unwrap();
+        cache.commit_tmp_file(12, 320_000, &tmp_file).unwrap();
+
+        assert!(has_cached_song(&temp_dir.path().join(AUDIO_CACHE_DIR), 12));
+        assert!(
+            !has_cached_song(&temp_dir.path().join(AUDIO_CACHE_DIR), 1),
+            "12_ 不应匹配 id 1"
+        );
+        assert!(!has_cached_song(
+            &temp_dir.path().join(AUDIO_CACHE_DIR),
+            120
+        ));
+        assert_eq!(
+            cached_song_ids(&temp_dir.path().join(AUDIO_CACHE_DIR)),
+            HashSet::from([12])
+        );
+    }
+
+    /// 写入 `sizes` 指定大小的缓存文件，最近访问时间依次递增（第一个最旧）
+    fn fill_cache(cache: &mut AudioCache, tmp_dir: &Path, sizes: &[(i64, usize)]) {
+        for (i, &(id, size)) in sizes.iter().enumerate() {
+            let tmp_file = tmp_dir.join("tmp.bin");
+            fs::write(&tmp_file, vec![0u8; size]).unwrap();
+            cache.commit_tmp_file(id, 320_000, &tmp_file).unwrap();
+            cache
+                .index
+                .entries
+                .get_mut(&cache_key(id, 320_000))
+                .unwrap()
+                .last_access_ms = 1_000 + i as u64;
+        }
+    }
+
+    fn key(song_id: i64) -> CacheKey {
+        CacheKey {
+            song_id,
+            br: 320_000,
+        }
+    }
+
+    #[test]
+    fn test_evict_to_limit_removes_least_recently_used_except_protected() {
+        const KB: usize = 1024;
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 1);
+        fill_cache(
+            &mut cache,
+            temp_dir.path(),
+            &[(1, 400 * KB), (2, 400 * KB), (3, 400 * KB), (4, 400 * KB)],
+        );
+
+        // 1 最旧但正在播放：依次删除 2、3 后降到上限以内
+        let evicted = cache.evict_to_limit(&[key(1)]);
+        assert_eq!(evicted, (2, 800 * KB as u64));
+        let dir = cache.cache_dir().unwrap().to_path_buf();
+        assert!(dir.join("1_320000.bin").exists());
+        assert!(!dir.join("2_320000.bin").exists());
+        assert!(!dir.join("3_320000.bin").exists());
+        assert!(dir.join("4_320000.bin").exists());
+        assert_eq!(cache.index.entries.len(), 2);
+
+        // 已在上限以内时不删除
+        assert_eq!(cache.evict_to_limit(&[]), (0, 0));
+    }
+
+    #[test]
+    fn test_touch_key_moves_entry_to_most_recent() {
+        const KB: usize = 1024;
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 1);
+        fill_cache(&mut cache, temp_dir.path(), &[(1, 600 * KB), (2, 600 * KB)]);
+
+        // 播放 1 之后，超出上限时删除的是 2
+        cache.touch_key(1, 320_000);
+        assert_eq!(cache.evict_to_limit(&[]), (1, 600 * KB as u64));
+        assert!(cache.lookup_path(1, 320_000).is_some());
+        assert!(cache.lookup_path(2, 320_000).is_none());
+    }
+
+    #[test]
+    fn test_stats_and_cached_lookup_use_the_index() {
+        const KB: usize = 1024;
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = AudioCache::new_with_config(&temp_dir.path().join(AUDIO_CACHE_DIR), 64);
+        fill_cache(&mut cache, temp_dir.path(), &[(1, 3 * KB), (2, 5 * KB)]);
+        let tmp_file = temp_dir.path().join("tmp.bin");
+        fs::write(&tmp_file, vec![0u8; KB]).unwrap();
+        cache.commit_tmp_file(3, 999_000, &tmp_file).unwrap();
+
+        assert_eq!(cache.stats(), (3, 9 * KB as u64));
+        // 3 只有无损文件，按 320k 查询不算已缓存
+        assert_eq!(cache.cached_among(&[3, 2, 4, 1], 320_000), vec![2, 1]);
+        assert_eq!(cache.cached_among(&[3, 4], 999_000), vec![3]);
+
+        // 文件被外部删除后不再计入
+        fs::remove_file(cache.lookup_path(2, 320_000).unwrap()).unwrap();
+        assert_eq!(cache.stats(), (2, 4 * KB as u64));
+    }
+
+    #[test]
+    fn test_adopt_untracked_indexes_moved_files_and_clear_keeps_other_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut cache = AudioCache::new_with_config(temp_dir.path(), 100);
+        fs::write(temp_dir.path().join("7_320000.bin"), b"abc").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), b"keep").unwrap();
+
+        assert_eq!(cache.adopt_untracked(), 1);
+        assert_eq!(cache.adopt_untracked(), 0);
+        assert_eq!(cache.stats(), (1, 3));
+
+        // 缓存目录与其它文件共用时，清空缓存只删除缓存文件
+        assert_eq!(cache.clear_all(None), (1, 3));
+        assert!(temp_dir.path().join("notes.txt").exists());
+        assert!(temp_dir.path().join("index.json").exists());
+    }
 }
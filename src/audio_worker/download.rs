@@ -6,8 +6,27 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use futures_util::StreamExt;
 use tokio::io::AsyncWriteExt;
 
+use crate::cooldown::{CooldownHandle, host_of};
 use crate::error::DownloadError;
 
+/// 为下载客户端配置代理；地址已在启动时校验，万一无效则记录警告并直连
+pub(super) fn with_proxy(
+    builder: reqwest::ClientBuilder,
+    proxy: Option<&str>,
+) -> reqwest::ClientBuilder {
+    let Some(url) = proxy else {
+        return builder;
+    };
+    match crate::netease::parse_proxy(url) {
+        Ok(proxy) => builder.proxy(proxy),
+        Err(e) => {
+            tracing::warn!(err = %e, "下载代理无效，改为直连");
+            builder
+        }
+    }
+}
+
+/// 删除目录中的缓存文件（`*.bin` 与下载中的 `*.tmp`）；缓存目录可能与其它文件共用，其余文件不动
 pub(super) fn clear_dir_files(dir: &Path, keep: Option<&Path>) -> (usize, u64) {
     let mut removed_files = 0usize;
     let mut removed_bytes = 0u64;
@@ -22,7 +41,10 @@ pub(super) fn clear_dir_files(dir: &Path, keep: Option<&Path>) -> (usize, u64) {
         if p.is_dir() {
             continue;
         }
-        if p.file_name().is_some_and(|n| n == "index.json") {
+        if !p
+            .extension()
+            .is_some_and(|ext| ext == "bin" || ext == "tmp")
+        {
             continue;
         }
         if keep.as_ref().is_some_and(|kp| kp == &p) {
@@ -46,6 +68,7 @@ pub async fn download_to_path_with_config<FP, FR>(
     out_path: &Path,
     url: &str,
     title: &str,
+    expected_bytes: Option<u64>,
     retries: u32,
     backoff_ms: u64,
     backoff_max_ms: u64,
@@ -85,6 +108,9 @@ where
             });
         }
 
+        // 内容不对时换个 attempt 也拿不到正确数据，直接交给上层重新获取链接
+        check_response_headers(&resp, expected_bytes)?;
+
         let mut file = match tokio::fs::File::create(out_path).await {
             Ok(f) => f,
             Err(e) => {
@@ -102,6 +128,7 @@ where
 
         let total_bytes = resp.content_length().filter(|bytes| *bytes > 0);
         let mut downloaded_bytes = 0u64;
+        let mut sniffer = AudioSniffer::default();
         on_progress(downloaded_bytes, total_bytes);
 
         let mut stream = resp.bytes_stream();
@@ -109,6 +136,11 @@ where
         while let Some(chunk) = stream.next().await {
             match chunk {
                 Ok(bytes) => {
+                    if let Err(e) = sniffer.feed(&bytes) {
+                        drop(file);
+                        let _ = tokio::fs::remove_file(out_path).await;
+                        return Err(e);
+                    }
                     if let Err(e) = file.write_all(&bytes).await {
                         failed = Some(DownloadError::Write {
                             title: title.to_string(),
@@ -135,6 +167,16 @@ where
             });
         }
 
+        if failed.is_none()
+            && let Err(e) = sniffer
+                .finish()
+                .and_then(|()| check_body_size(downloaded_bytes, expected_bytes))
+        {
+            drop(file);
+            let _ = tokio::fs::remove_file(out_path).await;
+            return Err(e);
+        }
+
         if let Some(err) = failed {
             if attempt < retries {
                 on_retry(attempt + 1);
@@ -156,6 +198,7 @@ pub async fn download_to_path_for_streaming_with_config<FP, FR>(
     out_path: &Path,
     url: &str,
     title: &str,
+    expected_bytes: Option<u64>,
     retries: u32,
     backoff_ms: u64,
     backoff_max_ms: u64,
@@ -196,6 +239,9 @@ where
             });
         }
 
+        // 内容不对时换个 attempt 也拿不到正确数据，直接交给上层重新获取链接
+        check_response_headers(&resp, expected_bytes)?;
+
         let mut file = match tokio::fs::File::create(out_path).await {
             Ok(f) => f,
             Err(e) => {
@@ -214,6 +260,7 @@ where
         let total_bytes = resp.content_length().filter(|bytes| *bytes > 0);
         let mut downloaded_bytes = 0u64;
         let mut started_streaming = false;
+        let mut sniffer = AudioSniffer::default();
         on_progress(downloaded_bytes, total_bytes);
 
         let mut stream = resp.bytes_stream();
@@ -221,6 +268,11 @@ where
         while let Some(chunk) = stream.next().await {
             match chunk {
                 Ok(bytes) => {
+                    if let Err(e) = sniffer.feed(&bytes) {
+                        drop(file);
+                        let _ = tokio::fs::remove_file(out_path).await;
+                        return Err(e);
+                    }
                     if let Err(e) = file.write_all(&bytes).await {
                         failed = Some(DownloadError::Write {
                             title: title.to_string(),
@@ -229,8 +281,11 @@ where
                         break;
                     }
                     downloaded_bytes = downloaded_bytes.saturating_add(bytes.len() as u64);
-                    started_streaming = started_streaming || downloaded_bytes > 0;
-                    on_progress(downloaded_bytes, total_bytes);
+                    // 文件头校验通过前不向播放端暴露数据
+                    if sniffer.is_verified() {
+                        started_streaming = true;
+                        on_progress(downloaded_bytes, total_bytes);
+                    }
                 }
                 Err(e) => {
                     failed = Some(DownloadError::Http(e));
@@ -248,6 +303,16 @@ where
             });
         }
 
+        if failed.is_none()
+            && let Err(e) = sniffer
+                .finish()
+                .and_then(|()| check_body_size(downloaded_bytes, expected_bytes))
+        {
+            drop(file);
+            let _ = tokio::fs::remove_file(out_path).await;
+            return Err(e);
+        }
+
         if let Some(err) = failed {
             if attempt < retries && !started_streaming {
                 on_retry(attempt + 1);
@@ -257,12 +322,32 @@ where
             return Err(err);
         }
 
+        if !started_streaming {
+            on_progress(downloaded_bytes, total_bytes);
+        }
         return Ok(());
     }
 
     Ok(())
 }
 
+/// 按下载结果更新 host 冷却状态；因此进入冷却时返回 host 与冷却时长
+pub(super) fn track_cooldown(
+    cooldown: &CooldownHandle,
+    url: &str,
+    err: Option<&DownloadError>,
+) -> Option<(String, std::time::Duration)> {
+    let host = host_of(url)?;
+    match err {
+        None => {
+            cooldown.record_success(&host);
+            None
+        }
+        Some(e) if e.is_rate_limited() => cooldown.record_failure(&host).map(|d| (host, d)),
+        Some(_) => None,
+    }
+}
+
 pub(super) fn now_ms() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -270,6 +355,112 @@ pub(super) fn now_ms() -> u64 {
         .as_millis() as u64
 }
 
+/// 嗅探文件头所需的字节数（`ftyp` 位于偏移 4..8）
+const SNIFF_LEN: usize = 12;
+/// 与 SongUrl 返回的 size 相比允许的偏差（10%）
+const SIZE_TOLERANCE_DIVISOR: u64 = 10;
+
+/// 校验响应头：Content-Type 必须是音频（或 octet-stream），Content-Length 与预期大小相符
+fn check_response_headers(
+    resp: &reqwest::Response,
+    expected_bytes: Option<u64>,
+) -> Result<(), DownloadError> {
+    if let Some(ct) = resp
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        && !is_audio_content_type(ct)
+    {
+        return Err(DownloadError::InvalidContent(format!(
+            "Content-Type 为 {ct}"
+        )));
+    }
+    if let Some(len) = resp.content_length() {
+        check_body_size(len, expected_bytes)?;
+    }
+    Ok(())
+}
+
+fn is_audio_content_type(content_type: &str) -> bool {
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime.starts_with("audio/")
+        || mime.ends_with("/octet-stream")
+        || mime == "application/ogg"
+        || mime == "video/mp4"
+}
+
+fn check_body_size(actual: u64, expected_bytes: Option<u64>) -> Result<(), DownloadError> {
+    let Some(expected) = expected_bytes.filter(|b| *b > 0) else {
+        return Ok(());
+    };
+    if actual.abs_diff(expected) > expected / SIZE_TOLERANCE_DIVISOR {
+        return Err(DownloadError::InvalidContent(format!(
+            "大小 {actual} 字节，预期约 {expected} 字节"
+        )));
+    }
+    Ok(())
+}
+
+/// 判断文件头是否为已知音频格式
+fn is_audio_magic(head: &[u8]) -> bool {
+    head.starts_with(b"ID3")
+        || head.starts_with(b"fLaC")
+        || head.starts_with(b"OggS")
+        || head.get(4..8) == Some(b"ftyp".as_slice())
+        // 无 ID3 标签的 MP3/AAC(ADTS)：以帧同步字开头
+        || (head.len() >= 2 && head[0] == 0xFF && head[1] & 0xE0 == 0xE0)
+}
+
+/// 累积响应开头的字节并校验音频文件头
+#[derive(Default)]
+struct AudioSniffer {
+    head: Vec<u8>,
+    verified: bool,
+}
+
+impl AudioSniffer {
+    fn feed(&mut self, bytes: &[u8]) -> Result<(), DownloadError> {
+        if self.verified {
+            return Ok(());
+        }
+        let take = SNIFF_LEN.saturating_sub(self.head.len()).min(bytes.len());
+        self.head.extend_from_slice(&bytes[..take]);
+        if self.head.len() >= SNIFF_LEN {
+            self.verify()?;
+        }
+        Ok(())
+    }
+
+    /// 响应结束时校验（body 可能短于 [`SNIFF_LEN`]）
+    fn finish(&mut self) -> Result<(), DownloadError> {
+        if self.verified {
+            return Ok(());
+        }
+        self.verify()
+    }
+
+    fn verify(&mut self) -> Result<(), DownloadError> {
+        if !is_audio_magic(&self.head) {
+            let preview = String::from_utf8_lossy(&self.head);
+            return Err(DownloadError::InvalidContent(format!(
+                "未识别的文件头 {:?}",
+                preview.trim()
+            )));
+        }
+        self.verified = true;
+        Ok(())
+    }
+
+    fn is_verified(&self) -> bool {
+        self.verified
+    }
+}
+
 fn is_retryable_status(status: StatusCode) -> bool {
     status == StatusCode::REQUEST_TIMEOUT
         || status == StatusCode::TOO_MANY_REQUESTS
@@ -286,3 +477,164 @@ async fn sleep_backoff(attempt: u32, base_ms: u64, max_ms: u64) {
 
     tokio::time::sleep(std::time::Duration::from_millis(ms)).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp3_body(len: usize) -> Vec<u8> {
+        let mut body = b"ID3\x04\x00\x00\x00\x00\x00\x00".to_vec();
+        body.resize(len, 0x55);
+        body
+    }
+
+    async fn download(
+        server_url: &str,
+        out_path: &Path,
+        expected_bytes: Option<u64>,
+    ) -> Result<(), DownloadError> {
+        download_to_path_with_config(
+            &reqwest::Client::new(),
+            out_path,
+            &format!("{server_url}/song.mp3"),
+            "Test",
+            expected_bytes,
+            2,
+            1,
+            1,
+            |_, _| {},
+            |_| {},
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn html_error_page_is_rejected_without_retry() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/song.mp3")
+            .with_status(200)
+            .with_header("content-type", "text/html; charset=utf-8")
+            .with_body("<html><body>403 Forbidden</body></html>")
+            .expect(1)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("a.tmp");
+
+        let err = download(&server.url(), &out, None).await.unwrap_err();
+
+        assert!(err.is_stale_url(), "{err}");
+        assert!(!out.exists(), "无效内容不应留在磁盘上");
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn body_far_smaller_than_song_url_size_is_rejected() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/song.mp3")
+            .with_status(200)
+            .with_header("content-type", "audio/mpeg")
+            .with_body(mp3_body(2_000))
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("a.tmp");
+
+        let err = download(&server.url(), &out, Some(4_000_000))
+            .await
+            .unwrap_err();
+
+        assert!(err.is_stale_url(), "{err}");
+        assert!(!out.exists());
+    }
+
+    #[tokio::test]
+    async fn octet_stream_with_json_body_is_rejected_by_magic() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/song.mp3")
+            .with_status(200)
+            .with_header("content-type", "application/octet-stream")
+            .with_body(r#"{"code":404,"msg":"expired"}"#)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("a.tmp");
+
+        let err = download(&server.url(), &out, None).await.unwrap_err();
+
+        assert!(err.is_stale_url(), "{err}");
+    }
+
+    #[tokio::test]
+    async fn valid_audio_within_size_tolerance_is_accepted() {
+        let mut server = mockito::Server::new_async().await;
+        let body = mp3_body(10_000);
+        server
+            .mock("GET", "/song.mp3")
+            .with_status(200)
+            .with_header("content-type", "audio/mpeg")
+            .with_body(&body)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let out = dir.path().join("a.tmp");
+
+        download(&server.url(), &out, Some(10_500))
+            .await
+            .expect("valid audio");
+
+        assert_eq!(std::fs::read(&out).unwrap(), body);
+    }
+
+    #[test]
+    fn audio_magic_detection() {
+        assert!(is_audio_magic(b"ID3\x03"));
+        assert!(is_audio_magic(b"fLaC\x00\x00"));
+        assert!(is_audio_magic(b"OggS\x00\x02"));
+        assert!(is_audio_magic(b"\x00\x00\x00\x20ftypM4A "));
+        assert!(is_audio_magic(&[0xFF, 0xFB, 0x90, 0x64]));
+        assert!(!is_audio_magic(b"<!DOCTYPE html>"));
+        assert!(!is_audio_magic(b"{\"code\":403}"));
+        assert!(!is_audio_magic(b""));
+    }
+
+    #[test]
+    fn size_check_allows_ten_percent_deviation() {
+        assert!(check_body_size(1_000, None).is_ok());
+        assert!(check_body_size(950, Some(1_000)).is_ok());
+        assert!(check_body_size(1_100, Some(1_000)).is_ok());
+        assert!(check_body_size(1_101, Some(1_000)).is_err());
+        assert!(check_body_size(120, Some(1_000)).is_err());
+    }
+
+    #[test]
+    fn track_cooldown_counts_only_rate_limited_errors() {
+        let cooldown = CooldownHandle::default();
+        let url = "http://m701.music.126.net/a.mp3";
+        let forbidden = || DownloadError::StatusCode {
+            status: StatusCode::FORBIDDEN,
+            url: url.to_owned(),
+        };
+
+        let not_found = DownloadError::StatusCode {
+            status: StatusCode::NOT_FOUND,
+            url: url.to_owned(),
+        };
+        for _ in 0..5 {
+            assert_eq!(track_cooldown(&cooldown, url, Some(&not_found)), None);
+        }
+
+        assert_eq!(track_cooldown(&cooldown, url, Some(&forbidden())), None);
+        assert_eq!(track_cooldown(&cooldown, url, None), None, "成功后清零");
+        assert_eq!(track_cooldown(&cooldown, url, Some(&forbidden())), None);
+        assert_eq!(track_cooldown(&cooldown, url, Some(&forbidden())), None);
+        let (host, duration) =
+            track_cooldown(&cooldown, url, Some(&forbidden())).expect("第 3 次 403 进入冷却");
+        assert_eq!(host, "m701.music.126.net");
+        assert_eq!(duration.as_secs(), 60);
+        assert!(cooldown.remaining(&host).is_some());
+    }
+}
@@ -1,31 +1,59 @@
-use crate::error::MessageError;
-use rodio::OutputStreamBuilder;
+use crate::error::{AudioErrorVariant, MessageError};
+use rodio::Sink;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::select;
 use tokio::sync::mpsc;
+use tokio::time::Instant;
 
-use super::AudioSettings;
-use super::fade::Crossfade;
+use super::export::TrackExports;
+use super::fade::{Crossfade, Transition, gapless_append_due, queued_handoff, select_transition};
 use super::messages::{
     AudioBufferState, AudioCommand, AudioEvent, AudioLoadStage, AudioStreamHint,
+    MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED,
 };
-use super::player::{PlayerState, seek_to_ms};
+use super::player::{AppendedTrack, PlayerState, seek_to_ms};
 use super::streaming::StreamingSession;
 use super::transfer::{
     CacheKey, Priority, TransferCommand, TransferConfig, TransferEvent, TransferReceiver,
     TransferSender, spawn_transfer_actor_with_config,
 };
+use super::{AudioSettings, idle_release_due};
 
 struct PendingPlay {
     token: u64,
     key: CacheKey,
     title: String,
     url: String,
+    expected_bytes: Option<u64>,
     duration_ms: Option<u64>,
     retries: u8,
     streaming_started: bool,
+    no_fade: bool,
+    gain_db: Option<f32>,
+}
+
+/// 预排的下一首（`QueueNext`）：缓存就绪后预先解码，当前歌曲结束时由引擎直接切换（不淡入淡出时提前接在当前 sink 后面）
+struct QueuedTrack {
+    token: u64,
+    key: CacheKey,
+    title: String,
+    duration_ms: Option<u64>,
+    no_fade: bool,
+    gain_db: Option<f32>,
+    prepared: Option<PreparedTrack>,
+    /// 不淡入淡出时提前接在当前 sink 后面的音源；Seek 重建 sink 后需要重新接上
+    appended: Option<AppendedTrack>,
+    /// 接上失败时不再重试，播完后直接衔接
+    append_failed: bool,
+}
+
+/// 已解码、暂停中的下一首
+struct PreparedTrack {
+    sink: Arc<Sink>,
+    path: PathBuf,
+    duration_ms: Option<u64>,
 }
 
 struct AudioEngine {
@@ -42,6 +70,37 @@ struct AudioEngine {
     current_streaming: Option<StreamingSession>,
     fading_streaming: Option<StreamingSession>,
     ended_reported_play_id: Option<u64>,
+    queued: Option<QueuedTrack>,
+    /// 当前歌曲总时长，用于判断预排歌曲何时开始淡入
+    current_duration_ms: Option<u64>,
+    /// 最近一次 Seek 的位置；Seek 会重建 sink，sink 报告的位置从 0 重新计
+    position_base_ms: u64,
+    /// `position_base_ms` 对应的 sink 位置；改变倍速时从当前位置重新起算
+    position_anchor_ms: u64,
+    /// 最近一次上报的 (play_id, 位置)；位置不变（暂停、缓冲停滞）时不重复上报
+    last_position: Option<(u64, u64)>,
+    /// 等待缓存就绪后复制到下载目录的歌曲
+    exports: TrackExports,
+    idle_release_after: Option<Duration>,
+    /// 停止后释放音频输出的时刻；暂停不算空闲
+    idle_deadline: Option<Instant>,
+}
+
+/// 播放位置的上报间隔
+const POSITION_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 把 sink 位置换算为歌曲内的位置
+///
+/// sink 报告的是实际经过的播放时长；自 `anchor_ms`（最近一次改变倍速时的 sink 位置）起按当前倍速折算，
+/// 加上此前已折算好的 `base_ms`。
+fn media_position_ms(base_ms: u64, anchor_ms: u64, sink_pos_ms: u64, speed: f32) -> u64 {
+    let played_ms = sink_pos_ms.saturating_sub(anchor_ms) as f64 * f64::from(speed);
+    base_ms.saturating_add(played_ms.round() as u64)
+}
+
+/// 歌曲内的一段时长在当前倍速下实际需要播放的时长
+fn wall_duration_ms(media_ms: u64, speed: f32) -> u64 {
+    (media_ms as f64 / f64::from(speed)).round() as u64
 }
 
 fn take_pending_play_for_token(
@@ -66,6 +125,7 @@ impl AudioEngine {
         rx_transfer: TransferReceiver,
         state: PlayerState,
         settings: AudioSettings,
+        exports: TrackExports,
     ) -> Self {
         Self {
             tx_evt,
@@ -81,12 +141,22 @@ impl AudioEngine {
             current_streaming: None,
             fading_streaming: None,
             ended_reported_play_id: None,
+            queued: None,
+            current_duration_ms: None,
+            position_base_ms: 0,
+            position_anchor_ms: 0,
+            last_position: None,
+            exports,
+            idle_release_after: settings.idle_release_after,
+            idle_deadline: None,
         }
     }
 
     async fn run(mut self) {
         let mut fade_tick = tokio::time::interval(Duration::from_millis(20));
         let mut end_tick = tokio::time::interval(Duration::from_millis(200));
+        let mut position_tick = tokio::time::interval(POSITION_INTERVAL);
+        position_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         loop {
             select! {
@@ -97,6 +167,12 @@ impl AudioEngine {
                 _ = end_tick.tick() => {
                     self.tick_end().await;
                 }
+                _ = position_tick.tick() => {
+                    self.tick_position();
+                }
+                _ = idle_release_due(self.idle_deadline) => {
+                    self.release_idle_output();
+                }
                 maybe_evt = self.rx_transfer.recv(), if !self.transfer_closed => {
                     match maybe_evt {
                         Some(evt) => self.handle_transfer_event(evt).await,
@@ -115,18 +191,39 @@ impl AudioEngine {
         }
     }
 
+    /// 停止后长时间没有新的播放：关闭音频输出，下次播放时重新打开
+    fn release_idle_output(&mut self) {
+        self.idle_deadline = None;
+        if self.pending_play.is_some() || self.state.current_sink().is_some() {
+            return;
+        }
+        self.clear_fade();
+        if self.state.release_output() {
+            tracing::info!("播放已停止，释放空闲的音频输出");
+        }
+    }
+
     fn tick_fade(&mut self) {
         if let Some(fade) = &mut self.fade
-            && fade.apply(self.state.volume())
+            && fade.apply(self.state.volume(), self.state.normalize())
         {
             self.fade = None;
             self.cancel_fading_streaming();
             if let Some(sink) = self.state.current_sink() {
-                sink.set_volume(self.state.volume());
+                sink.set_volume(self.state.output_volume());
             }
         }
     }
 
+    /// 音量、音量均衡变化后立即应用到正在播放（或淡入淡出中）的 sink
+    fn refresh_volume(&mut self) {
+        if let Some(fade) = &mut self.fade {
+            let _ = fade.apply(self.state.volume(), self.state.normalize());
+        } else if let Some(sink) = self.state.current_sink() {
+            sink.set_volume(self.state.output_volume());
+        }
+    }
+
     fn clear_fade(&mut self) {
         if let Some(fade) = self.fade.take() {
             fade.stop();
@@ -146,6 +243,70 @@ impl AudioEngine {
         }
     }
 
+    fn next_token(&mut self) -> u64 {
+        let token = self.next_token;
+        self.next_token = self.next_token.wrapping_add(1).max(1);
+        token
+    }
+
+    /// 当前 sink 的实际播放位置
+    fn position_ms(&self, sink: &Sink) -> u64 {
+        media_position_ms(
+            self.position_base_ms,
+            self.position_anchor_ms,
+            sink.get_pos().as_millis() as u64,
+            self.state.speed(),
+        )
+    }
+
+    /// 播放中上报实际位置；通道已满时丢弃，core 以下一次上报为准
+    fn tick_position(&mut self) {
+        let Some(sink) = self.state.current_sink() else {
+            return;
+        };
+        if self.state.paused() || self.appended_started(&sink) {
+            // 已接上的下一首开始出声、但还没切换 play_id 时，sink 位置属于下一首，等 tick_end 接管
+            return;
+        }
+        let play_id = self.state.play_id();
+        let position_ms = self.position_ms(&sink);
+        if self.last_position == Some((play_id, position_ms)) {
+            return;
+        }
+        self.last_position = Some((play_id, position_ms));
+        let _ = self.tx_evt.try_send(AudioEvent::Position {
+            play_id,
+            position_ms,
+        });
+    }
+
+    /// 接在当前 sink 后面的下一首是否已经开始播放
+    fn appended_started(&self, sink: &Sink) -> bool {
+        self.queued
+            .as_ref()
+            .and_then(|q| q.appended.as_ref())
+            .is_some_and(|next| next.started(sink))
+    }
+
+    /// 改变倍速：先按旧倍速结算当前位置，之后的播放按新倍速折算
+    fn set_speed(&mut self, speed: f32) {
+        let speed = speed.clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED);
+        if let Some(sink) = self.state.current_sink() {
+            self.position_base_ms = self.position_ms(&sink);
+            self.position_anchor_ms = sink.get_pos().as_millis() as u64;
+            sink.set_speed(speed);
+        }
+        if let Some(prepared) = self.queued.as_ref().and_then(|q| q.prepared.as_ref()) {
+            prepared.sink.set_speed(speed);
+        }
+        self.state.set_speed(speed);
+        tracing::debug!(
+            speed,
+            position_ms = self.position_base_ms,
+            "playback speed changed"
+        );
+    }
+
     async fn tick_end(&mut self) {
         // Poll for natural end of the *current* sink; avoids spawning an OS thread per track.
         let Some(sink) = self.state.current_sink() else {
@@ -161,7 +322,43 @@ impl AudioEngine {
             return;
         }
 
-        if sink.empty() {
+        let ended = sink.empty();
+        let position_ms = self.position_ms(&sink);
+        let remaining_ms = self
+            .current_duration_ms
+            .map(|total| wall_duration_ms(total.saturating_sub(position_ms), self.state.speed()));
+        // 有进行中的播放请求（用户已切歌）时不接管
+        if self.pending_play.is_none()
+            && let Some(queued) = self.queued.as_mut()
+            && let Some(prepared) = queued.prepared.as_ref()
+        {
+            if let Some(next) = &queued.appended {
+                if next.started(&sink) {
+                    self.start_queued(Transition::Gapless).await;
+                    return;
+                }
+            } else if !queued.append_failed
+                && gapless_append_due(self.crossfade_ms, queued.no_fade, remaining_ms)
+            {
+                match self.state.append_to_current(&prepared.path, &queued.title) {
+                    Ok(next) => {
+                        tracing::debug!(song_id = queued.key.song_id, "预排歌曲已接在当前歌曲后面");
+                        queued.appended = Some(next);
+                    }
+                    Err(e) => {
+                        tracing::warn!(song_id = queued.key.song_id, err = %e, "预排歌曲无法无缝接上");
+                        queued.append_failed = true;
+                    }
+                }
+            } else if let Some(transition) =
+                queued_handoff(self.crossfade_ms, queued.no_fade, remaining_ms, ended)
+            {
+                self.start_queued(transition).await;
+                return;
+            }
+        }
+
+        if ended {
             self.ended_reported_play_id = Some(play_id);
             tracing::debug!(play_id, "detected sink ended");
             let _ = self.tx_evt.send(AudioEvent::Ended { play_id }).await;
@@ -184,6 +381,7 @@ impl AudioEngine {
                 key,
                 downloaded_bytes,
                 total_bytes,
+                bytes_per_sec,
             } => {
                 let hint = self.progressive_loading_hint(token, downloaded_bytes, total_bytes);
                 self.emit_loading_event(
@@ -192,6 +390,7 @@ impl AudioEngine {
                     AudioLoadStage::Downloading {
                         downloaded_bytes,
                         total_bytes,
+                        bytes_per_sec,
                     },
                     hint,
                 )
@@ -219,11 +418,19 @@ impl AudioEngine {
                 key,
                 session,
             } => {
-                let Some((title, duration_ms, streaming_started)) = self
+                let Some((title, duration_ms, streaming_started, no_fade, gain_db)) = self
                     .pending_play
                     .as_ref()
                     .filter(|p| p.token == token)
-                    .map(|p| (p.title.clone(), p.duration_ms, p.streaming_started))
+                    .map(|p| {
+                        (
+                            p.title.clone(),
+                            p.duration_ms,
+                            p.streaming_started,
+                            p.no_fade,
+                            p.gain_db,
+                        )
+                    })
                 else {
                     return;
                 };
@@ -237,7 +444,8 @@ impl AudioEngine {
                     path = %session.path().display(),
                     "stream became playable"
                 );
-                match self.start_streaming_playback(&session, &title, duration_ms) {
+                match self.start_streaming_playback(&session, &title, duration_ms, no_fade, gain_db)
+                {
                     Ok(actual_duration_ms) => {
                         if let Some(pending) =
                             self.pending_play.as_mut().filter(|p| p.token == token)
@@ -246,6 +454,7 @@ impl AudioEngine {
                         }
                         self.current_streaming = Some(session.clone());
                         self.ended_reported_play_id = None;
+                        self.mark_playing(Some(key)).await;
                         let _ = self
                             .tx_evt
                             .send(AudioEvent::NowPlaying {
@@ -273,6 +482,13 @@ impl AudioEngine {
                 }
             }
             TransferEvent::Ready { token, key, path } => {
+                if self.exports.on_ready(token, path.clone(), &self.tx_evt) {
+                    return;
+                }
+                if self.queued.as_ref().is_some_and(|q| q.token == token) {
+                    self.prepare_queued(path);
+                    return;
+                }
                 if let Some(pending) = self.pending_play.as_ref()
                     && pending.token != token
                 {
@@ -319,10 +535,18 @@ impl AudioEngine {
                     )),
                 )
                 .await;
-                match self.start_playback(&key, &path, &p.title, p.duration_ms) {
+                match self.start_playback(
+                    &key,
+                    &path,
+                    &p.title,
+                    p.duration_ms,
+                    p.no_fade,
+                    p.gain_db,
+                ) {
                     Ok(duration_ms) => {
                         let total_bytes = std::fs::metadata(&path).ok().map(|meta| meta.len());
                         self.ended_reported_play_id = None;
+                        self.mark_playing(Some(key)).await;
                         let _ = self
                             .tx_evt
                             .send(AudioEvent::NowPlaying {
@@ -348,8 +572,10 @@ impl AudioEngine {
                                     token: p.token,
                                     key: p.key,
                                     url: p.url.clone(),
+                                    expected_bytes: p.expected_bytes,
                                     title: p.title.clone(),
                                     priority: Priority::High,
+                                    legacy_br: None,
                                 })
                                 .await;
                             self.pending_play = Some(p);
@@ -362,15 +588,31 @@ impl AudioEngine {
                     }
                 }
             }
-            TransferEvent::Error { token, message } => {
-                tracing::warn!(token, err = %message, "cache error");
+            TransferEvent::Error {
+                token,
+                message,
+                stale_url,
+            } => {
+                tracing::warn!(token, err = %message, stale_url, "cache error");
+                if let Some(evt) = self.exports.on_error(token, &message) {
+                    let _ = self.tx_evt.send(evt).await;
+                    return;
+                }
+                if self.queued.as_ref().is_some_and(|q| q.token == token) {
+                    // 预排失败：当前歌曲结束时照常上报 Ended，由 core 重新请求
+                    self.queued = None;
+                    return;
+                }
                 if self.pending_play.as_ref().is_some_and(|p| p.token == token) {
                     self.pending_play = None;
                     self.cancel_current_streaming();
-                    let _ = self
-                        .tx_evt
-                        .send(AudioEvent::Error(MessageError::other(message)))
-                        .await;
+                    // 链接过期：以可重试的下载错误上报，由上层重新获取播放链接
+                    let err = if stale_url {
+                        MessageError::Audio(AudioErrorVariant::Download(message))
+                    } else {
+                        MessageError::other(message)
+                    };
+                    let _ = self.tx_evt.send(AudioEvent::Error(err)).await;
                 }
             }
             TransferEvent::CacheCleared { files, bytes } => {
@@ -379,6 +621,36 @@ impl AudioEngine {
                     .send(AudioEvent::CacheCleared { files, bytes })
                     .await;
             }
+            TransferEvent::CacheEvicted { files, bytes } => {
+                let _ = self
+                    .tx_evt
+                    .send(AudioEvent::CacheEvicted { files, bytes })
+                    .await;
+            }
+            TransferEvent::CacheStats { files, bytes } => {
+                let _ = self
+                    .tx_evt
+                    .send(AudioEvent::CacheStats { files, bytes })
+                    .await;
+            }
+            TransferEvent::CachedSongs { song_ids } => {
+                let _ = self.tx_evt.send(AudioEvent::CachedSongs { song_ids }).await;
+            }
+            TransferEvent::Cooldown { duration } => {
+                let _ = self
+                    .tx_evt
+                    .send(AudioEvent::DownloadCooldown { duration })
+                    .await;
+            }
+            TransferEvent::MigrateProgress { done, total } => {
+                let _ = self
+                    .tx_evt
+                    .send(AudioEvent::CacheMigrateProgress { done, total })
+                    .await;
+            }
+            TransferEvent::Migrated(summary) => {
+                let _ = self.tx_evt.send(summary.into()).await;
+            }
         }
     }
 
@@ -438,11 +710,16 @@ impl AudioEngine {
             AudioCommand::PlayTrack {
                 id,
                 br,
+                requested_br,
                 url,
+                expected_bytes,
                 title,
                 duration_ms,
+                no_fade,
+                gain_db,
             } => {
-                tracing::info!(song_id = id, br, title = %title, "开始播放请求");
+                tracing::info!(song_id = id, br, no_fade, ?gain_db, title = %title, "开始播放请求");
+                self.idle_deadline = None;
                 if let Some(old_pending) = self.pending_play.take() {
                     tracing::debug!(
                         old_token = old_pending.token,
@@ -460,18 +737,19 @@ impl AudioEngine {
                 }
                 self.clear_fade();
 
-                let token = self.next_token;
-                self.next_token = self.next_token.wrapping_add(1).max(1);
-
+                let token = self.next_token();
                 let key = CacheKey { song_id: id, br };
                 self.pending_play = Some(PendingPlay {
                     token,
                     key,
                     title: title.clone(),
                     url: url.clone(),
+                    expected_bytes,
                     duration_ms,
                     retries: 0,
                     streaming_started: false,
+                    no_fade,
+                    gain_db,
                 });
 
                 tracing::info!(song_id = id, br, token, "request cache");
@@ -481,8 +759,10 @@ impl AudioEngine {
                         token,
                         key,
                         url,
+                        expected_bytes,
                         title,
                         priority: Priority::High,
+                        legacy_br: Some(requested_br),
                     })
                     .await;
             }
@@ -534,11 +814,14 @@ impl AudioEngine {
                 tracing::debug!(next_paused, "🎵 [AudioEngine] 发送 Paused 事件");
             }
             AudioCommand::Stop => {
+                self.discard_queued().await;
                 self.pending_play = None;
                 self.clear_fade();
                 self.cancel_current_streaming();
                 self.state.stop();
                 self.ended_reported_play_id = None;
+                self.mark_playing(None).await;
+                self.idle_deadline = self.idle_release_after.map(|after| Instant::now() + after);
                 let _ = self.tx_evt.send(AudioEvent::Stopped).await;
             }
             AudioCommand::SeekToMs(ms) => {
@@ -550,6 +833,7 @@ impl AudioEngine {
                     has_sink = self.state.current_sink().is_some(),
                     "🎵 [AudioEngine] SeekToMs"
                 );
+                let before = self.state.current_sink();
                 if let Err(e) = seek_to_ms(&mut self.state, ms) {
                     tracing::warn!(ms, err = %e, "Seek 失败");
                     let _ = self
@@ -557,23 +841,40 @@ impl AudioEngine {
                         .send(AudioEvent::Error(MessageError::other(e)))
                         .await;
                 } else {
+                    self.position_base_ms = ms;
+                    self.position_anchor_ms = 0;
                     self.ended_reported_play_id = None;
+                    // 重建 sink 后接上的下一首随旧 sink 一起停止，由 tick_end 重新接到新 sink 后面
+                    let rebuilt = match (&before, self.state.current_sink()) {
+                        (Some(before), Some(after)) => !Arc::ptr_eq(before, &after),
+                        _ => true,
+                    };
+                    if rebuilt && let Some(queued) = self.queued.as_mut() {
+                        queued.appended = None;
+                    }
                 }
             }
             AudioCommand::SetVolume(v) => {
                 self.state.set_volume(v.clamp(0.0, 2.0));
-                if let Some(fade) = &mut self.fade {
-                    let _ = fade.apply(self.state.volume());
-                } else if let Some(sink) = self.state.current_sink() {
-                    sink.set_volume(self.state.volume());
-                }
+                self.refresh_volume();
+            }
+            AudioCommand::SetVolumeNormalization(on) => {
+                self.state.set_normalize(on);
+                self.refresh_volume();
+            }
+            AudioCommand::SetPlaybackSpeed(speed) => {
+                self.set_speed(speed);
+            }
+            AudioCommand::SetEqualizer { bass_db, treble_db } => {
+                tracing::debug!(bass_db, treble_db, "equalizer changed");
+                self.state.set_eq(f32::from(bass_db), f32::from(treble_db));
             }
             AudioCommand::SetCrossfadeMs(ms) => {
                 self.crossfade_ms = ms;
                 if self.crossfade_ms == 0 {
                     self.clear_fade();
                     if let Some(sink) = self.state.current_sink() {
-                        sink.set_volume(self.state.volume());
+                        sink.set_volume(self.state.output_volume());
                     }
                 }
             }
@@ -589,77 +890,300 @@ impl AudioEngine {
             AudioCommand::SetCacheBr(br) => {
                 let _ = self
                     .tx_transfer
-                    .send(TransferCommand::PurgeNotBr {
+                    .send(TransferCommand::PurgeAboveBr {
                         br,
                         keep: self.state.path(),
                     })
                     .await;
             }
-            AudioCommand::PrefetchAudio { id, br, url, title } => {
-                tracing::info!(song_id = id, br, title = %title, "开始预缓存");
+            AudioCommand::QueryCacheStats => {
+                let _ = self.tx_transfer.send(TransferCommand::QueryStats).await;
+            }
+            AudioCommand::CancelExport { id } => {
+                for (token, key) in self.exports.cancel(id) {
+                    tracing::info!(song_id = id, token, "取消导出歌曲");
+                    let _ = self
+                        .tx_transfer
+                        .send(TransferCommand::Cancel { token, key })
+                        .await;
+                }
+            }
+            AudioCommand::MigrateCache { from } => {
+                tracing::info!(from = %from.display(), "用户触发：移动旧缓存");
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::MigrateFrom { from })
+                    .await;
+            }
+            AudioCommand::CancelCacheMigration => {
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::CancelMigration)
+                    .await;
+            }
+            AudioCommand::QueryCachedSongs { song_ids, br } => {
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::QueryCached { song_ids, br })
+                    .await;
+            }
+            AudioCommand::QueueNext {
+                id,
+                br,
+                requested_br,
+                url,
+                expected_bytes,
+                title,
+                duration_ms,
+                no_fade,
+                gain_db,
+            } => {
+                self.discard_queued().await;
+                self.idle_deadline = None;
+                let token = self.next_token();
                 let key = CacheKey { song_id: id, br };
+                tracing::info!(song_id = id, br, token, title = %title, "预排下一首");
+                self.queued = Some(QueuedTrack {
+                    token,
+                    key,
+                    title: title.clone(),
+                    duration_ms,
+                    no_fade,
+                    gain_db,
+                    prepared: None,
+                    appended: None,
+                    append_failed: false,
+                });
                 let _ = self
                     .tx_transfer
                     .send(TransferCommand::EnsureCached {
-                        token: 0,
+                        token,
                         key,
                         url,
+                        expected_bytes,
                         title,
                         priority: Priority::Low,
+                        legacy_br: Some(requested_br),
+                    })
+                    .await;
+            }
+            AudioCommand::ClearQueued => {
+                self.discard_queued().await;
+            }
+            AudioCommand::ExportTrack {
+                id,
+                br,
+                url,
+                expected_bytes,
+                tags,
+                dest,
+            } => {
+                let token = self.next_token();
+                let key = CacheKey { song_id: id, br };
+                tracing::info!(song_id = id, br, token, dest = %dest.display(), "导出歌曲");
+                let title = tags.title.clone();
+                self.exports
+                    .insert(token, CacheKey { song_id: id, br }, dest, tags);
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::EnsureCached {
+                        token,
+                        key,
+                        url,
+                        expected_bytes,
+                        title,
+                        priority: Priority::High,
+                        legacy_br: None,
                     })
                     .await;
             }
         }
     }
 
-    fn start_playback(
-        &mut self,
-        key: &CacheKey,
-        path: &std::path::Path,
-        title: &str,
-        fallback_duration_ms: Option<u64>,
-    ) -> Result<Option<u64>, String> {
-        let (sink, duration_ms) = self
+    /// 预排歌曲的缓存就绪：提前解码，切换时无需再打开文件
+    fn prepare_queued(&mut self, path: PathBuf) {
+        let Some(queued) = self.queued.as_mut() else {
+            return;
+        };
+        match self
             .state
-            .build_sink(path, None, title, fallback_duration_ms)?;
-        let sink = Arc::new(sink);
+            .build_paused_sink(&path, &queued.title, queued.duration_ms)
+        {
+            Ok((sink, duration_ms)) => {
+                tracing::info!(
+                    song_id = queued.key.song_id,
+                    path = %path.display(),
+                    "预排歌曲已就绪"
+                );
+                queued.prepared = Some(PreparedTrack {
+                    sink: Arc::new(sink),
+                    path,
+                    duration_ms,
+                });
+            }
+            Err(e) => {
+                tracing::warn!(song_id = queued.key.song_id, err = %e, "预排歌曲解码失败");
+                self.queued = None;
+            }
+        }
+    }
+
+    async fn discard_queued(&mut self) {
+        let Some(queued) = self.queued.take() else {
+            return;
+        };
+        tracing::debug!(song_id = queued.key.song_id, "丢弃预排的下一首");
+        if let Some(next) = queued.appended {
+            next.cancel();
+        }
+        if let Some(prepared) = queued.prepared {
+            prepared.sink.stop();
+        }
+        let _ = self
+            .tx_transfer
+            .send(TransferCommand::Cancel {
+                token: queued.token,
+                key: queued.key,
+            })
+            .await;
+    }
+
+    /// 告知传输层当前播放的缓存条目：刷新 LRU 时间，且清理缓存时不删除它
+    async fn mark_playing(&self, key: Option<CacheKey>) {
+        let _ = self
+            .tx_transfer
+            .send(TransferCommand::MarkPlaying { key })
+            .await;
+    }
+
+    /// 切换到预排的下一首并通知 core
+    async fn start_queued(&mut self, transition: Transition) {
+        let Some(QueuedTrack {
+            key,
+            title,
+            gain_db,
+            prepared: Some(prepared),
+            ..
+        }) = self.queued.take()
+        else {
+            return;
+        };
+        let total_bytes = std::fs::metadata(&prepared.path)
+            .ok()
+            .map(|meta| meta.len());
+        if transition == Transition::Gapless {
+            prepared.sink.stop();
+            self.adopt_appended(prepared.path, gain_db);
+        } else {
+            self.switch_sink(prepared.sink, prepared.path, true, gain_db, transition);
+        }
+        self.current_streaming = None;
+        self.current_duration_ms = prepared.duration_ms;
+        self.ended_reported_play_id = None;
+        self.mark_playing(Some(key)).await;
+        tracing::info!(song_id = key.song_id, ?transition, "无缝切换到预排的下一首");
+        let _ = self
+            .tx_evt
+            .send(AudioEvent::NowPlaying {
+                song_id: key.song_id,
+                play_id: self.state.play_id(),
+                title,
+                duration_ms: prepared.duration_ms,
+                stream_hint: AudioStreamHint::cached_file(total_bytes),
+            })
+            .await;
+    }
 
-        let has_current = self.state.current_sink().is_some();
-        let can_fade = self.crossfade_ms > 0 && has_current && !self.state.paused();
+    /// 接在当前 sink 后面的下一首已经开始播放：sink 不变，只切换到新歌的播放状态
+    fn adopt_appended(&mut self, path: PathBuf, gain_db: Option<f32>) {
+        self.position_base_ms = 0;
+        self.position_anchor_ms = 0;
+        self.clear_fade();
+        self.cancel_current_streaming();
+        self.state.next_play_id();
+        self.state.set_path(path);
+        self.state.set_seekable(true);
+        self.state.set_track_gain(gain_db);
+        if let Some(sink) = self.state.current_sink() {
+            sink.set_volume(self.state.output_volume());
+        }
+    }
 
-        if can_fade {
+    /// 用新 sink 替换当前播放：淡入淡出时旧 sink 交给 [`Crossfade`] 淡出，否则立即停止
+    fn switch_sink(
+        &mut self,
+        sink: Arc<Sink>,
+        path: PathBuf,
+        seekable: bool,
+        gain_db: Option<f32>,
+        transition: Transition,
+    ) {
+        self.position_base_ms = 0;
+        self.position_anchor_ms = 0;
+        if let Transition::Crossfade { ms } = transition {
+            self.clear_fade();
             let old = self.state.take_current_for_fade();
+            let old_gain_db = self.state.track_gain();
             self.fading_streaming = self.current_streaming.take();
             self.state.next_play_id();
-            self.state.set_path(path.to_path_buf());
-            self.state.set_seekable(true);
+            self.state.set_path(path);
+            self.state.set_seekable(seekable);
+            self.state.set_track_gain(gain_db);
             sink.set_volume(0.0);
             sink.play();
             self.state.attach_sink(Arc::clone(&sink));
             if let Some(old) = old {
-                old.set_volume(self.state.volume());
-                self.fade = Some(Crossfade::new(old, Arc::clone(&sink), self.crossfade_ms));
-                if let Some(fade) = &mut self.fade {
-                    let _ = fade.apply(self.state.volume());
-                }
+                let mut fade = Crossfade::new(old, Arc::clone(&sink), ms, old_gain_db, gain_db);
+                let _ = fade.apply(self.state.volume(), self.state.normalize());
+                self.fade = Some(fade);
             }
         } else {
             self.clear_fade();
             self.cancel_current_streaming();
             self.state.stop();
-            self.state.set_path(path.to_path_buf());
-            self.state.set_seekable(true);
+            self.state.set_path(path);
+            self.state.set_seekable(seekable);
+            self.state.set_track_gain(gain_db);
             if self.state.paused() {
                 sink.pause();
             } else {
                 sink.play();
             }
-            sink.set_volume(self.state.volume());
-            self.state.attach_sink(Arc::clone(&sink));
+            sink.set_volume(self.state.output_volume());
+            self.state.attach_sink(sink);
         }
+    }
+
+    fn start_playback(
+        &mut self,
+        key: &CacheKey,
+        path: &std::path::Path,
+        title: &str,
+        fallback_duration_ms: Option<u64>,
+        no_fade: bool,
+        gain_db: Option<f32>,
+    ) -> Result<Option<u64>, String> {
+        let (sink, duration_ms) = self
+            .state
+            .build_sink(path, None, title, fallback_duration_ms)?;
+        let sink = Arc::new(sink);
+
+        let transition = select_transition(
+            self.crossfade_ms,
+            no_fade,
+            self.state.current_sink().is_some(),
+            self.state.paused(),
+        );
+        self.switch_sink(sink, path.to_path_buf(), true, gain_db, transition);
         self.current_streaming = None;
+        self.current_duration_ms = duration_ms;
 
-        tracing::debug!(song_id = key.song_id, path = %path.display(), "start playback");
+        tracing::debug!(
+            song_id = key.song_id,
+            path = %path.display(),
+            ?transition,
+            "start playback"
+        );
         Ok(duration_ms)
     }
 
@@ -668,48 +1192,35 @@ impl AudioEngine {
         session: &StreamingSession,
         title: &str,
         fallback_duration_ms: Option<u64>,
+        no_fade: bool,
+        gain_db: Option<f32>,
     ) -> Result<Option<u64>, String> {
         let (sink, duration_ms) =
             self.state
                 .build_streaming_sink(session, title, fallback_duration_ms)?;
         let sink = Arc::new(sink);
 
-        let has_current = self.state.current_sink().is_some();
-        let can_fade = self.crossfade_ms > 0 && has_current && !self.state.paused();
-
-        if can_fade {
-            let old = self.state.take_current_for_fade();
-            self.fading_streaming = self.current_streaming.take();
-            self.state.next_play_id();
-            self.state.set_path(session.path().to_path_buf());
-            self.state.set_seekable(false);
-            sink.set_volume(0.0);
-            sink.play();
-            self.state.attach_sink(Arc::clone(&sink));
-            if let Some(old) = old {
-                old.set_volume(self.state.volume());
-                self.fade = Some(Crossfade::new(old, Arc::clone(&sink), self.crossfade_ms));
-                if let Some(fade) = &mut self.fade {
-                    let _ = fade.apply(self.state.volume());
-                }
-            }
-        } else {
-            self.clear_fade();
-            self.cancel_current_streaming();
-            self.state.stop();
-            self.state.set_path(session.path().to_path_buf());
-            self.state.set_seekable(false);
-            if self.state.paused() {
-                sink.pause();
-            } else {
-                sink.play();
-            }
-            sink.set_volume(self.state.volume());
-            self.state.attach_sink(Arc::clone(&sink));
-        }
+        let transition = select_transition(
+            self.crossfade_ms,
+            no_fade,
+            self.state.current_sink().is_some(),
+            self.state.paused(),
+        );
+        self.switch_sink(
+            sink,
+            session.path().to_path_buf(),
+            false,
+            gain_db,
+            transition,
+        );
         self.current_streaming = Some(session.clone());
+        self.current_duration_ms = duration_ms;
 
-        tracing::debug!(path = %session.path().display(), "start streaming playback");
+        tracing::debug!(
+            path = %session.path().display(),
+            ?transition,
+            "start streaming playback"
+        );
         Ok(duration_ms)
     }
 }
@@ -717,7 +1228,7 @@ impl AudioEngine {
 #[cfg(test)]
 #[allow(clippy::items_after_test_module)]
 mod tests {
-    use super::{PendingPlay, take_pending_play_for_token};
+    use super::{PendingPlay, media_position_ms, take_pending_play_for_token, wall_duration_ms};
     use crate::audio_worker::transfer::CacheKey;
 
     #[test]
@@ -730,9 +1241,12 @@ mod tests {
             },
             title: "B".to_owned(),
             url: "https://example.com/b.mp3".to_owned(),
+            expected_bytes: None,
             duration_ms: Some(180_000),
             retries: 0,
             streaming_started: false,
+            no_fade: false,
+            gain_db: None,
         });
 
         let taken = take_pending_play_for_token(&mut pending_play, 1);
@@ -742,12 +1256,39 @@ mod tests {
         assert_eq!(pending.token, 2);
         assert_eq!(pending.key.song_id, 200);
     }
+
+    #[test]
+    fn media_position_scales_sink_position_by_speed() {
+        assert_eq!(media_position_ms(0, 0, 10_000, 1.0), 10_000);
+        assert_eq!(media_position_ms(0, 0, 10_000, 1.25), 12_500);
+        assert_eq!(media_position_ms(30_000, 0, 10_000, 0.5), 35_000);
+    }
+
+    #[test]
+    fn speed_change_mid_track_keeps_position_before_the_change() {
+        // 1.0x 播放 20s 后改为 2.0x：此前的 20s 不再按新倍速折算
+        let base = media_position_ms(0, 0, 20_000, 1.0);
+        let anchor = 20_000;
+        assert_eq!(media_position_ms(base, anchor, 20_000, 2.0), 20_000);
+        assert_eq!(media_position_ms(base, anchor, 25_000, 2.0), 30_000);
+
+        // 再改回 0.75x
+        let base = media_position_ms(base, anchor, 25_000, 2.0);
+        assert_eq!(media_position_ms(base, 25_000, 29_000, 0.75), 33_000);
+    }
+
+    #[test]
+    fn remaining_media_time_converts_to_wall_time() {
+        assert_eq!(wall_duration_ms(3_000, 1.0), 3_000);
+        assert_eq!(wall_duration_ms(3_000, 2.0), 1_500);
+        assert_eq!(wall_duration_ms(3_000, 0.5), 6_000);
+    }
 }
 
 pub(super) fn spawn(
     rx_cmd: mpsc::Receiver<AudioCommand>,
     tx_evt: mpsc::Sender<AudioEvent>,
-    data_dir: PathBuf,
+    cache_dir: PathBuf,
     transfer_config: TransferConfig,
     settings: AudioSettings,
 ) {
@@ -769,8 +1310,8 @@ pub(super) fn spawn(
         //    - panic 是合理的系统级失败响应
         //
         // 3. **真正的风险已处理**:
-        //    - `OutputStreamBuilder::open_default_stream()` 可能失败（无音频设备）
-        //    - 已通过 `match` 处理，发送 `AudioEvent::Error` 给 UI
+        //    - `PlayerState::open_output()` 可能失败（无音频设备）
+        //    - 失败时发送 `AudioEvent::Error` 给 UI
         //    - 用户会看到友好的错误消息
         //
         // 4. **未来改进方向**:
@@ -783,28 +1324,38 @@ pub(super) fn spawn(
             .expect("tokio runtime: 系统资源不足或配置错误");
         let local = tokio::task::LocalSet::new();
         local.block_on(&rt, async move {
+            let exports = TrackExports::new(transfer_config.proxy.as_deref());
             let (tx_transfer, rx_transfer) =
-                spawn_transfer_actor_with_config(data_dir.clone(), transfer_config);
-
-            let stream = match OutputStreamBuilder::open_default_stream() {
-                Ok(v) => v,
-                Err(e) => {
-                    tracing::error!(err = %e, "初始化音频输出失败");
-                    let _ = tx_evt
-                        .send(AudioEvent::Error(MessageError::other(format!(
-                            "初始化音频输出失败: {e}"
-                        ))))
-                        .await;
-                    return;
-                }
-            };
-            let mixer = stream.mixer().clone();
-            let state = PlayerState::new(mixer, stream);
+                spawn_transfer_actor_with_config(cache_dir.clone(), transfer_config);
+
+            let mut state = PlayerState::new();
+            state.set_normalize(settings.normalize_volume);
+            state.set_speed(
+                settings
+                    .playback_speed
+                    .clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED),
+            );
+            state.set_eq(
+                f32::from(settings.eq_bass_db),
+                f32::from(settings.eq_treble_db),
+            );
+            if let Err(e) = state.open_output() {
+                tracing::error!(err = %e, "初始化音频输出失败");
+                let _ = tx_evt.send(AudioEvent::Error(MessageError::other(e))).await;
+                return;
+            }
 
-            tracing::info!(data_dir = %data_dir.display(), "AudioWorker 已启动");
+            tracing::info!(cache_dir = %cache_dir.display(), "AudioWorker 已启动");
 
-            let engine =
-                AudioEngine::new(tx_evt, rx_cmd, tx_transfer, rx_transfer, state, settings);
+            let engine = AudioEngine::new(
+                tx_evt,
+                rx_cmd,
+                tx_transfer,
+                rx_transfer,
+                state,
+                settings,
+                exports,
+            );
             engine.run().await;
         });
     });
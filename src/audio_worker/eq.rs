@@ -0,0 +1,268 @@
+//! 低音/高音调节：在解码后的音源上串联低频与高频搁架滤波器（RBJ biquad）
+//!
+//! 增益保存在共享的 [`EqControl`] 中，正在播放的音源每隔一小段采样读取一次，
+//! 调整后无需重建 sink 即可生效；两项增益都为 0 时直接透传采样。
+
+use rodio::source::SeekError;
+use rodio::{ChannelCount, Sample, SampleRate, Source};
+use std::f32::consts::PI;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+
+/// 低音/高音增益的可调范围（dB）
+pub const EQ_MIN_DB: i8 = -12;
+pub const EQ_MAX_DB: i8 = 12;
+
+/// 低频搁架的转折频率
+const BASS_HZ: f32 = 120.0;
+/// 高频搁架的转折频率
+const TREBLE_HZ: f32 = 6_000.0;
+/// 每隔多少帧重新读取增益与音源格式
+const POLL_FRAMES: usize = 512;
+
+/// 低音/高音增益（dB），引擎与所有音源共享
+#[derive(Debug, Default)]
+pub struct EqControl {
+    bass_db: AtomicU32,
+    treble_db: AtomicU32,
+}
+
+impl EqControl {
+    pub fn set(&self, bass_db: f32, treble_db: f32) {
+        let clamp = |db: f32| db.clamp(f32::from(EQ_MIN_DB), f32::from(EQ_MAX_DB));
+        self.bass_db
+            .store(clamp(bass_db).to_bits(), Ordering::Relaxed);
+        self.treble_db
+            .store(clamp(treble_db).to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn gains(&self) -> (f32, f32) {
+        (
+            f32::from_bits(self.bass_db.load(Ordering::Relaxed)),
+            f32::from_bits(self.treble_db.load(Ordering::Relaxed)),
+        )
+    }
+}
+
+/// 二阶滤波器系数（已按 a0 归一化）
+#[derive(Debug, Clone, Copy)]
+struct Coeffs {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+}
+
+impl Coeffs {
+    /// 搁架滤波器（斜率 S = 1）；`high` 为 true 时为高频搁架
+    fn shelf(high: bool, gain_db: f32, freq: f32, sample_rate: u32) -> Self {
+        let a = 10f32.powf(gain_db / 40.0);
+        // 转折频率不能超过奈奎斯特频率
+        let freq = freq.min(sample_rate as f32 * 0.45);
+        let w0 = 2.0 * PI * freq / sample_rate as f32;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / 2.0 * 2f32.sqrt();
+        let k = 2.0 * a.sqrt() * alpha;
+        let sign = if high { -1.0 } else { 1.0 };
+        let b0 = a * ((a + 1.0) - sign * (a - 1.0) * cos + k);
+        let b1 = sign * 2.0 * a * ((a - 1.0) - sign * (a + 1.0) * cos);
+        let b2 = a * ((a + 1.0) - sign * (a - 1.0) * cos - k);
+        let a0 = (a + 1.0) + sign * (a - 1.0) * cos + k;
+        let a1 = -sign * 2.0 * ((a - 1.0) + sign * (a + 1.0) * cos);
+        let a2 = (a + 1.0) + sign * (a - 1.0) * cos - k;
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+}
+
+/// 单声道滤波状态（转置直接 II 型）
+#[derive(Debug, Clone, Copy, Default)]
+struct Biquad {
+    z1: f32,
+    z2: f32,
+}
+
+impl Biquad {
+    fn process(&mut self, c: &Coeffs, x: f32) -> f32 {
+        let y = c.b0 * x + self.z1;
+        self.z1 = c.b1 * x - c.a1 * y + self.z2;
+        self.z2 = c.b2 * x - c.a2 * y;
+        y
+    }
+}
+
+/// 给音源加上低音/高音调节
+pub struct Equalizer<S> {
+    inner: S,
+    control: Arc<EqControl>,
+    gains: (f32, f32),
+    sample_rate: SampleRate,
+    channels: ChannelCount,
+    /// 增益都为 0 时为 None（直接透传）
+    coeffs: Option<(Coeffs, Coeffs)>,
+    /// 每个声道的 (低音, 高音) 滤波状态
+    state: Vec<(Biquad, Biquad)>,
+    channel: usize,
+    frames_until_poll: usize,
+}
+
+impl<S: Source> Equalizer<S> {
+    pub fn new(inner: S, control: Arc<EqControl>) -> Self {
+        let mut eq = Self {
+            sample_rate: inner.sample_rate(),
+            channels: inner.channels(),
+            inner,
+            control,
+            gains: (0.0, 0.0),
+            coeffs: None,
+            state: Vec::new(),
+            channel: 0,
+            frames_until_poll: 0,
+        };
+        eq.poll();
+        eq
+    }
+
+    /// 读取最新的增益与音源格式，有变化时重新计算系数
+    fn poll(&mut self) {
+        self.frames_until_poll = POLL_FRAMES;
+        let gains = self.control.gains();
+        let (sample_rate, channels) = (self.inner.sample_rate(), self.inner.channels());
+        let format_changed = sample_rate != self.sample_rate || channels != self.channels;
+        if gains == self.gains && !format_changed && !self.state.is_empty() {
+            return;
+        }
+        self.gains = gains;
+        self.sample_rate = sample_rate;
+        self.channels = channels;
+        if format_changed || self.state.len() != usize::from(channels) {
+            self.state = vec![Default::default(); usize::from(channels.max(1))];
+        }
+        self.coeffs = (gains != (0.0, 0.0) && sample_rate > 0).then(|| {
+            (
+                Coeffs::shelf(false, gains.0, BASS_HZ, sample_rate),
+                Coeffs::shelf(true, gains.1, TREBLE_HZ, sample_rate),
+            )
+        });
+    }
+}
+
+impl<S: Source> Iterator for Equalizer<S> {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Sample> {
+        if self.channel == 0 {
+            if self.frames_until_poll == 0 {
+                self.poll();
+            }
+            self.frames_until_poll -= 1;
+        }
+        let sample = self.inner.next()?;
+        let channel = self.channel;
+        self.channel = (self.channel + 1) % self.state.len();
+        let Some((bass, treble)) = &self.coeffs else {
+            return Some(sample);
+        };
+        let (low, high) = &mut self.state[channel];
+        let y = high.process(treble, low.process(bass, sample));
+        Some(y.clamp(-1.0, 1.0))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<S: Source> Source for Equalizer<S> {
+    fn current_span_len(&self) -> Option<usize> {
+        self.inner.current_span_len()
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.inner.try_seek(pos)?;
+        self.state.fill(Default::default());
+        self.channel = 0;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    const RATE: u32 = 44_100;
+
+    fn sine(freq: f32) -> SamplesBuffer {
+        let samples: Vec<f32> = (0..RATE)
+            .map(|i| 0.25 * (2.0 * PI * freq * i as f32 / RATE as f32).sin())
+            .collect();
+        SamplesBuffer::new(1, RATE, samples)
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    /// 跳过开头的瞬态后（后半秒）的均方根
+    fn settled_rms(source: impl Iterator<Item = f32>) -> f32 {
+        let samples: Vec<f32> = source.collect();
+        rms(&samples[samples.len() / 2..])
+    }
+
+    fn gain_db(freq: f32, bass_db: f32, treble_db: f32) -> f32 {
+        let control = Arc::new(EqControl::default());
+        control.set(bass_db, treble_db);
+        let out = settled_rms(Equalizer::new(sine(freq), control));
+        20.0 * (out / settled_rms(sine(freq))).log10()
+    }
+
+    #[test]
+    fn flat_settings_pass_samples_through() {
+        let control = Arc::new(EqControl::default());
+        let out: Vec<f32> = Equalizer::new(sine(440.0), control).collect();
+        let original: Vec<f32> = sine(440.0).collect();
+        assert_eq!(out, original);
+    }
+
+    #[test]
+    fn bass_and_treble_boost_their_own_band() {
+        assert!((gain_db(40.0, 6.0, 0.0) - 6.0).abs() < 0.5);
+        assert!(gain_db(8_000.0, 6.0, 0.0).abs() < 0.5);
+        assert!((gain_db(15_000.0, 0.0, -6.0) + 6.0).abs() < 0.5);
+        assert!(gain_db(40.0, 0.0, -6.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn control_changes_apply_to_playing_source() {
+        let control = Arc::new(EqControl::default());
+        let mut eq = Equalizer::new(sine(40.0), Arc::clone(&control));
+        let before: Vec<f32> = eq.by_ref().take(RATE as usize / 2).collect();
+        control.set(12.0, 0.0);
+        // 跳过调整后的瞬态
+        let after: Vec<f32> = eq.skip(RATE as usize / 10).collect();
+        assert!(rms(&after) > rms(&before) * 2.0);
+        // 超出范围的增益被限制
+        control.set(40.0, -40.0);
+        assert_eq!(control.gains(), (12.0, -12.0));
+    }
+}
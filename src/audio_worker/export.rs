@@ -0,0 +1,231 @@
+//! 下载歌曲到下载目录：等缓存就绪后把缓存文件复制出去（`AudioCommand::ExportTrack`）
+//!
+//! 缓存文件统一以 `.bin` 保存，导出时按文件头判断扩展名，并写入标签与封面（见 [`super::tags`]）。
+//! 复制在阻塞线程中进行，结果以 `AudioEvent::Exported` / `AudioEvent::ExportFailed` 上报。
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+use super::download::with_proxy;
+use super::messages::{AudioEvent, TrackTags};
+use super::tags::{self, Cover};
+use super::transfer::CacheKey;
+
+/// 判断格式所需的文件头字节数（`ftyp` 位于偏移 4..8）
+const SNIFF_LEN: usize = 12;
+/// 下载封面的超时；超时只是不写封面
+const COVER_TIMEOUT: Duration = Duration::from_secs(15);
+/// 网易云图片服务按参数缩放，避免把原图（可达数 MB）写进每个文件
+const COVER_SIZE_PARAM: &str = "param=800y800";
+
+/// 等待缓存就绪的导出
+struct ExportJob {
+    key: CacheKey,
+    /// 目标路径（不含扩展名）
+    dest: PathBuf,
+    tags: TrackTags,
+}
+
+/// 进行中的导出，按 transfer token 索引
+pub(super) struct TrackExports {
+    jobs: HashMap<u64, ExportJob>,
+    /// 下载封面用
+    http: reqwest::Client,
+}
+
+impl TrackExports {
+    pub(super) fn new(proxy: Option<&str>) -> Self {
+        let builder = reqwest::Client::builder().timeout(COVER_TIMEOUT);
+        let http = with_proxy(builder, proxy).build().unwrap_or_default();
+        Self {
+            jobs: HashMap::new(),
+            http,
+        }
+    }
+
+    pub(super) fn insert(&mut self, token: u64, key: CacheKey, dest: PathBuf, tags: TrackTags) {
+        self.jobs.insert(token, ExportJob { key, dest, tags });
+    }
+
+    /// 放弃该歌曲还在等待缓存的导出，返回需要取消的缓存请求
+    pub(super) fn cancel(&mut self, song_id: i64) -> Vec<(u64, CacheKey)> {
+        let tokens: Vec<(u64, CacheKey)> = self
+            .jobs
+            .iter()
+            .filter(|(_, job)| job.key.song_id == song_id)
+            .map(|(token, job)| (*token, job.key))
+            .collect();
+        for (token, _) in &tokens {
+            self.jobs.remove(token);
+        }
+        tokens
+    }
+
+    /// 缓存就绪：在后台下载封面、复制文件并写入标签，再上报结果；token 不属于导出时返回 false
+    pub(super) fn on_ready(
+        &mut self,
+        token: u64,
+        path: PathBuf,
+        tx_evt: &mpsc::Sender<AudioEvent>,
+    ) -> bool {
+        let Some(job) = self.jobs.remove(&token) else {
+            return false;
+        };
+        let tx_evt = tx_evt.clone();
+        let http = self.http.clone();
+        tokio::spawn(async move {
+            let ExportJob { key, dest, tags } = job;
+            let song_id = key.song_id;
+            let cover = match tags.cover_url.as_deref() {
+                Some(url) => fetch_cover(&http, song_id, url).await,
+                None => None,
+            };
+            let result = tokio::task::spawn_blocking(move || {
+                copy_to(&path, &dest, |partial, extension| {
+                    // 标签写不进去时仍保留文件，只是没有标签
+                    if let Err(e) = tags::write(partial, extension, &tags, cover.as_ref()) {
+                        tracing::warn!(song_id, err = %e, "写入标签失败");
+                    }
+                })
+            })
+            .await;
+            let evt = match result {
+                Ok(Ok(path)) => {
+                    tracing::info!(song_id, path = %path.display(), "歌曲已导出");
+                    AudioEvent::Exported { song_id, path }
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!(song_id, err = %e, "导出歌曲失败");
+                    AudioEvent::ExportFailed {
+                        song_id,
+                        message: e.to_string(),
+                    }
+                }
+                Err(e) => AudioEvent::ExportFailed {
+                    song_id,
+                    message: e.to_string(),
+                },
+            };
+            let _ = tx_evt.send(evt).await;
+        });
+        true
+    }
+
+    /// 缓存失败：返回要上报的事件；token 不属于导出时返回 None
+    pub(super) fn on_error(&mut self, token: u64, message: &str) -> Option<AudioEvent> {
+        let job = self.jobs.remove(&token)?;
+        Some(AudioEvent::ExportFailed {
+            song_id: job.key.song_id,
+            message: message.to_owned(),
+        })
+    }
+}
+
+/// 下载封面；任何失败都只记录日志并返回 None
+async fn fetch_cover(http: &reqwest::Client, song_id: i64, url: &str) -> Option<Cover> {
+    let sep = if url.contains('?') { '&' } else { '?' };
+    let url = format!("{url}{sep}{COVER_SIZE_PARAM}");
+    let result = async {
+        http.get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await
+    }
+    .await;
+    match result {
+        Ok(bytes) => {
+            let cover = Cover::from_bytes(bytes.to_vec());
+            if cover.is_none() {
+                tracing::warn!(song_id, "封面不是 JPEG/PNG，跳过");
+            }
+            cover
+        }
+        Err(e) => {
+            tracing::warn!(song_id, err = %e, "下载封面失败，跳过");
+            None
+        }
+    }
+}
+
+/// 按文件头判断扩展名；无法识别时按 MP3 处理（网易云音源只有 MP3/FLAC/M4A）
+fn extension_for(head: &[u8]) -> &'static str {
+    if head.starts_with(b"fLaC") {
+        "flac"
+    } else if head.starts_with(b"OggS") {
+        "ogg"
+    } else if head.get(4..8) == Some(b"ftyp".as_slice()) {
+        "m4a"
+    } else {
+        "mp3"
+    }
+}
+
+/// 复制到 `dest` 加上识别出的扩展名，返回最终路径；同名文件会被覆盖。
+/// `finish` 在改名前以临时文件与扩展名调用（写标签）
+fn copy_to(src: &Path, dest: &Path, finish: impl FnOnce(&Path, &str)) -> io::Result<PathBuf> {
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    fs::File::open(src)?
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut head)?;
+    let extension = extension_for(&head);
+    let mut file_name = dest.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    let target = dest.with_file_name(file_name);
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // 先写临时文件再改名，中途失败或退出都不会留下半个文件
+    let partial = target.with_extension("part");
+    fs::copy(src, &partial)?;
+    finish(&partial, extension);
+    fs::rename(&partial, &target)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extension_follows_the_file_header() {
+        assert_eq!(extension_for(b"fLaC\x00\x00\x00\x22"), "flac");
+        assert_eq!(extension_for(b"ID3\x04\x00"), "mp3");
+        assert_eq!(extension_for(&[0xFF, 0xFB, 0x90, 0x00]), "mp3");
+        assert_eq!(extension_for(b"\x00\x00\x00\x20ftypM4A "), "m4a");
+        assert_eq!(extension_for(b"OggS\x00\x02"), "ogg");
+        assert_eq!(extension_for(b""), "mp3");
+    }
+
+    #[test]
+    fn copies_with_detected_extension_and_creates_the_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let src = dir.path().join("1_999000.bin");
+        fs::write(&src, b"fLaC\x00\x00\x00\x22rest").unwrap();
+
+        // 歌名中含 `.` 时不能被当作扩展名替换掉
+        let dest = dir.path().join("music").join("Artist - Vol. 2");
+        let mut finished = None;
+        let target = copy_to(&src, &dest, |partial, extension| {
+            finished = Some((partial.to_path_buf(), extension.to_owned()));
+        })
+        .unwrap();
+
+        assert_eq!(
+            target,
+            dir.path().join("music").join("Artist - Vol. 2.flac")
+        );
+        assert_eq!(fs::read(&target).unwrap(), fs::read(&src).unwrap());
+        assert!(!target.with_extension("part").exists());
+        assert_eq!(
+            finished,
+            Some((target.with_extension("part"), "flac".to_owned()))
+        );
+    }
+}
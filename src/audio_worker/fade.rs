@@ -1,7 +1,69 @@
 use rodio::Sink;
+
+use super::player::gained_volume;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+/// 切歌方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Transition {
+    /// 新旧两首交叉淡入淡出
+    Crossfade { ms: u64 },
+    /// 直接衔接：立即停止旧歌、以正常音量开始新歌
+    Cut,
+    /// 无缝衔接：下一首已接在当前 sink 的队列后面，旧歌播完即出声，中间没有静音（无缝专辑/现场录音）
+    Gapless,
+}
+
+/// 不淡入淡出时，下一首提前多久接到当前 sink 后面
+pub(super) const GAPLESS_LEAD_MS: u64 = 2_000;
+
+/// 选择切歌方式；`no_fade` 只影响这一次切换，之后仍按全局淡入淡出设置
+pub(super) fn select_transition(
+    crossfade_ms: u64,
+    no_fade: bool,
+    has_current: bool,
+    paused: bool,
+) -> Transition {
+    if crossfade_ms > 0 && !no_fade && has_current && !paused {
+        Transition::Crossfade { ms: crossfade_ms }
+    } else {
+        Transition::Cut
+    }
+}
+
+/// 预排的下一首何时接管当前播放
+///
+/// 需要淡入淡出时在当前歌曲剩余时长不超过淡入淡出时长时开始交叉淡入淡出；否则由
+/// [`gapless_append_due`] 提前接到当前 sink 后面，这里只在没能接上（解码失败等）时于播完后直接衔接。
+/// 返回 None 表示还不到切换的时候。
+pub(super) fn queued_handoff(
+    crossfade_ms: u64,
+    no_fade: bool,
+    remaining_ms: Option<u64>,
+    ended: bool,
+) -> Option<Transition> {
+    if ended {
+        return Some(Transition::Cut);
+    }
+    if crossfade_ms > 0 && !no_fade && remaining_ms.is_some_and(|ms| ms <= crossfade_ms) {
+        return Some(Transition::Crossfade { ms: crossfade_ms });
+    }
+    None
+}
+
+/// 不淡入淡出时是否该把预排的下一首接到当前 sink 后面；剩余时长未知时立即接上
+pub(super) fn gapless_append_due(
+    crossfade_ms: u64,
+    no_fade: bool,
+    remaining_ms: Option<u64>,
+) -> bool {
+    if crossfade_ms > 0 && !no_fade {
+        return false;
+    }
+    remaining_ms.is_none_or(|ms| ms <= GAPLESS_LEAD_MS)
+}
+
 pub(super) struct Crossfade {
     from: Arc<Sink>,
     to: Arc<Sink>,
@@ -10,14 +72,25 @@ pub(super) struct Crossfade {
     paused_at: Option<Instant>,
     paused_total: Duration,
     last_ratio: f32,
+    /// 新旧两首各自的响度增益：各自从/向叠加增益后的目标音量渐变
+    from_gain_db: Option<f32>,
+    to_gain_db: Option<f32>,
 }
 
 impl Crossfade {
-    pub(super) fn new(from: Arc<Sink>, to: Arc<Sink>, duration_ms: u64) -> Self {
+    pub(super) fn new(
+        from: Arc<Sink>,
+        to: Arc<Sink>,
+        duration_ms: u64,
+        from_gain_db: Option<f32>,
+        to_gain_db: Option<f32>,
+    ) -> Self {
         let duration = Duration::from_millis(duration_ms.max(1));
         Self {
             from,
             to,
+            from_gain_db,
+            to_gain_db,
             start: Instant::now(),
             duration,
             paused_at: None,
@@ -48,15 +121,17 @@ impl Crossfade {
         self.to.play();
     }
 
-    pub(super) fn apply(&mut self, base_volume: f32) -> bool {
+    pub(super) fn apply(&mut self, base_volume: f32, normalize: bool) -> bool {
         let now = self.paused_at.unwrap_or_else(Instant::now);
         let elapsed = now
             .duration_since(self.start)
             .saturating_sub(self.paused_total);
         let t = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).clamp(0.0, 1.0);
         self.last_ratio = t;
-        self.from.set_volume(base_volume * (1.0 - t));
-        self.to.set_volume(base_volume * t);
+        self.from
+            .set_volume(gained_volume(base_volume, self.from_gain_db, normalize) * (1.0 - t));
+        self.to
+            .set_volume(gained_volume(base_volume, self.to_gain_db, normalize) * t);
         if t >= 1.0 {
             self.from.stop();
             return true;
@@ -68,3 +143,47 @@ impl Crossfade {
         self.from.stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queued_track_crossfades_before_current_ends() {
+        assert_eq!(queued_handoff(300, false, Some(5_000), false), None);
+        assert_eq!(
+            queued_handoff(300, false, Some(300), false),
+            Some(Transition::Crossfade { ms: 300 })
+        );
+        assert_eq!(
+            queued_handoff(300, false, Some(0), true),
+            Some(Transition::Cut),
+            "错过淡入淡出窗口时直接衔接"
+        );
+    }
+
+    #[test]
+    fn queued_track_without_fade_is_appended_ahead_of_the_end() {
+        assert!(!gapless_append_due(0, false, Some(GAPLESS_LEAD_MS + 1)));
+        assert!(gapless_append_due(0, false, Some(GAPLESS_LEAD_MS)));
+        assert!(gapless_append_due(300, true, Some(100)));
+        assert!(gapless_append_due(0, false, None), "时长未知时立即接上");
+        assert!(
+            !gapless_append_due(300, false, Some(100)),
+            "需要淡入淡出时走交叉淡入淡出"
+        );
+    }
+
+    #[test]
+    fn queued_track_without_fade_waits_for_end() {
+        // 淡入淡出关闭、同专辑连续曲目或时长未知：没能提前接上时播完才切换
+        assert_eq!(queued_handoff(0, false, Some(100), false), None);
+        assert_eq!(queued_handoff(300, true, Some(100), false), None);
+        assert_eq!(queued_handoff(300, false, None, false), None);
+        assert_eq!(
+            queued_handoff(0, false, Some(0), true),
+            Some(Transition::Cut)
+        );
+        assert_eq!(queued_handoff(300, true, None, true), Some(Transition::Cut));
+    }
+}
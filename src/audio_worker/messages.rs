@@ -1,4 +1,10 @@
 use crate::error::MessageError;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// 播放倍速的范围（`AudioCommand::SetPlaybackSpeed`）
+pub const MIN_PLAYBACK_SPEED: f32 = 0.5;
+pub const MAX_PLAYBACK_SPEED: f32 = 2.0;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AudioPlaybackMode {
@@ -57,6 +63,8 @@ pub enum AudioLoadStage {
     Downloading {
         downloaded_bytes: u64,
         total_bytes: Option<u64>,
+        /// 最近 2 秒的平均下载速度（字节/秒）；0 表示网络停滞
+        bytes_per_sec: Option<u64>,
     },
     PreparingPlayback,
     Retrying {
@@ -69,26 +77,91 @@ pub enum AudioLoadStage {
 pub enum AudioCommand {
     PlayTrack {
         id: i64,
+        /// 实际音质档位，用作缓存键
         br: i64,
+        /// 请求的音质；旧版本按它作为缓存键，未命中时回退查找一次
+        requested_br: i64,
         url: String,
+        /// SongUrl 接口给出的文件大小，用于校验下载结果
+        expected_bytes: Option<u64>,
         title: String,
         duration_ms: Option<u64>,
+        /// 本次切歌不做淡入淡出（同专辑连续曲目或队列禁用了淡入淡出）
+        no_fade: bool,
+        /// SongUrl 接口给出的响度增益（dB），开启音量均衡时叠加到音量上
+        gain_db: Option<f32>,
     },
     TogglePause,
     Stop,
     SeekToMs(u64),
     SetVolume(f32),
+    /// 开关音量均衡：按歌曲的响度增益调整音量
+    SetVolumeNormalization(bool),
+    /// 播放倍速（变速同时变调），范围见 [`MIN_PLAYBACK_SPEED`] / [`MAX_PLAYBACK_SPEED`]
+    SetPlaybackSpeed(f32),
+    /// 低音/高音增益（dB），范围见 [`EQ_MIN_DB`](super::EQ_MIN_DB) / [`EQ_MAX_DB`](super::EQ_MAX_DB)
+    SetEqualizer {
+        bass_db: i8,
+        treble_db: i8,
+    },
     SetCrossfadeMs(u64),
     ClearCache,
-    /// 设置“仅保留当前音质(br)”的缓存策略
+    /// 设置缓存音质上限：清理高于当前音质(br)的缓存
     SetCacheBr(i64),
-    /// 预缓存音频文件（仅缓存，不播放）
-    PrefetchAudio {
+    /// 预排下一首：提前下载并解码，当前歌曲结束时由引擎直接切换并发送 `NowPlaying`，
+    /// 不再经由 core 请求播放链接。再次发送会替换之前预排的歌曲
+    QueueNext {
         id: i64,
         br: i64,
+        requested_br: i64,
         url: String,
+        expected_bytes: Option<u64>,
         title: String,
+        duration_ms: Option<u64>,
+        /// 切换时不做淡入淡出（同专辑连续曲目或队列禁用了淡入淡出）
+        no_fade: bool,
+        gain_db: Option<f32>,
+    },
+    /// 丢弃预排的下一首（手动切歌、切换播放模式或更换队列）
+    ClearQueued,
+    /// 查询缓存占用，以 [`AudioEvent::CacheStats`] 回复
+    QueryCacheStats,
+    /// 查询这些歌曲中已缓存（音质不高于 br）的，以 [`AudioEvent::CachedSongs`] 回复
+    QueryCachedSongs {
+        song_ids: Vec<i64>,
+        br: i64,
+    },
+    /// 确保歌曲已缓存后复制到 `dest`（不含扩展名，按文件内容补上），
+    /// 以 [`AudioEvent::Exported`] / [`AudioEvent::ExportFailed`] 回复
+    ExportTrack {
+        id: i64,
+        br: i64,
+        url: String,
+        expected_bytes: Option<u64>,
+        tags: TrackTags,
+        dest: PathBuf,
+    },
+    /// 放弃该歌曲还在等待缓存的 `ExportTrack`（已开始复制的仍会完成），不再回复
+    CancelExport {
+        id: i64,
+    },
+    /// 把 `from`（之前的缓存目录）中的缓存文件移到当前缓存目录，
+    /// 以 [`AudioEvent::CacheMigrateProgress`] / [`AudioEvent::CacheMigrated`] 回复
+    MigrateCache {
+        from: PathBuf,
     },
+    CancelCacheMigration,
+}
+
+/// 导出时写入文件的标签（MP3 为 ID3v2.4，FLAC 为 Vorbis comment）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackTags {
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: String,
+    pub track_no: Option<u32>,
+    /// 封面图片地址；下载失败时只是不写封面
+    pub cover_url: Option<String>,
 }
 
 #[derive(Debug)]
@@ -116,10 +189,54 @@ pub enum AudioEvent {
     Ended {
         play_id: u64,
     },
+    /// 播放中定期上报的实际播放位置（sink 位置，已计入 Seek）
+    Position {
+        play_id: u64,
+        position_ms: u64,
+    },
     CacheCleared {
         files: usize,
         bytes: u64,
     },
+    /// 缓存超出上限，自动删除了最久未播放的文件
+    CacheEvicted {
+        files: usize,
+        bytes: u64,
+    },
+    /// 当前缓存的文件数与总字节数
+    CacheStats {
+        files: usize,
+        bytes: u64,
+    },
+    /// `QueryCachedSongs` 的结果：已缓存的歌曲 id
+    CachedSongs {
+        song_ids: Vec<i64>,
+    },
+    /// `ExportTrack` 完成，`path` 为最终文件路径
+    Exported {
+        song_id: i64,
+        path: PathBuf,
+    },
+    ExportFailed {
+        song_id: i64,
+        message: String,
+    },
+    /// 移动旧缓存的进度（已处理文件数 / 总数）
+    CacheMigrateProgress {
+        done: usize,
+        total: usize,
+    },
+    /// 移动旧缓存结束；`failed` 个文件留在原位置
+    CacheMigrated {
+        files: usize,
+        bytes: u64,
+        failed: usize,
+        cancelled: bool,
+    },
+    /// CDN 限流，后台下载暂停一段时间
+    DownloadCooldown {
+        duration: Duration,
+    },
     Error(MessageError),
     NeedsReload,
 }
@@ -149,6 +266,7 @@ mod tests {
         let stage = AudioLoadStage::Downloading {
             downloaded_bytes: 1024,
             total_bytes: Some(2048),
+            bytes_per_sec: None,
         };
         let debug_str = format!("{:?}", stage);
         assert!(debug_str.contains("Downloading"));
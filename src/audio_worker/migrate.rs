@@ -0,0 +1,203 @@
+//! 把旧位置的音频缓存文件移动到当前缓存目录（设置页「移动现有缓存」）
+//!
+//! 在阻塞线程中逐个移动 `*.bin`；同一磁盘内直接改名，跨磁盘时复制后删除源文件。
+//! 移动完成后由 TransferActor 把新文件登记进缓存索引并按上限清理。
+
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::cache::is_cache_file_name;
+use super::messages::AudioEvent;
+
+/// 移动结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrationSummary {
+    pub files: usize,
+    pub bytes: u64,
+    /// 移动失败、留在原位置的文件数
+    pub failed: usize,
+    pub cancelled: bool,
+}
+
+impl From<MigrationSummary> for AudioEvent {
+    fn from(s: MigrationSummary) -> Self {
+        AudioEvent::CacheMigrated {
+            files: s.files,
+            bytes: s.bytes,
+            failed: s.failed,
+            cancelled: s.cancelled,
+        }
+    }
+}
+
+/// 把 `from` 中的缓存文件移到 `to`；`on_progress(已处理, 总数)` 大约每 1% 调用一次。
+/// 目标中已有同名文件时以目标为准，删除源文件。全部移走后删除旧索引与空目录。
+pub(super) fn move_cache_files(
+    from: &Path,
+    to: &Path,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(usize, usize),
+) -> MigrationSummary {
+    let mut summary = MigrationSummary::default();
+    let names = match fs::read_dir(from) {
+        Ok(rd) => rd
+            .flatten()
+            .filter_map(|e| e.file_name().into_string().ok())
+            .filter(|name| is_cache_file_name(name))
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            tracing::info!(from = %from.display(), err = %e, "旧缓存目录不可读，没有需要移动的文件");
+            return summary;
+        }
+    };
+
+    let total = names.len();
+    let step = (total / 100).max(1);
+    on_progress(0, total);
+    for (i, name) in names.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            summary.cancelled = true;
+            break;
+        }
+        let src = from.join(name);
+        let dst = to.join(name);
+        let moved = if dst.exists() {
+            fs::remove_file(&src).map(|()| 0)
+        } else {
+            move_file(&src, &dst)
+        };
+        match moved {
+            Ok(bytes) => {
+                summary.files += 1;
+                summary.bytes += bytes;
+            }
+            Err(e) => {
+                tracing::warn!(src = %src.display(), err = %e, "移动缓存文件失败");
+                summary.failed += 1;
+            }
+        }
+        let done = i + 1;
+        if done % step == 0 || done == total {
+            on_progress(done, total);
+        }
+    }
+
+    if !summary.cancelled && summary.failed == 0 {
+        let _ = fs::remove_file(from.join("index.json"));
+        // 目录中还有其它文件时删除会失败，保留即可
+        let _ = fs::remove_dir(from);
+    }
+    summary
+}
+
+/// 移动单个文件，返回字节数
+fn move_file(src: &Path, dst: &Path) -> io::Result<u64> {
+    move_file_with(src, dst, |a, b| fs::rename(a, b))
+}
+
+/// `rename` 报告跨设备时改为复制后删除：先复制到临时文件再改名，中途失败不会留下半个缓存文件
+fn move_file_with(
+    src: &Path,
+    dst: &Path,
+    rename: impl Fn(&Path, &Path) -> io::Result<()>,
+) -> io::Result<u64> {
+    let size = fs::metadata(src)?.len();
+    match rename(src, dst) {
+        Ok(()) => return Ok(size),
+        Err(e) if e.kind() == io::ErrorKind::CrossesDevices => {}
+        Err(e) => return Err(e),
+    }
+    let partial = dst.with_extension("moving.tmp");
+    if let Err(e) = fs::copy(src, &partial).and_then(|_| fs::rename(&partial, dst)) {
+        let _ = fs::remove_file(&partial);
+        return Err(e);
+    }
+    fs::remove_file(src)?;
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cross_device(_: &Path, _: &Path) -> io::Result<()> {
+        Err(io::Error::from(io::ErrorKind::CrossesDevices))
+    }
+
+    #[test]
+    fn cross_device_rename_falls_back_to_copy_and_delete() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let src = dir.path().join("1_320000.bin");
+        let dst = dir.path().join("out").join("1_320000.bin");
+        fs::create_dir_all(dst.parent().unwrap()).unwrap();
+        fs::write(&src, b"audio").unwrap();
+
+        assert_eq!(move_file_with(&src, &dst, cross_device).unwrap(), 5);
+        assert!(!src.exists());
+        assert_eq!(fs::read(&dst).unwrap(), b"audio");
+        assert!(!dst.with_extension("moving.tmp").exists());
+    }
+
+    #[test]
+    fn other_rename_errors_keep_the_source() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let src = dir.path().join("1_320000.bin");
+        let dst = dir.path().join("1_999000.bin");
+        fs::write(&src, b"audio").unwrap();
+
+        let denied = |_: &Path, _: &Path| Err(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert!(move_file_with(&src, &dst, denied).is_err());
+        assert!(src.exists());
+        assert!(!dst.exists());
+    }
+
+    #[test]
+    fn moves_cache_files_and_removes_the_old_directory() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let from = dir.path().join("audio_cache");
+        let to = dir.path().join("hdd");
+        fs::create_dir_all(&from).unwrap();
+        fs::create_dir_all(&to).unwrap();
+        fs::write(from.join("1_320000.bin"), b"aaa").unwrap();
+        fs::write(from.join("2_320000.bin"), b"bb").unwrap();
+        fs::write(from.join("index.json"), b"{}").unwrap();
+        // 目标中已有的文件以目标为准
+        fs::write(to.join("2_320000.bin"), b"kept").unwrap();
+
+        let mut progress = Vec::new();
+        let summary = move_cache_files(&from, &to, &AtomicBool::new(false), |d, t| {
+            progress.push((d, t))
+        });
+
+        assert_eq!(
+            summary,
+            MigrationSummary {
+                files: 2,
+                bytes: 3,
+                failed: 0,
+                cancelled: false,
+            }
+        );
+        assert_eq!(progress.first(), Some(&(0, 2)));
+        assert_eq!(progress.last(), Some(&(2, 2)));
+        assert_eq!(fs::read(to.join("1_320000.bin")).unwrap(), b"aaa");
+        assert_eq!(fs::read(to.join("2_320000.bin")).unwrap(), b"kept");
+        assert!(!from.exists());
+    }
+
+    #[test]
+    fn cancelled_move_keeps_the_remaining_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let from = dir.path().join("audio_cache");
+        fs::create_dir_all(&from).unwrap();
+        fs::write(from.join("1_320000.bin"), b"a").unwrap();
+
+        let summary = move_cache_files(&from, dir.path(), &AtomicBool::new(true), |_, _| {});
+
+        assert!(summary.cancelled);
+        assert_eq!(summary.files, 0);
+        assert!(from.join("1_320000.bin").exists());
+    }
+}
@@ -1,16 +1,26 @@
+use std::time::Duration;
+use tokio::time::Instant;
+
 mod cache;
 mod download;
 mod engine;
+mod eq;
+mod export;
 mod fade;
 mod messages;
+mod migrate;
 mod null_engine;
 mod player;
 mod streaming;
+mod tags;
 mod transfer;
 mod worker;
 
+pub use cache::{AUDIO_CACHE_DIR, cached_song_ids, has_cached_song};
+pub use eq::{EQ_MAX_DB, EQ_MIN_DB};
 pub use messages::{
     AudioBufferState, AudioCommand, AudioEvent, AudioLoadStage, AudioPlaybackMode, AudioStreamHint,
+    MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED, TrackTags,
 };
 #[allow(unused_imports)]
 pub use streaming::{ProgressiveReader, SessionSnapshot, StreamingSession};
@@ -25,11 +35,35 @@ pub enum AudioBackend {
 #[derive(Debug, Clone, Copy)]
 pub struct AudioSettings {
     pub crossfade_ms: u64,
+    /// 停止播放后空闲多久释放音频输出；`None` 表示一直保留
+    pub idle_release_after: Option<Duration>,
+    /// 启动时是否开启音量均衡，运行时由 `SetVolumeNormalization` 切换
+    pub normalize_volume: bool,
+    /// 启动时的播放倍速，运行时由 `SetPlaybackSpeed` 调整
+    pub playback_speed: f32,
+    /// 启动时的低音/高音增益（dB），运行时由 `SetEqualizer` 调整
+    pub eq_bass_db: i8,
+    pub eq_treble_db: i8,
 }
 
 impl Default for AudioSettings {
     fn default() -> Self {
-        Self { crossfade_ms: 300 }
+        Self {
+            crossfade_ms: 300,
+            idle_release_after: Some(Duration::from_secs(60)),
+            normalize_volume: false,
+            playback_speed: 1.0,
+            eq_bass_db: 0,
+            eq_treble_db: 0,
+        }
+    }
+}
+
+/// 等到空闲释放的时刻；没有安排释放时永不返回
+async fn idle_release_due(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
     }
 }
 
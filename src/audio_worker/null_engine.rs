@@ -1,13 +1,17 @@
 use std::path::PathBuf;
+use std::time::Duration;
 use tokio::select;
 use tokio::sync::mpsc;
+use tokio::time::Instant;
 
-use super::AudioSettings;
+use super::export::TrackExports;
+use super::fade::{Transition, select_transition};
 use super::messages::{AudioCommand, AudioEvent, AudioStreamHint};
 use super::transfer::{
     CacheKey, Priority, TransferCommand, TransferConfig, TransferEvent, TransferReceiver,
     TransferSender, spawn_transfer_actor_with_config,
 };
+use super::{AudioSettings, idle_release_due};
 
 struct NullEngine {
     tx_evt: mpsc::Sender<AudioEvent>,
@@ -16,7 +20,20 @@ struct NullEngine {
     rx_transfer: TransferReceiver,
     play_id: u64,
     paused: bool,
-    _settings: AudioSettings,
+    crossfade_ms: u64,
+    /// 是否有正在播放的歌曲（决定切歌时能否淡入淡出）
+    has_current: bool,
+    /// 最近一次切歌选择的方式（不真正播放，只记录，便于测试切歌逻辑）
+    last_transition: Option<Transition>,
+    /// 预排的下一首；不会真正播完，因此只缓存、不切换
+    queued: Option<i64>,
+    /// 导出照常进行：缓存就绪后复制到下载目录
+    exports: TrackExports,
+    next_token: u64,
+    /// 模拟的音频输出：停止后空闲超时即释放，下次播放时重新打开
+    output_open: bool,
+    idle_release_after: Option<Duration>,
+    idle_deadline: Option<Instant>,
 }
 
 impl NullEngine {
@@ -26,6 +43,7 @@ impl NullEngine {
         tx_transfer: TransferSender,
         rx_transfer: TransferReceiver,
         settings: AudioSettings,
+        exports: TrackExports,
     ) -> Self {
         Self {
             tx_evt,
@@ -34,45 +52,98 @@ impl NullEngine {
             rx_transfer,
             play_id: 0,
             paused: false,
-            _settings: settings,
+            crossfade_ms: settings.crossfade_ms,
+            has_current: false,
+            last_transition: None,
+            queued: None,
+            exports,
+            next_token: 1,
+            output_open: true,
+            idle_release_after: settings.idle_release_after,
+            idle_deadline: None,
         }
     }
 
     async fn run(mut self) {
-        loop {
-            select! {
-                maybe_evt = self.rx_transfer.recv() => {
-                    let Some(evt) = maybe_evt else {
-                        break;
-                    };
-                    match evt {
-                        TransferEvent::CacheCleared { files, bytes } => {
-                            let _ = self.tx_evt.send(AudioEvent::CacheCleared { files, bytes }).await;
+        while self.step().await {}
+    }
+
+    /// 处理一个事件、命令或空闲释放；通道关闭时返回 false
+    async fn step(&mut self) -> bool {
+        select! {
+            _ = idle_release_due(self.idle_deadline) => {
+                self.idle_deadline = None;
+                self.output_open = false;
+                tracing::debug!("NullEngine 释放空闲的音频输出");
+            }
+            maybe_evt = self.rx_transfer.recv() => {
+                let Some(evt) = maybe_evt else {
+                    return false;
+                };
+                match evt {
+                    TransferEvent::CacheCleared { files, bytes } => {
+                        let _ = self.tx_evt.send(AudioEvent::CacheCleared { files, bytes }).await;
+                    }
+                    TransferEvent::CacheEvicted { files, bytes } => {
+                        let _ = self.tx_evt.send(AudioEvent::CacheEvicted { files, bytes }).await;
+                    }
+                    TransferEvent::CacheStats { files, bytes } => {
+                        let _ = self.tx_evt.send(AudioEvent::CacheStats { files, bytes }).await;
+                    }
+                    TransferEvent::CachedSongs { song_ids } => {
+                        let _ = self.tx_evt.send(AudioEvent::CachedSongs { song_ids }).await;
+                    }
+                    TransferEvent::Cooldown { duration } => {
+                        let _ = self.tx_evt.send(AudioEvent::DownloadCooldown { duration }).await;
+                    }
+                    TransferEvent::MigrateProgress { done, total } => {
+                        let _ = self.tx_evt.send(AudioEvent::CacheMigrateProgress { done, total }).await;
+                    }
+                    TransferEvent::Migrated(summary) => {
+                        let _ = self.tx_evt.send(summary.into()).await;
+                    }
+                    TransferEvent::Ready { token, path, .. } => {
+                        self.exports.on_ready(token, path, &self.tx_evt);
+                    }
+                    TransferEvent::Error { token, message, .. } => {
+                        if let Some(evt) = self.exports.on_error(token, &message) {
+                            let _ = self.tx_evt.send(evt).await;
                         }
-                        TransferEvent::CacheHit { .. }
-                        | TransferEvent::DownloadQueued { .. }
-                        | TransferEvent::Progress { .. }
-                        | TransferEvent::Retrying { .. }
-                        | TransferEvent::Playable { .. }
-                        | TransferEvent::Ready { .. }
-                        | TransferEvent::Error { .. } => {}
                     }
-                }
-                maybe_cmd = self.rx_cmd.recv() => {
-                    let Some(cmd) = maybe_cmd else {
-                        break;
-                    };
-                    self.handle_audio_command(cmd).await;
+                    TransferEvent::CacheHit { .. }
+                    | TransferEvent::DownloadQueued { .. }
+                    | TransferEvent::Progress { .. }
+                    | TransferEvent::Retrying { .. }
+                    | TransferEvent::Playable { .. } => {}
                 }
             }
+            maybe_cmd = self.rx_cmd.recv() => {
+                let Some(cmd) = maybe_cmd else {
+                    return false;
+                };
+                self.handle_audio_command(cmd).await;
+            }
         }
+        true
     }
 
     async fn handle_audio_command(&mut self, cmd: AudioCommand) {
         match cmd {
-            AudioCommand::PlayTrack { id, title, .. } => {
+            AudioCommand::PlayTrack {
+                id, title, no_fade, ..
+            } => {
+                self.last_transition = Some(select_transition(
+                    self.crossfade_ms,
+                    no_fade,
+                    self.has_current,
+                    self.paused,
+                ));
+                tracing::debug!(song_id = id, transition = ?self.last_transition, "NullEngine 切歌");
+                self.idle_deadline = None;
+                self.output_open = true;
                 self.play_id = self.play_id.wrapping_add(1).max(1);
                 self.paused = false;
+                self.has_current = true;
                 let _ = self
                     .tx_evt
                     .send(AudioEvent::NowPlaying {
@@ -89,12 +160,20 @@ impl NullEngine {
                 let _ = self.tx_evt.send(AudioEvent::Paused(self.paused)).await;
             }
             AudioCommand::Stop => {
+                self.queued = None;
                 self.paused = false;
+                self.has_current = false;
+                self.idle_deadline = self.idle_release_after.map(|after| Instant::now() + after);
                 let _ = self.tx_evt.send(AudioEvent::Stopped).await;
             }
             AudioCommand::SeekToMs(_) => {}
             AudioCommand::SetVolume(_) => {}
-            AudioCommand::SetCrossfadeMs(_) => {}
+            AudioCommand::SetVolumeNormalization(_) => {}
+            AudioCommand::SetPlaybackSpeed(_) => {}
+            AudioCommand::SetEqualizer { .. } => {}
+            AudioCommand::SetCrossfadeMs(ms) => {
+                self.crossfade_ms = ms;
+            }
             AudioCommand::ClearCache => {
                 let _ = self
                     .tx_transfer
@@ -104,10 +183,50 @@ impl NullEngine {
             AudioCommand::SetCacheBr(br) => {
                 let _ = self
                     .tx_transfer
-                    .send(TransferCommand::PurgeNotBr { br, keep: None })
+                    .send(TransferCommand::PurgeAboveBr { br, keep: None })
+                    .await;
+            }
+            AudioCommand::QueryCacheStats => {
+                let _ = self.tx_transfer.send(TransferCommand::QueryStats).await;
+            }
+            AudioCommand::CancelExport { id } => {
+                for (token, key) in self.exports.cancel(id) {
+                    tracing::info!(song_id = id, token, "取消导出歌曲");
+                    let _ = self
+                        .tx_transfer
+                        .send(TransferCommand::Cancel { token, key })
+                        .await;
+                }
+            }
+            AudioCommand::MigrateCache { from } => {
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::MigrateFrom { from })
+                    .await;
+            }
+            AudioCommand::CancelCacheMigration => {
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::CancelMigration)
+                    .await;
+            }
+            AudioCommand::QueryCachedSongs { song_ids, br } => {
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::QueryCached { song_ids, br })
                     .await;
             }
-            AudioCommand::PrefetchAudio { id, br, url, title } => {
+            AudioCommand::QueueNext {
+                id,
+                br,
+                requested_br,
+                url,
+                expected_bytes,
+                title,
+                ..
+            } => {
+                self.queued = Some(id);
+                self.idle_deadline = None;
                 let key = CacheKey { song_id: id, br };
                 let _ = self
                     .tx_transfer
@@ -115,8 +234,39 @@ impl NullEngine {
                         token: 0,
                         key,
                         url,
+                        expected_bytes,
                         title,
                         priority: Priority::Low,
+                        legacy_br: Some(requested_br),
+                    })
+                    .await;
+            }
+            AudioCommand::ClearQueued => {
+                self.queued = None;
+            }
+            AudioCommand::ExportTrack {
+                id,
+                br,
+                url,
+                expected_bytes,
+                tags,
+                dest,
+            } => {
+                let token = self.next_token;
+                self.next_token = self.next_token.wrapping_add(1).max(1);
+                let title = tags.title.clone();
+                self.exports
+                    .insert(token, CacheKey { song_id: id, br }, dest, tags);
+                let _ = self
+                    .tx_transfer
+                    .send(TransferCommand::EnsureCached {
+                        token,
+                        key: CacheKey { song_id: id, br },
+                        url,
+                        expected_bytes,
+                        title,
+                        priority: Priority::High,
+                        legacy_br: None,
                     })
                     .await;
             }
@@ -127,13 +277,166 @@ impl NullEngine {
 pub(super) fn spawn(
     rx_cmd: mpsc::Receiver<AudioCommand>,
     tx_evt: mpsc::Sender<AudioEvent>,
-    data_dir: PathBuf,
+    cache_dir: PathBuf,
     transfer_config: TransferConfig,
     settings: AudioSettings,
 ) {
-    let (tx_transfer, rx_transfer) = spawn_transfer_actor_with_config(data_dir, transfer_config);
-    tokio::spawn(async move {
-        let engine = NullEngine::new(tx_evt, rx_cmd, tx_transfer, rx_transfer, settings);
+    let exports = TrackExports::new(transfer_config.proxy.as_deref());
+    let (tx_transfer, rx_transfer) = spawn_transfer_actor_with_config(cache_dir, transfer_config);
+    let engine = tokio::spawn(async move {
+        let engine = NullEngine::new(tx_evt, rx_cmd, tx_transfer, rx_transfer, settings, exports);
         engine.run().await;
     });
+    crate::crash::supervise("AudioWorker", engine);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engine(crossfade_ms: u64) -> (NullEngine, mpsc::Receiver<AudioEvent>) {
+        let (tx_evt, rx_evt) = mpsc::channel(16);
+        let (_tx_cmd, rx_cmd) = mpsc::channel(1);
+        let (tx_transfer, _rx_transfer_cmd) = mpsc::channel(1);
+        let (_tx_transfer_evt, rx_transfer) = mpsc::channel(1);
+        let engine = NullEngine::new(
+            tx_evt,
+            rx_cmd,
+            tx_transfer,
+            rx_transfer,
+            AudioSettings {
+                crossfade_ms,
+                idle_release_after: None,
+                normalize_volume: false,
+                playback_speed: 1.0,
+                eq_bass_db: 0,
+                eq_treble_db: 0,
+            },
+            TrackExports::new(None),
+        );
+        (engine, rx_evt)
+    }
+
+    fn play(id: i64, no_fade: bool) -> AudioCommand {
+        AudioCommand::PlayTrack {
+            id,
+            br: 320_000,
+            requested_br: 320_000,
+            url: format!("https://example.com/{id}.mp3"),
+            expected_bytes: None,
+            title: format!("song {id}"),
+            duration_ms: None,
+            no_fade,
+            gain_db: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn no_fade_hint_only_affects_its_own_transition() {
+        let (mut e, _rx) = engine(300);
+
+        e.handle_audio_command(play(1, false)).await;
+        assert_eq!(
+            e.last_transition,
+            Some(Transition::Cut),
+            "首首歌没有可淡出的旧歌"
+        );
+
+        e.handle_audio_command(play(2, false)).await;
+        assert_eq!(e.last_transition, Some(Transition::Crossfade { ms: 300 }));
+
+        // 同专辑连续曲目：直接衔接
+        e.handle_audio_command(play(3, true)).await;
+        assert_eq!(e.last_transition, Some(Transition::Cut));
+
+        // 提示只作用于那一次切换，之后恢复全局设置
+        e.handle_audio_command(play(4, false)).await;
+        assert_eq!(e.last_transition, Some(Transition::Crossfade { ms: 300 }));
+    }
+
+    #[tokio::test]
+    async fn toggling_override_mid_playback_applies_to_next_transition() {
+        let (mut e, _rx) = engine(300);
+        e.handle_audio_command(play(1, false)).await;
+
+        // 播放中开启「此队列禁用淡入淡出」：下一次切歌带上 no_fade
+        e.handle_audio_command(play(2, true)).await;
+        assert_eq!(e.last_transition, Some(Transition::Cut));
+        e.handle_audio_command(play(3, true)).await;
+        assert_eq!(e.last_transition, Some(Transition::Cut));
+
+        // 再次关闭：回到全局淡入淡出（且跟随运行中修改的时长）
+        e.handle_audio_command(AudioCommand::SetCrossfadeMs(800))
+            .await;
+        e.handle_audio_command(play(4, false)).await;
+        assert_eq!(e.last_transition, Some(Transition::Crossfade { ms: 800 }));
+
+        // 全局关闭淡入淡出时始终直接衔接；停止后也没有可淡出的旧歌
+        e.handle_audio_command(AudioCommand::SetCrossfadeMs(0))
+            .await;
+        e.handle_audio_command(play(5, false)).await;
+        assert_eq!(e.last_transition, Some(Transition::Cut));
+        e.handle_audio_command(AudioCommand::SetCrossfadeMs(300))
+            .await;
+        e.handle_audio_command(AudioCommand::Stop).await;
+        e.handle_audio_command(play(6, false)).await;
+        assert_eq!(e.last_transition, Some(Transition::Cut));
+    }
+
+    #[tokio::test]
+    async fn idle_output_is_released_after_stop_and_reopened_on_play() {
+        let (tx_evt, mut rx_evt) = mpsc::channel(16);
+        let (tx_cmd, rx_cmd) = mpsc::channel(4);
+        let (tx_transfer, _rx_transfer_cmd) = mpsc::channel(1);
+        let (_tx_transfer_evt, rx_transfer) = mpsc::channel(1);
+        let mut e = NullEngine::new(
+            tx_evt,
+            rx_cmd,
+            tx_transfer,
+            rx_transfer,
+            AudioSettings {
+                crossfade_ms: 0,
+                idle_release_after: Some(Duration::from_millis(30)),
+                normalize_volume: false,
+                playback_speed: 1.0,
+                eq_bass_db: 0,
+                eq_treble_db: 0,
+            },
+            TrackExports::new(None),
+        );
+        let step = Duration::from_secs(2);
+
+        tx_cmd.send(play(1, false)).await.unwrap();
+        assert!(tokio::time::timeout(step, e.step()).await.unwrap());
+        assert!(matches!(
+            rx_evt.recv().await,
+            Some(AudioEvent::NowPlaying { .. })
+        ));
+
+        // 暂停不算空闲：不会安排释放
+        tx_cmd.send(AudioCommand::TogglePause).await.unwrap();
+        assert!(tokio::time::timeout(step, e.step()).await.unwrap());
+        assert!(e.idle_deadline.is_none());
+
+        tx_cmd.send(AudioCommand::Stop).await.unwrap();
+        assert!(tokio::time::timeout(step, e.step()).await.unwrap());
+        assert!(e.output_open);
+        assert!(e.idle_deadline.is_some());
+
+        // 没有新命令时下一步就是空闲释放
+        assert!(tokio::time::timeout(step, e.step()).await.unwrap());
+        assert!(!e.output_open);
+        assert!(e.idle_deadline.is_none());
+
+        // 释放后照常播放
+        tx_cmd.send(play(2, false)).await.unwrap();
+        assert!(tokio::time::timeout(step, e.step()).await.unwrap());
+        assert!(e.output_open);
+        let _ = rx_evt.recv().await; // Paused
+        let _ = rx_evt.recv().await; // Stopped
+        assert!(matches!(
+            rx_evt.recv().await,
+            Some(AudioEvent::NowPlaying { song_id: 2, .. })
+        ));
+    }
 }
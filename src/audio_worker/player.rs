@@ -1,39 +1,84 @@
 use rodio::mixer::Mixer;
-use rodio::{Decoder, OutputStream, Sink, Source};
+use rodio::source::SeekError;
+use rodio::{
+    ChannelCount, Decoder, OutputStream, OutputStreamBuilder, Sample, SampleRate, Sink, Source,
+};
 use std::fs::File;
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+use super::eq::{EqControl, Equalizer};
 use super::streaming::StreamingSession;
 
-pub struct PlayerState {
+/// 打开的音频输出设备；丢弃即关闭设备并释放混音器中残留的音源
+struct AudioOutput {
     mixer: Mixer,
-    #[allow(dead_code)]
-    stream: OutputStream,
+    _stream: OutputStream,
+}
+
+pub struct PlayerState {
+    /// 空闲释放后为 `None`，下次创建 sink 时重新打开
+    output: Option<AudioOutput>,
     current: Option<Arc<Sink>>,
     path: Option<PathBuf>,
     play_id: u64,
     paused: bool,
     volume: f32,
+    /// 当前歌曲的响度增益（dB），音量均衡开启时叠加到 `volume` 上
+    gain_db: Option<f32>,
+    normalize: bool,
+    /// 播放倍速，新建的 sink 都按它播放
+    speed: f32,
+    /// 低音/高音增益，与所有音源共享，调整后立即生效
+    eq: Arc<EqControl>,
     seekable: bool,
 }
 
 impl PlayerState {
-    pub fn new(mixer: Mixer, stream: OutputStream) -> Self {
+    pub fn new() -> Self {
         Self {
-            mixer,
-            stream,
+            output: None,
             current: None,
             path: None,
             play_id: 0,
             paused: false,
             volume: 1.0,
+            gain_db: None,
+            normalize: false,
+            speed: 1.0,
+            eq: Arc::new(EqControl::default()),
             seekable: false,
         }
     }
 
+    /// 确保音频输出已打开（启动时与空闲释放后的首次播放共用）
+    pub fn open_output(&mut self) -> Result<&Mixer, String> {
+        let output = match self.output.take() {
+            Some(output) => output,
+            None => {
+                let mut stream = OutputStreamBuilder::open_default_stream()
+                    .map_err(|e| format!("初始化音频输出失败: {e}"))?;
+                // 空闲释放是预期行为，不需要 rodio 在丢弃时提示
+                stream.log_on_drop(false);
+                tracing::debug!("音频输出已打开");
+                AudioOutput {
+                    mixer: stream.mixer().clone(),
+                    _stream: stream,
+                }
+            }
+        };
+        Ok(&self.output.insert(output).mixer)
+    }
+
+    /// 关闭音频输出；返回之前是否处于打开状态
+    pub fn release_output(&mut self) -> bool {
+        self.stop_current();
+        self.output.take().is_some()
+    }
+
     pub fn play_id(&self) -> u64 {
         self.play_id
     }
@@ -101,27 +146,215 @@ impl PlayerState {
         self.volume
     }
 
+    pub fn set_track_gain(&mut self, gain_db: Option<f32>) {
+        self.gain_db = gain_db;
+    }
+
+    pub fn track_gain(&self) -> Option<f32> {
+        self.gain_db
+    }
+
+    pub fn set_normalize(&mut self, normalize: bool) {
+        self.normalize = normalize;
+    }
+
+    pub fn normalize(&self) -> bool {
+        self.normalize
+    }
+
+    /// 当前歌曲实际使用的音量：用户音量叠加响度增益
+    pub fn output_volume(&self) -> f32 {
+        gained_volume(self.volume, self.gain_db, self.normalize)
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    pub fn set_eq(&self, bass_db: f32, treble_db: f32) {
+        self.eq.set(bass_db, treble_db);
+    }
+
     pub fn attach_sink(&mut self, sink: Arc<Sink>) {
         self.current = Some(sink);
     }
 
     pub fn build_sink(
-        &self,
+        &mut self,
         path: &Path,
         seek: Option<Duration>,
         title: &str,
         fallback_duration_ms: Option<u64>,
     ) -> Result<(Sink, Option<u64>), String> {
-        build_sink_from_path(&self.mixer, path, seek, title, fallback_duration_ms)
+        let chain = self.chain(false);
+        build_sink_from_path(
+            self.open_output()?,
+            path,
+            seek,
+            title,
+            fallback_duration_ms,
+            chain,
+        )
+    }
+
+    /// 预先解码但不出声的 sink，用于预排的下一首
+    pub fn build_paused_sink(
+        &mut self,
+        path: &Path,
+        title: &str,
+        fallback_duration_ms: Option<u64>,
+    ) -> Result<(Sink, Option<u64>), String> {
+        let chain = self.chain(true);
+        build_sink_from_path(
+            self.open_output()?,
+            path,
+            None,
+            title,
+            fallback_duration_ms,
+            chain,
+        )
     }
 
     pub fn build_streaming_sink(
-        &self,
+        &mut self,
         session: &StreamingSession,
         title: &str,
         fallback_duration_ms: Option<u64>,
     ) -> Result<(Sink, Option<u64>), String> {
-        build_sink_from_streaming_session(&self.mixer, session, title, fallback_duration_ms)
+        let chain = self.chain(false);
+        build_sink_from_streaming_session(
+            self.open_output()?,
+            session,
+            title,
+            fallback_duration_ms,
+            chain,
+        )
+    }
+
+    /// 把下一首接在当前 sink 的队列后面：当前歌曲一播完就接着出声，不经过新建 sink
+    pub fn append_to_current(&self, path: &Path, title: &str) -> Result<AppendedTrack, String> {
+        let Some(sink) = self.current.as_ref() else {
+            return Err(format!("没有正在播放的 sink，无法接上({title})"));
+        };
+        let decoder = open_file_decoder(path, title)?;
+        Ok(append_track(sink, decoder, Arc::clone(&self.eq)))
+    }
+
+    fn chain(&self, paused: bool) -> SinkChain {
+        SinkChain {
+            speed: self.speed,
+            eq: Arc::clone(&self.eq),
+            paused,
+        }
+    }
+}
+
+/// 新建 sink 时套用的倍速、均衡器与初始暂停状态
+struct SinkChain {
+    speed: f32,
+    eq: Arc<EqControl>,
+    paused: bool,
+}
+
+impl SinkChain {
+    fn connect<S: Source + Send + 'static>(self, mixer: &Mixer, source: S) -> Sink {
+        let sink = Sink::connect_new(mixer);
+        sink.set_speed(self.speed);
+        if self.paused {
+            sink.pause();
+        }
+        sink.append(Equalizer::new(source, self.eq));
+        sink
+    }
+}
+
+/// 已接在当前 sink 后面的下一首
+pub struct AppendedTrack {
+    skip: Arc<AtomicBool>,
+    /// 接上后 sink 队列中的音源数；当前歌曲播完后队列变短，说明已轮到下一首
+    queue_len: usize,
+}
+
+impl AppendedTrack {
+    /// 下一首是否已经开始播放
+    pub fn started(&self, sink: &Sink) -> bool {
+        sink.len() < self.queue_len
+    }
+
+    /// 不再播放这一首：sink 不支持移除队列中的音源，轮到它时直接结束
+    pub fn cancel(&self) {
+        self.skip.store(true, Ordering::Relaxed);
+    }
+}
+
+fn append_track<S: Source + Send + 'static>(
+    sink: &Sink,
+    source: S,
+    eq: Arc<EqControl>,
+) -> AppendedTrack {
+    let skip = Arc::new(AtomicBool::new(false));
+    sink.append(Skippable {
+        inner: Equalizer::new(source, eq),
+        skip: Arc::clone(&skip),
+    });
+    AppendedTrack {
+        skip,
+        queue_len: sink.len(),
+    }
+}
+
+/// 可在播放前取消的音源
+struct Skippable<S> {
+    inner: S,
+    skip: Arc<AtomicBool>,
+}
+
+impl<S: Source> Iterator for Skippable<S> {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Sample> {
+        if self.skip.load(Ordering::Relaxed) {
+            return None;
+        }
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<S: Source> Source for Skippable<S> {
+    fn current_span_len(&self) -> Option<usize> {
+        self.inner.current_span_len()
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.inner.channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.inner.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        self.inner.total_duration()
+    }
+
+    fn try_seek(&mut self, pos: Duration) -> Result<(), SeekError> {
+        self.inner.try_seek(pos)
+    }
+}
+
+/// 叠加响度增益后的音量；未开启音量均衡或没有增益数据时即用户音量，结果不超过 2.0
+pub(super) fn gained_volume(volume: f32, gain_db: Option<f32>, normalize: bool) -> f32 {
+    match gain_db {
+        Some(db) if normalize && db.is_finite() => (volume * 10f32.powf(db / 20.0)).clamp(0.0, 2.0),
+        _ => volume,
     }
 }
 
@@ -145,7 +378,7 @@ pub(super) fn seek_to_ms(state: &mut PlayerState, position_ms: u64) -> Result<()
     state.stop_keep_play_id();
 
     let sink = Arc::new(sink);
-    sink.set_volume(state.volume());
+    sink.set_volume(state.output_volume());
     if state.paused() {
         sink.pause();
     } else {
@@ -168,10 +401,9 @@ fn build_sink_from_path(
     seek: Option<Duration>,
     title: &str,
     fallback_duration_ms: Option<u64>,
+    chain: SinkChain,
 ) -> Result<(Sink, Option<u64>), String> {
-    let file = File::open(path).map_err(|e| format!("打开音频文件失败({title}): {e}"))?;
-    let decoder =
-        Decoder::new(BufReader::new(file)).map_err(|e| format!("解码失败({title}): {e}"))?;
+    let decoder = open_file_decoder(path, title)?;
     let duration_ms = decoder
         .total_duration()
         .map(|d| d.as_millis() as u64)
@@ -182,9 +414,12 @@ fn build_sink_from_path(
         Box::new(decoder)
     };
 
-    let sink = Sink::connect_new(mixer);
-    sink.append(source);
-    Ok((sink, duration_ms))
+    Ok((chain.connect(mixer, source), duration_ms))
+}
+
+fn open_file_decoder(path: &Path, title: &str) -> Result<Decoder<BufReader<File>>, String> {
+    let file = File::open(path).map_err(|e| format!("打开音频文件失败({title}): {e}"))?;
+    Decoder::new(BufReader::new(file)).map_err(|e| format!("解码失败({title}): {e}"))
 }
 
 fn build_sink_from_streaming_session(
@@ -192,6 +427,7 @@ fn build_sink_from_streaming_session(
     session: &StreamingSession,
     title: &str,
     fallback_duration_ms: Option<u64>,
+    chain: SinkChain,
 ) -> Result<(Sink, Option<u64>), String> {
     let reader = session
         .open_reader()
@@ -206,7 +442,55 @@ fn build_sink_from_streaming_session(
         .map(|d| d.as_millis() as u64)
         .or(fallback_duration_ms);
 
-    let sink = Sink::connect_new(mixer);
-    sink.append(Box::new(decoder) as Box<dyn Source + Send>);
-    Ok((sink, duration_ms))
+    Ok((chain.connect(mixer, decoder), duration_ms))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rodio::buffer::SamplesBuffer;
+
+    fn tone(value: f32, len: usize) -> SamplesBuffer {
+        SamplesBuffer::new(2, 44_100, vec![value; len])
+    }
+
+    #[test]
+    fn appended_track_follows_without_silence() {
+        let eq = Arc::new(EqControl::default());
+        let (sink, output) = Sink::new();
+        sink.append(Equalizer::new(tone(0.5, 4_410), Arc::clone(&eq)));
+        let next = append_track(&sink, tone(-0.25, 4_410), Arc::clone(&eq));
+        assert!(!next.started(&sink));
+
+        let samples: Vec<f32> = output.take(4_410 * 2).collect();
+        assert!(samples[..4_410].iter().all(|&s| s == 0.5));
+        assert!(
+            samples[4_410..].iter().all(|&s| s == -0.25),
+            "两首之间不应插入静音"
+        );
+    }
+
+    #[test]
+    fn cancelled_appended_track_is_skipped() {
+        let eq = Arc::new(EqControl::default());
+        let (sink, output) = Sink::new();
+        sink.append(Equalizer::new(tone(0.5, 100), Arc::clone(&eq)));
+        append_track(&sink, tone(-0.25, 100), Arc::clone(&eq)).cancel();
+        append_track(&sink, tone(0.75, 100), Arc::clone(&eq));
+
+        let samples: Vec<f32> = output.take(200).collect();
+        assert!(samples[..100].iter().all(|&s| s == 0.5));
+        assert!(samples[100..].iter().all(|&s| s == 0.75));
+    }
+
+    #[test]
+    fn gain_scales_volume_only_when_normalizing() {
+        assert_eq!(gained_volume(0.8, Some(-6.0), false), 0.8);
+        assert_eq!(gained_volume(0.8, None, true), 0.8);
+        assert!((gained_volume(1.0, Some(-6.0), true) - 0.501).abs() < 1e-3);
+        assert!((gained_volume(0.5, Some(6.0), true) - 0.998).abs() < 1e-3);
+        // 增益后不超过 2.0
+        assert_eq!(gained_volume(1.5, Some(12.0), true), 2.0);
+        assert_eq!(gained_volume(0.8, Some(f32::NAN), true), 0.8);
+    }
 }
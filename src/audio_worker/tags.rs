@@ -0,0 +1,253 @@
+//! 导出文件的标签：MP3 写 ID3v2.4，FLAC 重写 Vorbis comment 与封面块
+//!
+//! 其它格式（M4A/OGG）暂不写标签，文件照常导出。
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use id3::TagLike;
+
+use super::messages::TrackTags;
+
+/// FLAC 元数据块类型
+const FLAC_PADDING: u8 = 1;
+const FLAC_VORBIS_COMMENT: u8 = 4;
+const FLAC_PICTURE: u8 = 6;
+/// 元数据块长度字段为 24 位
+const FLAC_MAX_BLOCK_LEN: usize = (1 << 24) - 1;
+/// 图片类型「封面（正面）」，ID3 APIC 与 FLAC PICTURE 通用
+const PICTURE_COVER_FRONT: u32 = 3;
+
+/// FLAC 元数据块：`(类型, 内容)`
+type FlacBlock<'a> = (u8, &'a [u8]);
+
+/// 已下载的封面图片
+#[derive(Debug)]
+pub(super) struct Cover {
+    mime: &'static str,
+    data: Vec<u8>,
+}
+
+impl Cover {
+    /// 按文件头识别 JPEG/PNG，其它内容（如错误页）视为无封面
+    pub(super) fn from_bytes(data: Vec<u8>) -> Option<Self> {
+        let mime = if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            "image/jpeg"
+        } else if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            "image/png"
+        } else {
+            return None;
+        };
+        Some(Self { mime, data })
+    }
+}
+
+/// 按扩展名写入标签；不支持的格式直接跳过
+pub(super) fn write(
+    path: &Path,
+    extension: &str,
+    tags: &TrackTags,
+    cover: Option<&Cover>,
+) -> io::Result<()> {
+    match extension {
+        "mp3" => write_id3(path, tags, cover).map_err(io::Error::other),
+        "flac" => write_flac(path, tags, cover),
+        _ => {
+            tracing::debug!(extension, "该格式暂不写入标签");
+            Ok(())
+        }
+    }
+}
+
+fn write_id3(path: &Path, tags: &TrackTags, cover: Option<&Cover>) -> id3::Result<()> {
+    let mut tag = id3::Tag::new();
+    tag.set_title(&tags.title);
+    if !tags.artists.is_empty() {
+        tag.set_artist(tags.artists.join("/"));
+    }
+    if !tags.album.is_empty() {
+        tag.set_album(&tags.album);
+    }
+    if let Some(no) = tags.track_no {
+        tag.set_track(no);
+    }
+    if let Some(cover) = cover {
+        tag.add_frame(id3::frame::Picture {
+            mime_type: cover.mime.to_owned(),
+            picture_type: id3::frame::PictureType::CoverFront,
+            description: String::new(),
+            data: cover.data.clone(),
+        });
+    }
+    tag.write_to_path(path, id3::Version::Id3v24)
+}
+
+/// 保留原有的 STREAMINFO 等元数据块，替换 Vorbis comment 与图片块，去掉填充块
+fn write_flac(path: &Path, tags: &TrackTags, cover: Option<&Cover>) -> io::Result<()> {
+    let data = fs::read(path)?;
+    let (mut blocks, audio_start) = flac_blocks(&data)?;
+    blocks.retain(|(kind, _)| !matches!(*kind, FLAC_PADDING | FLAC_VORBIS_COMMENT | FLAC_PICTURE));
+    let comment = vorbis_comment(tags);
+    blocks.push((FLAC_VORBIS_COMMENT, &comment));
+    let picture = cover.map(flac_picture);
+    if let Some(picture) = picture.as_deref().filter(|p| p.len() <= FLAC_MAX_BLOCK_LEN) {
+        blocks.push((FLAC_PICTURE, picture));
+    }
+
+    let mut out = Vec::with_capacity(data.len() + comment.len());
+    out.extend_from_slice(b"fLaC");
+    let last = blocks.len() - 1;
+    for (i, (kind, body)) in blocks.iter().enumerate() {
+        let flag = if i == last { 0x80 } else { 0 };
+        out.push(flag | kind);
+        out.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        out.extend_from_slice(body);
+    }
+    out.extend_from_slice(&data[audio_start..]);
+    fs::write(path, out)
+}
+
+/// 拆出元数据块，并返回音频帧的起始偏移
+fn flac_blocks(data: &[u8]) -> io::Result<(Vec<FlacBlock<'_>>, usize)> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "FLAC 元数据块不完整");
+    if !data.starts_with(b"fLaC") {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "不是 FLAC 文件"));
+    }
+    let mut blocks = Vec::new();
+    let mut pos = 4;
+    loop {
+        let header = data.get(pos..pos + 4).ok_or_else(invalid)?;
+        let len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let body = data.get(pos + 4..pos + 4 + len).ok_or_else(invalid)?;
+        blocks.push((header[0] & 0x7F, body));
+        pos += 4 + len;
+        if header[0] & 0x80 != 0 {
+            return Ok((blocks, pos));
+        }
+    }
+}
+
+/// Vorbis comment（小端长度）；多位歌手各占一条 `ARTIST`
+fn vorbis_comment(tags: &TrackTags) -> Vec<u8> {
+    let mut fields = vec![format!("TITLE={}", tags.title)];
+    fields.extend(tags.artists.iter().map(|a| format!("ARTIST={a}")));
+    if !tags.album.is_empty() {
+        fields.push(format!("ALBUM={}", tags.album));
+    }
+    if let Some(no) = tags.track_no {
+        fields.push(format!("TRACKNUMBER={no}"));
+    }
+
+    let vendor = concat!("netease-ratui ", env!("CARGO_PKG_VERSION"));
+    let mut out = Vec::new();
+    out.extend_from_slice(&(vendor.len() as u32).to_le_bytes());
+    out.extend_from_slice(vendor.as_bytes());
+    out.extend_from_slice(&(fields.len() as u32).to_le_bytes());
+    for field in &fields {
+        out.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        out.extend_from_slice(field.as_bytes());
+    }
+    out
+}
+
+/// FLAC PICTURE 块（大端长度）；宽高等信息填 0，播放器会自行解码
+fn flac_picture(cover: &Cover) -> Vec<u8> {
+    let mut out = Vec::with_capacity(cover.data.len() + 64);
+    out.extend_from_slice(&PICTURE_COVER_FRONT.to_be_bytes());
+    out.extend_from_slice(&(cover.mime.len() as u32).to_be_bytes());
+    out.extend_from_slice(cover.mime.as_bytes());
+    // 描述为空；宽、高、色深、索引色数
+    out.extend_from_slice(&[0; 4 * 5]);
+    out.extend_from_slice(&(cover.data.len() as u32).to_be_bytes());
+    out.extend_from_slice(&cover.data);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags() -> TrackTags {
+        TrackTags {
+            title: "晴天".to_owned(),
+            artists: vec!["周杰伦".to_owned(), "五月天".to_owned()],
+            album: "叶惠美".to_owned(),
+            track_no: Some(3),
+            cover_url: None,
+        }
+    }
+
+    fn jpeg() -> Cover {
+        Cover::from_bytes(vec![0xFF, 0xD8, 0xFF, 0xE0, 1, 2, 3]).expect("jpeg")
+    }
+
+    #[test]
+    fn cover_requires_an_image_header() {
+        assert!(Cover::from_bytes(b"<html>404</html>".to_vec()).is_none());
+        assert_eq!(jpeg().mime, "image/jpeg");
+    }
+
+    #[test]
+    fn mp3_gets_an_id3_tag_with_cover() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("a.mp3");
+        fs::write(&path, [0xFF, 0xFB, 0x90, 0x00, 0, 0, 0, 0]).unwrap();
+
+        write(&path, "mp3", &tags(), Some(&jpeg())).unwrap();
+
+        let tag = id3::Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.title(), Some("晴天"));
+        assert_eq!(tag.artist(), Some("周杰伦/五月天"));
+        assert_eq!(tag.album(), Some("叶惠美"));
+        assert_eq!(tag.track(), Some(3));
+        assert_eq!(tag.pictures().next().map(|p| p.data.len()), Some(7));
+        assert!(
+            fs::read(&path)
+                .unwrap()
+                .ends_with(&[0xFF, 0xFB, 0x90, 0x00, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn flac_comments_are_replaced_and_audio_kept() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("a.flac");
+        let mut data = b"fLaC".to_vec();
+        // STREAMINFO（34 字节）、旧的 Vorbis comment、最后一块为填充
+        data.extend_from_slice(&[0, 0, 0, 34]);
+        data.extend_from_slice(&[7; 34]);
+        data.extend_from_slice(&[FLAC_VORBIS_COMMENT, 0, 0, 2, 9, 9]);
+        data.extend_from_slice(&[0x80 | FLAC_PADDING, 0, 0, 3, 0, 0, 0]);
+        data.extend_from_slice(b"FRAMES");
+        fs::write(&path, &data).unwrap();
+
+        write(&path, "flac", &tags(), Some(&jpeg())).unwrap();
+
+        let out = fs::read(&path).unwrap();
+        let (blocks, audio_start) = flac_blocks(&out).unwrap();
+        let kinds: Vec<u8> = blocks.iter().map(|(k, _)| *k).collect();
+        assert_eq!(kinds, [0, FLAC_VORBIS_COMMENT, FLAC_PICTURE]);
+        assert_eq!(blocks[0].1, &[7; 34]);
+        assert_eq!(blocks[1].1, vorbis_comment(&tags()).as_slice());
+        assert_eq!(&out[audio_start..], b"FRAMES");
+
+        let comment = String::from_utf8_lossy(blocks[1].1);
+        for field in [
+            "TITLE=晴天",
+            "ARTIST=周杰伦",
+            "ARTIST=五月天",
+            "TRACKNUMBER=3",
+        ] {
+            assert!(comment.contains(field), "{field}");
+        }
+    }
+
+    #[test]
+    fn truncated_flac_is_rejected() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("a.flac");
+        fs::write(&path, b"fLaC\x00\x00\x00\x22short").unwrap();
+        assert!(write(&path, "flac", &tags(), None).is_err());
+    }
+}
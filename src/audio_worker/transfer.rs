@@ -1,9 +1,10 @@
 use reqwest::Client;
 use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 use std::env;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
 use std::time::Duration;
 
 use tokio::sync::Semaphore;
@@ -12,8 +13,11 @@ use tokio::sync::mpsc;
 use super::cache::AudioCache;
 use super::download::{
     download_to_path_for_streaming_with_config, download_to_path_with_config, now_ms,
+    track_cooldown, with_proxy,
 };
+use super::migrate::{MigrationSummary, move_cache_files};
 use super::streaming::StreamingSession;
+use crate::cooldown::{CooldownHandle, host_of};
 use crate::error::DownloadError;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -44,8 +48,12 @@ pub enum TransferCommand {
         token: u64,
         key: CacheKey,
         url: String,
+        /// Expected file size from the SongUrl response, used to validate the download.
+        expected_bytes: Option<u64>,
         title: String,
         priority: Priority,
+        /// Bitrate the entry may still be cached under (keys used to be the requested br).
+        legacy_br: Option<i64>,
     },
     /// Cancel a pending waiter for this key/token (best-effort).
     Cancel {
@@ -59,11 +67,29 @@ pub enum TransferCommand {
     ClearAll {
         keep: Option<PathBuf>,
     },
-    /// Keep only this bitrate in cache (best-effort).
-    PurgeNotBr {
+    /// Drop cached entries above this bitrate (best-effort).
+    PurgeAboveBr {
         br: i64,
         keep: Option<PathBuf>,
     },
+    /// Playback of this key started (refreshes its LRU time); `None` when stopped.
+    /// The playing entry is never evicted.
+    MarkPlaying {
+        key: Option<CacheKey>,
+    },
+    /// Report file count and total size (answered with `CacheStats`).
+    QueryStats,
+    /// Report which of these songs are cached at or below `br` (answered with `CachedSongs`).
+    QueryCached {
+        song_ids: Vec<i64>,
+        br: i64,
+    },
+    /// Move cache files left in `from` (an earlier cache dir) into the current one.
+    MigrateFrom {
+        from: PathBuf,
+    },
+    /// Stop a running migration after the current file.
+    CancelMigration,
 }
 
 #[derive(Debug)]
@@ -81,6 +107,8 @@ pub enum TransferEvent {
         key: CacheKey,
         downloaded_bytes: u64,
         total_bytes: Option<u64>,
+        /// 最近 2 秒的平均下载速度（字节/秒）；样本不足时为 None，停滞时为 0
+        bytes_per_sec: Option<u64>,
     },
     Retrying {
         token: u64,
@@ -101,11 +129,35 @@ pub enum TransferEvent {
     Error {
         token: u64,
         message: String,
+        /// The URL served something that is not the song (expired CDN link); re-resolve it.
+        stale_url: bool,
     },
     CacheCleared {
         files: usize,
         bytes: u64,
     },
+    /// 下载完成后缓存超出上限，按最近访问时间删除了旧文件
+    CacheEvicted {
+        files: usize,
+        bytes: u64,
+    },
+    CacheStats {
+        files: usize,
+        bytes: u64,
+    },
+    CachedSongs {
+        song_ids: Vec<i64>,
+    },
+    /// 该 host 连续被拒绝，进入冷却（期间暂停后台下载）
+    Cooldown {
+        duration: Duration,
+    },
+    MigrateProgress {
+        done: usize,
+        total: usize,
+    },
+    /// 移动结束（含取消），新文件已登记进缓存索引
+    Migrated(MigrationSummary),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -141,11 +193,61 @@ impl Eq for HeapItem {}
 struct JobState {
     waiters: Vec<u64>,
     url: String,
+    expected_bytes: Option<u64>,
     title: String,
     prio: u8,
     in_flight: bool,
     playable_emitted: bool,
     session: Option<StreamingSession>,
+    speed: SpeedMeter,
+    total_bytes: Option<u64>,
+}
+
+/// 下载速度的滑动窗口平均：保留窗口内的样本，外加窗口前最后一个样本作为基线
+#[derive(Debug, Default)]
+struct SpeedMeter {
+    samples: VecDeque<(u64, u64)>,
+}
+
+impl SpeedMeter {
+    const WINDOW_MS: u64 = 2_000;
+
+    /// 记录 `now_ms` 时刻已下载的字节数；字节数回退（重试重新下载）时从头计算
+    fn record(&mut self, now_ms: u64, bytes: u64) {
+        if self.samples.back().is_some_and(|&(_, last)| bytes < last) {
+            self.samples.clear();
+        }
+        self.samples.push_back((now_ms, bytes));
+        self.prune(now_ms);
+    }
+
+    fn prune(&mut self, now_ms: u64) {
+        let cutoff = now_ms.saturating_sub(Self::WINDOW_MS);
+        while self.samples.len() > 1 && self.samples[1].0 <= cutoff {
+            self.samples.pop_front();
+        }
+    }
+
+    fn last_sample_ms(&self) -> Option<u64> {
+        self.samples.back().map(|&(at, _)| at)
+    }
+
+    fn last_bytes(&self) -> Option<u64> {
+        self.samples.back().map(|&(_, bytes)| bytes)
+    }
+
+    /// 截至 `now_ms` 的平均速度（字节/秒）；少于两个样本时无法估计
+    fn bytes_per_sec(&mut self, now_ms: u64) -> Option<u64> {
+        self.prune(now_ms);
+        let &(first_at, first_bytes) = self.samples.front()?;
+        let &(_, last_bytes) = self.samples.back()?;
+        if self.samples.len() < 2 {
+            // 窗口内没有新数据：只剩基线且已超出窗口视为停滞
+            return (now_ms.saturating_sub(first_at) >= Self::WINDOW_MS).then_some(0);
+        }
+        let elapsed = now_ms.saturating_sub(first_at).max(1);
+        Some((last_bytes - first_bytes).saturating_mul(1000) / elapsed)
+    }
 }
 
 #[derive(Debug)]
@@ -172,12 +274,19 @@ enum JobResult {
         key: CacheKey,
         message: DownloadError,
     },
+    MigrateProgress {
+        done: usize,
+        total: usize,
+    },
+    Migrated(MigrationSummary),
 }
 
 pub type TransferSender = mpsc::Sender<TransferCommand>;
 pub type TransferReceiver = mpsc::Receiver<TransferEvent>;
 
 const STREAMING_PREBUFFER_BYTES: u64 = 256 * 1024;
+/// 超过该时长没有新进度时补发一次（毫秒）
+const STALL_REPORT_MS: u64 = 1_000;
 
 /// 传输配置
 #[derive(Debug, Clone)]
@@ -196,6 +305,10 @@ pub struct TransferConfig {
     pub download_retry_backoff_max_ms: u64,
     /// 音频缓存大小（MB）
     pub audio_cache_max_mb: usize,
+    /// 按 host 的限流冷却（与 NeteaseActor 共享）
+    pub cooldown: CooldownHandle,
+    /// 下载经由的代理（与网易接口相同，见 `NeteaseClientConfig::proxy`）
+    pub proxy: Option<String>,
 }
 
 impl Default for TransferConfig {
@@ -229,21 +342,24 @@ impl Default for TransferConfig {
                 .ok()
                 .and_then(|s| s.parse().ok())
                 .unwrap_or(2048),
+            cooldown: CooldownHandle::default(),
+            proxy: None,
         }
     }
 }
 
 pub fn spawn_transfer_actor_with_config(
-    data_dir: PathBuf,
+    cache_dir: PathBuf,
     config: TransferConfig,
 ) -> (TransferSender, TransferReceiver) {
     let (tx_cmd, rx_cmd) = mpsc::channel::<TransferCommand>(256);
     let (tx_evt, rx_evt) = mpsc::channel::<TransferEvent>(256);
 
     let run = async move {
-        let http = Client::builder()
+        let builder = Client::builder()
             .timeout(Duration::from_secs(config.http_timeout_secs))
-            .connect_timeout(Duration::from_secs(config.http_connect_timeout_secs))
+            .connect_timeout(Duration::from_secs(config.http_connect_timeout_secs));
+        let http = with_proxy(builder, config.proxy.as_deref())
             .build()
             .unwrap_or_else(|e| {
                 tracing::error!(err = %e, "初始化 HTTP 客户端失败");
@@ -266,7 +382,7 @@ pub fn spawn_transfer_actor_with_config(
             "TransferActor 已启动（配置化模式）"
         );
 
-        let mut cache = AudioCache::new_with_config(&data_dir, config.audio_cache_max_mb);
+        let mut cache = AudioCache::new_with_config(&cache_dir, config.audio_cache_max_mb);
         let cache_dir = cache.cache_dir().map(|p| p.to_path_buf());
 
         let (tx_done, mut rx_done) = mpsc::channel::<JobResult>(256);
@@ -275,16 +391,25 @@ pub fn spawn_transfer_actor_with_config(
         let mut heap = BinaryHeap::<HeapItem>::new();
         let mut jobs = HashMap::<CacheKey, JobState>::new();
         let mut active_br: i64 = 0;
+        let mut playing = None::<CacheKey>;
         let mut tmp_seq: u64 = 1;
+        // host 冷却期间推迟的后台下载，到期后重新入队
+        let mut deferred = Vec::<CacheKey>::new();
+        let mut deferred_until = None::<tokio::time::Instant>;
+        // 正在移动旧缓存时的取消标记
+        let mut migration = None::<Arc<AtomicBool>>;
+
+        let mut stall_tick = tokio::time::interval(Duration::from_millis(STALL_REPORT_MS));
+        stall_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
         let mut rx_cmd = rx_cmd;
         loop {
             tokio::select! {
                 Some(cmd) = rx_cmd.recv() => {
                     match cmd {
-                        TransferCommand::EnsureCached { token, key, url, title, priority } => {
+                        TransferCommand::EnsureCached { token, key, url, expected_bytes, title, priority, legacy_br } => {
                             // Fast path: cache hit.
-                            if let Some(path) = cache.lookup_path(key.song_id, key.br) {
+                            if let Some(path) = cache.lookup_path_or_migrate(key.song_id, key.br, legacy_br) {
                                 tracing::info!(
                                     song_id = key.song_id,
                                     br = key.br,
@@ -314,13 +439,17 @@ pub fn spawn_transfer_actor_with_config(
                             let st = jobs.entry(key).or_insert(JobState {
                                 waiters: Vec::new(),
                                 url: url.clone(),
+                                expected_bytes,
                                 title: title.clone(),
                                 prio: priority.as_u8(),
                                 in_flight: false,
                                 playable_emitted: false,
                                 session: None,
+                                speed: SpeedMeter::default(),
+                                total_bytes: None,
                             });
                             st.url = url;
+                            st.expected_bytes = expected_bytes;
                             st.title = title;
                             st.prio = st.prio.max(priority.as_u8());
                             st.waiters.push(token);
@@ -378,10 +507,51 @@ pub fn spawn_transfer_actor_with_config(
                             let (files, bytes) = cache.clear_all(keep.as_deref());
                             let _ = tx_evt.send(TransferEvent::CacheCleared { files, bytes }).await;
                         }
-                        TransferCommand::PurgeNotBr { br, keep } => {
-                            tracing::info!(br, "cache purge other bitrates");
+                        TransferCommand::PurgeAboveBr { br, keep } => {
+                            tracing::info!(br, "cache purge higher bitrates");
                             active_br = br;
-                            cache.purge_not_br(br, keep.as_deref());
+                            cache.purge_above_br(br, keep.as_deref());
+                        }
+                        TransferCommand::MarkPlaying { key } => {
+                            if let Some(key) = key {
+                                cache.touch_key(key.song_id, key.br);
+                            }
+                            playing = key;
+                        }
+                        TransferCommand::QueryStats => {
+                            let (files, bytes) = cache.stats();
+                            let _ = tx_evt.send(TransferEvent::CacheStats { files, bytes }).await;
+                        }
+                        TransferCommand::QueryCached { song_ids, br } => {
+                            let song_ids = cache.cached_among(&song_ids, br);
+                            let _ = tx_evt.send(TransferEvent::CachedSongs { song_ids }).await;
+                        }
+                        TransferCommand::MigrateFrom { from } => {
+                            let Some(to) = cache_dir.clone().filter(|to| *to != from) else {
+                                tracing::warn!(from = %from.display(), "缓存目录不可用或与来源相同，跳过移动");
+                                let _ = tx_evt.send(TransferEvent::Migrated(MigrationSummary::default())).await;
+                                continue;
+                            };
+                            if migration.is_some() {
+                                tracing::warn!("已有移动缓存任务在进行，忽略重复请求");
+                                continue;
+                            }
+                            tracing::info!(from = %from.display(), to = %to.display(), "开始移动旧缓存");
+                            let cancel = Arc::new(AtomicBool::new(false));
+                            migration = Some(cancel.clone());
+                            let tx_done = tx_done.clone();
+                            tokio::task::spawn_blocking(move || {
+                                let summary = move_cache_files(&from, &to, &cancel, |done, total| {
+                                    let _ = tx_done.blocking_send(JobResult::MigrateProgress { done, total });
+                                });
+                                let _ = tx_done.blocking_send(JobResult::Migrated(summary));
+                            });
+                        }
+                        TransferCommand::CancelMigration => {
+                            if let Some(cancel) = migration.as_ref() {
+                                tracing::info!("取消移动旧缓存");
+                                cancel.store(true, AtomicOrdering::Relaxed);
+                            }
                         }
                     }
                 }
@@ -392,7 +562,11 @@ pub fn spawn_transfer_actor_with_config(
                             downloaded_bytes,
                             total_bytes,
                         } => {
-                            if let Some(st) = jobs.get(&key) {
+                            if let Some(st) = jobs.get_mut(&key) {
+                                let now = now_ms();
+                                st.speed.record(now, downloaded_bytes);
+                                st.total_bytes = total_bytes;
+                                let bytes_per_sec = st.speed.bytes_per_sec(now);
                                 for token in st.waiters.iter().copied().filter(|t| *t != 0) {
                                     let _ = tx_evt
                                         .send(TransferEvent::Progress {
@@ -400,6 +574,7 @@ pub fn spawn_transfer_actor_with_config(
                                             key,
                                             downloaded_bytes,
                                             total_bytes,
+                                            bytes_per_sec,
                                         })
                                         .await;
                                 }
@@ -439,6 +614,9 @@ pub fn spawn_transfer_actor_with_config(
                             }
                         }
                         JobResult::Ok { key, tmp_path } => {
+                            if let Some(st) = jobs.get(&key) {
+                                track_cooldown(&config.cooldown, &st.url, None);
+                            }
                             let final_path = match cache.commit_tmp_file(key.song_id, key.br, &tmp_path) {
                                 Ok(p) => p,
                                 Err(e) => {
@@ -452,7 +630,7 @@ pub fn spawn_transfer_actor_with_config(
                                     // Fan out errors to waiters.
                                     if let Some(st) = jobs.remove(&key) {
                                         for token in st.waiters.into_iter().filter(|t| *t != 0) {
-                                            let _ = tx_evt.send(TransferEvent::Error { token, message: e.to_string() }).await;
+                                            let _ = tx_evt.send(TransferEvent::Error { token, message: e.to_string(), stale_url: false }).await;
                                         }
                                     }
                                     continue;
@@ -465,17 +643,24 @@ pub fn spawn_transfer_actor_with_config(
                                 "download complete"
                             );
 
-                            // Enforce "only keep current br" policy (best-effort).
+                            // Enforce "nothing above current br" policy (best-effort).
                             if active_br != 0 {
-                                if key.br == active_br {
-                                    // 下载的是保留音质，仅清理这首歌的其他音质
+                                if key.br <= active_br {
+                                    // 不高于设置的音质（含服务端降级），仅清理这首歌的其他音质
                                     cache.purge_song_other_brs(key.song_id, key.br, None);
                                 } else {
-                                    // 下载的不是保留音质，全局清理所有非保留音质的文件
-                                    cache.purge_not_br(active_br, None);
+                                    // 高于设置的音质，全局清理
+                                    cache.purge_above_br(active_br, None);
                                 }
                             }
 
+                            // 超出缓存上限时按 LRU 清理；正在播放与正在下载（含刚完成）的不删除
+                            let protected = playing.into_iter().chain(jobs.keys().copied()).collect::<Vec<_>>();
+                            let (files, bytes) = cache.evict_to_limit(&protected);
+                            if files > 0 {
+                                let _ = tx_evt.send(TransferEvent::CacheEvicted { files, bytes }).await;
+                            }
+
                             if let Some(st) = jobs.remove(&key) {
                                 for token in st.waiters.into_iter().filter(|t| *t != 0) {
                                     let _ = tx_evt.send(TransferEvent::Ready { token, key, path: final_path.clone() }).await;
@@ -489,12 +674,80 @@ pub fn spawn_transfer_actor_with_config(
                                 err = %message,
                                 "download failed"
                             );
+                            let stale_url = message.is_stale_url();
+                            if let Some((host, duration)) = jobs
+                                .get(&key)
+                                .and_then(|st| track_cooldown(&config.cooldown, &st.url, Some(&message)))
+                            {
+                                tracing::warn!(host = %host, secs = duration.as_secs(), "CDN 限流，进入冷却");
+                                let _ = tx_evt.send(TransferEvent::Cooldown { duration }).await;
+                            }
                             if let Some(st) = jobs.remove(&key) {
                                 for token in st.waiters.into_iter().filter(|t| *t != 0) {
-                                    let _ = tx_evt.send(TransferEvent::Error { token, message: message.to_string() }).await;
+                                    let _ = tx_evt.send(TransferEvent::Error { token, message: message.to_string(), stale_url }).await;
                                 }
                             }
                         }
+                        JobResult::MigrateProgress { done, total } => {
+                            let _ = tx_evt.send(TransferEvent::MigrateProgress { done, total }).await;
+                        }
+                        JobResult::Migrated(summary) => {
+                            migration = None;
+                            let adopted = cache.adopt_untracked();
+                            tracing::info!(
+                                files = summary.files,
+                                bytes = summary.bytes,
+                                failed = summary.failed,
+                                cancelled = summary.cancelled,
+                                adopted,
+                                "旧缓存移动结束"
+                            );
+                            // 移入的文件可能让缓存超出上限
+                            let protected = playing.into_iter().chain(jobs.keys().copied()).collect::<Vec<_>>();
+                            let (files, bytes) = cache.evict_to_limit(&protected);
+                            if files > 0 {
+                                let _ = tx_evt.send(TransferEvent::CacheEvicted { files, bytes }).await;
+                            }
+                            let _ = tx_evt.send(TransferEvent::Migrated(summary)).await;
+                        }
+                    }
+                }
+                _ = tokio::time::sleep_until(deferred_until.unwrap_or_else(tokio::time::Instant::now)),
+                    if deferred_until.is_some() =>
+                {
+                    deferred_until = None;
+                    tracing::info!(count = deferred.len(), "冷却结束，恢复后台下载");
+                    for key in deferred.drain(..) {
+                        if let Some(st) = jobs.get(&key).filter(|st| !st.in_flight) {
+                            heap.push(HeapItem { prio: st.prio, seq, key });
+                            seq = seq.wrapping_add(1);
+                        }
+                    }
+                }
+                // 数据停止到达时没有进度回调，定时补发进度让速度衰减到 0
+                _ = stall_tick.tick(), if jobs.values().any(|st| st.in_flight && st.speed.last_sample_ms().is_some()) => {
+                    let now = now_ms();
+                    for (key, st) in jobs.iter_mut().filter(|(_, st)| st.in_flight) {
+                        let (Some(last_at), Some(downloaded_bytes)) =
+                            (st.speed.last_sample_ms(), st.speed.last_bytes())
+                        else {
+                            continue;
+                        };
+                        if now.saturating_sub(last_at) < STALL_REPORT_MS {
+                            continue;
+                        }
+                        let bytes_per_sec = st.speed.bytes_per_sec(now);
+                        for token in st.waiters.iter().copied().filter(|t| *t != 0) {
+                            let _ = tx_evt
+                                .send(TransferEvent::Progress {
+                                    token,
+                                    key: *key,
+                                    downloaded_bytes,
+                                    total_bytes: st.total_bytes,
+                                    bytes_per_sec,
+                                })
+                                .await;
+                        }
                     }
                 }
                 else => break,
@@ -531,6 +784,24 @@ pub fn spawn_transfer_actor_with_config(
                     drop(permit);
                     continue;
                 };
+
+                // host 冷却中：后台下载推迟到冷却结束，用户触发的播放只尝试一次
+                let cooling = host_of(&st.url).and_then(|host| config.cooldown.remaining(&host));
+                if let Some(remaining) = cooling
+                    && st.prio == Priority::Low.as_u8()
+                {
+                    tracing::debug!(
+                        song_id = key.song_id,
+                        br = key.br,
+                        remaining_secs = remaining.as_secs(),
+                        "host 冷却中，推迟后台下载"
+                    );
+                    deferred.push(key);
+                    let wake = tokio::time::Instant::now() + remaining;
+                    deferred_until = Some(deferred_until.map_or(wake, |t| t.min(wake)));
+                    drop(permit);
+                    continue;
+                }
                 st.in_flight = true;
 
                 let Some(dir) = cache_dir.as_ref() else {
@@ -550,6 +821,7 @@ pub fn spawn_transfer_actor_with_config(
                             .send(TransferEvent::Error {
                                 token,
                                 message: message.clone(),
+                                stale_url: false,
                             })
                             .await;
                     }
@@ -560,11 +832,16 @@ pub fn spawn_transfer_actor_with_config(
                 tmp_seq = tmp_seq.wrapping_add(1);
 
                 let url = st.url.clone();
+                let expected_bytes = st.expected_bytes;
                 let title = st.title.clone();
                 let progressive = st.waiters.iter().any(|t| *t != 0);
                 let http = http.clone();
                 let tx_done = tx_done.clone();
-                let retries = config.download_retries;
+                let retries = if cooling.is_some() {
+                    0
+                } else {
+                    config.download_retries
+                };
                 let backoff_ms = config.download_retry_backoff_ms;
                 let backoff_max_ms = config.download_retry_backoff_max_ms;
                 let streaming_session =
@@ -589,6 +866,7 @@ pub fn spawn_transfer_actor_with_config(
                             &tmp_path,
                             &url,
                             &title,
+                            expected_bytes,
                             retries,
                             backoff_ms,
                             backoff_max_ms,
@@ -656,6 +934,7 @@ pub fn spawn_transfer_actor_with_config(
                             &tmp_path,
                             &url,
                             &title,
+                            expected_bytes,
                             retries,
                             backoff_ms,
                             backoff_max_ms,
@@ -735,7 +1014,7 @@ pub fn spawn_transfer_actor_with_config(
     };
 
     if tokio::runtime::Handle::try_current().is_ok() {
-        tokio::spawn(run);
+        crate::crash::supervise("TransferActor", tokio::spawn(run));
     } else {
         std::thread::spawn(move || {
             // 在独立线程中创建 tokio runtime 用于音频下载
@@ -778,3 +1057,47 @@ fn tmp_path_for(dir: &Path, key: CacheKey, seq: u64) -> PathBuf {
         seq,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SpeedMeter;
+
+    #[test]
+    fn speed_meter_averages_over_last_two_seconds() {
+        let mut meter = SpeedMeter::default();
+        meter.record(0, 0);
+        assert_eq!(meter.bytes_per_sec(0), None);
+
+        meter.record(1_000, 100_000);
+        assert_eq!(meter.bytes_per_sec(1_000), Some(100_000));
+
+        // 起步慢、后来变快：窗口外的旧样本不再拉低速度
+        meter.record(2_000, 200_000);
+        meter.record(3_000, 1_200_000);
+        meter.record(4_000, 2_200_000);
+        assert_eq!(meter.bytes_per_sec(4_000), Some(1_000_000));
+    }
+
+    #[test]
+    fn speed_meter_decays_to_zero_when_stalled() {
+        let mut meter = SpeedMeter::default();
+        meter.record(0, 0);
+        meter.record(500, 50_000);
+        assert_eq!(meter.bytes_per_sec(1_000), Some(50_000));
+        // 数据停止到达：速度随时间下降，超出窗口后为 0
+        assert_eq!(meter.bytes_per_sec(2_000), Some(25_000));
+        assert_eq!(meter.bytes_per_sec(2_600), Some(0));
+    }
+
+    #[test]
+    fn speed_meter_restarts_when_bytes_go_backwards() {
+        let mut meter = SpeedMeter::default();
+        meter.record(0, 0);
+        meter.record(1_000, 500_000);
+        // 重试从头下载
+        meter.record(1_500, 10_000);
+        assert_eq!(meter.bytes_per_sec(1_500), None);
+        meter.record(2_500, 110_000);
+        assert_eq!(meter.bytes_per_sec(2_500), Some(100_000));
+    }
+}
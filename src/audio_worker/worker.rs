@@ -9,7 +9,7 @@ use super::{AudioBackend, AudioSettings};
 
 pub fn spawn_audio_worker(
     backend: AudioBackend,
-    data_dir: PathBuf,
+    cache_dir: PathBuf,
     transfer_config: TransferConfig,
     settings: AudioSettings,
 ) -> (mpsc::Sender<AudioCommand>, mpsc::Receiver<AudioEvent>) {
@@ -18,10 +18,10 @@ pub fn spawn_audio_worker(
 
     match backend {
         AudioBackend::Real => {
-            engine::spawn(rx_cmd, tx_evt, data_dir, transfer_config, settings);
+            engine::spawn(rx_cmd, tx_evt, cache_dir, transfer_config, settings);
         }
         AudioBackend::Null => {
-            null_engine::spawn(rx_cmd, tx_evt, data_dir, transfer_config, settings);
+            null_engine::spawn(rx_cmd, tx_evt, cache_dir, transfer_config, settings);
         }
     }
 
@@ -0,0 +1,271 @@
+//! 按 host 的限流冷却
+//!
+//! CDN 连续返回 403（链接过期/限流）或接口返回 -460 时，后台下载与预取仍会不断重试，
+//! 容易导致 IP 被临时封禁。一分钟内同一 host 出现 3 次此类失败即进入冷却期
+//! （指数增长，最长 5 分钟）：冷却期间推迟非用户触发的请求，用户主动播放仍尝试一次。
+//!
+//! [`HostCooldown`] 是纯状态机，所有方法显式传入当前时间以便测试；
+//! TransferActor 与 NeteaseActor 通过 [`CooldownHandle`] 共享同一份状态。
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// 冷却策略
+#[derive(Debug, Clone, Copy)]
+pub struct CooldownPolicy {
+    /// 统计失败次数的时间窗口
+    pub window: Duration,
+    /// 窗口内触发冷却的失败次数
+    pub threshold: usize,
+    /// 首次冷却时长，之后每次翻倍
+    pub base: Duration,
+    /// 冷却时长上限
+    pub max: Duration,
+}
+
+impl Default for CooldownPolicy {
+    fn default() -> Self {
+        Self {
+            window: Duration::from_secs(60),
+            threshold: 3,
+            base: Duration::from_secs(60),
+            max: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    failures: VecDeque<Instant>,
+    until: Option<Instant>,
+    /// 连续触发冷却的次数（决定下一次冷却时长），请求成功后清零
+    level: u32,
+}
+
+#[derive(Debug)]
+pub struct HostCooldown {
+    policy: CooldownPolicy,
+    hosts: HashMap<String, HostState>,
+}
+
+impl Default for HostCooldown {
+    fn default() -> Self {
+        Self::with_policy(CooldownPolicy::default())
+    }
+}
+
+impl HostCooldown {
+    pub fn with_policy(policy: CooldownPolicy) -> Self {
+        Self {
+            policy,
+            hosts: HashMap::new(),
+        }
+    }
+
+    /// 记录一次限流类失败；因此进入冷却时返回冷却时长
+    ///
+    /// 冷却期间的失败（用户主动播放的那一次尝试）不再累计。
+    pub fn record_failure(&mut self, host: &str, now: Instant) -> Option<Duration> {
+        let policy = self.policy;
+        let st = self.hosts.entry(host.to_owned()).or_default();
+        if st.until.is_some_and(|until| now < until) {
+            return None;
+        }
+
+        st.failures.push_back(now);
+        while st
+            .failures
+            .front()
+            .is_some_and(|t| now.saturating_duration_since(*t) > policy.window)
+        {
+            st.failures.pop_front();
+        }
+        if st.failures.len() < policy.threshold {
+            return None;
+        }
+
+        let factor = 2u32.saturating_pow(st.level.min(16));
+        let duration = policy.base.saturating_mul(factor).min(policy.max);
+        st.failures.clear();
+        st.until = Some(now + duration);
+        st.level = st.level.saturating_add(1);
+        Some(duration)
+    }
+
+    /// 请求成功：清空失败记录并重置冷却时长
+    pub fn record_success(&mut self, host: &str) {
+        self.hosts.remove(host);
+    }
+
+    /// 剩余冷却时间；不在冷却期时返回 None
+    pub fn remaining(&self, host: &str, now: Instant) -> Option<Duration> {
+        self.hosts
+            .get(host)
+            .and_then(|st| st.until)
+            .filter(|until| now < *until)
+            .map(|until| until - now)
+    }
+}
+
+/// 多个 actor 共享的冷却状态（内部加锁，临界区很短）
+#[derive(Debug, Clone, Default)]
+pub struct CooldownHandle(Arc<Mutex<HostCooldown>>);
+
+impl CooldownHandle {
+    pub fn record_failure(&self, host: &str) -> Option<Duration> {
+        self.lock().record_failure(host, Instant::now())
+    }
+
+    pub fn record_success(&self, host: &str) {
+        self.lock().record_success(host);
+    }
+
+    pub fn remaining(&self, host: &str) -> Option<Duration> {
+        self.lock().remaining(host, Instant::now())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HostCooldown> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 提取 URL 的 host（用作冷却的 key）
+pub fn host_of(url: &str) -> Option<String> {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_owned))
+}
+
+/// 接口返回的限流类业务码（-460/-462：风控拦截）
+pub fn is_rate_limited_code(code: i64) -> bool {
+    matches!(code, -460 | -462)
+}
+
+/// 冷却时长的展示文本（向上取整到分钟）
+pub fn minutes_label(duration: Duration) -> String {
+    format!("{} 分钟", duration.as_secs().div_ceil(60).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: &str = "m701.music.126.net";
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn three_failures_within_window_trigger_cooldown() {
+        let mut cd = HostCooldown::default();
+        let t0 = Instant::now();
+        assert_eq!(cd.record_failure(HOST, t0), None);
+        assert_eq!(cd.record_failure(HOST, t0 + secs(10)), None);
+        assert_eq!(cd.remaining(HOST, t0 + secs(10)), None);
+
+        assert_eq!(cd.record_failure(HOST, t0 + secs(20)), Some(secs(60)));
+        assert_eq!(cd.remaining(HOST, t0 + secs(50)), Some(secs(30)));
+        assert_eq!(cd.remaining(HOST, t0 + secs(80)), None, "冷却到期");
+    }
+
+    #[test]
+    fn failures_outside_window_do_not_accumulate() {
+        let mut cd = HostCooldown::default();
+        let t0 = Instant::now();
+        cd.record_failure(HOST, t0);
+        cd.record_failure(HOST, t0 + secs(30));
+        assert_eq!(cd.record_failure(HOST, t0 + secs(61)), None);
+        assert_eq!(cd.record_failure(HOST, t0 + secs(62)), Some(secs(60)));
+    }
+
+    #[test]
+    fn cooldown_grows_exponentially_and_is_capped() {
+        let mut cd = HostCooldown::default();
+        let mut now = Instant::now();
+        let mut durations = Vec::new();
+        for _ in 0..5 {
+            let mut triggered = None;
+            for _ in 0..3 {
+                triggered = cd.record_failure(HOST, now);
+            }
+            let d = triggered.expect("应进入冷却");
+            durations.push(d.as_secs());
+            now += d;
+        }
+        assert_eq!(durations, vec![60, 120, 240, 300, 300]);
+    }
+
+    #[test]
+    fn failures_during_cooldown_are_ignored() {
+        let mut cd = HostCooldown::default();
+        let t0 = Instant::now();
+        for _ in 0..3 {
+            cd.record_failure(HOST, t0);
+        }
+        for i in 0..5 {
+            assert_eq!(cd.record_failure(HOST, t0 + secs(i)), None);
+        }
+        assert_eq!(cd.remaining(HOST, t0), Some(secs(60)), "不应延长冷却");
+    }
+
+    #[test]
+    fn success_resets_backoff_level() {
+        let mut cd = HostCooldown::default();
+        let t0 = Instant::now();
+        for _ in 0..3 {
+            cd.record_failure(HOST, t0);
+        }
+        cd.record_success(HOST);
+        assert_eq!(cd.remaining(HOST, t0), None);
+
+        let t1 = t0 + secs(100);
+        for _ in 0..2 {
+            cd.record_failure(HOST, t1);
+        }
+        assert_eq!(
+            cd.record_failure(HOST, t1),
+            Some(secs(60)),
+            "退避级别应重置"
+        );
+    }
+
+    #[test]
+    fn hosts_are_tracked_independently() {
+        let mut cd = HostCooldown::default();
+        let t0 = Instant::now();
+        for _ in 0..3 {
+            cd.record_failure(HOST, t0);
+        }
+        assert!(cd.remaining(HOST, t0).is_some());
+        assert_eq!(cd.remaining("interface.music.163.com", t0), None);
+    }
+
+    #[test]
+    fn custom_policy_is_respected() {
+        let mut cd = HostCooldown::with_policy(CooldownPolicy {
+            window: secs(5),
+            threshold: 1,
+            base: secs(10),
+            max: secs(15),
+        });
+        let t0 = Instant::now();
+        assert_eq!(cd.record_failure(HOST, t0), Some(secs(10)));
+        assert_eq!(cd.record_failure(HOST, t0 + secs(10)), Some(secs(15)));
+    }
+
+    #[test]
+    fn helpers() {
+        assert_eq!(
+            host_of("http://m701.music.126.net/a/b.mp3?x=1").as_deref(),
+            Some(HOST)
+        );
+        assert_eq!(host_of("not a url"), None);
+        assert!(is_rate_limited_code(-460));
+        assert!(!is_rate_limited_code(200));
+        assert_eq!(minutes_label(secs(120)), "2 分钟");
+        assert_eq!(minutes_label(secs(90)), "2 分钟");
+        assert_eq!(minutes_label(secs(1)), "1 分钟");
+    }
+}
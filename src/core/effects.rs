@@ -1,4 +1,4 @@
-use crate::app::{App, AppSnapshot, Toast};
+use crate::app::{App, AppSnapshot, Toast, ToastLevel};
 use crate::audio_worker::AudioCommand;
 use crate::error::MessageError;
 use crate::messages::app::AppEvent;
@@ -85,6 +85,34 @@ impl CoreEffects {
     pub fn error(&mut self, err: MessageError) {
         self.actions.push(CoreEffect::EmitError(err));
     }
+
+    /// 并入另一批副作用，丢弃其中的提示与错误（由调用方统一提示）
+    pub fn extend_without_notices(&mut self, other: CoreEffects) {
+        self.actions
+            .extend(other.actions.into_iter().filter(|effect| {
+                !matches!(
+                    effect,
+                    CoreEffect::SetToast(_) | CoreEffect::EmitToast(_) | CoreEffect::EmitError(_)
+                )
+            }));
+    }
+
+    /// 把本轮产生的通知写入 `App::notifications`，返回是否有新通知
+    pub(super) fn record_notifications(&self, app: &mut App) -> bool {
+        let mut recorded = false;
+        for effect in &self.actions {
+            match effect {
+                CoreEffect::SetToast(toast) => app.record_notification(toast.level, &toast.message),
+                CoreEffect::EmitToast(msg) => app.record_notification(ToastLevel::Info, msg),
+                CoreEffect::EmitError(err) => {
+                    app.record_notification(ToastLevel::Error, &format!("错误: {err}"))
+                }
+                _ => continue,
+            }
+            recorded = true;
+        }
+        recorded
+    }
 }
 
 pub struct CoreDispatch<'a> {
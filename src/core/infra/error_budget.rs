@@ -0,0 +1,136 @@
+//! 预加载的错误预算（熔断）：接口持续报错时停止发起预加载，避免重试风暴
+//!
+//! 窗口内失败次数达到上限后进入暂停状态，之后只能由 `reset` 恢复
+//! （用户手动重新预加载或重新登录），不会随时间自动恢复。
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// 默认：60 秒内失败 3 次即暂停
+pub const DEFAULT_MAX_FAILURES: usize = 3;
+pub const DEFAULT_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug)]
+pub struct ErrorBudget {
+    max_failures: usize,
+    window: Duration,
+    /// 窗口内的失败时间（按时间顺序）
+    failures: VecDeque<Instant>,
+    /// 暂停原因（触发熔断的最后一次错误）；None 表示未暂停
+    tripped: Option<String>,
+}
+
+impl Default for ErrorBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FAILURES, DEFAULT_WINDOW)
+    }
+}
+
+impl ErrorBudget {
+    pub fn new(max_failures: usize, window: Duration) -> Self {
+        Self {
+            max_failures: max_failures.max(1),
+            window,
+            failures: VecDeque::new(),
+            tripped: None,
+        }
+    }
+
+    /// 记录一次失败，返回是否因此进入暂停（已暂停时返回 false）
+    pub fn record_failure(&mut self, now: Instant, reason: &str) -> bool {
+        if self.tripped.is_some() {
+            return false;
+        }
+        while self
+            .failures
+            .front()
+            .is_some_and(|&t| now.saturating_duration_since(t) >= self.window)
+        {
+            self.failures.pop_front();
+        }
+        self.failures.push_back(now);
+        if self.failures.len() >= self.max_failures {
+            self.failures.clear();
+            self.tripped = Some(reason.to_owned());
+            return true;
+        }
+        false
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.tripped.is_some()
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.tripped.as_deref()
+    }
+
+    /// 清空失败记录并解除暂停
+    pub fn reset(&mut self) {
+        self.failures.clear();
+        self.tripped = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(s: u64) -> Duration {
+        Duration::from_secs(s)
+    }
+
+    #[test]
+    fn trips_after_max_failures_within_window() {
+        let t0 = Instant::now();
+        let mut budget = ErrorBudget::default();
+        assert!(!budget.record_failure(t0, "e1"));
+        assert!(!budget.record_failure(t0 + secs(10), "e2"));
+        assert!(!budget.is_tripped());
+
+        assert!(budget.record_failure(t0 + secs(20), "e3"));
+        assert!(budget.is_tripped());
+        assert_eq!(budget.reason(), Some("e3"));
+
+        // 已暂停：后续失败不再重复触发，也不改写原因
+        assert!(!budget.record_failure(t0 + secs(21), "e4"));
+        assert_eq!(budget.reason(), Some("e3"));
+    }
+
+    #[test]
+    fn failures_outside_window_expire() {
+        let t0 = Instant::now();
+        let mut budget = ErrorBudget::default();
+        budget.record_failure(t0, "e1");
+        budget.record_failure(t0 + secs(30), "e2");
+        // 第一次失败已过窗口：窗口内只有 2 次
+        assert!(!budget.record_failure(t0 + secs(60), "e3"));
+        assert!(!budget.is_tripped());
+        assert!(budget.record_failure(t0 + secs(61), "e4"));
+    }
+
+    #[test]
+    fn does_not_resume_with_time_only_after_reset() {
+        let t0 = Instant::now();
+        let mut budget = ErrorBudget::new(1, secs(60));
+        assert!(budget.record_failure(t0, "risk"));
+        assert!(!budget.record_failure(t0 + secs(3600), "later"));
+        assert!(budget.is_tripped());
+
+        budget.reset();
+        assert!(!budget.is_tripped());
+        assert_eq!(budget.reason(), None);
+        assert!(budget.record_failure(t0 + secs(3601), "again"));
+    }
+
+    #[test]
+    fn reset_clears_accumulated_failures() {
+        let t0 = Instant::now();
+        let mut budget = ErrorBudget::default();
+        budget.record_failure(t0, "e1");
+        budget.record_failure(t0 + secs(1), "e2");
+        budget.reset();
+        assert!(!budget.record_failure(t0 + secs(2), "e3"));
+        assert!(!budget.is_tripped());
+    }
+}
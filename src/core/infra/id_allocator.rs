@@ -0,0 +1,88 @@
+//! 全局请求 ID 分配器
+//!
+//! 所有发往 NeteaseActor 的请求共用同一个递增序列，响应按 req_id 与
+//! [`RequestTracker`](super::RequestTracker) 中记录的最新请求比对。分配器内部是
+//! `Arc<AtomicU64>`，克隆后可交给后台任务独立分配，不需要借用 reducer 状态。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 第一个分配出的 req_id
+const FIRST_ID: u64 = 1;
+
+/// 请求 ID 分配器；克隆共享同一序列
+#[derive(Debug, Clone)]
+pub struct IdAllocator {
+    next: Arc<AtomicU64>,
+}
+
+impl Default for IdAllocator {
+    fn default() -> Self {
+        Self::starting_at(FIRST_ID)
+    }
+}
+
+impl IdAllocator {
+    /// 从 `first` 开始分配
+    pub fn starting_at(first: u64) -> Self {
+        Self {
+            next: Arc::new(AtomicU64::new(first)),
+        }
+    }
+
+    /// 分配下一个 ID；到达 `u64::MAX` 后回绕到 0
+    pub fn next(&self) -> u64 {
+        // 只要求唯一递增，不与其它内存操作同步
+        self.next.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn sequential_ids_start_at_one_and_increase() {
+        let ids = IdAllocator::default();
+        assert_eq!(ids.next(), 1);
+        assert_eq!(ids.next(), 2);
+
+        // 克隆共享序列
+        let shared = ids.clone();
+        assert_eq!(shared.next(), 3);
+        assert_eq!(ids.next(), 4);
+    }
+
+    #[test]
+    fn wraps_around_at_u64_max() {
+        let ids = IdAllocator::starting_at(u64::MAX - 1);
+        assert_eq!(ids.next(), u64::MAX - 1);
+        assert_eq!(ids.next(), u64::MAX);
+        assert_eq!(ids.next(), 0);
+        assert_eq!(ids.next(), 1);
+    }
+
+    #[test]
+    fn concurrent_allocation_yields_unique_ids() {
+        const THREADS: usize = 8;
+        const PER_THREAD: usize = 1_000;
+
+        let ids = IdAllocator::default();
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let ids = ids.clone();
+                std::thread::spawn(move || (0..PER_THREAD).map(|_| ids.next()).collect::<Vec<_>>())
+            })
+            .collect();
+
+        let mut seen = HashSet::new();
+        for handle in handles {
+            for id in handle.join().expect("join") {
+                assert!(seen.insert(id), "重复的 req_id: {id}");
+            }
+        }
+        assert_eq!(seen.len(), THREADS * PER_THREAD);
+        assert_eq!(ids.next(), (THREADS * PER_THREAD) as u64 + 1);
+    }
+}
@@ -1,7 +1,11 @@
+mod error_budget;
+mod id_allocator;
 mod next_song_cache;
 mod preload;
+mod preload_plan;
 mod request_tracker;
 
+pub use id_allocator::IdAllocator;
 pub use next_song_cache::NextSongCacheManager;
 pub use request_tracker::{RequestKey, RequestTracker};
 
@@ -1,11 +1,15 @@
 //! 下一首歌预缓存管理器
+//!
+//! 拿到下一首的播放链接后向音频引擎发送 `QueueNext`：引擎提前下载、解码，当前歌曲结束时
+//! 直接切换（无缝衔接），再由 [`NextSongCacheManager::take_queued`] 把播放队列推进到这首。
 
 use crate::app::App;
-use crate::domain::model::SongUrl;
+use crate::domain::model::{Song, SongUrl, fallback_br};
+
+use crate::core::prelude::{audio::AudioCommand, effects::CoreEffects, netease::NeteaseCommand};
+
+use super::IdAllocator;
 
-use crate::core::prelude::{
-    audio::AudioCommand, effects::CoreEffects, netease::NeteaseCommand, utils::next_id,
-};
 /// 待处理的预缓存请求
 struct PendingPrefetch {
     req_id: u64,
@@ -13,19 +17,78 @@ struct PendingPrefetch {
     song_id: i64,
 }
 
+/// 接下来要播放的歌曲
+fn upcoming_song(app: &App) -> Option<&Song> {
+    // 播完当前歌曲就停止，不预排下一首
+    if app.stop_after_current {
+        return None;
+    }
+    if app.fm_active() {
+        return app.fm.as_ref()?.buffer.front();
+    }
+    // 边界检查
+    if app.play_queue.is_empty() || app.play_queue.current_index().is_none() {
+        return None;
+    }
+    let current_idx = app.play_queue.current_index();
+    let next_idx = app.play_queue.peek_next_index()?;
+    if current_idx == Some(next_idx) {
+        return None;
+    }
+    app.play_queue.songs().get(next_idx)
+}
+
+/// 切换到预排歌曲时是否跳过淡入淡出：队列禁用了淡入淡出，或与当前歌曲同专辑（与 PlayTrack 一致）
+fn queued_no_fade(app: &App, next: &Song) -> bool {
+    if app.play_queue.no_fade() {
+        return true;
+    }
+    if app.fm_active() || matches!(app.play_mode, crate::app::PlayMode::Shuffle) {
+        return false;
+    }
+    let current_album = app.play_queue.current().and_then(|s| s.album_id);
+    current_album.is_some() && current_album == next.album_id
+}
+
 #[derive(Default)]
 pub struct NextSongCacheManager {
     generation: u64,
     pending: Option<PendingPrefetch>,
-    cached_song_id: Option<i64>,
+    /// 已预缓存的歌曲及实际缓存的音质
+    cached: Option<(i64, i64)>,
+    /// 已在音频引擎中预排（`QueueNext`）的歌曲及音质
+    queued: Option<(i64, i64)>,
 }
 
 impl NextSongCacheManager {
-    /// 失效当前预缓存状态（队列改变、模式切换时调用）
-    pub fn reset(&mut self) {
+    /// 失效当前预缓存状态（队列改变、模式切换时调用），并丢弃引擎中预排的歌曲
+    pub fn reset(&mut self, effects: &mut CoreEffects) {
         self.generation = self.generation.wrapping_add(1);
         self.pending = None;
-        self.cached_song_id = None;
+        self.cached = None;
+        self.discard_queued(effects);
+    }
+
+    /// 丢弃引擎中预排的下一首（手动切歌时调用；预缓存的音质仍然保留）
+    pub fn discard_queued(&mut self, effects: &mut CoreEffects) {
+        if self.queued.take().is_some() {
+            effects.send_audio(AudioCommand::ClearQueued);
+        }
+    }
+
+    /// 引擎开始播放 `song_id`：若正是预排的歌曲（引擎自行切换），返回其音质
+    pub fn take_queued(&mut self, song_id: i64) -> Option<i64> {
+        let (_, br) = self.queued.filter(|(id, _)| *id == song_id)?;
+        self.queued = None;
+        self.cached = None;
+        Some(br)
+    }
+
+    /// 已预缓存的音质；播放这首时按该音质请求，缓存键才能与实际播放一致
+    pub fn cached_br(&self, song_id: i64) -> Option<i64> {
+        self.cached
+            .filter(|(id, _)| *id == song_id)
+            .map(|(_, br)| br)
     }
 
     /// 检查 req_id 是否属于预缓存请求
@@ -36,27 +99,19 @@ impl NextSongCacheManager {
             .unwrap_or(false)
     }
 
-    /// 触发预缓存下一首
-    pub async fn prefetch_next(&mut self, app: &App, effects: &mut CoreEffects, req_id: &mut u64) {
-        // 边界检查
-        if app.play_queue.is_empty() || app.play_queue.current_index().is_none() {
-            return;
-        }
-
-        let current_idx = app.play_queue.current_index();
-        let Some(next_idx) = app.play_queue.peek_next_index() else {
-            return;
-        };
-        if current_idx == Some(next_idx) {
-            return;
-        }
-
-        let Some(next_song) = app.play_queue.songs().get(next_idx) else {
+    /// 触发预缓存下一首（私人 FM 时为缓冲区中的下一首）
+    pub async fn prefetch_next(
+        &mut self,
+        app: &App,
+        effects: &mut CoreEffects,
+        req_ids: &IdAllocator,
+    ) {
+        let Some(next_song) = upcoming_song(app) else {
             return;
         };
 
         // 检查是否已经缓存过这首
-        if self.cached_song_id == Some(next_song.id) {
+        if self.cached.is_some_and(|(id, _)| id == next_song.id) {
             tracing::debug!(
                 song_id = next_song.id,
                 song_name = %next_song.name,
@@ -65,34 +120,73 @@ impl NextSongCacheManager {
             return;
         }
 
-        // 发起预缓存请求
-        let id = next_id(req_id);
+        // 同一首的请求还在进行中
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|p| p.song_id == next_song.id && p.generation == self.generation)
+        {
+            return;
+        }
+
+        tracing::debug!(song_id = next_song.id, song_name = %next_song.name, "预缓存下一首");
+        self.request(next_song.id, app.play_br, effects, req_ids);
+    }
+
+    /// 发起预缓存请求
+    fn request(&mut self, song_id: i64, br: i64, effects: &mut CoreEffects, req_ids: &IdAllocator) {
+        let id = req_ids.next();
         self.pending = Some(PendingPrefetch {
             req_id: id,
             generation: self.generation,
-            song_id: next_song.id,
+            song_id,
         });
 
         tracing::info!(
             req_id = id,
             generation = self.generation,
-            song_id = next_song.id,
-            song_name = %next_song.name,
+            song_id,
+            br,
             "开始预缓存下一首"
         );
 
         effects.send_netease_lo(NeteaseCommand::SongUrl {
             req_id: id,
-            id: next_song.id,
-            br: app.play_br,
+            id: song_id,
+            br,
         });
     }
 
-    /// 处理 SongUrl 响应，发送 PrefetchAudio 命令
+    /// 预缓存的歌曲在请求的音质下无可用链接：按音质阶梯降级重试
+    pub fn on_unavailable(
+        &mut self,
+        req_id: u64,
+        song_id: i64,
+        br: i64,
+        effects: &mut CoreEffects,
+        req_ids: &IdAllocator,
+    ) {
+        let Some(pending) = self.pending.take().filter(|p| p.req_id == req_id) else {
+            return;
+        };
+        if pending.generation != self.generation || pending.song_id != song_id {
+            return;
+        }
+        match fallback_br(br) {
+            Some(lower) => {
+                tracing::info!(song_id, from_br = br, to_br = lower, "预缓存降级重试");
+                self.request(song_id, lower, effects, req_ids);
+            }
+            None => tracing::debug!(song_id, "预缓存歌曲无可用链接"),
+        }
+    }
+
+    /// 处理 SongUrl 响应，发送 QueueNext 命令
     pub fn on_song_url(
         &mut self,
         req_id: u64,
         song_url: &SongUrl,
+        attempted_br: i64,
         effects: &mut CoreEffects,
         app: &App,
     ) -> bool {
@@ -125,18 +219,28 @@ impl NextSongCacheManager {
             return false;
         }
 
-        // 发送预缓存命令到 Audio Worker
-        let title = format!("预缓存: {}", song_url.id);
-        effects.send_audio(AudioCommand::PrefetchAudio {
+        // 预排到 Audio Worker：提前缓存并在当前歌曲结束时直接切换
+        let Some(next_song) = upcoming_song(app).filter(|s| s.id == song_url.id) else {
+            tracing::debug!(song_id = song_url.id, "下一首已变化,丢弃预缓存响应");
+            return false;
+        };
+        let br = song_url.effective_br(attempted_br);
+        effects.send_audio(AudioCommand::QueueNext {
             id: song_url.id,
-            br: app.play_br,
+            br,
+            requested_br: app.play_br,
             url: song_url.url.clone(),
-            title,
+            expected_bytes: song_url.size,
+            title: next_song.display_title(app.song_name_mode),
+            duration_ms: next_song.duration_ms,
+            no_fade: queued_no_fade(app, next_song),
+            gain_db: song_url.gain_db,
         });
 
-        self.cached_song_id = Some(song_url.id);
+        self.cached = Some((song_url.id, br));
+        self.queued = Some((song_url.id, br));
 
-        tracing::info!(song_id = song_url.id, "预缓存成功");
+        tracing::info!(song_id = song_url.id, br, "预缓存成功");
 
         true
     }
@@ -145,7 +249,7 @@ impl NextSongCacheManager {
     pub fn on_error(&mut self, req_id: u64) -> bool {
         if self.owns_req(req_id) {
             self.pending = None;
-            self.cached_song_id = None; // 清理以允许重试
+            self.cached = None; // 清理以允许重试
 
             tracing::warn!(req_id, "预缓存请求失败,已清除状态");
 
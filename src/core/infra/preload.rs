@@ -1,7 +1,13 @@
-use crate::app::{App, PlaylistPreload, PreloadStatus};
+use crate::app::{App, PlaylistPreload, PlaylistStats, PreloadStatus, Song, TaskKind};
 use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 
-use crate::core::prelude::{effects::CoreEffects, netease::NeteaseCommand, utils::next_id};
+use super::error_budget::ErrorBudget;
+use super::preload_plan::{LatencyStats, plan_preloads};
+
+use crate::core::prelude::{effects::CoreEffects, netease::NeteaseCommand};
+
+use super::IdAllocator;
 use crate::features::playlists::PlaylistTracksLoad;
 
 #[derive(Debug, Clone, Copy)]
@@ -13,16 +19,21 @@ enum PreloadPendingKind {
 #[derive(Default)]
 pub struct PreloadManager {
     generation: u64,
-    pending: HashMap<u64, (u64, PreloadPendingKind)>,
+    /// req_id → (generation, 请求类型, 发出时间)
+    pending: HashMap<u64, (u64, PreloadPendingKind, Instant)>,
     loaders: HashMap<i64, PlaylistTracksLoad>,
     active_playlists: HashSet<i64>,
+    /// 接口错误过多时暂停本次会话的预加载
+    budget: ErrorBudget,
+    /// 预加载请求的往返耗时，用于下一次制定预加载计划
+    latency: LatencyStats,
 }
 
 impl PreloadManager {
     pub fn owns_req(&self, req_id_evt: u64) -> bool {
         self.pending
             .get(&req_id_evt)
-            .is_some_and(|(generation, _)| *generation == self.generation)
+            .is_some_and(|(generation, ..)| *generation == self.generation)
     }
 
     pub fn reset(&mut self, app: &mut App) {
@@ -30,15 +41,45 @@ impl PreloadManager {
         self.pending.clear();
         self.loaders.clear();
         self.active_playlists.clear();
+        self.budget.reset();
         app.playlist_preloads.clear();
         app.preload_summary.clear();
+        app.preload_suspended = false;
+        sync_preload_tasks(app);
+    }
+
+    /// 用户主动恢复预加载（手动重新预加载、重新登录）：清空错误预算
+    pub fn resume(&mut self, app: &mut App) {
+        if self.budget.is_tripped() {
+            tracing::info!("🎵 [Preload] 用户操作，恢复预加载");
+        }
+        self.budget.reset();
+        app.preload_suspended = false;
+    }
+
+    /// 错误预算耗尽：丢弃所有进行中的预加载，本次会话不再发起新的预加载
+    fn suspend(&mut self, app: &mut App) {
+        self.generation = self.generation.wrapping_add(1);
+        self.pending.clear();
+        self.loaders.clear();
+        self.active_playlists.clear();
+        for p in app.playlist_preloads.values_mut() {
+            if !matches!(
+                p.status,
+                PreloadStatus::Completed | PreloadStatus::Failed(_)
+            ) {
+                p.status = PreloadStatus::Cancelled;
+                p.songs.clear();
+            }
+        }
+        app.preload_suspended = true;
     }
 
     pub async fn start_for_playlists(
         &mut self,
         app: &mut App,
         effects: &mut CoreEffects,
-        req_id: &mut u64,
+        req_ids: &IdAllocator,
         preload_count: usize,
     ) {
         self.generation = self.generation.wrapping_add(1);
@@ -72,7 +113,25 @@ impl PreloadManager {
             app.playlist_preloads.len()
         );
 
-        let selected = select_preload_targets(&app.playlists, preload_count);
+        if self.budget.is_tripped() {
+            tracing::info!(
+                reason = self.budget.reason().unwrap_or_default(),
+                "🎵 [Preload] 预加载已暂停，跳过"
+            );
+            update_preload_summary(app);
+            return;
+        }
+
+        let plan = plan_preloads(&app.playlists, &self.latency, preload_count);
+        if plan.skipped_for_latency {
+            tracing::info!(
+                median_ms = self.latency.median_ms().unwrap_or_default(),
+                "🎵 [Preload] 接口延迟过高，跳过本次预加载"
+            );
+            app.preload_summary = "预加载已跳过：接口延迟过高".to_owned();
+            return;
+        }
+        let selected = plan.targets;
         if selected.is_empty() {
             return;
         }
@@ -107,6 +166,11 @@ impl PreloadManager {
                         total: 0,
                     },
                     songs: Vec::new(),
+                    missing_count: 0,
+                    stats: None,
+                    completed_at_ms: None,
+                    added_at: HashMap::new(),
+                    track_ids_fingerprint: None,
                 },
             );
             // 新增日志
@@ -116,12 +180,13 @@ impl PreloadManager {
 
         for playlist_id in to_preload {
             self.active_playlists.insert(playlist_id);
-            let rid = next_id(req_id);
+            let rid = req_ids.next();
             self.pending.insert(
                 rid,
                 (
                     self.generation,
                     PreloadPendingKind::PlaylistDetail { playlist_id },
+                    Instant::now(),
                 ),
             );
             effects.send_netease_lo(NeteaseCommand::PlaylistDetail {
@@ -137,7 +202,7 @@ impl PreloadManager {
         let to_remove: Vec<u64> = self
             .pending
             .iter()
-            .filter_map(|(rid, (_, kind))| match kind {
+            .filter_map(|(rid, (_, kind, _))| match kind {
                 PreloadPendingKind::PlaylistDetail { playlist_id: p }
                 | PreloadPendingKind::SongsChunk { playlist_id: p } => {
                     if *p == playlist_id {
@@ -152,25 +217,38 @@ impl PreloadManager {
             self.pending.remove(&rid);
         }
 
-        if let Some(p) = app.playlist_preloads.get_mut(&playlist_id) {
+        // 已完成的预加载不受取消影响，避免丢弃完整数据
+        if let Some(p) = app.playlist_preloads.get_mut(&playlist_id)
+            && !matches!(p.status, PreloadStatus::Completed)
+        {
             p.status = PreloadStatus::Cancelled;
             p.songs.clear();
         }
         update_preload_summary(app);
     }
 
+    /// 歌单内容已变化：丢弃预加载结果（含已完成的），下次打开时重新拉取
+    pub fn invalidate_playlist(&mut self, app: &mut App, playlist_id: i64) {
+        self.cancel_playlist(app, playlist_id);
+        app.playlist_preloads.remove(&playlist_id);
+        update_preload_summary(app);
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn on_playlist_track_ids(
         &mut self,
         app: &mut App,
         effects: &mut CoreEffects,
-        req_id: &mut u64,
+        req_ids: &IdAllocator,
         req_id_evt: u64,
         playlist_id_evt: i64,
         ids: &[i64],
+        added_at: &HashMap<i64, i64>,
     ) -> bool {
-        let Some((generation, kind)) = self.pending.remove(&req_id_evt) else {
+        let Some((generation, kind, sent_at)) = self.pending.remove(&req_id_evt) else {
             return false;
         };
+        self.latency.record(sent_at.elapsed());
         if generation != self.generation {
             return true;
         }
@@ -194,9 +272,9 @@ impl PreloadManager {
         }
 
         let total = ids.len();
-        let mut loader = PlaylistTracksLoad::new(playlist_id, ids.to_vec());
+        let mut loader = PlaylistTracksLoad::new(playlist_id, ids.to_vec(), added_at.clone());
 
-        let rid = next_id(req_id);
+        let rid = req_ids.next();
         let chunk = loader.next_chunk();
         loader.inflight_req_id = Some(rid);
         self.loaders.insert(playlist_id, loader);
@@ -205,6 +283,7 @@ impl PreloadManager {
             (
                 self.generation,
                 PreloadPendingKind::SongsChunk { playlist_id },
+                Instant::now(),
             ),
         );
 
@@ -224,13 +303,14 @@ impl PreloadManager {
         &mut self,
         app: &mut App,
         effects: &mut CoreEffects,
-        req_id: &mut u64,
+        req_ids: &IdAllocator,
         req_id_evt: u64,
         songs: &[crate::app::Song],
     ) -> bool {
-        let Some((generation, kind)) = self.pending.remove(&req_id_evt) else {
+        let Some((generation, kind, sent_at)) = self.pending.remove(&req_id_evt) else {
             return false;
         };
+        self.latency.record(sent_at.elapsed());
         if generation != self.generation {
             return true;
         }
@@ -264,21 +344,20 @@ impl PreloadManager {
                 tracing::warn!(playlist_id, "预加载 loader 丢失（已完成但无法取出）");
                 return true;
             };
-            if let Some(p) = app.playlist_preloads.get_mut(&playlist_id) {
-                p.status = PreloadStatus::Completed;
-                p.songs = loader.songs;
-                // 新增日志
-                tracing::info!(
-                    "🎵 [Preload] 预加载完成: playlist_id={}, songs={}",
+            if app.playlist_preloads.contains_key(&playlist_id) {
+                store_completed_preload(
+                    app,
                     playlist_id,
-                    p.songs.len()
+                    loader.songs,
+                    &loader.ids,
+                    loader.added_at,
                 );
             }
             update_preload_summary(app);
             return true;
         }
 
-        let rid = next_id(req_id);
+        let rid = req_ids.next();
         let chunk = loader.next_chunk();
         loader.inflight_req_id = Some(rid);
         self.pending.insert(
@@ -286,6 +365,7 @@ impl PreloadManager {
             (
                 self.generation,
                 PreloadPendingKind::SongsChunk { playlist_id },
+                Instant::now(),
             ),
         );
         effects.send_netease_lo(NeteaseCommand::SongDetailByIds {
@@ -296,7 +376,7 @@ impl PreloadManager {
     }
 
     pub fn on_error(&mut self, app: &mut App, req_id_evt: u64, message: &str) -> bool {
-        let Some((generation, kind)) = self.pending.remove(&req_id_evt) else {
+        let Some((generation, kind, _)) = self.pending.remove(&req_id_evt) else {
             return false;
         };
         if generation != self.generation {
@@ -311,15 +391,99 @@ impl PreloadManager {
         if let Some(p) = app.playlist_preloads.get_mut(&playlist_id) {
             p.status = PreloadStatus::Failed(message.to_owned());
             p.songs.clear();
+            p.stats = None;
         }
         self.loaders.remove(&playlist_id);
         self.active_playlists.remove(&playlist_id);
+        if self.budget.record_failure(Instant::now(), message) {
+            tracing::warn!(
+                reason = message,
+                "🎵 [Preload] 短时间内多次失败，暂停本次会话的预加载"
+            );
+            self.suspend(app);
+        }
         update_preload_summary(app);
         true
     }
 }
 
+/// 请求的歌曲 id 列表（含顺序）的指纹（FNV-1a）
+fn track_ids_fingerprint(ids: &[i64]) -> u64 {
+    ids.iter()
+        .flat_map(|id| id.to_le_bytes())
+        .fold(0xcbf2_9ce4_8422_2325, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        })
+}
+
+/// 记录一次完整加载的歌单歌曲，返回是否采用了本次结果
+///
+/// `requested` 为请求的歌曲 id 列表，与返回数之差记为 `missing_count`；`added_at` 为各歌曲的添加时间。
+/// 若已完成的预加载请求的是同一份 id 列表且歌曲更多（接口偶发少返回），保留原有数据；
+/// 歌单内容变化（增删、重排）时总是采用新结果。
+pub fn store_completed_preload(
+    app: &mut App,
+    playlist_id: i64,
+    songs: Vec<Song>,
+    requested: &[i64],
+    added_at: HashMap<i64, i64>,
+) -> bool {
+    let fingerprint = track_ids_fingerprint(requested);
+    let requested = requested.len();
+    if let Some(prev) = app.playlist_preloads.get(&playlist_id)
+        && matches!(prev.status, PreloadStatus::Completed)
+        && prev.track_ids_fingerprint == Some(fingerprint)
+        && prev.songs.len() > songs.len()
+    {
+        tracing::warn!(
+            playlist_id,
+            kept = prev.songs.len(),
+            returned = songs.len(),
+            requested,
+            "🎵 [Preload] 本次加载结果少于已完成的预加载，保留原有数据"
+        );
+        crate::features::favorites::apply_pending_likes(app, playlist_id);
+        return false;
+    }
+
+    let missing_count = requested.saturating_sub(songs.len());
+    if missing_count > 0 {
+        tracing::info!(
+            playlist_id,
+            requested,
+            returned = songs.len(),
+            "🎵 [Preload] 部分歌曲详情未返回: {missing_count} 首不可用"
+        );
+    }
+    tracing::info!(
+        "🎵 [Preload] 预加载完成: playlist_id={}, songs={}",
+        playlist_id,
+        songs.len()
+    );
+    app.playlist_preloads.insert(
+        playlist_id,
+        PlaylistPreload {
+            status: PreloadStatus::Completed,
+            stats: Some(PlaylistStats::compute(&songs)),
+            songs,
+            missing_count,
+            completed_at_ms: Some(chrono::Utc::now().timestamp_millis()),
+            added_at,
+            track_ids_fingerprint: Some(fingerprint),
+        },
+    );
+    crate::features::favorites::apply_pending_likes(app, playlist_id);
+    true
+}
+
 pub fn update_preload_summary(app: &mut App) {
+    sync_preload_tasks(app);
+
+    if app.preload_suspended {
+        app.preload_summary = "预加载已暂停：接口错误".to_owned();
+        return;
+    }
+
     if app.playlist_preloads.is_empty() {
         app.preload_summary.clear();
         return;
@@ -367,32 +531,323 @@ pub fn update_preload_summary(app: &mut App) {
     };
 }
 
-fn select_preload_targets(
-    playlists: &[crate::domain::model::Playlist],
-    max_count: usize,
-) -> Vec<i64> {
-    if max_count == 0 || playlists.is_empty() {
-        return vec![];
+/// 将预加载状态同步到后台任务登记表
+fn sync_preload_tasks(app: &mut App) {
+    for (playlist_id, preload) in &app.playlist_preloads {
+        let kind = TaskKind::PlaylistPreload {
+            playlist_id: *playlist_id,
+        };
+        match &preload.status {
+            PreloadStatus::Loading { loaded, total } => {
+                let title = app
+                    .playlists
+                    .iter()
+                    .find(|p| p.id == *playlist_id)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_else(|| format!("歌单 {playlist_id}"));
+                let id = app.tasks.register(kind, title);
+                let total = (*total > 0).then_some(*total as u64);
+                app.tasks.update_progress(id, *loaded as u64, total);
+            }
+            PreloadStatus::Completed => {
+                if let Some(id) = app.tasks.running_id(kind) {
+                    app.tasks.complete(id);
+                }
+            }
+            PreloadStatus::Failed(message) => {
+                if let Some(id) = app.tasks.running_id(kind) {
+                    app.tasks.fail(id, message.clone());
+                }
+            }
+            PreloadStatus::Cancelled => {
+                if let Some(id) = app.tasks.running_id(kind) {
+                    app.tasks.cancel(id);
+                }
+            }
+            PreloadStatus::NotStarted => {}
+        }
     }
 
-    let mut out = Vec::with_capacity(max_count);
-
-    if let Some(p) = playlists
+    // reset 后条目被清空：仍在运行的预加载任务视为已取消
+    let orphaned: Vec<u64> = app
+        .tasks
+        .tasks()
         .iter()
-        .find(|p| p.special_type == 5 || p.name.contains("我喜欢"))
-    {
-        out.push(p.id);
+        .filter(|t| t.is_running())
+        .filter_map(|t| match t.kind {
+            TaskKind::PlaylistPreload { playlist_id }
+                if !app.playlist_preloads.contains_key(&playlist_id) =>
+            {
+                Some(t.id)
+            }
+            _ => None,
+        })
+        .collect();
+    for id in orphaned {
+        app.tasks.cancel(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::Playlist;
+
+    fn songs(ids: std::ops::Range<i64>) -> Vec<Song> {
+        ids.map(Song::fixture).collect()
+    }
+
+    #[tokio::test]
+    async fn preload_records_missing_count_when_detail_returns_fewer_songs() {
+        let mut app = App {
+            playlists: vec![Playlist {
+                id: 1,
+                name: "我喜欢的音乐".to_owned(),
+                track_count: 5,
+                special_type: 5,
+                subscribed: false,
+                updated_at_ms: None,
+            }],
+            ..Default::default()
+        };
+        let mut mgr = PreloadManager::default();
+        let mut effects = CoreEffects::default();
+        let req_ids = IdAllocator::default();
+
+        mgr.start_for_playlists(&mut app, &mut effects, &req_ids, 1)
+            .await;
+        let detail_rid = 1;
+        assert!(
+            mgr.on_playlist_track_ids(
+                &mut app,
+                &mut effects,
+                &req_ids,
+                detail_rid,
+                1,
+                &[1, 2, 3, 4, 5],
+                &HashMap::from([(1, 100), (4, 400)])
+            )
+            .await
+        );
+        let chunk_rid = 2;
+        // 其中两首因地区限制未返回
+        assert!(
+            mgr.on_songs(&mut app, &mut effects, &req_ids, chunk_rid, &songs(1..4))
+                .await
+        );
+
+        let preload = app.playlist_preloads.get(&1).expect("preload");
+        assert!(matches!(preload.status, PreloadStatus::Completed));
+        assert_eq!(preload.songs.len(), 3);
+        assert_eq!(preload.missing_count, 2);
+        assert!(preload.completed_at_ms.is_some());
+        assert_eq!(preload.added_at, HashMap::from([(1, 100), (4, 400)]));
+    }
+
+    #[test]
+    fn store_completed_preload_without_missing_songs() {
+        let mut app = App::default();
+        assert!(store_completed_preload(
+            &mut app,
+            1,
+            songs(1..4),
+            &[1, 2, 3],
+            HashMap::new()
+        ));
+        assert_eq!(app.playlist_preloads[&1].missing_count, 0);
+    }
+
+    #[test]
+    fn smaller_refresh_does_not_overwrite_completed_preload() {
+        let mut app = App::default();
+        let ids: Vec<i64> = (1..13).collect();
+        let ids_of = |app: &App| -> Vec<i64> {
+            app.playlist_preloads[&1]
+                .songs
+                .iter()
+                .map(|s| s.id)
+                .collect()
+        };
+        assert!(store_completed_preload(
+            &mut app,
+            1,
+            songs(1..11),
+            &ids,
+            HashMap::new()
+        ));
+
+        // 同一份歌单，接口偶发少返回：保留原有的更大集合
+        assert!(!store_completed_preload(
+            &mut app,
+            1,
+            songs(1..6),
+            &ids,
+            HashMap::new()
+        ));
+        let preload = &app.playlist_preloads[&1];
+        assert_eq!(preload.songs.len(), 10);
+        assert_eq!(preload.missing_count, 2);
+
+        // 结果不少于原有时正常更新
+        assert!(store_completed_preload(
+            &mut app,
+            1,
+            songs(1..13),
+            &ids,
+            HashMap::new()
+        ));
+        let preload = &app.playlist_preloads[&1];
+        assert_eq!(preload.songs.len(), 12);
+        assert_eq!(preload.missing_count, 0);
+
+        // 歌单重排：数量相同也采用新顺序
+        let reordered: Vec<i64> = ids.iter().rev().copied().collect();
+        let songs_rev: Vec<Song> = reordered.iter().copied().map(Song::fixture).collect();
+        assert!(store_completed_preload(
+            &mut app,
+            1,
+            songs_rev,
+            &reordered,
+            HashMap::new()
+        ));
+        assert_eq!(ids_of(&app), reordered);
+
+        // 用户删除了歌曲：更短的新列表也会保存
+        assert!(store_completed_preload(
+            &mut app,
+            1,
+            songs(1..4),
+            &[1, 2, 3],
+            HashMap::new()
+        ));
+        assert_eq!(ids_of(&app), vec![1, 2, 3]);
+        assert_eq!(app.playlist_preloads[&1].missing_count, 0);
+    }
+
+    #[test]
+    fn cancel_keeps_completed_preload() {
+        let mut app = App::default();
+        store_completed_preload(&mut app, 1, songs(1..4), &[1, 2, 3], HashMap::new());
+        let mut mgr = PreloadManager::default();
+
+        mgr.cancel_playlist(&mut app, 1);
+
+        let preload = &app.playlist_preloads[&1];
+        assert!(matches!(preload.status, PreloadStatus::Completed));
+        assert_eq!(preload.songs.len(), 3);
+    }
+
+    fn playlists(n: i64) -> Vec<Playlist> {
+        (1..=n)
+            .map(|id| Playlist {
+                id,
+                name: format!("歌单{id}"),
+                track_count: 10,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn pending_detail_count(effects: &mut CoreEffects) -> usize {
+        use crate::core::effects::CoreEffect;
+        std::mem::take(&mut effects.actions)
+            .into_iter()
+            .filter(|e| {
+                matches!(
+                    e,
+                    CoreEffect::SendNeteaseLo {
+                        cmd: NeteaseCommand::PlaylistDetail { .. },
+                        ..
+                    }
+                )
+            })
+            .count()
     }
 
-    for p in playlists {
-        if out.len() >= max_count {
-            break;
+    #[tokio::test]
+    async fn error_budget_suspends_preload_until_resumed() {
+        let mut app = App {
+            playlists: playlists(4),
+            ..Default::default()
+        };
+        let mut mgr = PreloadManager::default();
+        let mut effects = CoreEffects::default();
+        let req_ids = IdAllocator::default();
+
+        mgr.start_for_playlists(&mut app, &mut effects, &req_ids, 4)
+            .await;
+        assert_eq!(pending_detail_count(&mut effects), 4);
+
+        for rid in 1..=3 {
+            assert!(mgr.on_error(&mut app, rid, "风控"));
         }
-        if out.contains(&p.id) {
-            continue;
+        assert!(app.preload_suspended);
+        assert_eq!(app.preload_summary, "预加载已暂停：接口错误");
+        // 剩余的预加载被丢弃，晚到的响应不再处理
+        assert!(!mgr.owns_req(4));
+        assert!(matches!(
+            app.playlist_preloads[&4].status,
+            PreloadStatus::Cancelled
+        ));
+
+        // 重新拿到歌单列表也不会再发起预加载
+        mgr.start_for_playlists(&mut app, &mut effects, &req_ids, 4)
+            .await;
+        assert_eq!(pending_detail_count(&mut effects), 0);
+        assert_eq!(app.preload_summary, "预加载已暂停：接口错误");
+
+        mgr.resume(&mut app);
+        mgr.start_for_playlists(&mut app, &mut effects, &req_ids, 4)
+            .await;
+        assert!(!app.preload_suspended);
+        assert_eq!(pending_detail_count(&mut effects), 4);
+    }
+
+    #[tokio::test]
+    async fn reset_clears_error_budget() {
+        let mut app = App {
+            playlists: playlists(3),
+            ..Default::default()
+        };
+        let mut mgr = PreloadManager::default();
+        let mut effects = CoreEffects::default();
+        let req_ids = IdAllocator::default();
+
+        mgr.start_for_playlists(&mut app, &mut effects, &req_ids, 3)
+            .await;
+        for rid in 1..=3 {
+            mgr.on_error(&mut app, rid, "风控");
         }
-        out.push(p.id);
+        assert!(app.preload_suspended);
+
+        mgr.reset(&mut app);
+        assert!(!app.preload_suspended);
+        pending_detail_count(&mut effects);
+        mgr.start_for_playlists(&mut app, &mut effects, &req_ids, 3)
+            .await;
+        assert_eq!(pending_detail_count(&mut effects), 3);
     }
 
-    out
+    #[test]
+    fn preload_targets_prefer_owned_playlists() {
+        let playlist = |id: i64, name: &str, subscribed: bool| Playlist {
+            id,
+            name: name.to_owned(),
+            subscribed,
+            track_count: 10,
+            ..Default::default()
+        };
+        let select = |playlists: &[Playlist], cap| {
+            plan_preloads(playlists, &LatencyStats::default(), cap).targets
+        };
+        let playlists = vec![
+            playlist(1, "收藏A", true),
+            playlist(2, "自建A", false),
+            playlist(3, "我喜欢的音乐", false),
+            playlist(4, "收藏B", true),
+            playlist(5, "自建B", false),
+        ];
+        assert_eq!(select(&playlists, 4), vec![3, 2, 5, 1]);
+        assert_eq!(select(&playlists, 1), vec![3]);
+    }
 }
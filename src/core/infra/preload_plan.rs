@@ -0,0 +1,216 @@
+//! 预加载计划
+//!
+//! 纯函数：根据歌单元数据、近期接口延迟与 `preload_count`（硬上限）决定本次预加载哪些歌单及顺序。
+//! 「我喜欢」总是第一个；其余歌单按歌曲数从少到多，在歌曲预算内尽量多预加载（小歌单很快完成）。
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use crate::domain::model::Playlist;
+
+/// 一次预加载最多拉取的歌曲数（「我喜欢」不受限制，但计入预算）
+pub const PRELOAD_SONG_BUDGET: i64 = 3_000;
+/// 延迟中位数超过该值时预算减半
+pub const DEGRADED_LATENCY_MS: u64 = 600;
+/// 延迟中位数超过该值时完全跳过预加载
+pub const SLOW_LATENCY_MS: u64 = 1_500;
+/// 样本不足时不根据延迟调整
+pub const MIN_LATENCY_SAMPLES: usize = 3;
+/// 只保留最近的样本
+const LATENCY_WINDOW: usize = 20;
+
+/// 最近若干次接口请求的耗时
+#[derive(Debug, Default, Clone)]
+pub struct LatencyStats {
+    samples_ms: VecDeque<u64>,
+}
+
+impl LatencyStats {
+    pub fn record(&mut self, elapsed: Duration) {
+        if self.samples_ms.len() == LATENCY_WINDOW {
+            self.samples_ms.pop_front();
+        }
+        self.samples_ms
+            .push_back(elapsed.as_millis().min(u64::MAX as u128) as u64);
+    }
+
+    /// 延迟中位数；样本少于 [`MIN_LATENCY_SAMPLES`] 时为 None
+    pub fn median_ms(&self) -> Option<u64> {
+        if self.samples_ms.len() < MIN_LATENCY_SAMPLES {
+            return None;
+        }
+        let mut sorted: Vec<u64> = self.samples_ms.iter().copied().collect();
+        sorted.sort_unstable();
+        Some(sorted[sorted.len() / 2])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PreloadPlan {
+    /// 按顺序预加载的歌单 id
+    pub targets: Vec<i64>,
+    /// 因接口延迟过高而整体跳过（此时 `targets` 为空）
+    pub skipped_for_latency: bool,
+}
+
+fn is_liked(p: &Playlist) -> bool {
+    p.special_type == 5 || p.name.contains("我喜欢")
+}
+
+/// 生成预加载计划
+pub fn plan_preloads(
+    playlists: &[Playlist],
+    latency: &LatencyStats,
+    preload_count: usize,
+) -> PreloadPlan {
+    if preload_count == 0 || playlists.is_empty() {
+        return PreloadPlan::default();
+    }
+
+    let median = latency.median_ms();
+    if median.is_some_and(|ms| ms > SLOW_LATENCY_MS) {
+        return PreloadPlan {
+            targets: Vec::new(),
+            skipped_for_latency: true,
+        };
+    }
+    let budget = if median.is_some_and(|ms| ms > DEGRADED_LATENCY_MS) {
+        PRELOAD_SONG_BUDGET / 2
+    } else {
+        PRELOAD_SONG_BUDGET
+    };
+
+    let mut targets = Vec::with_capacity(preload_count);
+    let mut used = 0;
+    let liked = playlists.iter().find(|p| is_liked(p));
+    if let Some(p) = liked {
+        targets.push(p.id);
+        used += p.track_count.max(0);
+    }
+
+    // 歌曲数相同时自己创建的优先；空歌单没有可预加载的内容
+    let mut rest: Vec<&Playlist> = playlists
+        .iter()
+        .filter(|p| Some(p.id) != liked.map(|l| l.id) && p.track_count > 0)
+        .collect();
+    rest.sort_by_key(|p| (p.track_count, p.subscribed));
+
+    for p in rest {
+        if targets.len() >= preload_count || used + p.track_count > budget {
+            break;
+        }
+        used += p.track_count;
+        targets.push(p.id);
+    }
+
+    PreloadPlan {
+        targets,
+        skipped_for_latency: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn playlist(id: i64, track_count: i64) -> Playlist {
+        Playlist {
+            id,
+            name: format!("歌单{id}"),
+            track_count,
+            ..Default::default()
+        }
+    }
+
+    fn liked(id: i64, track_count: i64) -> Playlist {
+        Playlist {
+            name: "我喜欢的音乐".to_owned(),
+            special_type: 5,
+            ..playlist(id, track_count)
+        }
+    }
+
+    fn latency(samples_ms: &[u64]) -> LatencyStats {
+        let mut stats = LatencyStats::default();
+        for ms in samples_ms {
+            stats.record(Duration::from_millis(*ms));
+        }
+        stats
+    }
+
+    fn targets(playlists: &[Playlist], latency: &LatencyStats, cap: usize) -> Vec<i64> {
+        plan_preloads(playlists, latency, cap).targets
+    }
+
+    #[test]
+    fn liked_first_then_smallest_playlists() {
+        let playlists = [
+            playlist(1, 500),
+            liked(2, 800),
+            playlist(3, 20),
+            playlist(4, 120),
+        ];
+        assert_eq!(
+            targets(&playlists, &LatencyStats::default(), 5),
+            [2, 3, 4, 1]
+        );
+        assert_eq!(targets(&playlists, &LatencyStats::default(), 2), [2, 3]);
+    }
+
+    #[test]
+    fn preload_count_is_a_hard_cap() {
+        let playlists: Vec<Playlist> = (1..=10).map(|id| playlist(id, 10)).collect();
+        assert_eq!(targets(&playlists, &LatencyStats::default(), 3).len(), 3);
+        assert!(targets(&playlists, &LatencyStats::default(), 0).is_empty());
+        assert!(targets(&[], &LatencyStats::default(), 5).is_empty());
+    }
+
+    #[test]
+    fn song_budget_excludes_huge_playlists_but_keeps_liked() {
+        let playlists = [liked(1, 2_980), playlist(2, 50), playlist(3, 8_000)];
+        // 「我喜欢」占去大部分预算，剩余预算只够小歌单
+        assert_eq!(targets(&playlists, &LatencyStats::default(), 5), [1]);
+
+        let playlists = [liked(1, 10), playlist(2, 50), playlist(3, 8_000)];
+        assert_eq!(targets(&playlists, &LatencyStats::default(), 5), [1, 2]);
+
+        // 「我喜欢」超出预算也总会预加载
+        let playlists = [liked(1, 9_000), playlist(2, 10)];
+        assert_eq!(targets(&playlists, &LatencyStats::default(), 5), [1]);
+    }
+
+    #[test]
+    fn empty_playlists_are_skipped_and_ties_prefer_owned() {
+        let mut subscribed = playlist(1, 30);
+        subscribed.subscribed = true;
+        let playlists = [subscribed, playlist(2, 0), playlist(3, 30)];
+        assert_eq!(targets(&playlists, &LatencyStats::default(), 5), [3, 1]);
+    }
+
+    #[test]
+    fn high_latency_halves_budget_or_skips_entirely() {
+        let playlists = [liked(1, 100), playlist(2, 1_000), playlist(3, 1_000)];
+        assert_eq!(
+            targets(&playlists, &latency(&[200, 300, 250]), 5),
+            [1, 2, 3]
+        );
+        assert_eq!(targets(&playlists, &latency(&[700, 800, 900]), 5), [1, 2]);
+
+        let plan = plan_preloads(&playlists, &latency(&[2_000, 1_800, 100]), 5);
+        assert!(plan.skipped_for_latency);
+        assert!(plan.targets.is_empty());
+    }
+
+    #[test]
+    fn latency_median_needs_enough_recent_samples() {
+        assert_eq!(latency(&[5_000, 5_000]).median_ms(), None);
+        assert_eq!(latency(&[100, 5_000, 300]).median_ms(), Some(300));
+
+        // 旧样本滚出窗口
+        let mut stats = latency(&[9_000; LATENCY_WINDOW]);
+        for _ in 0..LATENCY_WINDOW / 2 + 1 {
+            stats.record(Duration::from_millis(100));
+        }
+        assert_eq!(stats.median_ms(), Some(100));
+    }
+}
@@ -6,6 +6,8 @@
 use std::collections::HashMap;
 use std::hash::Hash;
 
+use super::IdAllocator;
+
 /// 通用请求追踪器
 ///
 /// 支持任意 key 类型，用于管理同类请求的去重。
@@ -29,11 +31,11 @@ impl<K: Eq + Hash> RequestTracker<K> {
         }
     }
 
-    /// 发起新请求，返回分配的 req_id
+    /// 发起新请求，从 `ids` 分配并返回 req_id
     ///
     /// 如果同一 key 已有 pending 请求，旧请求会被覆盖（后续 accept 会失败）。
-    pub fn issue(&mut self, key: K, next_id_fn: impl FnOnce() -> u64) -> u64 {
-        let id = next_id_fn();
+    pub fn issue(&mut self, key: K, ids: &IdAllocator) -> u64 {
+        let id = ids.next();
         self.pending.insert(key, id);
         id
     }
@@ -54,7 +56,6 @@ impl<K: Eq + Hash> RequestTracker<K> {
     }
 
     /// 清除指定 key 的 pending 状态
-    #[allow(dead_code)]
     pub fn clear(&mut self, key: &K) {
         self.pending.remove(key);
     }
@@ -84,12 +85,22 @@ impl<K: Eq + Hash> RequestTracker<K> {
 pub enum RequestKey {
     /// 统一音源：搜索请求
     SourceSearch,
+    /// 搜索结果翻页（加载更多）请求
+    SourceSearchMore,
+    /// 搜索页打开专辑/歌手的曲目请求
+    SearchOpenTracks,
+    /// 搜索结果预览（单曲详情）请求
+    SongPreview,
     /// 登录二维码 key 请求
     LoginQrKey,
     /// 登录二维码轮询请求
     LoginQrPoll,
     /// Cookie 登录请求
     LoginSetCookie,
+    /// 发送短信验证码请求
+    LoginCaptchaSend,
+    /// 手机号登录：校验验证码与登录（依次进行）
+    LoginCellphone,
     /// 用户账号信息请求
     Account,
     /// 用户歌单列表请求
@@ -98,10 +109,20 @@ pub enum RequestKey {
     PlaylistDetail,
     /// 歌单歌曲详情分页请求
     PlaylistTracks,
+    /// 每日推荐歌曲请求
+    RecommendSongs,
+    /// 私人 FM 补充请求
+    PersonalFm,
     /// 播放链接请求
     SongUrl,
     /// 歌词请求
     Lyric,
+    /// 「我喜欢」歌曲 id 列表请求
+    LikeList,
+    /// 导出 CSV 时补全歌曲详情的批量请求
+    ExportDetails,
+    /// 检查不可用歌曲：详情、搜索替换版本与确认候选可播放（依次进行）
+    UnavailableCheck,
 }
 
 #[cfg(test)]
@@ -111,13 +132,9 @@ mod tests {
     #[test]
     fn test_issue_and_accept() {
         let mut tracker: RequestTracker<&str> = RequestTracker::new();
-        let mut id_counter = 1u64;
+        let ids = IdAllocator::default();
 
-        let req_id = tracker.issue("search", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
+        let req_id = tracker.issue("search", &ids);
 
         assert_eq!(req_id, 1);
         assert!(tracker.is_pending(&"search"));
@@ -130,19 +147,11 @@ mod tests {
     #[test]
     fn test_consecutive_issue_only_accepts_latest() {
         let mut tracker: RequestTracker<&str> = RequestTracker::new();
-        let mut id_counter = 1u64;
+        let ids = IdAllocator::default();
 
         // 连续发起两次请求
-        let req_id_1 = tracker.issue("search", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
-        let req_id_2 = tracker.issue("search", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
+        let req_id_1 = tracker.issue("search", &ids);
+        let req_id_2 = tracker.issue("search", &ids);
 
         assert_eq!(req_id_1, 1);
         assert_eq!(req_id_2, 2);
@@ -168,13 +177,9 @@ mod tests {
     #[test]
     fn test_clear_key() {
         let mut tracker: RequestTracker<&str> = RequestTracker::new();
-        let mut id_counter = 1u64;
+        let ids = IdAllocator::default();
 
-        tracker.issue("search", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
+        tracker.issue("search", &ids);
 
         assert!(tracker.is_pending(&"search"));
 
@@ -185,18 +190,10 @@ mod tests {
     #[test]
     fn test_reset_all() {
         let mut tracker: RequestTracker<&str> = RequestTracker::new();
-        let mut id_counter = 1u64;
-
-        tracker.issue("search", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
-        tracker.issue("playlists", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
+        let ids = IdAllocator::default();
+
+        tracker.issue("search", &ids);
+        tracker.issue("playlists", &ids);
 
         assert!(tracker.is_pending(&"search"));
         assert!(tracker.is_pending(&"playlists"));
@@ -210,18 +207,10 @@ mod tests {
     #[test]
     fn test_different_keys_independent() {
         let mut tracker: RequestTracker<&str> = RequestTracker::new();
-        let mut id_counter = 1u64;
-
-        let search_id = tracker.issue("search", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
-        let playlists_id = tracker.issue("playlists", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
+        let ids = IdAllocator::default();
+
+        let search_id = tracker.issue("search", &ids);
+        let playlists_id = tracker.issue("playlists", &ids);
 
         // 两个不同 key 的请求应该独立
         assert!(tracker.accept(&"search", search_id));
@@ -232,13 +221,9 @@ mod tests {
     #[test]
     fn test_request_key_enum() {
         let mut tracker: RequestTracker<RequestKey> = RequestTracker::new();
-        let mut id_counter = 1u64;
+        let ids = IdAllocator::default();
 
-        let req_id = tracker.issue(RequestKey::SourceSearch, || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
+        let req_id = tracker.issue(RequestKey::SourceSearch, &ids);
 
         assert!(tracker.accept(&RequestKey::SourceSearch, req_id));
     }
@@ -246,15 +231,11 @@ mod tests {
     #[test]
     fn test_get_pending() {
         let mut tracker: RequestTracker<&str> = RequestTracker::new();
-        let mut id_counter = 1u64;
+        let ids = IdAllocator::default();
 
         assert_eq!(tracker.get_pending(&"search"), None);
 
-        let req_id = tracker.issue("search", || {
-            let id = id_counter;
-            id_counter += 1;
-            id
-        });
+        let req_id = tracker.issue("search", &ids);
 
         assert_eq!(tracker.get_pending(&"search"), Some(req_id));
     }
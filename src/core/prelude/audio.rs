@@ -1,3 +1,4 @@
 pub use crate::audio_worker::{
     AudioBufferState, AudioCommand, AudioEvent, AudioLoadStage, AudioPlaybackMode, AudioStreamHint,
+    TrackTags,
 };
@@ -1 +1 @@
-pub use crate::core::infra::{NextSongCacheManager, RequestKey, RequestTracker};
+pub use crate::core::infra::{IdAllocator, NextSongCacheManager, RequestKey, RequestTracker};
@@ -7,12 +7,10 @@ pub mod effects;
 pub mod infra;
 pub mod messages;
 pub mod netease;
-pub mod utils;
 
 pub use app::{App, View};
 pub use audio::{AudioCommand, AudioEvent, AudioLoadStage};
 pub use effects::{CoreDispatch, CoreEffects};
-pub use infra::{NextSongCacheManager, RequestKey, RequestTracker};
+pub use infra::{IdAllocator, NextSongCacheManager, RequestKey, RequestTracker};
 pub use messages::{AppCommand, AppEvent};
 pub use netease::{NeteaseCommand, NeteaseEvent};
-pub use utils::*;
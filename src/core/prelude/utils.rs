@@ -1 +0,0 @@
-pub use crate::core::utils::next_id;
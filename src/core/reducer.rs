@@ -1,19 +1,28 @@
-use crate::app::App;
+use crate::app::{App, Toast};
 use crate::audio_worker::{AudioBackend, AudioCommand, AudioEvent, AudioSettings};
+use crate::data_paths::{DataPaths, prepare_audio_cache_dir, resolve_audio_cache_dir};
+use crate::error::{MessageError, NeteaseErrorVariant};
 use crate::messages::app::{AppCommand, AppEvent};
 use crate::netease::NeteaseClientConfig;
 use crate::netease::actor::NeteaseEvent;
 use crate::settings as app_settings;
 
+use futures_util::FutureExt;
+use std::panic::AssertUnwindSafe;
+use std::path::PathBuf;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 
 use crate::core::effects::{CoreDispatch, CoreEffect, CoreEffects, run_effects};
-use crate::core::infra::{NextSongCacheManager, PreloadManager, RequestKey, RequestTracker};
+use crate::core::infra::{
+    IdAllocator, NextSongCacheManager, PreloadManager, RequestKey, RequestTracker,
+};
 
 use crate::features::settings as settings_handlers;
 
+mod export;
+mod favorites;
 mod login;
 mod lyrics;
 mod player;
@@ -31,19 +40,51 @@ enum CoreMsg {
     Netease(NeteaseEvent),
     Audio(AudioEvent),
     QrPoll,
+    /// 搜索结果预览的防抖到期
+    SearchPreviewDue(i64),
+    /// CSV 导出写盘结束
+    ExportWritten(crate::features::export::ExportSummary, std::io::Result<()>),
 }
 
 struct CoreState {
     app: App,
-    req_id: u64,
+    req_ids: IdAllocator,
     preload_mgr: PreloadManager,
     next_song_cache: NextSongCacheManager,
     settings: app_settings::AppSettings,
     request_tracker: RequestTracker<RequestKey>,
     playlist_tracks_loader: Option<playlists::PlaylistTracksLoad>,
     song_request_titles: std::collections::HashMap<i64, String>,
+    pending_likes: std::collections::HashMap<u64, crate::features::favorites::PendingLike>,
+    pending_playlist_edits:
+        std::collections::HashMap<u64, crate::features::playlists::edit::PendingPlaylistEdit>,
+    /// 进行中的不可用歌曲检查
+    unavailable_check: Option<crate::features::playlists::unavailable::UnavailableCheck>,
+    /// 已确认、等待接口返回的不可用歌曲替换
+    pending_swaps:
+        std::collections::HashMap<u64, crate::features::playlists::unavailable::PendingSwap>,
+    /// 等待播放链接的歌曲下载
+    pending_downloads:
+        std::collections::HashMap<u64, crate::features::player::download::PendingDownload>,
+    /// 正在播放的歌曲，切歌/结束时写入播放历史
+    play_session: Option<crate::play_history::PlaySession>,
+    /// 正在播放的歌曲的听歌记录上报（播放超过 30 秒或自然结束时上报一次）
+    scrobble: Option<crate::features::player::scrobble::ListenScrobble>,
+    /// 过旧的播放状态，等待用户在弹窗中选择是否恢复队列
+    pending_restore: Option<crate::player_state::AppStateSnapshot>,
+    search_preview: crate::features::search::preview::PreviewDebounce,
+    cached_tracks: crate::features::player::cache_status::CachedTracksWatch,
+    /// 补拉歌曲详情中的 CSV 导出
+    export: Option<crate::features::export::CsvExport>,
+    /// 写盘中的 CSV 导出（在 select 循环中等待，见 [`export_written`]）
+    export_writer: Option<ExportWriter>,
 }
 
+type ExportWriter = (
+    crate::features::export::ExportSummary,
+    tokio::task::JoinHandle<std::io::Result<()>>,
+);
+
 enum UiAction {
     Handled,
     NotHandled,
@@ -59,13 +100,25 @@ impl CoreState {
     fn new_with_settings(_data_dir: &std::path::Path, settings: app_settings::AppSettings) -> Self {
         Self {
             app: App::default(),
-            req_id: 1,
+            req_ids: IdAllocator::default(),
             preload_mgr: PreloadManager::default(),
             next_song_cache: NextSongCacheManager::default(),
             settings,
             request_tracker: RequestTracker::new(),
             playlist_tracks_loader: None,
             song_request_titles: Default::default(),
+            pending_likes: Default::default(),
+            pending_playlist_edits: Default::default(),
+            unavailable_check: None,
+            pending_swaps: Default::default(),
+            pending_downloads: Default::default(),
+            play_session: None,
+            scrobble: None,
+            pending_restore: None,
+            search_preview: Default::default(),
+            cached_tracks: Default::default(),
+            export: None,
+            export_writer: None,
         }
     }
 }
@@ -79,6 +132,10 @@ async fn reduce(
 ) -> bool {
     match msg {
         CoreMsg::QrPoll => login::handle_qr_poll(state, effects),
+        CoreMsg::SearchPreviewDue(song_id) => search::handle_preview_due(song_id, state, effects),
+        CoreMsg::ExportWritten(summary, result) => {
+            export::handle_written(summary, result, state, effects)
+        }
         CoreMsg::Ui(cmd) => {
             match settings::handle_ui(&cmd, state, effects, data_dir).await {
                 UiAction::Quit => return true,
@@ -91,6 +148,12 @@ async fn reduce(
                 UiAction::NotHandled => {}
             }
 
+            if matches!(
+                export::handle_ui(&cmd, state, effects, data_dir).await,
+                UiAction::Handled
+            ) {
+                return false;
+            }
             if matches!(
                 login::handle_ui(&cmd, state, effects).await,
                 UiAction::Handled
@@ -121,36 +184,191 @@ async fn reduce(
             ) {
                 return false;
             }
-        }
-        CoreMsg::Netease(evt) => {
-            if login::handle_netease_event(&evt, state, effects).await {
-                return false;
-            }
-            if playlists::handle_netease_event(&evt, state, effects).await {
-                return false;
-            }
-            if search::handle_netease_event(&evt, state, effects).await {
-                return false;
-            }
-            if player::handle_netease_event(&evt, state, effects).await {
-                return false;
-            }
-            if lyrics::handle_netease_event(&evt, state, effects).await {
+            if matches!(
+                favorites::handle_ui(&cmd, state, effects).await,
+                UiAction::Handled
+            ) {
                 return false;
             }
-            settings::handle_netease_event(&evt, state, effects).await;
         }
-        CoreMsg::Audio(evt) => {
-            player::handle_audio_event(evt, state, effects).await;
+        CoreMsg::Netease(NeteaseEvent::NeedLogin { req_id }) => {
+            // 先按普通错误分发，让发起请求的功能收尾（清理挂起请求、加载状态），
+            // 各自的错误提示由统一的「需要登录」提示取代
+            let evt = NeteaseEvent::Error {
+                req_id,
+                error: MessageError::Netease(NeteaseErrorVariant::NeedLogin),
+            };
+            let mut feature_effects = CoreEffects::default();
+            handle_netease_event(&evt, state, &mut feature_effects).await;
+            effects.extend_without_notices(feature_effects);
+            login::handle_need_login(state, effects);
         }
+        CoreMsg::Netease(evt) => handle_netease_event(&evt, state, effects).await,
+        CoreMsg::Audio(evt) => player::handle_audio_event(evt, state, effects, data_dir).await,
     }
 
+    // 下载随音频事件结束，导出随网络事件或写盘结束
+    if ui::finish_quit_wait(&mut state.app) {
+        effects.emit_state(&state.app);
+    }
     false
 }
 
+/// 依次交给各功能处理，直到有一个接手
+async fn handle_netease_event(
+    evt: &NeteaseEvent,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+) {
+    if login::handle_netease_event(evt, state, effects).await {
+        return;
+    }
+    if playlists::handle_netease_event(evt, state, effects).await {
+        return;
+    }
+    if search::handle_netease_event(evt, state, effects).await {
+        return;
+    }
+    if player::handle_netease_event(evt, state, effects).await {
+        return;
+    }
+    if lyrics::handle_netease_event(evt, state, effects).await {
+        return;
+    }
+    if favorites::handle_netease_event(evt, state, effects).await {
+        return;
+    }
+    if export::handle_netease_event(evt, state, effects).await {
+        return;
+    }
+    settings::handle_netease_event(evt, state, effects).await;
+}
+
+/// 等待写盘中的 CSV 导出结束；没有时一直挂起（select 分支另有条件守卫）
+async fn export_written(writer: &mut Option<ExportWriter>) -> CoreMsg {
+    let Some((_, handle)) = writer.as_mut() else {
+        return std::future::pending().await;
+    };
+    let joined = handle.await;
+    let Some((summary, _)) = writer.take() else {
+        unreachable!("checked above");
+    };
+    let result =
+        joined.unwrap_or_else(|e| Err(std::io::Error::other(format!("写盘任务异常退出: {e}"))));
+    CoreMsg::ExportWritten(summary, result)
+}
+
+/// 快照保存时间超过阈值（小时，0 表示不限）时视为过旧
+fn is_stale_snapshot(age_ms: u64, threshold_hours: u64) -> bool {
+    threshold_hours > 0 && age_ms > threshold_hours.saturating_mul(3_600_000)
+}
+
+fn snapshot_age_label(age_ms: u64) -> String {
+    let hours = age_ms / 3_600_000;
+    if hours >= 24 {
+        format!("{} 天", hours / 24)
+    } else {
+        format!("{} 小时", hours.max(1))
+    }
+}
+
+/// 应用启动时加载到的状态快照，返回是否恢复成功
+///
+/// 先把 settings 同步到 App，再由快照覆盖上次会话的播放器状态；
+/// 关闭「启动时恢复队列」时跳过播放队列与进度。
+/// 快照过旧时同样先跳过队列与进度，暂存快照并弹窗询问（见 `RestorePromptAccept`）。
+fn restore_startup_state(
+    state: &mut CoreState,
+    loaded: Result<crate::player_state::AppStateSnapshot, crate::player_state::PlayerStateError>,
+) -> bool {
+    settings_handlers::apply_settings_to_app(&mut state.app, &state.settings);
+
+    let snapshot = match loaded {
+        Ok(snapshot) => snapshot,
+        Err(crate::player_state::PlayerStateError::Io(ref e))
+            if e.kind() == std::io::ErrorKind::NotFound =>
+        {
+            tracing::debug!("首次启动，无历史状态");
+            return false;
+        }
+        Err(e) => {
+            tracing::warn!("加载状态失败: {}, 使用默认状态", e);
+            return false;
+        }
+    };
+
+    let age_ms = u64::try_from(
+        chrono::Utc::now()
+            .timestamp_millis()
+            .saturating_sub(snapshot.saved_at_epoch_ms),
+    )
+    .unwrap_or(0);
+    let stale = state.app.restore_queue_on_startup
+        && !snapshot.player.play_queue.songs.is_empty()
+        && is_stale_snapshot(age_ms, state.settings.stale_state_prompt_hours);
+    let restore_queue = state.app.restore_queue_on_startup && !stale;
+    if let Err(e) =
+        crate::player_state::apply_snapshot_to_app(&snapshot, &mut state.app, restore_queue)
+    {
+        tracing::warn!("状态恢复失败: {}, 使用默认状态", e);
+        return false;
+    }
+
+    tracing::trace!(
+        play_song_id = ?state.app.play_song_id,
+        paused = state.app.paused,
+        paused_at = state.app.play_paused_at.is_some(),
+        paused_accum_ms = state.app.play_paused_accum_ms,
+        elapsed_ms = playback_elapsed_ms_for_log(&state.app),
+        total_ms = ?state.app.play_total_ms,
+        saved_at_epoch_ms = snapshot.saved_at_epoch_ms,
+        started_at_epoch_ms = snapshot.player.progress.started_at_epoch_ms,
+        snapshot_paused = snapshot.player.progress.paused,
+        snapshot_paused_accum_ms = snapshot.player.progress.paused_accum_ms,
+        "🎵 [StateRestoreDbg] restore applied"
+    );
+    tracing::info!(
+        play_song_id = ?state.app.play_song_id,
+        play_queue_len = state.app.play_queue.songs().len(),
+        current_index = ?state.app.play_queue.current_index(),
+        paused = state.app.paused,
+        volume = state.app.volume,
+        play_mode = ?state.app.play_mode,
+        restore_queue,
+        last_view = ?state.app.last_view,
+        "🎵 [StateRestore] 播放状态已恢复（默认暂停）"
+    );
+
+    // 验证状态一致性
+    if let Some(song_id) = state.app.play_song_id {
+        let song_exists = state.app.play_queue.songs().iter().any(|s| s.id == song_id);
+        if !song_exists {
+            tracing::warn!(
+                song_id,
+                "🎵 [StateRestore] 状态不一致：play_song_id 存在但队列中找不到对应歌曲"
+            );
+        }
+    }
+
+    if stale {
+        tracing::info!(
+            age_ms,
+            "🎵 [StateRestore] 播放状态过旧，等待用户确认是否恢复队列"
+        );
+        state.app.restore_prompt = Some(format!(
+            "发现 {}前的播放状态：恢复队列 (r) / 忽略 (i)",
+            snapshot_age_label(age_ms)
+        ));
+        state.pending_restore = Some(snapshot);
+    }
+    true
+}
+
+/// `cache_dir` 为 `--cache-dir` 指定的音频缓存目录，优先于设置中的 `audio_cache_dir`
 pub fn spawn_app_actor(
-    cfg: NeteaseClientConfig,
+    mut cfg: NeteaseClientConfig,
     audio_backend: AudioBackend,
+    cache_dir: Option<PathBuf>,
 ) -> (
     mpsc::Sender<AppCommand>,
     mpsc::Receiver<AppEvent>,
@@ -164,8 +382,36 @@ pub fn spawn_app_actor(
     // 先加载 settings，以便创建配置化的 audio worker
     let settings = app_settings::load_settings(&data_dir);
 
+    // 指定的缓存目录不可用时退回数据目录下的默认位置，启动后提示
+    let mut cache_paths = DataPaths::new(&data_dir).with_audio_cache_dir(resolve_audio_cache_dir(
+        cache_dir.as_deref(),
+        settings.audio_cache_dir.as_deref(),
+    ));
+    let mut cache_dir_error = None;
+    if cache_paths.audio_cache_overridden() {
+        let dir = cache_paths.audio_cache_dir();
+        if let Err(e) = prepare_audio_cache_dir(&dir) {
+            tracing::warn!(dir = %dir.display(), err = %e, "音频缓存目录不可用，改用默认位置");
+            cache_dir_error = Some(format!(
+                "音频缓存目录 {} 不可用（{e}），本次使用默认位置",
+                dir.display()
+            ));
+            cache_paths = cache_paths.with_audio_cache_dir(None);
+        }
+    }
+    let audio_cache_dir = cache_paths.audio_cache_dir();
+
+    // 按 host 的限流冷却，NeteaseActor 与 TransferActor 共享
+    let cooldown = crate::cooldown::CooldownHandle::default();
+    let proxy = cfg.proxy.clone();
+    // 网易接口与音频下载共用超时与重试设置
+    cfg.timeout = Duration::from_secs(settings.http_timeout_secs);
+    cfg.connect_timeout = Duration::from_secs(settings.http_connect_timeout_secs);
+    cfg.retries = settings.download_retries;
+    cfg.retry_backoff = Duration::from_millis(settings.download_retry_backoff_ms);
+    cfg.retry_backoff_max = Duration::from_millis(settings.download_retry_backoff_max_ms);
     let (tx_netease_hi, tx_netease_lo, mut rx_netease) =
-        crate::netease::actor::spawn_netease_actor(cfg);
+        crate::netease::actor::spawn_netease_actor(cfg, cooldown.clone());
 
     // Audio worker is now tokio-native, no need for std mpsc bridge
     let transfer_config = crate::audio_worker::TransferConfig {
@@ -176,19 +422,47 @@ pub fn spawn_app_actor(
         download_retry_backoff_ms: settings.download_retry_backoff_ms,
         download_retry_backoff_max_ms: settings.download_retry_backoff_max_ms,
         audio_cache_max_mb: settings.audio_cache_max_mb,
+        cooldown,
+        proxy,
     };
     let audio_settings = AudioSettings {
         crossfade_ms: settings.crossfade_ms,
+        idle_release_after: (settings.audio_idle_release_secs > 0)
+            .then(|| Duration::from_secs(settings.audio_idle_release_secs)),
+        normalize_volume: settings.normalize_volume,
+        playback_speed: settings.playback_speed,
+        eq_bass_db: settings.eq_bass_db,
+        eq_treble_db: settings.eq_treble_db,
     };
     let (tx_audio, mut rx_audio_evt) = crate::audio_worker::spawn_audio_worker(
         audio_backend,
-        data_dir.clone(),
+        audio_cache_dir.clone(),
         transfer_config,
         audio_settings,
     );
 
+    // 全局快捷键（媒体键）监听线程，随 actor 任务结束而注销
+    let global_hotkeys = settings.global_hotkeys.then(|| {
+        crate::keybindings::start_global_hotkeys(
+            crate::keybindings::load_global_hotkeys(&data_dir),
+            tx_cmd.clone(),
+        )
+    });
+
+    // MPRIS：转发给 UI 的事件流中取状态快照，D-Bus 调用转成 AppCommand
+    #[cfg(feature = "mpris")]
+    let rx_evt = crate::mpris::start_mpris(tx_cmd.clone(), rx_evt);
+
+    // 音频焦点：其它程序开始/停止播放声音时发送 AudioFocusChanged
+    crate::audio_focus::start_audio_focus(tx_cmd.clone());
+
     let join_handle = tokio::spawn(async move {
+        let _global_hotkeys = global_hotkeys.flatten();
         let mut state = CoreState::new_with_settings(&data_dir, settings);
+        state.app.audio_cache_dir = audio_cache_dir;
+        state.app.audio_cache_previous_dir = cache_paths
+            .audio_cache_overridden()
+            .then(|| cache_paths.default_audio_cache_dir());
 
         // 加载 keybindings.toml（失败时回退到默认绑定）
         state.app.keybindings =
@@ -197,72 +471,35 @@ pub fn spawn_app_actor(
         let mut state_save_task: Option<tokio::task::JoinHandle<()>> = None;
 
         // ========== 加载保存的状态 ==========
-        let restored_player_state = match crate::player_state::load_player_state_async(&data_dir)
-            .await
-        {
-            Ok(snapshot) => {
-                match crate::player_state::apply_snapshot_to_app(&snapshot, &mut state.app) {
-                    Ok(()) => {
-                        tracing::trace!(
-                            play_song_id = ?state.app.play_song_id,
-                            paused = state.app.paused,
-                            paused_at = state.app.play_paused_at.is_some(),
-                            paused_accum_ms = state.app.play_paused_accum_ms,
-                            elapsed_ms = playback_elapsed_ms_for_log(&state.app),
-                            total_ms = ?state.app.play_total_ms,
-                            saved_at_epoch_ms = snapshot.saved_at_epoch_ms,
-                            started_at_epoch_ms = snapshot.player.progress.started_at_epoch_ms,
-                            snapshot_paused = snapshot.player.progress.paused,
-                            snapshot_paused_accum_ms = snapshot.player.progress.paused_accum_ms,
-                            "🎵 [StateRestoreDbg] restore applied"
-                        );
-                        tracing::info!(
-                            play_song_id = ?state.app.play_song_id,
-                            play_queue_len = state.app.play_queue.songs().len(),
-                            current_index = ?state.app.play_queue.current_index(),
-                            paused = state.app.paused,
-                            volume = state.app.volume,
-                            play_mode = ?state.app.play_mode,
-                            "🎵 [StateRestore] 播放状态已恢复（默认暂停）"
-                        );
-
-                        // 验证状态一致性
-                        if let Some(song_id) = state.app.play_song_id {
-                            let song_exists =
-                                state.app.play_queue.songs().iter().any(|s| s.id == song_id);
-                            if !song_exists {
-                                tracing::warn!(
-                                    song_id,
-                                    "🎵 [StateRestore] 状态不一致：play_song_id 存在但队列中找不到对应歌曲"
-                                );
-                            }
-                        }
-                        true
-                    }
-                    Err(e) => {
-                        tracing::warn!("状态恢复失败: {}, 使用默认状态", e);
-                        false
-                    }
-                }
-            }
+        let loaded = crate::player_state::load_player_state_async(&data_dir).await;
+        let queue = match crate::player_state::load_queue_async(&data_dir).await {
+            Ok(queue) => Some(queue),
             Err(crate::player_state::PlayerStateError::Io(ref e))
                 if e.kind() == std::io::ErrorKind::NotFound =>
             {
-                tracing::debug!("首次启动，无历史状态");
-                false
+                None
             }
             Err(e) => {
-                tracing::warn!("加载状态失败: {}, 使用默认状态", e);
-                false
+                tracing::warn!("加载 queue.json 失败: {}", e);
+                None
             }
         };
+        restore_startup_state(
+            &mut state,
+            crate::player_state::resolve_newest(loaded, queue),
+        );
+        if let Some(report) = crate::crash::take_unseen_report(&data_dir) {
+            tracing::warn!(path = %report.display(), "上次运行发生崩溃");
+            state.app.toast = Some(Toast::warning(format!(
+                "上次运行发生崩溃，报告已保存到 {}",
+                report.display()
+            )));
+        }
+        if let Some(msg) = cache_dir_error {
+            state.app.toast = Some(Toast::error(msg));
+        }
         // ========== 加载完成 ==========
 
-        if restored_player_state {
-            state.app.lyrics_offset_ms = state.settings.lyrics_offset_ms;
-        } else {
-            settings_handlers::apply_settings_to_app(&mut state.app, &state.settings);
-        }
         let _ = tx_audio
             .send(AudioCommand::SetCacheBr(state.app.play_br))
             .await;
@@ -273,6 +510,11 @@ pub fn spawn_app_actor(
             .send(AudioCommand::SetCrossfadeMs(state.app.crossfade_ms))
             .await;
 
+        // 队列变化后单独写 queue.json（见 player_state::queue）
+        let mut queue_debounce =
+            crate::player_state::QueueSaveDebounce::new(state.app.play_queue.revision());
+        let mut queue_save_task: Option<tokio::task::JoinHandle<()>> = None;
+
         let mut qr_poll = tokio::time::interval(Duration::from_secs(2));
         let mut state_save_timer = tokio::time::interval(Duration::from_secs(30));
         state_save_timer.tick().await; // 立即消耗第一个周期
@@ -284,9 +526,46 @@ pub fn spawn_app_actor(
         };
 
         loop {
+            let queue_deadline = queue_debounce.deadline();
+            let preview_deadline = state.search_preview.deadline();
             let msg = tokio::select! {
                 _ = qr_poll.tick() => CoreMsg::QrPoll,
+                _ = tokio::time::sleep_until(tokio::time::Instant::from_std(
+                    preview_deadline.unwrap_or_else(std::time::Instant::now),
+                )), if preview_deadline.is_some() => {
+                    match state.search_preview.take_due(std::time::Instant::now()) {
+                        Some(song_id) => CoreMsg::SearchPreviewDue(song_id),
+                        None => continue,
+                    }
+                }
+                _ = tokio::time::sleep_until(tokio::time::Instant::from_std(
+                    queue_deadline.unwrap_or_else(std::time::Instant::now),
+                )), if queue_deadline.is_some() => {
+                    if !queue_debounce.take_due(std::time::Instant::now()) {
+                        continue;
+                    }
+                    // 等待用户决定是否恢复旧状态期间，队列为空，不能覆盖 queue.json
+                    if state.pending_restore.is_some() {
+                        continue;
+                    }
+                    // 保证写入顺序（queue.json 很小，等待代价可以忽略）
+                    if let Some(h) = queue_save_task.take() {
+                        let _ = h.await;
+                    }
+                    let data_dir = data_dir.clone();
+                    let file = crate::player_state::QueueFile::from_app(&state.app);
+                    queue_save_task = Some(tokio::spawn(async move {
+                        if let Err(e) = crate::player_state::save_queue_async(&data_dir, file).await {
+                            tracing::warn!("保存播放队列失败: {}", e);
+                        }
+                    }));
+                    continue;
+                }
                 _ = state_save_timer.tick() => {
+                    // 用户尚未决定是否恢复旧状态时不覆盖它
+                    if state.pending_restore.is_some() {
+                        continue;
+                    }
                     // 定时保存状态（后台写盘，避免阻塞主循环）
                     if state_save_task.as_ref().is_some_and(|h| !h.is_finished()) {
                         tracing::trace!("🎵 [StateSaveDbg] previous save still running, skip tick");
@@ -316,25 +595,70 @@ pub fn spawn_app_actor(
                     }));
                     continue; // 继续循环，不生成 CoreMsg
                 }
+                msg = export_written(&mut state.export_writer), if state.export_writer.is_some() => msg,
                 Some(cmd) = rx_cmd.recv() => CoreMsg::Ui(cmd),
                 Some(evt) = rx_netease.recv() => CoreMsg::Netease(evt),
                 Some(evt) = rx_audio_evt.recv() => CoreMsg::Audio(evt),
             };
 
+            if let CoreMsg::Ui(cmd) = &msg {
+                let redact_titles = state.app.focus_mode && state.app.focus_mask_titles;
+                crate::crash::record_command(cmd, redact_titles);
+            }
             let mut effects = CoreEffects::default();
-            let should_quit = reduce(msg, &mut state, &mut effects, &data_dir).await;
-            // 处理 SetToast 效果（直接修改 state）
+            // 单条消息处理 panic 时丢弃该条的效果并继续运行，避免整个核心退出后界面卡死
+            let reduced = AssertUnwindSafe(reduce(msg, &mut state, &mut effects, &data_dir))
+                .catch_unwind()
+                .await;
+            let should_quit = match reduced {
+                Ok(should_quit) => should_quit,
+                Err(payload) => {
+                    tracing::error!(panic = %crate::crash::panic_message(&*payload), "处理消息时 panic，已忽略该消息");
+                    effects = CoreEffects::default();
+                    effects.set_toast(Toast::error("内部错误，已忽略该操作（详见日志）"));
+                    effects.emit_state(&state.app);
+                    false
+                }
+            };
+            crate::crash::record_view(state.app.view);
+            queue_debounce.observe(state.app.play_queue.revision(), std::time::Instant::now());
+            state.search_preview.observe(
+                crate::features::search::preview::preview_target(&state.app),
+                std::time::Instant::now(),
+            );
+            state.cached_tracks.observe(&state.app, &mut effects);
+            // 处理 SetToast 效果（直接修改 state）；专注模式下只保留错误
             for effect in &effects.actions {
-                if let CoreEffect::SetToast(toast) = effect {
-                    state.app.toast = Some(toast.clone());
+                if let CoreEffect::SetToast(toast) = effect
+                    && let Some(toast) = toast.clone().filter_for_focus_mode(state.app.focus_mode)
+                {
+                    state.app.toast = Some(toast);
                 }
             }
+            // 所有通知（含专注模式隐藏的）记入通知历史；面板打开时刷新
+            if effects.record_notifications(&mut state.app) && state.app.notifications_visible {
+                effects.emit_state(&state.app);
+            }
             run_effects(effects, &dispatch).await;
             if should_quit {
                 // ========== 保存播放状态 ==========
+                if let Some(record) = player::finish_play_session(&mut state) {
+                    player::append_history(&data_dir, record).await;
+                }
                 if let Some(h) = state_save_task.take() {
                     let _ = h.await;
                 }
+                if let Some(h) = queue_save_task.take() {
+                    let _ = h.await;
+                }
+                if let Some((_, h)) = state.export_writer.take() {
+                    let _ = h.await;
+                }
+                if state.pending_restore.is_some() {
+                    // 未作选择就退出：保留旧状态，下次启动再询问
+                    tracing::info!("旧播放状态待确认，跳过保存");
+                    break;
+                }
                 tracing::trace!(
                     save_kind = "quit",
                     play_song_id = ?state.app.play_song_id,
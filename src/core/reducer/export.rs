@@ -0,0 +1,262 @@
+use super::{CoreState, UiAction};
+use crate::app::Toast;
+use crate::core::effects::CoreEffects;
+use crate::features::export::{self as export_handlers, ExportScope, ExportStep, ExportSummary};
+use crate::messages::app::AppCommand;
+use crate::netease::actor::NeteaseEvent;
+
+pub async fn handle_ui(
+    cmd: &AppCommand,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+    data_dir: &std::path::Path,
+) -> UiAction {
+    match cmd {
+        AppCommand::ExportCsv { all } => {
+            let scope = if *all {
+                ExportScope::AllPlaylists
+            } else {
+                ExportScope::OpenPlaylist
+            };
+            start(scope, state, effects, data_dir);
+            UiAction::Handled
+        }
+        _ => UiAction::NotHandled,
+    }
+}
+
+/// 开始导出（快捷键与设置页共用）；同一时间只进行一个导出
+pub(super) fn start(
+    scope: ExportScope,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+    data_dir: &std::path::Path,
+) {
+    if state.export.is_some() || state.export_writer.is_some() {
+        effects.toast("已有导出正在进行，请稍候");
+        return;
+    }
+    let step = export_handlers::start_export(
+        scope,
+        &mut state.app,
+        data_dir,
+        &state.req_ids,
+        &mut state.request_tracker,
+        &mut state.export,
+        effects,
+    );
+    apply_step(step, state, effects);
+}
+
+pub async fn handle_netease_event(
+    evt: &NeteaseEvent,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+) -> bool {
+    let (req_id, previews) = match evt {
+        NeteaseEvent::SongPreviews { req_id, previews } => (*req_id, Ok(previews.as_slice())),
+        NeteaseEvent::Error { req_id, error } => (*req_id, Err(error)),
+        _ => return false,
+    };
+    let step = export_handlers::handle_details_event(
+        req_id,
+        previews,
+        &mut state.app,
+        &state.req_ids,
+        &mut state.request_tracker,
+        &mut state.export,
+        effects,
+    );
+    apply_step(step, state, effects)
+}
+
+/// 数据齐全时把写盘交给后台任务，结果由 select 循环交回 [`handle_written`]
+fn apply_step(step: ExportStep, state: &mut CoreState, effects: &mut CoreEffects) -> bool {
+    match step {
+        ExportStep::Ignored => false,
+        ExportStep::Fetching => true,
+        ExportStep::Ready(ready) => {
+            let summary = ready.summary.clone();
+            state.export_writer =
+                Some((summary, tokio::spawn(export_handlers::write_export(ready))));
+            effects.emit_state(&state.app);
+            true
+        }
+    }
+}
+
+/// 写盘结束：结束后台任务并提示文件路径
+pub fn handle_written(
+    summary: ExportSummary,
+    result: std::io::Result<()>,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+) {
+    match result {
+        Ok(()) => {
+            tracing::info!(
+                path = %summary.path.display(),
+                songs = summary.songs,
+                incomplete = summary.incomplete,
+                "CSV 导出完成"
+            );
+            state.app.tasks.complete(summary.task_id);
+            effects.toast(summary.message());
+        }
+        Err(e) => {
+            tracing::warn!(path = %summary.path.display(), "CSV 导出失败: {e}");
+            state.app.tasks.fail(summary.task_id, e.to_string());
+            effects.set_toast(Toast::error(format!("导出 CSV 失败: {e}")));
+        }
+    }
+    effects.emit_state(&state.app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{PlaylistMode, Song, TaskKind, TaskStatus, View};
+    use crate::core::effects::CoreEffect;
+    use crate::domain::model::SongPreview;
+    use crate::error::MessageError;
+    use crate::features::export::DETAIL_BATCH;
+    use crate::netease::actor::NeteaseCommand;
+
+    fn open_tracks(state: &mut CoreState) {
+        state.app.view = View::Playlists;
+        state.app.playlist_mode = PlaylistMode::Tracks;
+        state.app.playlist_tracks_recommend = true;
+        state.app.playlist_tracks = vec![Song {
+            id: 1,
+            name: "晴天".to_owned(),
+            ..Default::default()
+        }];
+    }
+
+    fn sent_batch(effects: &CoreEffects) -> (u64, Vec<i64>) {
+        effects
+            .actions
+            .iter()
+            .find_map(|e| match e {
+                CoreEffect::SendNeteaseLo {
+                    cmd: NeteaseCommand::SongPreviews { req_id, ids },
+                    ..
+                } => Some((*req_id, ids.clone())),
+                _ => None,
+            })
+            .expect("SongPreviews request")
+    }
+
+    fn running_progress(state: &CoreState) -> (u64, Option<u64>) {
+        let id = state.app.tasks.running_id(TaskKind::CsvExport).unwrap();
+        let task = state.app.tasks.get(id).unwrap();
+        (task.done, task.total)
+    }
+
+    #[tokio::test]
+    async fn export_key_fetches_details_then_writes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = CoreState::new(dir.path());
+        open_tracks(&mut state);
+
+        let mut effects = CoreEffects::default();
+        let cmd = AppCommand::ExportCsv { all: false };
+        assert!(matches!(
+            handle_ui(&cmd, &mut state, &mut effects, dir.path()).await,
+            UiAction::Handled
+        ));
+        let (req_id, _) = sent_batch(&effects);
+        assert!(state.export.is_some());
+
+        // 进行中再次导出被拒绝
+        let mut effects = CoreEffects::default();
+        handle_ui(&cmd, &mut state, &mut effects, dir.path()).await;
+        assert!(
+            effects
+                .actions
+                .iter()
+                .all(|e| !matches!(e, CoreEffect::SendNeteaseLo { .. }))
+        );
+
+        let evt = NeteaseEvent::SongPreviews {
+            req_id,
+            previews: Vec::new(),
+        };
+        let mut effects = CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(state.export.is_none());
+
+        let (summary, handle) = state.export_writer.take().expect("writer spawned");
+        let result = handle.await.unwrap();
+        let mut effects = CoreEffects::default();
+        handle_written(summary.clone(), result, &mut state, &mut effects);
+        assert!(summary.path.exists());
+        let task = state.app.tasks.get(summary.task_id).unwrap();
+        assert_eq!(task.kind, TaskKind::CsvExport);
+        assert_eq!(task.status, TaskStatus::Completed);
+        assert!(effects.actions.iter().any(
+            |e| matches!(e, CoreEffect::EmitToast(msg) if msg.contains(&summary.path.display().to_string()))
+        ));
+    }
+
+    #[tokio::test]
+    async fn details_are_fetched_in_batches_and_failed_batches_still_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut state = CoreState::new(dir.path());
+        open_tracks(&mut state);
+        state.app.playlist_tracks = (1..=DETAIL_BATCH as i64 + 5)
+            .map(|id| Song {
+                id,
+                ..Default::default()
+            })
+            .collect();
+
+        let mut effects = CoreEffects::default();
+        start(
+            ExportScope::OpenPlaylist,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        );
+        let (first_req, ids) = sent_batch(&effects);
+        assert_eq!(ids.len(), DETAIL_BATCH);
+        assert_eq!(running_progress(&state), (0, Some(DETAIL_BATCH as u64 + 5)));
+
+        let previews = ids
+            .iter()
+            .map(|&id| SongPreview {
+                id,
+                ..Default::default()
+            })
+            .collect();
+        let evt = NeteaseEvent::SongPreviews {
+            req_id: first_req,
+            previews,
+        };
+        let mut effects = CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let (second_req, ids) = sent_batch(&effects);
+        assert_eq!(ids.len(), 5);
+        assert_eq!(
+            running_progress(&state),
+            (DETAIL_BATCH as u64, Some(DETAIL_BATCH as u64 + 5))
+        );
+
+        // 过期响应不属于导出
+        let stale = NeteaseEvent::SongPreviews {
+            req_id: first_req,
+            previews: Vec::new(),
+        };
+        assert!(!handle_netease_event(&stale, &mut state, &mut CoreEffects::default()).await);
+
+        let failed = NeteaseEvent::Error {
+            req_id: second_req,
+            error: MessageError::other("timeout"),
+        };
+        assert!(handle_netease_event(&failed, &mut state, &mut CoreEffects::default()).await);
+        let (summary, handle) = state.export_writer.take().expect("writer spawned");
+        assert_eq!(summary.songs, DETAIL_BATCH + 5);
+        assert_eq!(summary.incomplete, 5);
+        handle.await.unwrap().unwrap();
+    }
+}
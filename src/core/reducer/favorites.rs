@@ -0,0 +1,277 @@
+use super::{CoreState, UiAction};
+use crate::core::effects::CoreEffects;
+use crate::features::favorites as favorites_handlers;
+use crate::messages::app::AppCommand;
+use crate::netease::actor::NeteaseEvent;
+
+pub async fn handle_ui(
+    cmd: &AppCommand,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+) -> UiAction {
+    match cmd {
+        AppCommand::PlayerToggleLike => {
+            favorites_handlers::handle_toggle_like(
+                &mut state.app,
+                &mut state.pending_likes,
+                &state.req_ids,
+                effects,
+            );
+            UiAction::Handled
+        }
+        _ => UiAction::NotHandled,
+    }
+}
+
+pub async fn handle_netease_event(
+    evt: &NeteaseEvent,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+) -> bool {
+    match evt {
+        NeteaseEvent::LikeList { req_id, ids } => favorites_handlers::handle_like_list_event(
+            *req_id,
+            ids,
+            &mut state.app,
+            &state.pending_likes,
+            &mut state.request_tracker,
+            effects,
+        ),
+        NeteaseEvent::SongLiked {
+            req_id,
+            song_id,
+            liked,
+        } => {
+            tracing::debug!(req_id, song_id, liked, "NeteaseActor: SongLiked");
+            favorites_handlers::handle_song_liked_event(
+                *req_id,
+                &mut state.app,
+                &mut state.pending_likes,
+                effects,
+            )
+        }
+        NeteaseEvent::Error { req_id, error } => favorites_handlers::handle_like_error(
+            *req_id,
+            error,
+            &mut state.app,
+            &mut state.pending_likes,
+            effects,
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{handle_netease_event, handle_ui};
+    use crate::app::{PlaylistPreload, PreloadStatus, Song};
+    use crate::core::effects::{CoreEffect, CoreEffects};
+    use crate::core::infra::preload_pub::store_completed_preload;
+    use crate::core::reducer::{CoreState, UiAction};
+    use crate::domain::model::Playlist;
+    use crate::error::MessageError;
+    use crate::messages::app::AppCommand;
+    use crate::netease::actor::{NeteaseCommand, NeteaseEvent};
+
+    const LIKED_PLAYLIST: i64 = 100;
+
+    fn playing_state(dir: &std::path::Path, song_id: i64, status: PreloadStatus) -> CoreState {
+        let mut state = CoreState::new(dir);
+        state.app.logged_in = true;
+        state.app.playlists = vec![Playlist {
+            id: LIKED_PLAYLIST,
+            name: "我喜欢的音乐".to_owned(),
+            track_count: 2,
+            special_type: 5,
+            subscribed: false,
+            updated_at_ms: None,
+        }];
+        state.app.playlist_preloads.insert(
+            LIKED_PLAYLIST,
+            PlaylistPreload {
+                status,
+                songs: Vec::new(),
+                missing_count: 0,
+                stats: None,
+                completed_at_ms: None,
+                added_at: std::collections::HashMap::new(),
+                track_ids_fingerprint: None,
+            },
+        );
+        state
+            .app
+            .play_queue
+            .set_songs(vec![Song::fixture(song_id)], Some(0));
+        state.app.play_song_id = Some(song_id);
+        state
+    }
+
+    async fn toggle(state: &mut CoreState) -> (u64, bool) {
+        let mut effects = CoreEffects::default();
+        let outcome = handle_ui(&AppCommand::PlayerToggleLike, state, &mut effects).await;
+        assert!(matches!(outcome, UiAction::Handled));
+        effects
+            .actions
+            .iter()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::LikeSong { req_id, like, .. },
+                    ..
+                } => Some((*req_id, *like)),
+                _ => None,
+            })
+            .expect("应发送 LikeSong 请求")
+    }
+
+    #[tokio::test]
+    async fn toggle_flips_icon_and_success_updates_completed_preload() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = playing_state(dir.path(), 7, PreloadStatus::Completed);
+        state
+            .app
+            .playlist_preloads
+            .get_mut(&LIKED_PLAYLIST)
+            .unwrap()
+            .songs = vec![Song::fixture(1), Song::fixture(2)];
+
+        let (req_id, like) = toggle(&mut state).await;
+        assert!(like);
+        assert!(state.app.liked_song_ids.contains(&7), "应乐观更新");
+
+        let mut effects = CoreEffects::default();
+        let evt = NeteaseEvent::SongLiked {
+            req_id,
+            song_id: 7,
+            liked: true,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let ids: Vec<i64> = state.app.playlist_preloads[&LIKED_PLAYLIST]
+            .songs
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, vec![7, 1, 2], "收藏的歌曲应插入最前");
+
+        let (req_id, like) = toggle(&mut state).await;
+        assert!(!like);
+        assert!(!state.app.liked_song_ids.contains(&7));
+        let evt = NeteaseEvent::SongLiked {
+            req_id,
+            song_id: 7,
+            liked: false,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.playlist_preloads[&LIKED_PLAYLIST].songs.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_toggle_rolls_back_and_reports_error() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = playing_state(dir.path(), 7, PreloadStatus::Completed);
+        state.app.liked_song_ids.insert(7);
+        state
+            .app
+            .playlist_preloads
+            .get_mut(&LIKED_PLAYLIST)
+            .unwrap()
+            .songs = vec![Song::fixture(7)];
+
+        let (req_id, like) = toggle(&mut state).await;
+        assert!(!like);
+        assert!(!state.app.liked_song_ids.contains(&7));
+
+        let mut effects = CoreEffects::default();
+        let evt = NeteaseEvent::Error {
+            req_id,
+            error: MessageError::other("网络错误"),
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(state.app.liked_song_ids.contains(&7), "失败应回滚");
+        assert_eq!(state.app.playlist_preloads[&LIKED_PLAYLIST].songs.len(), 1);
+        assert!(effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SetToast(toast) if toast.message.contains("取消收藏")
+        )));
+        assert!(state.pending_likes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unrelated_error_is_not_consumed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = playing_state(dir.path(), 7, PreloadStatus::Completed);
+        let mut effects = CoreEffects::default();
+        let evt = NeteaseEvent::Error {
+            req_id: 9999,
+            error: MessageError::other("x"),
+        };
+        assert!(!handle_netease_event(&evt, &mut state, &mut effects).await);
+    }
+
+    #[tokio::test]
+    async fn toggle_while_preload_loading_applies_on_completion() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = playing_state(
+            dir.path(),
+            7,
+            PreloadStatus::Loading {
+                loaded: 0,
+                total: 2,
+            },
+        );
+
+        let (req_id, _) = toggle(&mut state).await;
+        let mut effects = CoreEffects::default();
+        let evt = NeteaseEvent::SongLiked {
+            req_id,
+            song_id: 7,
+            liked: true,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(
+            state.app.playlist_preloads[&LIKED_PLAYLIST]
+                .songs
+                .is_empty()
+        );
+        assert_eq!(state.app.liked_pending.len(), 1);
+
+        // 预加载返回的是收藏前的列表
+        store_completed_preload(
+            &mut state.app,
+            LIKED_PLAYLIST,
+            vec![Song::fixture(1), Song::fixture(2)],
+            &[1, 2],
+            std::collections::HashMap::new(),
+        );
+
+        let ids: Vec<i64> = state.app.playlist_preloads[&LIKED_PLAYLIST]
+            .songs
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, vec![7, 1, 2]);
+        assert!(state.app.liked_pending.is_empty());
+    }
+
+    #[tokio::test]
+    async fn like_list_keeps_inflight_toggles() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = playing_state(dir.path(), 7, PreloadStatus::Completed);
+        let list_req = state.request_tracker.issue(
+            crate::core::infra::RequestKey::LikeList,
+            &crate::core::infra::IdAllocator::starting_at(500),
+        );
+
+        toggle(&mut state).await;
+        let mut effects = CoreEffects::default();
+        let evt = NeteaseEvent::LikeList {
+            req_id: list_req,
+            ids: vec![1, 2],
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(state.app.liked_song_ids.contains(&1));
+        assert!(
+            state.app.liked_song_ids.contains(&7),
+            "在途的收藏不应被覆盖"
+        );
+    }
+}
@@ -13,15 +13,28 @@ pub async fn handle_ui(
         AppCommand::LoginGenerateQr => AppCommand::LoginGenerateQr,
         AppCommand::LoginToggleCookieInput => AppCommand::LoginToggleCookieInput,
         AppCommand::LoginCookieInputChar { c } => AppCommand::LoginCookieInputChar { c: *c },
+        AppCommand::LoginCookieInputString { s } => {
+            AppCommand::LoginCookieInputString { s: s.clone() }
+        }
         AppCommand::LoginCookieInputBackspace => AppCommand::LoginCookieInputBackspace,
         AppCommand::LoginCookieSubmit => AppCommand::LoginCookieSubmit,
+        AppCommand::LoginTogglePhoneForm => AppCommand::LoginTogglePhoneForm,
+        AppCommand::LoginPhoneFieldNext => AppCommand::LoginPhoneFieldNext,
+        AppCommand::LoginPhoneToggleCaptcha => AppCommand::LoginPhoneToggleCaptcha,
+        AppCommand::LoginPhoneInputChar { c } => AppCommand::LoginPhoneInputChar { c: *c },
+        AppCommand::LoginPhoneInputString { s } => {
+            AppCommand::LoginPhoneInputString { s: s.clone() }
+        }
+        AppCommand::LoginPhoneInputBackspace => AppCommand::LoginPhoneInputBackspace,
+        AppCommand::LoginPhoneSendCaptcha => AppCommand::LoginPhoneSendCaptcha,
+        AppCommand::LoginPhoneSubmit => AppCommand::LoginPhoneSubmit,
         _ => return UiAction::NotHandled,
     };
 
     login_handlers::handle_login_command(
         login_cmd,
         &mut state.app,
-        &mut state.req_id,
+        &state.req_ids,
         &mut state.request_tracker,
         effects,
     )
@@ -35,20 +48,33 @@ pub async fn handle_netease_event(
     state: &mut CoreState,
     effects: &mut CoreEffects,
 ) -> bool {
-    login_handlers::handle_login_event(
+    let was_logged_in = state.app.logged_in;
+    let handled = login_handlers::handle_login_event(
         evt,
         &mut state.app,
-        &mut state.req_id,
+        &state.req_ids,
         &mut state.request_tracker,
         effects,
     )
-    .await
+    .await;
+    // 重新登录后恢复因接口错误暂停的预加载
+    if !was_logged_in && state.app.logged_in {
+        state.preload_mgr.resume(&mut state.app);
+    }
+    if matches!(evt, NeteaseEvent::ClientReady { .. }) {
+        super::player::resume_restored_track(state, effects).await;
+    }
+    handled
+}
+
+pub fn handle_need_login(state: &mut CoreState, effects: &mut CoreEffects) {
+    login_handlers::handle_need_login(&mut state.app, effects);
 }
 
 pub fn handle_qr_poll(state: &mut CoreState, effects: &mut CoreEffects) {
     login_handlers::handle_qr_poll(
         &state.app,
-        &mut state.req_id,
+        &state.req_ids,
         &mut state.request_tracker,
         effects,
     );
@@ -56,11 +82,350 @@ pub fn handle_qr_poll(state: &mut CoreState, effects: &mut CoreEffects) {
 
 #[cfg(test)]
 mod tests {
-    use super::handle_ui;
-    use crate::core::effects::CoreEffect;
-    use crate::core::reducer::{CoreState, UiAction};
+    use super::{handle_netease_event, handle_ui};
+    use crate::app::{App, Song, StartupView, View};
+    use crate::core::effects::{CoreEffect, CoreEffects};
+    use crate::core::reducer::{CoreState, UiAction, restore_startup_state};
     use crate::messages::app::AppCommand;
-    use crate::netease::actor::NeteaseCommand;
+    use crate::netease::actor::{CellphoneCredential, NeteaseCommand, NeteaseEvent};
+    use crate::settings::{AppSettings, startup_view_to_string};
+
+    /// 上次会话：停在搜索页，队列里有两首歌，音量 0.4
+    fn save_previous_session(dir: &std::path::Path) {
+        let mut app = App {
+            view: View::Search,
+            volume: 0.4,
+            ..Default::default()
+        };
+        let songs = (1..=2)
+            .map(|id| Song {
+                id,
+                name: format!("song-{id}"),
+                artists: "artist".to_owned(),
+                duration_ms: None,
+                ..Default::default()
+            })
+            .collect();
+        app.play_queue.set_songs(songs, Some(1));
+        app.play_song_id = Some(2);
+        crate::player_state::save_player_state(dir, &app).expect("保存状态");
+    }
+
+    /// 模拟启动：加载 settings 与状态快照，随后收到 ClientReady
+    async fn start_up(
+        settings: AppSettings,
+        has_restore_data: bool,
+        logged_in: bool,
+    ) -> (CoreState, bool) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        if has_restore_data {
+            save_previous_session(dir.path());
+        }
+        let mut state = CoreState::new_with_settings(dir.path(), settings);
+        let restored = restore_startup_state(
+            &mut state,
+            crate::player_state::load_player_state(dir.path()),
+        );
+
+        let mut effects = CoreEffects::default();
+        let evt = NeteaseEvent::ClientReady {
+            req_id: 1,
+            logged_in,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        (state, restored)
+    }
+
+    #[tokio::test]
+    async fn startup_view_follows_setting_restore_data_and_login() {
+        let cases = [
+            (StartupView::Last, View::Search, View::Playlists),
+            (StartupView::Playlists, View::Playlists, View::Playlists),
+            (StartupView::Search, View::Search, View::Search),
+            (StartupView::NowPlaying, View::Lyrics, View::Lyrics),
+        ];
+        for (startup_view, with_restore, without_restore) in cases {
+            for has_restore_data in [true, false] {
+                for logged_in in [true, false] {
+                    let settings = AppSettings {
+                        startup_view: startup_view_to_string(startup_view),
+                        ..Default::default()
+                    };
+                    let (state, restored) = start_up(settings, has_restore_data, logged_in).await;
+                    assert_eq!(restored, has_restore_data);
+
+                    let expected = match (logged_in, has_restore_data) {
+                        (false, _) => View::Login,
+                        (true, true) => with_restore,
+                        (true, false) => without_restore,
+                    };
+                    assert_eq!(
+                        state.app.view, expected,
+                        "startup_view={startup_view:?} restore={has_restore_data} logged_in={logged_in}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn queue_restore_toggle_keeps_other_restored_state() {
+        for restore_queue in [true, false] {
+            for has_restore_data in [true, false] {
+                for logged_in in [true, false] {
+                    let settings = AppSettings {
+                        restore_queue_on_startup: restore_queue,
+                        ..Default::default()
+                    };
+                    let (state, _) = start_up(settings, has_restore_data, logged_in).await;
+                    let case = format!(
+                        "restore_queue={restore_queue} restore={has_restore_data} logged_in={logged_in}"
+                    );
+
+                    let queue_restored = restore_queue && has_restore_data;
+                    assert_eq!(
+                        state.app.play_queue.songs().len(),
+                        if queue_restored { 2 } else { 0 },
+                        "{case}"
+                    );
+                    assert_eq!(
+                        state.app.play_song_id,
+                        queue_restored.then_some(2),
+                        "{case}"
+                    );
+                    // 关闭队列恢复不影响其它状态
+                    let volume = if has_restore_data { 0.4 } else { 1.0 };
+                    assert_eq!(state.app.volume, volume, "{case}");
+                    assert_eq!(state.app.restore_queue_on_startup, restore_queue);
+                }
+            }
+        }
+    }
+
+    /// 模拟启动：上次会话保存于 `age_hours` 小时前
+    fn start_up_with_age(settings: AppSettings, age_hours: i64) -> CoreState {
+        let dir = tempfile::tempdir().expect("tempdir");
+        save_previous_session(dir.path());
+        let mut snapshot = crate::player_state::load_player_state(dir.path()).expect("加载状态");
+        snapshot.saved_at_epoch_ms -= age_hours * 3_600_000;
+
+        let mut state = CoreState::new_with_settings(dir.path(), settings);
+        assert!(restore_startup_state(&mut state, Ok(snapshot)));
+        state
+    }
+
+    #[test]
+    fn stale_snapshot_defers_queue_until_user_decides() {
+        let settings = |threshold, restore_queue| AppSettings {
+            stale_state_prompt_hours: threshold,
+            restore_queue_on_startup: restore_queue,
+            ..Default::default()
+        };
+        // (阈值, 恢复队列开关, 快照年龄, 是否弹窗)
+        let cases = [
+            (48, true, 1, false),
+            (48, true, 47, false),
+            (48, true, 72, true),
+            (0, true, 24 * 30, false),
+            (48, false, 72, false),
+        ];
+        for (threshold, restore_queue, age_hours, prompted) in cases {
+            let state = start_up_with_age(settings(threshold, restore_queue), age_hours);
+            let case =
+                format!("threshold={threshold} restore_queue={restore_queue} age={age_hours}h");
+
+            assert_eq!(state.app.restore_prompt.is_some(), prompted, "{case}");
+            assert_eq!(state.pending_restore.is_some(), prompted, "{case}");
+            let queue_restored = restore_queue && !prompted;
+            assert_eq!(
+                state.app.play_queue.songs().len(),
+                if queue_restored { 2 } else { 0 },
+                "{case}"
+            );
+            // 设置类字段总是恢复
+            assert_eq!(state.app.volume, 0.4, "{case}");
+        }
+
+        let state = start_up_with_age(settings(48, true), 24 * 5 + 3);
+        assert_eq!(
+            state.app.restore_prompt.as_deref(),
+            Some("发现 5 天前的播放状态：恢复队列 (r) / 忽略 (i)")
+        );
+    }
+
+    #[tokio::test]
+    async fn restore_prompt_choices_apply_or_discard_snapshot() {
+        use crate::core::reducer::ui::handle_ui as handle_ui_cmd;
+
+        for accept in [true, false] {
+            let mut state = start_up_with_age(AppSettings::default(), 72);
+            let mut effects = CoreEffects::default();
+            let cmd = if accept {
+                AppCommand::RestorePromptAccept
+            } else {
+                AppCommand::RestorePromptDismiss
+            };
+            let outcome = handle_ui_cmd(&cmd, &mut state, &mut effects).await;
+
+            assert!(matches!(outcome, UiAction::Handled));
+            assert!(state.app.restore_prompt.is_none());
+            assert!(state.pending_restore.is_none());
+            assert_eq!(
+                state.app.play_queue.songs().len(),
+                if accept { 2 } else { 0 }
+            );
+            assert_eq!(state.app.play_song_id, accept.then_some(2));
+            assert_eq!(state.app.volume, 0.4);
+            assert_eq!(
+                effects
+                    .actions
+                    .iter()
+                    .any(|e| matches!(e, CoreEffect::SetToast(_))),
+                accept
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn restored_song_is_loaded_paused_at_saved_position() {
+        use crate::audio_worker::{AudioCommand, AudioEvent, AudioStreamHint};
+        use crate::core::reducer::player::{
+            handle_audio_event, handle_netease_event as player_evt,
+        };
+
+        fn audio_cmds(effects: &CoreEffects) -> Vec<&AudioCommand> {
+            effects
+                .actions
+                .iter()
+                .filter_map(|e| match e {
+                    CoreEffect::SendAudio { cmd, .. } => Some(cmd),
+                    _ => None,
+                })
+                .collect()
+        }
+
+        for resume_on_start in [false, true] {
+            let dir = tempfile::tempdir().expect("tempdir");
+            save_previous_session(dir.path());
+            let mut snapshot =
+                crate::player_state::load_player_state(dir.path()).expect("加载状态");
+            snapshot.player.progress.started_at_epoch_ms =
+                Some(snapshot.saved_at_epoch_ms - 42_000);
+            snapshot.player.progress.paused = true;
+            let settings = AppSettings {
+                resume_on_start,
+                ..Default::default()
+            };
+            let mut state = CoreState::new_with_settings(dir.path(), settings);
+            assert!(restore_startup_state(&mut state, Ok(snapshot)));
+
+            let mut effects = CoreEffects::default();
+            let ready = NeteaseEvent::ClientReady {
+                req_id: 1,
+                logged_in: true,
+            };
+            handle_netease_event(&ready, &mut state, &mut effects).await;
+            let req_id = effects
+                .actions
+                .iter()
+                .find_map(|e| match e {
+                    CoreEffect::SendNeteaseHi {
+                        cmd: NeteaseCommand::SongUrl { req_id, id: 2, .. },
+                        ..
+                    } => Some(*req_id),
+                    _ => None,
+                })
+                .expect("请求上次播放的歌曲");
+            assert_eq!(state.app.pending_seek_ms, Some(42_000));
+            assert_eq!(
+                audio_cmds(&effects)
+                    .iter()
+                    .any(|c| matches!(c, AudioCommand::SetVolume(v) if *v == 0.0)),
+                !resume_on_start,
+                "保持暂停时加载期间静音"
+            );
+
+            let song_url = NeteaseEvent::SongUrl {
+                req_id,
+                song_url: crate::domain::model::SongUrl {
+                    id: 2,
+                    url: "https://example.com/2.mp3".to_owned(),
+                    br: None,
+                    size: None,
+                    gain_db: None,
+                },
+                br: state.app.play_br,
+            };
+            let mut effects = CoreEffects::default();
+            assert!(player_evt(&song_url, &mut state, &mut effects).await);
+            assert!(
+                audio_cmds(&effects)
+                    .iter()
+                    .any(|c| matches!(c, AudioCommand::PlayTrack { id: 2, .. }))
+            );
+
+            let mut effects = CoreEffects::default();
+            let now_playing = AudioEvent::NowPlaying {
+                song_id: 2,
+                play_id: 1,
+                title: "song-2".to_owned(),
+                duration_ms: Some(180_000),
+                stream_hint: AudioStreamHint::cached_file(None),
+            };
+            handle_audio_event(now_playing, &mut state, &mut effects, dir.path()).await;
+            let cmds = audio_cmds(&effects);
+            assert!(
+                cmds.iter()
+                    .any(|c| matches!(c, AudioCommand::SeekToMs(42_000)))
+            );
+            let pause = cmds
+                .iter()
+                .position(|c| matches!(c, AudioCommand::TogglePause));
+            let volume = cmds
+                .iter()
+                .position(|c| matches!(c, AudioCommand::SetVolume(v) if *v == 0.4));
+            assert!(volume.is_some());
+            if resume_on_start {
+                assert_eq!(pause, None);
+            } else {
+                assert!(pause < volume, "先暂停再恢复音量");
+            }
+            assert_eq!(state.app.startup_resume, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn restored_song_without_url_keeps_queue() {
+        let (mut state, _) = start_up(AppSettings::default(), true, true).await;
+        let req_id = state
+            .request_tracker
+            .get_pending(&crate::core::infra::RequestKey::SongUrl)
+            .expect("请求上次播放的歌曲");
+
+        let mut effects = CoreEffects::default();
+        let evt = NeteaseEvent::SongUrlUnavailable {
+            req_id,
+            id: 2,
+            br: 128_000,
+        };
+        assert!(
+            crate::core::reducer::player::handle_netease_event(&evt, &mut state, &mut effects)
+                .await
+        );
+        assert_eq!(
+            state.app.play_status,
+            "无法恢复上次播放：歌曲已不可播放（队列已保留）"
+        );
+        assert_eq!(state.app.play_queue.songs().len(), 2);
+        assert_eq!(state.app.play_queue.current_index(), Some(1));
+        assert!(
+            !effects
+                .actions
+                .iter()
+                .any(|e| matches!(e, CoreEffect::SendNeteaseHi { .. })),
+            "不自动跳到下一首"
+        );
+    }
 
     #[tokio::test]
     async fn login_generate_qr_emits_request() {
@@ -82,4 +447,284 @@ mod tests {
             )
         }));
     }
+
+    #[tokio::test]
+    async fn expired_qr_regenerates_automatically_up_to_the_cap() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = send_all(&mut state, vec![AppCommand::LoginGenerateQr]).await;
+
+        for round in 0..=3 {
+            let [NeteaseCommand::LoginQrKey { req_id }] = sent_netease(&effects)[..] else {
+                panic!("第 {round} 轮应请求新的二维码");
+            };
+            let key = NeteaseEvent::LoginQrKey {
+                req_id: *req_id,
+                unikey: format!("key-{round}"),
+            };
+            let mut fx = CoreEffects::default();
+            assert!(handle_netease_event(&key, &mut state, &mut fx).await);
+            assert!(state.app.login_qr_created_at.is_some());
+            assert!(state.app.login_qr_ascii.is_some());
+
+            let poll = state
+                .request_tracker
+                .issue(crate::core::infra::RequestKey::LoginQrPoll, &state.req_ids);
+            let expired = NeteaseEvent::LoginQrStatus {
+                req_id: poll,
+                status: crate::domain::model::LoginStatus {
+                    code: 800,
+                    message: "二维码不存在或已过期".to_owned(),
+                    logged_in: false,
+                },
+            };
+            effects = CoreEffects::default();
+            assert!(handle_netease_event(&expired, &mut state, &mut effects).await);
+            assert!(state.app.login_unikey.is_none());
+            assert!(state.app.login_qr_ascii.is_none());
+            assert!(state.app.login_qr_created_at.is_none());
+        }
+
+        // 自动重新生成 3 次后不再请求，等待用户按 l
+        assert!(sent_netease(&effects).is_empty());
+        assert_eq!(state.app.login_status, "二维码已过期，按 l 重新生成");
+        let effects = send_all(&mut state, vec![AppCommand::LoginGenerateQr]).await;
+        assert!(matches!(
+            sent_netease(&effects)[..],
+            [NeteaseCommand::LoginQrKey { .. }]
+        ));
+        assert_eq!(state.app.login_qr_auto_regenerations, 0);
+    }
+
+    #[test]
+    fn qr_countdown_counts_down_to_expiry() {
+        use crate::features::login::{QR_KEY_LIFETIME, qr_remaining_text};
+        use std::time::{Duration, Instant};
+
+        let created = Instant::now();
+        assert_eq!(qr_remaining_text(created, created).as_deref(), Some("5:00"));
+        assert_eq!(
+            qr_remaining_text(created, created + Duration::from_secs(55)).as_deref(),
+            Some("4:05")
+        );
+        assert_eq!(qr_remaining_text(created, created + QR_KEY_LIFETIME), None);
+    }
+
+    #[tokio::test]
+    async fn account_records_login_expiry_and_warns_when_close() {
+        let now = chrono::Utc::now().timestamp();
+        for (days_left, warns) in [(3, true), (30, false)] {
+            let dir = tempfile::tempdir().expect("tempdir");
+            let mut state = CoreState::new(dir.path());
+            state.app.logged_in = true;
+            let req_id = state.request_tracker.issue(
+                crate::core::infra::RequestKey::Account,
+                &crate::core::infra::IdAllocator::starting_at(7),
+            );
+            let expires_at = now + days_left * 24 * 3600;
+            let evt = NeteaseEvent::Account {
+                req_id,
+                account: crate::domain::model::Account {
+                    uid: 1,
+                    nickname: "n".to_owned(),
+                },
+                login_expires_at: Some(expires_at),
+            };
+            let mut effects = CoreEffects::default();
+            assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+            assert_eq!(state.app.login_expires_at, Some(expires_at));
+            let warned = effects.actions.iter().any(|effect| {
+                matches!(effect, CoreEffect::SetToast(t) if t.message.contains("重新扫码登录"))
+            });
+            assert_eq!(warned, warns, "剩余 {days_left} 天");
+        }
+    }
+
+    #[tokio::test]
+    async fn session_expiry_returns_to_login_view() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.logged_in = true;
+        state.app.view = View::Search;
+        state.app.login_expires_at = Some(1);
+
+        let mut effects = CoreEffects::default();
+        assert!(
+            handle_netease_event(&NeteaseEvent::SessionExpired, &mut state, &mut effects).await
+        );
+        assert!(!state.app.logged_in);
+        assert_eq!(state.app.view, View::Login);
+        assert_eq!(state.app.login_expires_at, None);
+        assert!(effects.actions.iter().any(|effect| {
+            matches!(effect, CoreEffect::SetToast(t) if t.message == "登录已失效，请重新登录")
+        }));
+    }
+
+    /// 依次发送 UI 命令，返回最后一条命令的副作用
+    async fn send_all(state: &mut CoreState, cmds: Vec<AppCommand>) -> CoreEffects {
+        let mut effects = CoreEffects::default();
+        for cmd in cmds {
+            effects = CoreEffects::default();
+            assert!(matches!(
+                handle_ui(&cmd, state, &mut effects).await,
+                UiAction::Handled
+            ));
+        }
+        effects
+    }
+
+    fn sent_netease(effects: &CoreEffects) -> Vec<&NeteaseCommand> {
+        effects
+            .actions
+            .iter()
+            .filter_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi { cmd, .. } => Some(cmd),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn phone_password_login_sends_only_the_digest_and_loads_the_account() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let effects = send_all(
+            &mut state,
+            vec![
+                AppCommand::LoginTogglePhoneForm,
+                AppCommand::LoginPhoneInputString {
+                    s: "+852 9123-4567".to_owned(),
+                },
+                AppCommand::LoginPhoneFieldNext,
+                AppCommand::LoginPhoneInputString {
+                    s: "pass".to_owned(),
+                },
+                AppCommand::LoginPhoneInputChar { c: 'q' },
+                AppCommand::LoginPhoneSubmit,
+            ],
+        )
+        .await;
+
+        let [
+            NeteaseCommand::LoginCellphone {
+                req_id,
+                phone,
+                country_code,
+                credential,
+            },
+        ] = sent_netease(&effects)[..]
+        else {
+            panic!("应发送手机号登录请求");
+        };
+        assert_eq!((phone.as_str(), country_code.as_str()), ("91234567", "852"));
+        assert_eq!(
+            credential,
+            &CellphoneCredential::PasswordMd5(crate::netease::password_md5("passq"))
+        );
+        let req_id = *req_id;
+        let form = state.app.login_phone_form.as_ref().expect("表单仍打开");
+        assert!(form.secret.is_empty(), "提交后立即清空密码");
+
+        let done = NeteaseEvent::LoginCellphoneDone {
+            req_id,
+            nickname: "n".to_owned(),
+        };
+        let mut effects = CoreEffects::default();
+        assert!(handle_netease_event(&done, &mut state, &mut effects).await);
+        assert!(state.app.logged_in);
+        assert!(state.app.login_phone_form.is_none());
+        assert_eq!(state.app.view, View::Playlists);
+        assert!(matches!(
+            sent_netease(&effects)[..],
+            [NeteaseCommand::UserAccount { .. }]
+        ));
+    }
+
+    #[tokio::test]
+    async fn phone_captcha_login_verifies_the_code_before_logging_in() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let effects = send_all(
+            &mut state,
+            vec![
+                AppCommand::LoginTogglePhoneForm,
+                AppCommand::LoginPhoneInputString {
+                    s: "13800000000".to_owned(),
+                },
+                AppCommand::LoginPhoneSendCaptcha,
+            ],
+        )
+        .await;
+        let [NeteaseCommand::LoginCaptchaSend { req_id, .. }] = sent_netease(&effects)[..] else {
+            panic!("应发送验证码请求");
+        };
+        let sent = NeteaseEvent::LoginCaptchaSent { req_id: *req_id };
+        let mut effects = CoreEffects::default();
+        assert!(handle_netease_event(&sent, &mut state, &mut effects).await);
+
+        // 验证码只接受数字
+        let effects = send_all(
+            &mut state,
+            vec![
+                AppCommand::LoginPhoneInputString {
+                    s: "12a34".to_owned(),
+                },
+                AppCommand::LoginPhoneSubmit,
+            ],
+        )
+        .await;
+        let [
+            NeteaseCommand::LoginCaptchaVerify {
+                req_id, captcha, ..
+            },
+        ] = sent_netease(&effects)[..]
+        else {
+            panic!("应先校验验证码");
+        };
+        assert_eq!(captcha, "1234");
+        let verified = NeteaseEvent::LoginCaptchaVerified { req_id: *req_id };
+        let mut effects = CoreEffects::default();
+        assert!(handle_netease_event(&verified, &mut state, &mut effects).await);
+        let [
+            NeteaseCommand::LoginCellphone {
+                req_id, credential, ..
+            },
+        ] = sent_netease(&effects)[..]
+        else {
+            panic!("校验通过后应登录");
+        };
+        assert_eq!(credential, &CellphoneCredential::Captcha("1234".to_owned()));
+
+        // 登录失败：提示错误，表单保留以便重试
+        let failed = NeteaseEvent::Error {
+            req_id: *req_id,
+            error: crate::error::MessageError::other("验证码错误"),
+        };
+        let mut effects = CoreEffects::default();
+        assert!(handle_netease_event(&failed, &mut state, &mut effects).await);
+        assert!(!state.app.logged_in);
+        assert!(state.app.login_phone_form.is_some());
+        assert!(state.app.login_status.starts_with("登录失败"));
+    }
+
+    #[tokio::test]
+    async fn phone_submit_without_input_asks_for_the_missing_field() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let effects = send_all(
+            &mut state,
+            vec![
+                AppCommand::LoginTogglePhoneForm,
+                AppCommand::LoginPhoneInputString {
+                    s: "13800000000".to_owned(),
+                },
+                AppCommand::LoginPhoneSubmit,
+            ],
+        )
+        .await;
+        assert!(sent_netease(&effects).is_empty());
+        assert_eq!(state.app.login_status, "请输入密码");
+        let form = state.app.login_phone_form.as_ref().expect("表单");
+        assert_eq!(form.field, crate::app::PhoneLoginField::Secret);
+    }
 }
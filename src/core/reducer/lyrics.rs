@@ -15,6 +15,7 @@ pub async fn handle_ui(
         AppCommand::LyricsMoveUp => AppCommand::LyricsMoveUp,
         AppCommand::LyricsMoveDown => AppCommand::LyricsMoveDown,
         AppCommand::LyricsGotoCurrent => AppCommand::LyricsGotoCurrent,
+        AppCommand::LyricsCycleDisplay => AppCommand::LyricsCycleDisplay,
         AppCommand::LyricsOffsetAddMs { ms } => AppCommand::LyricsOffsetAddMs { ms: *ms },
         _ => return UiAction::NotHandled,
     };
@@ -47,6 +48,7 @@ pub async fn handle_netease_event(
                 *song_id,
                 lyrics.clone(),
                 &mut state.app,
+                &mut state.settings,
                 &mut state.request_tracker,
                 effects,
             )
@@ -58,9 +60,9 @@ pub async fn handle_netease_event(
 
 #[cfg(test)]
 mod tests {
-    use super::handle_ui;
+    use super::{handle_netease_event, handle_ui};
     use crate::app::View;
-    use crate::core::infra::RequestKey;
+    use crate::core::infra::{IdAllocator, RequestKey};
     use crate::core::reducer::{CoreState, UiAction};
     use crate::domain::model::LyricLine;
     use crate::messages::app::AppCommand;
@@ -86,14 +88,62 @@ mod tests {
         assert_eq!(state.settings.lyrics_offset_ms, 200);
     }
 
+    #[tokio::test]
+    async fn lyrics_offset_is_stored_per_playing_song_and_applied_on_load() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.view = View::Lyrics;
+        state.app.lyrics_offset_ms = 100;
+        state.app.play_song_id = Some(7);
+        state.app.lyrics_song_id = Some(7);
+
+        let add = AppCommand::LyricsOffsetAddMs { ms: 200 };
+        handle_ui(&add, &mut state, &mut effects, dir.path()).await;
+        // 以全局 offset 为起点，只写入这首歌
+        assert_eq!(state.app.lyrics_offset_ms, 100);
+        assert_eq!(state.settings.lyrics_song_offsets.get(7), Some(300));
+        assert_eq!(state.app.lyrics_effective_offset_ms(), 300);
+        assert_eq!(
+            crate::settings::load_settings(dir.path())
+                .lyrics_song_offsets
+                .get(7),
+            Some(300),
+            "调整后立即保存"
+        );
+
+        // 换到另一首歌：没有单独的 offset，使用全局值
+        let lyric = |req_id, song_id| NeteaseEvent::Lyric {
+            req_id,
+            song_id,
+            lyrics: Vec::new(),
+        };
+        let ids = IdAllocator::default();
+        let req_id = state.request_tracker.issue(RequestKey::Lyric, &ids);
+        handle_netease_event(&lyric(req_id, 8), &mut state, &mut effects).await;
+        assert_eq!(state.app.lyrics_effective_offset_ms(), 100);
+
+        // 再回到这首歌时恢复
+        let req_id = state.request_tracker.issue(RequestKey::Lyric, &ids);
+        handle_netease_event(&lyric(req_id, 7), &mut state, &mut effects).await;
+        assert_eq!(state.app.lyrics_effective_offset_ms(), 300);
+
+        // 未播放时调整全局 offset
+        state.app.play_song_id = None;
+        handle_ui(&add, &mut state, &mut effects, dir.path()).await;
+        assert_eq!(state.settings.lyrics_offset_ms, 300);
+        assert_eq!(state.settings.lyrics_song_offsets.get(7), Some(300));
+    }
+
     #[tokio::test]
     async fn outdated_lyric_is_dropped() {
         let dir = tempfile::tempdir().expect("tempdir");
         let mut state = CoreState::new(dir.path());
         let mut effects = crate::core::effects::CoreEffects::default();
 
-        state.request_tracker.issue(RequestKey::Lyric, || 1);
-        state.request_tracker.issue(RequestKey::Lyric, || 2);
+        let ids = IdAllocator::default();
+        state.request_tracker.issue(RequestKey::Lyric, &ids);
+        state.request_tracker.issue(RequestKey::Lyric, &ids);
 
         let stale = NeteaseEvent::Lyric {
             req_id: 1,
@@ -102,6 +152,7 @@ mod tests {
                 time_ms: 0,
                 text: "old".to_owned(),
                 translation: None,
+                roman: None,
             }],
         };
         let handled_stale = super::handle_netease_event(&stale, &mut state, &mut effects).await;
@@ -116,6 +167,7 @@ mod tests {
                 time_ms: 0,
                 text: "new".to_owned(),
                 translation: None,
+                roman: None,
             }],
         };
         let handled_fresh = super::handle_netease_event(&fresh, &mut state, &mut effects).await;
@@ -123,4 +175,50 @@ mod tests {
         assert_eq!(state.app.lyrics_song_id, Some(2));
         assert_eq!(state.app.lyrics_status, "歌词: 1 行");
     }
+
+    #[tokio::test]
+    async fn cycle_display_walks_original_translation_roman() {
+        use crate::app::LyricsDisplay;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.view = View::Lyrics;
+        state.app.lyrics = vec![LyricLine {
+            time_ms: 0,
+            text: "夜に駆ける".to_owned(),
+            translation: Some("向夜晚奔去".to_owned()),
+            roman: None,
+        }]
+        .into();
+        assert_eq!(state.app.lyrics_display, LyricsDisplay::Translation);
+
+        let mut cycle = async |state: &mut CoreState| {
+            handle_ui(
+                &AppCommand::LyricsCycleDisplay,
+                state,
+                &mut effects,
+                dir.path(),
+            )
+            .await;
+            state.app.lyrics_display
+        };
+        assert_eq!(cycle(&mut state).await, LyricsDisplay::Roman);
+        assert_eq!(
+            state.app.lyrics_status,
+            "歌词显示: 原文+罗马音（当前歌曲没有罗马音）"
+        );
+        assert_eq!(cycle(&mut state).await, LyricsDisplay::Original);
+        assert_eq!(state.app.lyrics_status, "歌词显示: 原文");
+        assert_eq!(cycle(&mut state).await, LyricsDisplay::Translation);
+        assert_eq!(state.app.lyrics_status, "歌词显示: 原文+翻译");
+
+        let line = &state.app.lyrics[0];
+        assert_eq!(
+            LyricsDisplay::Translation.secondary(line),
+            Some("向夜晚奔去")
+        );
+        assert_eq!(LyricsDisplay::Roman.secondary(line), None);
+        assert_eq!(LyricsDisplay::Original.secondary(line), None);
+    }
 }
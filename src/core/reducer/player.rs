@@ -1,29 +1,154 @@
 use super::{CoreState, UiAction};
+use crate::app::{SongFailureKind, Toast};
 use crate::audio_worker::{AudioCommand, AudioEvent};
+use crate::cooldown::minutes_label;
 use crate::core::effects::CoreEffects;
 use crate::core::infra::RequestKey;
+use crate::domain::model::fallback_br;
 use crate::features::player;
+use crate::features::player::queue_edit::EnqueueAt;
+use crate::features::player::scrobble::{self, ListenScrobble};
+use crate::features::settings::br_label;
 use crate::messages::app::AppCommand;
-use crate::netease::actor::NeteaseEvent;
+use crate::netease::actor::{NeteaseCommand, NeteaseEvent};
+use crate::notifications::{self, TrackNotice};
+use crate::play_history::{self, PlayRecord, PlaySession};
+use std::path::Path;
 
 pub async fn handle_ui(
     cmd: &AppCommand,
     state: &mut CoreState,
     effects: &mut CoreEffects,
 ) -> UiAction {
+    if matches!(cmd, AppCommand::QueueToggleNoFade) {
+        let no_fade = state.app.play_queue.toggle_no_fade();
+        tracing::info!(no_fade, "切换此队列的淡入淡出");
+        effects.set_toast(Toast::info(if no_fade {
+            "此队列禁用淡入淡出"
+        } else {
+            "此队列恢复淡入淡出"
+        }));
+        // 引擎按预排时的设置衔接下一首，需要按新设置重新预排
+        state.next_song_cache.reset(effects);
+        state
+            .next_song_cache
+            .prefetch_next(&state.app, effects, &state.req_ids)
+            .await;
+        effects.emit_state(&state.app);
+        return UiAction::Handled;
+    }
+
+    match cmd {
+        AppCommand::QueueMoveUp | AppCommand::QueueMoveDown => {
+            let delta = if matches!(cmd, AppCommand::QueueMoveUp) {
+                -1
+            } else {
+                1
+            };
+            player::queue_panel::move_selection(&mut state.app, delta);
+            effects.emit_state(&state.app);
+            return UiAction::Handled;
+        }
+        AppCommand::QueueCollapse | AppCommand::QueueExpand => {
+            let collapse = matches!(cmd, AppCommand::QueueCollapse);
+            if player::queue_panel::set_collapsed(&mut state.app, collapse) {
+                effects.emit_state(&state.app);
+            }
+            return UiAction::Handled;
+        }
+        AppCommand::QueueUndoReplace => {
+            player::queue_undo::undo(&mut state.app, &mut state.next_song_cache, effects);
+            return UiAction::Handled;
+        }
+        AppCommand::QueueReorderUp
+        | AppCommand::QueueReorderDown
+        | AppCommand::QueueRemoveSelected
+        | AppCommand::QueuePlaySelected
+        | AppCommand::EnqueueNextSelected
+        | AppCommand::EnqueueLastSelected => {
+            let mut ctx = player::control::PlayerControlCtx {
+                req_ids: &state.req_ids,
+                request_tracker: &mut state.request_tracker,
+                song_request_titles: &mut state.song_request_titles,
+                next_song_cache: &mut state.next_song_cache,
+                effects,
+            };
+            let app = &mut state.app;
+            match cmd {
+                AppCommand::QueueReorderUp => {
+                    player::queue_edit::reorder_selected(app, true, &mut ctx).await;
+                }
+                AppCommand::QueueReorderDown => {
+                    player::queue_edit::reorder_selected(app, false, &mut ctx).await;
+                }
+                AppCommand::QueueRemoveSelected => {
+                    player::queue_edit::remove_selected(app, &mut ctx).await;
+                }
+                AppCommand::EnqueueNextSelected => {
+                    player::queue_edit::enqueue_selected(app, EnqueueAt::Next, &mut ctx).await;
+                }
+                AppCommand::EnqueueLastSelected => {
+                    player::queue_edit::enqueue_selected(app, EnqueueAt::Last, &mut ctx).await;
+                }
+                _ => player::queue_edit::play_selected(app, &mut ctx).await,
+            }
+            return UiAction::Handled;
+        }
+        _ => {}
+    }
+
+    if let AppCommand::AudioFocusChanged { others_playing } = cmd {
+        player::audio_focus::on_others_playing(&mut state.app, *others_playing, effects);
+        effects.emit_state(&state.app);
+        return UiAction::Handled;
+    }
+
+    if matches!(cmd, AppCommand::DownloadSelected) {
+        player::download::download_selected(
+            &mut state.app,
+            state.settings.download_dir.as_deref(),
+            &mut state.pending_downloads,
+            &state.req_ids,
+            effects,
+        );
+        return UiAction::Handled;
+    }
+
+    if matches!(cmd, AppCommand::FmToggle) {
+        let mut ctx = player::audio::AudioEventCtx {
+            request_tracker: &mut state.request_tracker,
+            song_request_titles: &mut state.song_request_titles,
+            req_ids: &state.req_ids,
+            next_song_cache: &mut state.next_song_cache,
+        };
+        player::fm::toggle(&mut state.app, &mut ctx, effects);
+        return UiAction::Handled;
+    }
+
     let control_cmd = match cmd {
         AppCommand::PlayerTogglePause => AppCommand::PlayerTogglePause,
         AppCommand::PlayerStop => AppCommand::PlayerStop,
         AppCommand::PlayerPrev => AppCommand::PlayerPrev,
         AppCommand::PlayerNext => AppCommand::PlayerNext,
+        AppCommand::PlayerToggleStopAfterCurrent => AppCommand::PlayerToggleStopAfterCurrent,
+        AppCommand::PlayerSetLoopPointA => AppCommand::PlayerSetLoopPointA,
+        AppCommand::PlayerSetLoopPointB => AppCommand::PlayerSetLoopPointB,
+        AppCommand::PlayerClearLoop => AppCommand::PlayerClearLoop,
         AppCommand::PlayerSeekBackwardMs { ms } => AppCommand::PlayerSeekBackwardMs { ms: *ms },
         AppCommand::PlayerSeekForwardMs { ms } => AppCommand::PlayerSeekForwardMs { ms: *ms },
         AppCommand::PlayerSeekAbsoluteMs { ms } => AppCommand::PlayerSeekAbsoluteMs { ms: *ms },
+        AppCommand::TerminalSuspending => AppCommand::TerminalSuspending,
+        AppCommand::TerminalResumed { suspended_ms } => AppCommand::TerminalResumed {
+            suspended_ms: *suspended_ms,
+        },
+        AppCommand::TerminalFocusChanged { focused } => {
+            AppCommand::TerminalFocusChanged { focused: *focused }
+        }
         _ => return UiAction::NotHandled,
     };
 
     let mut ctx = player::control::PlayerControlCtx {
-        req_id: &mut state.req_id,
+        req_ids: &state.req_ids,
         request_tracker: &mut state.request_tracker,
         song_request_titles: &mut state.song_request_titles,
         next_song_cache: &mut state.next_song_cache,
@@ -40,11 +165,23 @@ pub async fn handle_netease_event(
     effects: &mut CoreEffects,
 ) -> bool {
     match evt {
-        NeteaseEvent::SongUrlUnavailable { req_id, id } => {
+        NeteaseEvent::SongUrlUnavailable { req_id, id, br } => {
+            if player::download::on_unavailable(
+                *req_id,
+                *br,
+                &mut state.pending_downloads,
+                &mut state.app.tasks,
+                &state.req_ids,
+                effects,
+            ) {
+                return true;
+            }
+
             // 检查是否为预缓存请求
             if state.next_song_cache.owns_req(*req_id) {
-                // 预缓存失败，静默忽略
-                tracing::debug!(req_id, song_id = id, "预缓存歌曲无可用链接");
+                state
+                    .next_song_cache
+                    .on_unavailable(*req_id, *id, *br, effects, &state.req_ids);
                 return true;
             }
 
@@ -53,9 +190,52 @@ pub async fn handle_netease_event(
                 return false;
             }
 
+            // 按音质阶梯降级重试
+            if let Some(lower) = fallback_br(*br)
+                && state.song_request_titles.contains_key(id)
+            {
+                tracing::info!(
+                    song_id = id,
+                    from_br = br,
+                    to_br = lower,
+                    "当前音质无可用链接，降级重试"
+                );
+                state.app.play_status =
+                    format!("{} 音质不可用，尝试 {}...", br_label(*br), br_label(lower));
+                let retry_id = state
+                    .request_tracker
+                    .issue(RequestKey::SongUrl, &state.req_ids);
+                effects.send_netease_hi(NeteaseCommand::SongUrl {
+                    req_id: retry_id,
+                    id: *id,
+                    br: lower,
+                });
+                effects.emit_state(&state.app);
+                return true;
+            }
+
+            // 启动时恢复的歌曲：保留队列，不自动跳过
+            if player::playback::fail_startup_resume(
+                &mut state.app,
+                Some(*id),
+                "歌曲已不可播放",
+                effects,
+            ) {
+                state.song_request_titles.remove(id);
+                return true;
+            }
+
             // 自动播放下一首
-            tracing::info!(song_id = id, "歌曲无可用播放链接，自动跳转到下一首");
+            tracing::info!(
+                song_id = id,
+                br,
+                "所有音质均无可用播放链接，自动跳转到下一首"
+            );
             state.app.play_status = "歌曲不可播放，自动跳过...".to_owned();
+            state
+                .app
+                .song_failures
+                .record(*id, SongFailureKind::Unavailable);
 
             // 清理该歌曲的请求标题（如果有）
             state.song_request_titles.remove(id);
@@ -64,14 +244,14 @@ pub async fn handle_netease_event(
             let ctx = player::audio::AudioEventCtx {
                 request_tracker: &mut state.request_tracker,
                 song_request_titles: &mut state.song_request_titles,
-                req_id: &mut state.req_id,
+                req_ids: &state.req_ids,
                 next_song_cache: &mut state.next_song_cache,
             };
             player::playback::play_next(
                 &mut state.app,
                 ctx.request_tracker,
                 ctx.song_request_titles,
-                ctx.req_id,
+                ctx.req_ids,
                 ctx.next_song_cache,
                 effects,
             )
@@ -80,11 +260,29 @@ pub async fn handle_netease_event(
             effects.emit_state(&state.app);
             true
         }
-        NeteaseEvent::SongUrl { req_id, song_url } => {
+        NeteaseEvent::SongUrl {
+            req_id,
+            song_url,
+            br: attempted_br,
+        } => {
+            if player::download::on_song_url(
+                *req_id,
+                song_url,
+                *attempted_br,
+                &mut state.pending_downloads,
+                effects,
+            ) {
+                return true;
+            }
+
             if state.next_song_cache.owns_req(*req_id) {
-                state
-                    .next_song_cache
-                    .on_song_url(*req_id, song_url, effects, &state.app);
+                state.next_song_cache.on_song_url(
+                    *req_id,
+                    song_url,
+                    *attempted_br,
+                    effects,
+                    &state.app,
+                );
                 return true;
             }
 
@@ -96,20 +294,47 @@ pub async fn handle_netease_event(
                 let duration_ms = state
                     .app
                     .play_queue
-                    .songs()
-                    .iter()
-                    .find(|song| song.id == song_url.id)
+                    .index_of_playing(song_url.id)
+                    .and_then(|idx| state.app.play_queue.songs().get(idx))
                     .and_then(|song| song.duration_ms);
+                let requested_br = state.app.play_br;
+                let br = song_url.effective_br(*attempted_br);
+                if *attempted_br < requested_br {
+                    tracing::info!(
+                        song_id = song_url.id,
+                        requested_br,
+                        attempted_br,
+                        actual_br = ?song_url.br,
+                        "降级后获取到播放链接"
+                    );
+                } else if br < requested_br {
+                    tracing::info!(
+                        song_id = song_url.id,
+                        requested_br,
+                        actual_br = ?song_url.br,
+                        "服务端返回的音质低于请求"
+                    );
+                }
+                // 同专辑连续曲目或队列禁用了淡入淡出时直接衔接
+                let no_fade = state.app.play_queue.no_fade()
+                    || (state.app.play_queue.index_of_playing(song_url.id)
+                        == state.app.play_queue.current_index()
+                        && state.app.play_queue.continues_album());
                 state.app.play_status = format!("已获取链接，准备缓存: {title}");
                 state.app.play_song_id = Some(song_url.id);
+                state.app.play_actual_br = Some(br);
                 effects.emit_state(&state.app);
                 effects.send_audio_warn(
                     AudioCommand::PlayTrack {
                         id: song_url.id,
-                        br: state.app.play_br,
+                        br,
+                        requested_br,
                         url: song_url.url.clone(),
+                        expected_bytes: song_url.size,
                         title,
                         duration_ms,
+                        no_fade,
+                        gain_db: song_url.gain_db,
                     },
                     "AudioWorker 通道已关闭：PlayTrack 发送失败",
                 );
@@ -117,23 +342,150 @@ pub async fn handle_netease_event(
 
             true
         }
+        NeteaseEvent::PersonalFm { req_id, songs } => {
+            let mut ctx = player::audio::AudioEventCtx {
+                request_tracker: &mut state.request_tracker,
+                song_request_titles: &mut state.song_request_titles,
+                req_ids: &state.req_ids,
+                next_song_cache: &mut state.next_song_cache,
+            };
+            player::fm::handle_personal_fm_event(*req_id, songs, &mut state.app, &mut ctx, effects)
+                .await
+        }
+        NeteaseEvent::Error { req_id, error } if state.pending_downloads.contains_key(req_id) => {
+            player::download::on_error(
+                *req_id,
+                error,
+                &mut state.pending_downloads,
+                &mut state.app.tasks,
+                effects,
+            )
+        }
+        NeteaseEvent::Error { req_id, error }
+            if state.app.startup_resume.is_some()
+                && state.request_tracker.accept(&RequestKey::SongUrl, *req_id) =>
+        {
+            let reason = format!("获取播放链接失败: {error}");
+            player::playback::fail_startup_resume(&mut state.app, None, &reason, effects)
+        }
+        NeteaseEvent::Error { req_id, error } => player::fm::handle_personal_fm_error(
+            *req_id,
+            error,
+            &mut state.app,
+            &mut state.request_tracker,
+            effects,
+        ),
+        NeteaseEvent::RateLimited { duration } => {
+            effects.set_toast(Toast::warning(format!(
+                "接口限流，暂停后台请求 {}",
+                minutes_label(*duration)
+            )));
+            true
+        }
         _ => false,
     }
 }
 
-pub async fn handle_audio_event(evt: AudioEvent, state: &mut CoreState, effects: &mut CoreEffects) {
+/// 加载启动时恢复的歌曲（见 [`player::playback::resume_restored_track`]）
+pub async fn resume_restored_track(state: &mut CoreState, effects: &mut CoreEffects) {
+    player::playback::resume_restored_track(
+        &mut state.app,
+        &mut state.request_tracker,
+        &mut state.song_request_titles,
+        &state.req_ids,
+        &mut state.next_song_cache,
+        effects,
+    )
+    .await;
+}
+
+/// 结算当前播放的歌曲（须在 App 的播放进度被新歌覆盖前调用）
+pub(super) fn finish_play_session(state: &mut CoreState) -> Option<PlayRecord> {
+    let session = state.play_session.take()?;
+    session.finish(state.app.playback_elapsed_ms(), state.app.play_total_ms)
+}
+
+pub(super) async fn append_history(data_dir: &Path, record: PlayRecord) {
+    let data_dir = data_dir.to_path_buf();
+    let result =
+        tokio::task::spawn_blocking(move || play_history::append_record(&data_dir, &record)).await;
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => tracing::warn!(err = %e, "写入播放历史失败"),
+        Err(e) => tracing::warn!(err = %e, "写入播放历史任务失败"),
+    }
+}
+
+pub async fn handle_audio_event(
+    evt: AudioEvent,
+    state: &mut CoreState,
+    effects: &mut CoreEffects,
+    data_dir: &Path,
+) {
+    // 位置上报很频繁：只在校正了进度时刷新 UI
+    if let AudioEvent::Position {
+        play_id,
+        position_ms,
+    } = evt
+    {
+        scrobble::on_position(&mut state.scrobble, play_id, position_ms, effects);
+        let corrected = player::audio::apply_position(&mut state.app, play_id, position_ms);
+        if corrected | player::ab_loop::on_position(&mut state.app, effects) {
+            effects.emit_state(&state.app);
+        }
+        return;
+    }
+
     let is_stopped = matches!(evt, AudioEvent::Stopped);
+    let now_playing = match &evt {
+        AudioEvent::NowPlaying {
+            song_id, play_id, ..
+        } => Some((*song_id, *play_id)),
+        _ => None,
+    };
+    if let AudioEvent::Ended { play_id } = evt {
+        let played_ms = state.app.playback_elapsed_ms();
+        scrobble::on_ended(&mut state.scrobble, play_id, played_ms, effects);
+    }
+
+    // 新歌开始、播放结束或停止时，上一首写入播放历史
+    if (now_playing.is_some() || is_stopped || matches!(evt, AudioEvent::Ended { .. }))
+        && let Some(record) = finish_play_session(state)
+    {
+        append_history(data_dir, record).await;
+    }
 
     let mut ctx = player::audio::AudioEventCtx {
         request_tracker: &mut state.request_tracker,
         song_request_titles: &mut state.song_request_titles,
-        req_id: &mut state.req_id,
+        req_ids: &state.req_ids,
         next_song_cache: &mut state.next_song_cache,
     };
     player::audio::handle_audio_event(&mut state.app, evt, &mut ctx, effects).await;
 
     if is_stopped {
-        state.next_song_cache.reset();
+        state.next_song_cache.reset(effects);
+        state.scrobble = None;
+    }
+    if let Some((song_id, play_id)) = now_playing {
+        state.scrobble = ListenScrobble::start(&state.app, play_id, song_id);
+        state.play_session = state
+            .app
+            .play_queue
+            .index_of_playing(song_id)
+            .and_then(|idx| state.app.play_queue.songs().get(idx))
+            .map(|song| PlaySession {
+                song: song.clone(),
+                br: state.app.play_actual_br,
+                started_at_epoch_ms: chrono::Utc::now().timestamp_millis(),
+            });
+        if let Some(notice) = state
+            .play_session
+            .as_ref()
+            .and_then(|session| TrackNotice::for_song(&state.app, &session.song))
+        {
+            notifications::show(notice);
+        }
     }
 
     effects.emit_state(&state.app);
@@ -141,13 +493,15 @@ pub async fn handle_audio_event(evt: AudioEvent, state: &mut CoreState, effects:
 
 #[cfg(test)]
 mod tests {
-    use super::handle_netease_event;
+    use super::{handle_audio_event, handle_netease_event, handle_ui};
+    use crate::app::{AudioFocus, AudioFocusPolicy};
     use crate::audio_worker::AudioCommand;
     use crate::core::effects::CoreEffect;
     use crate::core::infra::RequestKey;
     use crate::core::reducer::CoreState;
     use crate::domain::model::SongUrl;
-    use crate::netease::actor::NeteaseEvent;
+    use crate::messages::app::AppCommand;
+    use crate::netease::actor::{NeteaseCommand, NeteaseEvent};
 
     #[tokio::test]
     async fn song_url_starts_playback() {
@@ -156,7 +510,10 @@ mod tests {
         let mut effects = crate::core::effects::CoreEffects::default();
 
         let req_id = 42;
-        state.request_tracker.issue(RequestKey::SongUrl, || req_id);
+        state.request_tracker.issue(
+            RequestKey::SongUrl,
+            &crate::core::infra::IdAllocator::starting_at(req_id),
+        );
         state
             .song_request_titles
             .insert(7, "artist - title".to_owned());
@@ -167,7 +524,11 @@ mod tests {
                 song_url: SongUrl {
                     id: 7,
                     url: "http://example.com".to_owned(),
+                    size: None,
+                    br: None,
+                    gain_db: None,
                 },
+                br: 999_000,
             },
             &mut state,
             &mut effects,
@@ -197,9 +558,15 @@ mod tests {
         let mut state = CoreState::new(dir.path());
         let mut effects = crate::core::effects::CoreEffects::default();
 
-        state.request_tracker.issue(RequestKey::SongUrl, || 1);
+        state.request_tracker.issue(
+            RequestKey::SongUrl,
+            &crate::core::infra::IdAllocator::starting_at(1),
+        );
         state.song_request_titles.insert(1, "old".to_owned());
-        state.request_tracker.issue(RequestKey::SongUrl, || 2);
+        state.request_tracker.issue(
+            RequestKey::SongUrl,
+            &crate::core::infra::IdAllocator::starting_at(2),
+        );
         state.song_request_titles.insert(1, "new".to_owned());
 
         let stale = NeteaseEvent::SongUrl {
@@ -207,7 +574,11 @@ mod tests {
             song_url: SongUrl {
                 id: 1,
                 url: "stale".to_owned(),
+                size: None,
+                br: None,
+                gain_db: None,
             },
+            br: 999_000,
         };
         let handled_stale = handle_netease_event(&stale, &mut state, &mut effects).await;
         assert!(!handled_stale);
@@ -222,7 +593,11 @@ mod tests {
             song_url: SongUrl {
                 id: 1,
                 url: "fresh".to_owned(),
+                size: None,
+                br: None,
+                gain_db: None,
             },
+            br: 999_000,
         };
         let handled_fresh = handle_netease_event(&fresh, &mut state, &mut effects).await;
         assert!(handled_fresh);
@@ -230,4 +605,1579 @@ mod tests {
         assert_eq!(state.app.play_status, "已获取链接，准备缓存: new");
         assert!(!state.song_request_titles.contains_key(&1));
     }
+
+    #[tokio::test]
+    async fn terminal_suspend_pauses_first_and_resume_honors_pause_setting() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.play_started_at = Some(std::time::Instant::now());
+        state.app.play_paused_accum_ms = 1_000;
+        let resumed = AppCommand::TerminalResumed {
+            suspended_ms: 5_000,
+        };
+        let sends_toggle = |effects: &crate::core::effects::CoreEffects| {
+            matches!(audio_sends(effects)[..], [AudioCommand::TogglePause])
+        };
+
+        // 挂起前先暂停；默认恢复后继续播放
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::TerminalSuspending, &mut state, &mut effects).await;
+        assert!(sends_toggle(&effects));
+        assert!(state.app.suspend_paused && state.app.auto_paused);
+        state.app.paused = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&resumed, &mut state, &mut effects).await;
+        assert!(sends_toggle(&effects));
+        assert!(!state.app.suspend_paused && !state.app.auto_paused);
+        assert_eq!(
+            state.app.play_paused_accum_ms, 1_000,
+            "暂停计时已覆盖挂起时长"
+        );
+
+        // 开启 pause_on_suspend：恢复后保持暂停
+        state.app.paused = false;
+        state.app.pause_on_suspend = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::TerminalSuspending, &mut state, &mut effects).await;
+        assert!(sends_toggle(&effects));
+        state.app.paused = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&resumed, &mut state, &mut effects).await;
+        assert!(audio_sends(&effects).is_empty());
+        assert!(state.app.auto_paused && !state.app.suspend_paused);
+
+        // 挂起前已暂停：不动播放状态，恢复后也不会继续
+        state.app.pause_on_suspend = false;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::TerminalSuspending, &mut state, &mut effects).await;
+        handle_ui(&resumed, &mut state, &mut effects).await;
+        assert!(audio_sends(&effects).is_empty());
+
+        // 没来得及暂停：进度计时扣除挂起时长
+        state.app.paused = false;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&resumed, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_paused_accum_ms, 6_000);
+        assert!(audio_sends(&effects).is_empty());
+    }
+
+    #[tokio::test]
+    async fn focus_loss_pauses_and_regain_resumes_only_when_enabled() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.play_started_at = Some(std::time::Instant::now());
+        let lost = AppCommand::TerminalFocusChanged { focused: false };
+        let gained = AppCommand::TerminalFocusChanged { focused: true };
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&lost, &mut state, &mut effects).await;
+        assert!(audio_sends(&effects).is_empty(), "默认失焦不暂停");
+        assert!(!state.app.auto_paused);
+
+        state.app.pause_on_focus_lost = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&lost, &mut state, &mut effects).await;
+        assert!(matches!(
+            audio_sends(&effects)[..],
+            [AudioCommand::TogglePause]
+        ));
+        assert!(state.app.auto_paused);
+
+        state.app.paused = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&gained, &mut state, &mut effects).await;
+        assert!(matches!(
+            audio_sends(&effects)[..],
+            [AudioCommand::TogglePause]
+        ));
+        assert!(!state.app.auto_paused);
+
+        // 用户手动暂停后失焦/获得焦点：保持暂停
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&lost, &mut state, &mut effects).await;
+        handle_ui(&gained, &mut state, &mut effects).await;
+        assert!(audio_sends(&effects).is_empty());
+    }
+
+    fn audio_sends(effects: &crate::core::effects::CoreEffects) -> Vec<&AudioCommand> {
+        effects
+            .actions
+            .iter()
+            .filter_map(|effect| match effect {
+                CoreEffect::SendAudio { cmd, .. } => Some(cmd),
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn focus_changed(
+        state: &mut CoreState,
+        others_playing: bool,
+    ) -> crate::core::effects::CoreEffects {
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::AudioFocusChanged { others_playing },
+            state,
+            &mut effects,
+        )
+        .await;
+        effects
+    }
+
+    #[tokio::test]
+    async fn audio_focus_ducks_then_restores_volume() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.play_started_at = Some(std::time::Instant::now());
+        state.app.volume = 0.8;
+        state.app.audio_focus_duck_percent = 75;
+
+        // 默认忽略
+        assert!(audio_sends(&focus_changed(&mut state, true).await).is_empty());
+        assert!(audio_sends(&focus_changed(&mut state, false).await).is_empty());
+
+        state.app.audio_focus_policy = AudioFocusPolicy::Duck;
+        assert!(matches!(
+            audio_sends(&focus_changed(&mut state, true).await).as_slice(),
+            [AudioCommand::SetVolume(v)] if (*v - 0.2).abs() < 1e-6
+        ));
+        assert_eq!(state.app.audio_focus, AudioFocus::Ducked);
+        // 降低音量不改动用户音量设置
+        assert_eq!(state.app.volume, 0.8);
+
+        assert!(matches!(
+            audio_sends(&focus_changed(&mut state, false).await).as_slice(),
+            [AudioCommand::SetVolume(v)] if *v == 0.8
+        ));
+        assert_eq!(state.app.audio_focus, AudioFocus::Held);
+
+        // 未在播放时不处理
+        state.app.paused = true;
+        assert!(audio_sends(&focus_changed(&mut state, true).await).is_empty());
+        assert_eq!(state.app.audio_focus, AudioFocus::Held);
+    }
+
+    #[tokio::test]
+    async fn audio_focus_pause_resumes_unless_user_intervened() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.play_started_at = Some(std::time::Instant::now());
+        state.app.play_song_id = Some(1);
+        state.app.audio_focus_policy = AudioFocusPolicy::Pause;
+
+        assert!(matches!(
+            audio_sends(&focus_changed(&mut state, true).await).as_slice(),
+            [AudioCommand::TogglePause]
+        ));
+        assert!(state.app.auto_paused);
+        state.app.paused = true;
+        assert!(matches!(
+            audio_sends(&focus_changed(&mut state, false).await).as_slice(),
+            [AudioCommand::TogglePause]
+        ));
+        assert!(!state.app.auto_paused);
+        state.app.paused = false;
+
+        // 自动暂停后用户手动恢复，其它声音结束时不再切换
+        focus_changed(&mut state, true).await;
+        state.app.paused = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlayerTogglePause, &mut state, &mut effects).await;
+        assert!(!state.app.auto_paused);
+        state.app.paused = false;
+        assert!(audio_sends(&focus_changed(&mut state, false).await).is_empty());
+        assert_eq!(state.app.audio_focus, AudioFocus::Held);
+    }
+
+    #[tokio::test]
+    async fn downgraded_song_url_uses_actual_br_and_passes_gain() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.play_br = 320_000;
+        state.request_tracker.issue(
+            RequestKey::SongUrl,
+            &crate::core::infra::IdAllocator::starting_at(5),
+        );
+        state.song_request_titles.insert(7, "title".to_owned());
+
+        let evt = NeteaseEvent::SongUrl {
+            req_id: 5,
+            song_url: SongUrl {
+                id: 7,
+                url: "http://example.com".to_owned(),
+                size: None,
+                br: Some(128_000),
+                gain_db: Some(-5.2),
+            },
+            br: 320_000,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.play_actual_br, Some(128_000));
+        let snapshot = crate::app::AppSnapshot::from_app(&state.app);
+        assert_eq!(snapshot.player.downgraded_br(), Some(128_000));
+        assert!(effects.actions.iter().any(|effect| {
+            matches!(
+                effect,
+                CoreEffect::SendAudio {
+                    cmd: AudioCommand::PlayTrack {
+                        br: 128_000,
+                        requested_br: 320_000,
+                        gain_db: Some(-5.2),
+                        ..
+                    },
+                    ..
+                }
+            )
+        }));
+    }
+
+    #[tokio::test]
+    async fn song_url_carries_no_fade_hint_for_album_runs_and_queue_override() {
+        use crate::app::PlayMode;
+        use crate::domain::model::Song;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let song = |id, album_id| Song {
+            id,
+            name: format!("song-{id}"),
+            album_id: Some(album_id),
+            ..Default::default()
+        };
+        state.app.play_queue.set_mode(PlayMode::Sequential);
+        state
+            .app
+            .play_queue
+            .set_songs(vec![song(1, 10), song(2, 10), song(3, 20)], Some(0));
+
+        async fn play_url(state: &mut CoreState, id: i64) -> Option<bool> {
+            let req_id = state.request_tracker.issue(
+                RequestKey::SongUrl,
+                &crate::core::infra::IdAllocator::starting_at(id as u64 + 100),
+            );
+            state.song_request_titles.insert(id, format!("song-{id}"));
+            let mut effects = crate::core::effects::CoreEffects::default();
+            let evt = NeteaseEvent::SongUrl {
+                req_id,
+                song_url: SongUrl {
+                    id,
+                    url: "http://example.com".to_owned(),
+                    size: None,
+                    br: None,
+                    gain_db: None,
+                },
+                br: 999_000,
+            };
+            handle_netease_event(&evt, state, &mut effects).await;
+            effects.actions.iter().find_map(|effect| match effect {
+                CoreEffect::SendAudio {
+                    cmd: AudioCommand::PlayTrack { no_fade, .. },
+                    ..
+                } => Some(*no_fade),
+                _ => None,
+            })
+        }
+
+        assert_eq!(play_url(&mut state, 1).await, Some(false));
+        state.app.play_queue.next_index();
+        assert_eq!(play_url(&mut state, 2).await, Some(true), "同专辑连续曲目");
+        state.app.play_queue.next_index();
+        assert_eq!(
+            play_url(&mut state, 3).await,
+            Some(false),
+            "换专辑恢复淡入淡出"
+        );
+
+        // 播放中切换队列覆盖：之后的切歌都不淡入淡出，再次切换恢复
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::QueueToggleNoFade, &mut state, &mut effects).await;
+        assert!(state.app.play_queue.no_fade());
+        state.app.play_queue.set_current_index(0);
+        assert_eq!(play_url(&mut state, 1).await, Some(true));
+        handle_ui(&AppCommand::QueueToggleNoFade, &mut state, &mut effects).await;
+        assert_eq!(play_url(&mut state, 1).await, Some(false));
+    }
+
+    fn queue_songs(ids: std::ops::Range<i64>) -> Vec<crate::domain::model::Song> {
+        ids.map(|id| crate::domain::model::Song {
+            id,
+            name: format!("song-{id}"),
+            ..Default::default()
+        })
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn undo_replace_restores_queue_and_position_when_song_still_playing() {
+        use crate::features::player::queue_undo;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.play_queue.set_songs(queue_songs(0..4), Some(2));
+        state.app.play_queue.set_origin("album:1");
+        state.app.play_song_id = Some(2);
+
+        assert!(queue_undo::replace(
+            &mut state.app,
+            queue_songs(10..13),
+            Some(0),
+            Some("playlist:7".to_owned()),
+        ));
+        // 打开歌单只替换队列，播放仍停留在原来的歌曲
+        handle_ui(&AppCommand::QueueUndoReplace, &mut state, &mut effects).await;
+
+        let queue = &state.app.play_queue;
+        assert_eq!(
+            queue.songs().iter().map(|s| s.id).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(queue.origin(), Some("album:1"));
+        assert_eq!(queue.current_index(), Some(2));
+        assert!(state.app.queue_undo.is_none(), "只保留一层");
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::QueueUndoReplace, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.current_index(), Some(2));
+    }
+
+    #[tokio::test]
+    async fn undo_replace_keeps_current_playback_when_another_song_plays() {
+        use crate::features::player::queue_undo;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.play_queue.set_songs(queue_songs(0..4), Some(1));
+        state.app.play_song_id = Some(1);
+
+        queue_undo::replace(&mut state.app, queue_songs(10..13), Some(2), None);
+        state.app.play_song_id = Some(12);
+        handle_ui(&AppCommand::QueueUndoReplace, &mut state, &mut effects).await;
+
+        let queue = &state.app.play_queue;
+        assert_eq!(
+            queue.songs().iter().map(|s| s.id).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(
+            queue.current_index(),
+            None,
+            "正在播放的歌曲不在恢复的队列中"
+        );
+        assert_eq!(state.app.play_song_id, Some(12));
+        assert!(
+            !effects.actions.iter().any(|e| matches!(
+                e,
+                CoreEffect::SendAudio {
+                    cmd: AudioCommand::PlayTrack { .. } | AudioCommand::Stop,
+                    ..
+                }
+            )),
+            "不打断当前播放"
+        );
+
+        // 正在播放的歌曲也在旧队列中时游标跟随它
+        queue_undo::replace(&mut state.app, queue_songs(10..13), Some(0), None);
+        state.app.play_song_id = Some(3);
+        handle_ui(&AppCommand::QueueUndoReplace, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.current_index(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn queue_view_reorders_and_removes_selected_song() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.view = crate::app::View::Queue;
+        state.app.play_queue.set_songs(queue_songs(0..4), Some(1));
+        state.app.play_song_id = Some(1);
+        let ids = |state: &CoreState| {
+            state
+                .app
+                .play_queue
+                .ordered_songs()
+                .iter()
+                .map(|s| s.id)
+                .collect::<Vec<_>>()
+        };
+
+        // 选中从当前歌曲开始，调整顺序后选中项与游标都跟随歌曲
+        handle_ui(&AppCommand::QueueReorderDown, &mut state, &mut effects).await;
+        assert_eq!(ids(&state), [0, 2, 1, 3]);
+        assert_eq!(state.app.queue_selected, Some(2));
+        assert_eq!(state.app.play_queue.current_index(), Some(2));
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(1));
+
+        // 移除其他歌曲不影响播放
+        handle_ui(&AppCommand::QueueMoveUp, &mut state, &mut effects).await;
+        effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::QueueRemoveSelected, &mut state, &mut effects).await;
+        assert_eq!(ids(&state), [0, 1, 3]);
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(1));
+        assert!(!effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendNeteaseHi {
+                cmd: NeteaseCommand::SongUrl { .. },
+                ..
+            }
+        )));
+
+        // 选中项停在原位置，即正在播放的歌曲；移除后接着播放原来的下一首
+        assert_eq!(state.app.queue_selected, Some(1));
+        handle_ui(&AppCommand::QueueRemoveSelected, &mut state, &mut effects).await;
+        assert_eq!(ids(&state), [0, 3]);
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(3));
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendNeteaseHi {
+                cmd: NeteaseCommand::SongUrl { id: 3, .. },
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn queue_view_plays_selected_song_and_stops_when_last_is_removed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.view = crate::app::View::Queue;
+        state.app.play_queue.set_songs(queue_songs(0..2), Some(0));
+        state.app.play_song_id = Some(0);
+
+        handle_ui(&AppCommand::QueueMoveDown, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::QueuePlaySelected, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.current_index(), Some(1));
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendNeteaseHi {
+                cmd: NeteaseCommand::SongUrl { id: 1, .. },
+                ..
+            }
+        )));
+
+        // 最后一首正在播放时被移除：没有下一首，停止播放
+        state.app.play_song_id = Some(1);
+        effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::QueueRemoveSelected, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.songs().len(), 1);
+        assert_eq!(state.app.play_queue.current_index(), None);
+        assert_eq!(state.app.queue_selected, Some(0));
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendAudio {
+                cmd: AudioCommand::Stop,
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn enqueue_selected_search_result_plays_next_or_last() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.view = crate::app::View::Search;
+        state.app.play_queue.set_songs(queue_songs(0..3), Some(0));
+        state.app.play_song_id = Some(0);
+        state.app.search_results = queue_songs(10..12)
+            .into_iter()
+            .map(|s| crate::domain::model::Song {
+                artists: "A".to_owned(),
+                ..s
+            })
+            .collect();
+        state.app.search_selected = 1;
+        let ids = |state: &CoreState| {
+            state
+                .app
+                .play_queue
+                .ordered_songs()
+                .iter()
+                .map(|s| s.id)
+                .collect::<Vec<_>>()
+        };
+
+        handle_ui(&AppCommand::EnqueueNextSelected, &mut state, &mut effects).await;
+        assert_eq!(ids(&state), [0, 11, 1, 2]);
+        assert_eq!(state.app.search_status, "已添加到下一首播放: song-11 - A");
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(0));
+        // 下一首变了：重新预缓存新的下一首
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendNeteaseLo {
+                cmd: NeteaseCommand::SongUrl { id: 11, .. },
+                ..
+            }
+        )));
+
+        // 允许重复；追加到队尾不改变下一首
+        effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::EnqueueLastSelected, &mut state, &mut effects).await;
+        assert_eq!(ids(&state), [0, 11, 1, 2, 11]);
+        assert_eq!(state.app.search_status, "已添加到队尾: song-11 - A");
+        assert!(!effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendNeteaseLo {
+                cmd: NeteaseCommand::SongUrl { .. },
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn queue_panel_collapses_sections_without_touching_playback() {
+        use crate::domain::model::Song;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let songs = |ids: std::ops::Range<i64>| {
+            ids.map(|id| Song {
+                id,
+                name: format!("song-{id}"),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>()
+        };
+        let queue = &mut state.app.play_queue;
+        queue.set_songs(songs(0..3), Some(1));
+        queue.set_origin("album:1");
+        queue.extend_from(songs(3..6), Some("playlist:7"));
+        let order_before = queue.order().to_vec();
+
+        // 选中从当前歌曲开始；折叠第一组后选择停在标题（该组第一首）
+        handle_ui(&AppCommand::QueueCollapse, &mut state, &mut effects).await;
+        assert!(state.app.queue_collapsed.contains("album:1"));
+        handle_ui(&AppCommand::QueueMoveDown, &mut state, &mut effects).await;
+        assert_eq!(state.app.queue_selected, Some(3), "跳过折叠的歌曲");
+        handle_ui(&AppCommand::QueueMoveUp, &mut state, &mut effects).await;
+        assert_eq!(state.app.queue_selected, Some(0));
+
+        handle_ui(&AppCommand::QueueExpand, &mut state, &mut effects).await;
+        assert!(state.app.queue_collapsed.is_empty());
+        handle_ui(&AppCommand::QueueMoveDown, &mut state, &mut effects).await;
+        assert_eq!(state.app.queue_selected, Some(1));
+
+        assert_eq!(state.app.play_queue.order(), order_before);
+        assert_eq!(state.app.play_queue.current_index(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn finished_song_is_appended_to_play_history() {
+        use crate::audio_worker::{AudioEvent, AudioStreamHint};
+        use crate::domain::model::Song;
+        use std::time::{Duration, Instant};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let song = |id| Song {
+            id,
+            name: format!("song-{id}"),
+            artists: "A/B".to_owned(),
+            duration_ms: Some(200_000),
+            album: "Album".to_owned(),
+            album_id: Some(9),
+            artist_ids: vec![1, 2],
+            translated: None,
+            cover_url: None,
+            track_no: None,
+        };
+        state
+            .app
+            .play_queue
+            .set_songs(vec![song(1), song(2)], Some(0));
+        state.app.play_actual_br = Some(128_000);
+        let now_playing = |song_id| AudioEvent::NowPlaying {
+            song_id,
+            play_id: song_id as u64,
+            title: String::new(),
+            duration_ms: Some(200_000),
+            stream_hint: AudioStreamHint::cached_file(None),
+        };
+
+        handle_audio_event(now_playing(1), &mut state, &mut effects, dir.path()).await;
+        state.app.play_started_at = Some(Instant::now() - Duration::from_secs(150));
+        state
+            .app
+            .play_queue
+            .set_songs(vec![song(1), song(2)], Some(1));
+        handle_audio_event(now_playing(2), &mut state, &mut effects, dir.path()).await;
+
+        // 第二首刚开始就停止：视为跳过
+        handle_audio_event(AudioEvent::Stopped, &mut state, &mut effects, dir.path()).await;
+
+        let records = crate::play_history::load_history(dir.path()).expect("load");
+        assert_eq!(records.len(), 1);
+        let rec = &records[0];
+        assert_eq!(rec.song_id, 1);
+        assert_eq!(rec.album_id, Some(9));
+        assert_eq!(rec.artist_ids, vec![1, 2]);
+        assert_eq!(rec.br, Some(128_000));
+        assert!(rec.played_ms >= 150_000);
+        assert!(rec.completion.is_some_and(|c| c >= 0.75));
+        assert!(state.play_session.is_none());
+    }
+
+    fn scrobbles(effects: &crate::core::effects::CoreEffects) -> Vec<(i64, Option<i64>, u64)> {
+        effects
+            .actions
+            .iter()
+            .filter_map(|e| match e {
+                CoreEffect::SendNeteaseLo {
+                    cmd:
+                        NeteaseCommand::Scrobble {
+                            song_id,
+                            source_id,
+                            time_secs,
+                        },
+                    ..
+                } => Some((*song_id, *source_id, *time_secs)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn listen_is_scrobbled_once_after_30_seconds_with_playlist_source() {
+        use crate::audio_worker::{AudioEvent, AudioStreamHint};
+        use crate::domain::model::Song;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.logged_in = true;
+        let songs = vec![
+            Song {
+                id: 1,
+                ..Default::default()
+            },
+            Song {
+                id: 2,
+                duration_ms: Some(20_000),
+                ..Default::default()
+            },
+        ];
+        state.app.play_queue.set_songs(songs, Some(0));
+        state.app.play_queue.set_origin("playlist:77".to_owned());
+        let now_playing = |song_id| AudioEvent::NowPlaying {
+            song_id,
+            play_id: song_id as u64,
+            title: String::new(),
+            duration_ms: None,
+            stream_hint: AudioStreamHint::cached_file(None),
+        };
+        let position = |play_id, position_ms| AudioEvent::Position {
+            play_id,
+            position_ms,
+        };
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(now_playing(1), &mut state, &mut effects, dir.path()).await;
+        for (play_id, ms) in [(1, 29_500), (9, 45_000), (1, 30_000), (1, 30_500)] {
+            handle_audio_event(position(play_id, ms), &mut state, &mut effects, dir.path()).await;
+        }
+        handle_audio_event(
+            AudioEvent::Ended { play_id: 1 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(scrobbles(&effects), vec![(1, Some(77), 30)]);
+
+        // 不足 30 秒的短歌在自然结束时上报
+        state.app.play_queue.set_songs(
+            vec![Song {
+                id: 2,
+                ..Default::default()
+            }],
+            Some(0),
+        );
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(now_playing(2), &mut state, &mut effects, dir.path()).await;
+        handle_audio_event(position(2, 19_000), &mut state, &mut effects, dir.path()).await;
+        handle_audio_event(
+            AudioEvent::Ended { play_id: 2 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        let sent = scrobbles(&effects);
+        assert_eq!(sent.len(), 1);
+        assert_eq!((sent[0].0, sent[0].1), (2, None), "新队列没有来源歌单");
+    }
+
+    #[tokio::test]
+    async fn stop_after_current_ends_without_advancing_queue() {
+        use crate::audio_worker::{AudioEvent, AudioStreamHint};
+        use crate::domain::model::Song;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let songs = (1..=3)
+            .map(|id| Song {
+                id,
+                ..Default::default()
+            })
+            .collect();
+        state.app.play_queue.set_songs(songs, Some(0));
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(
+            AudioEvent::NowPlaying {
+                song_id: 1,
+                play_id: 1,
+                title: String::new(),
+                duration_ms: None,
+                stream_hint: AudioStreamHint::cached_file(None),
+            },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+
+        let toggle = AppCommand::PlayerToggleStopAfterCurrent;
+        handle_ui(&toggle, &mut state, &mut effects).await;
+        assert!(state.app.stop_after_current);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(
+            AudioEvent::Ended { play_id: 1 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(!state.app.stop_after_current);
+        assert_eq!(state.app.play_status, "播放结束（单曲后停止）");
+        assert_eq!(state.app.play_queue.current_index(), Some(0));
+        assert!(
+            !effects
+                .actions
+                .iter()
+                .any(|e| matches!(e, CoreEffect::SendNeteaseHi { .. })),
+            "不应请求下一首"
+        );
+
+        // 手动切歌、停止都会取消
+        handle_ui(&toggle, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::PlayerNext, &mut state, &mut effects).await;
+        assert!(!state.app.stop_after_current);
+        assert_eq!(state.app.play_queue.current_index(), Some(1));
+        handle_ui(&toggle, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::PlayerStop, &mut state, &mut effects).await;
+        assert!(!state.app.stop_after_current);
+    }
+
+    #[tokio::test]
+    async fn ab_loop_seeks_back_to_a_after_passing_b() {
+        use crate::audio_worker::{AudioEvent, AudioStreamHint};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let now_playing = |song_id| AudioEvent::NowPlaying {
+            song_id,
+            play_id: song_id as u64,
+            title: String::new(),
+            duration_ms: Some(180_000),
+            stream_hint: AudioStreamHint::cached_file(None),
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(now_playing(1), &mut state, &mut effects, dir.path()).await;
+
+        state.app.set_playback_position(30_000);
+        handle_ui(&AppCommand::PlayerSetLoopPointB, &mut state, &mut effects).await;
+        assert_eq!(state.app.loop_b_ms, None, "没有 A 点时忽略");
+        handle_ui(&AppCommand::PlayerSetLoopPointA, &mut state, &mut effects).await;
+        let a = state.app.loop_a_ms.expect("A 点");
+        state.app.set_playback_position(20_000);
+        handle_ui(&AppCommand::PlayerSetLoopPointB, &mut state, &mut effects).await;
+        assert_eq!(state.app.loop_b_ms, None);
+        assert_eq!(state.app.play_status, "B 点须在 A 点之后，已忽略");
+        state.app.set_playback_position(45_000);
+        handle_ui(&AppCommand::PlayerSetLoopPointB, &mut state, &mut effects).await;
+        let b = state.app.loop_b_ms.expect("B 点");
+        assert!(b > a);
+
+        let seeks = |effects: &crate::core::effects::CoreEffects| {
+            effects
+                .actions
+                .iter()
+                .filter_map(|e| match e {
+                    CoreEffect::SendAudio {
+                        cmd: AudioCommand::SeekToMs(ms),
+                        ..
+                    } => Some(*ms),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let position = |position_ms| AudioEvent::Position {
+            play_id: 1,
+            position_ms,
+        };
+        state.app.set_playback_position(40_000);
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(position(40_000), &mut state, &mut effects, dir.path()).await;
+        assert!(seeks(&effects).is_empty());
+        state.app.set_playback_position(b + 200);
+        handle_audio_event(position(b + 200), &mut state, &mut effects, dir.path()).await;
+        assert_eq!(seeks(&effects), vec![a]);
+        assert!(state.app.playback_elapsed_ms() < b);
+
+        // 换歌后清除
+        handle_audio_event(now_playing(2), &mut state, &mut effects, dir.path()).await;
+        assert_eq!((state.app.loop_a_ms, state.app.loop_b_ms), (None, None));
+    }
+
+    #[tokio::test]
+    async fn needs_reload_replays_song_and_resumes_at_elapsed_time() {
+        use crate::audio_worker::{AudioEvent, AudioStreamHint};
+        use crate::domain::model::Song;
+        use std::time::{Duration, Instant};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let songs = (1..=3)
+            .map(|id| Song {
+                id,
+                name: format!("song-{id}"),
+                ..Default::default()
+            })
+            .collect();
+        state.app.play_queue.set_songs(songs, Some(1));
+        // 恢复出来的状态：暂停在 40s
+        let now = Instant::now();
+        state.app.paused = true;
+        state.app.play_started_at = Some(now - Duration::from_secs(50));
+        state.app.play_paused_at = Some(now - Duration::from_secs(10));
+        state.app.play_song_id = Some(2);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(
+            AudioEvent::NeedsReload,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.pending_seek_ms, Some(40_000));
+        let req_id = effects
+            .actions
+            .iter()
+            .find_map(|e| match e {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::SongUrl { req_id, id: 2, br },
+                    ..
+                } if *br == state.app.play_br => Some(*req_id),
+                _ => None,
+            })
+            .expect("按当前音质重新请求");
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id,
+            song_url: url(2, None),
+            br: state.app.play_br,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        handle_audio_event(
+            AudioEvent::NowPlaying {
+                song_id: 2,
+                play_id: 1,
+                title: "song-2".to_owned(),
+                duration_ms: Some(180_000),
+                stream_hint: AudioStreamHint::cached_file(None),
+            },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        // 用户按的是播放：跳到之前的进度并继续播放
+        assert!(!state.app.paused);
+        assert_eq!(state.app.pending_seek_ms, None);
+        let cmds = audio_sends(&effects);
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, AudioCommand::PlayTrack { id: 2, .. }))
+        );
+        assert!(
+            cmds.iter()
+                .any(|c| matches!(c, AudioCommand::SeekToMs(40_000)))
+        );
+        assert!(!cmds.iter().any(|c| matches!(c, AudioCommand::TogglePause)));
+    }
+
+    #[tokio::test]
+    async fn guests_and_skipped_songs_are_not_scrobbled() {
+        use crate::audio_worker::{AudioEvent, AudioStreamHint};
+        use crate::domain::model::Song;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.play_queue.set_songs(
+            vec![Song {
+                id: 1,
+                ..Default::default()
+            }],
+            Some(0),
+        );
+        let now_playing = || AudioEvent::NowPlaying {
+            song_id: 1,
+            play_id: 1,
+            title: String::new(),
+            duration_ms: None,
+            stream_hint: AudioStreamHint::cached_file(None),
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(now_playing(), &mut state, &mut effects, dir.path()).await;
+        handle_audio_event(
+            AudioEvent::Position {
+                play_id: 1,
+                position_ms: 60_000,
+            },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(scrobbles(&effects).is_empty(), "游客不上报");
+
+        // 登录后：停止（跳过）不上报
+        state.app.logged_in = true;
+        handle_audio_event(now_playing(), &mut state, &mut effects, dir.path()).await;
+        handle_audio_event(AudioEvent::Stopped, &mut state, &mut effects, dir.path()).await;
+        handle_audio_event(
+            AudioEvent::Ended { play_id: 1 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(scrobbles(&effects).is_empty());
+    }
+
+    fn url(id: i64, br: Option<i64>) -> SongUrl {
+        SongUrl {
+            id,
+            url: "http://example.com".to_owned(),
+            size: None,
+            br,
+            gain_db: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn unavailable_song_url_falls_back_down_the_br_ladder() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.play_br = 999_000;
+        state.request_tracker.issue(
+            RequestKey::SongUrl,
+            &crate::core::infra::IdAllocator::starting_at(1),
+        );
+        state.song_request_titles.insert(7, "title".to_owned());
+
+        let mut next_req = 1;
+        for (br, expected) in [(999_000, 320_000), (320_000, 128_000)] {
+            let mut effects = crate::core::effects::CoreEffects::default();
+            let evt = NeteaseEvent::SongUrlUnavailable {
+                req_id: next_req,
+                id: 7,
+                br,
+            };
+            assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+            let retry = effects.actions.iter().find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::SongUrl { req_id, id: 7, br },
+                    ..
+                } => Some((*req_id, *br)),
+                _ => None,
+            });
+            let (req_id, retry_br) = retry.expect("应降级重试");
+            assert_eq!(retry_br, expected);
+            next_req = req_id;
+        }
+        assert!(
+            state.app.song_failures.count(7) == 0,
+            "降级过程中不记为失败"
+        );
+
+        // 128k 拿到链接：按实际使用的音质播放
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id: next_req,
+            song_url: url(7, None),
+            br: 128_000,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.play_actual_br, Some(128_000));
+        assert!(effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SendAudio {
+                cmd: AudioCommand::PlayTrack {
+                    br: 128_000,
+                    requested_br: 999_000,
+                    ..
+                },
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn lowest_br_unavailable_skips_song() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.request_tracker.issue(
+            RequestKey::SongUrl,
+            &crate::core::infra::IdAllocator::starting_at(1),
+        );
+        state.song_request_titles.insert(7, "title".to_owned());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        let evt = NeteaseEvent::SongUrlUnavailable {
+            req_id: 1,
+            id: 7,
+            br: 128_000,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.song_failures.count(7), 1);
+        assert_eq!(state.app.play_status, "歌曲不可播放，自动跳过...");
+    }
+
+    #[tokio::test]
+    async fn prefetch_falls_back_and_playback_reuses_cached_br() {
+        use crate::domain::model::Song;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.play_br = 999_000;
+        let song = |id| Song {
+            id,
+            name: format!("song-{id}"),
+            ..Default::default()
+        };
+        state
+            .app
+            .play_queue
+            .set_mode(crate::app::PlayMode::Sequential);
+        state
+            .app
+            .play_queue
+            .set_songs(vec![song(1), song(2)], Some(0));
+
+        let prefetch_req = |effects: &crate::core::effects::CoreEffects| {
+            effects.actions.iter().find_map(|effect| match effect {
+                CoreEffect::SendNeteaseLo {
+                    cmd: NeteaseCommand::SongUrl { req_id, id: 2, br },
+                    ..
+                } => Some((*req_id, *br)),
+                _ => None,
+            })
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state
+            .next_song_cache
+            .prefetch_next(&state.app, &mut effects, &state.req_ids)
+            .await;
+        let (req_id, br) = prefetch_req(&effects).expect("应预缓存第二首");
+        assert_eq!(br, 999_000);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrlUnavailable { req_id, id: 2, br };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let (req_id, br) = prefetch_req(&effects).expect("预缓存应降级重试");
+        assert_eq!(br, 320_000);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id,
+            song_url: url(2, Some(320_000)),
+            br,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SendAudio {
+                cmd: AudioCommand::QueueNext {
+                    id: 2,
+                    br: 320_000,
+                    ..
+                },
+                ..
+            }
+        )));
+        assert_eq!(state.next_song_cache.cached_br(2), Some(320_000));
+
+        // 切到下一首时直接按预缓存的音质请求，与缓存键一致
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlayerNext, &mut state, &mut effects).await;
+        assert!(effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SendNeteaseHi {
+                cmd: NeteaseCommand::SongUrl {
+                    id: 2,
+                    br: 320_000,
+                    ..
+                },
+                ..
+            }
+        )));
+    }
+
+    #[tokio::test]
+    async fn up_next_matches_what_player_next_plays() {
+        use crate::app::{AppSnapshot, PlayMode};
+        use crate::domain::model::Song;
+
+        let song = |id| Song {
+            id,
+            name: format!("song-{id}"),
+            ..Default::default()
+        };
+        for mode in [
+            PlayMode::Sequential,
+            PlayMode::ListLoop,
+            PlayMode::SingleLoop,
+            PlayMode::Shuffle,
+        ] {
+            for start in 0..3 {
+                let dir = tempfile::tempdir().expect("tempdir");
+                let mut state = CoreState::new(dir.path());
+                state.app.play_mode = mode;
+                state.app.play_queue.set_mode(mode);
+                state
+                    .app
+                    .play_queue
+                    .set_songs((1..=3).map(song).collect(), Some(start));
+                let up_next = AppSnapshot::from_app(&state.app).player.up_next;
+
+                let mut effects = crate::core::effects::CoreEffects::default();
+                handle_ui(&AppCommand::PlayerNext, &mut state, &mut effects).await;
+                let played = state
+                    .app
+                    .play_queue
+                    .current()
+                    .map(|s| s.display_title(state.app.song_name_mode));
+                let case = format!("{mode:?} start={start}");
+                match up_next.as_deref() {
+                    Some("无") => {
+                        assert_eq!(played, None, "{case}");
+                        assert_eq!(state.app.play_status, "播放结束", "{case}");
+                    }
+                    Some(title) => assert_eq!(played.as_deref(), Some(title), "{case}"),
+                    None => panic!("{case}: 有当前歌曲时应显示下一首"),
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn queued_next_song_hands_off_without_round_trip() {
+        use crate::audio_worker::{AudioEvent, AudioStreamHint};
+        use crate::domain::model::Song;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let song = |id| Song {
+            id,
+            name: format!("song-{id}"),
+            ..Default::default()
+        };
+        state
+            .app
+            .play_queue
+            .set_mode(crate::app::PlayMode::Sequential);
+        state
+            .app
+            .play_queue
+            .set_songs(vec![song(1), song(2), song(3)], Some(0));
+
+        let prefetch_req = |effects: &crate::core::effects::CoreEffects, want: i64| {
+            effects.actions.iter().find_map(|effect| match effect {
+                CoreEffect::SendNeteaseLo {
+                    cmd: NeteaseCommand::SongUrl { req_id, id, br },
+                    ..
+                } if *id == want => Some((*req_id, *br)),
+                _ => None,
+            })
+        };
+        let sent_audio = |effects: &crate::core::effects::CoreEffects| -> Vec<String> {
+            effects
+                .actions
+                .iter()
+                .filter_map(|effect| match effect {
+                    CoreEffect::SendAudio { cmd, .. } => Some(format!("{cmd:?}")),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        // 下一首链接就绪：预排到音频引擎
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state
+            .next_song_cache
+            .prefetch_next(&state.app, &mut effects, &state.req_ids)
+            .await;
+        let (req_id, br) = prefetch_req(&effects, 2).expect("应预缓存第二首");
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id,
+            song_url: url(2, Some(320_000)),
+            br,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SendAudio {
+                cmd: AudioCommand::QueueNext {
+                    id: 2,
+                    br: 320_000,
+                    ..
+                },
+                ..
+            }
+        )));
+
+        // 引擎自行切换：队列推进到第二首，不再请求播放链接，并接着预排第三首
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = AudioEvent::NowPlaying {
+            song_id: 2,
+            play_id: 2,
+            title: "song-2".to_owned(),
+            duration_ms: None,
+            stream_hint: AudioStreamHint::cached_file(None),
+        };
+        handle_audio_event(evt, &mut state, &mut effects, dir.path()).await;
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(2));
+        assert_eq!(state.app.play_actual_br, Some(320_000));
+        assert!(!effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SendNeteaseHi {
+                cmd: NeteaseCommand::SongUrl { .. },
+                ..
+            }
+        )));
+        let (req_id, br) = prefetch_req(&effects, 3).expect("应预缓存第三首");
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id,
+            song_url: url(3, None),
+            br,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+
+        // 手动切歌：预排的歌曲作废
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlayerPrev, &mut state, &mut effects).await;
+        assert_eq!(sent_audio(&effects), ["ClearQueued"]);
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(1));
+    }
+
+    #[tokio::test]
+    async fn toggling_no_fade_requeues_the_next_song() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state
+            .app
+            .play_queue
+            .set_mode(crate::app::PlayMode::Sequential);
+        state.app.play_queue.set_songs(
+            (1..=3).map(crate::domain::model::Song::fixture).collect(),
+            Some(0),
+        );
+
+        let prefetch_req = |effects: &crate::core::effects::CoreEffects| {
+            effects.actions.iter().find_map(|effect| match effect {
+                CoreEffect::SendNeteaseLo {
+                    cmd: NeteaseCommand::SongUrl { req_id, id: 2, br },
+                    ..
+                } => Some((*req_id, *br)),
+                _ => None,
+            })
+        };
+        let queued_no_fade = |effects: &crate::core::effects::CoreEffects| {
+            audio_sends(effects).into_iter().find_map(|cmd| match cmd {
+                AudioCommand::QueueNext { id: 2, no_fade, .. } => Some(*no_fade),
+                _ => None,
+            })
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state
+            .next_song_cache
+            .prefetch_next(&state.app, &mut effects, &state.req_ids)
+            .await;
+        let (req_id, br) = prefetch_req(&effects).expect("应预缓存第二首");
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id,
+            song_url: url(2, None),
+            br,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(queued_no_fade(&effects), Some(false));
+
+        // 已预排后切换：丢弃引擎中的预排，按新设置重新预排
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::QueueToggleNoFade, &mut state, &mut effects).await;
+        assert!(matches!(
+            audio_sends(&effects).as_slice(),
+            [AudioCommand::ClearQueued]
+        ));
+        let (req_id, br) = prefetch_req(&effects).expect("应重新请求第二首");
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id,
+            song_url: url(2, None),
+            br,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(queued_no_fade(&effects), Some(true));
+    }
+
+    fn fm_song(id: i64) -> crate::app::Song {
+        crate::app::Song {
+            id,
+            name: format!("FM{id}"),
+            artists: "歌手".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn sent_netease(effects: &crate::core::effects::CoreEffects) -> Vec<String> {
+        effects
+            .actions
+            .iter()
+            .filter_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi { cmd, .. } | CoreEffect::SendNeteaseLo { cmd, .. } => {
+                    Some(format!("{cmd:?}"))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    async fn deliver_fm(state: &mut CoreState, ids: &[i64]) -> crate::core::effects::CoreEffects {
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let req_id = state
+            .request_tracker
+            .get_pending(&RequestKey::PersonalFm)
+            .expect("应有进行中的私人FM请求");
+        let evt = NeteaseEvent::PersonalFm {
+            req_id,
+            songs: ids.iter().map(|id| fm_song(*id)).collect(),
+        };
+        assert!(handle_netease_event(&evt, state, &mut effects).await);
+        effects
+    }
+
+    #[tokio::test]
+    async fn fm_plays_from_buffer_refills_and_precaches() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.logged_in = true;
+        state.app.play_queue.set_songs(vec![fm_song(100)], Some(0));
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        handle_ui(&AppCommand::FmToggle, &mut state, &mut effects).await;
+        assert!(state.app.fm_active());
+        assert!(state.app.play_queue.is_empty());
+        assert!(sent_netease(&effects)[0].starts_with("PersonalFm"));
+
+        // 第一批到达：立即播放第一首，预缓存第二首；缓冲区只剩 1 首，继续补充
+        let effects = deliver_fm(&mut state, &[1, 2]).await;
+        let sent = sent_netease(&effects);
+        assert!(
+            sent[0].starts_with("SongUrl") && sent[0].contains("id: 1,"),
+            "{sent:?}"
+        );
+        assert!(
+            sent[1].starts_with("SongUrl") && sent[1].contains("id: 2,"),
+            "{sent:?}"
+        );
+        assert!(sent[2].starts_with("PersonalFm"), "{sent:?}");
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(1));
+
+        // 下一首从缓冲区取，而不是按队列顺序
+        let effects = deliver_fm(&mut state, &[2, 3]).await;
+        assert!(
+            sent_netease(&effects).is_empty(),
+            "重复歌曲应被过滤、无需补充"
+        );
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlayerNext, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(2));
+        let sent = sent_netease(&effects);
+        assert!(sent[0].contains("id: 2,"), "{sent:?}");
+        assert!(
+            sent[1].contains("id: 3,"),
+            "应预缓存下一首 FM 歌曲: {sent:?}"
+        );
+        assert!(sent[2].starts_with("PersonalFm"), "{sent:?}");
+
+        // 上一首不可用
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlayerPrev, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(2));
+        assert_eq!(state.app.play_status, "私人FM 不支持上一首");
+        assert!(sent_netease(&effects).is_empty());
+    }
+
+    #[tokio::test]
+    async fn fm_toggle_off_restores_queue_and_new_queue_ends_fm() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.logged_in = true;
+        state
+            .app
+            .play_queue
+            .set_songs(vec![fm_song(100), fm_song(101)], Some(1));
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        handle_ui(&AppCommand::FmToggle, &mut state, &mut effects).await;
+        deliver_fm(&mut state, &[1, 2, 3]).await;
+        assert_eq!(
+            state.app.user_queue().songs().len(),
+            2,
+            "保存状态时使用原队列"
+        );
+
+        handle_ui(&AppCommand::FmToggle, &mut state, &mut effects).await;
+        assert!(state.app.fm.is_none());
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(101));
+
+        // FM 期间换了播放队列：下一首按新队列播放，FM 结束
+        handle_ui(&AppCommand::FmToggle, &mut state, &mut effects).await;
+        deliver_fm(&mut state, &[1, 2]).await;
+        state
+            .app
+            .play_queue
+            .set_songs(vec![fm_song(200), fm_song(201)], Some(0));
+        assert!(!state.app.fm_active());
+        handle_ui(&AppCommand::PlayerNext, &mut state, &mut effects).await;
+        assert!(state.app.fm.is_none());
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(201));
+    }
+
+    #[tokio::test]
+    async fn cache_changes_refresh_usage_and_cached_marks() {
+        use crate::audio_worker::AudioEvent;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.playlist_mode = crate::app::PlaylistMode::Tracks;
+        state.app.playlist_tracks = (1..=3)
+            .map(|id| crate::domain::model::Song {
+                id,
+                ..Default::default()
+            })
+            .collect();
+        state.app.play_br = 320_000;
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_audio_event(
+            AudioEvent::CacheEvicted { files: 1, bytes: 1 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(matches!(
+            audio_sends(&effects).as_slice(),
+            [
+                AudioCommand::QueryCacheStats,
+                AudioCommand::QueryCachedSongs { song_ids, br: 320_000 },
+            ] if song_ids == &[1, 2, 3]
+        ));
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        for evt in [
+            AudioEvent::CacheStats {
+                files: 12,
+                bytes: 40 << 20,
+            },
+            AudioEvent::CachedSongs {
+                song_ids: vec![1, 3],
+            },
+        ] {
+            handle_audio_event(evt, &mut state, &mut effects, dir.path()).await;
+        }
+        assert_eq!(state.app.cache_usage, Some((12, 40 << 20)));
+        assert_eq!(state.app.cached_song_ids, [1, 3].into());
+    }
+
+    #[tokio::test]
+    async fn download_resolves_the_url_then_exports_to_the_download_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.settings.download_dir = Some(dir.path().join("music"));
+        state.app.view = crate::app::View::Search;
+        state.app.play_br = 320_000;
+        state.app.search_results = vec![crate::domain::model::Song {
+            id: 7,
+            name: "晴天".to_owned(),
+            artists: "周杰伦".to_owned(),
+            ..Default::default()
+        }];
+        let song_url_req = |effects: &crate::core::effects::CoreEffects| {
+            effects.actions.iter().find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::SongUrl { req_id, id: 7, br },
+                    ..
+                } => Some((*req_id, *br)),
+                _ => None,
+            })
+        };
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::DownloadSelected, &mut state, &mut effects).await;
+        let (req_id, br) = song_url_req(&effects).expect("请求播放链接");
+        assert_eq!(br, 320_000);
+        assert_eq!(state.app.tasks.quit_blockers(), 1, "下载期间退出需要确认");
+
+        // 当前音质无链接时降级重试
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let unavailable = NeteaseEvent::SongUrlUnavailable { req_id, id: 7, br };
+        assert!(handle_netease_event(&unavailable, &mut state, &mut effects).await);
+        let (req_id, br) = song_url_req(&effects).expect("降级重试");
+        assert_eq!(br, 128_000);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let evt = NeteaseEvent::SongUrl {
+            req_id,
+            song_url: SongUrl {
+                id: 7,
+                url: "http://example.com".to_owned(),
+                size: Some(1024),
+                br: Some(128_000),
+                gain_db: None,
+            },
+            br,
+        };
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let expected = dir.path().join("music").join("周杰伦 - 晴天");
+        assert!(matches!(
+            audio_sends(&effects).as_slice(),
+            [AudioCommand::ExportTrack { id: 7, br: 128_000, dest, .. }] if *dest == expected
+        ));
+        assert!(state.pending_downloads.is_empty());
+        assert_eq!(state.app.play_song_id, None, "下载不影响播放");
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let exported = crate::audio_worker::AudioEvent::Exported {
+            song_id: 7,
+            path: expected.with_extension("mp3"),
+        };
+        handle_audio_event(exported, &mut state, &mut effects, dir.path()).await;
+        let task = &state.app.tasks.tasks()[0];
+        assert_eq!(task.kind, crate::app::TaskKind::TrackExport { song_id: 7 });
+        assert_eq!(task.status, crate::app::TaskStatus::Completed);
+        assert_eq!(state.app.tasks.quit_blockers(), 0);
+    }
 }
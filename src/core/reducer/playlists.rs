@@ -1,6 +1,7 @@
 use super::{CoreState, UiAction};
 use crate::core::effects::CoreEffects;
 use crate::features::playlists as playlists_handlers;
+use crate::features::playlists::{edit, filter, unavailable};
 use crate::messages::app::AppCommand;
 use crate::netease::actor::NeteaseEvent;
 
@@ -19,7 +20,9 @@ pub async fn handle_ui(
         | AppCommand::PlaylistsPageUp
         | AppCommand::PlaylistsJumpTop
         | AppCommand::PlaylistsJumpBottom
+        | AppCommand::PlaylistsCycleFilter
         | AppCommand::PlaylistsOpenSelected
+        | AppCommand::PlaylistsOpenRecommend
         | AppCommand::PlaylistTracksMoveUp
         | AppCommand::PlaylistTracksMoveDown
         | AppCommand::PlaylistTracksMoveTo { .. }
@@ -27,6 +30,9 @@ pub async fn handle_ui(
         | AppCommand::PlaylistTracksPageUp
         | AppCommand::PlaylistTracksJumpTop
         | AppCommand::PlaylistTracksJumpBottom
+        | AppCommand::PlaylistTracksJumpToPlaying
+        | AppCommand::PlaylistTracksToggleOrder
+        | AppCommand::PlaylistTracksCycleSort
         | AppCommand::PlaylistTracksPlaySelected => {
             let playlist_cmd = match cmd {
                 AppCommand::PlaylistsMoveUp => AppCommand::PlaylistsMoveUp,
@@ -38,7 +44,9 @@ pub async fn handle_ui(
                 AppCommand::PlaylistsPageUp => AppCommand::PlaylistsPageUp,
                 AppCommand::PlaylistsJumpTop => AppCommand::PlaylistsJumpTop,
                 AppCommand::PlaylistsJumpBottom => AppCommand::PlaylistsJumpBottom,
+                AppCommand::PlaylistsCycleFilter => AppCommand::PlaylistsCycleFilter,
                 AppCommand::PlaylistsOpenSelected => AppCommand::PlaylistsOpenSelected,
+                AppCommand::PlaylistsOpenRecommend => AppCommand::PlaylistsOpenRecommend,
                 AppCommand::PlaylistTracksMoveUp => AppCommand::PlaylistTracksMoveUp,
                 AppCommand::PlaylistTracksMoveDown => AppCommand::PlaylistTracksMoveDown,
                 AppCommand::PlaylistTracksMoveTo { index } => {
@@ -48,13 +56,16 @@ pub async fn handle_ui(
                 AppCommand::PlaylistTracksPageUp => AppCommand::PlaylistTracksPageUp,
                 AppCommand::PlaylistTracksJumpTop => AppCommand::PlaylistTracksJumpTop,
                 AppCommand::PlaylistTracksJumpBottom => AppCommand::PlaylistTracksJumpBottom,
+                AppCommand::PlaylistTracksJumpToPlaying => AppCommand::PlaylistTracksJumpToPlaying,
+                AppCommand::PlaylistTracksToggleOrder => AppCommand::PlaylistTracksToggleOrder,
+                AppCommand::PlaylistTracksCycleSort => AppCommand::PlaylistTracksCycleSort,
                 AppCommand::PlaylistTracksPlaySelected => AppCommand::PlaylistTracksPlaySelected,
                 _ => unreachable!("checked by outer match"),
             };
             playlists_handlers::handle_playlists_command(
                 playlist_cmd,
                 &mut state.app,
-                &mut state.req_id,
+                &state.req_ids,
                 &mut state.request_tracker,
                 &mut state.song_request_titles,
                 &mut state.playlist_tracks_loader,
@@ -65,10 +76,130 @@ pub async fn handle_ui(
             .await;
             UiAction::Handled
         }
+        AppCommand::PlaylistsRetryPreload => {
+            state.preload_mgr.resume(&mut state.app);
+            state
+                .preload_mgr
+                .start_for_playlists(
+                    &mut state.app,
+                    effects,
+                    &state.req_ids,
+                    state.settings.preload_count,
+                )
+                .await;
+            playlists_handlers::refresh_playlist_list_status(&mut state.app);
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::SearchAddSelectedToPlaylist => {
+            edit::open_picker_for_search(&mut state.app, effects);
+            UiAction::Handled
+        }
+        AppCommand::SearchAddMarkedToPlaylist => {
+            edit::open_picker_for_marked(&mut state.app, effects);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistPickerInputChar { c } => {
+            edit::edit_picker_filter(&mut state.app, effects, Some(c.encode_utf8(&mut [0; 4])));
+            UiAction::Handled
+        }
+        AppCommand::PlaylistPickerInputString { s } => {
+            edit::edit_picker_filter(&mut state.app, effects, Some(s));
+            UiAction::Handled
+        }
+        AppCommand::PlaylistPickerInputBackspace => {
+            edit::edit_picker_filter(&mut state.app, effects, None);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistFilterOpen => {
+            filter::open(&mut state.app, effects);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistFilterChar { c } => {
+            filter::edit(&mut state.app, effects, Some(c.encode_utf8(&mut [0; 4])));
+            UiAction::Handled
+        }
+        AppCommand::PlaylistFilterString { s } => {
+            filter::edit(&mut state.app, effects, Some(s));
+            UiAction::Handled
+        }
+        AppCommand::PlaylistFilterBackspace => {
+            filter::edit(&mut state.app, effects, None);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistFilterSubmit => {
+            filter::submit(&mut state.app, effects);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistFilterCancel => {
+            filter::cancel(&mut state.app, effects);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistPickerMoveUp | AppCommand::PlaylistPickerMoveDown => {
+            let down = matches!(cmd, AppCommand::PlaylistPickerMoveDown);
+            edit::move_picker(&mut state.app, effects, down);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistPickerCancel => {
+            edit::cancel_picker(&mut state.app, effects);
+            UiAction::Handled
+        }
+        AppCommand::PlaylistPickerConfirm => {
+            edit::confirm_picker(
+                &mut state.app,
+                &mut state.pending_playlist_edits,
+                &state.req_ids,
+                effects,
+            );
+            UiAction::Handled
+        }
+        AppCommand::PlaylistTracksRemoveSelected => {
+            edit::remove_selected_track(
+                &mut state.app,
+                &mut state.pending_playlist_edits,
+                &state.req_ids,
+                effects,
+            );
+            UiAction::Handled
+        }
+        AppCommand::PlaylistTracksCheckUnavailable => {
+            unavailable::start(
+                &mut state.app,
+                state.playlist_tracks_loader.is_some(),
+                &mut state.unavailable_check,
+                &state.req_ids,
+                &mut state.request_tracker,
+                effects,
+            );
+            UiAction::Handled
+        }
+        AppCommand::UnavailableReviewMoveUp | AppCommand::UnavailableReviewMoveDown => {
+            let down = matches!(cmd, AppCommand::UnavailableReviewMoveDown);
+            unavailable::move_review(&mut state.app, effects, down);
+            UiAction::Handled
+        }
+        AppCommand::UnavailableReviewAccept => {
+            unavailable::accept_selected(
+                &mut state.app,
+                &mut state.pending_swaps,
+                &state.req_ids,
+                effects,
+            );
+            UiAction::Handled
+        }
+        AppCommand::UnavailableReviewSkip => {
+            unavailable::skip_selected(&mut state.app, effects);
+            UiAction::Handled
+        }
+        AppCommand::UnavailableReviewClose => {
+            unavailable::close_review(&mut state.app, effects);
+            UiAction::Handled
+        }
         AppCommand::Back => {
             playlists_handlers::handle_playlists_back_command(
                 AppCommand::Back,
                 &mut state.app,
+                &mut state.request_tracker,
                 &mut state.playlist_tracks_loader,
                 effects,
             )
@@ -93,7 +224,7 @@ pub async fn handle_netease_event(
                 &mut state.request_tracker,
                 &mut state.preload_mgr,
                 effects,
-                &mut state.req_id,
+                &state.req_ids,
                 state.settings.preload_count,
             )
             .await
@@ -102,10 +233,92 @@ pub async fn handle_netease_event(
             }
             true
         }
+        NeteaseEvent::RecommendSongs { req_id, songs } => {
+            playlists_handlers::handle_recommend_songs_event(
+                *req_id,
+                songs.clone(),
+                &mut state.app,
+                &mut state.request_tracker,
+                effects,
+                &mut state.next_song_cache,
+            )
+        }
+        NeteaseEvent::PlaylistTracksManipulated {
+            req_id,
+            op,
+            playlist_id,
+            track_ids,
+        } => {
+            tracing::debug!(
+                req_id,
+                playlist_id,
+                op = op.as_str(),
+                changed = track_ids.len(),
+                "NeteaseActor: PlaylistTracksManipulated"
+            );
+            edit::handle_playlist_tracks_manipulated_event(
+                *req_id,
+                track_ids,
+                &mut state.app,
+                &mut state.pending_playlist_edits,
+                &mut state.preload_mgr,
+                effects,
+            ) || unavailable::handle_swap_manipulated(
+                *req_id,
+                track_ids,
+                &mut state.app,
+                &mut state.pending_swaps,
+                &mut state.preload_mgr,
+                &state.req_ids,
+                effects,
+            )
+        }
+        NeteaseEvent::SongPreviews { req_id, previews } => unavailable::handle_previews(
+            *req_id,
+            Ok(previews),
+            &mut state.app,
+            &mut state.unavailable_check,
+            &state.req_ids,
+            &mut state.request_tracker,
+            effects,
+        ),
+        NeteaseEvent::SearchSongs { req_id, songs } => unavailable::handle_search(
+            *req_id,
+            songs,
+            &mut state.app,
+            &mut state.unavailable_check,
+            &state.req_ids,
+            &mut state.request_tracker,
+            effects,
+        ),
+        NeteaseEvent::Error { req_id, error } => {
+            playlists_handlers::handle_recommend_songs_error(
+                *req_id,
+                error,
+                &mut state.app,
+                &mut state.request_tracker,
+                effects,
+            ) || edit::handle_playlist_edit_error(
+                *req_id,
+                error,
+                &mut state.pending_playlist_edits,
+                effects,
+            ) || unavailable::handle_error(
+                *req_id,
+                error,
+                &mut state.app,
+                &mut state.unavailable_check,
+                &mut state.pending_swaps,
+                &state.req_ids,
+                &mut state.request_tracker,
+                effects,
+            )
+        }
         NeteaseEvent::PlaylistTrackIds {
             req_id,
             playlist_id,
             ids,
+            added_at,
         } => {
             if state.preload_mgr.owns_req(*req_id)
                 && state
@@ -113,10 +326,11 @@ pub async fn handle_netease_event(
                     .on_playlist_track_ids(
                         &mut state.app,
                         effects,
-                        &mut state.req_id,
+                        &state.req_ids,
                         *req_id,
                         *playlist_id,
                         ids,
+                        added_at,
                     )
                     .await
             {
@@ -129,12 +343,13 @@ pub async fn handle_netease_event(
                 *req_id,
                 *playlist_id,
                 ids.clone(),
+                added_at.clone(),
                 &mut state.app,
                 &mut state.request_tracker,
                 &mut state.playlist_tracks_loader,
                 &state.preload_mgr,
                 effects,
-                &mut state.req_id,
+                &state.req_ids,
             )
             .await
             {
@@ -147,7 +362,7 @@ pub async fn handle_netease_event(
             if state.preload_mgr.owns_req(*req_id)
                 && state
                     .preload_mgr
-                    .on_songs(&mut state.app, effects, &mut state.req_id, *req_id, songs)
+                    .on_songs(&mut state.app, effects, &state.req_ids, *req_id, songs)
                     .await
             {
                 playlists_handlers::refresh_playlist_list_status(&mut state.app);
@@ -163,7 +378,7 @@ pub async fn handle_netease_event(
                 &mut state.playlist_tracks_loader,
                 &mut state.preload_mgr,
                 effects,
-                &mut state.req_id,
+                &state.req_ids,
             )
             .await
             {
@@ -178,13 +393,13 @@ pub async fn handle_netease_event(
 
 #[cfg(test)]
 mod tests {
-    use super::handle_ui;
-    use crate::app::{Playlist, PlaylistMode};
+    use super::{handle_netease_event, handle_ui};
+    use crate::app::{Playlist, PlaylistMode, Song};
     use crate::core::effects::CoreEffect;
     use crate::core::infra::RequestKey;
     use crate::core::reducer::{CoreState, UiAction};
     use crate::messages::app::AppCommand;
-    use crate::netease::actor::NeteaseCommand;
+    use crate::netease::actor::{NeteaseCommand, NeteaseEvent};
 
     #[tokio::test]
     async fn playlists_open_selected_requests_detail() {
@@ -197,6 +412,8 @@ mod tests {
             name: "test".to_owned(),
             track_count: 0,
             special_type: 0,
+            subscribed: false,
+            updated_at_ms: None,
         }];
         state.app.playlists_selected = 0;
         state.app.playlist_mode = PlaylistMode::List;
@@ -221,4 +438,1141 @@ mod tests {
             )
         }));
     }
+
+    #[tokio::test]
+    async fn jump_to_playing_selects_the_playing_track() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.playlist_mode = PlaylistMode::Tracks;
+        state.app.playlist_tracks = (1..=50)
+            .map(|id| Song {
+                id,
+                ..Default::default()
+            })
+            .collect();
+
+        state.app.play_song_id = Some(42);
+        handle_ui(
+            &AppCommand::PlaylistTracksJumpToPlaying,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlist_tracks_selected, 41);
+
+        state.app.play_song_id = Some(99);
+        handle_ui(
+            &AppCommand::PlaylistTracksJumpToPlaying,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlist_tracks_selected, 41, "不在歌单中时不移动");
+        assert_eq!(state.app.playlists_status, "正在播放的歌曲不在此歌单中");
+    }
+
+    #[tokio::test]
+    async fn track_filter_plays_from_full_playlist_and_esc_clears() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.view = crate::app::View::Playlists;
+        state.app.playlist_mode = PlaylistMode::Tracks;
+        state.app.playlist_tracks = ["Yellow", "晴天", "Fix You", "夜曲", "You Raise Me Up"]
+            .iter()
+            .zip(1..)
+            .map(|(name, id)| Song {
+                id,
+                name: (*name).to_owned(),
+                artists: "A".to_owned(),
+                ..Default::default()
+            })
+            .collect();
+
+        handle_ui(&AppCommand::PlaylistFilterOpen, &mut state, &mut effects).await;
+        assert!(state.app.playlist_filter_editing);
+        handle_ui(
+            &AppCommand::PlaylistFilterString {
+                s: "YOU".to_owned(),
+            },
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.visible_playlist_tracks(), vec![2, 4]);
+        assert_eq!(state.app.playlist_tracks_selected, 2, "选中第一个匹配");
+        assert_eq!(state.app.playlists_status, "匹配 2/5 首（Esc 清除筛选）");
+
+        // 在筛选结果中移动，快照中的位置按筛选结果换算，序号保留原值
+        handle_ui(
+            &AppCommand::PlaylistTracksMoveDown,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlist_tracks_selected, 4);
+        handle_ui(
+            &AppCommand::PlaylistTracksMoveDown,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlist_tracks_selected, 4, "已在最后一个匹配");
+        handle_ui(&AppCommand::PlaylistFilterSubmit, &mut state, &mut effects).await;
+        assert!(!state.app.playlist_filter_editing);
+        let snapshot = crate::app::AppSnapshot::from_app(&state.app);
+        let crate::app::AppViewSnapshot::Playlists(view) = &snapshot.view_state else {
+            panic!("应为歌单视图");
+        };
+        assert_eq!(view.playlist_tracks.len(), 2);
+        assert_eq!(view.playlist_tracks_selected, 1);
+        assert_eq!(view.playlist_tracks_index, vec![2, 4]);
+        assert_eq!(view.playlist_tracks_total, 5);
+
+        // 播放：队列为完整歌单，游标在选中的歌曲上
+        handle_ui(
+            &AppCommand::PlaylistTracksPlaySelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.play_queue.songs().len(), 5);
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(5));
+
+        handle_ui(&AppCommand::PlaylistFilterCancel, &mut state, &mut effects).await;
+        assert!(state.app.playlist_filter_input.is_empty());
+        assert_eq!(state.app.visible_playlist_tracks().len(), 5);
+        assert_eq!(state.app.playlist_tracks_selected, 4, "清除后仍选中原歌曲");
+    }
+
+    #[tokio::test]
+    async fn filter_hides_playlists_and_navigation_skips_them() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+        state.app.playlists = (1..=4)
+            .map(|id| Playlist {
+                id,
+                name: format!("p{id}"),
+                subscribed: id % 2 == 0,
+                ..Default::default()
+            })
+            .collect();
+        state.app.playlists_selected = 1; // id 2（收藏）
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlaylistsCycleFilter, &mut state, &mut effects).await;
+        assert_eq!(state.app.playlist_filter, crate::app::PlaylistFilter::Mine);
+        assert_eq!(
+            state.app.playlists_selected, 0,
+            "被隐藏时回到第一个可见歌单"
+        );
+        assert!(state.app.playlists_status.contains("我的 2 · 收藏 2"));
+
+        handle_ui(&AppCommand::PlaylistsMoveDown, &mut state, &mut effects).await;
+        assert_eq!(state.app.playlists_selected, 2, "跳过收藏的歌单");
+        handle_ui(&AppCommand::PlaylistsMoveDown, &mut state, &mut effects).await;
+        assert_eq!(state.app.playlists_selected, 2, "已到可见列表末尾");
+
+        let snapshot = crate::app::AppSnapshot::from_app(&state.app);
+        let crate::app::AppViewSnapshot::Playlists(view) = &snapshot.view_state else {
+            panic!("应为歌单视图");
+        };
+        let ids: Vec<i64> = view.playlists.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+        assert_eq!(view.playlists_selected, 1);
+
+        // 鼠标点击使用可见位置
+        handle_ui(
+            &AppCommand::PlaylistsMoveTo { index: 0 },
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlists_selected, 0);
+
+        // 切到收藏：id 1 被隐藏；再切回全部时按 id 保持选中
+        handle_ui(&AppCommand::PlaylistsCycleFilter, &mut state, &mut effects).await;
+        assert_eq!(state.app.playlists_selected, 1);
+        handle_ui(&AppCommand::PlaylistsCycleFilter, &mut state, &mut effects).await;
+        assert_eq!(state.app.playlist_filter, crate::app::PlaylistFilter::All);
+        assert_eq!(state.app.playlists_selected, 1);
+    }
+
+    /// 打开含 `count` 首歌曲的歌单，返回首批歌曲详情请求 (req_id, ids)
+    ///
+    /// 歌曲 id 越大加入歌单越晚。
+    async fn open_playlist_with_tracks(state: &mut CoreState, count: i64) -> (u64, Vec<i64>) {
+        state.app.view = crate::app::View::Playlists;
+        state.app.playlists = vec![Playlist {
+            id: 1,
+            name: "big".to_owned(),
+            track_count: count,
+            ..Default::default()
+        }];
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlaylistsOpenSelected, state, &mut effects).await;
+        let req_id = state
+            .request_tracker
+            .get_pending(&RequestKey::PlaylistDetail)
+            .expect("detail pending");
+        let evt = NeteaseEvent::PlaylistTrackIds {
+            req_id,
+            playlist_id: 1,
+            ids: (1..=count).collect(),
+            added_at: (1..=count).map(|id| (id, 1_700_000_000_000 + id)).collect(),
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_netease_event(&evt, state, &mut effects).await;
+        last_song_detail_request(&effects).expect("首批歌曲详情请求")
+    }
+
+    fn last_song_detail_request(
+        effects: &crate::core::effects::CoreEffects,
+    ) -> Option<(u64, Vec<i64>)> {
+        effects
+            .actions
+            .iter()
+            .rev()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::SongDetailByIds { req_id, ids },
+                    ..
+                } => Some((*req_id, ids.clone())),
+                _ => None,
+            })
+    }
+
+    fn songs_for(ids: &[i64]) -> Vec<Song> {
+        ids.iter()
+            .map(|&id| Song {
+                id,
+                name: format!("s{id}"),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    async fn deliver_songs(
+        state: &mut CoreState,
+        req_id: u64,
+        ids: &[i64],
+    ) -> crate::core::effects::CoreEffects {
+        let evt = NeteaseEvent::Songs {
+            req_id,
+            songs: songs_for(ids),
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_netease_event(&evt, state, &mut effects).await;
+        effects
+    }
+
+    #[tokio::test]
+    async fn huge_playlist_shows_first_chunk_and_extends_queue_while_playing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let (req_id, ids) = open_playlist_with_tracks(&mut state, 350).await;
+        assert_eq!(ids.len(), 100, "首批请求较小");
+
+        let effects = deliver_songs(&mut state, req_id, &ids).await;
+        assert!(matches!(state.app.playlist_mode, PlaylistMode::Tracks));
+        assert_eq!(state.app.playlist_tracks.len(), 100);
+        assert_eq!(
+            state.app.playlists_status,
+            "歌曲: 100/350 首（加载中，p 播放）"
+        );
+        assert!(
+            state.app.playlist_tracks_stats.is_none(),
+            "加载完成前不统计"
+        );
+        let (req_id, ids) = last_song_detail_request(&effects).expect("继续请求下一批");
+        assert_eq!(ids.len(), 200);
+
+        // 加载中途开始播放第 51 首
+        state.app.playlist_tracks_selected = 50;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistTracksPlaySelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(51));
+
+        deliver_songs(&mut state, req_id, &ids).await;
+        assert_eq!(state.app.playlist_tracks.len(), 300);
+        assert_eq!(
+            state.app.play_queue.songs().len(),
+            300,
+            "后续批次追加到队列"
+        );
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(51));
+        assert_eq!(state.app.playlist_tracks_selected, 50, "选中项不被重置");
+    }
+
+    #[tokio::test]
+    async fn huge_playlist_completes_with_stats_and_keeps_selection() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let (req_id, ids) = open_playlist_with_tracks(&mut state, 150).await;
+        let effects = deliver_songs(&mut state, req_id, &ids).await;
+        let (req_id, ids) = last_song_detail_request(&effects).expect("第二批");
+        assert_eq!(ids.len(), 50);
+
+        state.app.playlist_tracks_selected = 10;
+        deliver_songs(&mut state, req_id, &ids).await;
+        assert_eq!(state.app.playlist_tracks.len(), 150);
+        assert_eq!(state.app.playlist_tracks_selected, 10);
+        assert_eq!(state.app.playlists_status, "歌曲: 150 首（p 播放）");
+        assert!(state.app.playlist_tracks_stats.is_some());
+        assert!(state.playlist_tracks_loader.is_none());
+    }
+
+    fn track_ids(songs: &[Song]) -> Vec<i64> {
+        songs.iter().map(|s| s.id).collect()
+    }
+
+    #[tokio::test]
+    async fn toggle_order_keeps_selection_and_queue_follows_display() {
+        use crate::app::TrackOrder;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let (req_id, ids) = open_playlist_with_tracks(&mut state, 150).await;
+        let effects = deliver_songs(&mut state, req_id, &ids).await;
+
+        // 加载中切换：已显示的 100 首按添加时间倒序，选中的仍是第 11 首
+        state.app.playlist_tracks_selected = 10;
+        let mut effects2 = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistTracksToggleOrder,
+            &mut state,
+            &mut effects2,
+        )
+        .await;
+        assert_eq!(state.app.playlist_tracks_order(), TrackOrder::RecentlyAdded);
+        assert_eq!(state.app.playlist_tracks[0].id, 100);
+        let selected = state.app.playlist_tracks_selected;
+        assert_eq!(state.app.playlist_tracks[selected].id, 11);
+
+        // 后续批次加入后整体仍按最近添加排列
+        let (req_id, ids) = last_song_detail_request(&effects).expect("第二批");
+        deliver_songs(&mut state, req_id, &ids).await;
+        assert_eq!(
+            track_ids(&state.app.playlist_tracks),
+            (1..=150).rev().collect::<Vec<_>>()
+        );
+        let selected = state.app.playlist_tracks_selected;
+        assert_eq!(state.app.playlist_tracks[selected].id, 11);
+
+        // 开始播放：队列即显示顺序
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistTracksPlaySelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(
+            track_ids(state.app.play_queue.songs()),
+            track_ids(&state.app.playlist_tracks)
+        );
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(11));
+
+        // 切回原始顺序
+        handle_ui(
+            &AppCommand::PlaylistTracksToggleOrder,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(
+            track_ids(&state.app.playlist_tracks),
+            (1..=150).collect::<Vec<_>>()
+        );
+        assert_eq!(state.app.playlist_tracks_selected, 10);
+    }
+
+    #[tokio::test]
+    async fn reopening_cached_playlist_uses_chosen_order() {
+        use crate::app::TrackOrder;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+        state.app.playlists = vec![Playlist {
+            id: 1,
+            name: "cached".to_owned(),
+            track_count: 3,
+            ..Default::default()
+        }];
+        state.app.playlist_preloads.insert(
+            1,
+            crate::app::PlaylistPreload {
+                status: crate::app::PreloadStatus::Completed,
+                songs: songs_for(&[1, 2, 3]),
+                missing_count: 0,
+                stats: None,
+                completed_at_ms: None,
+                added_at: std::collections::HashMap::from([(1, 10), (2, 30), (3, 20)]),
+                track_ids_fingerprint: None,
+            },
+        );
+        state
+            .app
+            .playlist_track_orders
+            .insert(1, TrackOrder::RecentlyAdded);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlaylistsOpenSelected, &mut state, &mut effects).await;
+        assert_eq!(track_ids(&state.app.playlist_tracks), vec![2, 3, 1]);
+        assert_eq!(state.app.playlist_tracks_selected, 0);
+        assert_eq!(track_ids(state.app.play_queue.songs()), vec![2, 3, 1]);
+
+        // 每日推荐没有添加时间，不能切换
+        state.app.playlist_tracks_recommend = true;
+        handle_ui(
+            &AppCommand::PlaylistTracksToggleOrder,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(track_ids(&state.app.playlist_tracks), vec![2, 3, 1]);
+        assert_eq!(state.app.playlists_status, "每日推荐不支持切换歌曲顺序");
+    }
+
+    #[tokio::test]
+    async fn cycle_sort_survives_reopening_and_resets_for_other_playlists() {
+        use crate::app::TrackOrder;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+        state.app.playlists = (1..=2)
+            .map(|id| Playlist {
+                id,
+                name: format!("p{id}"),
+                track_count: 3,
+                ..Default::default()
+            })
+            .collect();
+        let named = |id: i64, name: &str, artists: &str| Song {
+            id,
+            name: name.to_owned(),
+            artists: artists.to_owned(),
+            ..Default::default()
+        };
+        for id in 1..=2 {
+            state.app.playlist_preloads.insert(
+                id,
+                crate::app::PlaylistPreload {
+                    status: crate::app::PreloadStatus::Completed,
+                    songs: vec![
+                        named(1, "晴天", "周杰伦"),
+                        named(2, "Yellow", "Coldplay"),
+                        named(3, "安静", "周杰伦"),
+                    ],
+                    missing_count: 0,
+                    stats: None,
+                    completed_at_ms: None,
+                    added_at: std::collections::HashMap::new(),
+                    track_ids_fingerprint: None,
+                },
+            );
+        }
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlaylistsOpenSelected, &mut state, &mut effects).await;
+        handle_ui(
+            &AppCommand::PlaylistTracksCycleSort,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlist_tracks_order(), TrackOrder::ByName);
+        assert_eq!(track_ids(&state.app.playlist_tracks), vec![3, 1, 2]);
+        assert_eq!(
+            state.app.playlists_status,
+            "歌曲顺序: 按歌名（S 切换，p 播放时队列按此顺序）"
+        );
+        handle_ui(
+            &AppCommand::PlaylistTracksCycleSort,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlist_tracks_order(), TrackOrder::ByArtist);
+        assert_eq!(track_ids(&state.app.playlist_tracks), vec![2, 3, 1]);
+
+        // 播放时队列按显示顺序
+        state.app.playlist_tracks_selected = 1;
+        handle_ui(
+            &AppCommand::PlaylistTracksPlaySelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(track_ids(state.app.play_queue.songs()), vec![2, 3, 1]);
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(3));
+
+        // 返回列表再打开同一歌单：沿用排序
+        handle_ui(&AppCommand::Back, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::PlaylistsOpenSelected, &mut state, &mut effects).await;
+        assert_eq!(track_ids(&state.app.playlist_tracks), vec![2, 3, 1]);
+
+        // 打开另一个歌单：原始顺序
+        handle_ui(&AppCommand::Back, &mut state, &mut effects).await;
+        state.app.playlists_selected = 1;
+        handle_ui(&AppCommand::PlaylistsOpenSelected, &mut state, &mut effects).await;
+        assert_eq!(state.app.playlist_tracks_order(), TrackOrder::Canonical);
+        assert_eq!(track_ids(&state.app.playlist_tracks), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn back_cancels_remaining_chunks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let (req_id, ids) = open_playlist_with_tracks(&mut state, 350).await;
+        let effects = deliver_songs(&mut state, req_id, &ids).await;
+        let (req_id, ids) = last_song_detail_request(&effects).expect("第二批");
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::Back, &mut state, &mut effects).await;
+        assert!(matches!(state.app.playlist_mode, PlaylistMode::List));
+        assert!(state.playlist_tracks_loader.is_none());
+        assert_eq!(
+            state
+                .request_tracker
+                .get_pending(&RequestKey::PlaylistTracks),
+            None
+        );
+
+        // 晚到的批次被丢弃，也不再发起新的请求
+        let effects = deliver_songs(&mut state, req_id, &ids).await;
+        assert!(matches!(state.app.playlist_mode, PlaylistMode::List));
+        assert_eq!(state.app.playlist_tracks.len(), 100);
+        assert!(last_song_detail_request(&effects).is_none());
+    }
+
+    #[tokio::test]
+    async fn recommend_requires_login_and_opens_like_a_playlist() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+
+        // 游客：不发请求，直接提示
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistsOpenRecommend,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.playlists_status, "每日推荐需要登录（当前为游客）");
+        assert_eq!(
+            state
+                .request_tracker
+                .get_pending(&RequestKey::RecommendSongs),
+            None
+        );
+
+        state.app.logged_in = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistsOpenRecommend,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        let req_id = effects
+            .actions
+            .iter()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::RecommendSongs { req_id },
+                    ..
+                } => Some(*req_id),
+                _ => None,
+            })
+            .expect("recommend request");
+
+        let evt = NeteaseEvent::RecommendSongs {
+            req_id,
+            songs: songs_for(&[7, 8, 9]),
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(matches!(state.app.playlist_mode, PlaylistMode::Tracks));
+        assert_eq!(state.app.playlists_status, "每日推荐: 3 首（p 播放）");
+        assert_eq!(state.app.play_queue.origin(), Some("recommend:daily"));
+        assert_eq!(state.app.play_queue.songs().len(), 3);
+        let snapshot = crate::app::AppSnapshot::from_app(&state.app);
+        let crate::app::AppViewSnapshot::Playlists(view) = &snapshot.view_state else {
+            panic!("应为歌单视图");
+        };
+        assert_eq!(view.playlist_tracks_name, "每日推荐");
+
+        state.app.playlist_tracks_selected = 2;
+        handle_ui(
+            &AppCommand::PlaylistTracksPlaySelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.play_queue.origin(), Some("recommend:daily"));
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(9));
+    }
+
+    #[tokio::test]
+    async fn expired_login_returns_to_login_view_with_one_toast() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+        state.app.logged_in = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistsOpenRecommend,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        let req_id = state
+            .request_tracker
+            .get_pending(&RequestKey::RecommendSongs)
+            .expect("pending");
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let msg = crate::core::reducer::CoreMsg::Netease(NeteaseEvent::NeedLogin { req_id });
+        crate::core::reducer::reduce(msg, &mut state, &mut effects, dir.path()).await;
+
+        assert!(
+            state
+                .request_tracker
+                .get_pending(&RequestKey::RecommendSongs)
+                .is_none()
+        );
+        assert_eq!(state.app.playlists_status, "每日推荐需要登录");
+        assert!(matches!(state.app.playlist_mode, PlaylistMode::List));
+        assert!(!state.app.logged_in);
+        assert_eq!(state.app.view, crate::app::View::Login);
+        let notices: Vec<String> = effects
+            .actions
+            .iter()
+            .filter_map(|e| match e {
+                CoreEffect::SetToast(t) => Some(t.message.clone()),
+                CoreEffect::EmitToast(m) => Some(m.clone()),
+                CoreEffect::EmitError(e) => Some(e.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(notices, ["登录已失效，请重新登录"]);
+    }
+
+    fn picker_playlists() -> Vec<Playlist> {
+        vec![
+            Playlist {
+                id: 10,
+                name: "我喜欢的音乐".to_owned(),
+                special_type: 5,
+                ..Default::default()
+            },
+            Playlist {
+                id: 11,
+                name: "收藏的".to_owned(),
+                subscribed: true,
+                ..Default::default()
+            },
+            Playlist {
+                id: 12,
+                name: "通勤".to_owned(),
+                track_count: 3,
+                ..Default::default()
+            },
+            Playlist {
+                id: 13,
+                name: "夜跑".to_owned(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn manipulate_request(
+        effects: &crate::core::effects::CoreEffects,
+    ) -> Option<(u64, crate::domain::model::PlaylistTrackOp, i64, Vec<i64>)> {
+        effects.actions.iter().find_map(|effect| match effect {
+            CoreEffect::SendNeteaseHi {
+                cmd:
+                    NeteaseCommand::PlaylistTracksManipulate {
+                        req_id,
+                        op,
+                        playlist_id,
+                        track_ids,
+                    },
+                ..
+            } => Some((*req_id, *op, *playlist_id, track_ids.clone())),
+            _ => None,
+        })
+    }
+
+    fn toasts(effects: &crate::core::effects::CoreEffects) -> Vec<String> {
+        effects
+            .actions
+            .iter()
+            .filter_map(|effect| match effect {
+                CoreEffect::EmitToast(msg) => Some(msg.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn add_search_result_to_own_playlist() {
+        use crate::domain::model::PlaylistTrackOp;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Search;
+        state.app.logged_in = true;
+        state.app.playlists = picker_playlists();
+        state.app.search_results = songs_for(&[7, 8]);
+        state.app.search_selected = 1;
+        state.app.playlist_preloads.insert(
+            13,
+            crate::app::PlaylistPreload {
+                status: crate::app::PreloadStatus::Completed,
+                songs: songs_for(&[1]),
+                missing_count: 0,
+                stats: None,
+                completed_at_ms: None,
+                added_at: std::collections::HashMap::new(),
+                track_ids_fingerprint: None,
+            },
+        );
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::SearchAddSelectedToPlaylist,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        let picker = state.app.playlist_picker.as_ref().expect("picker");
+        assert_eq!(picker.songs[0].id, 8);
+        let ids: Vec<i64> = picker.playlists.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![12, 13], "只列出自己创建的普通歌单");
+
+        handle_ui(
+            &AppCommand::PlaylistPickerMoveDown,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        handle_ui(
+            &AppCommand::PlaylistPickerMoveDown,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlaylistPickerConfirm, &mut state, &mut effects).await;
+        assert!(state.app.playlist_picker.is_none());
+        let (req_id, op, playlist_id, track_ids) =
+            manipulate_request(&effects).expect("编辑歌单请求");
+        assert_eq!(
+            (op, playlist_id, track_ids.clone()),
+            (PlaylistTrackOp::Add, 13, vec![8])
+        );
+
+        let evt = NeteaseEvent::PlaylistTracksManipulated {
+            req_id,
+            op,
+            playlist_id,
+            track_ids,
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let preload = &state.app.playlist_preloads[&13];
+        let ids: Vec<i64> = preload.songs.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![8, 1], "已完成的预加载就地更新，新歌曲在最前");
+        assert!(preload.added_at.contains_key(&8));
+        assert_eq!(state.app.playlists[3].track_count, 1);
+        assert_eq!(toasts(&effects), vec!["已添加「s8」到歌单「夜跑」"]);
+        assert!(
+            !handle_netease_event(&evt, &mut state, &mut effects).await,
+            "重复事件不再处理"
+        );
+    }
+
+    #[tokio::test]
+    async fn remove_track_from_open_playlist() {
+        use crate::domain::model::PlaylistTrackOp;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+        state.app.logged_in = true;
+        state.app.playlists = picker_playlists();
+        state.app.playlists_selected = 2;
+        state.app.playlist_mode = PlaylistMode::Tracks;
+        state.app.playlist_tracks = songs_for(&[1, 2, 3]);
+        state.app.playlist_tracks_selected = 2;
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistTracksRemoveSelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        let (req_id, op, playlist_id, track_ids) =
+            manipulate_request(&effects).expect("编辑歌单请求");
+        assert_eq!(
+            (op, playlist_id, track_ids.clone()),
+            (PlaylistTrackOp::Del, 12, vec![3])
+        );
+        assert_eq!(state.app.playlist_tracks.len(), 3, "接口返回前不改列表");
+
+        let evt = NeteaseEvent::PlaylistTracksManipulated {
+            req_id,
+            op,
+            playlist_id,
+            track_ids,
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let ids: Vec<i64> = state.app.playlist_tracks.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(state.app.playlist_tracks_selected, 1);
+        assert_eq!(state.app.playlists[2].track_count, 2);
+        assert_eq!(toasts(&effects), vec!["已从歌单「通勤」移除「s3」"]);
+
+        // 收藏的歌单不可编辑
+        state.app.playlists_selected = 1;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistTracksRemoveSelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert!(manipulate_request(&effects).is_none());
+    }
+
+    #[tokio::test]
+    async fn playlist_edit_error_shows_toast() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+        state.app.logged_in = true;
+        state.app.playlists = picker_playlists();
+        state.app.playlists_selected = 2;
+        state.app.playlist_mode = PlaylistMode::Tracks;
+        state.app.playlist_tracks = songs_for(&[1]);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistTracksRemoveSelected,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        let (req_id, ..) = manipulate_request(&effects).expect("编辑歌单请求");
+
+        let evt = NeteaseEvent::Error {
+            req_id,
+            error: crate::error::MessageError::from_netease(crate::error::NeteaseError::Api {
+                code: 401,
+                msg: "无权限".to_owned(),
+            }),
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SetToast(toast) if toast.message.starts_with("从歌单「通勤」移除失败")
+        )));
+        assert_eq!(state.app.playlist_tracks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn add_marked_search_results_in_one_request() {
+        use crate::domain::model::PlaylistTrackOp;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Search;
+        state.app.logged_in = true;
+        state.app.playlists = picker_playlists();
+        state.app.search_results = songs_for(&[5, 6, 7, 8]);
+        state.app.playlist_preloads.insert(
+            12,
+            crate::app::PlaylistPreload {
+                status: crate::app::PreloadStatus::Completed,
+                songs: songs_for(&[1, 6]),
+                missing_count: 0,
+                stats: None,
+                completed_at_ms: None,
+                added_at: std::collections::HashMap::new(),
+                track_ids_fingerprint: None,
+            },
+        );
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::SearchAddMarkedToPlaylist,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert!(state.app.playlist_picker.is_none(), "没有标记时不打开");
+
+        // 连续标记 5、6、7（标记后光标下移），回到 7 取消标记，再标记 8
+        for cmd in [
+            AppCommand::SearchToggleMark,
+            AppCommand::SearchToggleMark,
+            AppCommand::SearchToggleMark,
+            AppCommand::SearchMoveUp,
+            AppCommand::SearchToggleMark,
+            AppCommand::SearchMoveDown,
+            AppCommand::SearchToggleMark,
+        ] {
+            super::super::search::handle_ui(&cmd, &mut state, &mut effects).await;
+        }
+        let marked: Vec<i64> = state.app.search_marked.iter().map(|s| s.id).collect();
+        assert_eq!(marked, vec![5, 6, 8]);
+
+        handle_ui(
+            &AppCommand::SearchAddMarkedToPlaylist,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        // 输入「夜」后删除，再输入「勤」：只剩「通勤」
+        for cmd in [
+            AppCommand::PlaylistPickerInputChar { c: '夜' },
+            AppCommand::PlaylistPickerInputBackspace,
+            AppCommand::PlaylistPickerInputString {
+                s: "勤".to_owned()
+            },
+        ] {
+            handle_ui(&cmd, &mut state, &mut effects).await;
+        }
+        let picker = state.app.playlist_picker.as_ref().expect("picker");
+        assert_eq!(picker.songs.len(), 3);
+        let visible: Vec<i64> = picker.visible().iter().map(|p| p.id).collect();
+        assert_eq!(visible, vec![12]);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::PlaylistPickerConfirm, &mut state, &mut effects).await;
+        let (req_id, op, playlist_id, track_ids) =
+            manipulate_request(&effects).expect("编辑歌单请求");
+        assert_eq!(
+            (op, playlist_id, track_ids),
+            (PlaylistTrackOp::Add, 12, vec![5, 6, 8])
+        );
+        assert_eq!(
+            effects
+                .actions
+                .iter()
+                .filter(|e| matches!(e, CoreEffect::SendNeteaseHi { .. }))
+                .count(),
+            1,
+            "全部歌曲在一次请求中提交"
+        );
+        assert!(state.app.search_marked.is_empty(), "提交后清空标记");
+
+        // 6 已在歌单中，接口只返回 5 与 8
+        let evt = NeteaseEvent::PlaylistTracksManipulated {
+            req_id,
+            op,
+            playlist_id,
+            track_ids: vec![5, 8],
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(
+            toasts(&effects),
+            vec!["已添加 2/3 首到歌单「通勤」，1 首已存在"]
+        );
+        assert_eq!(state.app.playlists[2].track_count, 5);
+        let ids: Vec<i64> = state.app.playlist_preloads[&12]
+            .songs
+            .iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids, vec![5, 8, 1, 6]);
+    }
+
+    #[tokio::test]
+    async fn adding_only_existing_songs_reports_them_as_existing() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Search;
+        state.app.logged_in = true;
+        state.app.playlists = picker_playlists();
+        state.app.search_marked = songs_for(&[1, 2]);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::SearchAddMarkedToPlaylist,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        handle_ui(&AppCommand::PlaylistPickerConfirm, &mut state, &mut effects).await;
+        let (req_id, ..) = manipulate_request(&effects).expect("编辑歌单请求");
+
+        let evt = NeteaseEvent::Error {
+            req_id,
+            error: crate::error::MessageError::from_netease(crate::error::NeteaseError::Api {
+                code: 502,
+                msg: "歌曲已存在".to_owned(),
+            }),
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(
+            toasts(&effects),
+            vec!["已添加 0/2 首到歌单「通勤」，2 首已存在"]
+        );
+        assert_eq!(state.app.playlists[2].track_count, 3);
+    }
+
+    #[tokio::test]
+    async fn unavailable_liked_song_is_replaced_after_review() {
+        use crate::domain::model::{PlaylistTrackOp, SongAvailability, SongPreview};
+
+        fn last_lo_request(effects: &crate::core::effects::CoreEffects) -> Option<&NeteaseCommand> {
+            effects
+                .actions
+                .iter()
+                .rev()
+                .find_map(|effect| match effect {
+                    CoreEffect::SendNeteaseLo { cmd, .. } => Some(cmd),
+                    _ => None,
+                })
+        }
+        let track = |id: i64, name: &str, secs: u64| Song {
+            id,
+            name: name.to_owned(),
+            artists: "周杰伦".to_owned(),
+            duration_ms: Some(secs * 1000),
+            ..Default::default()
+        };
+        let preview = |id: i64, availability: SongAvailability| SongPreview {
+            id,
+            availability,
+            ..Default::default()
+        };
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Playlists;
+        state.app.logged_in = true;
+        state.app.playlists = picker_playlists();
+        state.app.playlist_mode = PlaylistMode::Tracks;
+        state.app.playlist_tracks = vec![track(1, "晴天", 269), track(2, "七里香", 299)];
+        state.app.liked_song_ids = [1, 2].into_iter().collect();
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlaylistTracksCheckUnavailable,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        let Some(NeteaseCommand::SongPreviews { req_id, ids }) = last_lo_request(&effects) else {
+            panic!("应先拉取歌曲详情");
+        };
+        assert_eq!(ids, &[1, 2]);
+        let evt = NeteaseEvent::SongPreviews {
+            req_id: *req_id,
+            previews: vec![
+                preview(1, SongAvailability::Unavailable),
+                preview(2, SongAvailability::Free),
+            ],
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let Some(NeteaseCommand::CloudSearchSongs {
+            req_id, keywords, ..
+        }) = last_lo_request(&effects)
+        else {
+            panic!("不可用的歌曲应按歌名与歌手搜索");
+        };
+        assert_eq!(keywords, "晴天 周杰伦");
+
+        let evt = NeteaseEvent::SearchSongs {
+            req_id: *req_id,
+            songs: vec![
+                track(1, "晴天", 269),
+                track(30, "晴天 (Live)", 280),
+                track(31, "晴天", 270),
+            ],
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert!(
+            state.app.search_results.is_empty(),
+            "检查用的搜索不影响搜索页"
+        );
+        let Some(NeteaseCommand::SongPreviews { req_id, ids }) = last_lo_request(&effects) else {
+            panic!("应确认候选可以播放");
+        };
+        assert_eq!(ids, &[31]);
+        let evt = NeteaseEvent::SongPreviews {
+            req_id: *req_id,
+            previews: vec![preview(31, SongAvailability::Free)],
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let review = state.app.unavailable_review.as_ref().expect("审阅框");
+        assert_eq!(review.items.len(), 1);
+        assert_eq!(review.items[0].candidate.id, 31);
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::UnavailableReviewAccept,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert!(state.app.unavailable_review.is_none(), "最后一条处理后关闭");
+        let (req_id, op, playlist_id, track_ids) =
+            manipulate_request(&effects).expect("添加新版本");
+        assert_eq!(
+            (op, playlist_id, track_ids.clone()),
+            (PlaylistTrackOp::Add, 10, vec![31])
+        );
+        let evt = NeteaseEvent::PlaylistTracksManipulated {
+            req_id,
+            op,
+            playlist_id,
+            track_ids,
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let (req_id, op, playlist_id, track_ids) =
+            manipulate_request(&effects).expect("移除原歌曲");
+        assert_eq!(
+            (op, playlist_id, track_ids.clone()),
+            (PlaylistTrackOp::Del, 10, vec![1])
+        );
+        assert!(toasts(&effects).is_empty(), "添加成功时不单独提示");
+
+        let evt = NeteaseEvent::PlaylistTracksManipulated {
+            req_id,
+            op,
+            playlist_id,
+            track_ids,
+        };
+        let mut effects = crate::core::effects::CoreEffects::default();
+        assert!(handle_netease_event(&evt, &mut state, &mut effects).await);
+        let ids: Vec<i64> = state.app.playlist_tracks.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![31, 2], "原位置换成新版本");
+        assert!(state.app.liked_song_ids.contains(&31));
+        assert!(!state.app.liked_song_ids.contains(&1));
+        assert_eq!(toasts(&effects), vec!["已把「晴天」替换为可播放的版本"]);
+    }
 }
@@ -13,6 +13,7 @@ pub async fn handle_ui(
         AppCommand::SearchSubmit => AppCommand::SearchSubmit,
         AppCommand::SearchInputBackspace => AppCommand::SearchInputBackspace,
         AppCommand::SearchInputChar { c } => AppCommand::SearchInputChar { c: *c },
+        AppCommand::SearchInputString { s } => AppCommand::SearchInputString { s: s.clone() },
         AppCommand::SearchMoveUp => AppCommand::SearchMoveUp,
         AppCommand::SearchMoveDown => AppCommand::SearchMoveDown,
         AppCommand::SearchMoveTo { index } => AppCommand::SearchMoveTo { index: *index },
@@ -20,16 +21,22 @@ pub async fn handle_ui(
         AppCommand::SearchPageUp => AppCommand::SearchPageUp,
         AppCommand::SearchJumpTop => AppCommand::SearchJumpTop,
         AppCommand::SearchJumpBottom => AppCommand::SearchJumpBottom,
+        AppCommand::SearchLoadMore => AppCommand::SearchLoadMore,
+        AppCommand::SearchCycleKind => AppCommand::SearchCycleKind,
+        AppCommand::SearchOpenSelected => AppCommand::SearchOpenSelected,
+        AppCommand::SearchBack => AppCommand::SearchBack,
         AppCommand::SearchPlaySelected => AppCommand::SearchPlaySelected,
+        AppCommand::SearchToggleMark => AppCommand::SearchToggleMark,
         _ => return UiAction::NotHandled,
     };
 
     search_handlers::handle_search_command(
         search_cmd,
         &mut state.app,
-        &mut state.req_id,
+        &state.req_ids,
         &mut state.request_tracker,
         &mut state.song_request_titles,
+        &mut state.next_song_cache,
         effects,
     )
     .await;
@@ -37,6 +44,21 @@ pub async fn handle_ui(
     UiAction::Handled
 }
 
+/// 预览防抖到期（由 actor 的 select 循环触发）
+pub fn handle_preview_due(song_id: i64, state: &mut CoreState, effects: &mut CoreEffects) {
+    // 到期前焦点或选中项已变化时，下一轮 observe 会重新计时
+    if search_handlers::preview::preview_target(&state.app) != Some(song_id) {
+        return;
+    }
+    search_handlers::preview::handle_preview_due(
+        song_id,
+        &mut state.app,
+        &state.req_ids,
+        &mut state.request_tracker,
+        effects,
+    );
+}
+
 pub async fn handle_netease_event(
     evt: &NeteaseEvent,
     state: &mut CoreState,
@@ -53,6 +75,57 @@ pub async fn handle_netease_event(
             )
             .await
         }
+        NeteaseEvent::SearchAlbums { req_id, albums } => {
+            search_handlers::handle_search_albums_event(
+                *req_id,
+                albums.clone(),
+                &mut state.app,
+                &mut state.request_tracker,
+                effects,
+            )
+        }
+        NeteaseEvent::AlbumTracks {
+            req_id,
+            album_id,
+            songs,
+        } => search_handlers::handle_album_tracks_event(
+            *req_id,
+            *album_id,
+            songs.clone(),
+            &mut state.app,
+            &mut state.request_tracker,
+            effects,
+        ),
+        NeteaseEvent::SearchArtists { req_id, artists } => {
+            search_handlers::handle_search_artists_event(
+                *req_id,
+                artists.clone(),
+                &mut state.app,
+                &mut state.request_tracker,
+                effects,
+            )
+        }
+        NeteaseEvent::ArtistTopSongs {
+            req_id,
+            artist_id,
+            songs,
+        } => search_handlers::handle_artist_top_songs_event(
+            *req_id,
+            *artist_id,
+            songs.clone(),
+            &mut state.app,
+            &mut state.request_tracker,
+            effects,
+        ),
+        NeteaseEvent::SongPreview { req_id, preview } => {
+            search_handlers::preview::handle_song_preview_event(
+                *req_id,
+                preview.clone(),
+                &mut state.app,
+                &mut state.request_tracker,
+                effects,
+            )
+        }
         NeteaseEvent::Error { req_id, .. } => {
             search_handlers::handle_search_error_event(
                 *req_id,
@@ -76,6 +149,26 @@ mod tests {
     use crate::messages::app::AppCommand;
     use crate::netease::actor::{NeteaseCommand, NeteaseEvent};
 
+    #[tokio::test]
+    async fn search_input_string_appends_whole_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        state.app.search_input = "周".to_owned();
+        let outcome = handle_ui(
+            &AppCommand::SearchInputString {
+                s: "杰伦 晴天".to_owned(),
+            },
+            &mut state,
+            &mut effects,
+        )
+        .await;
+
+        assert!(matches!(outcome, UiAction::Handled));
+        assert_eq!(state.app.search_input, "周杰伦 晴天");
+    }
+
     #[tokio::test]
     async fn search_submit_emits_request() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -116,6 +209,7 @@ mod tests {
                 name: "old".to_owned(),
                 artists: "a".to_owned(),
                 duration_ms: None,
+                ..Default::default()
             }],
         };
         let handled_stale = super::handle_netease_event(&stale_evt, &mut state, &mut effects).await;
@@ -130,6 +224,7 @@ mod tests {
                 name: "new".to_owned(),
                 artists: "b".to_owned(),
                 duration_ms: None,
+                ..Default::default()
             }],
         };
         let handled_fresh = super::handle_netease_event(&fresh_evt, &mut state, &mut effects).await;
@@ -139,4 +234,410 @@ mod tests {
         assert_eq!(state.app.search_results[0].id, 2);
         assert_eq!(state.app.search_status, "结果: 1 首");
     }
+
+    #[tokio::test]
+    async fn typing_shares_queue_snapshot_without_changing_contents() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let songs: Vec<Song> = (1..=3)
+            .map(|id| Song {
+                id,
+                name: format!("song-{id}"),
+                artists: "a".to_owned(),
+                duration_ms: None,
+                ..Default::default()
+            })
+            .collect();
+        state.app.play_queue.set_songs(songs, Some(1));
+
+        let mut snapshots = Vec::new();
+        for cmd in [
+            AppCommand::SearchInputChar { c: 'a' },
+            AppCommand::SearchInputChar { c: 'b' },
+            AppCommand::SearchInputBackspace,
+        ] {
+            let mut effects = crate::core::effects::CoreEffects::default();
+            let _ = handle_ui(&cmd, &mut state, &mut effects).await;
+            snapshots.extend(
+                effects
+                    .actions
+                    .into_iter()
+                    .filter_map(|effect| match effect {
+                        CoreEffect::EmitState(snapshot) => Some(snapshot),
+                        _ => None,
+                    }),
+            );
+        }
+
+        assert_eq!(snapshots.len(), 3);
+        assert_eq!(snapshots[1].search_input, "ab");
+        assert_eq!(snapshots[2].search_input, "a");
+        let ids: Vec<i64> = snapshots[2].queue.iter().map(|s| s.id).collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(snapshots[2].queue_pos, Some(1));
+        assert!(
+            snapshots
+                .windows(2)
+                .all(|w| std::sync::Arc::ptr_eq(&w[0].queue, &w[1].queue)),
+            "输入不改动队列时快照应共享同一份队列"
+        );
+    }
+
+    #[tokio::test]
+    async fn preview_is_fetched_once_per_song_and_cached_for_the_session() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.view = crate::app::View::Search;
+        state.app.ui_focus = crate::app::UiFocus::BodyCenter;
+        state.app.search_results = (1..=2)
+            .map(|id| Song {
+                id,
+                name: format!("song-{id}"),
+                ..Default::default()
+            })
+            .collect();
+
+        let preview_requests = |effects: &crate::core::effects::CoreEffects| {
+            effects
+                .actions
+                .iter()
+                .filter_map(|e| match e {
+                    CoreEffect::SendNeteaseHi {
+                        cmd: NeteaseCommand::SongPreview { req_id, id },
+                        ..
+                    } => Some((*req_id, *id)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        super::handle_preview_due(1, &mut state, &mut effects);
+        let requests = preview_requests(&effects);
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].1, 1);
+        assert_eq!(state.app.search_preview_cached, Some((1, false)));
+
+        let evt = NeteaseEvent::SongPreview {
+            req_id: requests[0].0,
+            preview: crate::domain::model::SongPreview {
+                id: 1,
+                album: "叶惠美".to_owned(),
+                publish_year: Some(2003),
+                ..Default::default()
+            },
+        };
+        assert!(super::handle_netease_event(&evt, &mut state, &mut effects).await);
+        let snapshot = crate::app::AppSnapshot::from_app(&state.app);
+        let crate::app::AppViewSnapshot::Search(search) = &snapshot.view_state else {
+            panic!("应为搜索页快照");
+        };
+        assert_eq!(
+            search.preview.as_ref().map(|p| p.album.as_str()),
+            Some("叶惠美")
+        );
+
+        // 移到第二首再回来：第一首命中缓存，不再请求
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.search_selected = 1;
+        super::handle_preview_due(2, &mut state, &mut effects);
+        state.app.search_selected = 0;
+        super::handle_preview_due(1, &mut state, &mut effects);
+        let ids: Vec<i64> = preview_requests(&effects).iter().map(|r| r.1).collect();
+        assert_eq!(ids, vec![2]);
+
+        // 焦点已离开结果列表：到期也不请求
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.ui_focus = crate::app::UiFocus::HeaderSearch;
+        state.app.search_selected = 1;
+        super::handle_preview_due(2, &mut state, &mut effects);
+        assert!(effects.actions.is_empty());
+    }
+
+    fn songs(ids: std::ops::RangeInclusive<i64>) -> Vec<Song> {
+        ids.map(|id| Song {
+            id,
+            name: format!("song-{id}"),
+            ..Default::default()
+        })
+        .collect()
+    }
+
+    /// 最近一次搜索请求的 (req_id, keywords, offset)
+    fn last_search(effects: &crate::core::effects::CoreEffects) -> Option<(u64, String, i64)> {
+        effects
+            .actions
+            .iter()
+            .rev()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd:
+                        NeteaseCommand::CloudSearchSongs {
+                            req_id,
+                            keywords,
+                            offset,
+                            ..
+                        },
+                    ..
+                } => Some((*req_id, keywords.clone(), *offset)),
+                _ => None,
+            })
+    }
+
+    #[tokio::test]
+    async fn load_more_appends_next_page_and_keeps_selection() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        state.app.search_input = "jay".to_owned();
+        handle_ui(&AppCommand::SearchSubmit, &mut state, &mut effects).await;
+        let (req_id, _, _) = last_search(&effects).expect("search request");
+        let evt = NeteaseEvent::SearchSongs {
+            req_id,
+            songs: songs(1..=30),
+        };
+        assert!(super::handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.search_status, "结果: 30 首（n 加载更多）");
+
+        // 翻页沿用已提交的关键词，不受输入框修改影响
+        state.app.search_input = "edited".to_owned();
+        state.app.search_selected = 12;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::SearchLoadMore, &mut state, &mut effects).await;
+        let (req_id, keywords, offset) = last_search(&effects).expect("load more request");
+        assert_eq!((keywords.as_str(), offset), ("jay", 30));
+
+        // 请求未返回时重复按键不重复请求
+        let mut again = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::SearchLoadMore, &mut state, &mut again).await;
+        assert!(last_search(&again).is_none());
+
+        let evt = NeteaseEvent::SearchSongs {
+            req_id,
+            songs: songs(31..=40),
+        };
+        assert!(super::handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.search_results.len(), 40);
+        assert_eq!(state.app.search_results[30].id, 31);
+        assert_eq!(state.app.search_selected, 12);
+        assert_eq!(state.app.search_status, "结果: 40 首（已加载更多）");
+
+        // 不足一页：不再请求
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::SearchLoadMore, &mut state, &mut effects).await;
+        assert!(last_search(&effects).is_none());
+        assert_eq!(state.app.search_status, "结果: 40 首（没有更多了）");
+    }
+
+    #[tokio::test]
+    async fn new_search_drops_pending_load_more() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        state.app.search_input = "first".to_owned();
+        handle_ui(&AppCommand::SearchSubmit, &mut state, &mut effects).await;
+        let (req_id, _, _) = last_search(&effects).unwrap();
+        let evt = NeteaseEvent::SearchSongs {
+            req_id,
+            songs: songs(1..=30),
+        };
+        super::handle_netease_event(&evt, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::SearchLoadMore, &mut state, &mut effects).await;
+        let (more_req_id, _, _) = last_search(&effects).unwrap();
+
+        state.app.search_input = "second".to_owned();
+        handle_ui(&AppCommand::SearchSubmit, &mut state, &mut effects).await;
+        let late = NeteaseEvent::SearchSongs {
+            req_id: more_req_id,
+            songs: songs(31..=60),
+        };
+        assert!(!super::handle_netease_event(&late, &mut state, &mut effects).await);
+        assert!(state.app.search_results.is_empty());
+        assert!(!state.app.search_has_more);
+    }
+
+    #[tokio::test]
+    async fn cycle_kind_researches_last_query_as_albums() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        state.app.search_input = "jay".to_owned();
+        handle_ui(&AppCommand::SearchSubmit, &mut state, &mut effects).await;
+        let (req_id, _, _) = last_search(&effects).unwrap();
+        let evt = NeteaseEvent::SearchSongs {
+            req_id,
+            songs: songs(1..=3),
+        };
+        super::handle_netease_event(&evt, &mut state, &mut effects).await;
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::SearchCycleKind, &mut state, &mut effects).await;
+        assert_eq!(state.app.search_kind, crate::app::SearchKind::Albums);
+        assert!(state.app.search_results.is_empty());
+        assert!(effects.actions.iter().any(|effect| matches!(
+            effect,
+            CoreEffect::SendNeteaseHi {
+                cmd: NeteaseCommand::CloudSearchAlbums { keywords, offset: 0, .. },
+                ..
+            } if keywords == "jay"
+        )));
+        // 旧的歌曲响应不再被接受
+        assert!(!super::handle_netease_event(&evt, &mut state, &mut effects).await);
+    }
+
+    #[tokio::test]
+    async fn open_album_and_play_queues_whole_album() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        state.app.search_kind = crate::app::SearchKind::Albums;
+        state.app.search_input = "叶惠美".to_owned();
+        handle_ui(&AppCommand::SearchSubmit, &mut state, &mut effects).await;
+        let req_id = effects
+            .actions
+            .iter()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::CloudSearchAlbums { req_id, .. },
+                    ..
+                } => Some(*req_id),
+                _ => None,
+            })
+            .expect("album search request");
+        let albums = (1..=2)
+            .map(|id| crate::domain::model::Album {
+                id: 100 + id,
+                name: format!("album-{id}"),
+                artists: "周杰伦".to_owned(),
+                track_count: 3,
+            })
+            .collect();
+        let evt = NeteaseEvent::SearchAlbums { req_id, albums };
+        assert!(super::handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.search_status, "结果: 2 个专辑");
+
+        state.app.search_selected = 1;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::SearchOpenSelected, &mut state, &mut effects).await;
+        let req_id = effects
+            .actions
+            .iter()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd:
+                        NeteaseCommand::AlbumTracks {
+                            req_id,
+                            album_id: 102,
+                        },
+                    ..
+                } => Some(*req_id),
+                _ => None,
+            })
+            .expect("album tracks request");
+        let evt = NeteaseEvent::AlbumTracks {
+            req_id,
+            album_id: 102,
+            songs: songs(1..=3),
+        };
+        assert!(super::handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(
+            state.app.search_status,
+            "专辑: album-2，3 首（p 播放 b 返回）"
+        );
+        assert_eq!(state.app.search_list_len(), 3);
+
+        state.app.search_selected = 1;
+        handle_ui(&AppCommand::SearchPlaySelected, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.origin(), Some("album:102"));
+        assert_eq!(state.app.play_queue.songs().len(), 3);
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(2));
+
+        // 返回专辑列表并恢复选中项
+        handle_ui(&AppCommand::SearchBack, &mut state, &mut effects).await;
+        assert!(state.app.search_showing_albums());
+        assert_eq!(state.app.search_selected, 1);
+        assert_eq!(state.app.search_status, "结果: 2 个专辑");
+    }
+
+    #[tokio::test]
+    async fn open_artist_and_play_queues_top_songs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        // 歌曲 → 专辑 → 歌手
+        state.app.search_input = "周杰伦".to_owned();
+        handle_ui(&AppCommand::SearchCycleKind, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::SearchCycleKind, &mut state, &mut effects).await;
+        assert_eq!(state.app.search_kind, crate::app::SearchKind::Artists);
+        handle_ui(&AppCommand::SearchSubmit, &mut state, &mut effects).await;
+        let req_id = effects
+            .actions
+            .iter()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd: NeteaseCommand::CloudSearchArtists { req_id, .. },
+                    ..
+                } => Some(*req_id),
+                _ => None,
+            })
+            .expect("artist search request");
+        let artists = vec![crate::domain::model::Artist {
+            id: 6452,
+            name: "周杰伦".to_owned(),
+            music_size: 500,
+            album_size: 40,
+        }];
+        let evt = NeteaseEvent::SearchArtists { req_id, artists };
+        assert!(super::handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(state.app.search_status, "结果: 1 位歌手");
+        assert!(state.app.search_showing_artists());
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::SearchPlaySelected, &mut state, &mut effects).await;
+        let req_id = effects
+            .actions
+            .iter()
+            .find_map(|effect| match effect {
+                CoreEffect::SendNeteaseHi {
+                    cmd:
+                        NeteaseCommand::ArtistTopSongs {
+                            req_id,
+                            artist_id: 6452,
+                        },
+                    ..
+                } => Some(*req_id),
+                _ => None,
+            })
+            .expect("artist top songs request");
+        let evt = NeteaseEvent::ArtistTopSongs {
+            req_id,
+            artist_id: 6452,
+            songs: songs(1..=4),
+        };
+        assert!(super::handle_netease_event(&evt, &mut state, &mut effects).await);
+        assert_eq!(
+            state.app.search_status,
+            "歌手: 周杰伦，热门 4 首（p 播放 b 返回）"
+        );
+
+        // 打开后不再翻页
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&AppCommand::SearchLoadMore, &mut state, &mut effects).await;
+        assert!(effects.actions.is_empty());
+
+        state.app.search_selected = 2;
+        handle_ui(&AppCommand::SearchPlaySelected, &mut state, &mut effects).await;
+        assert_eq!(state.app.play_queue.origin(), Some("artist:6452"));
+        assert_eq!(state.app.play_queue.songs().len(), 4);
+        assert_eq!(state.app.play_queue.current().map(|s| s.id), Some(3));
+
+        handle_ui(&AppCommand::SearchBack, &mut state, &mut effects).await;
+        assert!(state.app.search_showing_artists());
+        assert_eq!(state.app.search_status, "结果: 1 位歌手");
+    }
 }
@@ -1,11 +1,13 @@
 use super::{CoreState, UiAction};
-use crate::app::{UiFocus, View, tab_configs};
+use crate::app::{Toast, View, tab_configs};
 use crate::audio_worker::AudioCommand;
 use crate::core::effects::CoreEffects;
-use crate::core::utils;
+use crate::features::export::{self as export_handlers, ExportScope};
 use crate::features::logout;
+use crate::features::player::cache_status;
 use crate::features::playlists;
 use crate::features::settings as settings_handlers;
+use crate::features::settings::registry::ActionOutcome;
 use crate::messages::app::AppCommand;
 use crate::netease::actor::{NeteaseCommand, NeteaseEvent};
 
@@ -20,7 +22,7 @@ pub async fn handle_ui(
         AppCommand::Bootstrap => {
             state.app.login_status = "初始化中...".to_owned();
             effects.emit_state(&state.app);
-            let id = utils::next_id(&mut state.req_id);
+            let id = state.req_ids.next();
             effects.send_netease_hi_warn(
                 NeteaseCommand::Init { req_id: id },
                 "NeteaseActor 通道已关闭：Init 发送失败",
@@ -34,31 +36,41 @@ pub async fn handle_ui(
                 .position(|c| c.view == state.app.view)
                 .unwrap_or(0);
             let next_view = configs[(current_idx + 1) % configs.len()].view;
-            state.app.view = next_view;
-            state.app.ui_focus = if matches!(next_view, View::Search) {
-                UiFocus::HeaderSearch
-            } else {
-                UiFocus::BodyCenter
-            };
+            if next_view == View::Settings && state.app.view != View::Settings {
+                cache_status::query_cache_stats(effects);
+            }
+            state.app.switch_view(next_view);
             effects.emit_state(&state.app);
             return UiAction::Handled;
         }
         AppCommand::TabTo { index } => {
             if let Some(&cfg) = tab_configs(state.app.logged_in).get(*index) {
-                state.app.view = cfg.view;
-                state.app.ui_focus = if matches!(cfg.view, View::Search) {
-                    UiFocus::HeaderSearch
-                } else {
-                    UiFocus::BodyCenter
-                };
+                if cfg.view == View::Settings && state.app.view != View::Settings {
+                    cache_status::query_cache_stats(effects);
+                }
+                state.app.switch_view(cfg.view);
                 effects.emit_state(&state.app);
             }
             return UiAction::Handled;
         }
-        AppCommand::PlayerVolumeDown | AppCommand::PlayerVolumeUp | AppCommand::PlayerCycleMode => {
+        AppCommand::PlayerVolumeDown
+        | AppCommand::PlayerVolumeUp
+        | AppCommand::PlayerToggleMute
+        | AppCommand::PlayerSetVolume { .. }
+        | AppCommand::PlayerSpeedDown
+        | AppCommand::PlayerSpeedUp
+        | AppCommand::PlayerSpeedReset
+        | AppCommand::PlayerCycleMode => {
             let player_cmd = match cmd {
                 AppCommand::PlayerVolumeDown => AppCommand::PlayerVolumeDown,
                 AppCommand::PlayerVolumeUp => AppCommand::PlayerVolumeUp,
+                AppCommand::PlayerToggleMute => AppCommand::PlayerToggleMute,
+                AppCommand::PlayerSpeedDown => AppCommand::PlayerSpeedDown,
+                AppCommand::PlayerSpeedUp => AppCommand::PlayerSpeedUp,
+                AppCommand::PlayerSpeedReset => AppCommand::PlayerSpeedReset,
+                AppCommand::PlayerSetVolume { volume } => {
+                    AppCommand::PlayerSetVolume { volume: *volume }
+                }
                 AppCommand::PlayerCycleMode => AppCommand::PlayerCycleMode,
                 _ => unreachable!("checked by outer match"),
             };
@@ -99,34 +111,55 @@ pub async fn handle_ui(
             .await;
             return UiAction::Handled;
         }
+        AppCommand::UiPaneNudge { delta } => {
+            settings_handlers::nudge_pane_split(
+                &mut state.app,
+                &mut state.settings,
+                data_dir,
+                effects,
+                *delta,
+            );
+            return UiAction::Handled;
+        }
         AppCommand::SettingsActivate => {
-            match settings_handlers::handle_settings_activate_command(&mut state.app, effects).await
-            {
-                Some(true) => return UiAction::Handled,
-                Some(false) => {}
-                None => {}
-            }
-
-            if !state.app.logged_in {
-                state.app.settings_status = "未登录，无需退出".to_owned();
-                effects.emit_state(&state.app);
-                return UiAction::Handled;
+            let outcome = settings_handlers::handle_settings_activate_command(
+                &mut state.app,
+                &mut state.settings,
+                data_dir,
+                effects,
+                &mut state.next_song_cache,
+            )
+            .await;
+            match outcome {
+                ActionOutcome::Done => return UiAction::Handled,
+                ActionOutcome::ExportPlaylists => {
+                    super::export::start(ExportScope::AllPlaylists, state, effects, data_dir);
+                    return UiAction::Handled;
+                }
+                ActionOutcome::Logout => {}
             }
 
             tracing::info!("用户触发：退出登录");
             effects.send_audio_warn(AudioCommand::Stop, "AudioWorker 通道已关闭：Stop 发送失败");
-            let id = utils::next_id(&mut state.req_id);
+            let id = state.req_ids.next();
             effects.send_netease_hi_warn(
                 NeteaseCommand::LogoutLocal { req_id: id },
                 "NeteaseActor 通道已关闭：LogoutLocal 发送失败",
             );
 
+            if let Some(task_id) =
+                export_handlers::cancel_export(&mut state.request_tracker, &mut state.export)
+            {
+                state.app.tasks.cancel(task_id);
+            }
             state.request_tracker.reset_all();
             state.playlist_tracks_loader = None;
+            state.unavailable_check = None;
+            state.pending_swaps.clear();
             state.song_request_titles.clear();
 
             state.preload_mgr.reset(&mut state.app);
-            state.next_song_cache.reset();
+            state.next_song_cache.reset(effects);
             logout::reset_app_after_logout(&mut state.app);
             state.app.login_status = "已退出登录（已清理本地cookie），按 l 重新登录".to_owned();
             effects.emit_state(&state.app);
@@ -165,6 +198,7 @@ pub async fn handle_netease_event(
                 View::Search => state.app.search_status = format!("错误: {error}"),
                 View::Lyrics => state.app.lyrics_status = format!("错误: {error}"),
                 View::Settings => state.app.settings_status = format!("错误: {error}"),
+                View::Queue => effects.set_toast(Toast::error(format!("错误: {error}"))),
             }
             effects.emit_state(&state.app);
             true
@@ -184,7 +218,7 @@ pub async fn handle_netease_event(
 #[cfg(test)]
 mod tests {
     use super::handle_ui;
-    use crate::app::View;
+    use crate::app::{UiFocus, View};
     use crate::audio_worker::AudioCommand;
     use crate::core::effects::CoreEffect;
     use crate::core::reducer::{CoreState, UiAction};
@@ -220,4 +254,473 @@ mod tests {
             )
         }));
     }
+
+    #[tokio::test]
+    async fn entering_settings_queries_cache_usage() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.logged_in = true;
+        state.app.view = View::Playlists;
+        let queries = |effects: &crate::core::effects::CoreEffects| {
+            effects
+                .actions
+                .iter()
+                .filter(|effect| {
+                    matches!(
+                        effect,
+                        CoreEffect::SendAudio {
+                            cmd: AudioCommand::QueryCacheStats,
+                            ..
+                        }
+                    )
+                })
+                .count()
+        };
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let tab = AppCommand::TabTo { index: 3 };
+        handle_ui(&tab, &mut state, &mut effects, dir.path()).await;
+        assert_eq!(state.app.view, View::Settings);
+        assert_eq!(queries(&effects), 1);
+
+        // 已在设置页时不重复查询
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(&tab, &mut state, &mut effects, dir.path()).await;
+        assert_eq!(queries(&effects), 0);
+    }
+
+    #[tokio::test]
+    async fn settings_adjust_seek_steps_in_playback_group() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        state.app.view = View::Settings;
+        state.app.settings_group_selected = 0;
+        state.app.settings_selected = 8; // Seek 步长
+        handle_ui(
+            &AppCommand::SettingsIncrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.seek_step_small_ms, 6_000);
+
+        state.app.settings_selected = 9; // 长 Seek 步长
+        handle_ui(
+            &AppCommand::SettingsDecrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.seek_step_large_ms, 25_000);
+        assert_eq!(state.settings.seek_step_large_ms, 25_000, "应同步到设置");
+
+        let saved = crate::settings::load_settings(dir.path());
+        assert_eq!(saved.seek_step_small_ms, 6_000);
+    }
+
+    #[tokio::test]
+    async fn set_volume_clamps_and_persists() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        handle_ui(
+            &AppCommand::PlayerSetVolume { volume: 0.35 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.volume, 0.35);
+        assert_eq!(crate::settings::load_settings(dir.path()).volume, 0.35);
+
+        handle_ui(
+            &AppCommand::PlayerSetVolume { volume: 5.0 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.volume, 2.0);
+    }
+
+    #[tokio::test]
+    async fn mute_keeps_saved_volume_and_volume_keys_unmute() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.volume = 0.8;
+
+        let set_volumes = |effects: &crate::core::effects::CoreEffects| -> Vec<f32> {
+            effects
+                .actions
+                .iter()
+                .filter_map(|e| match e {
+                    CoreEffect::SendAudio {
+                        cmd: AudioCommand::SetVolume(v),
+                        ..
+                    } => Some(*v),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlayerToggleMute,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(state.app.muted);
+        assert_eq!(set_volumes(&effects), vec![0.0]);
+        assert_eq!(state.app.volume, 0.8, "静音不改动音量设置");
+
+        // 静音期间保存其它设置时，写入的仍是静音前的音量
+        handle_ui(
+            &AppCommand::PlayerCycleMode,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(crate::settings::load_settings(dir.path()).volume, 0.8);
+
+        // 调整音量即取消静音，按设置的步长变化
+        state.app.volume_step = 0.02;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlayerVolumeDown,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(!state.app.muted);
+        assert_eq!(state.app.volume, 0.78);
+        assert_eq!(set_volumes(&effects), vec![0.78]);
+
+        state.app.muted = true;
+        let mut effects = crate::core::effects::CoreEffects::default();
+        handle_ui(
+            &AppCommand::PlayerToggleMute,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(!state.app.muted);
+        assert_eq!(set_volumes(&effects), vec![0.78]);
+        assert_eq!(state.app.play_status, "已取消静音，音量 78%");
+    }
+
+    #[tokio::test]
+    async fn volume_normalization_toggle_reaches_audio_and_persists() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        state.app.view = View::Settings;
+        state.app.settings_group_selected = 0;
+        state.app.settings_selected = 3; // 音量均衡
+        handle_ui(
+            &AppCommand::SettingsIncrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(state.app.normalize_volume);
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendAudio {
+                cmd: AudioCommand::SetVolumeNormalization(true),
+                ..
+            }
+        )));
+        assert!(crate::settings::load_settings(dir.path()).normalize_volume);
+    }
+
+    #[tokio::test]
+    async fn speed_keys_and_equalizer_persist_and_reach_audio() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+
+        handle_ui(
+            &AppCommand::PlayerSpeedUp,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.play_speed, 1.05);
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendAudio {
+                cmd: AudioCommand::SetPlaybackSpeed(s),
+                ..
+            } if *s == 1.05
+        )));
+        assert_eq!(
+            crate::settings::load_settings(dir.path()).playback_speed,
+            1.05
+        );
+
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.view = View::Settings;
+        state.app.settings_group_selected = 0;
+        state.app.settings_selected = 6; // 低音
+        handle_ui(
+            &AppCommand::SettingsIncrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.eq_bass_db, 1);
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            CoreEffect::SendAudio {
+                cmd: AudioCommand::SetEqualizer {
+                    bass_db: 1,
+                    treble_db: 0
+                },
+                ..
+            }
+        )));
+        assert_eq!(crate::settings::load_settings(dir.path()).eq_bass_db, 1);
+    }
+
+    #[tokio::test]
+    async fn unchanged_settings_are_not_rewritten() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let path = crate::settings::settings_path(dir.path());
+
+        state.app.volume = 1.97;
+        handle_ui(
+            &AppCommand::PlayerVolumeUp,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(path.exists());
+        std::fs::remove_file(&path).unwrap();
+
+        // 音量已到上限，设置未变化时不应写盘
+        handle_ui(
+            &AppCommand::PlayerVolumeUp,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.volume, 2.0);
+        assert!(!path.exists());
+
+        // 账号分组没有可调整的项
+        state.app.view = View::Settings;
+        state.app.settings_group_selected = 4;
+        handle_ui(
+            &AppCommand::SettingsIncrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn privacy_group_toggles_focus_mode_without_persisting() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let path = crate::settings::settings_path(dir.path());
+
+        state.app.view = View::Settings;
+        state.app.settings_group_selected = 5; // 隐私分组
+        state.app.settings_selected = 0;
+        handle_ui(
+            &AppCommand::SettingsIncrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(state.app.focus_mode);
+        assert_eq!(
+            state.app.settings_status,
+            "专注模式: 开启（仅本次运行有效）"
+        );
+
+        state.app.settings_selected = 1;
+        handle_ui(
+            &AppCommand::SettingsIncrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert!(state.app.focus_mask_titles);
+        assert!(!path.exists(), "专注模式不写入设置");
+    }
+
+    #[tokio::test]
+    async fn song_name_mode_rerenders_now_playing_without_refetch() {
+        use crate::app::{Song, SongNameMode};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        let song = Song {
+            id: 7,
+            name: "밤편지".to_owned(),
+            artists: "IU".to_owned(),
+            translated: Some("夜信".to_owned()),
+            ..Default::default()
+        };
+        state.app.play_queue.set_songs(vec![song], Some(0));
+        state.app.play_song_id = Some(7);
+        state.app.now_playing = Some("밤편지 - IU".to_owned());
+
+        state.app.view = View::Settings;
+        state.app.settings_group_selected = 6; // 显示分组
+        state.app.settings_selected = 0;
+        handle_ui(
+            &AppCommand::SettingsIncrease,
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.song_name_mode, SongNameMode::TranslatedFirst);
+        assert_eq!(state.app.now_playing.as_deref(), Some("夜信 - IU"));
+        assert!(effects.actions.iter().all(|e| !matches!(
+            e,
+            crate::core::effects::CoreEffect::SendNeteaseHi { .. }
+                | crate::core::effects::CoreEffect::SendNeteaseLo { .. }
+        )));
+    }
+
+    #[tokio::test]
+    async fn every_persisted_item_round_trips_through_settings_file() {
+        use crate::features::settings::registry::{self, SettingKind};
+
+        for (g, group) in registry::groups().iter().enumerate() {
+            for (i, item) in group.items.iter().enumerate() {
+                if matches!(item.kind, SettingKind::Action { .. }) || !item.persisted {
+                    continue;
+                }
+                let dir = tempfile::tempdir().expect("tempdir");
+                let mut state = CoreState::new(dir.path());
+                let mut effects = crate::core::effects::CoreEffects::default();
+                state.app.view = View::Settings;
+                state.app.settings_group_selected = g;
+                state.app.settings_selected = i;
+                let initial = (item.label)(&state.app);
+
+                // 已在上限时改为向下调整
+                for cmd in [AppCommand::SettingsIncrease, AppCommand::SettingsDecrease] {
+                    handle_ui(&cmd, &mut state, &mut effects, dir.path()).await;
+                    if (item.label)(&state.app) != initial {
+                        break;
+                    }
+                }
+                let adjusted = (item.label)(&state.app);
+                assert_ne!(adjusted, initial, "{} 应可调整", group.title);
+
+                let mut reloaded = crate::app::App::default();
+                crate::features::settings::apply_settings_to_app(
+                    &mut reloaded,
+                    &crate::settings::load_settings(dir.path()),
+                );
+                assert_eq!(
+                    (item.label)(&reloaded),
+                    adjusted,
+                    "{} 应持久化",
+                    group.title
+                );
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn focus_is_remembered_per_view() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.logged_in = true;
+        state.app.view = View::Playlists;
+
+        // 歌单页聚焦左栏后切到搜索页（首次进入为默认的搜索框）
+        state.app.ui_focus = UiFocus::BodyLeft;
+        handle_ui(
+            &AppCommand::TabTo { index: 1 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.view, View::Search);
+        assert_eq!(state.app.ui_focus, UiFocus::HeaderSearch);
+
+        state.app.ui_focus = UiFocus::BodyCenter;
+
+        handle_ui(
+            &AppCommand::TabTo { index: 0 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.ui_focus, UiFocus::BodyLeft, "切回歌单页恢复左栏");
+        handle_ui(
+            &AppCommand::TabTo { index: 1 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(
+            state.app.ui_focus,
+            UiFocus::BodyCenter,
+            "切回搜索页恢复中栏"
+        );
+    }
+
+    #[tokio::test]
+    async fn remembered_right_focus_falls_back_when_the_pane_is_hidden() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = crate::core::effects::CoreEffects::default();
+        state.app.logged_in = true;
+        state.app.view = View::Lyrics;
+        state.app.ui_focus = UiFocus::BodyRight;
+
+        handle_ui(
+            &AppCommand::TabTo { index: 0 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        state.app.right_pane_visible = false;
+        handle_ui(
+            &AppCommand::TabTo { index: 2 },
+            &mut state,
+            &mut effects,
+            dir.path(),
+        )
+        .await;
+        assert_eq!(state.app.view, View::Lyrics);
+        assert_eq!(state.app.ui_focus, UiFocus::BodyCenter);
+    }
 }
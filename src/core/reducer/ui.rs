@@ -1,5 +1,9 @@
 use super::{CoreState, UiAction};
-use crate::app::{Toast, UiFocus, View, default_menu_items};
+use crate::app::{
+    App, QUIT_WAIT_TIMEOUT, QuitConfirm, TaskCancel, Toast, UiFocus, View, default_focus,
+    default_menu_items,
+};
+use crate::audio_worker::AudioCommand;
 use crate::core::effects::CoreEffects;
 use crate::messages::app::AppCommand;
 
@@ -13,7 +17,7 @@ pub async fn handle_ui(
             state.app.ui_focus = if focus_locked_to_login_center(&state.app) {
                 UiFocus::BodyCenter
             } else {
-                next_focus(state.app.ui_focus)
+                next_focus(&state.app)
             };
             effects.emit_state(&state.app);
             UiAction::Handled
@@ -22,7 +26,7 @@ pub async fn handle_ui(
             state.app.ui_focus = if focus_locked_to_login_center(&state.app) {
                 UiFocus::BodyCenter
             } else {
-                prev_focus(state.app.ui_focus)
+                prev_focus(&state.app)
             };
             effects.emit_state(&state.app);
             UiAction::Handled
@@ -31,16 +35,82 @@ pub async fn handle_ui(
             state.app.ui_focus = if focus_locked_to_login_center(&state.app) {
                 UiFocus::BodyCenter
             } else {
-                *focus
+                settable_focus(&state.app, *focus)
             };
             effects.emit_state(&state.app);
             UiAction::Handled
         }
+        AppCommand::UiResized { width } => {
+            let visible = crate::app::pane::right_pane_visible(*width);
+            if visible != state.app.right_pane_visible {
+                state.app.right_pane_visible = visible;
+                state.app.ui_focus = visible_focus(state.app.ui_focus, visible);
+                effects.emit_state(&state.app);
+            }
+            UiAction::Handled
+        }
         AppCommand::UiToggleHelp => {
             state.app.help_visible = !state.app.help_visible;
             effects.emit_state(&state.app);
             UiAction::Handled
         }
+        AppCommand::UiToggleFocusMode => {
+            state.app.toggle_focus_mode();
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::RestorePromptAccept => {
+            state.app.restore_prompt = None;
+            if let Some(snapshot) = state.pending_restore.take() {
+                crate::player_state::restore_queue_and_progress(&snapshot, &mut state.app);
+                tracing::info!(
+                    play_song_id = ?state.app.play_song_id,
+                    play_queue_len = state.app.play_queue.songs().len(),
+                    "🎵 [StateRestore] 用户选择恢复旧播放队列"
+                );
+                effects.set_toast(Toast::info("已恢复上次的播放队列"));
+                super::player::resume_restored_track(state, effects).await;
+            }
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::RestorePromptDismiss => {
+            state.app.restore_prompt = None;
+            if state.pending_restore.take().is_some() {
+                tracing::info!("🎵 [StateRestore] 用户忽略旧播放队列");
+            }
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::QuitConfirmOpen => {
+            let downloads = state.app.tasks.quit_blockers();
+            if downloads == 0 {
+                // UI 快照落后：下载已结束，直接让 UI 退出
+                state.app.exit_requested = true;
+            } else {
+                state.app.quit_confirm = Some(QuitConfirm {
+                    downloads,
+                    wait_deadline: None,
+                });
+            }
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::QuitConfirmWait => {
+            if let Some(confirm) = state.app.quit_confirm.as_mut() {
+                confirm.wait_deadline = Some(std::time::Instant::now() + QUIT_WAIT_TIMEOUT);
+                tracing::info!(downloads = confirm.downloads, "等待下载完成后退出");
+                finish_quit_wait(&mut state.app);
+                effects.emit_state(&state.app);
+            }
+            UiAction::Handled
+        }
+        AppCommand::QuitConfirmCancel => {
+            if state.app.quit_confirm.take().is_some() {
+                effects.emit_state(&state.app);
+            }
+            UiAction::Handled
+        }
         AppCommand::ToastDismiss => {
             state.app.toast = None;
             effects.emit_state(&state.app);
@@ -87,32 +157,194 @@ pub async fn handle_ui(
             }
             UiAction::Handled
         }
+        AppCommand::TasksToggle => {
+            state.app.tasks_visible = !state.app.tasks_visible;
+            state.app.tasks.prune(std::time::Instant::now());
+            clamp_tasks_selected(&mut state.app);
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::TasksMoveUp => {
+            if state.app.tasks_visible && state.app.tasks_selected > 0 {
+                state.app.tasks_selected -= 1;
+                effects.emit_state(&state.app);
+            }
+            UiAction::Handled
+        }
+        AppCommand::TasksMoveDown => {
+            if state.app.tasks_visible {
+                let max_idx = state.app.tasks.tasks().len().saturating_sub(1);
+                if state.app.tasks_selected < max_idx {
+                    state.app.tasks_selected += 1;
+                    effects.emit_state(&state.app);
+                }
+            }
+            UiAction::Handled
+        }
+        AppCommand::TasksCancelSelected => {
+            if !state.app.tasks_visible {
+                return UiAction::Handled;
+            }
+            let Some(task) = state
+                .app
+                .tasks
+                .tasks()
+                .get(state.app.tasks_selected)
+                .filter(|t| t.is_running())
+                .cloned()
+            else {
+                return UiAction::Handled;
+            };
+            match task.kind.cancel_action() {
+                TaskCancel::Preload { playlist_id } => {
+                    state
+                        .preload_mgr
+                        .cancel_playlist(&mut state.app, playlist_id);
+                }
+                TaskCancel::StopAudio => {
+                    effects.send_audio_warn(
+                        AudioCommand::Stop,
+                        "AudioWorker 通道已关闭：Stop 发送失败",
+                    );
+                }
+                TaskCancel::Export => {
+                    crate::features::export::cancel_export(
+                        &mut state.request_tracker,
+                        &mut state.export,
+                    );
+                }
+                TaskCancel::TrackExport { song_id } => {
+                    crate::features::player::download::cancel(
+                        song_id,
+                        &mut state.pending_downloads,
+                        effects,
+                    );
+                }
+                TaskCancel::CacheMigration => {
+                    effects.send_audio_warn(
+                        AudioCommand::CancelCacheMigration,
+                        "AudioWorker 通道已关闭：CancelCacheMigration 发送失败",
+                    );
+                }
+            }
+            state.app.tasks.cancel(task.id);
+            effects.set_toast(Toast::info(format!(
+                "已取消{}: {}",
+                task.kind.label(),
+                task.title
+            )));
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::NotificationsToggle => {
+            state.app.notifications_visible = !state.app.notifications_visible;
+            state.app.notifications_selected = 0;
+            state.app.notification_detail = None;
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::NotificationsMoveUp => {
+            if state.app.notifications_visible && state.app.notifications_selected > 0 {
+                state.app.notifications_selected -= 1;
+                effects.emit_state(&state.app);
+            }
+            UiAction::Handled
+        }
+        AppCommand::NotificationsMoveDown => {
+            if state.app.notifications_visible {
+                let max_idx = state.app.notifications.len().saturating_sub(1);
+                if state.app.notifications_selected < max_idx {
+                    state.app.notifications_selected += 1;
+                    effects.emit_state(&state.app);
+                }
+            }
+            UiAction::Handled
+        }
+        AppCommand::NotificationsOpenDetail => {
+            let selected = state.app.notifications_selected;
+            if state.app.notifications_visible && state.app.notifications.get(selected).is_some() {
+                state.app.notification_detail = Some(selected);
+                state.app.notification_detail_scroll = 0;
+                effects.emit_state(&state.app);
+            }
+            UiAction::Handled
+        }
+        AppCommand::NotificationsCloseDetail => {
+            state.app.notification_detail = None;
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
+        AppCommand::NotificationDetailScroll { offset } => {
+            if state.app.notification_detail.is_some() {
+                state.app.notification_detail_scroll = *offset;
+                effects.emit_state(&state.app);
+            }
+            UiAction::Handled
+        }
+        AppCommand::NotificationDetailCopied => {
+            effects.set_toast(Toast::info("已复制到剪贴板"));
+            effects.emit_state(&state.app);
+            UiAction::Handled
+        }
         _ => UiAction::NotHandled,
     }
 }
 
-fn next_focus(focus: UiFocus) -> UiFocus {
+fn clamp_tasks_selected(app: &mut crate::app::App) {
+    let max_idx = app.tasks.tasks().len().saturating_sub(1);
+    app.tasks_selected = app.tasks_selected.min(max_idx);
+}
+
+/// 右栏隐藏（窄终端）时，落在右栏的焦点转到中栏
+fn visible_focus(focus: UiFocus, right_visible: bool) -> UiFocus {
     match focus {
-        UiFocus::HeaderSearch => UiFocus::BodyLeft,
-        UiFocus::BodyLeft => UiFocus::BodyCenter,
-        UiFocus::BodyCenter => UiFocus::BodyRight,
-        UiFocus::BodyRight => UiFocus::HeaderSearch,
+        UiFocus::BodyRight if !right_visible => UiFocus::BodyCenter,
+        other => other,
     }
 }
 
-fn prev_focus(focus: UiFocus) -> UiFocus {
-    match focus {
-        UiFocus::HeaderSearch => UiFocus::BodyRight,
-        UiFocus::BodyLeft => UiFocus::HeaderSearch,
-        UiFocus::BodyCenter => UiFocus::BodyLeft,
-        UiFocus::BodyRight => UiFocus::BodyCenter,
+/// 数字键直接跳转：当前页面不可聚焦的区域忽略（焦点不变）
+fn settable_focus(app: &App, focus: UiFocus) -> UiFocus {
+    let focus = visible_focus(focus, app.right_pane_visible);
+    if app.focus_regions().any(|f| f == focus) {
+        focus
+    } else {
+        app.ui_focus
     }
 }
 
+/// 在当前页面的可聚焦区域中循环；焦点不在其中时从默认焦点开始
+fn next_focus(app: &App) -> UiFocus {
+    step_focus(app, 1)
+}
+
+fn prev_focus(app: &App) -> UiFocus {
+    step_focus(app, -1)
+}
+
+fn step_focus(app: &App, step: isize) -> UiFocus {
+    let regions: Vec<UiFocus> = app.focus_regions().collect();
+    let Some(pos) = regions.iter().position(|f| *f == app.ui_focus) else {
+        return default_focus(app.view);
+    };
+    let len = regions.len() as isize;
+    regions[(pos as isize + step).rem_euclid(len) as usize]
+}
+
 fn focus_locked_to_login_center(app: &crate::app::App) -> bool {
     !app.logged_in && matches!(app.view, View::Login)
 }
 
+/// 正在等待下载/导出完成后退出时，全部结束则请求 UI 退出；返回是否有变化
+pub(super) fn finish_quit_wait(app: &mut App) -> bool {
+    let waiting = app.quit_confirm.is_some_and(|c| c.wait_deadline.is_some());
+    if !waiting || app.exit_requested || app.tasks.quit_blockers() > 0 {
+        return false;
+    }
+    app.exit_requested = true;
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,18 +355,14 @@ mod tests {
         let dir = tempfile::tempdir().expect("tempdir");
         let mut state = CoreState::new(dir.path());
         let mut effects = CoreEffects::default();
-        state.app.view = View::Playlists;
+        state.app.view = View::Search;
         state.app.logged_in = true;
 
-        // Test setting focus to each possible value
-        let test_cases = vec![
+        for focus in [
             UiFocus::HeaderSearch,
-            UiFocus::BodyLeft,
             UiFocus::BodyCenter,
             UiFocus::BodyRight,
-        ];
-
-        for focus in test_cases {
+        ] {
             let cmd = AppCommand::UiFocusSet { focus };
             let outcome = handle_ui(&cmd, &mut state, &mut effects).await;
 
@@ -143,6 +371,70 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn ui_focus_set_ignores_unfocusable_regions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+        state.app.logged_in = true;
+
+        // 搜索页左栏只展示信息；歌单页没有搜索框
+        for (view, focus) in [
+            (View::Search, UiFocus::BodyLeft),
+            (View::Lyrics, UiFocus::BodyLeft),
+            (View::Playlists, UiFocus::HeaderSearch),
+        ] {
+            state.app.view = view;
+            state.app.ui_focus = UiFocus::BodyCenter;
+            handle_ui(&AppCommand::UiFocusSet { focus }, &mut state, &mut effects).await;
+            assert_eq!(state.app.ui_focus, UiFocus::BodyCenter, "{view:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn hidden_right_pane_focus_falls_through_to_center() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+        state.app.view = View::Playlists;
+        state.app.logged_in = true;
+        state.app.ui_focus = UiFocus::BodyRight;
+
+        handle_ui(
+            &AppCommand::UiResized { width: 100 },
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert!(!state.app.right_pane_visible);
+        assert_eq!(
+            state.app.ui_focus,
+            UiFocus::BodyCenter,
+            "右栏隐藏时焦点回到中栏"
+        );
+
+        let focus_right = AppCommand::UiFocusSet {
+            focus: UiFocus::BodyRight,
+        };
+        handle_ui(&focus_right, &mut state, &mut effects).await;
+        assert_eq!(state.app.ui_focus, UiFocus::BodyCenter);
+
+        handle_ui(&AppCommand::UiFocusNext, &mut state, &mut effects).await;
+        assert_eq!(state.app.ui_focus, UiFocus::BodyLeft, "Tab 跳过右栏");
+        handle_ui(&AppCommand::UiFocusPrev, &mut state, &mut effects).await;
+        assert_eq!(state.app.ui_focus, UiFocus::BodyCenter);
+
+        handle_ui(
+            &AppCommand::UiResized { width: 160 },
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert!(state.app.right_pane_visible);
+        handle_ui(&focus_right, &mut state, &mut effects).await;
+        assert_eq!(state.app.ui_focus, UiFocus::BodyRight);
+    }
+
     #[tokio::test]
     async fn unauth_login_focus_set_is_clamped_to_body_center() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -193,13 +485,8 @@ mod tests {
         // Starting focus is BodyCenter (from App::default)
         assert_eq!(state.app.ui_focus, UiFocus::BodyCenter);
 
-        // Test the full cycle starting from BodyCenter
-        let expected_sequence = vec![
-            UiFocus::BodyRight,    // BodyCenter -> BodyRight
-            UiFocus::HeaderSearch, // BodyRight -> HeaderSearch
-            UiFocus::BodyLeft,     // HeaderSearch -> BodyLeft
-            UiFocus::BodyCenter,   // BodyLeft -> BodyCenter
-        ];
+        // 歌单页没有搜索框：BodyCenter -> BodyRight -> BodyLeft -> BodyCenter
+        let expected_sequence = vec![UiFocus::BodyRight, UiFocus::BodyLeft, UiFocus::BodyCenter];
 
         for expected_focus in expected_sequence {
             let outcome = handle_ui(&AppCommand::UiFocusNext, &mut state, &mut effects).await;
@@ -219,13 +506,7 @@ mod tests {
         // Starting focus is BodyCenter (from App::default)
         assert_eq!(state.app.ui_focus, UiFocus::BodyCenter);
 
-        // Test the full cycle in reverse starting from BodyCenter
-        let expected_sequence = vec![
-            UiFocus::BodyLeft,     // BodyCenter -> BodyLeft
-            UiFocus::HeaderSearch, // BodyLeft -> HeaderSearch
-            UiFocus::BodyRight,    // HeaderSearch -> BodyRight
-            UiFocus::BodyCenter,   // BodyRight -> BodyCenter
-        ];
+        let expected_sequence = vec![UiFocus::BodyLeft, UiFocus::BodyRight, UiFocus::BodyCenter];
 
         for expected_focus in expected_sequence {
             let outcome = handle_ui(&AppCommand::UiFocusPrev, &mut state, &mut effects).await;
@@ -234,6 +515,60 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn ui_focus_cycles_only_through_the_views_regions() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+        state.app.logged_in = true;
+
+        for (view, start, expected) in [
+            (
+                View::Search,
+                UiFocus::HeaderSearch,
+                vec![
+                    UiFocus::BodyCenter,
+                    UiFocus::BodyRight,
+                    UiFocus::HeaderSearch,
+                ],
+            ),
+            (
+                View::Lyrics,
+                UiFocus::BodyCenter,
+                vec![UiFocus::BodyRight, UiFocus::BodyCenter],
+            ),
+            (
+                View::Settings,
+                UiFocus::BodyCenter,
+                vec![UiFocus::BodyRight, UiFocus::BodyLeft, UiFocus::BodyCenter],
+            ),
+        ] {
+            state.app.view = view;
+            state.app.ui_focus = start;
+            for focus in &expected {
+                handle_ui(&AppCommand::UiFocusNext, &mut state, &mut effects).await;
+                assert_eq!(state.app.ui_focus, *focus, "{view:?} Tab");
+            }
+            for focus in expected.iter().rev().skip(1) {
+                handle_ui(&AppCommand::UiFocusPrev, &mut state, &mut effects).await;
+                assert_eq!(state.app.ui_focus, *focus, "{view:?} Shift+Tab");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn ui_focus_next_from_an_unfocusable_region_goes_to_the_default() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+        state.app.logged_in = true;
+        state.app.view = View::Lyrics;
+        state.app.ui_focus = UiFocus::BodyLeft;
+
+        handle_ui(&AppCommand::UiFocusNext, &mut state, &mut effects).await;
+        assert_eq!(state.app.ui_focus, UiFocus::BodyCenter);
+    }
+
     #[tokio::test]
     async fn ui_toggle_help_toggles_visibility() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -370,10 +705,192 @@ mod tests {
         assert_eq!(state.app.ui_focus, UiFocus::BodyCenter);
     }
 
+    #[tokio::test]
+    async fn notifications_are_recorded_and_detail_follows_its_entry() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+        effects.set_toast(Toast::error("第一条"));
+        effects.toast("第二条");
+        assert!(effects.record_notifications(&mut state.app));
+        assert_eq!(state.app.notifications.len(), 2);
+        assert_eq!(state.app.notifications.get(0).unwrap().message, "第二条");
+
+        let mut effects = CoreEffects::default();
+        handle_ui(&AppCommand::NotificationsToggle, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::NotificationsMoveDown, &mut state, &mut effects).await;
+        handle_ui(
+            &AppCommand::NotificationsOpenDetail,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.notification_detail, Some(1));
+
+        // 查看期间来了新通知：仍指向「第一条」
+        state
+            .app
+            .record_notification(crate::app::ToastLevel::Info, "第三条");
+        assert_eq!(state.app.notification_detail, Some(2));
+        assert_eq!(state.app.notifications_selected, 2);
+        let shown = state.app.notification_detail.unwrap();
+        assert_eq!(
+            state.app.notifications.get(shown).unwrap().message,
+            "第一条"
+        );
+
+        handle_ui(
+            &AppCommand::NotificationsCloseDetail,
+            &mut state,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(state.app.notification_detail, None);
+        assert!(state.app.notifications_visible);
+    }
+
     /// VAL-MENU-002: default_menu_items 至少 4 个
     #[test]
     fn default_menu_items_len_at_least_4() {
         let items = default_menu_items();
         assert!(items.len() >= 4, "菜单应有至少 4 个选项");
     }
+
+    // ============================================================
+    // Background tasks reducer tests
+    // ============================================================
+
+    #[tokio::test]
+    async fn tasks_cancel_selected_routes_preload_to_preload_manager() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state.app.playlist_preloads.insert(
+            9,
+            crate::app::PlaylistPreload {
+                status: crate::app::PreloadStatus::Loading {
+                    loaded: 10,
+                    total: 100,
+                },
+                songs: Vec::new(),
+                missing_count: 0,
+                stats: None,
+                completed_at_ms: None,
+                added_at: std::collections::HashMap::new(),
+                track_ids_fingerprint: None,
+            },
+        );
+        crate::core::infra::preload_pub::update_preload_summary(&mut state.app);
+        assert_eq!(state.app.tasks.running_count(), 1);
+
+        let mut effects = CoreEffects::default();
+        handle_ui(&AppCommand::TasksToggle, &mut state, &mut effects).await;
+        assert!(state.app.tasks_visible);
+
+        let mut effects = CoreEffects::default();
+        handle_ui(&AppCommand::TasksCancelSelected, &mut state, &mut effects).await;
+
+        assert!(matches!(
+            state.app.playlist_preloads.get(&9).map(|p| &p.status),
+            Some(crate::app::PreloadStatus::Cancelled)
+        ));
+        assert_eq!(state.app.tasks.running_count(), 0);
+        assert_eq!(
+            state.app.tasks.tasks()[0].status,
+            crate::app::TaskStatus::Cancelled
+        );
+    }
+
+    #[tokio::test]
+    async fn tasks_cancel_selected_routes_download_to_audio_stop() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        state
+            .app
+            .tasks
+            .register(crate::app::TaskKind::AudioDownload { song_id: 1 }, "A - B");
+        state.app.tasks_visible = true;
+
+        let mut effects = CoreEffects::default();
+        handle_ui(&AppCommand::TasksCancelSelected, &mut state, &mut effects).await;
+
+        assert!(effects.actions.iter().any(|e| matches!(
+            e,
+            crate::core::effects::CoreEffect::SendAudio {
+                cmd: AudioCommand::Stop,
+                ..
+            }
+        )));
+        assert_eq!(state.app.tasks.running_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn focus_mode_toggle_hides_current_info_toast_but_keeps_errors() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+
+        state.app.toast = Some(Toast::info("已恢复上次的播放队列"));
+        handle_ui(&AppCommand::UiToggleFocusMode, &mut state, &mut effects).await;
+        assert!(state.app.focus_mode);
+        assert!(state.app.toast.is_none());
+
+        state.app.toast = Some(Toast::error("播放失败"));
+        handle_ui(&AppCommand::UiToggleFocusMode, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::UiToggleFocusMode, &mut state, &mut effects).await;
+        assert!(state.app.focus_mode);
+        assert!(state.app.toast.is_some(), "错误 Toast 不受专注模式影响");
+        assert!(crate::app::AppSnapshot::from_app(&state.app).focus_mode);
+    }
+
+    #[tokio::test]
+    async fn quit_confirm_waits_for_downloads_then_requests_exit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+        let id = state
+            .app
+            .tasks
+            .register(crate::app::TaskKind::AudioDownload { song_id: 1 }, "A");
+
+        handle_ui(&AppCommand::QuitConfirmOpen, &mut state, &mut effects).await;
+        let confirm = state.app.quit_confirm.expect("应打开退出确认");
+        assert_eq!(confirm.downloads, 1);
+        assert!(confirm.wait_deadline.is_none());
+
+        handle_ui(&AppCommand::QuitConfirmWait, &mut state, &mut effects).await;
+        assert!(
+            state
+                .app
+                .quit_confirm
+                .is_some_and(|c| c.wait_deadline.is_some())
+        );
+        assert!(!state.app.exit_requested, "下载未结束前不退出");
+
+        state.app.tasks.complete(id);
+        assert!(finish_quit_wait(&mut state.app));
+        assert!(state.app.exit_requested);
+        assert!(!finish_quit_wait(&mut state.app), "只请求一次");
+    }
+
+    #[tokio::test]
+    async fn quit_confirm_cancel_and_stale_open() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut state = CoreState::new(dir.path());
+        let mut effects = CoreEffects::default();
+        state
+            .app
+            .tasks
+            .register(crate::app::TaskKind::AudioDownload { song_id: 1 }, "A");
+
+        handle_ui(&AppCommand::QuitConfirmOpen, &mut state, &mut effects).await;
+        handle_ui(&AppCommand::QuitConfirmCancel, &mut state, &mut effects).await;
+        assert!(state.app.quit_confirm.is_none());
+        assert!(!finish_quit_wait(&mut state.app), "取消后不再等待");
+
+        // UI 快照落后、下载其实已经结束：不弹窗，直接请求退出
+        let mut state = CoreState::new(dir.path());
+        handle_ui(&AppCommand::QuitConfirmOpen, &mut state, &mut effects).await;
+        assert!(state.app.quit_confirm.is_none());
+        assert!(state.app.exit_requested);
+    }
 }
@@ -3,13 +3,6 @@ use crate::app::AppSnapshot;
 use crate::messages::app::AppEvent;
 use tokio::sync::mpsc;
 
-/// 生成下一个请求 ID
-pub fn next_id(id: &mut u64) -> u64 {
-    let out = *id;
-    *id = id.wrapping_add(1);
-    out
-}
-
 /// 推送应用状态到事件通道
 #[allow(dead_code)]
 pub async fn push_state(tx_evt: &mpsc::Sender<AppEvent>, app: &App) {
@@ -0,0 +1,253 @@
+//! 崩溃报告：panic 时把现场写入 `data_dir/crashes/{时间}.txt`
+//!
+//! 报告包含 panic 信息、backtrace、版本号、当前页面与最近的若干条 [`AppCommand`]。
+//! 最近命令由核心 actor 在处理每条 UI 命令前记录；Cookie 与手机号登录的输入始终隐藏，
+//! 开启「专注模式隐藏曲名」时搜索输入也会隐藏。下次启动时发现新的报告会提示一次。
+
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, TryLockError};
+
+use crate::app::View;
+use crate::messages::app::AppCommand;
+
+/// 崩溃报告目录（位于数据目录下）
+pub const CRASH_DIR: &str = "crashes";
+
+/// 记录已提示过的最新报告文件名
+const SEEN_FILE: &str = "last_seen";
+
+/// 报告中保留的最近命令条数
+const RECENT_COMMANDS: usize = 16;
+
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext::new());
+
+/// panic 时写入报告的现场信息
+#[derive(Debug)]
+struct CrashContext {
+    view: Option<View>,
+    recent: VecDeque<String>,
+}
+
+impl CrashContext {
+    const fn new() -> Self {
+        Self {
+            view: None,
+            recent: VecDeque::new(),
+        }
+    }
+
+    fn push(&mut self, line: String) {
+        if self.recent.len() == RECENT_COMMANDS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(line);
+    }
+}
+
+fn context() -> std::sync::MutexGuard<'static, CrashContext> {
+    CONTEXT.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// 记录一条即将处理的 UI 命令；`redact_titles` 为 true 时隐藏搜索输入
+pub fn record_command(cmd: &AppCommand, redact_titles: bool) {
+    context().push(describe_command(cmd, redact_titles));
+}
+
+/// 记录当前页面
+pub fn record_view(view: View) {
+    context().view = Some(view);
+}
+
+/// 命令的文字描述；Cookie 与手机号登录的输入始终隐藏
+fn describe_command(cmd: &AppCommand, redact_titles: bool) -> String {
+    match cmd {
+        AppCommand::LoginCookieInputChar { .. } => "LoginCookieInputChar { <已隐藏> }".to_owned(),
+        AppCommand::LoginCookieInputString { .. } => {
+            "LoginCookieInputString { <已隐藏> }".to_owned()
+        }
+        AppCommand::LoginPhoneInputChar { .. } => "LoginPhoneInputChar { <已隐藏> }".to_owned(),
+        AppCommand::LoginPhoneInputString { .. } => "LoginPhoneInputString { <已隐藏> }".to_owned(),
+        AppCommand::SearchInputChar { .. } if redact_titles => {
+            "SearchInputChar { <已隐藏> }".to_owned()
+        }
+        AppCommand::SearchInputString { .. } if redact_titles => {
+            "SearchInputString { <已隐藏> }".to_owned()
+        }
+        cmd => format!("{cmd:?}"),
+    }
+}
+
+/// 安装 panic hook：先写崩溃报告，再交给原来的 hook（打印到 stderr、继续展开）
+pub fn install(data_dir: &Path) {
+    let dir = data_dir.join(CRASH_DIR);
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match write_report(&dir, info) {
+            Ok(path) => tracing::error!(path = %path.display(), "发生 panic，已写入崩溃报告"),
+            Err(e) => tracing::error!(err = %e, "发生 panic，写入崩溃报告失败"),
+        }
+        previous(info);
+    }));
+}
+
+fn write_report(dir: &Path, info: &PanicHookInfo<'_>) -> io::Result<PathBuf> {
+    let now = chrono::Local::now();
+    let thread = std::thread::current();
+    let location = info
+        .location()
+        .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()))
+        .unwrap_or_else(|| "<未知>".to_owned());
+    let backtrace = Backtrace::force_capture();
+
+    let mut report = format!(
+        "netease-ratui {}\n时间: {}\n线程: {}\npanic: {}\n位置: {location}\n",
+        env!("CARGO_PKG_VERSION"),
+        now.to_rfc3339(),
+        thread.name().unwrap_or("<未命名>"),
+        panic_message(info.payload()),
+    );
+    // 不能阻塞：panic 可能恰好发生在本线程持锁期间；锁中毒时内容仍可读
+    match CONTEXT.try_lock() {
+        Ok(ctx) => push_context(&mut report, &ctx),
+        Err(TryLockError::Poisoned(e)) => push_context(&mut report, &e.into_inner()),
+        Err(TryLockError::WouldBlock) => report.push_str("当前页面与最近的命令: <不可用>\n"),
+    }
+    report.push_str(&format!("\nbacktrace:\n{backtrace}\n"));
+
+    fs::create_dir_all(dir)?;
+    // 同一秒内多次 panic 时追加序号，避免互相覆盖
+    let stamp = now.format("%Y%m%d-%H%M%S");
+    let mut path = dir.join(format!("{stamp}.txt"));
+    let mut n = 1;
+    while path.exists() {
+        path = dir.join(format!("{stamp}_{n}.txt"));
+        n += 1;
+    }
+    fs::write(&path, report)?;
+    Ok(path)
+}
+
+fn push_context(report: &mut String, ctx: &CrashContext) {
+    match ctx.view {
+        Some(view) => report.push_str(&format!("当前页面: {view:?}\n")),
+        None => report.push_str("当前页面: <未知>\n"),
+    }
+    report.push_str(&format!(
+        "\n最近的命令（旧 → 新，共 {} 条）:\n",
+        ctx.recent.len()
+    ));
+    for line in &ctx.recent {
+        report.push_str(&format!("  {line}\n"));
+    }
+}
+
+/// panic 载荷中的文字说明
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<非文字 panic>")
+}
+
+/// 启动时调用：存在尚未提示过的崩溃报告时返回其中最新的一份，并记为已提示
+pub fn take_unseen_report(data_dir: &Path) -> Option<PathBuf> {
+    let dir = data_dir.join(CRASH_DIR);
+    // 文件名以时间开头，按名字排序即按时间排序
+    let newest = fs::read_dir(&dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".txt"))
+        .max()?;
+    let seen_path = dir.join(SEEN_FILE);
+    let seen = fs::read_to_string(&seen_path).unwrap_or_default();
+    if seen.trim() >= newest.as_str() {
+        return None;
+    }
+    if let Err(e) = fs::write(&seen_path, &newest) {
+        tracing::warn!(err = %e, "记录已提示的崩溃报告失败");
+    }
+    Some(dir.join(newest))
+}
+
+/// 监视后台任务：任务 panic 或被取消时写日志（panic 本身已由 hook 写入报告）
+pub fn supervise(name: &'static str, handle: tokio::task::JoinHandle<()>) {
+    tokio::spawn(async move {
+        if let Err(e) = handle.await {
+            if e.is_panic() {
+                let payload = e.into_panic();
+                tracing::error!(task = name, panic = %panic_message(&*payload), "后台任务 panic 退出");
+            } else {
+                tracing::warn!(task = name, err = %e, "后台任务被取消");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cookie_input_is_always_hidden_and_search_input_follows_privacy() {
+        let cookie = AppCommand::LoginCookieInputString {
+            s: "MUSIC_U=secret".to_owned(),
+        };
+        let search = AppCommand::SearchInputString {
+            s: "晴天".to_owned(),
+        };
+
+        let password = AppCommand::LoginPhoneInputChar { c: 'x' };
+        assert!(!describe_command(&cookie, false).contains("secret"));
+        assert!(!describe_command(&password, false).contains('x'));
+        assert!(describe_command(&search, false).contains("晴天"));
+        assert!(!describe_command(&search, true).contains("晴天"));
+        assert_eq!(
+            describe_command(&AppCommand::TabTo { index: 2 }, true),
+            "TabTo { index: 2 }"
+        );
+    }
+
+    #[test]
+    fn ring_buffer_keeps_the_most_recent_commands() {
+        let mut ctx = CrashContext::new();
+        for i in 0..RECENT_COMMANDS + 3 {
+            ctx.push(format!("cmd {i}"));
+        }
+        assert_eq!(ctx.recent.len(), RECENT_COMMANDS);
+        assert_eq!(ctx.recent.front().map(String::as_str), Some("cmd 3"));
+        assert_eq!(
+            ctx.recent.back().cloned(),
+            Some(format!("cmd {}", RECENT_COMMANDS + 2))
+        );
+    }
+
+    #[test]
+    fn new_crash_report_is_reported_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(take_unseen_report(dir.path()), None);
+
+        let crashes = dir.path().join(CRASH_DIR);
+        fs::create_dir_all(&crashes).unwrap();
+        fs::write(crashes.join("20260101-080000.txt"), "old").unwrap();
+        fs::write(crashes.join("20260102-090000.txt"), "new").unwrap();
+
+        assert_eq!(
+            take_unseen_report(dir.path()),
+            Some(crashes.join("20260102-090000.txt"))
+        );
+        assert_eq!(take_unseen_report(dir.path()), None);
+
+        fs::write(crashes.join("20260103-100000.txt"), "newer").unwrap();
+        assert_eq!(
+            take_unseen_report(dir.path()),
+            Some(crashes.join("20260103-100000.txt"))
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! 数据目录下各类持久化文件的位置
+
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::error::AppError;
+
+/// 数据目录布局：所有路径都由各模块自身的路径函数解析，保证与读写逻辑一致
+#[derive(Debug, Clone)]
+pub struct DataPaths {
+    root: PathBuf,
+    /// 音频缓存放在数据目录之外时的位置（见 [`resolve_audio_cache_dir`]）
+    audio_cache: Option<PathBuf>,
+}
+
+impl DataPaths {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            audio_cache: None,
+        }
+    }
+
+    /// 使用另外指定的音频缓存目录；`None` 表示使用数据目录下的默认位置
+    pub fn with_audio_cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.audio_cache = dir;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// 登录 cookie 与设备 id（netease_state.json）
+    pub fn cookies(&self) -> PathBuf {
+        crate::netease::client::state_path(&self.root)
+    }
+
+    pub fn settings(&self) -> PathBuf {
+        crate::settings::settings_path(&self.root)
+    }
+
+    /// 播放状态快照（同时包含歌单预加载数据）
+    pub fn player_state(&self) -> PathBuf {
+        crate::player_state::state_path(&self.root)
+    }
+
+    /// 播放队列的独立自动保存（queue.json）
+    pub fn queue(&self) -> PathBuf {
+        crate::player_state::queue_path(&self.root)
+    }
+
+    /// 本地播放历史（play_history.jsonl）
+    pub fn play_history(&self) -> PathBuf {
+        crate::play_history::history_path(&self.root)
+    }
+
+    /// 崩溃报告目录（`crashes/`，报告中含最近的命令记录）
+    pub fn crash_reports_dir(&self) -> PathBuf {
+        self.root.join(crate::crash::CRASH_DIR)
+    }
+
+    /// 实际使用的音频缓存目录
+    pub fn audio_cache_dir(&self) -> PathBuf {
+        self.audio_cache
+            .clone()
+            .unwrap_or_else(|| self.default_audio_cache_dir())
+    }
+
+    /// 数据目录下的默认音频缓存目录（`audio_cache/`）
+    pub fn default_audio_cache_dir(&self) -> PathBuf {
+        self.root.join(crate::audio_worker::AUDIO_CACHE_DIR)
+    }
+
+    /// 音频缓存是否位于数据目录之外指定的位置
+    pub fn audio_cache_overridden(&self) -> bool {
+        self.audio_cache.is_some()
+    }
+
+    /// 校验路径位于数据目录内（拒绝 `..` 以及指向目录外的符号链接）
+    pub fn ensure_within(&self, path: &Path) -> Result<(), AppError> {
+        let outside = || {
+            AppError::DataDir(format!(
+                "拒绝操作数据目录之外的路径: {} (数据目录: {})",
+                path.display(),
+                self.root.display()
+            ))
+        };
+
+        if path.components().any(|c| matches!(c, Component::ParentDir))
+            || !path.starts_with(&self.root)
+            || path == self.root
+        {
+            return Err(outside());
+        }
+
+        match path.canonicalize() {
+            Ok(real) => {
+                let root = self.root.canonicalize()?;
+                if real.starts_with(&root) && real != root {
+                    Ok(())
+                } else {
+                    Err(outside())
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// 音频缓存目录的覆盖值：`--cache-dir` 优先，其次是设置 `audio_cache_dir`；
+/// 空路径视为未设置，开头的 `~` 展开为用户主目录
+pub fn resolve_audio_cache_dir(cli: Option<&Path>, setting: Option<&Path>) -> Option<PathBuf> {
+    cli.into_iter()
+        .chain(setting)
+        .find(|p| !p.as_os_str().is_empty())
+        .map(expand_home)
+}
+
+/// 展开开头的 `~`（找不到主目录时原样返回）
+pub fn expand_home(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => directories::BaseDirs::new()
+            .map(|d| d.home_dir().join(rest))
+            .unwrap_or_else(|| path.to_path_buf()),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// 启动时检查音频缓存目录：不存在则创建，并确认可以写入
+pub fn prepare_audio_cache_dir(dir: &Path) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    tempfile::NamedTempFile::new_in(dir).map(drop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_resolve_under_root() {
+        let paths = DataPaths::new("/data/netease");
+        assert_eq!(
+            paths.cookies(),
+            PathBuf::from("/data/netease/netease_state.json")
+        );
+        assert_eq!(
+            paths.settings(),
+            PathBuf::from("/data/netease/settings.json")
+        );
+        assert_eq!(
+            paths.player_state(),
+            PathBuf::from("/data/netease/player_state.json")
+        );
+        assert_eq!(
+            paths.play_history(),
+            PathBuf::from("/data/netease/play_history.jsonl")
+        );
+        assert_eq!(
+            paths.crash_reports_dir(),
+            PathBuf::from("/data/netease/crashes")
+        );
+        assert_eq!(
+            paths.audio_cache_dir(),
+            PathBuf::from("/data/netease/audio_cache")
+        );
+    }
+
+    #[test]
+    fn audio_cache_dir_prefers_cli_then_setting_then_default() {
+        let cli = Path::new("/hdd/cli");
+        let setting = Path::new("/hdd/setting");
+        assert_eq!(
+            resolve_audio_cache_dir(Some(cli), Some(setting)),
+            Some(cli.to_path_buf())
+        );
+        assert_eq!(
+            resolve_audio_cache_dir(None, Some(setting)),
+            Some(setting.to_path_buf())
+        );
+        assert_eq!(
+            resolve_audio_cache_dir(Some(Path::new("")), Some(setting)),
+            Some(setting.to_path_buf())
+        );
+        assert_eq!(resolve_audio_cache_dir(None, None), None);
+
+        let paths = DataPaths::new("/data/netease");
+        assert!(!paths.audio_cache_overridden());
+        let paths = paths.with_audio_cache_dir(resolve_audio_cache_dir(None, Some(setting)));
+        assert!(paths.audio_cache_overridden());
+        assert_eq!(paths.audio_cache_dir(), setting);
+        assert_eq!(
+            paths.default_audio_cache_dir(),
+            PathBuf::from("/data/netease/audio_cache")
+        );
+    }
+
+    #[test]
+    fn preparing_the_audio_cache_dir_creates_it() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cache = dir.path().join("a").join("b");
+        prepare_audio_cache_dir(&cache).unwrap();
+        assert!(cache.is_dir());
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 0);
+
+        let file = dir.path().join("file");
+        std::fs::write(&file, "").unwrap();
+        assert!(prepare_audio_cache_dir(&file).is_err());
+    }
+
+    #[test]
+    fn ensure_within_rejects_traversal_and_root() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = DataPaths::new(dir.path());
+
+        assert!(paths.ensure_within(&paths.settings()).is_ok());
+        assert!(paths.ensure_within(dir.path()).is_err());
+        assert!(
+            paths
+                .ensure_within(&dir.path().join("../etc/passwd"))
+                .is_err()
+        );
+        assert!(paths.ensure_within(Path::new("/tmp/other")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn ensure_within_rejects_symlink_escaping_root() {
+        let outside = tempfile::tempdir().expect("tempdir");
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = DataPaths::new(dir.path());
+        std::os::unix::fs::symlink(outside.path(), paths.audio_cache_dir()).expect("symlink");
+
+        assert!(paths.ensure_within(&paths.audio_cache_dir()).is_err());
+    }
+}
@@ -1,9 +1,87 @@
+use std::borrow::Cow;
+
 #[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Song {
     pub id: i64,
     pub name: String,
     pub artists: String,
     pub duration_ms: Option<u64>,
+    /// 专辑名（接口未返回时为空）
+    #[serde(default)]
+    pub album: String,
+    #[serde(default)]
+    pub album_id: Option<i64>,
+    /// 与 `artists`（`/` 分隔）一一对应的歌手 id
+    #[serde(default)]
+    pub artist_ids: Vec<i64>,
+    /// 译名（接口 `tns` 的第一项）；`name` 始终保存原名
+    #[serde(default)]
+    pub translated: Option<String>,
+    /// 专辑封面地址（接口 `al.picUrl`）
+    #[serde(default)]
+    pub cover_url: Option<String>,
+    /// 在专辑中的曲目号（接口 `no`，0 表示未知）
+    #[serde(default)]
+    pub track_no: Option<u32>,
+}
+
+/// 歌曲名的显示方式（设置「歌曲名语言」）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SongNameMode {
+    /// 只显示原名
+    #[default]
+    Original,
+    /// 有译名时显示译名
+    TranslatedFirst,
+    /// 原名后附译名：`原名 (译名)`
+    Both,
+}
+
+impl Song {
+    /// 按显示方式生成歌曲名；列表、标题与正在播放统一经由这里
+    ///
+    /// 没有译名或译名与原名相同时总是显示原名。
+    pub fn display_name(&self, mode: SongNameMode) -> Cow<'_, str> {
+        let translated = self
+            .translated
+            .as_deref()
+            .filter(|t| !t.is_empty() && *t != self.name);
+        match (mode, translated) {
+            (SongNameMode::TranslatedFirst, Some(t)) => Cow::Borrowed(t),
+            (SongNameMode::Both, Some(t)) => Cow::Owned(format!("{} ({t})", self.name)),
+            _ => Cow::Borrowed(&self.name),
+        }
+    }
+
+    /// 「歌曲名 - 歌手」形式的标题
+    pub fn display_title(&self, mode: SongNameMode) -> String {
+        format!("{} - {}", self.display_name(mode), self.artists)
+    }
+}
+
+/// 歌曲的可播放性（来自 song/detail 的 `fee` 与 `privileges`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SongAvailability {
+    /// 免费或非会员可播放试听音质
+    #[default]
+    Free,
+    /// VIP 专享
+    Vip,
+    /// 需购买专辑
+    Paid,
+    /// 无版权，无法播放
+    Unavailable,
+}
+
+/// 搜索结果预览：播放前查看的歌曲详情
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SongPreview {
+    pub id: i64,
+    pub album: String,
+    pub duration_ms: Option<u64>,
+    /// 发行年份（接口未返回发行时间时为 None）
+    pub publish_year: Option<i32>,
+    pub availability: SongAvailability,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -12,6 +90,47 @@ pub struct Playlist {
     pub name: String,
     pub track_count: i64,
     pub special_type: i64,
+    /// 收藏的他人歌单（非自己创建）
+    pub subscribed: bool,
+    /// 最近更新时间（毫秒时间戳；`updateTime` 与 `trackUpdateTime` 中较晚者）
+    pub updated_at_ms: Option<i64>,
+}
+
+/// 歌单曲目增删操作（`/api/playlist/manipulate/tracks` 的 `op`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaylistTrackOp {
+    Add,
+    Del,
+}
+
+impl PlaylistTrackOp {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PlaylistTrackOp::Add => "add",
+            PlaylistTrackOp::Del => "del",
+        }
+    }
+}
+
+/// 专辑搜索结果
+#[derive(Debug, Default, Clone)]
+pub struct Album {
+    pub id: i64,
+    pub name: String,
+    /// 歌手名（`/` 分隔）
+    pub artists: String,
+    pub track_count: i64,
+}
+
+/// 歌手搜索结果
+#[derive(Debug, Default, Clone)]
+pub struct Artist {
+    pub id: i64,
+    pub name: String,
+    /// 单曲数
+    pub music_size: i64,
+    /// 专辑数
+    pub album_size: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -24,6 +143,40 @@ pub struct Account {
 pub struct SongUrl {
     pub id: i64,
     pub url: String,
+    /// 接口返回的文件大小（字节），用于校验下载内容
+    pub size: Option<u64>,
+    /// 接口返回的实际码率；非会员等情况下可能低于请求的音质
+    pub br: Option<i64>,
+    /// 接口返回的响度增益（dB），开启音量均衡时叠加到音量上；没有数据时为 None
+    pub gain_db: Option<f32>,
+}
+
+/// 音质档位（与设置页可选的 br 一致）
+pub const BR_LEVELS: [i64; 4] = [128_000, 192_000, 320_000, 999_000];
+
+/// 请求的音质没有可用链接时依次尝试的档位
+pub const BR_FALLBACK_LADDER: [i64; 3] = [999_000, 320_000, 128_000];
+
+/// `br` 下没有可用链接时下一个尝试的音质；已是最低档时为 None
+pub fn fallback_br(br: i64) -> Option<i64> {
+    BR_FALLBACK_LADDER.iter().copied().find(|&level| level < br)
+}
+
+/// 将实际码率归入音质档位（无损文件的实际码率不固定，统一归为最高档）
+pub fn br_level(br: i64) -> i64 {
+    BR_LEVELS
+        .iter()
+        .copied()
+        .find(|&level| br <= level)
+        .unwrap_or(BR_LEVELS[BR_LEVELS.len() - 1])
+}
+
+impl SongUrl {
+    /// 实际拿到的音质档位：不高于请求的音质；接口未返回码率时按请求的音质处理
+    pub fn effective_br(&self, requested_br: i64) -> i64 {
+        self.br
+            .map_or(requested_br, |br| br_level(br).min(requested_br))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,5 +190,78 @@ pub struct LoginStatus {
 pub struct LyricLine {
     pub time_ms: u64,
     pub text: String,
+    /// 翻译（`tlyric`）中时间戳相同的行
     pub translation: Option<String>,
+    /// 罗马音（`romalrc`）中时间戳相同的行
+    pub roman: Option<String>,
+}
+
+#[cfg(test)]
+impl Song {
+    /// 测试用歌曲：歌名 `song-{id}`、歌手 `artist`
+    pub(crate) fn fixture(id: i64) -> Self {
+        Self::titled(id, &format!("song-{id}"), "artist")
+    }
+
+    /// 测试用歌曲：指定歌名与歌手，其余字段用下面的方法按需补上
+    pub(crate) fn titled(id: i64, name: &str, artists: &str) -> Self {
+        Self {
+            id,
+            name: name.to_owned(),
+            artists: artists.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn lasting_ms(mut self, duration_ms: u64) -> Self {
+        self.duration_ms = Some(duration_ms);
+        self
+    }
+
+    pub(crate) fn in_album(mut self, album: &str) -> Self {
+        self.album = album.to_owned();
+        self
+    }
+
+    pub(crate) fn translated_as(mut self, translated: &str) -> Self {
+        self.translated = Some(translated.to_owned());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_name_with_translation() {
+        let s = Song::titled(1, "밤편지", "IU").translated_as("夜信");
+        assert_eq!(s.display_name(SongNameMode::Original), "밤편지");
+        assert_eq!(s.display_name(SongNameMode::TranslatedFirst), "夜信");
+        assert_eq!(s.display_name(SongNameMode::Both), "밤편지 (夜信)");
+        assert_eq!(s.display_title(SongNameMode::TranslatedFirst), "夜信 - IU");
+    }
+
+    #[test]
+    fn display_name_without_translation_falls_back_to_original() {
+        for s in [
+            Song::titled(1, "Blueming", "IU"),
+            Song::titled(1, "Blueming", "IU").translated_as(""),
+        ] {
+            for mode in [
+                SongNameMode::Original,
+                SongNameMode::TranslatedFirst,
+                SongNameMode::Both,
+            ] {
+                assert_eq!(s.display_name(mode), "Blueming");
+            }
+        }
+    }
+
+    #[test]
+    fn display_name_ignores_translation_identical_to_name() {
+        let s = Song::titled(1, "晴天", "IU").translated_as("晴天");
+        assert_eq!(s.display_name(SongNameMode::TranslatedFirst), "晴天");
+        assert_eq!(s.display_name(SongNameMode::Both), "晴天");
+    }
 }
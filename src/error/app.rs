@@ -26,7 +26,6 @@ pub enum AppError {
     Audio(#[from] AudioError),
 
     /// 数据目录错误
-    #[allow(dead_code)]
     #[error("数据目录错误: {0}")]
     DataDir(String),
 
@@ -40,6 +40,10 @@ pub enum DownloadError {
     #[allow(dead_code)]
     #[error("下载 URL 无效: {0}")]
     InvalidUrl(String),
+
+    /// 响应内容不是音频（CDN 链接过期时常返回 HTML/JSON 错误页）
+    #[error("下载内容无效（链接可能已过期）: {0}")]
+    InvalidContent(String),
 }
 
 impl DownloadError {
@@ -51,6 +55,20 @@ impl DownloadError {
             DownloadError::Http(_) | DownloadError::StatusCode { .. } | DownloadError::Write { .. }
         )
     }
+
+    /// 判断是否需要重新获取播放链接（同一链接重试无意义）
+    pub fn is_stale_url(&self) -> bool {
+        matches!(self, DownloadError::InvalidContent(_))
+    }
+
+    /// CDN 拒绝访问或限流（403/429），计入 host 冷却
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(
+            self,
+            DownloadError::StatusCode { status, .. }
+                if *status == StatusCode::FORBIDDEN || *status == StatusCode::TOO_MANY_REQUESTS
+        )
+    }
 }
 
 #[cfg(test)]
@@ -76,5 +94,22 @@ mod tests {
 
         // MaxRetriesExceeded 不可重试
         assert!(!DownloadError::MaxRetriesExceeded { retries: 3 }.is_retryable());
+
+        // 内容无效不在同一链接上重试，而是重新获取链接
+        let err = DownloadError::InvalidContent("text/html".to_owned());
+        assert!(!err.is_retryable());
+        assert!(err.is_stale_url());
+    }
+
+    #[test]
+    fn test_is_rate_limited() {
+        let status = |status| DownloadError::StatusCode {
+            status,
+            url: "http://example.com".to_string(),
+        };
+        assert!(status(StatusCode::FORBIDDEN).is_rate_limited());
+        assert!(status(StatusCode::TOO_MANY_REQUESTS).is_rate_limited());
+        assert!(!status(StatusCode::NOT_FOUND).is_rate_limited());
+        assert!(!DownloadError::InvalidContent("x".to_owned()).is_rate_limited());
     }
 }
@@ -25,8 +25,10 @@ pub enum NeteaseErrorVariant {
     Crypto(String),
     CookieValidationFailed(String),
     Api { code: i32, msg: String },
+    NeedLogin,
     BadHeader(String),
     BadInput(&'static str),
+    InvalidProxy(String),
 }
 
 /// 轻量级音频错误变体
@@ -54,8 +56,12 @@ impl From<crate::error::NeteaseError> for NeteaseErrorVariant {
                 NeteaseErrorVariant::CookieValidationFailed(s)
             }
             crate::error::NeteaseError::Api { code, msg } => NeteaseErrorVariant::Api { code, msg },
+            crate::error::NeteaseError::NeedLogin => NeteaseErrorVariant::NeedLogin,
             crate::error::NeteaseError::BadHeader(s) => NeteaseErrorVariant::BadHeader(s),
             crate::error::NeteaseError::BadInput(s) => NeteaseErrorVariant::BadInput(s),
+            e @ crate::error::NeteaseError::InvalidProxy { .. } => {
+                NeteaseErrorVariant::InvalidProxy(e.to_string())
+            }
         }
     }
 }
@@ -257,8 +263,10 @@ impl std::fmt::Display for NeteaseErrorVariant {
             NeteaseErrorVariant::Crypto(s) => write!(f, "加密错误: {s}"),
             NeteaseErrorVariant::CookieValidationFailed(s) => write!(f, "Cookie 验证失败: {s}"),
             NeteaseErrorVariant::Api { code, msg } => write!(f, "API 错误 (code={code}): {msg}"),
+            NeteaseErrorVariant::NeedLogin => write!(f, "需要登录"),
             NeteaseErrorVariant::BadHeader(s) => write!(f, "Header 构造失败: {s}"),
             NeteaseErrorVariant::BadInput(s) => write!(f, "输入错误: {s}"),
+            NeteaseErrorVariant::InvalidProxy(s) => write!(f, "{s}"),
         }
     }
 }
@@ -8,6 +8,7 @@ mod cache;
 mod download;
 mod message;
 mod netease;
+mod play_history;
 mod player_state;
 
 // 重新导出所有错误类型，便于使用
@@ -15,8 +16,9 @@ pub use app::AppError;
 pub use audio::AudioError;
 pub use cache::CacheError;
 pub use download::DownloadError;
-pub use message::MessageError;
+pub use message::{AudioErrorVariant, MessageError, NeteaseErrorVariant};
 pub use netease::NeteaseError;
+pub use play_history::PlayHistoryError;
 pub use player_state::PlayerStateError;
 
 // 为方便 UI 层使用，提供 Display 的 trait impl
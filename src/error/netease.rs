@@ -23,11 +23,14 @@ pub enum NeteaseError {
     #[error("Cookie 验证失败: {0}")]
     CookieValidationFailed(String),
 
-    /// API 返回业务错误
-    #[allow(dead_code)]
+    /// API 返回业务错误（响应中的 `code` 不是 200）
     #[error("API 返回错误: code={code}, msg={msg}")]
     Api { code: i32, msg: String },
 
+    /// 接口要求登录（code 301）：游客调用或登录已失效
+    #[error("需要登录")]
+    NeedLogin,
+
     /// HTTP 头构造失败
     #[error("Header 构造失败: {0}")]
     BadHeader(String),
@@ -35,6 +38,10 @@ pub enum NeteaseError {
     /// 输入参数无效
     #[error("输入错误: {0}")]
     BadInput(&'static str),
+
+    /// 代理地址无法解析（`--proxy` / `HTTPS_PROXY`）
+    #[error("代理地址无效: {url}（{reason}）")]
+    InvalidProxy { url: String, reason: String },
 }
 
 // 实现 From traits 以便自动转换
@@ -64,11 +71,12 @@ impl NeteaseError {
     }
 
     /// 判断是否是认证错误
-    #[allow(dead_code)]
     pub fn is_auth_error(&self) -> bool {
         matches!(
             self,
-            NeteaseError::CookieValidationFailed(_) | NeteaseError::Api { code: -100, .. }
+            NeteaseError::CookieValidationFailed(_)
+                | NeteaseError::NeedLogin
+                | NeteaseError::Api { code: -100, .. }
         )
     }
 
@@ -119,5 +127,6 @@ mod tests {
             msg: "未登录".to_string(),
         };
         assert!(api_err.is_auth_error());
+        assert!(NeteaseError::NeedLogin.is_auth_error());
     }
 }
@@ -0,0 +1,13 @@
+//! 播放历史持久化相关错误
+
+/// 播放历史读写错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum PlayHistoryError {
+    /// IO 错误
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// 序列化错误
+    #[error("JSON 序列化失败: {0}")]
+    Serde(#[from] serde_json::Error),
+}
@@ -0,0 +1,90 @@
+//! 最小的 CSV 写出（RFC 4180）：逗号分隔、CRLF 换行，必要时给字段加引号
+
+use std::borrow::Cow;
+
+/// 转义单个字段：含逗号、双引号或换行时整体用双引号包裹，内部的双引号写两遍
+pub fn escape_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\r', '\n']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// 一条记录（含行尾 CRLF）
+pub fn record<S: AsRef<str>>(fields: &[S]) -> String {
+    let mut line = fields
+        .iter()
+        .map(|f| escape_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push_str("\r\n");
+    line
+}
+
+/// 解析 [`record`] 写出的内容（仅测试用于回读）
+#[cfg(test)]
+pub fn parse(text: &str) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => fields.push(std::mem::take(&mut field)),
+            (false, '\r') if chars.peek() == Some(&'\n') => {}
+            (false, '\n') => {
+                fields.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut fields));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        records.push(fields);
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_fields_are_written_as_is() {
+        assert_eq!(escape_field("晴天"), "晴天");
+        assert_eq!(escape_field(""), "");
+        assert_eq!(record(&["1", "晴天", ""]), "1,晴天,\r\n");
+    }
+
+    #[test]
+    fn special_characters_are_quoted() {
+        assert_eq!(escape_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("line1\nline2"), "\"line1\nline2\"");
+        assert_eq!(escape_field("cr\r"), "\"cr\r\"");
+        assert_eq!(escape_field("\""), "\"\"\"\"");
+    }
+
+    #[test]
+    fn parse_reads_back_quoted_records() {
+        let fields = ["a,b", "\"q\"", "multi\r\nline", "", "plain"];
+        let text = format!("{}{}", record(&fields), record(&["x"]));
+        assert_eq!(
+            parse(&text),
+            vec![
+                fields.iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+                vec!["x".to_owned()],
+            ]
+        );
+    }
+}
@@ -0,0 +1,580 @@
+//! 歌单导出为 CSV
+//!
+//! 歌曲取自内存中已加载的数据：打开的歌单，或（导出全部时）预加载完成的歌单，未预加载的歌单跳过。
+//! 列表数据里没有的可播放性等字段按 [`DETAIL_BATCH`] 首一批、低优先级补拉，进度显示在后台任务面板；
+//! 补拉失败的批次照常导出，对应列留空。数据齐全后由后台任务逐行写盘（[`write_export`]），
+//! 写完在 reducer 的 select 循环中收尾并提示文件路径（见 `spawn_app_actor`）。
+
+pub mod csv;
+
+use crate::app::{App, PlaylistMode, PreloadStatus, Song, TaskKind, View};
+use crate::core::infra::IdAllocator;
+use crate::core::prelude::{
+    effects::CoreEffects,
+    infra::{RequestKey, RequestTracker},
+};
+use crate::domain::model::{SongAvailability, SongPreview};
+use crate::error::MessageError;
+use crate::netease::actor::NeteaseCommand;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+
+/// 每批补拉详情的歌曲数
+pub const DETAIL_BATCH: usize = 200;
+/// 数据目录下存放导出文件的子目录
+pub const EXPORT_DIR: &str = "exports";
+/// CSV 表头
+pub const HEADER: [&str; 9] = [
+    "id",
+    "title",
+    "artists",
+    "album",
+    "duration_ms",
+    "liked",
+    "cached",
+    "availability",
+    "playlist",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportScope {
+    /// 当前打开的歌单（含每日推荐）
+    OpenPlaylist,
+    /// 全部已加载的歌单
+    AllPlaylists,
+}
+
+/// 导出的一行：歌曲及其所在歌单
+#[derive(Debug, Clone)]
+pub struct ExportRow {
+    pub song: Song,
+    pub liked: bool,
+    pub cached: bool,
+    pub playlist: String,
+}
+
+/// 正在补拉详情的导出
+#[derive(Debug)]
+pub struct CsvExport {
+    task_id: u64,
+    path: PathBuf,
+    rows: Vec<ExportRow>,
+    details: HashMap<i64, SongPreview>,
+    /// 需要补拉详情的歌曲 id（去重）
+    missing: Vec<i64>,
+    cursor: usize,
+    /// 请求中的这一批的歌曲数
+    batch_len: usize,
+    /// 补拉失败的歌曲数
+    failed: usize,
+    skipped_playlists: usize,
+}
+
+/// 数据已齐全、等待写盘的导出
+#[derive(Debug)]
+pub struct ReadyExport {
+    pub summary: ExportSummary,
+    rows: Vec<ExportRow>,
+    details: HashMap<i64, SongPreview>,
+}
+
+/// 导出结果说明（写盘结束后用于提示）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportSummary {
+    pub task_id: u64,
+    pub path: PathBuf,
+    pub songs: usize,
+    /// 详情补拉失败、部分列留空的歌曲数
+    pub incomplete: usize,
+    /// 未预加载完成而跳过的歌单数
+    pub skipped_playlists: usize,
+}
+
+impl ExportSummary {
+    pub fn message(&self) -> String {
+        let mut msg = format!("已导出 {} 首到 {}", self.songs, self.path.display());
+        if self.skipped_playlists > 0 {
+            msg.push_str(&format!(
+                "（跳过 {} 个未加载的歌单）",
+                self.skipped_playlists
+            ));
+        }
+        if self.incomplete > 0 {
+            msg.push_str(&format!("（{} 首详情获取失败）", self.incomplete));
+        }
+        msg
+    }
+}
+
+/// 导出命令或详情响应处理后的下一步
+#[derive(Debug)]
+pub enum ExportStep {
+    /// 与导出无关（或已拒绝开始）
+    Ignored,
+    /// 仍在补拉详情
+    Fetching,
+    /// 可以写盘
+    Ready(ReadyExport),
+}
+
+/// 开始导出：收集歌曲并发出第一批详情请求；无需补拉时直接返回 [`ExportStep::Ready`]
+#[allow(clippy::too_many_arguments)]
+pub fn start_export(
+    scope: ExportScope,
+    app: &mut App,
+    data_dir: &Path,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    job: &mut Option<CsvExport>,
+    effects: &mut CoreEffects,
+) -> ExportStep {
+    let (name, rows, skipped_playlists) = match collect_rows(app, scope) {
+        Ok(collected) => collected,
+        Err(msg) => {
+            effects.toast(msg);
+            return ExportStep::Ignored;
+        }
+    };
+
+    let mut details: HashMap<i64, SongPreview> = HashMap::new();
+    let mut missing = Vec::new();
+    let mut seen = HashSet::new();
+    for row in &rows {
+        let id = row.song.id;
+        if !seen.insert(id) {
+            continue;
+        }
+        match app.search_previews.get(&id) {
+            Some(preview) => {
+                details.insert(id, preview.clone());
+            }
+            None => missing.push(id),
+        }
+    }
+
+    let task_id = app.tasks.register(TaskKind::CsvExport, name.clone());
+    tracing::info!(
+        songs = rows.len(),
+        missing = missing.len(),
+        skipped_playlists,
+        "开始导出 CSV: {name}"
+    );
+    *job = Some(CsvExport {
+        task_id,
+        path: export_path(data_dir, &name, chrono::Local::now()),
+        rows,
+        details,
+        missing,
+        cursor: 0,
+        batch_len: 0,
+        failed: 0,
+        skipped_playlists,
+    });
+    request_next_batch(app, req_ids, request_tracker, job, effects)
+}
+
+/// 处理详情批量请求的响应（`Err` 为该批请求失败）
+#[allow(clippy::too_many_arguments)]
+pub fn handle_details_event(
+    req_id: u64,
+    previews: Result<&[SongPreview], &MessageError>,
+    app: &mut App,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    job: &mut Option<CsvExport>,
+    effects: &mut CoreEffects,
+) -> ExportStep {
+    if !request_tracker.accept(&RequestKey::ExportDetails, req_id) {
+        return ExportStep::Ignored;
+    }
+    let Some(export) = job.as_mut() else {
+        return ExportStep::Ignored;
+    };
+    match previews {
+        Ok(previews) => {
+            for p in previews {
+                export.details.insert(p.id, p.clone());
+            }
+        }
+        Err(error) => {
+            tracing::warn!(
+                req_id,
+                batch = export.batch_len,
+                "导出 CSV：歌曲详情获取失败，对应列留空: {error}"
+            );
+            export.failed += export.batch_len;
+        }
+    }
+    request_next_batch(app, req_ids, request_tracker, job, effects)
+}
+
+/// 放弃补拉中的导出（已开始写盘的不受影响），返回其任务 id
+pub fn cancel_export(
+    request_tracker: &mut RequestTracker<RequestKey>,
+    job: &mut Option<CsvExport>,
+) -> Option<u64> {
+    let export = job.take()?;
+    request_tracker.clear(&RequestKey::ExportDetails);
+    Some(export.task_id)
+}
+
+fn request_next_batch(
+    app: &mut App,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    job: &mut Option<CsvExport>,
+    effects: &mut CoreEffects,
+) -> ExportStep {
+    let Some(export) = job.as_mut() else {
+        return ExportStep::Ignored;
+    };
+    app.tasks.update_progress(
+        export.task_id,
+        export.cursor as u64,
+        Some(export.missing.len() as u64),
+    );
+    if export.cursor >= export.missing.len() {
+        let Some(export) = job.take() else {
+            return ExportStep::Ignored;
+        };
+        return ExportStep::Ready(export.into_ready());
+    }
+
+    let end = (export.cursor + DETAIL_BATCH).min(export.missing.len());
+    let ids = export.missing[export.cursor..end].to_vec();
+    export.batch_len = ids.len();
+    export.cursor = end;
+    let id = request_tracker.issue(RequestKey::ExportDetails, req_ids);
+    effects.send_netease_lo(NeteaseCommand::SongPreviews { req_id: id, ids });
+    effects.emit_state(app);
+    ExportStep::Fetching
+}
+
+impl CsvExport {
+    fn into_ready(self) -> ReadyExport {
+        ReadyExport {
+            summary: ExportSummary {
+                task_id: self.task_id,
+                path: self.path,
+                songs: self.rows.len(),
+                incomplete: self.failed,
+                skipped_playlists: self.skipped_playlists,
+            },
+            rows: self.rows,
+            details: self.details,
+        }
+    }
+}
+
+/// 收集要导出的歌曲，返回（文件名用的名称, 行, 跳过的歌单数）
+fn collect_rows(
+    app: &App,
+    scope: ExportScope,
+) -> Result<(String, Vec<ExportRow>, usize), &'static str> {
+    let open =
+        (app.view == View::Playlists && app.playlist_mode == PlaylistMode::Tracks).then(|| {
+            let name = if app.playlist_tracks_recommend {
+                "每日推荐".to_owned()
+            } else {
+                app.playlists
+                    .get(app.playlists_selected)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_default()
+            };
+            (name, &app.playlist_tracks)
+        });
+    let cached = crate::audio_worker::cached_song_ids(&app.audio_cache_dir);
+    let row = |song: &Song, playlist: &str| ExportRow {
+        song: song.clone(),
+        liked: app.liked_song_ids.contains(&song.id),
+        cached: cached.contains(&song.id),
+        playlist: playlist.to_owned(),
+    };
+
+    match scope {
+        ExportScope::OpenPlaylist => {
+            let Some((name, songs)) = open.filter(|(_, songs)| !songs.is_empty()) else {
+                return Err("请先打开要导出的歌单");
+            };
+            let rows = songs.iter().map(|s| row(s, &name)).collect();
+            Ok((name, rows, 0))
+        }
+        ExportScope::AllPlaylists => {
+            let open_id = open
+                .as_ref()
+                .filter(|_| !app.playlist_tracks_recommend)
+                .and_then(|_| app.playlists.get(app.playlists_selected))
+                .map(|p| p.id);
+            let mut rows = Vec::new();
+            let mut skipped = 0;
+            for playlist in &app.playlists {
+                let songs = if open_id == Some(playlist.id) {
+                    &app.playlist_tracks
+                } else {
+                    match app.playlist_preloads.get(&playlist.id) {
+                        Some(p) if matches!(p.status, PreloadStatus::Completed) => &p.songs,
+                        _ => {
+                            skipped += 1;
+                            continue;
+                        }
+                    }
+                };
+                rows.extend(songs.iter().map(|s| row(s, &playlist.name)));
+            }
+            if rows.is_empty() {
+                return Err("没有已加载的歌单可导出");
+            }
+            Ok(("全部歌单".to_owned(), rows, skipped))
+        }
+    }
+}
+
+/// `{data_dir}/exports/<名称>-<时间>.csv`，名称中不能用于文件名的字符替换为 `_`
+fn export_path(data_dir: &Path, name: &str, now: chrono::DateTime<chrono::Local>) -> PathBuf {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    let stem = if stem.is_empty() { "playlist" } else { &stem };
+    data_dir
+        .join(EXPORT_DIR)
+        .join(format!("{stem}-{}.csv", now.format("%Y%m%d-%H%M%S")))
+}
+
+fn availability_label(availability: SongAvailability) -> &'static str {
+    match availability {
+        SongAvailability::Free => "free",
+        SongAvailability::Vip => "vip",
+        SongAvailability::Paid => "paid",
+        SongAvailability::Unavailable => "unavailable",
+    }
+}
+
+/// 一行的各列；列表与详情都没有的字段留空
+fn row_fields(row: &ExportRow, detail: Option<&SongPreview>) -> [String; 9] {
+    let song = &row.song;
+    let album = if song.album.is_empty() {
+        detail.map(|d| d.album.clone()).unwrap_or_default()
+    } else {
+        song.album.clone()
+    };
+    let duration_ms = song
+        .duration_ms
+        .or_else(|| detail.and_then(|d| d.duration_ms))
+        .map(|ms| ms.to_string())
+        .unwrap_or_default();
+    [
+        song.id.to_string(),
+        song.name.clone(),
+        song.artists.clone(),
+        album,
+        duration_ms,
+        row.liked.to_string(),
+        row.cached.to_string(),
+        detail
+            .map(|d| availability_label(d.availability).to_owned())
+            .unwrap_or_default(),
+        row.playlist.clone(),
+    ]
+}
+
+/// 逐行写出 CSV（UTF-8 带 BOM，便于表格软件识别中文）：先写 `.part` 临时文件，完成后改名
+pub async fn write_export(ready: ReadyExport) -> std::io::Result<()> {
+    let result = write_rows(&ready).await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(part_path(&ready.summary.path)).await;
+    }
+    result
+}
+
+async fn write_rows(ready: &ReadyExport) -> std::io::Result<()> {
+    let path = &ready.summary.path;
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let part = part_path(path);
+    let mut out = tokio::io::BufWriter::new(tokio::fs::File::create(&part).await?);
+    out.write_all("\u{feff}".as_bytes()).await?;
+    out.write_all(csv::record(&HEADER).as_bytes()).await?;
+    for row in &ready.rows {
+        let fields = row_fields(row, ready.details.get(&row.song.id));
+        out.write_all(csv::record(&fields).as_bytes()).await?;
+    }
+    out.flush().await?;
+    out.into_inner().sync_all().await?;
+    tokio::fs::rename(&part, path).await
+}
+
+fn part_path(path: &Path) -> PathBuf {
+    let mut part = path.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::PlaylistPreload;
+    use crate::domain::model::Playlist;
+
+    fn playlist(id: i64, name: &str) -> Playlist {
+        Playlist {
+            id,
+            name: name.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    fn open_playlist_app() -> App {
+        App {
+            view: View::Playlists,
+            playlist_mode: PlaylistMode::Tracks,
+            playlists: vec![playlist(10, "周末, \"精选\"")],
+            playlist_tracks: vec![
+                Song::titled(1, "晴天", "歌手A/歌手B").lasting_ms(200_000),
+                Song::titled(2, "line1\nline2", "歌手A/歌手B").lasting_ms(200_000),
+            ],
+            liked_song_ids: HashSet::from([1]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn open_playlist_export_requires_open_tracks() {
+        let mut app = App::default();
+        let mut job = None;
+        let mut effects = CoreEffects::default();
+        let dir = tempfile::tempdir().unwrap();
+        let step = start_export(
+            ExportScope::OpenPlaylist,
+            &mut app,
+            dir.path(),
+            &IdAllocator::default(),
+            &mut RequestTracker::new(),
+            &mut job,
+            &mut effects,
+        );
+        assert!(matches!(step, ExportStep::Ignored));
+        assert!(job.is_none());
+        assert!(app.tasks.running_id(TaskKind::CsvExport).is_none());
+    }
+
+    #[test]
+    fn all_playlists_skips_unfinished_preloads() {
+        let mut app = App {
+            playlists: vec![playlist(1, "我喜欢"), playlist(2, "未加载")],
+            ..Default::default()
+        };
+        app.playlist_preloads.insert(
+            1,
+            PlaylistPreload {
+                status: PreloadStatus::Completed,
+                songs: vec![Song::titled(7, "七里香", "歌手A/歌手B").lasting_ms(200_000)],
+                missing_count: 0,
+                stats: None,
+                completed_at_ms: None,
+                added_at: HashMap::new(),
+                track_ids_fingerprint: None,
+            },
+        );
+        let (name, rows, skipped) = collect_rows(&app, ExportScope::AllPlaylists).unwrap();
+        assert_eq!(name, "全部歌单");
+        assert_eq!(skipped, 1);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].playlist, "我喜欢");
+    }
+
+    #[tokio::test]
+    async fn written_file_reads_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = open_playlist_app();
+        app.search_previews.insert(
+            1,
+            SongPreview {
+                id: 1,
+                album: "叶惠美".to_owned(),
+                availability: SongAvailability::Free,
+                ..Default::default()
+            },
+        );
+        app.search_previews.insert(
+            2,
+            SongPreview {
+                id: 2,
+                availability: SongAvailability::Unavailable,
+                ..Default::default()
+            },
+        );
+        let mut job = None;
+        let step = start_export(
+            ExportScope::OpenPlaylist,
+            &mut app,
+            dir.path(),
+            &IdAllocator::default(),
+            &mut RequestTracker::new(),
+            &mut job,
+            &mut CoreEffects::default(),
+        );
+        let ExportStep::Ready(ready) = step else {
+            panic!("all details cached, expected ready export, got {step:?}");
+        };
+
+        let summary = ready.summary.clone();
+        write_export(ready).await.unwrap();
+        assert!(summary.path.starts_with(dir.path().join(EXPORT_DIR)));
+        assert!(!part_path(&summary.path).exists());
+
+        let text = std::fs::read_to_string(&summary.path).unwrap();
+        let text = text.strip_prefix('\u{feff}').expect("UTF-8 BOM");
+        assert_eq!(
+            csv::parse(text),
+            vec![
+                HEADER.map(str::to_owned).to_vec(),
+                [
+                    "1",
+                    "晴天",
+                    "歌手A/歌手B",
+                    "叶惠美",
+                    "200000",
+                    "true",
+                    "false",
+                    "free",
+                    "周末, \"精选\"",
+                ]
+                .map(str::to_owned)
+                .to_vec(),
+                [
+                    "2",
+                    "line1\nline2",
+                    "歌手A/歌手B",
+                    "",
+                    "200000",
+                    "false",
+                    "false",
+                    "unavailable",
+                    "周末, \"精选\"",
+                ]
+                .map(str::to_owned)
+                .to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn export_path_replaces_reserved_characters() {
+        let now = chrono::Local::now();
+        let path = export_path(Path::new("/data"), "a/b:c?", now);
+        let name = path.file_name().unwrap().to_str().unwrap();
+        assert!(name.starts_with("a_b_c_-"), "{name}");
+        assert!(name.ends_with(".csv"));
+        assert_eq!(path.parent().unwrap(), Path::new("/data").join(EXPORT_DIR));
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use crate::app::{PlaylistPreload, PreloadStatus, Song, Toast};
+use crate::core::infra::IdAllocator;
+use crate::core::prelude::{
+    app::App,
+    effects::CoreEffects,
+    infra::{RequestKey, RequestTracker},
+    netease::NeteaseCommand,
+};
+use crate::error::MessageError;
+
+/// 已发出、尚未返回的收藏请求
+#[derive(Debug, Clone)]
+pub struct PendingLike {
+    pub song: Song,
+    pub like: bool,
+}
+
+/// 「我喜欢」歌单 id（与预加载的选择规则一致）
+pub fn liked_playlist_id(app: &App) -> Option<i64> {
+    app.playlists
+        .iter()
+        .find(|p| p.special_type == 5 || p.name.contains("我喜欢"))
+        .map(|p| p.id)
+}
+
+/// 切换当前播放歌曲的收藏状态：先乐观更新图标，再调用接口
+pub fn handle_toggle_like(
+    app: &mut App,
+    pending: &mut HashMap<u64, PendingLike>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    if !app.logged_in {
+        effects.toast("登录后才能收藏歌曲");
+        return;
+    }
+    let Some(song) = playing_song(app) else {
+        effects.toast("当前没有正在播放的歌曲");
+        return;
+    };
+
+    let like = !app.liked_song_ids.contains(&song.id);
+    set_liked(app, song.id, like);
+    effects.emit_state(app);
+
+    let id = req_ids.next();
+    tracing::info!(
+        req_id = id,
+        song_id = song.id,
+        like,
+        "❤ [Favorites] 切换收藏"
+    );
+    effects.send_netease_hi_warn(
+        NeteaseCommand::LikeSong {
+            req_id: id,
+            song_id: song.id,
+            like,
+        },
+        "NeteaseActor 通道已关闭：LikeSong 发送失败",
+    );
+    pending.insert(id, PendingLike { song, like });
+}
+
+/// 处理登录后拉取的「我喜欢」id 列表
+/// 返回 true 表示事件已处理，false 表示 req_id 不匹配
+pub fn handle_like_list_event(
+    req_id: u64,
+    ids: &[i64],
+    app: &mut App,
+    pending: &HashMap<u64, PendingLike>,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::LikeList, req_id) {
+        return false;
+    }
+    app.liked_song_ids = ids.iter().copied().collect();
+    // 列表返回前已发出的切换仍以本地为准
+    for p in pending.values() {
+        set_liked(app, p.song.id, p.like);
+    }
+    tracing::info!(count = ids.len(), "❤ [Favorites] 已加载我喜欢列表");
+    effects.emit_state(app);
+    true
+}
+
+/// 收藏接口成功：同步「我喜欢」预加载
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+pub fn handle_song_liked_event(
+    req_id: u64,
+    app: &mut App,
+    pending: &mut HashMap<u64, PendingLike>,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(PendingLike { song, like }) = pending.remove(&req_id) else {
+        return false;
+    };
+    update_liked_preload(app, &song, like);
+    effects.toast(if like {
+        format!("已收藏「{}」到我喜欢", song.name)
+    } else {
+        format!("已从我喜欢移除「{}」", song.name)
+    });
+    effects.emit_state(app);
+    true
+}
+
+/// 收藏接口失败：回滚乐观更新并提示
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+pub fn handle_like_error(
+    req_id: u64,
+    error: &MessageError,
+    app: &mut App,
+    pending: &mut HashMap<u64, PendingLike>,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(PendingLike { song, like }) = pending.remove(&req_id) else {
+        return false;
+    };
+    tracing::warn!(
+        req_id,
+        song_id = song.id,
+        like,
+        "❤ [Favorites] 收藏失败: {error}"
+    );
+    // 同一首歌还有更新的请求在途时，以最新一次操作为准
+    if !pending.values().any(|p| p.song.id == song.id) {
+        set_liked(app, song.id, !like);
+    }
+    let action = if like { "收藏" } else { "取消收藏" };
+    effects.set_toast(Toast::error(format!(
+        "{action}「{}」失败: {error}",
+        song.name
+    )));
+    effects.emit_state(app);
+    true
+}
+
+/// 将收藏变更写入「我喜欢」预加载；预加载未完成时暂存，完成后再应用
+pub fn update_liked_preload(app: &mut App, song: &Song, like: bool) {
+    let Some(playlist_id) = liked_playlist_id(app) else {
+        return;
+    };
+    match app.playlist_preloads.get_mut(&playlist_id) {
+        Some(PlaylistPreload {
+            status: PreloadStatus::Completed,
+            songs,
+            stats,
+            ..
+        }) => {
+            apply_like(songs, song, like);
+            *stats = None;
+        }
+        Some(_) => {
+            app.liked_pending.retain(|(s, _)| s.id != song.id);
+            app.liked_pending.push((song.clone(), like));
+        }
+        None => {}
+    }
+}
+
+/// 「我喜欢」预加载完成时补应用暂存的收藏变更
+pub fn apply_pending_likes(app: &mut App, playlist_id: i64) {
+    if app.liked_pending.is_empty() || liked_playlist_id(app) != Some(playlist_id) {
+        return;
+    }
+    let Some(preload) = app.playlist_preloads.get_mut(&playlist_id) else {
+        return;
+    };
+    for (song, like) in app.liked_pending.drain(..) {
+        apply_like(&mut preload.songs, &song, like);
+    }
+    preload.stats = None;
+}
+
+/// 收藏插入到最前（与网易云「我喜欢」排序一致），取消收藏则移除
+fn apply_like(songs: &mut Vec<Song>, song: &Song, like: bool) {
+    songs.retain(|s| s.id != song.id);
+    if like {
+        songs.insert(0, song.clone());
+    }
+}
+
+fn set_liked(app: &mut App, song_id: i64, like: bool) {
+    if like {
+        app.liked_song_ids.insert(song_id);
+    } else {
+        app.liked_song_ids.remove(&song_id);
+    }
+}
+
+fn playing_song(app: &App) -> Option<Song> {
+    let id = app.play_song_id?;
+    app.play_queue
+        .current()
+        .filter(|s| s.id == id)
+        .or_else(|| app.play_queue.songs().iter().find(|s| s.id == id))
+        .cloned()
+}
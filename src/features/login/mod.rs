@@ -1,3 +1,5 @@
+use crate::app::{PhoneLoginField, PhoneLoginForm, StartupView, Toast, View};
+use crate::core::infra::IdAllocator;
 use crate::core::prelude::{
     app::App,
     effects::CoreEffects,
@@ -5,14 +7,60 @@ use crate::core::prelude::{
     messages::AppCommand,
     netease::{NeteaseCommand, NeteaseEvent},
 };
-use crate::core::utils;
+use crate::netease::actor::CellphoneCredential;
+use std::time::{Duration, Instant};
+
+/// 登录有效期不足该时长时提示重新登录
+pub const LOGIN_EXPIRY_WARN_SECS: i64 = 7 * 24 * 3600;
+
+/// 登录有效期的日期文本（本地时区，如 2025-06-01）
+pub fn login_expiry_date(expires_at: i64) -> String {
+    chrono::DateTime::from_timestamp(expires_at, 0)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// 二维码 key 的有效期（约 5 分钟后扫码状态返回 800）
+pub const QR_KEY_LIFETIME: Duration = Duration::from_secs(5 * 60);
+
+/// 扫码状态：二维码已过期
+const QR_CODE_EXPIRED: i64 = 800;
+
+/// 二维码过期后最多自动重新生成的次数，之后需按 l 手动生成
+const QR_AUTO_REGENERATE_MAX: u8 = 3;
+
+/// 二维码的剩余有效期文本（如 `4:05`），已过期时为 None
+pub fn qr_remaining_text(created_at: Instant, now: Instant) -> Option<String> {
+    let remaining = QR_KEY_LIFETIME.checked_sub(now.saturating_duration_since(created_at))?;
+    let secs = remaining.as_secs();
+    (secs > 0).then(|| format!("{}:{:02}", secs / 60, secs % 60))
+}
+
+fn request_qr_key(
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) {
+    let id = request_tracker.issue(RequestKey::LoginQrKey, req_ids);
+    effects.send_netease_hi_warn(
+        NeteaseCommand::LoginQrKey { req_id: id },
+        "NeteaseActor 通道已关闭：LoginQrKey 发送失败",
+    );
+}
+
+/// 未登录时登录页的默认提示
+const LOGIN_HINT: &str = "按 l 生成二维码；按 c 切换到 Cookie 登录；按 p 手机号登录";
 
 /// 处理登录相关的 AppCommand
 /// 返回 true 表示命令已处理，false 表示需要 continue
 pub async fn handle_login_command(
     cmd: AppCommand,
     app: &mut App,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     request_tracker: &mut RequestTracker<RequestKey>,
     effects: &mut CoreEffects,
 ) -> bool {
@@ -22,27 +70,157 @@ pub async fn handle_login_command(
                 return true; // 已登录，需要 continue
             }
             app.login_status = "正在生成二维码...".to_owned();
+            app.login_qr_auto_regenerations = 0;
             effects.emit_state(app);
-            let id = request_tracker.issue(RequestKey::LoginQrKey, || utils::next_id(req_id));
-            effects.send_netease_hi_warn(
-                NeteaseCommand::LoginQrKey { req_id: id },
-                "NeteaseActor 通道已关闭：LoginQrKey 发送失败",
-            );
+            request_qr_key(req_ids, request_tracker, effects);
         }
         AppCommand::LoginToggleCookieInput => {
             app.login_cookie_input_visible = !app.login_cookie_input_visible;
             app.login_cookie_input.clear();
+            app.login_phone_form = None;
             app.login_status = if app.login_cookie_input_visible {
                 "Cookie 输入模式：输入 MUSIC_U 值".to_owned()
             } else {
-                "按 l 生成二维码；按 c 切换到 Cookie 登录".to_owned()
+                LOGIN_HINT.to_owned()
+            };
+            effects.emit_state(app);
+        }
+        AppCommand::LoginTogglePhoneForm => {
+            app.login_cookie_input_visible = false;
+            app.login_cookie_input.clear();
+            app.login_phone_form = match app.login_phone_form.take() {
+                Some(_) => {
+                    app.login_status = LOGIN_HINT.to_owned();
+                    None
+                }
+                None => {
+                    app.login_status = "手机号登录：输入手机号与密码后回车".to_owned();
+                    Some(PhoneLoginForm::default())
+                }
+            };
+            effects.emit_state(app);
+        }
+        AppCommand::LoginPhoneFieldNext => {
+            if let Some(form) = app.login_phone_form.as_mut() {
+                form.field = match form.field {
+                    PhoneLoginField::Phone => PhoneLoginField::Secret,
+                    PhoneLoginField::Secret => PhoneLoginField::Phone,
+                };
+                effects.emit_state(app);
+            }
+        }
+        AppCommand::LoginPhoneToggleCaptcha => {
+            if let Some(form) = app.login_phone_form.as_mut() {
+                form.captcha = !form.captcha;
+                form.secret.clear();
+                app.login_status = if form.captcha {
+                    "验证码登录：按 Ctrl+S 发送验证码，输入后回车".to_owned()
+                } else {
+                    "密码登录：输入手机号与密码后回车".to_owned()
+                };
+                effects.emit_state(app);
+            }
+        }
+        AppCommand::LoginPhoneInputChar { c } => {
+            if let Some(form) = app.login_phone_form.as_mut() {
+                push_phone_form_input(form, &c.to_string());
+                effects.emit_state(app);
+            }
+        }
+        AppCommand::LoginPhoneInputString { s } => {
+            if let Some(form) = app.login_phone_form.as_mut() {
+                push_phone_form_input(form, &s);
+                effects.emit_state(app);
+            }
+        }
+        AppCommand::LoginPhoneInputBackspace => {
+            if let Some(form) = app.login_phone_form.as_mut() {
+                match form.field {
+                    PhoneLoginField::Phone => form.phone.pop(),
+                    PhoneLoginField::Secret => form.secret.pop(),
+                };
+                effects.emit_state(app);
+            }
+        }
+        AppCommand::LoginPhoneSendCaptcha => {
+            let Some(form) = app.login_phone_form.as_mut() else {
+                return true;
+            };
+            let Some((country_code, phone)) = form.parse_phone() else {
+                form.field = PhoneLoginField::Phone;
+                app.login_status = "请输入手机号".to_owned();
+                effects.emit_state(app);
+                return true;
+            };
+            form.captcha = true;
+            form.secret.clear();
+            form.field = PhoneLoginField::Secret;
+            app.login_status = "正在发送验证码...".to_owned();
+            effects.emit_state(app);
+            let id = request_tracker.issue(RequestKey::LoginCaptchaSend, req_ids);
+            effects.send_netease_hi_warn(
+                NeteaseCommand::LoginCaptchaSend {
+                    req_id: id,
+                    phone,
+                    country_code,
+                },
+                "NeteaseActor 通道已关闭：LoginCaptchaSend 发送失败",
+            );
+        }
+        AppCommand::LoginPhoneSubmit => {
+            let Some(form) = app.login_phone_form.as_mut() else {
+                return true;
+            };
+            let Some((country_code, phone)) = form.parse_phone() else {
+                form.field = PhoneLoginField::Phone;
+                app.login_status = "请输入手机号".to_owned();
+                effects.emit_state(app);
+                return true;
+            };
+            if form.secret.is_empty() {
+                form.field = PhoneLoginField::Secret;
+                app.login_status = if form.captcha {
+                    "请输入验证码"
+                } else {
+                    "请输入密码"
+                }
+                .to_owned();
+                effects.emit_state(app);
+                return true;
+            }
+            let id = request_tracker.issue(RequestKey::LoginCellphone, req_ids);
+            let cmd = if form.captcha {
+                // 先校验验证码，通过后再登录（见 LoginCaptchaVerified）
+                app.login_status = "正在校验验证码...".to_owned();
+                NeteaseCommand::LoginCaptchaVerify {
+                    req_id: id,
+                    phone,
+                    country_code,
+                    captcha: form.secret.clone(),
+                }
+            } else {
+                // 密码只以摘要形式离开表单
+                let password_md5 = crate::netease::password_md5(&form.secret);
+                form.secret.clear();
+                app.login_status = "正在登录...".to_owned();
+                NeteaseCommand::LoginCellphone {
+                    req_id: id,
+                    phone,
+                    country_code,
+                    credential: CellphoneCredential::PasswordMd5(password_md5),
+                }
             };
             effects.emit_state(app);
+            effects.send_netease_hi_warn(cmd, "NeteaseActor 通道已关闭：手机号登录请求发送失败");
         }
         AppCommand::LoginCookieInputChar { c } => {
             app.login_cookie_input.push(c);
             effects.emit_state(app);
         }
+        AppCommand::LoginCookieInputString { s } => {
+            app.login_cookie_input.push_str(&s);
+            effects.emit_state(app);
+        }
         AppCommand::LoginCookieInputBackspace => {
             app.login_cookie_input.pop();
             effects.emit_state(app);
@@ -56,7 +234,7 @@ pub async fn handle_login_command(
             }
             app.login_status = "正在验证 Cookie...".to_owned();
             effects.emit_state(app);
-            let id = request_tracker.issue(RequestKey::LoginSetCookie, || utils::next_id(req_id));
+            let id = request_tracker.issue(RequestKey::LoginSetCookie, req_ids);
             effects.send_netease_hi_warn(
                 NeteaseCommand::LoginSetCookie {
                     req_id: id,
@@ -70,12 +248,28 @@ pub async fn handle_login_command(
     false
 }
 
+/// 接口返回 code 301 或刷新登录态被拒绝：登录已失效（或游客用了需登录的功能），回到登录页重新登录
+pub fn handle_need_login(app: &mut App, effects: &mut CoreEffects) {
+    let message = if app.logged_in {
+        "登录已失效，请重新登录"
+    } else {
+        "该功能需要登录，请先登录"
+    };
+    tracing::warn!(logged_in = app.logged_in, "接口要求登录，回到登录页");
+    app.logged_in = false;
+    app.login_expires_at = None;
+    app.switch_view(View::Login);
+    app.login_status = format!("{message}：{LOGIN_HINT}");
+    effects.set_toast(Toast::error(message));
+    effects.emit_state(app);
+}
+
 /// 处理登录相关的 NeteaseEvent
 /// 返回 true 表示事件已处理，false 表示需要跳过
 pub async fn handle_login_event(
     evt: &NeteaseEvent,
     app: &mut App,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     request_tracker: &mut RequestTracker<RequestKey>,
     effects: &mut CoreEffects,
 ) -> bool {
@@ -87,10 +281,10 @@ pub async fn handle_login_event(
             tracing::debug!(req_id = evt_req_id, logged_in, "NeteaseActor: ClientReady");
             app.logged_in = *logged_in;
             if app.logged_in {
-                app.view = crate::app::View::Playlists;
+                enter_startup_view(app);
                 app.playlists_status = "已登录（已从本地状态恢复），正在加载账号信息...".to_owned();
                 effects.emit_state(app);
-                let id = request_tracker.issue(RequestKey::Account, || utils::next_id(req_id));
+                let id = request_tracker.issue(RequestKey::Account, req_ids);
                 effects.send_netease_hi_warn(
                     NeteaseCommand::UserAccount { req_id: id },
                     "NeteaseActor 通道已关闭：UserAccount 发送失败",
@@ -101,6 +295,10 @@ pub async fn handle_login_event(
             }
             true
         }
+        NeteaseEvent::SessionExpired => {
+            handle_need_login(app, effects);
+            true
+        }
         NeteaseEvent::LoginQrKey { req_id: id, unikey } => {
             if !request_tracker.accept(&RequestKey::LoginQrKey, *id) {
                 tracing::debug!(req_id = id, "LoginQrKey 响应过期，丢弃");
@@ -109,7 +307,12 @@ pub async fn handle_login_event(
             app.login_unikey = Some(unikey.clone());
             app.login_qr_url = Some(format!("https://music.163.com/login?codekey={unikey}"));
             app.login_qr_ascii = Some(render_qr_ascii(app.login_qr_url.as_deref().unwrap_or("")));
-            app.login_status = "请用网易云 APP 扫码；扫码后会自动轮询状态".to_owned();
+            app.login_qr_created_at = Some(Instant::now());
+            app.login_status = if app.login_qr_auto_regenerations > 0 {
+                "二维码已重新生成，请用网易云 APP 扫码".to_owned()
+            } else {
+                "请用网易云 APP 扫码；扫码后会自动轮询状态".to_owned()
+            };
             app.logged_in = false;
             effects.emit_state(app);
             true
@@ -122,15 +325,28 @@ pub async fn handle_login_event(
             if status.logged_in {
                 app.logged_in = true;
                 app.login_status = "登录成功".to_owned();
-                app.view = crate::app::View::Playlists;
+                app.switch_view(crate::app::View::Playlists);
                 app.playlists_status = "登录成功，正在加载账号信息...".to_owned();
                 effects.emit_state(app);
                 effects.toast("扫码登录成功");
-                let id = request_tracker.issue(RequestKey::Account, || utils::next_id(req_id));
+                let id = request_tracker.issue(RequestKey::Account, req_ids);
                 effects.send_netease_hi_warn(
                     NeteaseCommand::UserAccount { req_id: id },
                     "NeteaseActor 通道已关闭：UserAccount 发送失败",
                 );
+            } else if status.code == QR_CODE_EXPIRED {
+                app.login_unikey = None;
+                app.login_qr_url = None;
+                app.login_qr_ascii = None;
+                app.login_qr_created_at = None;
+                if app.login_qr_auto_regenerations < QR_AUTO_REGENERATE_MAX {
+                    app.login_qr_auto_regenerations += 1;
+                    app.login_status = "二维码已过期，正在重新生成...".to_owned();
+                    request_qr_key(req_ids, request_tracker, effects);
+                } else {
+                    app.login_status = "二维码已过期，按 l 重新生成".to_owned();
+                }
+                effects.emit_state(app);
             } else {
                 app.login_status = format!("扫码状态 code={} {}", status.code, status.message);
                 effects.emit_state(app);
@@ -151,11 +367,11 @@ pub async fn handle_login_event(
                 app.login_cookie_input_visible = false;
                 app.logged_in = true;
                 app.login_status = message.clone();
-                app.view = crate::app::View::Playlists;
+                app.switch_view(crate::app::View::Playlists);
                 app.playlists_status = "登录成功，正在加载账号信息...".to_owned();
                 effects.emit_state(app);
                 effects.toast("Cookie 登录成功");
-                let id = request_tracker.issue(RequestKey::Account, || utils::next_id(req_id));
+                let id = request_tracker.issue(RequestKey::Account, req_ids);
                 effects.send_netease_hi_warn(
                     NeteaseCommand::UserAccount { req_id: id },
                     "NeteaseActor 通道已关闭：UserAccount 发送失败",
@@ -169,9 +385,85 @@ pub async fn handle_login_event(
             }
             true
         }
+        NeteaseEvent::LoginCaptchaSent { req_id: id } => {
+            if !request_tracker.accept(&RequestKey::LoginCaptchaSend, *id) {
+                tracing::debug!(req_id = id, "LoginCaptchaSent 响应过期，丢弃");
+                return false;
+            }
+            app.login_status = "验证码已发送，请查收短信后输入并回车".to_owned();
+            effects.emit_state(app);
+            effects.toast("验证码已发送");
+            true
+        }
+        NeteaseEvent::LoginCaptchaVerified { req_id: id } => {
+            if !request_tracker.accept(&RequestKey::LoginCellphone, *id) {
+                tracing::debug!(req_id = id, "LoginCaptchaVerified 响应过期，丢弃");
+                return false;
+            }
+            // 表单已关闭或已改为密码登录：放弃本次登录
+            let Some(form) = app.login_phone_form.as_ref().filter(|f| f.captcha) else {
+                return true;
+            };
+            let Some((country_code, phone)) = form.parse_phone() else {
+                return true;
+            };
+            let captcha = form.secret.clone();
+            app.login_status = "正在登录...".to_owned();
+            effects.emit_state(app);
+            let id = request_tracker.issue(RequestKey::LoginCellphone, req_ids);
+            effects.send_netease_hi_warn(
+                NeteaseCommand::LoginCellphone {
+                    req_id: id,
+                    phone,
+                    country_code,
+                    credential: CellphoneCredential::Captcha(captcha),
+                },
+                "NeteaseActor 通道已关闭：LoginCellphone 发送失败",
+            );
+            true
+        }
+        NeteaseEvent::LoginCellphoneDone {
+            req_id: id,
+            nickname,
+        } => {
+            if !request_tracker.accept(&RequestKey::LoginCellphone, *id) {
+                tracing::debug!(req_id = id, "LoginCellphoneDone 响应过期，丢弃");
+                return false;
+            }
+            app.login_phone_form = None;
+            app.logged_in = true;
+            app.login_status = format!("登录成功: {nickname}");
+            app.switch_view(crate::app::View::Playlists);
+            app.playlists_status = "登录成功，正在加载账号信息...".to_owned();
+            effects.emit_state(app);
+            effects.toast("手机号登录成功");
+            let id = request_tracker.issue(RequestKey::Account, req_ids);
+            effects.send_netease_hi_warn(
+                NeteaseCommand::UserAccount { req_id: id },
+                "NeteaseActor 通道已关闭：UserAccount 发送失败",
+            );
+            true
+        }
+        NeteaseEvent::Error { req_id, error }
+            if request_tracker.get_pending(&RequestKey::LoginCaptchaSend) == Some(*req_id) =>
+        {
+            request_tracker.clear(&RequestKey::LoginCaptchaSend);
+            app.login_status = format!("验证码发送失败: {error}");
+            effects.emit_state(app);
+            true
+        }
+        NeteaseEvent::Error { req_id, error }
+            if request_tracker.get_pending(&RequestKey::LoginCellphone) == Some(*req_id) =>
+        {
+            request_tracker.clear(&RequestKey::LoginCellphone);
+            app.login_status = format!("登录失败: {error}");
+            effects.emit_state(app);
+            true
+        }
         NeteaseEvent::Account {
             req_id: id,
             account,
+            login_expires_at,
         } => {
             if !request_tracker.accept(&RequestKey::Account, *id) {
                 tracing::debug!(req_id = id, "Account 响应过期，丢弃");
@@ -179,11 +471,20 @@ pub async fn handle_login_event(
             }
             app.account_uid = Some(account.uid);
             app.account_nickname = Some(account.nickname.clone());
+            app.login_expires_at = *login_expires_at;
+            if let Some(at) = *login_expires_at
+                && at - chrono::Utc::now().timestamp() < LOGIN_EXPIRY_WARN_SECS
+            {
+                effects.set_toast(Toast::warning(format!(
+                    "登录将于 {} 过期，请在 设置 → 账号 中退出后重新扫码登录",
+                    login_expiry_date(at)
+                )));
+            }
             app.playlists_status = "正在加载用户歌单...".to_owned();
             effects.emit_state(app);
             // 发送 UserPlaylists 请求
             let key = RequestKey::Playlists;
-            let id = request_tracker.issue(key, || utils::next_id(req_id));
+            let id = request_tracker.issue(key, req_ids);
             effects.send_netease_hi_warn(
                 NeteaseCommand::UserPlaylists {
                     req_id: id,
@@ -191,21 +492,52 @@ pub async fn handle_login_event(
                 },
                 "NeteaseActor 通道已关闭：UserPlaylists 发送失败",
             );
+            let id = request_tracker.issue(RequestKey::LikeList, req_ids);
+            effects.send_netease_lo(NeteaseCommand::LikeList {
+                req_id: id,
+                uid: account.uid,
+            });
             true
         }
         _ => false,
     }
 }
 
+/// 向手机号登录表单当前的输入框追加文字：手机号只接受数字、`+`、空格与 `-`，
+/// 验证码只接受数字，密码接受除控制字符外的任意字符
+fn push_phone_form_input(form: &mut PhoneLoginForm, s: &str) {
+    match form.field {
+        PhoneLoginField::Phone => form.phone.extend(
+            s.chars()
+                .filter(|c| c.is_ascii_digit() || matches!(c, '+' | ' ' | '-')),
+        ),
+        PhoneLoginField::Secret if form.captcha => {
+            form.secret.extend(s.chars().filter(char::is_ascii_digit))
+        }
+        PhoneLoginField::Secret => form.secret.extend(s.chars().filter(|c| !c.is_control())),
+    }
+}
+
 /// 处理 QrPoll 定时器事件
+/// 按「启动页面」设置进入已登录后的首个页面
+fn enter_startup_view(app: &mut App) {
+    let view = match app.startup_view {
+        StartupView::Last => app.last_view.unwrap_or(View::Playlists),
+        StartupView::Playlists => View::Playlists,
+        StartupView::Search => View::Search,
+        StartupView::NowPlaying => View::Lyrics,
+    };
+    app.switch_view(view);
+}
+
 pub fn handle_qr_poll(
     app: &App,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     request_tracker: &mut RequestTracker<RequestKey>,
     effects: &mut CoreEffects,
 ) {
     if let Some(key) = app.login_unikey.as_ref().filter(|_| !app.logged_in) {
-        let id = request_tracker.issue(RequestKey::LoginQrPoll, || utils::next_id(req_id));
+        let id = request_tracker.issue(RequestKey::LoginQrPoll, req_ids);
         effects.send_netease_hi_warn(
             NeteaseCommand::LoginQrCheck {
                 req_id: id,
@@ -2,43 +2,67 @@
 
 pub fn reset_app_after_logout(app: &mut App) {
     app.logged_in = false;
-    app.view = View::Login;
+    app.switch_view(View::Login);
+    app.view_focus.clear();
 
     app.login_qr_url = None;
     app.login_qr_ascii = None;
     app.login_unikey = None;
+    app.login_qr_created_at = None;
+    app.login_phone_form = None;
     app.login_status = "按 l 生成二维码；q 退出；Tab 切换页面".to_owned();
 
     app.account_uid = None;
     app.account_nickname = None;
+    app.login_expires_at = None;
+    app.liked_song_ids.clear();
+    app.liked_pending.clear();
     app.playlists.clear();
     app.playlists_selected = 0;
     app.playlist_mode = PlaylistMode::List;
     app.playlist_tracks.clear();
     app.playlist_tracks_selected = 0;
+    app.playlist_tracks_stats = None;
+    app.playlist_tracks_recommend = false;
+    app.playlist_tracks_order_keys = Default::default();
+    app.playlist_track_orders.clear();
     app.playlists_status = "等待登录后加载歌单".to_owned();
 
+    app.playlist_picker = None;
+    app.unavailable_review = None;
     app.playlist_preloads.clear();
     app.preload_summary.clear();
 
     app.search_results.clear();
+    app.search_marked.clear();
     app.search_selected = 0;
     app.search_status = "输入关键词，回车搜索".to_owned();
+    app.search_query.clear();
+    app.search_has_more = false;
+    app.search_albums.clear();
+    app.search_artists.clear();
+    app.search_opened = None;
+    app.search_collection_selected = 0;
 
     app.play_queue.clear();
+    app.queue_undo = None;
+    app.fm = None;
     app.now_playing = None;
     app.play_status = "未播放".to_owned();
     app.paused = false;
     app.play_started_at = None;
+    app.play_position_ms = None;
     app.play_total_ms = None;
     app.play_paused_at = None;
     app.play_paused_accum_ms = 0;
     app.play_id = None;
     app.play_song_id = None;
+    app.play_actual_br = None;
     app.play_error_count = 0;
 
     app.lyrics_song_id = None;
-    app.lyrics.clear();
+    app.lyrics_song_offset_ms = None;
+    app.lyrics = Default::default();
     app.lyrics_status = "暂无歌词".to_owned();
     app.lyrics_follow = true;
     app.lyrics_selected = 0;
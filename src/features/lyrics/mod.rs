@@ -1,3 +1,4 @@
+use crate::app::LyricsDisplay;
 use crate::core::prelude::{
     app::App,
     effects::CoreEffects,
@@ -53,9 +54,47 @@ pub async fn handle_lyrics_command(
                 effects.emit_state(app);
             }
         }
+        AppCommand::LyricsCycleDisplay => {
+            if matches!(app.view, crate::app::View::Lyrics) {
+                app.lyrics_display = app.lyrics_display.next();
+                let display = app.lyrics_display;
+                let missing = match display {
+                    LyricsDisplay::Original => None,
+                    LyricsDisplay::Translation => Some("翻译"),
+                    LyricsDisplay::Roman => Some("罗马音"),
+                }
+                .filter(|_| {
+                    !app.lyrics.is_empty()
+                        && app.lyrics.iter().all(|l| display.secondary(l).is_none())
+                });
+                app.lyrics_status = match missing {
+                    Some(kind) => format!("歌词显示: {}（当前歌曲没有{kind}）", display.label()),
+                    None => format!("歌词显示: {}", display.label()),
+                };
+                effects.emit_state(app);
+            }
+        }
         AppCommand::LyricsOffsetAddMs { ms } => {
             if matches!(app.view, crate::app::View::Lyrics) {
-                app.lyrics_offset_ms = app.lyrics_offset_ms.saturating_add(ms);
+                match app.play_song_id {
+                    // 正在播放：只调整这首歌
+                    Some(song_id) => {
+                        let offset_ms = settings
+                            .lyrics_song_offsets
+                            .get(song_id)
+                            .unwrap_or(app.lyrics_offset_ms)
+                            .saturating_add(ms);
+                        settings.lyrics_song_offsets.set(song_id, offset_ms);
+                        if app.lyrics_song_id == Some(song_id) {
+                            app.lyrics_song_offset_ms = Some(offset_ms);
+                        }
+                        app.lyrics_status = format!("本曲歌词 offset: {offset_ms}ms");
+                    }
+                    None => {
+                        app.lyrics_offset_ms = app.lyrics_offset_ms.saturating_add(ms);
+                        app.lyrics_status = format!("全局歌词 offset: {}ms", app.lyrics_offset_ms);
+                    }
+                }
                 sync_settings_from_app(settings, app);
                 if let Err(e) = settings::save_settings(data_dir, settings) {
                     tracing::warn!(err = %e, "保存设置失败");
@@ -78,6 +117,7 @@ pub async fn handle_lyric_event(
     song_id: i64,
     lyrics: Vec<crate::domain::model::LyricLine>,
     app: &mut App,
+    settings: &mut settings::AppSettings,
     request_tracker: &mut RequestTracker<RequestKey>,
     effects: &mut CoreEffects,
 ) -> bool {
@@ -85,7 +125,9 @@ pub async fn handle_lyric_event(
         return false;
     }
     app.lyrics_song_id = Some(song_id);
-    app.lyrics = lyrics;
+    // 只在内存中更新使用顺序，随下次保存设置写入
+    app.lyrics_song_offset_ms = settings.lyrics_song_offsets.touch(song_id);
+    app.lyrics = lyrics.into();
     app.lyrics_selected = 0;
     app.lyrics_status = if app.lyrics.is_empty() {
         "暂无歌词".to_owned()
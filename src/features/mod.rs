@@ -1,3 +1,5 @@
+pub mod export;
+pub mod favorites;
 pub mod login;
 pub mod logout;
 pub mod lyrics;
@@ -0,0 +1,72 @@
+//! A-B 循环：反复播放当前歌曲中 A、B 两点之间的片段
+//!
+//! `,` / `.` 以当前播放位置设置 A / B 点（B 必须在 A 之后）。两点都设置后，每次收到
+//! 播放进度时检查是否越过 B 点，越过则跳回 A 点。换歌时自动清除。
+
+use crate::core::prelude::{app::App, effects::CoreEffects};
+use crate::features::player::playback::seek_absolute;
+
+fn fmt_point(ms: u64) -> String {
+    let secs = ms / 1000;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// 以当前播放位置设置 A 点；已有的 B 点不在新 A 点之后时一并清除
+pub fn set_point_a(app: &mut App) {
+    if app.play_started_at.is_none() {
+        return;
+    }
+    let a = app.playback_elapsed_ms();
+    app.loop_a_ms = Some(a);
+    app.play_status = if app.loop_b_ms.take_if(|b| *b <= a).is_some() {
+        format!("A 点 {}，已清除原来的 B 点", fmt_point(a))
+    } else if let Some(b) = app.loop_b_ms {
+        format!("A-B 循环 {} → {}", fmt_point(a), fmt_point(b))
+    } else {
+        format!("A 点 {}（. 设置 B 点）", fmt_point(a))
+    };
+}
+
+/// 以当前播放位置设置 B 点；须先设置 A 点，且 B 在 A 之后，否则忽略
+pub fn set_point_b(app: &mut App) {
+    if app.play_started_at.is_none() {
+        return;
+    }
+    let b = app.playback_elapsed_ms();
+    app.play_status = match app.loop_a_ms {
+        None => "请先按 , 设置 A 点".to_owned(),
+        Some(a) if b <= a => "B 点须在 A 点之后，已忽略".to_owned(),
+        Some(a) => {
+            app.loop_b_ms = Some(b);
+            format!("A-B 循环 {} → {}（; 取消）", fmt_point(a), fmt_point(b))
+        }
+    };
+}
+
+/// 手动取消 A-B 循环
+pub fn clear(app: &mut App) {
+    let had_loop = app.loop_a_ms.is_some() || app.loop_b_ms.is_some();
+    clear_points(app);
+    if had_loop {
+        app.play_status = "已取消 A-B 循环".to_owned();
+    }
+}
+
+/// 清除 A、B 点（换歌时调用）
+pub fn clear_points(app: &mut App) {
+    app.loop_a_ms = None;
+    app.loop_b_ms = None;
+}
+
+/// 收到播放进度后调用：越过 B 点时跳回 A 点；返回是否发出了 Seek
+pub fn on_position(app: &mut App, effects: &mut CoreEffects) -> bool {
+    let (Some(a), Some(b)) = (app.loop_a_ms, app.loop_b_ms) else {
+        return false;
+    };
+    if app.paused || app.playback_elapsed_ms() < b {
+        return false;
+    }
+    tracing::debug!(a, b, "🎵 [PlayerAudio] A-B 循环跳回 A 点");
+    seek_absolute(app, effects, a);
+    true
+}
@@ -1,3 +1,6 @@
+use crate::app::{SongFailureKind, TaskKind, TaskRegistry, Toast};
+use crate::cooldown::minutes_label;
+use crate::core::infra::IdAllocator;
 use crate::core::prelude::{
     app::App,
     audio::{
@@ -8,11 +11,15 @@ use crate::core::prelude::{
     infra::{NextSongCacheManager, RequestKey, RequestTracker},
     netease::NeteaseCommand,
 };
-use crate::core::utils;
-use crate::features::player::playback::play_next;
+use crate::features::player::playback::{advance_to_queued, play_next};
+use crate::features::player::{ab_loop, cache_status, download};
+use crate::features::settings::br_label;
 use std::time::{Duration, Instant};
 
-fn format_bytes(bytes: u64) -> String {
+/// 等待开始播放时缓冲状态的最短刷新间隔
+const BUFFERING_STATUS_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn format_bytes(bytes: u64) -> String {
     const KB: f64 = 1024.0;
     const MB: f64 = 1024.0 * 1024.0;
     const GB: f64 = 1024.0 * 1024.0 * 1024.0;
@@ -43,6 +50,62 @@ fn format_download_progress(downloaded_bytes: u64, total_bytes: Option<u64>) ->
     }
 }
 
+/// 紧凑的字节数，如 "890KB" / "4.6MB"（缓冲状态一行内显示）
+fn format_bytes_compact(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * 1024;
+    const GB: u64 = 1024 * 1024 * 1024;
+
+    if bytes >= GB {
+        format!("{:.1}GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.1}MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{}KB", bytes / KB)
+    } else {
+        format!("{bytes}B")
+    }
+}
+
+fn format_eta(secs: u64) -> String {
+    if secs >= 60 {
+        format!("约{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("约{secs}s")
+    }
+}
+
+/// 等待开始播放时的缓冲状态，如 "缓冲中 1.2MB/4.6MB · 890KB/s · 约4s: 歌名"
+/// 总大小未知时不显示 ETA；速度为 0 时显示网络停滞
+fn format_buffering_status(
+    title: &str,
+    downloaded_bytes: u64,
+    total_bytes: Option<u64>,
+    bytes_per_sec: Option<u64>,
+) -> String {
+    let total_bytes = total_bytes.filter(|total| *total > 0);
+    let mut parts = vec![match total_bytes {
+        Some(total) => format!(
+            "{}/{}",
+            format_bytes_compact(downloaded_bytes),
+            format_bytes_compact(total)
+        ),
+        None => format_bytes_compact(downloaded_bytes),
+    }];
+    match bytes_per_sec {
+        Some(0) => parts.push("网络停滞…".to_owned()),
+        Some(speed) => {
+            parts.push(format!("{}/s", format_bytes_compact(speed)));
+            if let Some(total) = total_bytes {
+                let remaining = total.saturating_sub(downloaded_bytes);
+                parts.push(format_eta(remaining.div_ceil(speed)));
+            }
+        }
+        None => {}
+    }
+    format!("缓冲中 {}: {title}", parts.join(" · "))
+}
+
 fn format_playback_status(paused: bool, stream_hint: Option<&AudioStreamHint>) -> String {
     let base = if paused { "已暂停" } else { "播放中" };
     let Some(hint) = stream_hint else {
@@ -64,6 +127,14 @@ fn format_playback_status(paused: bool, stream_hint: Option<&AudioStreamHint>) -
     }
 }
 
+/// 实际音质低于设置时在状态后注明，如「播放中 (320k 降级)」
+fn with_downgrade_note(app: &App, status: String) -> String {
+    match app.play_actual_br.filter(|&br| br < app.play_br) {
+        Some(br) => format!("{status} ({} 降级)", br_label(br)),
+        None => status,
+    }
+}
+
 fn format_loading_status(
     title: &str,
     stage: &AudioLoadStage,
@@ -85,12 +156,13 @@ fn format_loading_status(
             AudioLoadStage::Downloading {
                 downloaded_bytes,
                 total_bytes,
+                bytes_per_sec,
             } => {
-                let progress = format_download_progress(*downloaded_bytes, *total_bytes);
                 if is_currently_playing {
+                    let progress = format_download_progress(*downloaded_bytes, *total_bytes);
                     format!("播放中，后台下载 {progress}，暂不可拖动: {title}")
                 } else {
-                    format!("预缓冲中 {progress}，达到可播后自动开始: {title}")
+                    format_buffering_status(title, *downloaded_bytes, *total_bytes, *bytes_per_sec)
                 }
             }
             AudioLoadStage::PreparingPlayback => {
@@ -119,6 +191,14 @@ fn format_loading_status(
         AudioLoadStage::Downloading {
             downloaded_bytes,
             total_bytes,
+            bytes_per_sec,
+        } if !is_currently_playing => {
+            format_buffering_status(title, *downloaded_bytes, *total_bytes, *bytes_per_sec)
+        }
+        AudioLoadStage::Downloading {
+            downloaded_bytes,
+            total_bytes,
+            ..
         } => format!(
             "下载中 {}: {title}",
             format_download_progress(*downloaded_bytes, *total_bytes)
@@ -133,10 +213,56 @@ fn format_loading_status(
     }
 }
 
+/// 将音频加载阶段同步到后台任务登记表
+fn track_download_task(app: &mut App, song_id: i64, title: &str, stage: &AudioLoadStage) {
+    finish_download_tasks(&mut app.tasks, Some(song_id), TaskRegistry::cancel);
+    let kind = TaskKind::AudioDownload { song_id };
+    match stage {
+        AudioLoadStage::DownloadQueued | AudioLoadStage::Retrying { .. } => {
+            app.tasks.register(kind, title);
+        }
+        AudioLoadStage::Downloading {
+            downloaded_bytes,
+            total_bytes,
+            ..
+        } => {
+            let id = app.tasks.register(kind, title);
+            app.tasks
+                .update_progress(id, *downloaded_bytes, *total_bytes);
+        }
+        AudioLoadStage::CacheHit => {}
+        AudioLoadStage::PreparingPlayback => {
+            if let Some(id) = app.tasks.running_id(kind) {
+                app.tasks.complete(id);
+            }
+        }
+    }
+}
+
+/// 结束运行中的下载任务；`keep` 指定的歌曲不受影响
+fn finish_download_tasks(
+    tasks: &mut TaskRegistry,
+    keep: Option<i64>,
+    finish: impl Fn(&mut TaskRegistry, u64),
+) {
+    let ids: Vec<u64> = tasks
+        .tasks()
+        .iter()
+        .filter(|t| t.is_running())
+        .filter_map(|t| match t.kind {
+            TaskKind::AudioDownload { song_id } if Some(song_id) != keep => Some(t.id),
+            _ => None,
+        })
+        .collect();
+    for id in ids {
+        finish(tasks, id);
+    }
+}
+
 pub struct AudioEventCtx<'a> {
     pub request_tracker: &'a mut RequestTracker<RequestKey>,
     pub song_request_titles: &'a mut std::collections::HashMap<i64, String>,
-    pub req_id: &'a mut u64,
+    pub req_ids: &'a IdAllocator,
     pub next_song_cache: &'a mut NextSongCacheManager,
 }
 
@@ -156,13 +282,44 @@ fn restore_pending_seek_if_possible(
         "AudioWorker 通道已关闭：SeekToMs 发送失败",
     );
 
-    let now = Instant::now();
-    app.play_started_at = Some(now - Duration::from_millis(seek_ms));
-    app.play_paused_at = if app.paused { Some(now) } else { None };
-    app.play_paused_accum_ms = 0;
+    app.set_playback_position(seek_ms);
+    app.play_seek_at = Some(Instant::now());
     Some(seek_ms)
 }
 
+/// 引擎上报的位置与本地计时相差不超过该值时不校正（避免进度条来回跳动）
+const POSITION_DRIFT_TOLERANCE_MS: u64 = 250;
+/// 发出 Seek 后的这段时间内忽略位置上报（可能是 Seek 之前采样的）
+const SEEK_SETTLE: Duration = Duration::from_millis(1_000);
+
+/// 处理 `AudioEvent::Position`：用引擎的实际位置校正本地计时（Seek、缓冲停滞后会漂移）
+///
+/// 返回是否做了校正（需要刷新 UI）。旧 play_id、暂停中、等待恢复进度或刚 Seek 时忽略。
+pub fn apply_position(app: &mut App, play_id: u64, position_ms: u64) -> bool {
+    if app.play_id != Some(play_id) || app.play_started_at.is_none() {
+        return false;
+    }
+    app.play_position_ms = Some(position_ms);
+    if app.paused
+        || app.pending_seek_ms.is_some()
+        || app.play_seek_at.is_some_and(|t| t.elapsed() < SEEK_SETTLE)
+    {
+        return false;
+    }
+    let local_ms = app.playback_elapsed_ms();
+    if local_ms.abs_diff(position_ms) <= POSITION_DRIFT_TOLERANCE_MS {
+        return false;
+    }
+    tracing::debug!(
+        play_id,
+        local_ms,
+        position_ms,
+        "🎵 [PlayerAudio] 校正播放进度"
+    );
+    app.set_playback_position(position_ms);
+    true
+}
+
 /// 处理音频事件
 pub async fn handle_audio_event(
     app: &mut App,
@@ -178,8 +335,22 @@ pub async fn handle_audio_event(
             stream_hint,
         } => {
             let is_currently_playing = app.play_id.is_some() && app.play_song_id == Some(song_id);
+            track_download_task(app, song_id, &title, &stage);
             app.play_song_id = Some(song_id);
             app.play_stream_hint = stream_hint.clone();
+            // 缓冲进度每秒最多刷新两次
+            if !is_currently_playing && matches!(stage, AudioLoadStage::Downloading { .. }) {
+                let now = Instant::now();
+                if app
+                    .play_buffering_status_at
+                    .is_some_and(|at| now.duration_since(at) < BUFFERING_STATUS_INTERVAL)
+                {
+                    return;
+                }
+                app.play_buffering_status_at = Some(now);
+            } else {
+                app.play_buffering_status_at = None;
+            }
             app.play_status = format_loading_status(
                 &title,
                 &stage,
@@ -194,8 +365,17 @@ pub async fn handle_audio_event(
             duration_ms,
             stream_hint,
         } => {
+            // 引擎自行切换到了预排的下一首：推进播放队列
+            let queued_br = ctx.next_song_cache.take_queued(song_id);
+            if let Some(br) = queued_br {
+                tracing::info!(song_id, br, "🎵 [PlayerAudio] 无缝切换到预排的下一首");
+                advance_to_queued(app, song_id, ctx.request_tracker, ctx.req_ids, effects);
+                app.play_actual_br = Some(br);
+            }
+
             // 保存待恢复的播放位置（在重置之前）
             let seek_to = app.pending_seek_ms;
+            app.play_buffering_status_at = None;
 
             // 记录旧的播放进度
             let old_elapsed_ms = app.playback_elapsed_ms();
@@ -213,19 +393,43 @@ pub async fn handle_audio_event(
 
             app.now_playing = Some(title);
             app.paused = false;
-            app.play_status = format_playback_status(false, Some(&stream_hint));
+            app.play_status =
+                with_downgrade_note(app, format_playback_status(false, Some(&stream_hint)));
             app.play_started_at = Some(Instant::now());
+            app.play_position_ms = None;
+            app.play_seek_at = None;
             app.play_total_ms = duration_ms;
             app.play_stream_hint = Some(stream_hint.clone());
             app.play_paused_at = None;
             app.play_paused_accum_ms = 0;
             app.play_id = Some(play_id);
+            if app.play_song_id != Some(song_id) {
+                ab_loop::clear_points(app);
+            }
             app.play_song_id = Some(song_id);
             app.play_error_count = 0;
+            if matches!(stream_hint.mode, AudioPlaybackMode::CachedFile) {
+                finish_download_tasks(&mut app.tasks, None, TaskRegistry::complete);
+            }
+            // 启动时恢复的歌曲：先暂停再恢复音量（加载期间为 0），不会漏出声音
+            if let Some(resume) = app.startup_resume.take()
+                && resume.paused
+                && resume.song_id == song_id
+            {
+                tracing::info!(song_id, "🎵 [PlayerAudio] 已加载上次播放的歌曲，保持暂停");
+                effects.send_audio_warn(
+                    AudioCommand::TogglePause,
+                    "AudioWorker 通道已关闭：TogglePause 发送失败",
+                );
+            }
             effects.send_audio_warn(
-                AudioCommand::SetVolume(app.volume),
+                AudioCommand::SetVolume(app.output_volume()),
                 "AudioWorker 通道已关闭：SetVolume 发送失败",
             );
+            effects.send_audio_warn(
+                AudioCommand::SetPlaybackSpeed(app.play_speed),
+                "AudioWorker 通道已关闭：SetPlaybackSpeed 发送失败",
+            );
 
             // 记录是否恢复了播放进度
             let restored_seek_ms = restore_pending_seek_if_possible(app, effects, &stream_hint);
@@ -246,18 +450,25 @@ pub async fn handle_audio_event(
             );
 
             app.lyrics_song_id = None;
-            app.lyrics.clear();
+            app.lyrics_song_offset_ms = None;
+            app.lyrics = Default::default();
             app.lyrics_status = "加载歌词...".to_owned();
-            let id = ctx
-                .request_tracker
-                .issue(RequestKey::Lyric, || utils::next_id(ctx.req_id));
+            let id = ctx.request_tracker.issue(RequestKey::Lyric, ctx.req_ids);
             effects.send_netease_hi_warn(
                 NeteaseCommand::Lyric {
                     req_id: id,
                     song_id,
+                    max_lines: app.lyrics_max_lines,
                 },
                 "NeteaseActor 通道已关闭：Lyric 发送失败",
             );
+
+            // 没有经过 request_play_at_index，在这里接着预排再下一首
+            if queued_br.is_some() {
+                ctx.next_song_cache
+                    .prefetch_next(app, effects, ctx.req_ids)
+                    .await;
+            }
         }
         AudioEvent::PlaybackHint {
             song_id,
@@ -273,7 +484,10 @@ pub async fn handle_audio_event(
                 .as_ref()
                 .is_some_and(|prev| !prev.seekable && hint.seekable);
             app.play_stream_hint = Some(hint.clone());
-            app.play_status = if became_seekable {
+            if matches!(hint.mode, AudioPlaybackMode::CachedFile) {
+                finish_download_tasks(&mut app.tasks, None, TaskRegistry::complete);
+            }
+            let status = if became_seekable {
                 if app.paused {
                     "已暂停（已缓存完成，可拖动）".to_owned()
                 } else {
@@ -282,6 +496,7 @@ pub async fn handle_audio_event(
             } else {
                 format_playback_status(app.paused, Some(&hint))
             };
+            app.play_status = with_downgrade_note(app, status);
             let _ = restore_pending_seek_if_possible(app, effects, &hint);
         }
         AudioEvent::Paused(p) => {
@@ -292,7 +507,10 @@ pub async fn handle_audio_event(
             );
 
             app.paused = p;
-            app.play_status = format_playback_status(p, app.play_stream_hint.as_ref());
+            app.play_status = with_downgrade_note(
+                app,
+                format_playback_status(p, app.play_stream_hint.as_ref()),
+            );
 
             tracing::debug!(
                 play_status = %app.play_status,
@@ -308,17 +526,26 @@ pub async fn handle_audio_event(
             }
         }
         AudioEvent::Stopped => {
+            finish_download_tasks(&mut app.tasks, None, TaskRegistry::cancel);
             app.paused = false;
             app.play_status = "已停止".to_owned();
             app.play_started_at = None;
+            app.play_position_ms = None;
             app.play_total_ms = None;
             app.play_stream_hint = None;
             app.play_paused_at = None;
             app.play_paused_accum_ms = 0;
             app.play_id = None;
             app.play_song_id = None;
+            app.play_actual_br = None;
             app.play_error_count = 0;
         }
+        AudioEvent::Position {
+            play_id,
+            position_ms,
+        } => {
+            apply_position(app, play_id, position_ms);
+        }
         AudioEvent::CacheCleared { files, bytes } => {
             app.settings_status = format!(
                 "已清除音频缓存：{} 个文件，释放 {} MB",
@@ -326,16 +553,89 @@ pub async fn handle_audio_event(
                 bytes / 1024 / 1024
             );
             tracing::info!(files, bytes, "音频缓存已清除");
+            cache_status::refresh_after_change(app, effects);
+        }
+        AudioEvent::CacheEvicted { files, bytes } => {
+            app.cache_evicted_files += files;
+            app.cache_evicted_bytes += bytes;
+            tracing::info!(files, bytes, "音频缓存超出上限，已自动清理");
+            cache_status::refresh_after_change(app, effects);
+        }
+        AudioEvent::CacheStats { files, bytes } => {
+            app.cache_usage = Some((files, bytes));
+        }
+        AudioEvent::CacheMigrateProgress { done, total } => {
+            if let Some(id) = app.tasks.running_id(TaskKind::CacheMigration) {
+                app.tasks
+                    .update_progress(id, done as u64, Some(total as u64));
+            }
+        }
+        AudioEvent::CacheMigrated {
+            files,
+            bytes,
+            failed,
+            cancelled,
+        } => {
+            let task_id = app.tasks.running_id(TaskKind::CacheMigration);
+            let summary = format!("{files} 个文件，{}", format_bytes(bytes));
+            app.settings_status = if cancelled {
+                format!("已停止移动缓存：已移动 {summary}")
+            } else if failed > 0 {
+                format!("移动缓存完成：{summary}，{failed} 个文件移动失败（仍在原位置）")
+            } else {
+                format!("移动缓存完成：{summary}")
+            };
+            match task_id {
+                Some(id) if failed > 0 && !cancelled => {
+                    app.tasks.fail(id, format!("{failed} 个文件移动失败"));
+                    effects.set_toast(Toast::warning(app.settings_status.clone()));
+                }
+                Some(id) => {
+                    app.tasks.complete(id);
+                    effects.set_toast(Toast::info(app.settings_status.clone()));
+                }
+                // 已在任务面板取消
+                None => {}
+            }
+            tracing::info!(files, bytes, failed, cancelled, "移动旧缓存结束");
+            cache_status::refresh_after_change(app, effects);
+        }
+        AudioEvent::CachedSongs { song_ids } => {
+            app.cached_song_ids = song_ids.into_iter().collect();
+        }
+        AudioEvent::Exported { song_id, path } => {
+            // 导出前已确保缓存
+            app.cached_song_ids.insert(song_id);
+            download::on_exported(&mut app.tasks, song_id, None);
+            effects.set_toast(Toast::info(format!("已下载: {}", path.display())));
+        }
+        AudioEvent::ExportFailed { song_id, message } => {
+            tracing::warn!(song_id, err = %message, "下载歌曲失败");
+            download::on_exported(&mut app.tasks, song_id, Some(&message));
+            effects.set_toast(Toast::error(format!("下载失败: {message}")));
+        }
+        AudioEvent::DownloadCooldown { duration } => {
+            effects.set_toast(Toast::warning(format!(
+                "CDN 限流，暂停后台下载 {}",
+                minutes_label(duration)
+            )));
         }
         AudioEvent::Ended { play_id } => {
             if app.play_id != Some(play_id) {
                 return;
             }
+            if app.stop_after_current {
+                tracing::info!(play_id, "🎵 [PlayerAudio] 单曲后停止，不推进队列");
+                app.stop_after_current = false;
+                app.play_status = "播放结束（单曲后停止）".to_owned();
+                ctx.next_song_cache.reset(effects);
+                return;
+            }
             play_next(
                 app,
                 ctx.request_tracker,
                 ctx.song_request_titles,
-                ctx.req_id,
+                ctx.req_ids,
                 ctx.next_song_cache,
                 effects,
             )
@@ -353,29 +653,34 @@ pub async fn handle_audio_event(
                 "🎵 [PlayerAudio] 收到 NeedsReload 事件，重新加载音频"
             );
 
+            // 启动时恢复的歌曲还在加载：用户按了播放，加载完成后不再暂停
+            if let Some(resume) = app.startup_resume.as_mut() {
+                resume.paused = false;
+                app.play_status = "加载中，完成后继续播放...".to_owned();
+                return;
+            }
+
             // 保存播放进度，用于重新加载后恢复
             if current_elapsed_ms > 0 {
                 app.pending_seek_ms = Some(current_elapsed_ms);
                 tracing::info!("🎵 [PlayerAudio] 保存播放进度: {}ms", current_elapsed_ms);
             }
 
-            // 检查是否有有效的歌曲可以播放
-            let song_id = match app
-                .play_song_id
-                .or_else(|| app.play_queue.current().map(|s| s.id))
-            {
-                Some(id) => id,
-                None => {
-                    tracing::warn!("🎵 [PlayerAudio] 没有可播放的歌曲");
-                    app.play_status = "无歌曲可播放".to_string();
-                    return;
-                }
+            // 优先重新加载上次播放的歌曲，其次是队列当前的歌曲
+            let queued = app
+                .play_queue
+                .current()
+                .filter(|s| app.play_song_id.is_none_or(|id| id == s.id));
+            let Some(song_id) = app.play_song_id.or(queued.map(|s| s.id)) else {
+                tracing::warn!("🎵 [PlayerAudio] 没有可恢复的歌曲");
+                app.pending_seek_ms = None;
+                app.play_status = "无可恢复的歌曲".to_owned();
+                return;
             };
 
             // 获取歌曲标题用于请求
-            let current_song = app.play_queue.current();
-            let title = current_song
-                .map(|s| format!("{} - {}", s.name, s.artists))
+            let title = queued
+                .map(|s| s.display_title(app.song_name_mode))
                 .or_else(|| app.now_playing.clone())
                 .unwrap_or_else(|| "未知歌曲".to_string());
 
@@ -390,9 +695,7 @@ pub async fn handle_audio_event(
 
             // 清理旧的请求记录并重新请求
             ctx.song_request_titles.clear();
-            let req_id = ctx
-                .request_tracker
-                .issue(RequestKey::SongUrl, || utils::next_id(ctx.req_id));
+            let req_id = ctx.request_tracker.issue(RequestKey::SongUrl, ctx.req_ids);
             ctx.song_request_titles.insert(song_id, title.clone());
 
             effects.send_netease_hi_warn(
@@ -406,6 +709,35 @@ pub async fn handle_audio_event(
         }
         AudioEvent::Error(e) => {
             app.play_status = format!("播放错误: {e}");
+            let message = e.to_string();
+            finish_download_tasks(&mut app.tasks, None, |tasks, id| {
+                tasks.fail(id, message.clone())
+            });
+
+            let failed_song_id = app
+                .play_song_id
+                .or_else(|| app.play_queue.current().map(|s| s.id));
+            if let Some(song_id) = failed_song_id {
+                // 本次播放的首次失败，且此前已失败过：视为坏歌曲，直接跳过而不再重试
+                let known_bad = app.play_error_count == 0 && app.song_failures.count(song_id) > 0;
+                let count = app
+                    .song_failures
+                    .record(song_id, SongFailureKind::from_error(&e));
+                tracing::warn!(song_id, count, known_bad, "🎵 [PlayerAudio] 记录播放失败");
+                if known_bad {
+                    app.play_status = "歌曲此前播放失败，自动跳过...".to_owned();
+                    play_next(
+                        app,
+                        ctx.request_tracker,
+                        ctx.song_request_titles,
+                        ctx.req_ids,
+                        ctx.next_song_cache,
+                        effects,
+                    )
+                    .await;
+                    return;
+                }
+            }
 
             let retryable = e.is_retryable();
             if retryable {
@@ -415,14 +747,12 @@ pub async fn handle_audio_event(
                     && let Some(song_id) = app.play_song_id.or_else(|| current_song.map(|s| s.id))
                 {
                     let title = current_song
-                        .map(|s| format!("{} - {}", s.name, s.artists))
+                        .map(|s| s.display_title(app.song_name_mode))
                         .or_else(|| app.now_playing.clone())
                         .unwrap_or_else(|| "未知歌曲".to_owned());
                     app.play_status = format!("播放失败，正在重试({}/2)...", app.play_error_count);
                     ctx.song_request_titles.clear();
-                    let id = ctx
-                        .request_tracker
-                        .issue(RequestKey::SongUrl, || utils::next_id(ctx.req_id));
+                    let id = ctx.request_tracker.issue(RequestKey::SongUrl, ctx.req_ids);
                     ctx.song_request_titles.insert(song_id, title);
                     effects.send_netease_hi(crate::netease::actor::NeteaseCommand::SongUrl {
                         req_id: id,
@@ -437,10 +767,12 @@ pub async fn handle_audio_event(
 
 #[cfg(test)]
 mod tests {
-    use super::{format_loading_status, handle_audio_event};
+    use super::{SEEK_SETTLE, apply_position, format_loading_status, handle_audio_event};
+    use crate::app::SongFailureKind;
     use crate::audio_worker::{AudioBufferState, AudioEvent, AudioLoadStage, AudioStreamHint};
     use crate::core::CoreEffects;
-    use crate::core::infra::{NextSongCacheManager, RequestKey, RequestTracker};
+    use crate::core::infra::{IdAllocator, NextSongCacheManager, RequestKey, RequestTracker};
+    use crate::error::{AudioErrorVariant, MessageError};
     use crate::features::player::audio::AudioEventCtx;
     use std::time::Duration;
 
@@ -451,13 +783,13 @@ mod tests {
             &AudioLoadStage::Downloading {
                 downloaded_bytes: 512 * 1024,
                 total_bytes: Some(1024 * 1024),
+                bytes_per_sec: None,
             },
             None,
             false,
         );
 
-        assert!(status.contains("下载中 50%"));
-        assert!(status.contains("512.0 KB/1.0 MB"));
+        assert_eq!(status, "缓冲中 512KB/1.0MB: Test Song");
     }
 
     #[test]
@@ -467,12 +799,95 @@ mod tests {
             &AudioLoadStage::Downloading {
                 downloaded_bytes: 3 * 1024 * 1024,
                 total_bytes: None,
+                bytes_per_sec: Some(890 * 1024),
             },
             None,
             false,
         );
 
-        assert_eq!(status, "下载中 3.0 MB: Test Song");
+        // 总大小未知：不估算剩余时间
+        assert_eq!(status, "缓冲中 3.0MB · 890KB/s: Test Song");
+    }
+
+    #[test]
+    fn buffering_status_shows_speed_eta_and_stall() {
+        let downloading = |bytes_per_sec| AudioLoadStage::Downloading {
+            downloaded_bytes: 1258 * 1024,
+            total_bytes: Some(4710 * 1024),
+            bytes_per_sec,
+        };
+        assert_eq!(
+            format_loading_status("Test Song", &downloading(Some(890 * 1024)), None, false),
+            "缓冲中 1.2MB/4.6MB · 890KB/s · 约4s: Test Song"
+        );
+        assert_eq!(
+            format_loading_status("Test Song", &downloading(Some(0)), None, false),
+            "缓冲中 1.2MB/4.6MB · 网络停滞…: Test Song"
+        );
+        // 已在播放时仍显示后台下载进度
+        assert_eq!(
+            format_loading_status("Test Song", &downloading(Some(890 * 1024)), None, true),
+            "下载中 26% (1.2 MB/4.6 MB): Test Song"
+        );
+    }
+
+    #[tokio::test]
+    async fn buffering_status_updates_at_most_twice_per_second_and_clears_on_now_playing() {
+        let mut app = crate::app::App::default();
+        let mut request_tracker = RequestTracker::<RequestKey>::new();
+        let mut song_request_titles = std::collections::HashMap::new();
+        let req_ids = IdAllocator::default();
+        let mut next_song_cache = NextSongCacheManager::default();
+        let mut effects = CoreEffects::default();
+        let mut ctx = AudioEventCtx {
+            request_tracker: &mut request_tracker,
+            song_request_titles: &mut song_request_titles,
+            req_ids: &req_ids,
+            next_song_cache: &mut next_song_cache,
+        };
+        let progress = |downloaded_bytes| AudioEvent::Loading {
+            song_id: 7,
+            title: "Test Song".to_owned(),
+            stage: AudioLoadStage::Downloading {
+                downloaded_bytes,
+                total_bytes: Some(4 * 1024 * 1024),
+                bytes_per_sec: Some(1024 * 1024),
+            },
+            stream_hint: None,
+        };
+
+        handle_audio_event(&mut app, progress(1024 * 1024), &mut ctx, &mut effects).await;
+        assert_eq!(
+            app.play_status,
+            "缓冲中 1.0MB/4.0MB · 1.0MB/s · 约3s: Test Song"
+        );
+        handle_audio_event(&mut app, progress(2 * 1024 * 1024), &mut ctx, &mut effects).await;
+        assert_eq!(
+            app.play_status, "缓冲中 1.0MB/4.0MB · 1.0MB/s · 约3s: Test Song",
+            "500ms 内的进度不刷新状态"
+        );
+
+        handle_audio_event(
+            &mut app,
+            AudioEvent::NowPlaying {
+                song_id: 7,
+                play_id: 9,
+                title: "Test Song".to_owned(),
+                duration_ms: Some(240_000),
+                stream_hint: AudioStreamHint::cached_file(Some(4 * 1024 * 1024)),
+            },
+            &mut ctx,
+            &mut effects,
+        )
+        .await;
+        assert_eq!(app.play_status, "播放中");
+        assert!(app.play_buffering_status_at.is_none());
+
+        // 降级后的音质在状态中注明
+        app.play_br = 999_000;
+        app.play_actual_br = Some(320_000);
+        handle_audio_event(&mut app, AudioEvent::Paused(false), &mut ctx, &mut effects).await;
+        assert_eq!(app.play_status, "播放中 (320k 降级)");
     }
 
     #[tokio::test]
@@ -480,13 +895,13 @@ mod tests {
         let mut app = crate::app::App::default();
         let mut request_tracker = RequestTracker::<RequestKey>::new();
         let mut song_request_titles = std::collections::HashMap::new();
-        let mut req_id = 1u64;
+        let req_ids = IdAllocator::default();
         let mut next_song_cache = NextSongCacheManager::default();
         let mut effects = CoreEffects::default();
         let mut ctx = AudioEventCtx {
             request_tracker: &mut request_tracker,
             song_request_titles: &mut song_request_titles,
-            req_id: &mut req_id,
+            req_ids: &req_ids,
             next_song_cache: &mut next_song_cache,
         };
 
@@ -512,13 +927,13 @@ mod tests {
         let mut app = crate::app::App::default();
         let mut request_tracker = RequestTracker::<RequestKey>::new();
         let mut song_request_titles = std::collections::HashMap::new();
-        let mut req_id = 1u64;
+        let req_ids = IdAllocator::default();
         let mut next_song_cache = NextSongCacheManager::default();
         let mut effects = CoreEffects::default();
         let mut ctx = AudioEventCtx {
             request_tracker: &mut request_tracker,
             song_request_titles: &mut song_request_titles,
-            req_id: &mut req_id,
+            req_ids: &req_ids,
             next_song_cache: &mut next_song_cache,
         };
 
@@ -546,6 +961,47 @@ mod tests {
         assert!(!app.can_seek());
     }
 
+    #[test]
+    fn position_reports_correct_drift_and_ignore_stale_play_ids() {
+        let now = std::time::Instant::now();
+        let mut app = crate::app::App {
+            play_id: Some(9),
+            play_started_at: Some(now - Duration::from_secs(60)),
+            ..Default::default()
+        };
+
+        // 旧歌曲的上报不生效
+        assert!(!apply_position(&mut app, 8, 10_000));
+        assert_eq!(app.play_position_ms, None);
+
+        // 误差在容忍范围内不校正
+        assert!(!apply_position(&mut app, 9, 60_100));
+        assert_eq!(app.play_position_ms, Some(60_100));
+
+        // 缓冲停滞后本地计时超前：回到引擎的实际位置
+        assert!(apply_position(&mut app, 9, 45_000));
+        assert!(app.playback_elapsed_ms().abs_diff(45_000) < 100);
+    }
+
+    #[test]
+    fn position_reports_right_after_seek_are_ignored() {
+        let mut app = crate::app::App {
+            play_id: Some(9),
+            play_total_ms: Some(240_000),
+            play_started_at: Some(std::time::Instant::now() - Duration::from_secs(10)),
+            ..Default::default()
+        };
+        let mut effects = CoreEffects::default();
+        crate::features::player::playback::seek_absolute(&mut app, &mut effects, 120_000);
+
+        // 引擎在处理 Seek 之前采样的位置
+        assert!(!apply_position(&mut app, 9, 10_500));
+        assert!(app.playback_elapsed_ms().abs_diff(120_000) < 100);
+
+        app.play_seek_at = std::time::Instant::now().checked_sub(SEEK_SETTLE);
+        assert!(apply_position(&mut app, 9, 90_000));
+    }
+
     #[tokio::test]
     async fn needs_reload_uses_frozen_elapsed_time_when_paused() {
         let now = std::time::Instant::now();
@@ -560,13 +1016,13 @@ mod tests {
         };
         let mut request_tracker = RequestTracker::<RequestKey>::new();
         let mut song_request_titles = std::collections::HashMap::new();
-        let mut req_id = 1u64;
+        let req_ids = IdAllocator::default();
         let mut next_song_cache = NextSongCacheManager::default();
         let mut effects = CoreEffects::default();
         let mut ctx = AudioEventCtx {
             request_tracker: &mut request_tracker,
             song_request_titles: &mut song_request_titles,
-            req_id: &mut req_id,
+            req_ids: &req_ids,
             next_song_cache: &mut next_song_cache,
         };
 
@@ -583,4 +1039,131 @@ mod tests {
             Some("Paused Song - Artist")
         );
     }
+
+    #[tokio::test]
+    async fn needs_reload_without_song_or_during_startup_load() {
+        let mut request_tracker = RequestTracker::<RequestKey>::new();
+        let mut song_request_titles = std::collections::HashMap::new();
+        let req_ids = IdAllocator::default();
+        let mut next_song_cache = NextSongCacheManager::default();
+        let mut effects = CoreEffects::default();
+        let mut ctx = AudioEventCtx {
+            request_tracker: &mut request_tracker,
+            song_request_titles: &mut song_request_titles,
+            req_ids: &req_ids,
+            next_song_cache: &mut next_song_cache,
+        };
+
+        let mut app = crate::app::App::default();
+        handle_audio_event(&mut app, AudioEvent::NeedsReload, &mut ctx, &mut effects).await;
+        assert_eq!(app.play_status, "无可恢复的歌曲");
+        assert!(
+            ctx.request_tracker
+                .get_pending(&RequestKey::SongUrl)
+                .is_none()
+        );
+
+        // 启动恢复的歌曲加载中按了播放：不重复请求，加载后不再暂停
+        app.play_song_id = Some(7);
+        app.startup_resume = Some(crate::app::StartupResume {
+            song_id: 7,
+            paused: true,
+        });
+        handle_audio_event(&mut app, AudioEvent::NeedsReload, &mut ctx, &mut effects).await;
+        assert_eq!(
+            app.startup_resume,
+            Some(crate::app::StartupResume {
+                song_id: 7,
+                paused: false,
+            })
+        );
+        assert!(
+            ctx.request_tracker
+                .get_pending(&RequestKey::SongUrl)
+                .is_none()
+        );
+    }
+
+    fn queued_app(playing: i64) -> crate::app::App {
+        let mut app = crate::app::App {
+            play_song_id: Some(playing),
+            ..Default::default()
+        };
+        let songs = [1, 2]
+            .into_iter()
+            .map(|id| crate::app::Song {
+                id,
+                name: format!("song-{id}"),
+                artists: "artist".to_owned(),
+                ..Default::default()
+            })
+            .collect();
+        app.play_queue.set_songs(songs, Some(0));
+        app
+    }
+
+    fn download_error() -> AudioEvent {
+        AudioEvent::Error(MessageError::Audio(AudioErrorVariant::Download(
+            "HTTP 状态码 503".to_owned(),
+        )))
+    }
+
+    #[tokio::test]
+    async fn playback_errors_update_failure_record_across_retries() {
+        let mut app = queued_app(1);
+        let mut request_tracker = RequestTracker::<RequestKey>::new();
+        let mut song_request_titles = std::collections::HashMap::new();
+        let req_ids = IdAllocator::default();
+        let mut next_song_cache = NextSongCacheManager::default();
+        let mut effects = CoreEffects::default();
+        let mut ctx = AudioEventCtx {
+            request_tracker: &mut request_tracker,
+            song_request_titles: &mut song_request_titles,
+            req_ids: &req_ids,
+            next_song_cache: &mut next_song_cache,
+        };
+
+        for attempt in 1..=3u32 {
+            handle_audio_event(&mut app, download_error(), &mut ctx, &mut effects).await;
+            let record = app.song_failures.get(1).expect("失败记录");
+            assert_eq!(record.count, attempt);
+            assert_eq!(record.kind, SongFailureKind::Download);
+            assert_eq!(app.play_error_count, attempt);
+            assert_eq!(app.song_failures.is_marked(1), attempt >= 2);
+        }
+        assert_eq!(
+            app.play_queue.current().map(|s| s.id),
+            Some(1),
+            "重试中不跳过"
+        );
+        assert!(
+            app.play_status.starts_with("播放错误"),
+            "重试用尽后停在错误状态"
+        );
+    }
+
+    #[tokio::test]
+    async fn known_bad_song_is_skipped_on_first_failure() {
+        let mut app = queued_app(1);
+        app.song_failures.record(1, SongFailureKind::Unavailable);
+        let mut request_tracker = RequestTracker::<RequestKey>::new();
+        let mut song_request_titles = std::collections::HashMap::new();
+        let req_ids = IdAllocator::default();
+        let mut next_song_cache = NextSongCacheManager::default();
+        let mut effects = CoreEffects::default();
+        let mut ctx = AudioEventCtx {
+            request_tracker: &mut request_tracker,
+            song_request_titles: &mut song_request_titles,
+            req_ids: &req_ids,
+            next_song_cache: &mut next_song_cache,
+        };
+
+        handle_audio_event(&mut app, download_error(), &mut ctx, &mut effects).await;
+
+        assert_eq!(app.song_failures.count(1), 2);
+        assert_eq!(app.play_error_count, 0, "跳过而不是重试");
+        assert_eq!(app.play_queue.current().map(|s| s.id), Some(2));
+        assert!(ctx.song_request_titles.contains_key(&2));
+        assert!(!ctx.song_request_titles.contains_key(&1));
+    }
 }
@@ -0,0 +1,71 @@
+//! 音频焦点策略：其它程序的声音开始/结束时降低音量或暂停，结束后恢复
+//!
+//! 平台监听（`crate::audio_focus`）只报告「是否有其它程序在播放」，这里按
+//! `App::audio_focus_policy` 决定动作。自动暂停与挂起恢复共用 `App::auto_paused`：
+//! 期间用户手动播放/暂停会清除它，其它声音结束时就不再自动继续。
+
+use crate::app::{AudioFocus, AudioFocusPolicy};
+use crate::core::prelude::{app::App, audio::AudioCommand, effects::CoreEffects};
+
+/// 其它程序开始或停止播放声音
+pub fn on_others_playing(app: &mut App, others_playing: bool, effects: &mut CoreEffects) {
+    if others_playing {
+        take_focus_away(app, effects);
+    } else {
+        restore_focus(app, effects);
+    }
+}
+
+fn take_focus_away(app: &mut App, effects: &mut CoreEffects) {
+    let playing = app.play_started_at.is_some() && !app.paused;
+    if app.audio_focus != AudioFocus::Held || !playing {
+        return;
+    }
+    match app.audio_focus_policy {
+        AudioFocusPolicy::Ignore => {}
+        AudioFocusPolicy::Duck => {
+            app.audio_focus = AudioFocus::Ducked;
+            tracing::info!(
+                percent = app.audio_focus_duck_percent,
+                "🎵 [AudioFocus] 其它程序开始播放，降低音量"
+            );
+            effects.send_audio_warn(
+                AudioCommand::SetVolume(app.output_volume()),
+                "AudioWorker 通道已关闭：SetVolume 发送失败",
+            );
+        }
+        AudioFocusPolicy::Pause => {
+            app.audio_focus = AudioFocus::Paused;
+            app.auto_paused = true;
+            tracing::info!("🎵 [AudioFocus] 其它程序开始播放，自动暂停");
+            effects.send_audio_warn(
+                AudioCommand::TogglePause,
+                "AudioWorker 通道已关闭：TogglePause 发送失败",
+            );
+        }
+    }
+}
+
+fn restore_focus(app: &mut App, effects: &mut CoreEffects) {
+    match std::mem::take(&mut app.audio_focus) {
+        AudioFocus::Held => {}
+        AudioFocus::Ducked => {
+            tracing::info!("🎵 [AudioFocus] 其它程序已停止，恢复音量");
+            effects.send_audio_warn(
+                AudioCommand::SetVolume(app.output_volume()),
+                "AudioWorker 通道已关闭：SetVolume 发送失败",
+            );
+        }
+        AudioFocus::Paused => {
+            // 期间用户手动操作过（或已切到别的歌在播放）则保持现状
+            if app.auto_paused && app.paused {
+                tracing::info!("🎵 [AudioFocus] 其它程序已停止，自动继续播放");
+                effects.send_audio_warn(
+                    AudioCommand::TogglePause,
+                    "AudioWorker 通道已关闭：TogglePause 发送失败",
+                );
+            }
+            app.auto_paused = false;
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! 音频缓存状态：设置页的缓存占用与歌曲列表中的「已缓存」标记
+//!
+//! 缓存索引只在音频线程（transfer actor）中，这里发出查询命令，结果经
+//! `AudioEvent::CacheStats` / `AudioEvent::CachedSongs` 回到 `App`。
+
+use crate::app::PlaylistMode;
+use crate::core::prelude::{app::App, audio::AudioCommand, effects::CoreEffects};
+
+/// 查询缓存占用（进入设置页、清除缓存后）
+pub fn query_cache_stats(effects: &mut CoreEffects) {
+    effects.send_audio_warn(
+        AudioCommand::QueryCacheStats,
+        "AudioWorker 通道已关闭：QueryCacheStats 发送失败",
+    );
+}
+
+/// 查询歌曲列表中哪些歌曲已缓存（仅在歌曲模式下）
+pub fn query_cached_tracks(app: &App, effects: &mut CoreEffects) {
+    if app.playlist_mode != PlaylistMode::Tracks || app.playlist_tracks.is_empty() {
+        return;
+    }
+    effects.send_audio_warn(
+        AudioCommand::QueryCachedSongs {
+            song_ids: app.playlist_tracks.iter().map(|s| s.id).collect(),
+            br: app.play_br,
+        },
+        "AudioWorker 通道已关闭：QueryCachedSongs 发送失败",
+    );
+}
+
+/// 缓存内容变化（清除、超出上限自动清理）后刷新两处显示
+pub fn refresh_after_change(app: &App, effects: &mut CoreEffects) {
+    query_cache_stats(effects);
+    query_cached_tracks(app, effects);
+}
+
+/// 歌曲列表的内容与音质，任一变化时重新查询已缓存标记
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TracksKey {
+    playlist_id: Option<i64>,
+    recommend: bool,
+    len: usize,
+    br: i64,
+}
+
+/// 每次 reduce 后比较歌曲列表，打开新歌单、分批加载或切换音质时查询一次
+#[derive(Debug, Default)]
+pub struct CachedTracksWatch {
+    last: Option<TracksKey>,
+}
+
+impl CachedTracksWatch {
+    pub fn observe(&mut self, app: &App, effects: &mut CoreEffects) {
+        let key = (app.playlist_mode == PlaylistMode::Tracks).then(|| TracksKey {
+            playlist_id: app.open_playlist_id(),
+            recommend: app.playlist_tracks_recommend,
+            len: app.playlist_tracks.len(),
+            br: app.play_br,
+        });
+        if key == self.last {
+            return;
+        }
+        self.last = key;
+        query_cached_tracks(app, effects);
+    }
+}
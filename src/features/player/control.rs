@@ -1,14 +1,16 @@
+use crate::app::{AudioFocus, Toast};
 use crate::core::prelude::{
     app::App,
     audio::AudioCommand,
     effects::CoreEffects,
-    infra::{NextSongCacheManager, RequestKey, RequestTracker},
+    infra::{IdAllocator, NextSongCacheManager, RequestKey, RequestTracker},
     messages::AppCommand,
 };
+use crate::features::player::ab_loop;
 use crate::features::player::playback::{play_next, play_prev, seek_absolute, seek_relative};
 
 pub struct PlayerControlCtx<'a> {
-    pub req_id: &'a mut u64,
+    pub req_ids: &'a IdAllocator,
     pub request_tracker: &'a mut RequestTracker<RequestKey>,
     pub song_request_titles: &'a mut std::collections::HashMap<i64, String>,
     pub next_song_cache: &'a mut NextSongCacheManager,
@@ -42,22 +44,85 @@ pub async fn handle_player_control_command(
             if app.play_song_id.is_none() {
                 tracing::warn!("🎵 [PlayerControl] play_song_id 为空，无法切换播放状态");
             }
+            // 用户手动操作后不再自动继续
+            app.auto_paused = false;
 
             ctx.effects.send_audio_warn(
                 AudioCommand::TogglePause,
                 "AudioWorker 通道已关闭：TogglePause 发送失败",
             );
         }
+        AppCommand::TerminalSuspending => {
+            // 挂起期间整个进程（包括音频线程）都会停止：先暂停，不让声音随缓冲耗尽戛然而止
+            if app.play_started_at.is_some() && !app.paused {
+                tracing::info!("🎵 [PlayerControl] 终端即将挂起，暂停播放");
+                app.suspend_paused = true;
+                app.auto_paused = true;
+                ctx.effects.send_audio_warn(
+                    AudioCommand::TogglePause,
+                    "AudioWorker 通道已关闭：TogglePause 发送失败",
+                );
+            }
+        }
+        AppCommand::TerminalResumed { suspended_ms } => {
+            // 挂起前没来得及暂停：进度计时需扣除挂起时长
+            if app.play_started_at.is_some() && !app.paused {
+                app.play_paused_accum_ms = app.play_paused_accum_ms.saturating_add(suspended_ms);
+            }
+            // 挂起期间用户无法操作；期间若因其它原因取消了自动暂停，则保持现状
+            if std::mem::take(&mut app.suspend_paused)
+                && !app.pause_on_suspend
+                && app.auto_paused
+                && app.paused
+                && app.audio_focus == AudioFocus::Held
+            {
+                tracing::info!(suspended_ms, "🎵 [PlayerControl] 挂起恢复，继续播放");
+                app.auto_paused = false;
+                ctx.effects.send_audio_warn(
+                    AudioCommand::TogglePause,
+                    "AudioWorker 通道已关闭：TogglePause 发送失败",
+                );
+            }
+            ctx.effects.emit_state(app);
+        }
+        AppCommand::TerminalFocusChanged { focused } => {
+            if !app.pause_on_focus_lost {
+                return true;
+            }
+            let playing = app.play_started_at.is_some() && !app.paused;
+            if !focused && playing {
+                tracing::info!("🎵 [PlayerControl] 终端失去焦点，按设置暂停播放");
+                app.auto_paused = true;
+                ctx.effects.send_audio_warn(
+                    AudioCommand::TogglePause,
+                    "AudioWorker 通道已关闭：TogglePause 发送失败",
+                );
+            } else if focused
+                && app.auto_paused
+                && app.paused
+                && app.audio_focus == AudioFocus::Held
+            {
+                // 期间用户手动操作过，或其它程序的声音仍占着焦点，则保持现状
+                tracing::info!("🎵 [PlayerControl] 终端重新获得焦点，继续播放");
+                app.auto_paused = false;
+                ctx.effects.send_audio_warn(
+                    AudioCommand::TogglePause,
+                    "AudioWorker 通道已关闭：TogglePause 发送失败",
+                );
+            }
+        }
         AppCommand::PlayerStop => {
+            app.stop_after_current = false;
             ctx.effects
                 .send_audio_warn(AudioCommand::Stop, "AudioWorker 通道已关闭：Stop 发送失败");
         }
         AppCommand::PlayerPrev => {
+            app.stop_after_current = false;
             play_prev(
                 app,
                 ctx.request_tracker,
                 ctx.song_request_titles,
-                ctx.req_id,
+                ctx.req_ids,
                 ctx.next_song_cache,
                 ctx.effects,
             )
@@ -65,23 +130,57 @@ pub async fn handle_player_control_command(
             ctx.effects.emit_state(app);
         }
         AppCommand::PlayerNext => {
+            app.stop_after_current = false;
             play_next(
                 app,
                 ctx.request_tracker,
                 ctx.song_request_titles,
-                ctx.req_id,
+                ctx.req_ids,
                 ctx.next_song_cache,
                 ctx.effects,
             )
             .await;
             ctx.effects.emit_state(app);
         }
+        AppCommand::PlayerToggleStopAfterCurrent => {
+            app.stop_after_current = !app.stop_after_current;
+            let msg = if app.stop_after_current {
+                // 引擎中预排的下一首会在当前歌曲结束时自动切换，需要丢弃
+                ctx.next_song_cache.reset(ctx.effects);
+                "当前歌曲播完后停止"
+            } else {
+                ctx.next_song_cache
+                    .prefetch_next(app, ctx.effects, ctx.req_ids)
+                    .await;
+                "已取消单曲后停止"
+            };
+            ctx.effects.set_toast(Toast::info(msg));
+            ctx.effects.emit_state(app);
+        }
+        AppCommand::PlayerSetLoopPointA => {
+            ab_loop::set_point_a(app);
+            ctx.effects.emit_state(app);
+        }
+        AppCommand::PlayerSetLoopPointB => {
+            ab_loop::set_point_b(app);
+            ctx.effects.emit_state(app);
+        }
+        AppCommand::PlayerClearLoop => {
+            ab_loop::clear(app);
+            ctx.effects.emit_state(app);
+        }
         AppCommand::PlayerSeekBackwardMs { ms } => {
-            seek_relative(app, ctx.effects, -(ms as i64));
+            if seek_relative(app, ctx.effects, -(ms as i64)).is_some() {
+                ctx.effects
+                    .set_toast(Toast::info(format!("快退 {}", fmt_seek_step(ms))));
+            }
             ctx.effects.emit_state(app);
         }
         AppCommand::PlayerSeekForwardMs { ms } => {
-            seek_relative(app, ctx.effects, ms as i64);
+            if seek_relative(app, ctx.effects, ms as i64).is_some() {
+                ctx.effects
+                    .set_toast(Toast::info(format!("快进 {}", fmt_seek_step(ms))));
+            }
             ctx.effects.emit_state(app);
         }
         AppCommand::PlayerSeekAbsoluteMs { ms } => {
@@ -92,3 +191,13 @@ pub async fn handle_player_control_command(
     }
     true
 }
+
+/// Seek 步长提示文本，如 `5s`、`1m30s`
+fn fmt_seek_step(ms: u64) -> String {
+    let secs = ms / 1000;
+    match (secs / 60, secs % 60) {
+        (0, s) => format!("{s}s"),
+        (m, 0) => format!("{m}m"),
+        (m, s) => format!("{m}m{s}s"),
+    }
+}
@@ -0,0 +1,321 @@
+//! 下载歌曲到本地（`D`）
+//!
+//! 按当前音质获取播放链接（无可用链接时逐级降低音质），由音频线程确保缓存后复制到下载目录，
+//! 文件名为 `{歌手} - {歌名}`，扩展名由音频线程按文件内容判断。结果以 `AudioEvent::Exported` /
+//! `AudioEvent::ExportFailed` 回到 [`super::audio`] 提示。下载期间登记为 [`TaskKind::TrackExport`]，
+//! 可在任务面板取消，退出前需要确认。
+
+use crate::app::{App, PlaylistMode, Song, TaskKind, TaskRegistry, Toast, View};
+use crate::core::infra::IdAllocator;
+use crate::core::prelude::{
+    audio::{AudioCommand, TrackTags},
+    effects::CoreEffects,
+    netease::NeteaseCommand,
+};
+use crate::domain::model::{SongUrl, fallback_br};
+use crate::error::MessageError;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// 文件名（不含扩展名）的最大字节数，给扩展名与临时文件后缀留出余量
+const MAX_STEM_BYTES: usize = 200;
+
+/// Windows 保留的设备名，不能用作文件名
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 等待播放链接的下载
+#[derive(Debug)]
+pub struct PendingDownload {
+    song: Song,
+    /// 目标路径（不含扩展名）
+    dest: PathBuf,
+}
+
+/// 当前页面选中的歌曲：搜索结果（不含专辑/歌手列表）或已打开歌单中的歌曲
+fn selected_song(app: &App) -> Option<&Song> {
+    match app.view {
+        View::Search if !app.search_showing_albums() && !app.search_showing_artists() => {
+            app.search_results.get(app.search_selected)
+        }
+        View::Playlists if app.playlist_mode == PlaylistMode::Tracks => {
+            app.playlist_tracks.get(app.playlist_tracks_selected)
+        }
+        _ => None,
+    }
+}
+
+/// 下载选中的歌曲
+pub fn download_selected(
+    app: &mut App,
+    download_dir: Option<&Path>,
+    pending: &mut HashMap<u64, PendingDownload>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let Some(song) = selected_song(app).cloned() else {
+        return;
+    };
+    let Some(dir) = resolve_download_dir(download_dir) else {
+        effects.set_toast(Toast::warning(
+            "找不到音乐目录，请在 settings.json 中设置 download_dir",
+        ));
+        return;
+    };
+    tracing::info!(song_id = song.id, dir = %dir.display(), "下载歌曲");
+    let stem = file_stem(&song);
+    app.tasks
+        .register(TaskKind::TrackExport { song_id: song.id }, stem.clone());
+    effects.toast(format!("开始下载: {}", song.name));
+    let download = PendingDownload {
+        song,
+        dest: dir.join(stem),
+    };
+    request(pending, download, app.play_br, req_ids, effects);
+}
+
+fn request(
+    pending: &mut HashMap<u64, PendingDownload>,
+    download: PendingDownload,
+    br: i64,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let req_id = req_ids.next();
+    effects.send_netease_hi_warn(
+        NeteaseCommand::SongUrl {
+            req_id,
+            id: download.song.id,
+            br,
+        },
+        "NeteaseActor 通道已关闭：SongUrl 发送失败",
+    );
+    pending.insert(req_id, download);
+}
+
+/// 拿到播放链接：交给音频线程缓存并导出；`req_id` 不属于下载时返回 false
+pub fn on_song_url(
+    req_id: u64,
+    song_url: &SongUrl,
+    attempted_br: i64,
+    pending: &mut HashMap<u64, PendingDownload>,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(download) = pending.remove(&req_id) else {
+        return false;
+    };
+    effects.send_audio_warn(
+        AudioCommand::ExportTrack {
+            id: song_url.id,
+            br: song_url.effective_br(attempted_br),
+            url: song_url.url.clone(),
+            expected_bytes: song_url.size,
+            tags: track_tags(download.song),
+            dest: download.dest,
+        },
+        "AudioWorker 通道已关闭：ExportTrack 发送失败",
+    );
+    true
+}
+
+/// 写入文件的标签：原名、各位歌手、专辑、曲目号与封面
+fn track_tags(song: Song) -> TrackTags {
+    TrackTags {
+        artists: song_artists(&song.artists)
+            .into_iter()
+            .map(str::to_owned)
+            .collect(),
+        title: song.name,
+        album: song.album,
+        track_no: song.track_no,
+        cover_url: song.cover_url,
+    }
+}
+
+/// 当前音质无可用链接：降级重试，已是最低音质时提示失败
+pub fn on_unavailable(
+    req_id: u64,
+    br: i64,
+    pending: &mut HashMap<u64, PendingDownload>,
+    tasks: &mut TaskRegistry,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(download) = pending.remove(&req_id) else {
+        return false;
+    };
+    match fallback_br(br) {
+        Some(lower) => {
+            tracing::info!(
+                song_id = download.song.id,
+                from_br = br,
+                to_br = lower,
+                "下载降级重试"
+            );
+            request(pending, download, lower, req_ids, effects);
+        }
+        None => {
+            fail_task(tasks, download.song.id, "没有可用的播放链接");
+            effects.set_toast(Toast::error(format!(
+                "下载失败: {} 没有可用的播放链接",
+                download.song.name
+            )));
+        }
+    }
+    true
+}
+
+/// 获取链接的请求出错
+pub fn on_error(
+    req_id: u64,
+    error: &MessageError,
+    pending: &mut HashMap<u64, PendingDownload>,
+    tasks: &mut TaskRegistry,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(download) = pending.remove(&req_id) else {
+        return false;
+    };
+    fail_task(tasks, download.song.id, error.to_string());
+    effects.set_toast(Toast::error(format!(
+        "下载失败: {}: {error}",
+        download.song.name
+    )));
+    true
+}
+
+/// 音频线程上报导出结果：结束对应的任务；失败时返回 `message` 供提示
+pub fn on_exported(tasks: &mut TaskRegistry, song_id: i64, failure: Option<&str>) {
+    match failure {
+        Some(message) => fail_task(tasks, song_id, message),
+        None => {
+            if let Some(id) = tasks.running_id(TaskKind::TrackExport { song_id }) {
+                tasks.complete(id);
+            }
+        }
+    }
+}
+
+/// 在任务面板中取消：丢弃还在等待链接的请求，并让音频线程放弃等待缓存
+pub fn cancel(
+    song_id: i64,
+    pending: &mut HashMap<u64, PendingDownload>,
+    effects: &mut CoreEffects,
+) {
+    pending.retain(|_, download| download.song.id != song_id);
+    effects.send_audio_warn(
+        AudioCommand::CancelExport { id: song_id },
+        "AudioWorker 通道已关闭：CancelExport 发送失败",
+    );
+}
+
+fn fail_task(tasks: &mut TaskRegistry, song_id: i64, message: impl Into<String>) {
+    if let Some(id) = tasks.running_id(TaskKind::TrackExport { song_id }) {
+        tasks.fail(id, message);
+    }
+}
+
+/// 下载目录：设置的 `download_dir`（展开开头的 `~`），未设置时为系统音乐目录
+pub fn resolve_download_dir(configured: Option<&Path>) -> Option<PathBuf> {
+    match configured.filter(|p| !p.as_os_str().is_empty()) {
+        Some(path) => Some(crate::data_paths::expand_home(path)),
+        None => directories::UserDirs::new().and_then(|d| d.audio_dir().map(Path::to_path_buf)),
+    }
+}
+
+/// `{歌手} - {歌名}`（多位歌手以 `, ` 连接），已去除文件名中不允许的字符
+pub fn file_stem(song: &Song) -> String {
+    let artists = song_artists(&song.artists);
+    if artists.is_empty() {
+        sanitize_file_name(&song.name)
+    } else {
+        sanitize_file_name(&format!("{} - {}", artists.join(", "), song.name))
+    }
+}
+
+fn song_artists(artists: &str) -> Vec<&str> {
+    artists
+        .split('/')
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+        .collect()
+}
+
+/// 替换各平台文件名中的非法字符（`/`、Windows 的 `:` 等），并避开 Windows 的保留名与结尾的 `.`/空格
+fn sanitize_file_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        let c = match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        };
+        if out.len() + c.len_utf8() > MAX_STEM_BYTES {
+            break;
+        }
+        out.push(c);
+    }
+    let out = out.trim().trim_end_matches('.').trim_end();
+    if out.is_empty() {
+        return "未命名".to_owned();
+    }
+    if RESERVED_NAMES
+        .iter()
+        .any(|r| r.eq_ignore_ascii_case(out.split('.').next().unwrap_or(out)))
+    {
+        return format!("_{out}");
+    }
+    out.to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_stem_joins_artists_and_replaces_illegal_characters() {
+        assert_eq!(
+            file_stem(&Song::titled(0, "晴天", "周杰伦")),
+            "周杰伦 - 晴天"
+        );
+        assert_eq!(
+            file_stem(&Song::titled(0, "AC/DC: Live?", "A/B")),
+            "A, B - AC_DC_ Live_"
+        );
+        assert_eq!(file_stem(&Song::titled(0, "Title", "")), "Title");
+    }
+
+    #[test]
+    fn sanitize_avoids_reserved_names_and_trailing_dots() {
+        assert_eq!(sanitize_file_name("con"), "_con");
+        assert_eq!(sanitize_file_name("NUL.txt"), "_NUL.txt");
+        assert_eq!(sanitize_file_name("Console"), "Console");
+        assert_eq!(sanitize_file_name("Wait... "), "Wait");
+        assert_eq!(sanitize_file_name(" .. "), "未命名");
+        assert_eq!(sanitize_file_name("a\tb\n"), "a_b_");
+    }
+
+    #[test]
+    fn sanitize_caps_length_on_a_char_boundary() {
+        let long = "错".repeat(100);
+        let out = sanitize_file_name(&long);
+        assert!(out.len() <= MAX_STEM_BYTES);
+        assert_eq!(out, "错".repeat(MAX_STEM_BYTES / 3));
+    }
+
+    #[test]
+    fn configured_download_dir_expands_home() {
+        assert_eq!(
+            resolve_download_dir(Some(Path::new("/music"))),
+            Some(PathBuf::from("/music"))
+        );
+        if let Some(home) = directories::BaseDirs::new().map(|d| d.home_dir().to_path_buf()) {
+            assert_eq!(
+                resolve_download_dir(Some(Path::new("~/Music"))),
+                Some(home.join("Music"))
+            );
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! 私人 FM
+//!
+//! 开启后「下一首」不再按播放队列的顺序，而是从 FM 缓冲区取歌；缓冲区少于
+//! [`FM_REFILL_BELOW`] 首时向 `/api/v1/radio/get` 补充。播放过的 FM 歌曲追加到播放队列，
+//! 队列面板与播放历史照常工作；用户原来的队列暂存，退出 FM 时恢复。
+
+use std::collections::VecDeque;
+
+use crate::app::{FM_QUEUE_ORIGIN, FmState, PlayMode, PlayQueue, Song, Toast};
+use crate::core::infra::IdAllocator;
+use crate::core::prelude::{
+    app::App,
+    effects::CoreEffects,
+    infra::{NextSongCacheManager, RequestKey, RequestTracker},
+    netease::NeteaseCommand,
+};
+use crate::error::{MessageError, NeteaseErrorVariant};
+
+use super::audio::AudioEventCtx;
+use super::playback::request_play_at_index;
+
+/// 缓冲区少于该数量时补充
+pub const FM_REFILL_BELOW: usize = 2;
+
+/// 开启 / 退出私人 FM
+pub fn toggle(app: &mut App, ctx: &mut AudioEventCtx<'_>, effects: &mut CoreEffects) {
+    if app.fm_active() {
+        exit(app, ctx.request_tracker, ctx.next_song_cache, effects);
+        effects.set_toast(Toast::info("已退出私人FM，恢复原播放队列"));
+        effects.emit_state(app);
+        return;
+    }
+    if !app.logged_in {
+        effects.toast("登录后才能使用私人FM");
+        return;
+    }
+
+    let mut queue = PlayQueue::new(PlayMode::Sequential);
+    queue.set_origin(FM_QUEUE_ORIGIN);
+    let saved_queue = std::mem::replace(&mut app.play_queue, queue);
+    app.fm = Some(FmState {
+        buffer: VecDeque::new(),
+        saved_queue,
+        play_when_ready: true,
+    });
+    ctx.next_song_cache.reset(effects);
+    tracing::info!("📻 [FM] 开启私人FM");
+    app.play_status = "私人FM 加载中...".to_owned();
+    refill(app, ctx.request_tracker, ctx.req_ids, effects);
+    effects.emit_state(app);
+}
+
+/// 退出 FM 并恢复进入前的播放队列（正在播放的歌曲继续播放）
+fn exit(
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    next_song_cache: &mut NextSongCacheManager,
+    effects: &mut CoreEffects,
+) {
+    let Some(fm) = app.fm.take() else {
+        return;
+    };
+    if app.play_queue.origin() == Some(FM_QUEUE_ORIGIN) {
+        app.play_queue = fm.saved_queue;
+    }
+    request_tracker.clear(&RequestKey::PersonalFm);
+    next_song_cache.reset(effects);
+    tracing::info!("📻 [FM] 退出私人FM");
+}
+
+/// 缓冲区不足且没有进行中的请求时补充
+fn refill(
+    app: &App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let Some(fm) = &app.fm else {
+        return;
+    };
+    if fm.buffer.len() >= FM_REFILL_BELOW
+        || request_tracker
+            .get_pending(&RequestKey::PersonalFm)
+            .is_some()
+    {
+        return;
+    }
+    let id = request_tracker.issue(RequestKey::PersonalFm, req_ids);
+    tracing::debug!(
+        req_id = id,
+        buffered = fm.buffer.len(),
+        "📻 [FM] 补充缓冲区"
+    );
+    effects.send_netease_hi(NeteaseCommand::PersonalFm { req_id: id });
+}
+
+/// FM 模式下的下一首：从缓冲区取歌，追加到播放队列并播放
+///
+/// 用户已换成其他播放队列时 FM 失效，返回 false 由调用方按普通队列处理。
+pub(super) async fn play_next(
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    song_request_titles: &mut std::collections::HashMap<i64, String>,
+    req_ids: &IdAllocator,
+    next_song_cache: &mut NextSongCacheManager,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !app.fm_active() {
+        if app.fm.take().is_some() {
+            request_tracker.clear(&RequestKey::PersonalFm);
+            tracing::info!("📻 [FM] 播放队列已更换，私人FM 结束");
+        }
+        return false;
+    }
+    let Some(fm) = app.fm.as_mut() else {
+        return false;
+    };
+
+    match fm.buffer.pop_front() {
+        Some(song) => {
+            fm.play_when_ready = false;
+            app.play_queue.extend([song]);
+            let idx = app.play_queue.songs().len() - 1;
+            request_play_at_index(
+                app,
+                request_tracker,
+                song_request_titles,
+                req_ids,
+                idx,
+                next_song_cache,
+                effects,
+            )
+            .await;
+        }
+        None => {
+            fm.play_when_ready = true;
+            app.play_status = "私人FM 加载中...".to_owned();
+        }
+    }
+    refill(app, request_tracker, req_ids, effects);
+    true
+}
+
+/// 音频引擎已自行切换到预排的 FM 歌曲：从缓冲区取出并追加到播放队列
+pub(super) fn advance_to_queued(
+    app: &mut App,
+    song_id: i64,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let Some(fm) = app.fm.as_mut() else {
+        return;
+    };
+    if fm.buffer.front().is_none_or(|s| s.id != song_id) {
+        return;
+    }
+    let Some(song) = fm.buffer.pop_front() else {
+        return;
+    };
+    fm.play_when_ready = false;
+    app.play_queue.extend([song]);
+    app.play_queue
+        .set_current_index(app.play_queue.songs().len() - 1);
+    refill(app, request_tracker, req_ids, effects);
+}
+
+/// 处理 NeteaseEvent::PersonalFm：补充缓冲区，等待中时立即播放
+/// 返回 true 表示事件已处理，false 表示 req_id 不匹配
+pub async fn handle_personal_fm_event(
+    req_id: u64,
+    songs: &[Song],
+    app: &mut App,
+    ctx: &mut AudioEventCtx<'_>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !ctx.request_tracker.accept(&RequestKey::PersonalFm, req_id) {
+        return false;
+    }
+    let Some(fm) = app.fm.as_mut() else {
+        return true;
+    };
+    // 接口偶尔会返回缓冲区里已有的歌
+    let fresh: Vec<Song> = songs
+        .iter()
+        .filter(|s| !fm.buffer.iter().any(|b| b.id == s.id))
+        .cloned()
+        .collect();
+    tracing::info!(
+        received = songs.len(),
+        added = fresh.len(),
+        "📻 [FM] 收到歌曲"
+    );
+    fm.buffer.extend(fresh);
+
+    if fm.play_when_ready {
+        if fm.buffer.is_empty() {
+            app.play_status = "私人FM 暂无可播放的歌曲".to_owned();
+        } else {
+            play_next(
+                app,
+                ctx.request_tracker,
+                ctx.song_request_titles,
+                ctx.req_ids,
+                ctx.next_song_cache,
+                effects,
+            )
+            .await;
+        }
+    } else {
+        // 缓冲区刚补充，下一首可能此前无法预缓存
+        ctx.next_song_cache
+            .prefetch_next(app, effects, ctx.req_ids)
+            .await;
+        refill(app, ctx.request_tracker, ctx.req_ids, effects);
+    }
+    effects.emit_state(app);
+    true
+}
+
+/// 私人 FM 请求失败；需要登录时给出登录提示
+/// 返回 true 表示事件已处理，false 表示 req_id 不匹配
+pub fn handle_personal_fm_error(
+    req_id: u64,
+    error: &MessageError,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::PersonalFm, req_id) {
+        return false;
+    }
+    let message = match error {
+        MessageError::Netease(NeteaseErrorVariant::NeedLogin) => "私人FM需要登录".to_owned(),
+        e => format!("私人FM 加载失败: {e}"),
+    };
+    if app.fm.as_ref().is_some_and(|fm| fm.play_when_ready) {
+        app.play_status = message.clone();
+    }
+    effects.set_toast(Toast::error(message));
+    effects.emit_state(app);
+    true
+}
@@ -1,3 +1,12 @@
+pub mod ab_loop;
 pub mod audio;
+pub mod audio_focus;
+pub mod cache_status;
 pub mod control;
+pub mod download;
+pub mod fm;
 pub mod playback;
+pub mod queue_edit;
+pub mod queue_panel;
+pub mod queue_undo;
+pub mod scrobble;
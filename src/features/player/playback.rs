@@ -1,7 +1,9 @@
-use crate::app::{PlaylistMode, View};
-use crate::audio_worker::{AudioBufferState, AudioPlaybackMode};
-use std::time::Duration;
+use crate::app::{PlaylistMode, StartupResume, View};
+use crate::audio_worker::{
+    AudioBufferState, AudioPlaybackMode, MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED,
+};
 
+use crate::core::infra::IdAllocator;
 use crate::core::prelude::{
     app::App,
     audio::AudioCommand,
@@ -9,7 +11,6 @@ use crate::core::prelude::{
     infra::{NextSongCacheManager, RequestKey, RequestTracker},
     netease::NeteaseCommand,
 };
-use crate::core::utils;
 
 pub fn next_play_mode(m: crate::app::PlayMode) -> crate::app::PlayMode {
     use crate::app::PlayMode;
@@ -41,6 +42,32 @@ pub fn play_mode_label(m: crate::app::PlayMode) -> &'static str {
     }
 }
 
+/// `<` / `>` 每次调整的倍速步长
+pub const PLAYBACK_SPEED_STEP: f32 = 0.05;
+
+/// 倍速取整到 [`PLAYBACK_SPEED_STEP`] 并限制在引擎支持的范围内
+pub fn normalize_playback_speed(speed: f32) -> f32 {
+    if !speed.is_finite() {
+        return 1.0;
+    }
+    // 以步长的整数倍表示，避免多次 ±0.05 累积浮点误差
+    let steps = (speed / PLAYBACK_SPEED_STEP).round();
+    (steps / PLAYBACK_SPEED_STEP.recip().round()).clamp(MIN_PLAYBACK_SPEED, MAX_PLAYBACK_SPEED)
+}
+
+/// 设置播放倍速（见 [`normalize_playback_speed`]）；返回实际生效的倍速
+pub fn set_playback_speed(app: &mut App, effects: &mut CoreEffects, speed: f32) -> f32 {
+    let speed = normalize_playback_speed(speed);
+    if speed != app.play_speed {
+        app.set_play_speed(speed);
+        effects.send_audio_warn(
+            AudioCommand::SetPlaybackSpeed(speed),
+            "AudioWorker 通道已关闭：SetPlaybackSpeed 发送失败",
+        );
+    }
+    speed
+}
+
 fn blocked_seek_status(app: &App) -> Option<String> {
     if app.can_seek() {
         return None;
@@ -59,27 +86,21 @@ fn blocked_seek_status(app: &App) -> Option<String> {
     }
 }
 
-pub fn seek_relative(app: &mut App, effects: &mut CoreEffects, delta_ms: i64) {
+/// 相对当前位置 Seek，结果限制在 [0, 总时长]；返回实际跳转到的位置
+pub fn seek_relative(app: &mut App, effects: &mut CoreEffects, delta_ms: i64) -> Option<u64> {
     if let Some(status) = blocked_seek_status(app) {
         app.play_status = status;
-        return;
+        return None;
     }
-    let Some(total_ms) = app.play_total_ms else {
-        return;
-    };
+    let total_ms = app.play_total_ms?;
     let cur = app.playback_elapsed_ms() as i64;
     let next = (cur + delta_ms).clamp(0, total_ms as i64) as u64;
 
-    let now = std::time::Instant::now();
-    app.play_started_at = Some(now - Duration::from_millis(next));
-    if app.paused {
-        app.play_paused_at = Some(now);
-    } else {
-        app.play_paused_at = None;
-    }
-    app.play_paused_accum_ms = 0;
+    app.set_playback_position(next);
+    app.play_seek_at = Some(std::time::Instant::now());
 
     effects.send_audio(AudioCommand::SeekToMs(next));
+    Some(next)
 }
 
 pub fn seek_absolute(app: &mut App, effects: &mut CoreEffects, target_ms: u64) {
@@ -92,14 +113,8 @@ pub fn seek_absolute(app: &mut App, effects: &mut CoreEffects, target_ms: u64) {
     };
     let target = target_ms.min(total_ms);
 
-    let now = std::time::Instant::now();
-    app.play_started_at = Some(now - Duration::from_millis(target));
-    if app.paused {
-        app.play_paused_at = Some(now);
-    } else {
-        app.play_paused_at = None;
-    }
-    app.play_paused_accum_ms = 0;
+    app.set_playback_position(target);
+    app.play_seek_at = Some(std::time::Instant::now());
 
     effects.send_audio(AudioCommand::SeekToMs(target));
 }
@@ -108,7 +123,7 @@ pub(super) async fn request_play_at_index(
     app: &mut App,
     request_tracker: &mut RequestTracker<RequestKey>,
     song_request_titles: &mut std::collections::HashMap<i64, String>,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     idx: usize,
     next_song_cache: &mut NextSongCacheManager,
     effects: &mut CoreEffects,
@@ -122,29 +137,156 @@ pub(super) async fn request_play_at_index(
     if matches!(app.view, View::Playlists) && matches!(app.playlist_mode, PlaylistMode::Tracks) {
         app.playlist_tracks_selected = idx.min(app.playlist_tracks.len().saturating_sub(1));
     }
-    let title = format!("{} - {}", s.name, s.artists);
+    let title = s.display_title(app.song_name_mode);
     app.play_status = format!("获取播放链接中: {title}");
+    // 手动切歌（或 core 推进队列）：引擎中预排的下一首作废
+    next_song_cache.discard_queued(effects);
     song_request_titles.clear();
-    let id = request_tracker.issue(RequestKey::SongUrl, || utils::next_id(req_id));
+    let id = request_tracker.issue(RequestKey::SongUrl, req_ids);
     song_request_titles.insert(s.id, title);
+    // 预缓存时已降级的歌曲直接按缓存的音质请求
+    let br = next_song_cache
+        .cached_br(s.id)
+        .filter(|br| *br < app.play_br)
+        .unwrap_or(app.play_br);
     effects.send_netease_hi(NeteaseCommand::SongUrl {
         req_id: id,
         id: s.id,
-        br: app.play_br,
+        br,
     });
 
     // 触发下一首预缓存
-    next_song_cache.prefetch_next(app, effects, req_id).await;
+    next_song_cache.prefetch_next(app, effects, req_ids).await;
+}
+
+/// 加载启动时恢复的歌曲
+///
+/// 状态快照只恢复了队列与进度，音频引擎中并没有这首歌。客户端就绪（或接受恢复提示）后
+/// 请求当前歌曲的播放链接，开始播放后跳到恢复的进度；`resume_on_start` 关闭时随即暂停，
+/// 加载期间先把音量置 0，避免暂停前漏出声音。已有音频或没有可恢复的歌曲时不做任何事。
+pub async fn resume_restored_track(
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    song_request_titles: &mut std::collections::HashMap<i64, String>,
+    req_ids: &IdAllocator,
+    next_song_cache: &mut NextSongCacheManager,
+    effects: &mut CoreEffects,
+) {
+    if app.play_id.is_some() || app.startup_resume.is_some() {
+        return;
+    }
+    let Some(song_id) = app.play_song_id else {
+        return;
+    };
+    let Some(idx) = app.play_queue.index_of_playing(song_id) else {
+        return;
+    };
+    let position_ms = app.playback_elapsed_ms();
+    tracing::info!(
+        song_id,
+        position_ms,
+        resume_on_start = app.resume_on_start,
+        "🎵 [StateRestore] 加载上次播放的歌曲"
+    );
+    request_play_at_index(
+        app,
+        request_tracker,
+        song_request_titles,
+        req_ids,
+        idx,
+        next_song_cache,
+        effects,
+    )
+    .await;
+    app.pending_seek_ms = (position_ms > 0).then_some(position_ms);
+    app.startup_resume = Some(StartupResume {
+        song_id,
+        paused: !app.resume_on_start,
+    });
+    if !app.resume_on_start {
+        effects.send_audio_warn(
+            AudioCommand::SetVolume(0.0),
+            "AudioWorker 通道已关闭：SetVolume 发送失败",
+        );
+    }
+}
+
+/// 启动时恢复的歌曲拿不到播放链接：提示并保留队列（不自动跳到下一首）
+///
+/// 返回是否属于启动恢复；`song_id` 为 None 时不核对歌曲。
+pub fn fail_startup_resume(
+    app: &mut App,
+    song_id: Option<i64>,
+    reason: &str,
+    effects: &mut CoreEffects,
+) -> bool {
+    if app
+        .startup_resume
+        .take_if(|r| song_id.is_none_or(|id| id == r.song_id))
+        .is_none()
+    {
+        return false;
+    }
+    tracing::warn!(?song_id, reason, "🎵 [StateRestore] 上次播放的歌曲加载失败");
+    app.pending_seek_ms = None;
+    app.play_status = format!("无法恢复上次播放：{reason}（队列已保留）");
+    effects.send_audio_warn(
+        AudioCommand::SetVolume(app.output_volume()),
+        "AudioWorker 通道已关闭：SetVolume 发送失败",
+    );
+    effects.emit_state(app);
+    true
+}
+
+/// 音频引擎已自行切换到预排的下一首：推进播放队列，与实际播放保持一致
+pub(super) fn advance_to_queued(
+    app: &mut App,
+    song_id: i64,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    if app.fm_active() {
+        super::fm::advance_to_queued(app, song_id, request_tracker, req_ids, effects);
+        return;
+    }
+    let is_next = app
+        .play_queue
+        .peek_next_index()
+        .and_then(|idx| app.play_queue.songs().get(idx))
+        .is_some_and(|s| s.id == song_id);
+    if !is_next {
+        return;
+    }
+    if let Some(idx) = app.play_queue.next_index()
+        && matches!(app.view, View::Playlists)
+        && matches!(app.playlist_mode, PlaylistMode::Tracks)
+    {
+        app.playlist_tracks_selected = idx.min(app.playlist_tracks.len().saturating_sub(1));
+    }
 }
 
 pub async fn play_next(
     app: &mut App,
     request_tracker: &mut RequestTracker<RequestKey>,
     song_request_titles: &mut std::collections::HashMap<i64, String>,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     next_song_cache: &mut NextSongCacheManager,
     effects: &mut CoreEffects,
 ) {
+    if app.fm.is_some()
+        && super::fm::play_next(
+            app,
+            request_tracker,
+            song_request_titles,
+            req_ids,
+            next_song_cache,
+            effects,
+        )
+        .await
+    {
+        return;
+    }
     let Some(current_idx) = app.play_queue.current_index() else {
         return;
     };
@@ -172,7 +314,7 @@ pub async fn play_next(
         app,
         request_tracker,
         song_request_titles,
-        req_id,
+        req_ids,
         next_idx,
         next_song_cache,
         effects,
@@ -184,10 +326,14 @@ pub(super) async fn play_prev(
     app: &mut App,
     request_tracker: &mut RequestTracker<RequestKey>,
     song_request_titles: &mut std::collections::HashMap<i64, String>,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     next_song_cache: &mut NextSongCacheManager,
     effects: &mut CoreEffects,
 ) {
+    if app.fm_active() {
+        app.play_status = "私人FM 不支持上一首".to_owned();
+        return;
+    }
     if app.play_queue.is_empty() || app.play_queue.current_index().is_none() {
         return;
     }
@@ -198,7 +344,7 @@ pub(super) async fn play_prev(
         app,
         request_tracker,
         song_request_titles,
-        req_id,
+        req_ids,
         prev_idx,
         next_song_cache,
         effects,
@@ -208,10 +354,11 @@ pub(super) async fn play_prev(
 
 #[cfg(test)]
 mod tests {
-    use super::seek_absolute;
+    use super::{PLAYBACK_SPEED_STEP, seek_absolute, seek_relative, set_playback_speed};
     use crate::app::App;
     use crate::audio_worker::{AudioBufferState, AudioStreamHint};
     use crate::core::CoreEffects;
+    use std::time::Duration;
 
     #[test]
     fn seek_absolute_is_blocked_when_streaming_not_seekable() {
@@ -234,4 +381,105 @@ mod tests {
         assert!(matches!(app.play_stream_hint, Some(AudioStreamHint { .. })));
         let _ = effects;
     }
+
+    fn playing_at(elapsed_ms: u64, total_ms: u64) -> App {
+        App {
+            play_total_ms: Some(total_ms),
+            play_started_at: Some(std::time::Instant::now() - Duration::from_millis(elapsed_ms)),
+            paused: true,
+            play_paused_at: Some(std::time::Instant::now()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn large_seek_step_clamps_at_track_end() {
+        let mut app = playing_at(200_000, 210_000);
+        let mut effects = CoreEffects::default();
+
+        let pos = seek_relative(&mut app, &mut effects, 30_000);
+
+        assert_eq!(pos, Some(210_000));
+        assert!(app.playback_elapsed_ms().abs_diff(210_000) < 50);
+    }
+
+    #[test]
+    fn large_seek_step_clamps_at_track_start() {
+        let mut app = playing_at(12_000, 210_000);
+        let mut effects = CoreEffects::default();
+
+        let pos = seek_relative(&mut app, &mut effects, -30_000);
+
+        assert_eq!(pos, Some(0));
+        assert!(app.playback_elapsed_ms() < 50);
+    }
+
+    fn playing_unpaused_at(elapsed_ms: u64) -> App {
+        App {
+            play_total_ms: Some(600_000),
+            play_started_at: Some(std::time::Instant::now() - Duration::from_millis(elapsed_ms)),
+            ..Default::default()
+        }
+    }
+
+    /// 模拟经过了 `ms` 的实际时间
+    fn advance(app: &mut App, ms: u64) {
+        app.play_started_at = app.play_started_at.map(|t| t - Duration::from_millis(ms));
+    }
+
+    #[test]
+    fn elapsed_time_scales_with_speed_after_change() {
+        let mut app = playing_unpaused_at(20_000);
+        let mut effects = CoreEffects::default();
+
+        assert_eq!(set_playback_speed(&mut app, &mut effects, 2.0), 2.0);
+        // 改变倍速前的进度不变
+        assert!(app.playback_elapsed_ms().abs_diff(20_000) < 50);
+
+        advance(&mut app, 5_000);
+        assert!(app.playback_elapsed_ms().abs_diff(30_000) < 50);
+
+        set_playback_speed(&mut app, &mut effects, 0.5);
+        advance(&mut app, 10_000);
+        assert!(app.playback_elapsed_ms().abs_diff(35_000) < 50);
+    }
+
+    #[test]
+    fn pauses_do_not_count_under_speed() {
+        let mut app = playing_unpaused_at(10_000);
+        let mut effects = CoreEffects::default();
+        set_playback_speed(&mut app, &mut effects, 1.5);
+
+        advance(&mut app, 4_000);
+        app.play_paused_accum_ms = 2_000;
+        assert!(app.playback_elapsed_ms().abs_diff(13_000) < 50);
+    }
+
+    #[test]
+    fn seek_under_speed_lands_on_target() {
+        let mut app = playing_at(10_000, 210_000);
+        let mut effects = CoreEffects::default();
+        set_playback_speed(&mut app, &mut effects, 1.25);
+
+        let pos = seek_relative(&mut app, &mut effects, 30_000);
+
+        assert_eq!(pos, Some(40_000));
+        assert!(app.playback_elapsed_ms().abs_diff(40_000) < 50);
+    }
+
+    #[test]
+    fn speed_steps_are_exact_and_clamped() {
+        let mut app = App::default();
+        let mut effects = CoreEffects::default();
+
+        let mut speed = app.play_speed;
+        for _ in 0..5 {
+            speed = set_playback_speed(&mut app, &mut effects, speed + PLAYBACK_SPEED_STEP);
+        }
+        assert_eq!(speed, 1.25);
+        assert_eq!(set_playback_speed(&mut app, &mut effects, 3.0), 2.0);
+        assert_eq!(set_playback_speed(&mut app, &mut effects, 0.1), 0.5);
+        assert_eq!(set_playback_speed(&mut app, &mut effects, 1.0), 1.0);
+        assert_eq!(app.play_speed, 1.0);
+    }
 }
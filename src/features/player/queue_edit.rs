@@ -0,0 +1,187 @@
+//! 播放队列的编辑：队列页调整顺序、移除与播放选中的歌曲，
+//! 以及从搜索结果 / 歌单歌曲中把选中的歌曲加入队列
+//!
+//! 队列页的选中位置沿用队列面板的 [`App::queue_selected`]（播放顺序中的位置）。
+//! 编辑后「下一首」变了时，作废已预缓存 / 预排的下一首并重新预缓存。
+
+use crate::app::{PlaylistMode, RECOMMEND_ORIGIN, Song, Toast, View};
+use crate::core::prelude::{app::App, audio::AudioCommand};
+use crate::features::player::control::PlayerControlCtx;
+use crate::features::player::playback::request_play_at_index;
+use crate::features::player::queue_panel::selected_pos;
+
+/// 即将播放的下一首的歌曲 id
+fn upcoming(app: &App) -> Option<i64> {
+    app.play_queue.peek_next().map(|s| s.id)
+}
+
+/// 「下一首」变化时重新预缓存
+async fn refresh_next(app: &App, before: Option<i64>, ctx: &mut PlayerControlCtx<'_>) {
+    if upcoming(app) == before {
+        return;
+    }
+    ctx.next_song_cache.reset(ctx.effects);
+    ctx.next_song_cache
+        .prefetch_next(app, ctx.effects, ctx.req_ids)
+        .await;
+}
+
+/// 把选中的歌曲在播放顺序中前移（`up`）或后移一位，选中项跟随
+pub async fn reorder_selected(app: &mut App, up: bool, ctx: &mut PlayerControlCtx<'_>) {
+    let Some(pos) = selected_pos(app) else {
+        return;
+    };
+    let before = upcoming(app);
+    let Some(next) = app.play_queue.move_adjacent(pos, up) else {
+        return;
+    };
+    app.queue_selected = Some(next);
+    refresh_next(app, before, ctx).await;
+    ctx.effects.emit_state(app);
+}
+
+/// 从队列中移除选中的歌曲；移除的是正在播放的歌曲时接着播放下一首
+pub async fn remove_selected(app: &mut App, ctx: &mut PlayerControlCtx<'_>) {
+    let Some(pos) = selected_pos(app) else {
+        return;
+    };
+    let Some(&idx) = app.play_queue.order().get(pos) else {
+        return;
+    };
+    let was_current = app.play_queue.cursor_pos() == Some(pos);
+    let before = upcoming(app);
+    let Some(removed) = app.play_queue.remove(idx) else {
+        return;
+    };
+    tracing::info!(
+        song_id = removed.id,
+        pos,
+        was_current,
+        "🗑️ [Queue] 移除歌曲"
+    );
+
+    let len = app.play_queue.order().len();
+    app.queue_selected = (len > 0).then(|| pos.min(len - 1));
+    let title = removed.display_title(app.song_name_mode);
+    ctx.effects
+        .set_toast(Toast::info(format!("已从队列移除: {title}")));
+
+    if was_current && app.play_song_id.is_some() {
+        match app.play_queue.current_index() {
+            Some(next) => {
+                request_play_at_index(
+                    app,
+                    ctx.request_tracker,
+                    ctx.song_request_titles,
+                    ctx.req_ids,
+                    next,
+                    ctx.next_song_cache,
+                    ctx.effects,
+                )
+                .await;
+            }
+            None => {
+                ctx.next_song_cache.reset(ctx.effects);
+                ctx.effects
+                    .send_audio_warn(AudioCommand::Stop, "AudioWorker 通道已关闭：Stop 发送失败");
+            }
+        }
+    } else {
+        refresh_next(app, before, ctx).await;
+    }
+    ctx.effects.emit_state(app);
+}
+
+/// 从选中的歌曲开始播放
+pub async fn play_selected(app: &mut App, ctx: &mut PlayerControlCtx<'_>) {
+    let Some(pos) = selected_pos(app) else {
+        return;
+    };
+    let Some(&idx) = app.play_queue.order().get(pos) else {
+        return;
+    };
+    app.queue_selected = Some(pos);
+    request_play_at_index(
+        app,
+        ctx.request_tracker,
+        ctx.song_request_titles,
+        ctx.req_ids,
+        idx,
+        ctx.next_song_cache,
+        ctx.effects,
+    )
+    .await;
+    ctx.effects.emit_state(app);
+}
+
+/// 加入队列的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnqueueAt {
+    /// 当前歌曲之后
+    Next,
+    /// 队尾
+    Last,
+}
+
+/// 当前页面选中的歌曲及其来源（专辑 / 歌单等，用于队列分组）
+fn selected_song(app: &App) -> Option<(Song, Option<String>)> {
+    match app.view {
+        View::Search if !app.search_showing_albums() && !app.search_showing_artists() => {
+            let song = app.search_results.get(app.search_selected)?.clone();
+            Some((song, app.search_opened.as_ref().map(|o| o.origin())))
+        }
+        View::Playlists if app.playlist_mode == PlaylistMode::Tracks => {
+            let song = app
+                .playlist_tracks
+                .get(app.playlist_tracks_selected)?
+                .clone();
+            let origin = if app.playlist_tracks_recommend {
+                Some(RECOMMEND_ORIGIN.to_owned())
+            } else {
+                app.open_playlist_id().map(|id| format!("playlist:{id}"))
+            };
+            Some((song, origin))
+        }
+        _ => None,
+    }
+}
+
+/// 在当前页面的状态栏显示结果
+fn set_view_status(app: &mut App, status: String) {
+    match app.view {
+        View::Search => app.search_status = status,
+        View::Playlists => app.playlists_status = status,
+        _ => {}
+    }
+}
+
+/// 把选中的歌曲加入播放队列（允许重复），不打断当前播放
+pub async fn enqueue_selected(app: &mut App, at: EnqueueAt, ctx: &mut PlayerControlCtx<'_>) {
+    let Some((song, origin)) = selected_song(app) else {
+        return;
+    };
+    if app.fm_active() {
+        set_view_status(app, "私人FM 播放中，无法加入队列".to_owned());
+        ctx.effects.emit_state(app);
+        return;
+    }
+    let title = song.display_title(app.song_name_mode);
+    let before = upcoming(app);
+    let pos = match at {
+        EnqueueAt::Next => app.play_queue.insert_after_cursor(song, origin.as_deref()),
+        EnqueueAt::Last => app.play_queue.push_back(song, origin.as_deref()),
+    };
+    if let Some(sel) = app.queue_selected.filter(|&sel| sel >= pos) {
+        app.queue_selected = Some(sel + 1);
+    }
+    tracing::info!(?at, pos, "➕ [Queue] 加入队列: {title}");
+    set_view_status(
+        app,
+        match at {
+            EnqueueAt::Next => format!("已添加到下一首播放: {title}"),
+            EnqueueAt::Last => format!("已添加到队尾: {title}"),
+        },
+    );
+    refresh_next(app, before, ctx).await;
+    ctx.effects.emit_state(app);
+}
@@ -0,0 +1,63 @@
+//! 队列面板（右栏）的选择与来源分组折叠
+//!
+//! 只改变面板中的选中位置与折叠状态，不影响播放队列的顺序与游标。
+
+use std::sync::Arc;
+
+use crate::app::queue_groups::QueueGroupView;
+use crate::app::queue_groups::{self, QueueLayout};
+use crate::app::{App, View};
+
+/// 选中位置：未选择（或队列缩短后越界）时从当前歌曲开始
+pub(super) fn selected_pos(app: &App) -> Option<usize> {
+    let len = app.play_queue.order().len();
+    if len == 0 {
+        return None;
+    }
+    Some(
+        app.queue_selected
+            .or(app.play_queue.cursor_pos())
+            .unwrap_or(0)
+            .min(len - 1),
+    )
+}
+
+/// 移动选中位置；折叠分组中的歌曲被跳过（队列页不分组，逐首移动）
+pub fn move_selection(app: &mut App, delta: isize) {
+    let Some(pos) = selected_pos(app) else {
+        return;
+    };
+    let groups: Arc<[QueueGroupView]> = if app.view == View::Queue {
+        Arc::from([])
+    } else {
+        app.queue_group_views()
+    };
+    let next = if groups.is_empty() {
+        let last = app.play_queue.order().len() - 1;
+        pos.saturating_add_signed(delta).min(last)
+    } else {
+        let layout = QueueLayout::new(&groups, app.play_queue.cursor_pos());
+        layout.step(pos, delta).unwrap_or(pos)
+    };
+    app.queue_selected = Some(next);
+}
+
+/// 折叠 / 展开选中位置所在的分组；返回是否有变化
+pub fn set_collapsed(app: &mut App, collapsed: bool) -> bool {
+    let Some(pos) = selected_pos(app) else {
+        return false;
+    };
+    if app.queue_group_views().is_empty() {
+        return false;
+    }
+    let Some(&idx) = app.play_queue.order().get(pos) else {
+        return false;
+    };
+    let key = queue_groups::collapse_key(app.play_queue.entry_origin(idx).map(|o| &**o)).to_owned();
+    app.queue_selected = Some(pos);
+    if collapsed {
+        app.queue_collapsed.insert(key)
+    } else {
+        app.queue_collapsed.remove(&key)
+    }
+}
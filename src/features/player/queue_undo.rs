@@ -0,0 +1,81 @@
+//! 撤销播放队列的整体替换
+//!
+//! 打开歌单、播放专辑等操作会整体替换播放队列。替换前的队列记在
+//! [`App::queue_undo`] 中（只保留一层），在队列面板按 `u` 换回来：
+//! 替换时正在播放的歌曲仍在播放时连同播放位置一起恢复，否则只恢复队列内容，
+//! 当前播放不受影响。
+
+use crate::app::{FM_QUEUE_ORIGIN, ReplacedQueue, Song, Toast};
+use crate::core::prelude::{app::App, effects::CoreEffects, infra::NextSongCacheManager};
+
+/// 替换后的提示
+pub const REPLACED_TOAST: &str = "队列已替换 · u 撤销";
+
+/// 用新歌曲替换播放队列，记下旧队列供撤销
+///
+/// 返回是否记下了旧队列：旧队列为空、来自私人 FM 或与新队列歌曲相同时不记，
+/// 此时保留之前的撤销记录。
+pub fn replace(
+    app: &mut App,
+    songs: Vec<Song>,
+    start_index: Option<usize>,
+    origin: Option<String>,
+) -> bool {
+    let queue = &app.play_queue;
+    let undoable = !queue.is_empty()
+        && queue.origin() != Some(FM_QUEUE_ORIGIN)
+        && !queue
+            .songs()
+            .iter()
+            .map(|s| s.id)
+            .eq(songs.iter().map(|s| s.id));
+    if undoable {
+        app.queue_undo = Some(ReplacedQueue {
+            queue: app.play_queue.clone(),
+            playing_song_id: app.play_song_id,
+        });
+    }
+    let _old = app.play_queue.set_songs(songs, start_index);
+    if let Some(origin) = origin {
+        app.play_queue.set_origin(origin);
+    }
+    undoable
+}
+
+/// 替换队列后的提示：记下了旧队列时提示可撤销
+pub fn toast_replaced(recorded: bool, effects: &mut CoreEffects) {
+    if recorded {
+        effects.set_toast(Toast::info(REPLACED_TOAST));
+    }
+}
+
+/// 换回被替换掉的队列
+pub fn undo(app: &mut App, next_song_cache: &mut NextSongCacheManager, effects: &mut CoreEffects) {
+    let Some(saved) = app.queue_undo.take() else {
+        effects.set_toast(Toast::info("没有可撤销的队列替换"));
+        return;
+    };
+    let resumes = app.play_song_id.is_none() || app.play_song_id == saved.playing_song_id;
+    app.play_queue.restore_from(saved.queue);
+    if !resumes {
+        // 正在播放别的歌曲：只恢复内容，游标跟随当前歌曲（不在队列中时清空）
+        match app
+            .play_song_id
+            .and_then(|id| app.play_queue.index_of_playing(id))
+        {
+            Some(idx) => {
+                app.play_queue.set_current_index(idx);
+            }
+            None => app.play_queue.clear_cursor(),
+        }
+    }
+    app.queue_selected = None;
+    next_song_cache.reset(effects);
+    tracing::info!(
+        songs = app.play_queue.songs().len(),
+        resumes,
+        "↩️ [Queue] 撤销队列替换"
+    );
+    effects.set_toast(Toast::info("已恢复替换前的队列"));
+    effects.emit_state(app);
+}
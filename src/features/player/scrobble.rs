@@ -0,0 +1,86 @@
+//! 听歌记录上报（`/api/feedback/weblog`）
+//!
+//! 每次播放最多上报一次：播放超过 [`SCROBBLE_AFTER_MS`]，或不足该时长但自然播放结束时。
+//! 只在登录后上报；上报是尽力而为的，失败只记日志。
+
+use crate::app::App;
+use crate::core::prelude::{effects::CoreEffects, netease::NeteaseCommand};
+
+/// 播放超过该时长即计为一次收听
+pub const SCROBBLE_AFTER_MS: u64 = 30_000;
+
+/// 当前这次播放的上报状态
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListenScrobble {
+    play_id: u64,
+    song_id: i64,
+    /// 来自歌单队列时的歌单 id
+    source_id: Option<i64>,
+    sent: bool,
+}
+
+impl ListenScrobble {
+    /// 新歌开始播放；游客不上报
+    pub fn start(app: &App, play_id: u64, song_id: i64) -> Option<Self> {
+        if !app.logged_in {
+            return None;
+        }
+        let source_id = app
+            .play_queue
+            .origin()
+            .and_then(|o| o.strip_prefix("playlist:"))
+            .and_then(|id| id.parse().ok());
+        Some(Self {
+            play_id,
+            song_id,
+            source_id,
+            sent: false,
+        })
+    }
+
+    fn send(&mut self, played_ms: u64, effects: &mut CoreEffects) {
+        self.sent = true;
+        tracing::debug!(
+            song_id = self.song_id,
+            source_id = ?self.source_id,
+            played_ms,
+            "🎵 [Scrobble] 上报听歌记录"
+        );
+        effects.send_netease_lo(NeteaseCommand::Scrobble {
+            song_id: self.song_id,
+            source_id: self.source_id,
+            time_secs: played_ms / 1000,
+        });
+    }
+}
+
+/// 播放位置上报：超过 30 秒时上报一次
+pub fn on_position(
+    scrobble: &mut Option<ListenScrobble>,
+    play_id: u64,
+    position_ms: u64,
+    effects: &mut CoreEffects,
+) {
+    if let Some(s) = scrobble.as_mut()
+        && s.play_id == play_id
+        && !s.sent
+        && position_ms >= SCROBBLE_AFTER_MS
+    {
+        s.send(position_ms, effects);
+    }
+}
+
+/// 自然播放结束：不足 30 秒的短歌也算一次完整收听
+pub fn on_ended(
+    scrobble: &mut Option<ListenScrobble>,
+    play_id: u64,
+    played_ms: u64,
+    effects: &mut CoreEffects,
+) {
+    if let Some(s) = scrobble.as_mut()
+        && s.play_id == play_id
+        && !s.sent
+    {
+        s.send(played_ms, effects);
+    }
+}
@@ -0,0 +1,429 @@
+//! 编辑自己的歌单：从搜索结果添加歌曲（选中的一首或 `v` 标记的多首）、从歌单中移除歌曲
+//!
+//! 只允许操作自己创建的普通歌单；「我喜欢的音乐」由收藏功能（L）维护，这里不列出。
+//! 多首歌曲在一次请求中提交，接口返回实际添加的歌曲，已在歌单中的歌曲计为「已存在」。
+
+use std::collections::HashMap;
+
+use crate::app::{
+    Playlist, PlaylistMode, PlaylistPicker, PlaylistStats, PreloadStatus, Song, Toast,
+};
+use crate::core::infra::IdAllocator;
+use crate::core::infra::PreloadManager;
+use crate::core::prelude::{app::App, effects::CoreEffects, netease::NeteaseCommand};
+use crate::domain::model::PlaylistTrackOp;
+use crate::error::{MessageError, NeteaseErrorVariant};
+
+/// 已发出、尚未返回的歌单编辑请求
+#[derive(Debug, Clone)]
+pub struct PendingPlaylistEdit {
+    pub playlist_id: i64,
+    pub playlist_name: String,
+    pub songs: Vec<Song>,
+    pub op: PlaylistTrackOp,
+}
+
+impl PendingPlaylistEdit {
+    /// 提示中的歌曲描述：一首时为「歌名」，多首时为「N 首」
+    fn songs_label(&self) -> String {
+        match self.songs.as_slice() {
+            [song] => format!("「{}」", song.name),
+            songs => format!(" {} 首", songs.len()),
+        }
+    }
+}
+
+/// 可以编辑的歌单：自己创建的、非「我喜欢」
+fn is_editable(p: &Playlist) -> bool {
+    !p.subscribed && p.special_type != 5
+}
+
+/// 为选中的搜索结果打开「添加到歌单」选择框
+pub fn open_picker_for_search(app: &mut App, effects: &mut CoreEffects) {
+    if app.search_showing_albums() || app.search_showing_artists() {
+        return;
+    }
+    let Some(song) = app.search_results.get(app.search_selected).cloned() else {
+        return;
+    };
+    open_picker(app, vec![song], effects);
+}
+
+/// 为全部已标记的搜索结果打开「添加到歌单」选择框
+pub fn open_picker_for_marked(app: &mut App, effects: &mut CoreEffects) {
+    if app.search_marked.is_empty() {
+        effects.toast("没有标记的歌曲（v 标记）");
+        return;
+    }
+    let songs = app.search_marked.clone();
+    open_picker(app, songs, effects);
+}
+
+fn open_picker(app: &mut App, songs: Vec<Song>, effects: &mut CoreEffects) {
+    if !app.logged_in {
+        effects.toast("登录后才能编辑歌单");
+        return;
+    }
+    let playlists: Vec<Playlist> = app
+        .playlists
+        .iter()
+        .filter(|p| is_editable(p))
+        .cloned()
+        .collect();
+    if playlists.is_empty() {
+        effects.toast("没有可添加的歌单");
+        return;
+    }
+    app.playlist_picker = Some(PlaylistPicker {
+        songs,
+        playlists,
+        ..Default::default()
+    });
+    effects.emit_state(app);
+}
+
+pub fn move_picker(app: &mut App, effects: &mut CoreEffects, down: bool) {
+    let Some(picker) = app.playlist_picker.as_mut() else {
+        return;
+    };
+    let next = if down {
+        (picker.selected + 1).min(picker.visible().len().saturating_sub(1))
+    } else {
+        picker.selected.saturating_sub(1)
+    };
+    if next != picker.selected {
+        picker.selected = next;
+        effects.emit_state(app);
+    }
+}
+
+/// 修改筛选词：`push` 为 None 时删除最后一个字符；选中项回到第一个匹配的歌单
+pub fn edit_picker_filter(app: &mut App, effects: &mut CoreEffects, push: Option<&str>) {
+    let Some(picker) = app.playlist_picker.as_mut() else {
+        return;
+    };
+    match push {
+        Some(text) => picker.filter.push_str(text),
+        None => {
+            picker.filter.pop();
+        }
+    }
+    picker.selected = 0;
+    effects.emit_state(app);
+}
+
+pub fn cancel_picker(app: &mut App, effects: &mut CoreEffects) {
+    if app.playlist_picker.take().is_some() {
+        effects.emit_state(app);
+    }
+}
+
+/// 确认选择：把歌曲添加到选中的歌单；筛选后没有匹配的歌单时保持选择框打开
+pub fn confirm_picker(
+    app: &mut App,
+    pending: &mut HashMap<u64, PendingPlaylistEdit>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let Some((playlist_id, playlist_name)) = app
+        .playlist_picker
+        .as_ref()
+        .and_then(|p| p.selected_playlist())
+        .map(|p| (p.id, p.name.clone()))
+    else {
+        return;
+    };
+    let Some(picker) = app.playlist_picker.take() else {
+        return;
+    };
+    // 已提交的歌曲不再保留标记
+    app.search_marked
+        .retain(|m| !picker.songs.iter().any(|s| s.id == m.id));
+    effects.emit_state(app);
+    send_edit(
+        PendingPlaylistEdit {
+            playlist_id,
+            playlist_name,
+            songs: picker.songs,
+            op: PlaylistTrackOp::Add,
+        },
+        pending,
+        req_ids,
+        effects,
+    );
+}
+
+/// 从当前打开的歌单中移除选中的歌曲（每日推荐与收藏的歌单不可编辑）
+pub fn remove_selected_track(
+    app: &mut App,
+    pending: &mut HashMap<u64, PendingPlaylistEdit>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    if !matches!(app.playlist_mode, PlaylistMode::Tracks) || app.playlist_tracks_recommend {
+        return;
+    }
+    if !app.logged_in {
+        effects.toast("登录后才能编辑歌单");
+        return;
+    }
+    let Some(playlist) = app.playlists.get(app.playlists_selected) else {
+        return;
+    };
+    if !is_editable(playlist) {
+        effects.toast("只能从自己创建的歌单中移除歌曲");
+        return;
+    }
+    let Some(song) = app
+        .playlist_tracks
+        .get(app.playlist_tracks_selected)
+        .cloned()
+    else {
+        return;
+    };
+    send_edit(
+        PendingPlaylistEdit {
+            playlist_id: playlist.id,
+            playlist_name: playlist.name.clone(),
+            songs: vec![song],
+            op: PlaylistTrackOp::Del,
+        },
+        pending,
+        req_ids,
+        effects,
+    );
+}
+
+fn send_edit(
+    edit: PendingPlaylistEdit,
+    pending: &mut HashMap<u64, PendingPlaylistEdit>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let id = req_ids.next();
+    tracing::info!(
+        req_id = id,
+        playlist_id = edit.playlist_id,
+        songs = edit.songs.len(),
+        op = edit.op.as_str(),
+        "🎵 [Playlists] 编辑歌单"
+    );
+    effects.send_netease_hi_warn(
+        NeteaseCommand::PlaylistTracksManipulate {
+            req_id: id,
+            op: edit.op,
+            playlist_id: edit.playlist_id,
+            track_ids: edit.songs.iter().map(|s| s.id).collect(),
+        },
+        "NeteaseActor 通道已关闭：PlaylistTracksManipulate 发送失败",
+    );
+    pending.insert(id, edit);
+}
+
+/// 编辑成功：按实际生效的歌曲更新歌曲数与预加载；移除时同步已打开的歌曲列表
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+pub fn handle_playlist_tracks_manipulated_event(
+    req_id: u64,
+    changed: &[i64],
+    app: &mut App,
+    pending: &mut HashMap<u64, PendingPlaylistEdit>,
+    preload_mgr: &mut PreloadManager,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(edit) = pending.remove(&req_id) else {
+        return false;
+    };
+    let changed: Vec<&Song> = edit
+        .songs
+        .iter()
+        .filter(|s| changed.contains(&s.id))
+        .collect();
+    update_preload(app, preload_mgr, &edit, &changed);
+
+    let open_playlist_id = app.playlists.get(app.playlists_selected).map(|p| p.id);
+    if let Some(p) = app.playlists.iter_mut().find(|p| p.id == edit.playlist_id) {
+        let n = changed.len() as i64;
+        p.track_count = match edit.op {
+            PlaylistTrackOp::Add => p.track_count.saturating_add(n),
+            PlaylistTrackOp::Del => p.track_count.saturating_sub(n).max(0),
+        };
+    }
+
+    match edit.op {
+        PlaylistTrackOp::Add => {
+            effects.toast(add_summary(&edit, changed.len()));
+        }
+        PlaylistTrackOp::Del => {
+            let showing = matches!(app.playlist_mode, PlaylistMode::Tracks)
+                && !app.playlist_tracks_recommend
+                && open_playlist_id == Some(edit.playlist_id);
+            if showing {
+                app.playlist_tracks
+                    .retain(|s| !changed.iter().any(|c| c.id == s.id));
+                app.playlist_tracks_selected = app
+                    .playlist_tracks_selected
+                    .min(app.playlist_tracks.len().saturating_sub(1));
+                app.playlist_tracks_stats = Some(PlaylistStats::compute(&app.playlist_tracks));
+            }
+            effects.toast(format!(
+                "已从歌单「{}」移除{}",
+                edit.playlist_name,
+                edit.songs_label()
+            ));
+        }
+    }
+    effects.emit_state(app);
+    true
+}
+
+/// 添加结果的提示：全部添加、部分已存在（「已添加 8/10 首…，2 首已存在」）或全部已存在
+fn add_summary(edit: &PendingPlaylistEdit, added: usize) -> String {
+    let total = edit.songs.len();
+    let name = &edit.playlist_name;
+    match (total, added) {
+        (1, 0) => format!("{}已在歌单「{name}」中", edit.songs_label()),
+        (_, n) if n >= total => format!("已添加{}到歌单「{name}」", edit.songs_label()),
+        (_, n) => format!(
+            "已添加 {n}/{total} 首到歌单「{name}」，{} 首已存在",
+            total - n
+        ),
+    }
+}
+
+/// 同步目标歌单的预加载：已完成时就地更新（新歌曲插入到最前，与网易云歌单排序一致），
+/// 仍在加载时作废重来
+fn update_preload(
+    app: &mut App,
+    preload_mgr: &mut PreloadManager,
+    edit: &PendingPlaylistEdit,
+    changed: &[&Song],
+) {
+    let Some(preload) = app.playlist_preloads.get_mut(&edit.playlist_id) else {
+        return;
+    };
+    if !matches!(preload.status, PreloadStatus::Completed) {
+        preload_mgr.invalidate_playlist(app, edit.playlist_id);
+        return;
+    }
+    preload
+        .songs
+        .retain(|s| !changed.iter().any(|c| c.id == s.id));
+    if edit.op == PlaylistTrackOp::Add {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        for song in changed {
+            preload.added_at.insert(song.id, now_ms);
+        }
+        preload
+            .songs
+            .splice(0..0, changed.iter().map(|&s| s.clone()));
+    } else {
+        for song in changed {
+            preload.added_at.remove(&song.id);
+        }
+    }
+    preload.stats = None;
+}
+
+/// 编辑失败：提示错误，本地状态不变
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+pub fn handle_playlist_edit_error(
+    req_id: u64,
+    error: &MessageError,
+    pending: &mut HashMap<u64, PendingPlaylistEdit>,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(edit) = pending.remove(&req_id) else {
+        return false;
+    };
+    // 要添加的歌曲全部已在歌单中时接口返回 502
+    if edit.op == PlaylistTrackOp::Add
+        && matches!(
+            error,
+            MessageError::Netease(NeteaseErrorVariant::Api { code: 502, .. })
+        )
+    {
+        effects.toast(add_summary(&edit, 0));
+        return true;
+    }
+    tracing::warn!(
+        req_id,
+        playlist_id = edit.playlist_id,
+        songs = edit.songs.len(),
+        op = edit.op.as_str(),
+        "🎵 [Playlists] 编辑歌单失败: {error}"
+    );
+    effects.set_toast(Toast::error(match edit.op {
+        PlaylistTrackOp::Add => format!("添加到歌单「{}」失败: {error}", edit.playlist_name),
+        PlaylistTrackOp::Del => format!("从歌单「{}」移除失败: {error}", edit.playlist_name),
+    }));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit_with(n: i64) -> PendingPlaylistEdit {
+        PendingPlaylistEdit {
+            playlist_id: 1,
+            playlist_name: "夜跑".to_owned(),
+            songs: (1..=n)
+                .map(|id| Song {
+                    id,
+                    name: format!("s{id}"),
+                    ..Default::default()
+                })
+                .collect(),
+            op: PlaylistTrackOp::Add,
+        }
+    }
+
+    #[test]
+    fn add_summary_reports_existing_songs() {
+        let ten = edit_with(10);
+        assert_eq!(add_summary(&ten, 10), "已添加 10 首到歌单「夜跑」");
+        assert_eq!(
+            add_summary(&ten, 8),
+            "已添加 8/10 首到歌单「夜跑」，2 首已存在"
+        );
+        assert_eq!(
+            add_summary(&ten, 0),
+            "已添加 0/10 首到歌单「夜跑」，10 首已存在"
+        );
+
+        let one = edit_with(1);
+        assert_eq!(add_summary(&one, 1), "已添加「s1」到歌单「夜跑」");
+        assert_eq!(add_summary(&one, 0), "「s1」已在歌单「夜跑」中");
+    }
+
+    #[test]
+    fn picker_filter_matches_characters_in_order() {
+        use crate::app::fuzzy_match;
+
+        assert!(fuzzy_match("通勤路上", ""));
+        assert!(fuzzy_match("通勤路上", "通上"));
+        assert!(fuzzy_match("Night Run 2024", "nr24"));
+        assert!(fuzzy_match("Night Run", "night run"));
+        assert!(!fuzzy_match("通勤路上", "上通"), "顺序不能颠倒");
+        assert!(!fuzzy_match("夜跑", "通"));
+    }
+
+    #[test]
+    fn picker_filter_matches_playlist_names_by_pinyin() {
+        let picker = crate::app::PlaylistPicker {
+            playlists: ["通勤路上", "夜跑", "Night Run"]
+                .iter()
+                .zip(1..)
+                .map(|(name, id)| Playlist {
+                    id,
+                    name: (*name).to_owned(),
+                    ..Default::default()
+                })
+                .collect(),
+            filter: "yp".to_owned(),
+            ..Default::default()
+        };
+        let names: Vec<&str> = picker.visible().iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["夜跑"]);
+    }
+}
@@ -0,0 +1,74 @@
+//! 歌单内搜索：`/` 打开输入框，输入时即时筛选已打开歌单的歌曲
+
+use crate::app::{PlaylistMode, track_filter};
+use crate::core::prelude::{app::App, effects::CoreEffects};
+
+/// 打开输入框（仅歌曲模式）；已有关键词时接着编辑
+pub fn open(app: &mut App, effects: &mut CoreEffects) {
+    if !matches!(app.playlist_mode, PlaylistMode::Tracks) || app.playlist_filter_editing {
+        return;
+    }
+    app.playlist_filter_editing = true;
+    effects.emit_state(app);
+}
+
+/// 修改关键词：`push` 为 None 时删除最后一个字符；选中项尽量保持，否则回到第一个匹配的歌曲
+pub fn edit(app: &mut App, effects: &mut CoreEffects, push: Option<&str>) {
+    if !app.playlist_filter_editing {
+        return;
+    }
+    match push {
+        Some(text) => app.playlist_filter_input.push_str(text),
+        None => {
+            app.playlist_filter_input.pop();
+        }
+    }
+    app.song_pinyin.fill(&app.playlist_tracks);
+    app.playlist_tracks_selected = track_filter::remap_selection(
+        &app.playlist_tracks,
+        app.playlist_tracks_selected,
+        &app.playlist_filter_input,
+        &app.song_pinyin,
+    );
+    refresh_status(app);
+    effects.emit_state(app);
+}
+
+/// 确认关键词：关闭输入框，保留筛选结果
+pub fn submit(app: &mut App, effects: &mut CoreEffects) {
+    if !app.playlist_filter_editing {
+        return;
+    }
+    app.playlist_filter_editing = false;
+    if app.playlist_filter_input.trim().is_empty() {
+        app.playlist_filter_input.clear();
+    }
+    refresh_status(app);
+    effects.emit_state(app);
+}
+
+/// 清除筛选：关闭输入框并显示全部歌曲，选中项保持在原来的歌曲上
+pub fn cancel(app: &mut App, effects: &mut CoreEffects) {
+    if !app.playlist_filter_editing && app.playlist_filter_input.is_empty() {
+        return;
+    }
+    app.clear_playlist_filter();
+    app.playlists_status = format!("歌曲: {} 首（p 播放）", app.playlist_tracks.len());
+    effects.emit_state(app);
+}
+
+fn refresh_status(app: &mut App) {
+    if app.playlist_filter_input.trim().is_empty() {
+        app.playlists_status = format!("歌曲: {} 首（p 播放）", app.playlist_tracks.len());
+        return;
+    }
+    let visible = app.visible_playlist_tracks().len();
+    app.playlists_status = if visible == 0 {
+        format!("没有匹配「{}」的歌曲", app.playlist_filter_input.trim())
+    } else {
+        format!(
+            "匹配 {visible}/{} 首（Esc 清除筛选）",
+            app.playlist_tracks.len()
+        )
+    };
+}
@@ -1,25 +1,81 @@
-use crate::app::{PlaylistMode, PlaylistPreload, PreloadStatus};
+use crate::app::playlist_filter;
+use crate::app::{
+    PlaylistMode, PlaylistStats, PreloadStatus, RECOMMEND_ORIGIN, Song, TrackOrder, TrackOrderKeys,
+};
 
+use crate::core::infra::IdAllocator;
 use crate::core::infra::{NextSongCacheManager, PreloadManager, RequestKey, RequestTracker};
 use crate::core::prelude::{
     app::App, effects::CoreEffects, messages::AppCommand, netease::NeteaseCommand,
 };
-use crate::core::utils;
+use crate::error::{MessageError, NeteaseErrorVariant};
+use crate::features::player::queue_undo;
 
+pub mod edit;
+pub mod filter;
 mod tracks;
+pub mod unavailable;
 
 pub use tracks::PlaylistTracksLoad;
 
 /// 分页大小：PageDown/PageUp 一次跳转的行数
 const PAGE_SIZE: usize = 10;
 
+/// 在筛选后可见的歌单中移动选中项
+///
+/// `target(当前位置, 可见数量)` 返回新位置（None 表示不移动）；可见列表为空时不调用。
+fn move_playlist_selection(
+    app: &mut App,
+    effects: &mut CoreEffects,
+    target: impl FnOnce(usize, usize) -> Option<usize>,
+) {
+    let visible = playlist_filter::visible_indices(&app.playlists, app.playlist_filter);
+    if visible.is_empty() {
+        return;
+    }
+    let pos = visible
+        .iter()
+        .position(|&i| i == app.playlists_selected)
+        .unwrap_or(0);
+    let Some(&next) = target(pos, visible.len()).and_then(|p| visible.get(p)) else {
+        return;
+    };
+    if next != app.playlists_selected {
+        app.playlists_selected = next;
+        effects.emit_state(app);
+    }
+}
+
+/// 在歌单内搜索筛选后可见的歌曲中移动选中项（规则同 `move_playlist_selection`）
+fn move_track_selection(
+    app: &mut App,
+    effects: &mut CoreEffects,
+    target: impl FnOnce(usize, usize) -> Option<usize>,
+) {
+    let visible = app.visible_playlist_tracks();
+    if visible.is_empty() {
+        return;
+    }
+    let pos = visible
+        .iter()
+        .position(|&i| i == app.playlist_tracks_selected)
+        .unwrap_or(0);
+    let Some(&next) = target(pos, visible.len()).and_then(|p| visible.get(p)) else {
+        return;
+    };
+    if next != app.playlist_tracks_selected {
+        app.playlist_tracks_selected = next;
+        effects.emit_state(app);
+    }
+}
+
 /// 处理歌单相关的 AppCommand
 /// 返回 true 表示命令已处理，false 表示未处理
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_playlists_command(
     cmd: AppCommand,
     app: &mut App,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     request_tracker: &mut RequestTracker<RequestKey>,
     song_request_titles: &mut std::collections::HashMap<i64, String>,
     playlist_tracks_loader: &mut Option<PlaylistTracksLoad>,
@@ -29,56 +85,37 @@ pub async fn handle_playlists_command(
 ) -> bool {
     match cmd {
         AppCommand::PlaylistsMoveUp => {
-            if app.playlists_selected > 0 {
-                app.playlists_selected -= 1;
-                effects.emit_state(app);
-            }
+            move_playlist_selection(app, effects, |pos, _| pos.checked_sub(1));
         }
         AppCommand::PlaylistsMoveDown => {
-            if !app.playlists.is_empty() && app.playlists_selected + 1 < app.playlists.len() {
-                app.playlists_selected += 1;
-                effects.emit_state(app);
-            }
+            move_playlist_selection(app, effects, |pos, len| (pos + 1 < len).then_some(pos + 1));
         }
         AppCommand::PlaylistsMoveTo { index } => {
-            if !app.playlists.is_empty() && index < app.playlists.len() {
-                app.playlists_selected = index;
-                effects.emit_state(app);
-            }
+            move_playlist_selection(app, effects, |_, len| (index < len).then_some(index));
         }
         AppCommand::PlaylistsPageDown => {
-            if !app.playlists.is_empty() {
-                let new_idx =
-                    (app.playlists_selected + PAGE_SIZE).min(app.playlists.len().saturating_sub(1));
-                if new_idx != app.playlists_selected {
-                    app.playlists_selected = new_idx;
-                    effects.emit_state(app);
-                }
-            }
+            move_playlist_selection(app, effects, |pos, len| {
+                Some((pos + PAGE_SIZE).min(len - 1))
+            });
         }
         AppCommand::PlaylistsPageUp => {
-            if !app.playlists.is_empty() {
-                let new_idx = app.playlists_selected.saturating_sub(PAGE_SIZE);
-                if new_idx != app.playlists_selected {
-                    app.playlists_selected = new_idx;
-                    effects.emit_state(app);
-                }
-            }
+            move_playlist_selection(app, effects, |pos, _| Some(pos.saturating_sub(PAGE_SIZE)));
         }
         AppCommand::PlaylistsJumpTop => {
-            if !app.playlists.is_empty() && app.playlists_selected != 0 {
-                app.playlists_selected = 0;
-                effects.emit_state(app);
-            }
+            move_playlist_selection(app, effects, |_, _| Some(0));
         }
         AppCommand::PlaylistsJumpBottom => {
-            if !app.playlists.is_empty() {
-                let last = app.playlists.len().saturating_sub(1);
-                if app.playlists_selected != last {
-                    app.playlists_selected = last;
-                    effects.emit_state(app);
-                }
-            }
+            move_playlist_selection(app, effects, |_, len| Some(len - 1));
+        }
+        AppCommand::PlaylistsCycleFilter => {
+            app.playlist_filter = app.playlist_filter.next();
+            app.playlists_selected = playlist_filter::remap_selection(
+                &app.playlists,
+                app.playlists_selected,
+                app.playlist_filter,
+            );
+            refresh_playlist_list_status(app);
+            effects.emit_state(app);
         }
         AppCommand::PlaylistsOpenSelected => {
             if matches!(app.playlist_mode, PlaylistMode::List) {
@@ -110,13 +147,34 @@ pub async fn handle_playlists_command(
                     {
                         // 保留 playlist_tracks 给 UI 显示，同时克隆给 play_queue
                         app.playlist_tracks = preload.songs.clone();
+                        app.playlist_tracks_order_keys = TrackOrderKeys::new(
+                            preload.songs.iter().map(|s| s.id),
+                            preload.added_at.clone(),
+                        );
                         app.playlist_tracks_selected = 0;
+                        app.playlist_tracks_missing = preload.missing_count;
+                        app.playlist_tracks_stats = Some(
+                            preload
+                                .stats
+                                .get_or_insert_with(|| PlaylistStats::compute(&preload.songs))
+                                .clone(),
+                        );
                         app.playlist_mode = PlaylistMode::Tracks;
+                        app.playlist_tracks_recommend = false;
+                        app.clear_playlist_filter();
+                        sort_playlist_tracks(app);
+                        app.playlist_tracks_selected = 0;
 
                         // 克隆一份给 play_queue（不转移 playlist_tracks 的所有权）
-                        let _old = app.play_queue.set_songs(preload.songs.clone(), Some(0));
+                        let recorded = queue_undo::replace(
+                            app,
+                            app.playlist_tracks.clone(),
+                            Some(0),
+                            Some(format!("playlist:{playlist_id}")),
+                        );
+                        queue_undo::toast_replaced(recorded, effects);
 
-                        next_song_cache.reset(); // 失效预缓存
+                        next_song_cache.reset(effects); // 失效预缓存
                         app.playlists_status =
                             format!("歌曲: {} 首（已缓存，p 播放）", app.playlist_tracks.len());
                         // 新增：使用预加载的日志
@@ -141,88 +199,139 @@ pub async fn handle_playlists_command(
 
                 app.playlists_status = "加载歌单歌曲中...".to_owned();
                 *playlist_tracks_loader = None;
+                request_tracker.clear(&RequestKey::RecommendSongs);
                 effects.emit_state(app);
-                let id =
-                    request_tracker.issue(RequestKey::PlaylistDetail, || utils::next_id(req_id));
+                let id = request_tracker.issue(RequestKey::PlaylistDetail, req_ids);
                 effects.send_netease_hi(NeteaseCommand::PlaylistDetail {
                     req_id: id,
                     playlist_id,
                 });
             }
         }
-        AppCommand::PlaylistTracksMoveUp => {
-            if app.playlist_tracks_selected > 0 {
-                app.playlist_tracks_selected -= 1;
+        AppCommand::PlaylistsOpenRecommend => {
+            if !app.logged_in {
+                app.playlists_status = "每日推荐需要登录（当前为游客）".to_owned();
                 effects.emit_state(app);
+                return true;
             }
+            // 取消正在打开的歌单，晚到的响应直接丢弃
+            *playlist_tracks_loader = None;
+            request_tracker.clear(&RequestKey::PlaylistDetail);
+            request_tracker.clear(&RequestKey::PlaylistTracks);
+            app.playlists_status = "加载每日推荐中...".to_owned();
+            effects.emit_state(app);
+            let id = request_tracker.issue(RequestKey::RecommendSongs, req_ids);
+            effects.send_netease_hi(NeteaseCommand::RecommendSongs { req_id: id });
+        }
+        AppCommand::PlaylistTracksMoveUp => {
+            move_track_selection(app, effects, |pos, _| pos.checked_sub(1));
         }
         AppCommand::PlaylistTracksMoveDown => {
-            if !app.playlist_tracks.is_empty()
-                && app.playlist_tracks_selected + 1 < app.playlist_tracks.len()
-            {
-                app.playlist_tracks_selected += 1;
-                effects.emit_state(app);
-            }
+            move_track_selection(app, effects, |pos, len| (pos + 1 < len).then_some(pos + 1));
         }
         AppCommand::PlaylistTracksMoveTo { index } => {
-            if !app.playlist_tracks.is_empty() && index < app.playlist_tracks.len() {
-                app.playlist_tracks_selected = index;
-                effects.emit_state(app);
-            }
+            move_track_selection(app, effects, |_, len| (index < len).then_some(index));
         }
         AppCommand::PlaylistTracksPageDown => {
-            if !app.playlist_tracks.is_empty() {
-                let new_idx = (app.playlist_tracks_selected + PAGE_SIZE)
-                    .min(app.playlist_tracks.len().saturating_sub(1));
-                if new_idx != app.playlist_tracks_selected {
-                    app.playlist_tracks_selected = new_idx;
-                    effects.emit_state(app);
-                }
-            }
+            move_track_selection(app, effects, |pos, len| {
+                Some((pos + PAGE_SIZE).min(len - 1))
+            });
         }
         AppCommand::PlaylistTracksPageUp => {
-            if !app.playlist_tracks.is_empty() {
-                let new_idx = app.playlist_tracks_selected.saturating_sub(PAGE_SIZE);
-                if new_idx != app.playlist_tracks_selected {
-                    app.playlist_tracks_selected = new_idx;
-                    effects.emit_state(app);
-                }
-            }
+            move_track_selection(app, effects, |pos, _| Some(pos.saturating_sub(PAGE_SIZE)));
         }
         AppCommand::PlaylistTracksJumpTop => {
-            if !app.playlist_tracks.is_empty() && app.playlist_tracks_selected != 0 {
-                app.playlist_tracks_selected = 0;
-                effects.emit_state(app);
-            }
+            move_track_selection(app, effects, |_, _| Some(0));
         }
         AppCommand::PlaylistTracksJumpBottom => {
-            if !app.playlist_tracks.is_empty() {
-                let last = app.playlist_tracks.len().saturating_sub(1);
-                if app.playlist_tracks_selected != last {
-                    app.playlist_tracks_selected = last;
-                    effects.emit_state(app);
+            move_track_selection(app, effects, |_, len| Some(len - 1));
+        }
+        AppCommand::PlaylistTracksJumpToPlaying => {
+            if !matches!(app.playlist_mode, PlaylistMode::Tracks) {
+                return true;
+            }
+            match app
+                .play_song_id
+                .and_then(|id| app.playlist_tracks.iter().position(|s| s.id == id))
+            {
+                Some(idx) => {
+                    app.playlist_tracks_selected = idx;
+                    // 被歌单内搜索隐藏时清除筛选，否则选中项不可见
+                    if !app.visible_playlist_tracks().contains(&idx) {
+                        app.clear_playlist_filter();
+                    }
+                }
+                None => {
+                    app.playlists_status = if app.play_song_id.is_some() {
+                        "正在播放的歌曲不在此歌单中".to_owned()
+                    } else {
+                        "当前没有正在播放的歌曲".to_owned()
+                    };
                 }
             }
+            effects.emit_state(app);
+        }
+        AppCommand::PlaylistTracksToggleOrder | AppCommand::PlaylistTracksCycleSort => {
+            if !matches!(app.playlist_mode, PlaylistMode::Tracks) {
+                return true;
+            }
+            let Some(playlist_id) = app.open_playlist_id() else {
+                app.playlists_status = "每日推荐不支持切换歌曲顺序".to_owned();
+                effects.emit_state(app);
+                return true;
+            };
+            let (order, key) = if matches!(cmd, AppCommand::PlaylistTracksCycleSort) {
+                (app.playlist_tracks_order().cycle_sort(), 'S')
+            } else {
+                (app.playlist_tracks_order().toggle(), 't')
+            };
+            if order == TrackOrder::RecentlyAdded && !app.playlist_tracks_order_keys.has_added_at()
+            {
+                app.playlists_status = "该歌单没有添加时间信息，无法按最近添加排序".to_owned();
+                effects.emit_state(app);
+                return true;
+            }
+            app.playlist_track_orders.insert(playlist_id, order);
+            sort_playlist_tracks(app);
+            app.playlists_status = format!(
+                "歌曲顺序: {}（{key} 切换，p 播放时队列按此顺序）",
+                order.label()
+            );
+            effects.emit_state(app);
         }
         AppCommand::PlaylistTracksPlaySelected => {
+            // 歌单内搜索筛选时选中项在完整列表中的下标不变，队列仍由完整歌单构建
             if matches!(app.playlist_mode, PlaylistMode::Tracks)
+                && app
+                    .visible_playlist_tracks()
+                    .contains(&app.playlist_tracks_selected)
                 && let Some(s) = app.playlist_tracks.get(app.playlist_tracks_selected)
             {
                 // 先保存歌曲信息，因为后续会转移所有权
                 let song_id = s.id;
-                let title = format!("{} - {}", s.name, s.artists);
+                let title = s.display_title(app.song_name_mode);
                 app.play_status = format!("获取播放链接中: {title}");
 
                 // 克隆一份给 play_queue（保留 playlist_tracks 给 UI 显示）
-                let _old = app.play_queue.set_songs(
+                let origin = if app.playlist_tracks_recommend {
+                    Some(RECOMMEND_ORIGIN.to_owned())
+                } else {
+                    app.playlists
+                        .get(app.playlists_selected)
+                        .map(|p| format!("playlist:{}", p.id))
+                };
+                let recorded = queue_undo::replace(
+                    app,
                     app.playlist_tracks.clone(),
                     Some(app.playlist_tracks_selected),
+                    origin,
                 );
+                queue_undo::toast_replaced(recorded, effects);
 
-                next_song_cache.reset(); // 失效预缓存
+                next_song_cache.reset(effects); // 失效预缓存
                 effects.emit_state(app);
                 song_request_titles.clear();
-                let id = request_tracker.issue(RequestKey::SongUrl, || utils::next_id(req_id));
+                let id = request_tracker.issue(RequestKey::SongUrl, req_ids);
                 song_request_titles.insert(song_id, title);
                 effects.send_netease_hi(NeteaseCommand::SongUrl {
                     req_id: id,
@@ -241,12 +350,18 @@ pub async fn handle_playlists_command(
 pub async fn handle_playlists_back_command(
     cmd: AppCommand,
     app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
     playlist_tracks_loader: &mut Option<PlaylistTracksLoad>,
     effects: &mut CoreEffects,
 ) -> bool {
     if matches!(cmd, AppCommand::Back) && matches!(app.view, crate::app::View::Playlists) {
         app.playlist_mode = PlaylistMode::List;
+        app.clear_playlist_filter();
+        // 取消仍在进行的分批加载，晚到的响应直接丢弃
         *playlist_tracks_loader = None;
+        request_tracker.clear(&RequestKey::PlaylistDetail);
+        request_tracker.clear(&RequestKey::PlaylistTracks);
+        request_tracker.clear(&RequestKey::RecommendSongs);
         refresh_playlist_list_status(app);
         effects.emit_state(app);
         return true;
@@ -264,7 +379,7 @@ pub async fn handle_playlists_event(
     request_tracker: &mut RequestTracker<RequestKey>,
     preload_mgr: &mut PreloadManager,
     effects: &mut CoreEffects,
-    next_req_id: &mut u64,
+    req_ids: &IdAllocator,
     preload_count: usize,
 ) -> bool {
     let key = RequestKey::Playlists;
@@ -277,9 +392,17 @@ pub async fn handle_playlists_event(
         .iter()
         .position(|p| p.special_type == 5 || p.name.contains("我喜欢"))
         .unwrap_or(0);
+    app.playlists_selected = playlist_filter::remap_selection(
+        &app.playlists,
+        app.playlists_selected,
+        app.playlist_filter,
+    );
     app.playlist_mode = PlaylistMode::List;
     app.playlist_tracks.clear();
     app.playlist_tracks_selected = 0;
+    app.playlist_tracks_missing = 0;
+    app.playlist_tracks_stats = None;
+    app.playlist_tracks_recommend = false;
 
     // 新增：在调用 start_for_playlists 前记录
     tracing::info!(
@@ -288,7 +411,7 @@ pub async fn handle_playlists_event(
     );
 
     preload_mgr
-        .start_for_playlists(app, effects, next_req_id, preload_count)
+        .start_for_playlists(app, effects, req_ids, preload_count)
         .await;
 
     // 新增：调用后记录
@@ -309,12 +432,13 @@ pub async fn handle_playlist_detail_event(
     req_id: u64,
     playlist_id: i64,
     ids: Vec<i64>,
+    added_at: std::collections::HashMap<i64, i64>,
     app: &mut App,
     request_tracker: &mut RequestTracker<RequestKey>,
     playlist_tracks_loader: &mut Option<PlaylistTracksLoad>,
     preload_mgr: &PreloadManager,
     effects: &mut CoreEffects,
-    next_req_id: &mut u64,
+    req_ids: &IdAllocator,
 ) -> Option<bool> {
     // 检查是否是预加载管理器的请求
     if preload_mgr.owns_req(req_id) {
@@ -334,8 +458,8 @@ pub async fn handle_playlist_detail_event(
     app.playlists_status = format!("加载歌单歌曲中... 0/{}", ids.len());
     effects.emit_state(app);
 
-    let mut loader = PlaylistTracksLoad::new(playlist_id, ids);
-    let id = request_tracker.issue(RequestKey::PlaylistTracks, || utils::next_id(next_req_id));
+    let mut loader = PlaylistTracksLoad::new(playlist_id, ids, added_at);
+    let id = request_tracker.issue(RequestKey::PlaylistTracks, req_ids);
     let chunk = loader.next_chunk();
     loader.inflight_req_id = Some(id);
     *playlist_tracks_loader = Some(loader);
@@ -357,7 +481,7 @@ pub async fn handle_songs_event(
     playlist_tracks_loader: &mut Option<PlaylistTracksLoad>,
     preload_mgr: &mut PreloadManager,
     effects: &mut CoreEffects,
-    next_req_id: &mut u64,
+    req_ids: &IdAllocator,
 ) -> Option<bool> {
     // 检查是否是预加载管理器的请求
     if preload_mgr.owns_req(req_id) {
@@ -374,46 +498,99 @@ pub async fn handle_songs_event(
         return Some(false);
     }
     loader.inflight_req_id = None;
-    loader.songs.extend(songs);
+    let playlist_id = loader.playlist_id;
+    let origin = format!("playlist:{playlist_id}");
 
-    app.playlists_status = format!("加载歌单歌曲中... {}/{}", loader.songs.len(), loader.total);
-    effects.emit_state(app);
+    if !loader.shown {
+        // 首批到达即显示歌曲列表，其余分批在后台加载
+        loader.shown = true;
+        app.playlist_tracks = songs.clone();
+        app.playlist_tracks_order_keys =
+            TrackOrderKeys::new(loader.ids.iter().copied(), loader.added_at.clone());
+        app.playlist_tracks_selected = 0;
+        app.playlist_tracks_missing = 0;
+        app.playlist_tracks_stats = None;
+        app.playlist_tracks_recommend = false;
+        app.playlist_mode = PlaylistMode::Tracks;
+        app.clear_playlist_filter();
+        sort_playlist_tracks(app);
+        app.playlist_tracks_selected = 0;
+
+        // 克隆一份给 play_queue（保留 playlist_tracks 给 UI 显示）
+        let recorded = queue_undo::replace(
+            app,
+            app.playlist_tracks.clone(),
+            Some(0),
+            Some(origin.clone()),
+        );
+        queue_undo::toast_replaced(recorded, effects);
+    } else if app.playlist_tracks_order() == TrackOrder::Canonical {
+        app.playlist_tracks.extend(songs.iter().cloned());
+        // 队列仍来自本歌单时同步追加；游标保持在正在播放的歌曲上
+        if app.play_queue.origin() == Some(origin.as_str()) {
+            app.play_queue.extend(songs.iter().cloned());
+        }
+    } else {
+        // 新的一批可能排在已显示的歌曲之前：整体重排，队列随显示顺序更新
+        app.playlist_tracks.extend(songs.iter().cloned());
+        sort_playlist_tracks(app);
+        if app.play_queue.origin() == Some(origin.as_str()) {
+            replace_queue_keeping_current(app, app.playlist_tracks.clone(), origin.clone());
+        }
+    }
+    loader.songs.extend(songs);
 
     if loader.is_done() {
         let Some(loader) = playlist_tracks_loader.take() else {
             tracing::warn!("pending_playlist_tracks 丢失（已完成但无法 take）");
             return Some(true);
         };
-        let playlist_id = loader.playlist_id;
-        let songs = loader.songs;
-
-        // 更新预加载缓存
-        // 克隆保存到预加载缓存（songs 会被赋值给 playlist_tracks）
-        // 注意：这里需要克隆是因为 songs 还要赋值给 app.playlist_tracks
-        // TODO: 考虑使用 Arc<Song> 避免克隆
-        if let std::collections::hash_map::Entry::Occupied(mut entry) =
-            app.playlist_preloads.entry(playlist_id)
-        {
+        let requested = loader.total;
+        let mut missing = requested.saturating_sub(loader.songs.len());
+        let mut stats = None;
+
+        // 更新预加载缓存；同一份歌单已有更完整的预加载时沿用缓存数据
+        if app.playlist_preloads.contains_key(&playlist_id) {
             use crate::core::infra::preload_pub as preload;
-            entry.insert(PlaylistPreload {
-                status: PreloadStatus::Completed,
-                songs: songs.clone(),
-            });
+            if !preload::store_completed_preload(
+                app,
+                playlist_id,
+                loader.songs,
+                &loader.ids,
+                loader.added_at,
+            ) && let Some(p) = app.playlist_preloads.get(&playlist_id)
+            {
+                missing = p.missing_count;
+                app.playlist_tracks = p.songs.clone();
+                sort_playlist_tracks(app);
+                if app.play_queue.origin() == Some(origin.as_str()) {
+                    replace_queue_keeping_current(app, app.playlist_tracks.clone(), origin);
+                }
+            }
+            stats = app
+                .playlist_preloads
+                .get(&playlist_id)
+                .and_then(|p| p.stats.clone());
             preload::update_preload_summary(app);
         }
 
-        app.playlist_tracks = songs.clone();
-        app.playlist_tracks_selected = 0;
-        app.playlist_mode = PlaylistMode::Tracks;
-
-        // 克隆一份给 play_queue（保留 playlist_tracks 给 UI 显示）
-        let _old = app.play_queue.set_songs(songs, Some(0));
-
+        app.playlist_tracks_selected = app
+            .playlist_tracks_selected
+            .min(app.playlist_tracks.len().saturating_sub(1));
+        app.playlist_tracks_missing = missing;
+        app.playlist_tracks_stats =
+            Some(stats.unwrap_or_else(|| PlaylistStats::compute(&app.playlist_tracks)));
         app.playlists_status = format!("歌曲: {} 首（p 播放）", app.playlist_tracks.len());
         effects.emit_state(app);
         Some(true)
     } else {
-        let id = request_tracker.issue(RequestKey::PlaylistTracks, || utils::next_id(next_req_id));
+        app.playlists_status = format!(
+            "歌曲: {}/{} 首（加载中，p 播放）",
+            loader.songs.len(),
+            loader.total
+        );
+        effects.emit_state(app);
+        let id = request_tracker.issue(RequestKey::PlaylistTracks, req_ids);
         let chunk = loader.next_chunk();
         loader.inflight_req_id = Some(id);
         effects.send_netease_hi(NeteaseCommand::SongDetailByIds {
@@ -424,6 +601,80 @@ pub async fn handle_songs_event(
     }
 }
 
+/// 处理 NeteaseEvent::RecommendSongs：像普通歌单一样显示歌曲并设为播放队列
+/// 返回 true 表示事件已处理，false 表示 req_id 不匹配
+pub fn handle_recommend_songs_event(
+    req_id: u64,
+    songs: Vec<Song>,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+    next_song_cache: &mut NextSongCacheManager,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::RecommendSongs, req_id) {
+        return false;
+    }
+    if songs.is_empty() {
+        app.playlists_status = "今日暂无推荐歌曲".to_owned();
+        effects.emit_state(app);
+        return true;
+    }
+    app.playlist_tracks_stats = Some(PlaylistStats::compute(&songs));
+    app.playlist_tracks = songs.clone();
+    app.playlist_tracks_order_keys = TrackOrderKeys::default();
+    app.playlist_tracks_selected = 0;
+    app.playlist_tracks_missing = 0;
+    app.playlist_tracks_recommend = true;
+    app.playlist_mode = PlaylistMode::Tracks;
+    app.clear_playlist_filter();
+
+    let recorded = queue_undo::replace(app, songs, Some(0), Some(RECOMMEND_ORIGIN.to_owned()));
+    queue_undo::toast_replaced(recorded, effects);
+    next_song_cache.reset(effects); // 失效预缓存
+
+    app.playlists_status = format!("每日推荐: {} 首（p 播放）", app.playlist_tracks.len());
+    effects.emit_state(app);
+    true
+}
+
+/// 每日推荐请求失败；需要登录时给出登录提示而非原始接口错误
+/// 返回 true 表示事件已处理，false 表示 req_id 不匹配
+pub fn handle_recommend_songs_error(
+    req_id: u64,
+    error: &MessageError,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::RecommendSongs, req_id) {
+        return false;
+    }
+    app.playlists_status = match error {
+        MessageError::Netease(NeteaseErrorVariant::NeedLogin) => "每日推荐需要登录".to_owned(),
+        e => format!("加载每日推荐失败: {e}"),
+    };
+    effects.emit_state(app);
+    true
+}
+
+/// 换用更完整的歌曲列表作为播放队列，游标仍指向正在播放的歌曲
+fn replace_queue_keeping_current(app: &mut App, songs: Vec<Song>, origin: String) {
+    let current_id = app.play_queue.current().map(|s| s.id);
+    let start = current_id.and_then(|id| songs.iter().position(|s| s.id == id));
+    let _old = app.play_queue.set_songs(songs, start);
+    app.play_queue.set_origin(origin);
+}
+
+/// 按已打开歌单选择的显示顺序重排歌曲列表，选中的歌曲保持不变
+fn sort_playlist_tracks(app: &mut App) {
+    let order = app.playlist_tracks_order();
+    app.playlist_tracks_selected = app.playlist_tracks_order_keys.sort(
+        &mut app.playlist_tracks,
+        order,
+        app.playlist_tracks_selected,
+    );
+}
+
 /// 刷新歌单列表状态文本
 pub fn refresh_playlist_list_status(app: &mut App) {
     if matches!(app.view, crate::app::View::Playlists)
@@ -432,7 +683,17 @@ pub fn refresh_playlist_list_status(app: &mut App) {
         // 计算普通歌单数量（排除"我喜欢的音乐"）
         let normal_count = app.playlists.iter().filter(|p| p.special_type != 5).count();
 
-        let mut s = format!("歌单[{}]（已选中我喜欢的音乐，回车打开）", normal_count);
+        let (mine, subscribed) = playlist_filter::ownership_counts(&app.playlists);
+        let mut s = format!(
+            "歌单[{}]（已选中我喜欢的音乐，回车打开） 我的 {mine} · 收藏 {subscribed}",
+            normal_count
+        );
+        if app.playlist_filter != crate::app::PlaylistFilter::All {
+            s.push_str(&format!(
+                "（筛选：{}，f 切换）",
+                app.playlist_filter.label()
+            ));
+        }
         if !app.preload_summary.is_empty() {
             s.push_str(" | ");
             s.push_str(&app.preload_summary);
@@ -1,26 +1,35 @@
 ﻿use crate::app::Song;
+use std::collections::HashMap;
 
 pub(super) const PLAYLIST_TRACKS_PAGE_SIZE: usize = 200;
+/// 首批歌曲较少，尽快显示歌曲列表，其余分批在后台加载
+pub(super) const PLAYLIST_TRACKS_FIRST_PAGE_SIZE: usize = 100;
 
 pub struct PlaylistTracksLoad {
     pub playlist_id: i64,
     pub total: usize,
     pub ids: Vec<i64>,
+    /// 歌曲加入歌单的时间（id -> 毫秒时间戳），随歌曲一起存入预加载缓存
+    pub added_at: HashMap<i64, i64>,
     pub cursor: usize,
     pub songs: Vec<Song>,
     pub inflight_req_id: Option<u64>,
+    /// 首批结果是否已显示到歌曲列表
+    pub shown: bool,
 }
 
 impl PlaylistTracksLoad {
-    pub fn new(playlist_id: i64, ids: Vec<i64>) -> Self {
+    pub fn new(playlist_id: i64, ids: Vec<i64>, added_at: HashMap<i64, i64>) -> Self {
         let total = ids.len();
         Self {
             playlist_id,
             total,
             ids,
+            added_at,
             cursor: 0,
             songs: Vec::new(),
             inflight_req_id: None,
+            shown: false,
         }
     }
 
@@ -30,7 +39,12 @@ impl PlaylistTracksLoad {
 
     pub fn next_chunk(&mut self) -> Vec<i64> {
         let start = self.cursor;
-        let end = (start + PLAYLIST_TRACKS_PAGE_SIZE).min(self.ids.len());
+        let size = if start == 0 {
+            PLAYLIST_TRACKS_FIRST_PAGE_SIZE
+        } else {
+            PLAYLIST_TRACKS_PAGE_SIZE
+        };
+        let end = (start + size).min(self.ids.len());
         self.cursor = end;
         self.ids[start..end].to_vec()
     }
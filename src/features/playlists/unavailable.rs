@@ -0,0 +1,785 @@
+//! 检查歌单中的不可用歌曲并换成重新上架的版本
+//!
+//! 下架的歌曲常以新 id 重新上架。对打开的（自己的）歌单按 `U`：按 [`DETAIL_BATCH`] 首一批拉取详情，
+//! 按 `privileges` 找出不可用的歌曲；逐首以「歌名 歌手」搜索，用 [`match_score`] 挑出最接近的候选，
+//! 再确认候选本身可以播放；最后在审阅框中逐条确认——Enter 把歌单中的旧 id 换成新 id
+//! （先添加新歌曲再移除旧歌曲），`s` 跳过。检查过程中的请求依次发出，同一时间只有一个。
+
+use std::collections::{HashMap, HashSet};
+
+use crate::app::{PlaylistMode, Song, SongSubstitution, Toast, UnavailableReview};
+use crate::core::infra::{IdAllocator, PreloadManager};
+use crate::core::prelude::{
+    app::App,
+    effects::CoreEffects,
+    infra::{RequestKey, RequestTracker},
+    netease::NeteaseCommand,
+};
+use crate::domain::model::{PlaylistTrackOp, SongAvailability, SongPreview};
+use crate::error::{MessageError, NeteaseErrorVariant};
+
+/// 每批拉取详情的歌曲数
+pub const DETAIL_BATCH: usize = 200;
+/// 每首不可用歌曲的搜索结果数
+const SEARCH_LIMIT: i64 = 10;
+/// 候选被采纳的最低分（满分 1.0）
+pub const MIN_SCORE: f32 = 0.7;
+/// 时长相差不超过该值视为同一录音
+const DURATION_SAME_MS: u64 = 3_000;
+/// 时长相差超过该值时不是同一首歌
+const DURATION_MAX_MS: u64 = 20_000;
+
+/// 标题括号内出现这些词时视为另一个版本（现场、混音、伴奏、翻唱等）
+const VERSION_TAGS: [&str; 16] = [
+    "live",
+    "现场",
+    "remix",
+    "伴奏",
+    "instrumental",
+    "inst",
+    "offvocal",
+    "karaoke",
+    "cover",
+    "翻唱",
+    "翻自",
+    "demo",
+    "acoustic",
+    "纯音乐",
+    "piano",
+    "钢琴",
+];
+
+/// 归一化：全角转半角、转小写，只保留字母与数字（含汉字）
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .flat_map(char::to_lowercase)
+        .filter(|c| c.is_alphanumeric())
+        .collect()
+}
+
+/// 拆分标题：括号或「 - 」之前为歌名主体，之后的内容中出现的版本标记
+fn split_title(title: &str) -> (String, Vec<&'static str>) {
+    let cut = title
+        .char_indices()
+        .find(|&(i, c)| matches!(c, '(' | '（' | '[' | '【') || title[i..].starts_with(" - "))
+        .map_or(title.len(), |(i, _)| i);
+    let rest = normalize(&title[cut..]);
+    let tags = VERSION_TAGS
+        .iter()
+        .copied()
+        .filter(|tag| rest.contains(tag))
+        .collect();
+    (normalize(&title[..cut]), tags)
+}
+
+/// 时长接近程度：3 秒内为 1，之后线性降到 20 秒时的 0；超过 20 秒为 None（不是同一首）。
+/// 任一方未知时为 0.5
+fn duration_score(a: Option<u64>, b: Option<u64>) -> Option<f32> {
+    let (Some(a), Some(b)) = (a, b) else {
+        return Some(0.5);
+    };
+    let diff = a.abs_diff(b);
+    if diff > DURATION_MAX_MS {
+        return None;
+    }
+    let over = diff.saturating_sub(DURATION_SAME_MS) as f32;
+    Some(1.0 - over / (DURATION_MAX_MS - DURATION_SAME_MS) as f32)
+}
+
+/// 候选与原歌曲的相似度（0～1），不是同一首歌时为 None
+///
+/// 歌名主体与版本标记必须一致（现场版、伴奏等不能替换原曲）；原歌曲的歌手至少有一位
+/// 出现在候选中（同名翻唱不算）。分数由歌名（0.4）、歌手重合比例（0.35）与时长接近程度（0.25）组成。
+pub fn match_score(original: &Song, candidate: &Song) -> Option<f32> {
+    if original.id == candidate.id || split_title(&original.name) != split_title(&candidate.name) {
+        return None;
+    }
+    let artists = |s: &Song| -> HashSet<String> {
+        s.artists
+            .split('/')
+            .map(normalize)
+            .filter(|a| !a.is_empty())
+            .collect()
+    };
+    let wanted = artists(original);
+    let found = artists(candidate);
+    let shared = wanted.intersection(&found).count();
+    if shared == 0 {
+        return None;
+    }
+    let artist = shared as f32 / wanted.len() as f32;
+    let duration = duration_score(original.duration_ms, candidate.duration_ms)?;
+    Some(0.4 + 0.35 * artist + 0.25 * duration)
+}
+
+/// 搜索结果中分数最高且不低于 [`MIN_SCORE`] 的候选（同分时取靠前的）
+pub fn best_match<'a>(original: &Song, candidates: &'a [Song]) -> Option<&'a Song> {
+    candidates
+        .iter()
+        .filter_map(|c| match_score(original, c).map(|score| (score, c)))
+        .filter(|(score, _)| *score >= MIN_SCORE)
+        .fold(None, |best: Option<(f32, &Song)>, (score, c)| match best {
+            Some((top, _)) if top >= score => best,
+            _ => Some((score, c)),
+        })
+        .map(|(_, c)| c)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// 分批拉取歌单歌曲的详情
+    Details,
+    /// 逐首搜索不可用歌曲的替换版本
+    Search,
+    /// 确认找到的候选可以播放
+    Verify,
+}
+
+/// 进行中的检查
+#[derive(Debug)]
+pub struct UnavailableCheck {
+    playlist_id: i64,
+    playlist_name: String,
+    songs: Vec<Song>,
+    /// 已请求详情的歌曲数
+    cursor: usize,
+    /// 请求中的这一批的歌曲数
+    batch_len: usize,
+    unavailable: Vec<Song>,
+    /// 已搜索的不可用歌曲数（请求中的一首也计在内）
+    searched: usize,
+    found: Vec<SongSubstitution>,
+    /// 详情或搜索请求失败的歌曲数
+    failed: usize,
+    stage: Stage,
+}
+
+/// 已发出的替换：先添加新歌曲（`removing` 为 false），成功后移除旧歌曲
+#[derive(Debug, Clone)]
+pub struct PendingSwap {
+    playlist_id: i64,
+    playlist_name: String,
+    substitution: SongSubstitution,
+    removing: bool,
+    /// 新歌曲原本不在歌单中、确实被添加了
+    added: bool,
+}
+
+/// 对打开的歌单开始检查；每日推荐、收藏的歌单与仍在加载的歌单不检查
+#[allow(clippy::too_many_arguments)]
+pub fn start(
+    app: &mut App,
+    loading: bool,
+    job: &mut Option<UnavailableCheck>,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) {
+    if !matches!(app.playlist_mode, PlaylistMode::Tracks) || app.playlist_tracks_recommend {
+        return;
+    }
+    if !app.logged_in {
+        effects.toast("登录后才能编辑歌单");
+        return;
+    }
+    let Some(playlist) = app.playlists.get(app.playlists_selected) else {
+        return;
+    };
+    if playlist.subscribed {
+        effects.toast("只能检查自己创建的歌单");
+        return;
+    }
+    if job.is_some() {
+        effects.toast("正在检查不可用歌曲，请稍候");
+        return;
+    }
+    if loading {
+        effects.toast("歌单仍在加载，请稍候");
+        return;
+    }
+    let mut seen = HashSet::new();
+    let songs: Vec<Song> = app
+        .playlist_tracks
+        .iter()
+        .filter(|s| seen.insert(s.id))
+        .cloned()
+        .collect();
+    if songs.is_empty() {
+        return;
+    }
+    tracing::info!(
+        playlist_id = playlist.id,
+        songs = songs.len(),
+        "🎵 [Playlists] 检查不可用歌曲"
+    );
+    *job = Some(UnavailableCheck {
+        playlist_id: playlist.id,
+        playlist_name: playlist.name.clone(),
+        songs,
+        cursor: 0,
+        batch_len: 0,
+        unavailable: Vec::new(),
+        searched: 0,
+        found: Vec::new(),
+        failed: 0,
+        stage: Stage::Details,
+    });
+    advance(app, job, req_ids, request_tracker, effects);
+}
+
+/// 详情响应（`Err` 为该批请求失败）：记下不可用的歌曲，或剔除不能播放的候选
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+pub fn handle_previews(
+    req_id: u64,
+    previews: Result<&[SongPreview], &MessageError>,
+    app: &mut App,
+    job: &mut Option<UnavailableCheck>,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::UnavailableCheck, req_id) {
+        return false;
+    }
+    let Some(check) = job.as_mut() else {
+        return true;
+    };
+    let blocked = |previews: &[SongPreview]| -> HashSet<i64> {
+        previews
+            .iter()
+            .filter(|p| p.availability == SongAvailability::Unavailable)
+            .map(|p| p.id)
+            .collect()
+    };
+    match (check.stage, previews) {
+        (Stage::Details, Ok(previews)) => {
+            let blocked = blocked(previews);
+            let batch = &check.songs[check.cursor - check.batch_len..check.cursor];
+            check
+                .unavailable
+                .extend(batch.iter().filter(|s| blocked.contains(&s.id)).cloned());
+        }
+        (Stage::Details, Err(error)) => {
+            check.failed += check.batch_len;
+            tracing::warn!(req_id, "检查不可用歌曲：歌曲详情获取失败: {error}");
+        }
+        (Stage::Verify, Ok(previews)) => {
+            let blocked = blocked(previews);
+            check
+                .found
+                .retain(|sub| !blocked.contains(&sub.candidate.id));
+        }
+        (Stage::Verify, Err(error)) => {
+            tracing::warn!(
+                req_id,
+                "检查不可用歌曲：候选详情获取失败，不做确认: {error}"
+            );
+        }
+        (Stage::Search, Err(error)) => {
+            check.failed += 1;
+            tracing::warn!(req_id, "检查不可用歌曲：搜索失败: {error}");
+        }
+        (Stage::Search, Ok(_)) => {}
+    }
+    if check.stage == Stage::Verify {
+        finish(app, job, effects);
+    } else {
+        advance(app, job, req_ids, request_tracker, effects);
+    }
+    true
+}
+
+/// 搜索响应：为正在搜索的不可用歌曲挑选候选
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+pub fn handle_search(
+    req_id: u64,
+    songs: &[Song],
+    app: &mut App,
+    job: &mut Option<UnavailableCheck>,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::UnavailableCheck, req_id) {
+        return false;
+    }
+    let Some(check) = job.as_mut() else {
+        return true;
+    };
+    if let Some(original) = check
+        .searched
+        .checked_sub(1)
+        .and_then(|i| check.unavailable.get(i))
+        && let Some(candidate) = best_match(original, songs)
+    {
+        check.found.push(SongSubstitution {
+            original: original.clone(),
+            candidate: candidate.clone(),
+        });
+    }
+    advance(app, job, req_ids, request_tracker, effects);
+    true
+}
+
+/// 发出下一个请求；详情与搜索都结束后确认候选，没有候选时直接结束
+fn advance(
+    app: &mut App,
+    job: &mut Option<UnavailableCheck>,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) {
+    let Some(check) = job.as_mut() else {
+        return;
+    };
+    if check.stage == Stage::Details {
+        if check.cursor < check.songs.len() {
+            let end = (check.cursor + DETAIL_BATCH).min(check.songs.len());
+            let ids = check.songs[check.cursor..end]
+                .iter()
+                .map(|s| s.id)
+                .collect();
+            check.batch_len = end - check.cursor;
+            check.cursor = end;
+            app.playlists_status =
+                format!("检查不可用歌曲: {}/{} 首", check.cursor, check.songs.len());
+            let id = request_tracker.issue(RequestKey::UnavailableCheck, req_ids);
+            effects.send_netease_lo(NeteaseCommand::SongPreviews { req_id: id, ids });
+            effects.emit_state(app);
+            return;
+        }
+        check.stage = Stage::Search;
+    }
+    if check.stage == Stage::Search {
+        if let Some(song) = check.unavailable.get(check.searched) {
+            check.searched += 1;
+            app.playlists_status = format!(
+                "查找替换版本: {}/{} 首",
+                check.searched,
+                check.unavailable.len()
+            );
+            let id = request_tracker.issue(RequestKey::UnavailableCheck, req_ids);
+            effects.send_netease_lo(NeteaseCommand::CloudSearchSongs {
+                req_id: id,
+                keywords: format!("{} {}", song.name, song.artists.replace('/', " ")),
+                limit: SEARCH_LIMIT,
+                offset: 0,
+            });
+            effects.emit_state(app);
+            return;
+        }
+        check.stage = Stage::Verify;
+    }
+    if check.found.is_empty() {
+        finish(app, job, effects);
+        return;
+    }
+    let ids = check.found.iter().map(|sub| sub.candidate.id).collect();
+    let id = request_tracker.issue(RequestKey::UnavailableCheck, req_ids);
+    effects.send_netease_lo(NeteaseCommand::SongPreviews { req_id: id, ids });
+}
+
+/// 检查结束：有替换建议时打开审阅框，否则提示结果
+fn finish(app: &mut App, job: &mut Option<UnavailableCheck>, effects: &mut CoreEffects) {
+    let Some(check) = job.take() else {
+        return;
+    };
+    let unavailable = check.unavailable.len();
+    let mut summary = match (unavailable, check.found.len()) {
+        (0, _) => format!("歌单「{}」中没有不可用的歌曲", check.playlist_name),
+        (n, 0) => format!("{n} 首不可用歌曲没有找到可替换的版本"),
+        (n, found) => format!("{n} 首不可用歌曲中 {found} 首找到了可替换的版本"),
+    };
+    if check.failed > 0 {
+        summary.push_str(&format!("（{} 首检查失败）", check.failed));
+    }
+    tracing::info!(
+        playlist_id = check.playlist_id,
+        unavailable,
+        found = check.found.len(),
+        failed = check.failed,
+        "🎵 [Playlists] 不可用歌曲检查完成"
+    );
+    app.playlists_status = summary.clone();
+    if !check.found.is_empty() {
+        app.unavailable_review = Some(UnavailableReview {
+            playlist_id: check.playlist_id,
+            playlist_name: check.playlist_name,
+            items: check.found,
+            selected: 0,
+        });
+    }
+    effects.toast(summary);
+    effects.emit_state(app);
+}
+
+pub fn move_review(app: &mut App, effects: &mut CoreEffects, down: bool) {
+    let Some(review) = app.unavailable_review.as_mut() else {
+        return;
+    };
+    let next = if down {
+        (review.selected + 1).min(review.items.len().saturating_sub(1))
+    } else {
+        review.selected.saturating_sub(1)
+    };
+    if next != review.selected {
+        review.selected = next;
+        effects.emit_state(app);
+    }
+}
+
+pub fn close_review(app: &mut App, effects: &mut CoreEffects) {
+    if app.unavailable_review.take().is_some() {
+        effects.emit_state(app);
+    }
+}
+
+/// 取出选中的替换建议；处理完最后一条时关闭审阅框
+fn take_selected(app: &mut App) -> Option<(i64, String, SongSubstitution)> {
+    let review = app.unavailable_review.as_mut()?;
+    if review.selected >= review.items.len() {
+        return None;
+    }
+    let item = review.items.remove(review.selected);
+    review.selected = review.selected.min(review.items.len().saturating_sub(1));
+    let taken = (review.playlist_id, review.playlist_name.clone(), item);
+    if review.items.is_empty() {
+        app.unavailable_review = None;
+    }
+    Some(taken)
+}
+
+/// 跳过选中的替换建议
+pub fn skip_selected(app: &mut App, effects: &mut CoreEffects) {
+    if take_selected(app).is_some() {
+        effects.emit_state(app);
+    }
+}
+
+/// 接受选中的替换建议：先把新歌曲添加到歌单
+pub fn accept_selected(
+    app: &mut App,
+    swaps: &mut HashMap<u64, PendingSwap>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let Some((playlist_id, playlist_name, substitution)) = take_selected(app) else {
+        return;
+    };
+    effects.emit_state(app);
+    send_swap_step(
+        PendingSwap {
+            playlist_id,
+            playlist_name,
+            substitution,
+            removing: false,
+            added: false,
+        },
+        swaps,
+        req_ids,
+        effects,
+    );
+}
+
+fn send_swap_step(
+    swap: PendingSwap,
+    swaps: &mut HashMap<u64, PendingSwap>,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) {
+    let (op, song) = if swap.removing {
+        (PlaylistTrackOp::Del, &swap.substitution.original)
+    } else {
+        (PlaylistTrackOp::Add, &swap.substitution.candidate)
+    };
+    let id = req_ids.next();
+    tracing::info!(
+        req_id = id,
+        playlist_id = swap.playlist_id,
+        song_id = song.id,
+        op = op.as_str(),
+        "🎵 [Playlists] 替换不可用歌曲"
+    );
+    effects.send_netease_hi_warn(
+        NeteaseCommand::PlaylistTracksManipulate {
+            req_id: id,
+            op,
+            playlist_id: swap.playlist_id,
+            track_ids: vec![song.id],
+        },
+        "NeteaseActor 通道已关闭：PlaylistTracksManipulate 发送失败",
+    );
+    swaps.insert(id, swap);
+}
+
+/// 替换的一步成功：添加后移除旧歌曲，移除后更新本地的歌单、预加载与「我喜欢」
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+pub fn handle_swap_manipulated(
+    req_id: u64,
+    changed: &[i64],
+    app: &mut App,
+    swaps: &mut HashMap<u64, PendingSwap>,
+    preload_mgr: &mut PreloadManager,
+    req_ids: &IdAllocator,
+    effects: &mut CoreEffects,
+) -> bool {
+    let Some(mut swap) = swaps.remove(&req_id) else {
+        return false;
+    };
+    if !swap.removing {
+        swap.added = changed.contains(&swap.substitution.candidate.id);
+        swap.removing = true;
+        send_swap_step(swap, swaps, req_ids, effects);
+        return true;
+    }
+    apply_swap(app, &swap, preload_mgr);
+    effects.toast(format!(
+        "已把「{}」替换为可播放的版本",
+        swap.substitution.original.name
+    ));
+    effects.emit_state(app);
+    true
+}
+
+fn apply_swap(app: &mut App, swap: &PendingSwap, preload_mgr: &mut PreloadManager) {
+    let SongSubstitution {
+        original,
+        candidate,
+    } = &swap.substitution;
+    let showing = matches!(app.playlist_mode, PlaylistMode::Tracks)
+        && !app.playlist_tracks_recommend
+        && app.playlists.get(app.playlists_selected).map(|p| p.id) == Some(swap.playlist_id);
+    if showing && let Some(pos) = app.playlist_tracks.iter().position(|s| s.id == original.id) {
+        if app.playlist_tracks.iter().any(|s| s.id == candidate.id) {
+            app.playlist_tracks.remove(pos);
+            app.playlist_tracks_selected = app
+                .playlist_tracks_selected
+                .min(app.playlist_tracks.len().saturating_sub(1));
+        } else {
+            app.playlist_tracks[pos] = candidate.clone();
+        }
+    }
+    preload_mgr.invalidate_playlist(app, swap.playlist_id);
+    if let Some(p) = app.playlists.iter_mut().find(|p| p.id == swap.playlist_id) {
+        if !swap.added {
+            p.track_count = p.track_count.saturating_sub(1).max(0);
+        }
+        if p.special_type == 5 && app.liked_song_ids.remove(&original.id) {
+            app.liked_song_ids.insert(candidate.id);
+        }
+    }
+}
+
+/// 检查或替换的请求失败
+/// 返回 true 表示事件已处理，false 表示不是本模块发出的请求
+#[allow(clippy::too_many_arguments)]
+pub fn handle_error(
+    req_id: u64,
+    error: &MessageError,
+    app: &mut App,
+    job: &mut Option<UnavailableCheck>,
+    swaps: &mut HashMap<u64, PendingSwap>,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if request_tracker.get_pending(&RequestKey::UnavailableCheck) == Some(req_id) {
+        return handle_previews(
+            req_id,
+            Err(error),
+            app,
+            job,
+            req_ids,
+            request_tracker,
+            effects,
+        );
+    }
+    let Some(mut swap) = swaps.remove(&req_id) else {
+        return false;
+    };
+    // 新歌曲已在歌单中时添加接口返回 502，照常移除旧歌曲
+    if !swap.removing
+        && matches!(
+            error,
+            MessageError::Netease(NeteaseErrorVariant::Api { code: 502, .. })
+        )
+    {
+        swap.removing = true;
+        send_swap_step(swap, swaps, req_ids, effects);
+        return true;
+    }
+    let name = &swap.substitution.original.name;
+    tracing::warn!(
+        req_id,
+        playlist_id = swap.playlist_id,
+        removing = swap.removing,
+        "🎵 [Playlists] 替换不可用歌曲失败: {error}"
+    );
+    effects.set_toast(Toast::error(if swap.removing {
+        format!(
+            "已添加「{name}」的新版本，但从歌单「{}」移除原歌曲失败: {error}",
+            swap.playlist_name
+        )
+    } else {
+        format!("替换「{name}」失败: {error}")
+    }));
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reupload_with_same_title_and_artist_scores_highest() {
+        let original = Song::titled(1, "晴天", "周杰伦").lasting_ms(269_000);
+        let score = match_score(
+            &original,
+            &Song::titled(2, "晴天", "周杰伦").lasting_ms(269_000),
+        )
+        .expect("同一首");
+        assert!((score - 1.0).abs() < 1e-6);
+        assert!(match_score(&original, &original).is_none(), "不推荐自己");
+    }
+
+    #[test]
+    fn live_and_other_versions_do_not_replace_the_original() {
+        let original = Song::titled(1, "晴天", "周杰伦").lasting_ms(269_000);
+        assert!(
+            match_score(
+                &original,
+                &Song::titled(2, "晴天 (Live)", "周杰伦").lasting_ms(275_000)
+            )
+            .is_none()
+        );
+        assert!(
+            match_score(
+                &original,
+                &Song::titled(3, "晴天（伴奏）", "周杰伦").lasting_ms(269_000)
+            )
+            .is_none()
+        );
+        assert!(
+            match_score(
+                &original,
+                &Song::titled(4, "晴天 - Remix", "周杰伦").lasting_ms(269_000)
+            )
+            .is_none()
+        );
+
+        // 原歌曲本身是现场版时，现场版才是同一首
+        let live = Song::titled(5, "晴天 (Live)", "周杰伦").lasting_ms(300_000);
+        assert!(
+            match_score(
+                &live,
+                &Song::titled(6, "晴天（LIVE）", "周杰伦").lasting_ms(301_000)
+            )
+            .is_some()
+        );
+        assert!(
+            match_score(
+                &live,
+                &Song::titled(7, "晴天", "周杰伦").lasting_ms(300_000)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn covers_with_the_same_title_are_rejected() {
+        let original = Song::titled(1, "后来", "刘若英").lasting_ms(340_000);
+        assert!(
+            match_score(
+                &original,
+                &Song::titled(2, "后来", "某翻唱歌手").lasting_ms(340_000)
+            )
+            .is_none()
+        );
+
+        // 合唱：部分歌手重合仍算同一首，但分数更低
+        let duet = Song::titled(3, "因为爱情", "王菲/陈奕迅").lasting_ms(215_000);
+        let partial = match_score(
+            &duet,
+            &Song::titled(4, "因为爱情", "陈奕迅").lasting_ms(215_000),
+        )
+        .expect("部分重合");
+        let full = match_score(
+            &duet,
+            &Song::titled(5, "因为爱情", "陈奕迅/王菲").lasting_ms(215_000),
+        )
+        .expect("完全重合");
+        assert!(partial < full);
+    }
+
+    #[test]
+    fn duration_tolerates_a_few_seconds_but_not_a_different_recording() {
+        let original = Song::titled(1, "Hello", "Adele").lasting_ms(295_000);
+        let exact = match_score(
+            &original,
+            &Song::titled(2, "Hello", "Adele").lasting_ms(295_000),
+        )
+        .unwrap();
+        let close = match_score(
+            &original,
+            &Song::titled(3, "Hello", "Adele").lasting_ms(297_000),
+        )
+        .unwrap();
+        let off = match_score(
+            &original,
+            &Song::titled(4, "Hello", "Adele").lasting_ms(305_000),
+        )
+        .unwrap();
+        assert_eq!(exact, close, "3 秒内视为相同");
+        assert!(off < close);
+        assert!(
+            match_score(
+                &original,
+                &Song::titled(5, "Hello", "Adele").lasting_ms(340_000)
+            )
+            .is_none()
+        );
+
+        let unknown = Song {
+            duration_ms: None,
+            ..Song::titled(6, "Hello", "Adele").lasting_ms(0)
+        };
+        assert!(match_score(&original, &unknown).is_some());
+    }
+
+    #[test]
+    fn titles_match_across_width_case_and_spacing() {
+        let original = Song::titled(1, "ＨＥＬＬＯ World", "ADELE").lasting_ms(295_000);
+        assert!(
+            match_score(
+                &original,
+                &Song::titled(2, "hello  world", "Adele").lasting_ms(295_000)
+            )
+            .is_some()
+        );
+    }
+
+    #[test]
+    fn best_match_picks_the_closest_candidate_above_the_threshold() {
+        let original = Song::titled(1, "晴天", "周杰伦").lasting_ms(269_000);
+        let candidates = [
+            Song::titled(1, "晴天", "周杰伦").lasting_ms(269_000),
+            Song::titled(2, "晴天 (Live)", "周杰伦").lasting_ms(269_000),
+            Song::titled(3, "晴天", "周杰伦").lasting_ms(280_000),
+            Song::titled(4, "晴天", "周杰伦").lasting_ms(270_000),
+            Song::titled(5, "晴天", "翻唱").lasting_ms(269_000),
+        ];
+        assert_eq!(best_match(&original, &candidates).map(|s| s.id), Some(4));
+        assert!(best_match(&original, &candidates[..3]).is_some());
+        assert!(
+            best_match(
+                &original,
+                &[Song::titled(6, "雨天", "周杰伦").lasting_ms(269_000)]
+            )
+            .is_none()
+        );
+    }
+}
@@ -1,16 +1,22 @@
+use crate::app::{SearchKind, SearchOpened};
+use crate::core::infra::IdAllocator;
 use crate::core::prelude::{
     app::App,
     audio::AudioCommand,
     effects::CoreEffects,
-    infra::{RequestKey, RequestTracker},
+    infra::{NextSongCacheManager, RequestKey, RequestTracker},
     messages::AppCommand,
 };
-use crate::core::utils;
-use crate::domain::model::Song;
+use crate::domain::model::{Album, Artist, Song};
+use crate::features::player::queue_undo;
 use crate::netease::actor::{NeteaseCommand, NeteaseEvent};
 
+pub mod preview;
+
 /// 分页大小：PageDown/PageUp 一次跳转的行数
 const PAGE_SIZE: usize = 10;
+/// 每次搜索请求的结果数；返回不足一页时视为没有更多结果
+const SEARCH_LIMIT: usize = 30;
 
 /// 处理搜索相关的 AppCommand
 /// 返回 true 表示命令已处理，false 表示未处理
@@ -18,9 +24,10 @@ const PAGE_SIZE: usize = 10;
 pub async fn handle_search_command(
     cmd: AppCommand,
     app: &mut App,
-    req_id: &mut u64,
+    req_ids: &IdAllocator,
     request_tracker: &mut RequestTracker<RequestKey>,
     song_request_titles: &mut std::collections::HashMap<i64, String>,
+    next_song_cache: &mut NextSongCacheManager,
     effects: &mut CoreEffects,
 ) -> bool {
     match cmd {
@@ -32,20 +39,84 @@ pub async fn handle_search_command(
                 return true;
             }
             app.search_status = "搜索中...".to_owned();
-            app.search_results.clear();
-            app.search_selected = 0;
+            app.search_marked.clear();
+            reset_search_results(app);
+            app.search_query = q;
             effects.emit_state(app);
-            let id = request_tracker.issue(RequestKey::SourceSearch, || utils::next_id(req_id));
-            effects.send_netease_hi_warn(
-                NeteaseCommand::CloudSearchSongs {
-                    req_id: id,
-                    keywords: q,
-                    limit: 30,
-                    offset: 0,
-                },
-                "NeteaseActor 通道已关闭：CloudSearchSongs 发送失败",
+            start_search(app, req_ids, request_tracker, effects);
+        }
+        AppCommand::SearchLoadMore => {
+            // 专辑曲目/歌手热门歌曲一次取全，无需翻页
+            if app.search_opened.is_some() {
+                return true;
+            }
+            if request_tracker
+                .get_pending(&RequestKey::SourceSearch)
+                .is_some()
+                || request_tracker
+                    .get_pending(&RequestKey::SourceSearchMore)
+                    .is_some()
+            {
+                return true;
+            }
+            let loaded = result_count_label(app);
+            if !app.search_has_more {
+                if app.search_list_len() > 0 {
+                    app.search_status = format!("结果: {loaded}（没有更多了）");
+                    effects.emit_state(app);
+                }
+                return true;
+            }
+            app.search_status = format!("结果: {loaded}（加载更多中...）");
+            effects.emit_state(app);
+            let id = request_tracker.issue(RequestKey::SourceSearchMore, req_ids);
+            send_search(
+                effects,
+                id,
+                app.search_kind,
+                app.search_query.clone(),
+                app.search_list_len(),
             );
         }
+        AppCommand::SearchCycleKind => {
+            app.search_kind = match app.search_kind {
+                SearchKind::Songs => SearchKind::Albums,
+                SearchKind::Albums => SearchKind::Artists,
+                SearchKind::Artists => SearchKind::Songs,
+            };
+            reset_search_results(app);
+            let label = kind_label(app.search_kind);
+            if app.search_query.is_empty() {
+                request_tracker.clear(&RequestKey::SourceSearch);
+                request_tracker.clear(&RequestKey::SourceSearchMore);
+                request_tracker.clear(&RequestKey::SearchOpenTracks);
+                app.search_status = format!("搜索类型: {label}，输入关键词回车搜索");
+                effects.emit_state(app);
+            } else {
+                // 用上次的关键词按新类型重新搜索
+                app.search_status = format!("搜索类型: {label}，搜索中...");
+                effects.emit_state(app);
+                start_search(app, req_ids, request_tracker, effects);
+            }
+        }
+        AppCommand::SearchOpenSelected => {
+            open_selected(app, req_ids, request_tracker, effects);
+        }
+        AppCommand::SearchBack => {
+            request_tracker.clear(&RequestKey::SearchOpenTracks);
+            if app.search_opened.take().is_some() {
+                app.search_results.clear();
+                app.search_selected = app
+                    .search_collection_selected
+                    .min(app.search_list_len().saturating_sub(1));
+                app.search_status = format!("结果: {}", result_count_label(app));
+                effects.emit_state(app);
+            }
+        }
+        AppCommand::SearchToggleMark => {
+            toggle_mark(app);
+            effects.emit_state(app);
+        }
         AppCommand::SearchInputBackspace => {
             app.search_input.pop();
             effects.emit_state(app);
@@ -54,6 +125,10 @@ pub async fn handle_search_command(
             app.search_input.push(c);
             effects.emit_state(app);
         }
+        AppCommand::SearchInputString { s } => {
+            app.search_input.push_str(&s);
+            effects.emit_state(app);
+        }
         AppCommand::SearchMoveUp => {
             if app.search_selected > 0 {
                 app.search_selected -= 1;
@@ -61,22 +136,21 @@ pub async fn handle_search_command(
             }
         }
         AppCommand::SearchMoveDown => {
-            if !app.search_results.is_empty() && app.search_selected + 1 < app.search_results.len()
-            {
+            if app.search_selected + 1 < app.search_list_len() {
                 app.search_selected += 1;
                 effects.emit_state(app);
             }
         }
         AppCommand::SearchMoveTo { index } => {
-            if !app.search_results.is_empty() && index < app.search_results.len() {
+            if index < app.search_list_len() {
                 app.search_selected = index;
                 effects.emit_state(app);
             }
         }
         AppCommand::SearchPageDown => {
-            if !app.search_results.is_empty() {
-                let new_idx = (app.search_selected + PAGE_SIZE)
-                    .min(app.search_results.len().saturating_sub(1));
+            if app.search_list_len() > 0 {
+                let new_idx =
+                    (app.search_selected + PAGE_SIZE).min(app.search_list_len().saturating_sub(1));
                 if new_idx != app.search_selected {
                     app.search_selected = new_idx;
                     effects.emit_state(app);
@@ -84,7 +158,7 @@ pub async fn handle_search_command(
             }
         }
         AppCommand::SearchPageUp => {
-            if !app.search_results.is_empty() {
+            if app.search_list_len() > 0 {
                 let new_idx = app.search_selected.saturating_sub(PAGE_SIZE);
                 if new_idx != app.search_selected {
                     app.search_selected = new_idx;
@@ -93,14 +167,14 @@ pub async fn handle_search_command(
             }
         }
         AppCommand::SearchJumpTop => {
-            if !app.search_results.is_empty() && app.search_selected != 0 {
+            if app.search_list_len() > 0 && app.search_selected != 0 {
                 app.search_selected = 0;
                 effects.emit_state(app);
             }
         }
         AppCommand::SearchJumpBottom => {
-            if !app.search_results.is_empty() {
-                let last = app.search_results.len().saturating_sub(1);
+            if app.search_list_len() > 0 {
+                let last = app.search_list_len().saturating_sub(1);
                 if app.search_selected != last {
                     app.search_selected = last;
                     effects.emit_state(app);
@@ -108,13 +182,30 @@ pub async fn handle_search_command(
             }
         }
         AppCommand::SearchPlaySelected => {
-            if let Some(s) = app.search_results.get(app.search_selected) {
-                app.play_queue.clear();
-                let title = format!("{} - {}", s.name, s.artists);
+            if app.search_showing_albums() || app.search_showing_artists() {
+                open_selected(app, req_ids, request_tracker, effects);
+                return true;
+            }
+            if let Some(s) = app.search_results.get(app.search_selected).cloned() {
+                match app.search_opened.as_ref().map(|opened| opened.origin()) {
+                    // 专辑曲目/歌手热门歌曲：整个列表作为播放队列
+                    Some(origin) => {
+                        let recorded = queue_undo::replace(
+                            app,
+                            app.search_results.clone(),
+                            Some(app.search_selected),
+                            Some(origin),
+                        );
+                        queue_undo::toast_replaced(recorded, effects);
+                        next_song_cache.reset(effects); // 失效预缓存
+                    }
+                    None => app.play_queue.clear(),
+                }
+                let title = s.display_title(app.song_name_mode);
                 app.play_status = format!("获取播放链接中: {title}");
                 effects.emit_state(app);
                 song_request_titles.clear();
-                let id = request_tracker.issue(RequestKey::SongUrl, || utils::next_id(req_id));
+                let id = request_tracker.issue(RequestKey::SongUrl, req_ids);
                 song_request_titles.insert(s.id, title);
 
                 // 先停止当前播放
@@ -135,9 +226,145 @@ pub async fn handle_search_command(
     true
 }
 
+/// 标记或取消标记选中的歌曲，标记后光标移到下一行以便连续标记
+fn toggle_mark(app: &mut App) {
+    if app.search_showing_albums() || app.search_showing_artists() {
+        return;
+    }
+    let Some(song) = app.search_results.get(app.search_selected) else {
+        return;
+    };
+    if let Some(pos) = app.search_marked.iter().position(|s| s.id == song.id) {
+        app.search_marked.remove(pos);
+    } else {
+        app.search_marked.push(song.clone());
+        app.search_selected = (app.search_selected + 1).min(app.search_results.len() - 1);
+    }
+}
+
+/// 清空上一轮的结果（歌曲、专辑、歌手与已打开的专辑/歌手）
+fn reset_search_results(app: &mut App) {
+    app.search_results.clear();
+    app.search_albums.clear();
+    app.search_artists.clear();
+    app.search_opened = None;
+    app.search_selected = 0;
+    app.search_collection_selected = 0;
+    app.search_has_more = false;
+}
+
+/// 按当前搜索类型对 `search_query` 发起新搜索，未完成的翻页/打开请求随之失效
+fn start_search(
+    app: &mut App,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) {
+    request_tracker.clear(&RequestKey::SourceSearchMore);
+    request_tracker.clear(&RequestKey::SearchOpenTracks);
+    let id = request_tracker.issue(RequestKey::SourceSearch, req_ids);
+    send_search(effects, id, app.search_kind, app.search_query.clone(), 0);
+}
+
+fn send_search(
+    effects: &mut CoreEffects,
+    req_id: u64,
+    kind: SearchKind,
+    keywords: String,
+    offset: usize,
+) {
+    let (limit, offset) = (SEARCH_LIMIT as i64, offset as i64);
+    match kind {
+        SearchKind::Songs => effects.send_netease_hi_warn(
+            NeteaseCommand::CloudSearchSongs {
+                req_id,
+                keywords,
+                limit,
+                offset,
+            },
+            "NeteaseActor 通道已关闭：CloudSearchSongs 发送失败",
+        ),
+        SearchKind::Albums => effects.send_netease_hi_warn(
+            NeteaseCommand::CloudSearchAlbums {
+                req_id,
+                keywords,
+                limit,
+                offset,
+            },
+            "NeteaseActor 通道已关闭：CloudSearchAlbums 发送失败",
+        ),
+        SearchKind::Artists => effects.send_netease_hi_warn(
+            NeteaseCommand::CloudSearchArtists {
+                req_id,
+                keywords,
+                limit,
+                offset,
+            },
+            "NeteaseActor 通道已关闭：CloudSearchArtists 发送失败",
+        ),
+    }
+}
+
+/// 打开选中的专辑（加载全部曲目）或歌手（加载热门歌曲）
+fn open_selected(
+    app: &mut App,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) {
+    let selected = app.search_selected;
+    let (status, make_cmd): (String, Box<dyn FnOnce(u64) -> NeteaseCommand>) =
+        if app.search_showing_albums() {
+            let Some(album) = app.search_albums.get(selected) else {
+                return;
+            };
+            let album_id = album.id;
+            (
+                format!("加载专辑曲目中: {}", album.name),
+                Box::new(move |req_id| NeteaseCommand::AlbumTracks { req_id, album_id }),
+            )
+        } else if app.search_showing_artists() {
+            let Some(artist) = app.search_artists.get(selected) else {
+                return;
+            };
+            let artist_id = artist.id;
+            (
+                format!("加载歌手热门歌曲中: {}", artist.name),
+                Box::new(move |req_id| NeteaseCommand::ArtistTopSongs { req_id, artist_id }),
+            )
+        } else {
+            return;
+        };
+    app.search_status = status;
+    app.search_collection_selected = selected;
+    effects.emit_state(app);
+    let id = request_tracker.issue(RequestKey::SearchOpenTracks, req_ids);
+    effects.send_netease_hi_warn(
+        make_cmd(id),
+        "NeteaseActor 通道已关闭：打开专辑/歌手请求发送失败",
+    );
+}
+
+fn kind_label(kind: SearchKind) -> &'static str {
+    match kind {
+        SearchKind::Songs => "歌曲",
+        SearchKind::Albums => "专辑",
+        SearchKind::Artists => "歌手",
+    }
+}
+
+/// 当前列表的数量描述，如 "30 首" / "30 个专辑" / "30 位歌手"
+fn result_count_label(app: &App) -> String {
+    match app.search_kind {
+        SearchKind::Songs => format!("{} 首", app.search_results.len()),
+        SearchKind::Albums => format!("{} 个专辑", app.search_albums.len()),
+        SearchKind::Artists => format!("{} 位歌手", app.search_artists.len()),
+    }
+}
+
 /// 处理搜索相关的 NeteaseEvent::SearchSongs
 /// req_id: 请求ID，用于匹配pending请求
-/// songs: 搜索结果曲目列表
+/// songs: 搜索结果曲目列表（新搜索替换结果，翻页追加到末尾且保持选中项）
 /// 返回 true 表示事件已处理，false 表示未处理（req_id不匹配/过期）
 pub async fn handle_search_songs_event(
     req_id: u64,
@@ -146,14 +373,148 @@ pub async fn handle_search_songs_event(
     request_tracker: &mut RequestTracker<RequestKey>,
     effects: &mut CoreEffects,
 ) -> bool {
-    if !request_tracker.accept(&RequestKey::SourceSearch, req_id) {
+    let has_more = songs.len() >= SEARCH_LIMIT;
+    if request_tracker.accept(&RequestKey::SourceSearch, req_id) {
+        app.search_results = songs;
+        app.search_selected = 0;
+        app.search_has_more = has_more;
+        app.search_status = if has_more {
+            format!("结果: {} 首（n 加载更多）", app.search_results.len())
+        } else {
+            format!("结果: {} 首", app.search_results.len())
+        };
+    } else if request_tracker.accept(&RequestKey::SourceSearchMore, req_id) {
+        app.search_results.extend(songs);
+        app.search_has_more = has_more;
+        app.search_status = format!("结果: {} 首（已加载更多）", app.search_results.len());
+    } else {
         // 过期请求，丢弃
         tracing::trace!(req_id, "搜索响应过期，丢弃（Netease）");
         return false;
     }
+    effects.emit_state(app);
+    true
+}
+
+/// 处理 NeteaseEvent::SearchAlbums
+/// 新搜索替换专辑列表，翻页追加到末尾且保持选中项
+/// 返回 true 表示事件已处理，false 表示未处理（req_id不匹配/过期）
+pub fn handle_search_albums_event(
+    req_id: u64,
+    albums: Vec<Album>,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    let has_more = albums.len() >= SEARCH_LIMIT;
+    if request_tracker.accept(&RequestKey::SourceSearch, req_id) {
+        app.search_albums = albums;
+        app.search_selected = 0;
+        app.search_has_more = has_more;
+        app.search_status = if has_more {
+            format!("结果: {} 个专辑（n 加载更多）", app.search_albums.len())
+        } else {
+            format!("结果: {} 个专辑", app.search_albums.len())
+        };
+    } else if request_tracker.accept(&RequestKey::SourceSearchMore, req_id) {
+        app.search_albums.extend(albums);
+        app.search_has_more = has_more;
+        app.search_status = format!("结果: {} 个专辑（已加载更多）", app.search_albums.len());
+    } else {
+        tracing::trace!(req_id, "专辑搜索响应过期，丢弃（Netease）");
+        return false;
+    }
+    effects.emit_state(app);
+    true
+}
+
+/// 处理 NeteaseEvent::AlbumTracks：打开专辑，曲目显示在结果列表中
+/// 返回 true 表示事件已处理，false 表示未处理（req_id不匹配/过期）
+pub fn handle_album_tracks_event(
+    req_id: u64,
+    album_id: i64,
+    songs: Vec<Song>,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::SearchOpenTracks, req_id) {
+        tracing::trace!(req_id, "专辑曲目响应过期，丢弃（Netease）");
+        return false;
+    }
+    let Some(album) = app.search_albums.iter().find(|a| a.id == album_id).cloned() else {
+        return false;
+    };
+    app.search_status = format!("专辑: {}，{} 首（p 播放 b 返回）", album.name, songs.len());
+    app.search_opened = Some(SearchOpened::Album(album));
+    app.search_results = songs;
+    app.search_selected = 0;
+    effects.emit_state(app);
+    true
+}
+
+/// 处理 NeteaseEvent::SearchArtists
+/// 新搜索替换歌手列表，翻页追加到末尾且保持选中项
+/// 返回 true 表示事件已处理，false 表示未处理（req_id不匹配/过期）
+pub fn handle_search_artists_event(
+    req_id: u64,
+    artists: Vec<Artist>,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    let has_more = artists.len() >= SEARCH_LIMIT;
+    if request_tracker.accept(&RequestKey::SourceSearch, req_id) {
+        app.search_artists = artists;
+        app.search_selected = 0;
+        app.search_has_more = has_more;
+        app.search_status = if has_more {
+            format!("结果: {} 位歌手（n 加载更多）", app.search_artists.len())
+        } else {
+            format!("结果: {} 位歌手", app.search_artists.len())
+        };
+    } else if request_tracker.accept(&RequestKey::SourceSearchMore, req_id) {
+        app.search_artists.extend(artists);
+        app.search_has_more = has_more;
+        app.search_status = format!("结果: {} 位歌手（已加载更多）", app.search_artists.len());
+    } else {
+        tracing::trace!(req_id, "歌手搜索响应过期，丢弃（Netease）");
+        return false;
+    }
+    effects.emit_state(app);
+    true
+}
+
+/// 处理 NeteaseEvent::ArtistTopSongs：打开歌手，热门歌曲显示在结果列表中
+/// 返回 true 表示事件已处理，false 表示未处理（req_id不匹配/过期）
+pub fn handle_artist_top_songs_event(
+    req_id: u64,
+    artist_id: i64,
+    songs: Vec<Song>,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::SearchOpenTracks, req_id) {
+        tracing::trace!(req_id, "歌手热门歌曲响应过期，丢弃（Netease）");
+        return false;
+    }
+    let Some(artist) = app
+        .search_artists
+        .iter()
+        .find(|a| a.id == artist_id)
+        .cloned()
+    else {
+        return false;
+    };
+    app.search_status = format!(
+        "歌手: {}，热门 {} 首（p 播放 b 返回）",
+        artist.name,
+        songs.len()
+    );
+    app.search_opened = Some(SearchOpened::Artist(artist));
     app.search_results = songs;
     app.search_selected = 0;
-    app.search_status = format!("结果: {} 首", app.search_results.len());
     effects.emit_state(app);
     true
 }
@@ -172,6 +533,26 @@ pub async fn handle_search_error_event(
     else {
         return false;
     };
+    if request_tracker.accept(&RequestKey::SongPreview, *evt_req_id) {
+        // 预览失败不打扰用户，停留在该项时面板保持「加载中」
+        tracing::debug!(req_id = evt_req_id, "搜索预览加载失败: {error}");
+        return true;
+    }
+    if request_tracker.accept(&RequestKey::SourceSearchMore, *evt_req_id) {
+        // 保留已有结果，可再次按 n 重试
+        app.search_status = format!("加载更多失败: {error}");
+        effects.emit_state(app);
+        return true;
+    }
+    if request_tracker.accept(&RequestKey::SearchOpenTracks, *evt_req_id) {
+        // 停留在专辑/歌手列表，可再次 Enter 重试
+        app.search_status = match app.search_kind {
+            SearchKind::Artists => format!("加载歌手热门歌曲失败: {error}"),
+            _ => format!("加载专辑失败: {error}"),
+        };
+        effects.emit_state(app);
+        return true;
+    }
     if !request_tracker.accept(&RequestKey::SourceSearch, *evt_req_id) {
         return false;
     }
@@ -0,0 +1,148 @@
+//! 搜索结果预览：选中项停留一段时间后再拉取歌曲详情（专辑、时长、发行年份、可播放性）
+//!
+//! 上下移动时不发请求，停在某一项 [`PREVIEW_DELAY`] 后才拉取；详情按歌曲 id 缓存在
+//! `App::search_previews`，本次会话内来回移动不会重复请求。
+//! 计时器在 reducer 的 select 循环中检查（见 `spawn_app_actor`）。
+
+use crate::app::{App, UiFocus, View};
+use crate::core::infra::IdAllocator;
+use crate::core::prelude::{
+    effects::CoreEffects,
+    infra::{RequestKey, RequestTracker},
+};
+use crate::domain::model::SongPreview;
+use crate::netease::actor::NeteaseCommand;
+use std::time::{Duration, Instant};
+
+/// 选中项停留多久后拉取详情
+pub const PREVIEW_DELAY: Duration = Duration::from_millis(400);
+
+/// 当前需要预览的歌曲：搜索页且焦点在结果列表（或右栏预览）时的选中项
+pub fn preview_target(app: &App) -> Option<i64> {
+    if app.view != View::Search || !matches!(app.ui_focus, UiFocus::BodyCenter | UiFocus::BodyRight)
+    {
+        return None;
+    }
+    if app.search_showing_albums() || app.search_showing_artists() {
+        return None;
+    }
+    app.search_results.get(app.search_selected).map(|s| s.id)
+}
+
+/// 预览防抖：目标变化时重新计时，停留满 [`PREVIEW_DELAY`] 才到期
+#[derive(Debug, Default)]
+pub struct PreviewDebounce {
+    target: Option<i64>,
+    due_at: Option<Instant>,
+}
+
+impl PreviewDebounce {
+    /// 传入当前的预览目标（每次 reduce 后调用）
+    pub fn observe(&mut self, target: Option<i64>, now: Instant) {
+        if target != self.target {
+            self.target = target;
+            self.due_at = target.map(|_| now + PREVIEW_DELAY);
+        }
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.due_at
+    }
+
+    /// 到期时返回目标歌曲 id（每次停留只返回一次）
+    pub fn take_due(&mut self, now: Instant) -> Option<i64> {
+        if self.due_at.is_some_and(|d| now >= d) {
+            self.due_at = None;
+            self.target
+        } else {
+            None
+        }
+    }
+}
+
+/// 防抖到期：刷新本地缓存状态；详情未缓存时发起请求
+pub fn handle_preview_due(
+    song_id: i64,
+    app: &mut App,
+    req_ids: &IdAllocator,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) {
+    app.search_preview_cached = Some((
+        song_id,
+        crate::audio_worker::has_cached_song(&app.audio_cache_dir, song_id),
+    ));
+    if !app.search_previews.contains_key(&song_id) {
+        let id = request_tracker.issue(RequestKey::SongPreview, req_ids);
+        effects.send_netease_hi_warn(
+            NeteaseCommand::SongPreview {
+                req_id: id,
+                id: song_id,
+            },
+            "NeteaseActor 通道已关闭：SongPreview 发送失败",
+        );
+    }
+    effects.emit_state(app);
+}
+
+/// 处理 NeteaseEvent::SongPreview，返回 false 表示请求已过期
+pub fn handle_song_preview_event(
+    req_id: u64,
+    preview: SongPreview,
+    app: &mut App,
+    request_tracker: &mut RequestTracker<RequestKey>,
+    effects: &mut CoreEffects,
+) -> bool {
+    if !request_tracker.accept(&RequestKey::SongPreview, req_id) {
+        tracing::trace!(req_id, "预览响应过期，丢弃");
+        return false;
+    }
+    app.search_previews.insert(preview.id, preview);
+    effects.emit_state(app);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn debounce_fires_only_after_selection_rests() {
+        let t0 = Instant::now();
+        let mut d = PreviewDebounce::default();
+
+        // 快速上下移动：每 100ms 换一个目标，始终不到期
+        for (i, id) in [1, 2, 3, 2].into_iter().enumerate() {
+            let now = t0 + ms(i as u64 * 100);
+            d.observe(Some(id), now);
+            assert_eq!(d.take_due(now), None);
+        }
+        let rested = t0 + ms(300);
+        assert_eq!(d.deadline(), Some(rested + PREVIEW_DELAY));
+        assert_eq!(d.take_due(rested + ms(399)), None);
+        assert_eq!(d.take_due(rested + PREVIEW_DELAY), Some(2));
+
+        // 同一目标只触发一次，重复 observe 不会重新计时
+        d.observe(Some(2), rested + ms(500));
+        assert_eq!(d.deadline(), None);
+        assert_eq!(d.take_due(rested + ms(2_000)), None);
+    }
+
+    #[test]
+    fn leaving_results_cancels_pending_preview() {
+        let t0 = Instant::now();
+        let mut d = PreviewDebounce::default();
+        d.observe(Some(5), t0);
+        d.observe(None, t0 + ms(100));
+        assert_eq!(d.deadline(), None);
+        assert_eq!(d.take_due(t0 + ms(1_000)), None);
+
+        // 回到同一首歌：重新计时
+        d.observe(Some(5), t0 + ms(1_000));
+        assert_eq!(d.take_due(t0 + ms(1_400)), Some(5));
+    }
+}
@@ -1,50 +1,27 @@
+use crate::app::audio_focus::{DUCK_PERCENT_MAX, DUCK_PERCENT_MIN};
+use crate::app::{PaneRatios, SongNameMode, StartupView, Toast};
+use crate::audio_worker::{EQ_MAX_DB, EQ_MIN_DB};
 use crate::core::prelude::{
     app::App, audio::AudioCommand, effects::CoreEffects, infra::NextSongCacheManager,
     messages::AppCommand,
 };
+use crate::features::player::playback::{
+    PLAYBACK_SPEED_STEP, normalize_playback_speed, set_playback_speed,
+};
 use crate::settings;
 
-// 分组枚举
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum SettingsGroup {
-    Playback, // 0: 音质、音量、播放模式
-    Lyrics,   // 1: 歌词 offset
-    Cache,    // 2: 淡入淡出、清除缓存
-    Account,  // 3: 退出登录
-}
-
-impl SettingsGroup {
-    const COUNT: usize = 4;
+pub mod registry;
 
-    fn item_count(self) -> usize {
-        match self {
-            Self::Playback => 3,
-            Self::Lyrics => 1,
-            Self::Cache => 2,
-            Self::Account => 1,
-        }
-    }
+use registry::{ActionOutcome, SettingKind, SettingsGroupDef};
 
-    fn from_index(idx: usize) -> Self {
-        match idx {
-            0 => Self::Playback,
-            1 => Self::Lyrics,
-            2 => Self::Cache,
-            3 => Self::Account,
-            _ => Self::Playback,
-        }
-    }
-
-    // 转换为全局索引
-    fn to_global_index(self, item_idx: usize) -> usize {
-        match self {
-            Self::Playback => item_idx,
-            Self::Lyrics => 3 + item_idx,
-            Self::Cache => 4 + item_idx,
-            Self::Account => 6 + item_idx,
-        }
-    }
-}
+// Seek 步长的可调范围
+const SEEK_SMALL_MIN_MS: u64 = 1_000;
+const SEEK_SMALL_MAX_MS: u64 = 60_000;
+const SEEK_LARGE_MIN_MS: u64 = 5_000;
+const SEEK_LARGE_MAX_MS: u64 = 600_000;
+// 音量步长的可调范围
+const VOLUME_STEP_MIN: f32 = 0.01;
+const VOLUME_STEP_MAX: f32 = 0.25;
 
 /// 处理设置相关的 AppCommand
 /// 返回 true 表示命令已处理，false 表示未处理
@@ -58,22 +35,19 @@ pub async fn handle_settings_command(
 ) -> bool {
     match cmd {
         AppCommand::SettingsGroupPrev => {
-            if app.settings_group_selected > 0 {
-                app.settings_group_selected -= 1;
-            } else {
-                app.settings_group_selected = SettingsGroup::COUNT - 1;
-            }
+            let count = registry::groups().len();
+            app.settings_group_selected = (app.settings_group_selected + count - 1) % count;
             app.settings_selected = 0; // 重置设置项索引
             effects.emit_state(app);
         }
         AppCommand::SettingsGroupNext => {
-            app.settings_group_selected = (app.settings_group_selected + 1) % SettingsGroup::COUNT;
+            app.settings_group_selected =
+                (app.settings_group_selected + 1) % registry::groups().len();
             app.settings_selected = 0;
             effects.emit_state(app);
         }
         AppCommand::SettingsItemPrev => {
-            let group = SettingsGroup::from_index(app.settings_group_selected);
-            let max_idx = group.item_count().saturating_sub(1);
+            let max_idx = selected_group_len(app).saturating_sub(1);
             if app.settings_selected > 0 {
                 app.settings_selected -= 1;
             } else {
@@ -82,63 +56,26 @@ pub async fn handle_settings_command(
             effects.emit_state(app);
         }
         AppCommand::SettingsItemNext => {
-            let group = SettingsGroup::from_index(app.settings_group_selected);
-            let max_idx = group.item_count().saturating_sub(1);
+            let max_idx = selected_group_len(app).saturating_sub(1);
             app.settings_selected = (app.settings_selected + 1).min(max_idx);
             effects.emit_state(app);
         }
-        AppCommand::SettingsDecrease => {
-            if matches!(app.view, crate::app::View::Settings) {
-                let old_br = app.play_br;
-                let old_crossfade = app.crossfade_ms;
-                let group = SettingsGroup::from_index(app.settings_group_selected);
-                let global_idx = group.to_global_index(app.settings_selected);
-                apply_settings_adjust(app, global_idx, -1, next_song_cache);
-                sync_settings_from_app(settings, app);
-                if let Err(e) = settings::save_settings(data_dir, settings) {
-                    tracing::warn!(err = %e, "保存设置失败");
-                }
-                effects.send_audio_warn(
-                    AudioCommand::SetVolume(app.volume),
-                    "AudioWorker 通道已关闭：SetVolume 发送失败",
-                );
-                if old_br != app.play_br {
-                    effects.send_audio(AudioCommand::SetCacheBr(app.play_br));
-                }
-                if old_crossfade != app.crossfade_ms {
-                    effects.send_audio_warn(
-                        AudioCommand::SetCrossfadeMs(app.crossfade_ms),
-                        "AudioWorker 通道已关闭：SetCrossfadeMs 发送失败",
-                    );
-                }
-                effects.emit_state(app);
-            }
-        }
-        AppCommand::SettingsIncrease => {
+        AppCommand::SettingsDecrease | AppCommand::SettingsIncrease => {
             if matches!(app.view, crate::app::View::Settings) {
-                let old_br = app.play_br;
-                let old_crossfade = app.crossfade_ms;
-                let group = SettingsGroup::from_index(app.settings_group_selected);
-                let global_idx = group.to_global_index(app.settings_selected);
-                apply_settings_adjust(app, global_idx, 1, next_song_cache);
-                sync_settings_from_app(settings, app);
-                if let Err(e) = settings::save_settings(data_dir, settings) {
-                    tracing::warn!(err = %e, "保存设置失败");
-                }
-                effects.send_audio_warn(
-                    AudioCommand::SetVolume(app.volume),
-                    "AudioWorker 通道已关闭：SetVolume 发送失败",
+                let dir = if matches!(cmd, AppCommand::SettingsIncrease) {
+                    1
+                } else {
+                    -1
+                };
+                adjust_selected(
+                    registry::groups(),
+                    app,
+                    dir,
+                    settings,
+                    data_dir,
+                    effects,
+                    next_song_cache,
                 );
-                if old_br != app.play_br {
-                    effects.send_audio(AudioCommand::SetCacheBr(app.play_br));
-                }
-                if old_crossfade != app.crossfade_ms {
-                    effects.send_audio_warn(
-                        AudioCommand::SetCrossfadeMs(app.crossfade_ms),
-                        "AudioWorker 通道已关闭：SetCrossfadeMs 发送失败",
-                    );
-                }
-                effects.emit_state(app);
             }
         }
         _ => return false,
@@ -146,39 +83,133 @@ pub async fn handle_settings_command(
     true
 }
 
-/// 处理设置激活命令（SettingsActivate）
-/// 返回 Some(true) 表示已处理且应 continue，Some(false) 表示未处理
+/// 处理设置激活命令（SettingsActivate）：动作项执行动作，开关项切换，调整项忽略
+/// 返回 [`ActionOutcome::Logout`] 时由调用者执行退出登录
 pub async fn handle_settings_activate_command(
     app: &mut App,
+    settings: &mut settings::AppSettings,
+    data_dir: &std::path::Path,
     effects: &mut CoreEffects,
-) -> Option<bool> {
+    next_song_cache: &mut NextSongCacheManager,
+) -> ActionOutcome {
     if !matches!(app.view, crate::app::View::Settings) {
-        return Some(false);
+        return ActionOutcome::Done;
+    }
+    activate_selected(
+        registry::groups(),
+        app,
+        settings,
+        data_dir,
+        effects,
+        next_song_cache,
+    )
+}
+
+fn selected_group_len(app: &App) -> usize {
+    registry::groups()
+        .get(app.settings_group_selected)
+        .map_or(0, |g| g.items.len())
+}
+
+fn activate_selected(
+    groups: &[SettingsGroupDef],
+    app: &mut App,
+    settings: &mut settings::AppSettings,
+    data_dir: &std::path::Path,
+    effects: &mut CoreEffects,
+    next_song_cache: &mut NextSongCacheManager,
+) -> ActionOutcome {
+    let Some(item) = registry::selected_item(groups, app) else {
+        return ActionOutcome::Done;
+    };
+    match item.kind {
+        SettingKind::Action { run } => {
+            let outcome = run(app, effects);
+            if outcome == ActionOutcome::Done {
+                effects.emit_state(app);
+            }
+            outcome
+        }
+        SettingKind::Toggle { .. } => {
+            adjust_selected(groups, app, 1, settings, data_dir, effects, next_song_cache);
+            ActionOutcome::Done
+        }
+        SettingKind::Adjust { .. } => ActionOutcome::Done,
     }
+}
 
-    if is_clear_cache_selected(app) {
-        app.settings_status = "正在清除音频缓存...".to_owned();
-        tracing::info!("用户触发：清除音频缓存");
+/// 对选中项执行 ←/→ 调整（动作项忽略），按实际变化同步音频、失效预缓存并保存设置
+fn adjust_selected(
+    groups: &[SettingsGroupDef],
+    app: &mut App,
+    dir: i32,
+    settings: &mut settings::AppSettings,
+    data_dir: &std::path::Path,
+    effects: &mut CoreEffects,
+    next_song_cache: &mut NextSongCacheManager,
+) {
+    let Some(item) = registry::selected_item(groups, app) else {
+        return;
+    };
+    let (old_br, old_volume, old_mode, old_crossfade, old_normalize) = (
+        app.play_br,
+        app.output_volume(),
+        app.play_mode,
+        app.crossfade_ms,
+        app.normalize_volume,
+    );
+    let (old_speed, old_eq) = (app.play_speed, (app.eq_bass_db, app.eq_treble_db));
+    match item.kind {
+        SettingKind::Adjust { apply } => apply(app, dir),
+        SettingKind::Toggle { toggle } => toggle(app),
+        SettingKind::Action { .. } => return,
+    }
+    if item.persisted {
+        persist_settings(settings, app, data_dir);
+    }
+    if old_volume != app.output_volume() {
         effects.send_audio_warn(
-            AudioCommand::ClearCache,
-            "AudioWorker 通道已关闭：ClearCache 发送失败",
+            AudioCommand::SetVolume(app.output_volume()),
+            "AudioWorker 通道已关闭：SetVolume 发送失败",
         );
-        effects.emit_state(app);
-        Some(true)
-    } else if is_logout_selected(app) {
-        if !app.logged_in {
-            app.settings_status = "未登录，无需退出".to_owned();
-            effects.emit_state(app);
-            Some(true)
-        } else {
-            Some(false) // 由调用者处理登出逻辑
-        }
-    } else {
-        Some(true)
     }
+    if old_br != app.play_br {
+        effects.send_audio(AudioCommand::SetCacheBr(app.play_br));
+    }
+    if old_mode != app.play_mode {
+        next_song_cache.reset(effects); // 失效预缓存
+    }
+    if old_normalize != app.normalize_volume {
+        effects.send_audio_warn(
+            AudioCommand::SetVolumeNormalization(app.normalize_volume),
+            "AudioWorker 通道已关闭：SetVolumeNormalization 发送失败",
+        );
+    }
+    if old_speed != app.play_speed {
+        effects.send_audio_warn(
+            AudioCommand::SetPlaybackSpeed(app.play_speed),
+            "AudioWorker 通道已关闭：SetPlaybackSpeed 发送失败",
+        );
+    }
+    if old_eq != (app.eq_bass_db, app.eq_treble_db) {
+        effects.send_audio_warn(
+            AudioCommand::SetEqualizer {
+                bass_db: app.eq_bass_db,
+                treble_db: app.eq_treble_db,
+            },
+            "AudioWorker 通道已关闭：SetEqualizer 发送失败",
+        );
+    }
+    if old_crossfade != app.crossfade_ms {
+        effects.send_audio_warn(
+            AudioCommand::SetCrossfadeMs(app.crossfade_ms),
+            "AudioWorker 通道已关闭：SetCrossfadeMs 发送失败",
+        );
+    }
+    effects.emit_state(app);
 }
 
-/// 处理播放器音量和模式控制命令（涉及设置持久化）
+/// 处理播放器音量、倍速和模式控制命令（涉及设置持久化）
 /// 返回 true 表示命令已处理，false 表示未处理
 pub async fn handle_player_settings_command(
     cmd: AppCommand,
@@ -189,27 +220,59 @@ pub async fn handle_player_settings_command(
     next_song_cache: &mut NextSongCacheManager,
 ) -> bool {
     match cmd {
-        AppCommand::PlayerVolumeDown => {
-            app.volume = (app.volume - 0.1).clamp(0.0, 2.0);
+        AppCommand::PlayerVolumeDown | AppCommand::PlayerVolumeUp => {
+            let delta = if matches!(cmd, AppCommand::PlayerVolumeUp) {
+                app.volume_step
+            } else {
+                -app.volume_step
+            };
+            app.volume = step_volume(app.volume, delta);
+            app.muted = false; // 调整音量即取消静音
             effects.send_audio_warn(
-                AudioCommand::SetVolume(app.volume),
+                AudioCommand::SetVolume(app.output_volume()),
                 "AudioWorker 通道已关闭：SetVolume 发送失败",
             );
-            sync_settings_from_app(settings, app);
-            if let Err(e) = settings::save_settings(data_dir, settings) {
-                tracing::warn!(err = %e, "保存设置失败");
-            }
+            persist_settings(settings, app, data_dir);
             effects.emit_state(app);
         }
-        AppCommand::PlayerVolumeUp => {
-            app.volume = (app.volume + 0.1).clamp(0.0, 2.0);
+        AppCommand::PlayerToggleMute => {
+            app.muted = !app.muted;
+            app.play_status = if app.muted {
+                "已静音（Alt+M 恢复）".to_owned()
+            } else {
+                format!("已取消静音，音量 {:.0}%", app.volume * 100.0)
+            };
             effects.send_audio_warn(
-                AudioCommand::SetVolume(app.volume),
+                AudioCommand::SetVolume(app.output_volume()),
                 "AudioWorker 通道已关闭：SetVolume 发送失败",
             );
-            sync_settings_from_app(settings, app);
-            if let Err(e) = settings::save_settings(data_dir, settings) {
-                tracing::warn!(err = %e, "保存设置失败");
+            // 只切换输出，不改变 volume，设置中仍保存静音前的音量
+            effects.emit_state(app);
+        }
+        AppCommand::PlayerSetVolume { volume } => {
+            let volume = volume.clamp(0.0, 2.0);
+            if volume != app.volume || app.muted {
+                app.volume = volume;
+                app.muted = false;
+                effects.send_audio_warn(
+                    AudioCommand::SetVolume(app.output_volume()),
+                    "AudioWorker 通道已关闭：SetVolume 发送失败",
+                );
+                persist_settings(settings, app, data_dir);
+                effects.emit_state(app);
+            }
+        }
+        AppCommand::PlayerSpeedDown | AppCommand::PlayerSpeedUp | AppCommand::PlayerSpeedReset => {
+            let target = match cmd {
+                AppCommand::PlayerSpeedDown => app.play_speed - PLAYBACK_SPEED_STEP,
+                AppCommand::PlayerSpeedUp => app.play_speed + PLAYBACK_SPEED_STEP,
+                _ => 1.0,
+            };
+            let before = app.play_speed;
+            let speed = set_playback_speed(app, effects, target);
+            effects.set_toast(Toast::info(format!("播放倍速 {speed:.2}x")));
+            if speed != before {
+                persist_settings(settings, app, data_dir);
             }
             effects.emit_state(app);
         }
@@ -220,11 +283,8 @@ pub async fn handle_player_settings_command(
                 "播放模式: {}",
                 crate::features::player::playback::play_mode_label(app.play_mode)
             );
-            next_song_cache.reset(); // 失效预缓存
-            sync_settings_from_app(settings, app);
-            if let Err(e) = settings::save_settings(data_dir, settings) {
-                tracing::warn!(err = %e, "保存设置失败");
-            }
+            next_song_cache.reset(effects); // 失效预缓存
+            persist_settings(settings, app, data_dir);
             effects.emit_state(app);
         }
         _ => return false,
@@ -232,94 +292,177 @@ pub async fn handle_player_settings_command(
     true
 }
 
+/// 按步长调整音量，结果取整到 1% 并限制在 0～200%
+fn step_volume(volume: f32, delta: f32) -> f32 {
+    ((volume + delta) * 100.0).round().clamp(0.0, 200.0) / 100.0
+}
+
+/// 调整主体左/中栏分界线并保存（`delta` 为百分比，正数向右）
+pub fn nudge_pane_split(
+    app: &mut App,
+    settings: &mut settings::AppSettings,
+    data_dir: &std::path::Path,
+    effects: &mut CoreEffects,
+    delta: i16,
+) {
+    if !app.pane_ratios.nudge(delta) {
+        return;
+    }
+    let [left, center, right] = app.pane_ratios.to_percentages();
+    tracing::debug!(left, center, right, "调整主体栏宽比例");
+    persist_settings(settings, app, data_dir);
+    effects.emit_state(app);
+}
+
 /// 从设置同步到 App
 pub fn apply_settings_to_app(app: &mut App, s: &settings::AppSettings) {
     app.volume = s.volume.clamp(0.0, 2.0);
+    app.volume_step = s.volume_step.clamp(VOLUME_STEP_MIN, VOLUME_STEP_MAX);
     app.play_br = s.br;
     app.play_mode = settings::play_mode_from_string(&s.play_mode);
     app.play_queue.set_mode(app.play_mode);
     app.lyrics_offset_ms = s.lyrics_offset_ms;
+    app.lyrics_max_lines = s.lyrics_max_lines.max(1);
+    app.status_lyrics = s.status_lyrics;
     app.crossfade_ms = s.crossfade_ms;
+    app.normalize_volume = s.normalize_volume;
+    app.set_play_speed(normalize_playback_speed(s.playback_speed));
+    app.eq_bass_db = s.eq_bass_db.clamp(EQ_MIN_DB, EQ_MAX_DB);
+    app.eq_treble_db = s.eq_treble_db.clamp(EQ_MIN_DB, EQ_MAX_DB);
+    app.seek_step_small_ms = s
+        .seek_step_small_ms
+        .clamp(SEEK_SMALL_MIN_MS, SEEK_SMALL_MAX_MS);
+    app.seek_step_large_ms = s
+        .seek_step_large_ms
+        .clamp(SEEK_LARGE_MIN_MS, SEEK_LARGE_MAX_MS);
+    app.pause_on_suspend = s.pause_on_suspend;
+    app.pause_on_focus_lost = s.pause_on_focus_lost;
+    app.audio_focus_policy = settings::audio_focus_policy_from_string(&s.audio_focus_policy);
+    app.audio_focus_duck_percent = s
+        .audio_focus_duck_percent
+        .clamp(DUCK_PERCENT_MIN, DUCK_PERCENT_MAX);
+    app.notifications_enabled = s.notifications_enabled;
+    app.startup_view = settings::startup_view_from_string(&s.startup_view);
+    app.restore_queue_on_startup = s.restore_queue_on_startup;
+    app.resume_on_start = s.resume_on_start;
+    app.pane_ratios = PaneRatios::from_percentages(s.pane_ratios);
+    app.song_name_mode = settings::song_name_mode_from_string(&s.song_name_mode);
+    app.no_color = s.no_color;
 }
 
 /// 从 App 同步到设置
 pub fn sync_settings_from_app(s: &mut settings::AppSettings, app: &App) {
     s.volume = app.volume;
+    s.volume_step = app.volume_step;
     s.br = app.play_br;
     s.play_mode = settings::play_mode_to_string(app.play_mode);
     s.lyrics_offset_ms = app.lyrics_offset_ms;
+    s.lyrics_max_lines = app.lyrics_max_lines;
+    s.status_lyrics = app.status_lyrics;
     s.crossfade_ms = app.crossfade_ms;
+    s.normalize_volume = app.normalize_volume;
+    s.playback_speed = app.play_speed;
+    s.eq_bass_db = app.eq_bass_db;
+    s.eq_treble_db = app.eq_treble_db;
+    s.seek_step_small_ms = app.seek_step_small_ms;
+    s.seek_step_large_ms = app.seek_step_large_ms;
+    s.pause_on_suspend = app.pause_on_suspend;
+    s.pause_on_focus_lost = app.pause_on_focus_lost;
+    s.audio_focus_policy = settings::audio_focus_policy_to_string(app.audio_focus_policy);
+    s.audio_focus_duck_percent = app.audio_focus_duck_percent;
+    s.notifications_enabled = app.notifications_enabled;
+    s.startup_view = settings::startup_view_to_string(app.startup_view);
+    s.restore_queue_on_startup = app.restore_queue_on_startup;
+    s.resume_on_start = app.resume_on_start;
+    s.pane_ratios = app.pane_ratios.to_percentages();
+    s.song_name_mode = settings::song_name_mode_to_string(app.song_name_mode);
+    s.no_color = app.no_color;
 }
 
-fn is_logout_selected(app: &App) -> bool {
-    // 账号分组（group_selected=3）的第1项（settings_selected=0）
-    app.settings_group_selected == 3 && app.settings_selected == 0
+/// 同步到设置并写盘；设置没有实际变化时（如音量已到上限）跳过写盘
+fn persist_settings(settings: &mut settings::AppSettings, app: &App, data_dir: &std::path::Path) {
+    let before = settings.clone();
+    sync_settings_from_app(settings, app);
+    if *settings == before {
+        return;
+    }
+    if let Err(e) = settings::save_settings(data_dir, settings) {
+        tracing::warn!(err = %e, "保存设置失败");
+    }
 }
 
-fn is_clear_cache_selected(app: &App) -> bool {
-    // 缓存分组（group_selected=2）的第2项（settings_selected=1）
-    app.settings_group_selected == 2 && app.settings_selected == 1
+/// 按 `unit` 调整步长并对齐到整数倍
+fn adjust_step(cur: u64, dir: i32, unit: u64, min: u64, max: u64) -> u64 {
+    let aligned = cur / unit * unit;
+    let next = if dir > 0 {
+        aligned.saturating_add(unit)
+    } else if aligned < cur {
+        aligned
+    } else {
+        aligned.saturating_sub(unit)
+    };
+    next.clamp(min, max)
 }
 
-fn apply_settings_adjust(
-    app: &mut App,
-    global_idx: usize,
-    dir: i32,
-    next_song_cache: &mut NextSongCacheManager,
-) {
-    match global_idx {
-        0 => {
-            let options = [128_000, 192_000, 320_000, 999_000];
-            let pos = options
-                .iter()
-                .position(|v| *v == app.play_br)
-                .unwrap_or(options.len() - 1);
-            let next = if dir > 0 {
-                (pos + 1).min(options.len() - 1)
-            } else {
-                pos.saturating_sub(1)
-            };
-            app.play_br = options[next];
-            app.settings_status = format!("音质已设置为 {}", br_label(app.play_br));
-        }
-        1 => {
-            app.volume = (app.volume + if dir > 0 { 0.05 } else { -0.05 }).clamp(0.0, 2.0);
-            app.settings_status = format!("音量已设置为 {:.0}%", app.volume * 100.0);
-        }
-        2 => {
-            app.play_mode = if dir > 0 {
-                crate::features::player::playback::next_play_mode(app.play_mode)
-            } else {
-                crate::features::player::playback::prev_play_mode(app.play_mode)
-            };
-            app.play_queue.set_mode(app.play_mode);
-            app.settings_status = format!(
-                "播放模式: {}",
-                crate::features::player::playback::play_mode_label(app.play_mode)
-            );
-            next_song_cache.reset(); // 失效预缓存
-        }
-        3 => {
-            app.lyrics_offset_ms =
-                app.lyrics_offset_ms
-                    .saturating_add(if dir > 0 { 200 } else { -200 });
-            app.settings_status = format!("歌词 offset: {}ms", app.lyrics_offset_ms);
-        }
-        4 => {
-            let step = if dir > 0 { 50 } else { -50 };
-            let next = (app.crossfade_ms as i64 + step).clamp(0, 2000) as u64;
-            app.crossfade_ms = next;
-            app.settings_status = if app.crossfade_ms == 0 {
-                "淡入淡出已关闭".to_owned()
-            } else {
-                format!("淡入淡出: {}ms", app.crossfade_ms)
-            };
-        }
-        _ => {}
+fn cycle_startup_view(cur: StartupView, dir: i32) -> StartupView {
+    const OPTIONS: [StartupView; 4] = [
+        StartupView::Last,
+        StartupView::Playlists,
+        StartupView::Search,
+        StartupView::NowPlaying,
+    ];
+    let pos = OPTIONS.iter().position(|v| *v == cur).unwrap_or(1);
+    let next = if dir > 0 {
+        (pos + 1) % OPTIONS.len()
+    } else {
+        (pos + OPTIONS.len() - 1) % OPTIONS.len()
+    };
+    OPTIONS[next]
+}
+
+fn startup_view_label(v: StartupView) -> &'static str {
+    match v {
+        StartupView::Last => "上次使用",
+        StartupView::Playlists => "歌单",
+        StartupView::Search => "搜索",
+        StartupView::NowPlaying => "正在播放",
+    }
+}
+
+fn cycle_song_name_mode(cur: SongNameMode, dir: i32) -> SongNameMode {
+    const OPTIONS: [SongNameMode; 3] = [
+        SongNameMode::Original,
+        SongNameMode::TranslatedFirst,
+        SongNameMode::Both,
+    ];
+    let pos = OPTIONS.iter().position(|m| *m == cur).unwrap_or(0);
+    let next = if dir > 0 {
+        (pos + 1) % OPTIONS.len()
+    } else {
+        (pos + OPTIONS.len() - 1) % OPTIONS.len()
+    };
+    OPTIONS[next]
+}
+
+fn song_name_mode_label(m: SongNameMode) -> &'static str {
+    match m {
+        SongNameMode::Original => "原名",
+        SongNameMode::TranslatedFirst => "译名优先",
+        SongNameMode::Both => "原名(译名)",
+    }
+}
+
+/// 切换歌曲名语言后按新方式重建「正在播放」的标题（歌曲名只保存原名与译名，无需重新拉取）
+fn refresh_now_playing_title(app: &mut App) {
+    let Some(song_id) = app.play_song_id else {
+        return;
+    };
+    if let Some(song) = app.play_queue.songs().iter().find(|s| s.id == song_id) {
+        app.now_playing = Some(song.display_title(app.song_name_mode));
     }
 }
 
-fn br_label(br: i64) -> &'static str {
+pub(crate) fn br_label(br: i64) -> &'static str {
     match br {
         128_000 => "128k",
         192_000 => "192k",
@@ -328,3 +471,81 @@ fn br_label(br: i64) -> &'static str {
         _ => "自定义",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::registry::{self, ActionOutcome, SettingsGroupDef};
+    use super::*;
+
+    /// 在注册表中按文本前缀定位设置项，返回 (分组下标, 条目下标)
+    fn position_of(groups: &[SettingsGroupDef], app: &App, prefix: &str) -> (usize, usize) {
+        groups
+            .iter()
+            .enumerate()
+            .find_map(|(g, group)| {
+                group
+                    .items
+                    .iter()
+                    .position(|item| (item.label)(app).starts_with(prefix))
+                    .map(|i| (g, i))
+            })
+            .unwrap_or_else(|| panic!("未找到设置项: {prefix}"))
+    }
+
+    fn activate(groups: &[SettingsGroupDef], app: &mut App, prefix: &str) -> ActionOutcome {
+        let dir = tempfile::tempdir().expect("tempdir");
+        (app.settings_group_selected, app.settings_selected) = position_of(groups, app, prefix);
+        activate_selected(
+            groups,
+            app,
+            &mut settings::AppSettings::default(),
+            dir.path(),
+            &mut CoreEffects::default(),
+            &mut NextSongCacheManager::default(),
+        )
+    }
+
+    #[test]
+    fn activation_follows_registry_regardless_of_order() {
+        // 分组与条目全部倒序后，按文本找到的项仍分派到各自的动作
+        let mut reversed = registry::build();
+        reversed.reverse();
+        for group in &mut reversed {
+            group.items.reverse();
+        }
+
+        for groups in [registry::build(), reversed] {
+            let mut app = App {
+                logged_in: true,
+                ..App::default()
+            };
+            let outcome = activate(&groups, &mut app, "清除音频缓存");
+            assert_eq!(outcome, ActionOutcome::Done);
+            assert_eq!(app.settings_status, "正在清除音频缓存...");
+
+            app.settings_status.clear();
+            let outcome = activate(&groups, &mut app, "退出登录");
+            assert_eq!(outcome, ActionOutcome::Logout);
+            assert!(app.settings_status.is_empty());
+
+            // 开关项：Enter 切换
+            let outcome = activate(&groups, &mut app, "状态栏歌词");
+            assert_eq!(outcome, ActionOutcome::Done);
+            assert!(!app.status_lyrics);
+
+            // 调整项：Enter 不改变值
+            let before = app.volume;
+            activate(&groups, &mut app, "音量");
+            assert_eq!(app.volume, before);
+        }
+    }
+
+    #[test]
+    fn logout_action_when_logged_out_only_sets_status() {
+        let groups = registry::build();
+        let mut app = App::default();
+        let outcome = activate(&groups, &mut app, "退出登录");
+        assert_eq!(outcome, ActionOutcome::Done);
+        assert_eq!(app.settings_status, "未登录，无需退出");
+    }
+}
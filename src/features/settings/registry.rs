@@ -0,0 +1,505 @@
+//! 设置页条目注册表
+//!
+//! 分组与条目只在 [`build`] 中声明一次：设置页渲染、↑↓ 导航、←/→ 调整与 Enter 激活
+//! 都按注册表分派，新增设置项只需在这里加一条，不再需要同步维护下标映射。
+
+use std::sync::LazyLock;
+
+use super::{
+    SEEK_LARGE_MAX_MS, SEEK_LARGE_MIN_MS, SEEK_SMALL_MAX_MS, SEEK_SMALL_MIN_MS, VOLUME_STEP_MAX,
+    VOLUME_STEP_MIN,
+};
+use super::{
+    adjust_step, br_label, cycle_song_name_mode, cycle_startup_view, refresh_now_playing_title,
+    song_name_mode_label, startup_view_label,
+};
+use crate::app::TaskKind;
+use crate::app::audio_focus::{
+    AudioFocus, AudioFocusPolicy, DUCK_PERCENT_MAX, DUCK_PERCENT_MIN, DUCK_PERCENT_STEP,
+};
+use crate::audio_worker::{EQ_MAX_DB, EQ_MIN_DB};
+use crate::core::prelude::{app::App, audio::AudioCommand, effects::CoreEffects};
+use crate::features::login::login_expiry_date;
+use crate::features::player::audio::format_bytes;
+use crate::features::player::playback::{
+    PLAYBACK_SPEED_STEP, next_play_mode, normalize_playback_speed, play_mode_label, prev_play_mode,
+};
+
+/// 设置分组
+pub struct SettingsGroupDef {
+    pub title: &'static str,
+    pub items: Vec<SettingItemDef>,
+}
+
+/// 设置项
+pub struct SettingItemDef {
+    /// 列表中显示的文本（含当前值）
+    pub label: fn(&App) -> String,
+    pub kind: SettingKind,
+    /// 是否写入 settings.json；仅本次运行有效的项为 false
+    pub persisted: bool,
+}
+
+pub enum SettingKind {
+    /// ←/→ 调整，`dir` 为 1 或 -1；Enter 无效果
+    Adjust { apply: fn(&mut App, i32) },
+    /// ←/→ 与 Enter 都切换开关
+    Toggle { toggle: fn(&mut App) },
+    /// Enter 触发；←/→ 无效果
+    Action {
+        run: fn(&mut App, &mut CoreEffects) -> ActionOutcome,
+    },
+}
+
+/// 动作项的执行结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionOutcome {
+    /// 已在动作内完成
+    Done,
+    /// 需要由 reducer 执行退出登录（涉及请求跟踪、预加载等 Core 状态）
+    Logout,
+    /// 需要由 reducer 开始导出全部歌单（涉及请求跟踪与后台写盘）
+    ExportPlaylists,
+}
+
+static GROUPS: LazyLock<Vec<SettingsGroupDef>> = LazyLock::new(build);
+
+/// 全部设置分组（首次访问时构建）
+pub fn groups() -> &'static [SettingsGroupDef] {
+    &GROUPS
+}
+
+/// 当前选中的设置项
+pub fn selected_item<'a>(groups: &'a [SettingsGroupDef], app: &App) -> Option<&'a SettingItemDef> {
+    groups
+        .get(app.settings_group_selected)?
+        .items
+        .get(app.settings_selected)
+}
+
+fn on_off(v: bool) -> &'static str {
+    if v { "开启" } else { "关闭" }
+}
+
+fn adjust_eq(db: i8, dir: i32) -> i8 {
+    let step = if dir > 0 { 1 } else { -1 };
+    db.saturating_add(step).clamp(EQ_MIN_DB, EQ_MAX_DB)
+}
+
+fn fmt_offset(offset_ms: i64) -> String {
+    let sign = if offset_ms < 0 { "-" } else { "+" };
+    let s = offset_ms.unsigned_abs() as f64 / 1000.0;
+    format!("{sign}{s:.2}s")
+}
+
+fn adjust(apply: fn(&mut App, i32)) -> SettingKind {
+    SettingKind::Adjust { apply }
+}
+
+fn toggle(toggle: fn(&mut App)) -> SettingKind {
+    SettingKind::Toggle { toggle }
+}
+
+fn item(label: fn(&App) -> String, kind: SettingKind) -> SettingItemDef {
+    SettingItemDef {
+        label,
+        kind,
+        persisted: true,
+    }
+}
+
+fn session_item(label: fn(&App) -> String, kind: SettingKind) -> SettingItemDef {
+    SettingItemDef {
+        persisted: false,
+        ..item(label, kind)
+    }
+}
+
+pub(super) fn build() -> Vec<SettingsGroupDef> {
+    vec![
+        SettingsGroupDef {
+            title: "播放",
+            items: vec![
+                item(
+                    |app| format!("音质: {}", br_label(app.play_br)),
+                    adjust(|app, dir| {
+                        let options = [128_000, 192_000, 320_000, 999_000];
+                        let pos = options
+                            .iter()
+                            .position(|v| *v == app.play_br)
+                            .unwrap_or(options.len() - 1);
+                        let next = if dir > 0 {
+                            (pos + 1).min(options.len() - 1)
+                        } else {
+                            pos.saturating_sub(1)
+                        };
+                        app.play_br = options[next];
+                        app.settings_status = format!("音质已设置为 {}", br_label(app.play_br));
+                    }),
+                ),
+                item(
+                    |app| format!("音量: {:.0}%", app.volume * 100.0),
+                    adjust(|app, dir| {
+                        app.volume =
+                            (app.volume + if dir > 0 { 0.05 } else { -0.05 }).clamp(0.0, 2.0);
+                        app.muted = false;
+                        app.settings_status = format!("音量已设置为 {:.0}%", app.volume * 100.0);
+                    }),
+                ),
+                item(
+                    |app| format!("音量步长 (Alt+↑/↓): {:.0}%", app.volume_step * 100.0),
+                    adjust(|app, dir| {
+                        let step =
+                            (app.volume_step * 100.0).round() + if dir > 0 { 1.0 } else { -1.0 };
+                        app.volume_step = (step / 100.0).clamp(VOLUME_STEP_MIN, VOLUME_STEP_MAX);
+                        app.settings_status = format!("音量步长: {:.0}%", app.volume_step * 100.0);
+                    }),
+                ),
+                item(
+                    |app| format!("音量均衡: {}", on_off(app.normalize_volume)),
+                    toggle(|app| {
+                        app.normalize_volume = !app.normalize_volume;
+                        app.settings_status = format!(
+                            "音量均衡: {}（按歌曲响度调整音量）",
+                            on_off(app.normalize_volume)
+                        );
+                    }),
+                ),
+                item(
+                    |app| format!("播放模式: {}", play_mode_label(app.play_mode)),
+                    adjust(|app, dir| {
+                        app.play_mode = if dir > 0 {
+                            next_play_mode(app.play_mode)
+                        } else {
+                            prev_play_mode(app.play_mode)
+                        };
+                        app.play_queue.set_mode(app.play_mode);
+                        app.settings_status =
+                            format!("播放模式: {}", play_mode_label(app.play_mode));
+                    }),
+                ),
+                item(
+                    |app| format!("播放倍速 (</>): {:.2}x", app.play_speed),
+                    adjust(|app, dir| {
+                        let target = app.play_speed + dir.signum() as f32 * PLAYBACK_SPEED_STEP;
+                        app.set_play_speed(normalize_playback_speed(target));
+                        app.settings_status = format!("播放倍速: {:.2}x", app.play_speed);
+                    }),
+                ),
+                item(
+                    |app| format!("低音: {:+} dB", app.eq_bass_db),
+                    adjust(|app, dir| {
+                        app.eq_bass_db = adjust_eq(app.eq_bass_db, dir);
+                        app.settings_status = format!("低音: {:+} dB", app.eq_bass_db);
+                    }),
+                ),
+                item(
+                    |app| format!("高音: {:+} dB", app.eq_treble_db),
+                    adjust(|app, dir| {
+                        app.eq_treble_db = adjust_eq(app.eq_treble_db, dir);
+                        app.settings_status = format!("高音: {:+} dB", app.eq_treble_db);
+                    }),
+                ),
+                item(
+                    |app| format!("Seek 步长 (Ctrl+←/→): {}s", app.seek_step_small_ms / 1000),
+                    adjust(|app, dir| {
+                        app.seek_step_small_ms = adjust_step(
+                            app.seek_step_small_ms,
+                            dir,
+                            1_000,
+                            SEEK_SMALL_MIN_MS,
+                            SEEK_SMALL_MAX_MS,
+                        );
+                        app.settings_status =
+                            format!("Seek 步长: {}s", app.seek_step_small_ms / 1000);
+                    }),
+                ),
+                item(
+                    |app| {
+                        format!(
+                            "长 Seek 步长 (歌词页 PgUp/PgDn): {}s",
+                            app.seek_step_large_ms / 1000
+                        )
+                    },
+                    adjust(|app, dir| {
+                        app.seek_step_large_ms = adjust_step(
+                            app.seek_step_large_ms,
+                            dir,
+                            5_000,
+                            SEEK_LARGE_MIN_MS,
+                            SEEK_LARGE_MAX_MS,
+                        );
+                        app.settings_status =
+                            format!("长 Seek 步长: {}s", app.seek_step_large_ms / 1000);
+                    }),
+                ),
+                item(
+                    |app| format!("音频焦点策略: {}", app.audio_focus_policy.label()),
+                    adjust(|app, dir| {
+                        app.audio_focus_policy = app.audio_focus_policy.cycle(dir);
+                        // 不再降低音量时立即恢复
+                        if app.audio_focus_policy != AudioFocusPolicy::Duck
+                            && app.audio_focus == AudioFocus::Ducked
+                        {
+                            app.audio_focus = AudioFocus::Held;
+                        }
+                        app.settings_status = if app.audio_focus_policy != AudioFocusPolicy::Ignore
+                            && !cfg!(feature = "audio-focus")
+                        {
+                            format!(
+                                "音频焦点策略: {}（当前构建未启用 audio-focus 特性，不会生效）",
+                                app.audio_focus_policy.label()
+                            )
+                        } else {
+                            format!("音频焦点策略: {}", app.audio_focus_policy.label())
+                        };
+                    }),
+                ),
+                item(
+                    |app| format!("焦点降低音量: {}%", app.audio_focus_duck_percent),
+                    adjust(|app, dir| {
+                        app.audio_focus_duck_percent = if dir > 0 {
+                            app.audio_focus_duck_percent
+                                .saturating_add(DUCK_PERCENT_STEP)
+                        } else {
+                            app.audio_focus_duck_percent
+                                .saturating_sub(DUCK_PERCENT_STEP)
+                        }
+                        .clamp(DUCK_PERCENT_MIN, DUCK_PERCENT_MAX);
+                        app.settings_status =
+                            format!("焦点降低音量: {}%", app.audio_focus_duck_percent);
+                    }),
+                ),
+                item(
+                    |app| format!("切歌时桌面通知: {}", on_off(app.notifications_enabled)),
+                    toggle(|app| {
+                        app.notifications_enabled = !app.notifications_enabled;
+                        app.settings_status = if app.notifications_enabled
+                            && !cfg!(feature = "notifications")
+                        {
+                            "切歌时桌面通知: 开启（当前构建未启用 notifications 特性，不会显示）"
+                                .to_owned()
+                        } else {
+                            format!("切歌时桌面通知: {}", on_off(app.notifications_enabled))
+                        };
+                    }),
+                ),
+            ],
+        },
+        SettingsGroupDef {
+            title: "歌词",
+            items: vec![
+                item(
+                    |app| format!("歌词 offset: {}", fmt_offset(app.lyrics_offset_ms)),
+                    adjust(|app, dir| {
+                        app.lyrics_offset_ms =
+                            app.lyrics_offset_ms
+                                .saturating_add(if dir > 0 { 200 } else { -200 });
+                        app.settings_status = format!("歌词 offset: {}ms", app.lyrics_offset_ms);
+                    }),
+                ),
+                item(
+                    |app| format!("状态栏歌词: {}", on_off(app.status_lyrics)),
+                    toggle(|app| {
+                        app.status_lyrics = !app.status_lyrics;
+                        app.settings_status = format!("状态栏歌词: {}", on_off(app.status_lyrics));
+                    }),
+                ),
+            ],
+        },
+        SettingsGroupDef {
+            title: "缓存",
+            items: vec![
+                item(
+                    |app| {
+                        if app.crossfade_ms == 0 {
+                            "淡入淡出: 关闭".to_owned()
+                        } else {
+                            format!("淡入淡出: {}ms", app.crossfade_ms)
+                        }
+                    },
+                    adjust(|app, dir| {
+                        let step = if dir > 0 { 50 } else { -50 };
+                        app.crossfade_ms = (app.crossfade_ms as i64 + step).clamp(0, 2000) as u64;
+                        app.settings_status = if app.crossfade_ms == 0 {
+                            "淡入淡出已关闭".to_owned()
+                        } else {
+                            format!("淡入淡出: {}ms", app.crossfade_ms)
+                        };
+                    }),
+                ),
+                item(
+                    |app| {
+                        let mut notes = Vec::new();
+                        if let Some((files, bytes)) = app.cache_usage {
+                            notes.push(format!("已用 {}，{files} 个文件", format_bytes(bytes)));
+                        }
+                        if app.cache_evicted_files > 0 {
+                            notes.push(format!(
+                                "超出上限已自动清理 {} 个文件，{} MB",
+                                app.cache_evicted_files,
+                                app.cache_evicted_bytes / 1024 / 1024
+                            ));
+                        }
+                        if notes.is_empty() {
+                            "清除音频缓存".to_owned()
+                        } else {
+                            format!("清除音频缓存（{}）", notes.join("；"))
+                        }
+                    },
+                    SettingKind::Action {
+                        run: |app, effects| {
+                            app.settings_status = "正在清除音频缓存...".to_owned();
+                            tracing::info!("用户触发：清除音频缓存");
+                            effects.send_audio_warn(
+                                AudioCommand::ClearCache,
+                                "AudioWorker 通道已关闭：ClearCache 发送失败",
+                            );
+                            ActionOutcome::Done
+                        },
+                    },
+                ),
+                item(
+                    |app| match &app.audio_cache_previous_dir {
+                        None => "移动现有缓存（缓存在默认位置，无需移动）".to_owned(),
+                        Some(_) if app.tasks.running_id(TaskKind::CacheMigration).is_some() => {
+                            "移动现有缓存（进行中，可在任务面板取消）".to_owned()
+                        }
+                        Some(_) => format!("移动现有缓存到 {}", app.audio_cache_dir.display()),
+                    },
+                    SettingKind::Action {
+                        run: |app, effects| {
+                            let Some(from) = app.audio_cache_previous_dir.clone() else {
+                                app.settings_status = "缓存目录未改动，无需移动".to_owned();
+                                return ActionOutcome::Done;
+                            };
+                            if app.tasks.running_id(TaskKind::CacheMigration).is_some() {
+                                app.settings_status = "正在移动缓存...".to_owned();
+                                return ActionOutcome::Done;
+                            }
+                            app.tasks
+                                .register(TaskKind::CacheMigration, from.display().to_string());
+                            app.settings_status = format!(
+                                "正在把 {} 中的缓存移到 {}...",
+                                from.display(),
+                                app.audio_cache_dir.display()
+                            );
+                            effects.send_audio_warn(
+                                AudioCommand::MigrateCache { from },
+                                "AudioWorker 通道已关闭：MigrateCache 发送失败",
+                            );
+                            ActionOutcome::Done
+                        },
+                    },
+                ),
+            ],
+        },
+        SettingsGroupDef {
+            title: "启动",
+            items: vec![
+                item(
+                    |app| format!("启动页面: {}", startup_view_label(app.startup_view)),
+                    adjust(|app, dir| {
+                        app.startup_view = cycle_startup_view(app.startup_view, dir);
+                        app.settings_status =
+                            format!("启动页面: {}", startup_view_label(app.startup_view));
+                    }),
+                ),
+                item(
+                    |app| format!("启动时恢复队列: {}", on_off(app.restore_queue_on_startup)),
+                    toggle(|app| {
+                        app.restore_queue_on_startup = !app.restore_queue_on_startup;
+                        app.settings_status =
+                            format!("启动时恢复队列: {}", on_off(app.restore_queue_on_startup));
+                    }),
+                ),
+                item(
+                    |app| format!("启动后继续播放: {}", on_off(app.resume_on_start)),
+                    toggle(|app| {
+                        app.resume_on_start = !app.resume_on_start;
+                        app.settings_status =
+                            format!("启动后继续播放: {}", on_off(app.resume_on_start));
+                    }),
+                ),
+            ],
+        },
+        SettingsGroupDef {
+            title: "账号",
+            items: vec![item(
+                |app| {
+                    if app.logged_in {
+                        match app.login_expires_at {
+                            Some(at) => {
+                                format!("退出登录（登录有效期至 {}）", login_expiry_date(at))
+                            }
+                            None => "退出登录".to_owned(),
+                        }
+                    } else {
+                        "退出登录（未登录）".to_owned()
+                    }
+                },
+                SettingKind::Action {
+                    run: |app, _| {
+                        if app.logged_in {
+                            ActionOutcome::Logout
+                        } else {
+                            app.settings_status = "未登录，无需退出".to_owned();
+                            ActionOutcome::Done
+                        }
+                    },
+                },
+            )],
+        },
+        SettingsGroupDef {
+            title: "隐私",
+            items: vec![
+                session_item(
+                    |app| format!("专注模式 (Ctrl+F): {}", on_off(app.focus_mode)),
+                    toggle(|app| {
+                        app.toggle_focus_mode();
+                        app.settings_status =
+                            format!("专注模式: {}（仅本次运行有效）", on_off(app.focus_mode));
+                    }),
+                ),
+                session_item(
+                    |app| format!("专注模式隐藏曲名: {}", on_off(app.focus_mask_titles)),
+                    toggle(|app| {
+                        app.focus_mask_titles = !app.focus_mask_titles;
+                        app.settings_status =
+                            format!("专注模式隐藏曲名: {}", on_off(app.focus_mask_titles));
+                    }),
+                ),
+            ],
+        },
+        SettingsGroupDef {
+            title: "显示",
+            items: vec![
+                item(
+                    |app| format!("歌曲名语言: {}", song_name_mode_label(app.song_name_mode)),
+                    adjust(|app, dir| {
+                        app.song_name_mode = cycle_song_name_mode(app.song_name_mode, dir);
+                        refresh_now_playing_title(app);
+                        app.settings_status =
+                            format!("歌曲名语言: {}", song_name_mode_label(app.song_name_mode));
+                    }),
+                ),
+                item(
+                    |app| format!("单色显示: {}", on_off(app.no_color)),
+                    toggle(|app| {
+                        app.no_color = !app.no_color;
+                        app.settings_status = format!("单色显示: {}", on_off(app.no_color));
+                    }),
+                ),
+            ],
+        },
+        SettingsGroupDef {
+            title: "数据",
+            items: vec![item(
+                |_| "导出全部歌单为 CSV（仅已加载的歌单）".to_owned(),
+                SettingKind::Action {
+                    run: |_, _| ActionOutcome::ExportPlaylists,
+                },
+            )],
+        },
+    ]
+}
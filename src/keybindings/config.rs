@@ -26,16 +26,25 @@ pub struct KeyBindingFile {
     /// Value = single key string or array of key strings
     #[serde(default)]
     pub bindings: HashMap<String, toml::Value>,
+
+    /// Global hotkey overrides (`[global]` table), same format as `bindings`.
+    /// Only used when the `global_hotkeys` setting is enabled.
+    #[serde(default)]
+    pub global: HashMap<String, toml::Value>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+pub fn keybindings_path(data_dir: &Path) -> std::path::PathBuf {
+    data_dir.join("keybindings.toml")
+}
+
 /// Load keybindings from a `keybindings.toml` file in the data directory.
 /// Falls back to default bindings if the file is missing or malformed.
 pub fn load_keybindings(data_dir: &Path) -> super::resolver::KeyBindings {
-    let path = data_dir.join("keybindings.toml");
+    let path = keybindings_path(data_dir);
     let contents = match std::fs::read_to_string(&path) {
         Ok(c) => c,
         Err(_) => {
@@ -113,7 +122,7 @@ pub(crate) fn build_keybindings(config: KeyBindingFile) -> super::resolver::KeyB
 
 /// Parse a TOML value into a list of key strings.
 /// Accepts: string, array of strings.
-fn parse_key_values(value: &toml::Value) -> Option<Vec<String>> {
+pub(crate) fn parse_key_values(value: &toml::Value) -> Option<Vec<String>> {
     match value {
         toml::Value::String(s) => Some(vec![s.clone()]),
         toml::Value::Array(arr) => {
@@ -199,6 +208,14 @@ mod tests {
             bindings.resolve(KeyCode::Char('M')),
             Some(KeyAction::PlayerCycleMode)
         );
+        assert_eq!(
+            bindings.resolve(KeyCode::Char('f')),
+            Some(KeyAction::PlayerToggleLike)
+        );
+        assert_eq!(
+            bindings.resolve(KeyCode::Char('>')),
+            Some(KeyAction::PlayerSpeedUp)
+        );
     }
 
     /// VAL-KEYBIND-002: 覆盖单个快捷键
@@ -0,0 +1,193 @@
+//! Global hotkeys: key combinations that work even when the terminal is not focused.
+//!
+//! By default only the hardware media keys are registered. The `[global]` table in
+//! `keybindings.toml` overrides them per action:
+//!
+//! ```toml
+//! [global]
+//! PlayerTogglePause = ["MediaPlayPause", "Ctrl+Alt+KeyP"]
+//! PlayerStop = ""  # empty string unbinds
+//! ```
+//!
+//! Accelerator strings use the `global-hotkey` crate syntax and are parsed by the
+//! platform listener; this module only owns the action mapping, so it is compiled
+//! (and tested) without the `global-hotkeys` feature.
+
+use super::config::{keybindings_path, parse_config, parse_key_values};
+use super::resolver::{KeyAction, action_from_str};
+use crate::messages::app::AppCommand;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A hotkey to register: accelerator string → action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlobalHotkeySpec {
+    pub accelerator: String,
+    pub action: KeyAction,
+}
+
+impl GlobalHotkeySpec {
+    fn new(accelerator: &str, action: KeyAction) -> Self {
+        Self {
+            accelerator: accelerator.to_owned(),
+            action,
+        }
+    }
+}
+
+/// Default media key bindings.
+pub fn default_global_hotkeys() -> Vec<GlobalHotkeySpec> {
+    vec![
+        GlobalHotkeySpec::new("MediaPlayPause", KeyAction::PlayerTogglePause),
+        GlobalHotkeySpec::new("MediaTrackNext", KeyAction::PlayerNext),
+        GlobalHotkeySpec::new("MediaTrackPrevious", KeyAction::PlayerPrev),
+        GlobalHotkeySpec::new("MediaStop", KeyAction::PlayerStop),
+    ]
+}
+
+/// Apply the `[global]` table on top of the defaults.
+/// Like `[bindings]`, a listed action replaces its defaults and `""` unbinds it.
+pub(crate) fn build_global_hotkeys(table: &HashMap<String, toml::Value>) -> Vec<GlobalHotkeySpec> {
+    let mut specs = default_global_hotkeys();
+    let mut names: Vec<&String> = table.keys().collect();
+    names.sort();
+    for name in names {
+        let Some(action) = action_from_str(name) else {
+            tracing::warn!("keybindings.toml [global]: 未知操作 '{name}'，跳过");
+            continue;
+        };
+        if !action.allowed_globally() {
+            tracing::warn!("keybindings.toml [global]: 操作 '{name}' 不能作为全局快捷键，跳过");
+            continue;
+        }
+        let Some(keys) = parse_key_values(&table[name]) else {
+            tracing::warn!(
+                "keybindings.toml [global]: 操作 '{name}' 的值格式无效，期望字符串或字符串数组"
+            );
+            continue;
+        };
+        specs.retain(|s| s.action != action);
+        specs.extend(
+            keys.iter()
+                .filter(|k| !k.is_empty())
+                .map(|k| GlobalHotkeySpec::new(k, action)),
+        );
+    }
+    specs
+}
+
+/// Load global hotkeys from `keybindings.toml`, falling back to the media key defaults.
+pub fn load_global_hotkeys(data_dir: &Path) -> Vec<GlobalHotkeySpec> {
+    let Ok(contents) = std::fs::read_to_string(keybindings_path(data_dir)) else {
+        return default_global_hotkeys();
+    };
+    match parse_config(&contents) {
+        Ok(config) => build_global_hotkeys(&config.global),
+        Err(_) => default_global_hotkeys(),
+    }
+}
+
+/// Successfully registered hotkeys: platform hotkey id → action.
+/// Only the listener uses it at runtime, hence unused without the feature.
+#[cfg_attr(not(feature = "global-hotkeys"), allow(dead_code))]
+#[derive(Debug, Default)]
+pub struct GlobalHotkeyMap {
+    map: HashMap<u32, KeyAction>,
+}
+
+#[cfg_attr(not(feature = "global-hotkeys"), allow(dead_code))]
+impl GlobalHotkeyMap {
+    /// Returns false if the id is already taken (the first registration wins).
+    pub fn insert(&mut self, id: u32, action: KeyAction) -> bool {
+        if self.map.contains_key(&id) {
+            return false;
+        }
+        self.map.insert(id, action);
+        true
+    }
+
+    pub fn command(&self, id: u32) -> Option<AppCommand> {
+        self.map.get(&id).map(|a| a.command())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(toml_src: &str) -> HashMap<String, toml::Value> {
+        parse_config(toml_src).expect("toml").global
+    }
+
+    #[test]
+    fn defaults_cover_media_keys() {
+        let specs = build_global_hotkeys(&HashMap::new());
+        assert_eq!(specs, default_global_hotkeys());
+        assert!(specs.iter().all(|s| s.action.allowed_globally()));
+    }
+
+    #[test]
+    fn global_table_overrides_unbinds_and_rejects_non_player_actions() {
+        let specs = build_global_hotkeys(&table(
+            r#"
+[global]
+PlayerTogglePause = ["MediaPlayPause", "Ctrl+Alt+KeyP"]
+PlayerStop = ""
+PlayerToggleLike = "Ctrl+Alt+KeyL"
+Quit = "Ctrl+Alt+KeyQ"
+Bogus = "F13"
+"#,
+        ));
+        let pairs: Vec<(&str, KeyAction)> = specs
+            .iter()
+            .map(|s| (s.accelerator.as_str(), s.action))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("MediaTrackNext", KeyAction::PlayerNext),
+                ("MediaTrackPrevious", KeyAction::PlayerPrev),
+                ("Ctrl+Alt+KeyL", KeyAction::PlayerToggleLike),
+                ("MediaPlayPause", KeyAction::PlayerTogglePause),
+                ("Ctrl+Alt+KeyP", KeyAction::PlayerTogglePause),
+            ]
+        );
+    }
+
+    #[test]
+    fn hotkey_ids_map_to_shared_commands() {
+        let mut map = GlobalHotkeyMap::default();
+        assert!(map.insert(7, KeyAction::PlayerTogglePause));
+        assert!(map.insert(8, KeyAction::PlayerNext));
+        assert!(
+            !map.insert(7, KeyAction::PlayerStop),
+            "重复 id 保留先注册者"
+        );
+        assert!(matches!(
+            map.command(7),
+            Some(AppCommand::PlayerTogglePause)
+        ));
+        assert!(matches!(map.command(8), Some(AppCommand::PlayerNext)));
+        assert!(!matches!(map.command(7), Some(AppCommand::PlayerStop)));
+        assert!(map.command(9).is_none());
+    }
+
+    #[test]
+    fn load_falls_back_to_defaults() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert_eq!(load_global_hotkeys(dir.path()), default_global_hotkeys());
+        std::fs::write(keybindings_path(dir.path()), "not = [valid").expect("write");
+        assert_eq!(load_global_hotkeys(dir.path()), default_global_hotkeys());
+        std::fs::write(
+            keybindings_path(dir.path()),
+            "[global]\nPlayerNext = \"Ctrl+Alt+ArrowRight\"\n",
+        )
+        .expect("write");
+        assert!(
+            load_global_hotkeys(dir.path()).contains(&GlobalHotkeySpec::new(
+                "Ctrl+Alt+ArrowRight",
+                KeyAction::PlayerNext
+            ))
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Platform listener for global hotkeys (`global-hotkeys` feature).
+//!
+//! A dedicated thread owns the `GlobalHotKeyManager`, registers the configured
+//! hotkeys and forwards presses to the app actor as [`AppCommand`]s. Dropping the
+//! returned [`GlobalHotkeyListener`] unregisters everything and joins the thread.
+//!
+//! Only Linux/X11 is supported for now: on Windows and macOS the manager needs a
+//! native event loop on the thread that created it, which the TUI does not run.
+
+use super::global::{GlobalHotkeyMap, GlobalHotkeySpec};
+use crate::messages::app::AppCommand;
+use global_hotkey::hotkey::HotKey;
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+pub struct GlobalHotkeyListener {
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for GlobalHotkeyListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Start listening; returns None (after logging a warning) when nothing could be registered.
+pub fn start_global_hotkeys(
+    specs: Vec<GlobalHotkeySpec>,
+    tx: mpsc::Sender<AppCommand>,
+) -> Option<GlobalHotkeyListener> {
+    if !cfg!(target_os = "linux") {
+        tracing::warn!("全局快捷键目前仅支持 Linux (X11)，已跳过");
+        return None;
+    }
+    if specs.is_empty() {
+        tracing::info!("未配置全局快捷键");
+        return None;
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel::<bool>();
+    let thread_stop = stop.clone();
+    let handle = std::thread::Builder::new()
+        .name("global-hotkeys".to_owned())
+        .spawn(move || run(specs, tx, thread_stop, ready_tx))
+        .map_err(|e| tracing::warn!("全局快捷键线程启动失败: {e}"))
+        .ok()?;
+
+    let listener = GlobalHotkeyListener {
+        stop,
+        handle: Some(handle),
+    };
+    // 注册失败时线程已自行退出，drop 只负责 join
+    ready_rx.recv().unwrap_or(false).then_some(listener)
+}
+
+fn run(
+    specs: Vec<GlobalHotkeySpec>,
+    tx: mpsc::Sender<AppCommand>,
+    stop: Arc<AtomicBool>,
+    ready: std::sync::mpsc::Sender<bool>,
+) {
+    let manager = match GlobalHotKeyManager::new() {
+        Ok(m) => m,
+        Err(e) => {
+            tracing::warn!("全局快捷键不可用: {e}");
+            let _ = ready.send(false);
+            return;
+        }
+    };
+
+    let mut map = GlobalHotkeyMap::default();
+    let mut registered = Vec::new();
+    for spec in specs {
+        let hotkey: HotKey = match spec.accelerator.parse() {
+            Ok(h) => h,
+            Err(e) => {
+                tracing::warn!("全局快捷键 '{}' 无法解析: {e}", spec.accelerator);
+                continue;
+            }
+        };
+        if !map.insert(hotkey.id(), spec.action) {
+            tracing::warn!("全局快捷键 '{}' 重复绑定，跳过", spec.accelerator);
+            continue;
+        }
+        match manager.register(hotkey) {
+            Ok(()) => registered.push(hotkey),
+            Err(e) => tracing::warn!("全局快捷键 '{}' 注册失败: {e}", spec.accelerator),
+        }
+    }
+    if registered.is_empty() {
+        let _ = ready.send(false);
+        return;
+    }
+    tracing::info!(count = registered.len(), "全局快捷键已注册");
+    let _ = ready.send(true);
+
+    let receiver = GlobalHotKeyEvent::receiver();
+    while !stop.load(Ordering::Relaxed) {
+        let Ok(event) = receiver.recv_timeout(POLL_INTERVAL) else {
+            continue;
+        };
+        if event.state() != HotKeyState::Pressed {
+            continue;
+        }
+        let Some(cmd) = map.command(event.id()) else {
+            continue;
+        };
+        // 不阻塞发送：退出时 actor 在 join 本线程，阻塞可能死锁
+        match tx.try_send(cmd) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                tracing::warn!("命令队列已满，丢弃全局快捷键事件");
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => break,
+        }
+    }
+
+    if let Err(e) = manager.unregister_all(&registered) {
+        tracing::warn!("注销全局快捷键失败: {e}");
+    }
+}
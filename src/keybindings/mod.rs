@@ -1,5 +1,21 @@
 pub mod config;
+pub mod global;
+#[cfg(feature = "global-hotkeys")]
+pub mod listener;
 pub mod resolver;
 
 pub use config::load_keybindings;
+pub use global::load_global_hotkeys;
+#[cfg(feature = "global-hotkeys")]
+pub use listener::start_global_hotkeys;
 pub use resolver::{KeyAction, KeyBindings, SharedKeyBindings};
+
+/// Builds without the `global-hotkeys` feature cannot listen; the setting only logs a warning.
+#[cfg(not(feature = "global-hotkeys"))]
+pub fn start_global_hotkeys(
+    _specs: Vec<global::GlobalHotkeySpec>,
+    _tx: tokio::sync::mpsc::Sender<crate::messages::app::AppCommand>,
+) -> Option<()> {
+    tracing::warn!("已开启 global_hotkeys，但当前构建未启用 global-hotkeys 特性，已忽略");
+    None
+}
@@ -1,5 +1,6 @@
 //! Key-action resolution: maps KeyCode → KeyAction using a HashMap.
 
+use crate::messages::app::AppCommand;
 use crossterm::event::KeyCode;
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -17,6 +18,61 @@ pub enum KeyAction {
     PlayerNext,
     PlayerCycleMode,
     PlayerStop,
+    PlayerToggleLike,
+    PlayerToggleMute,
+    PlayerSpeedDown,
+    PlayerSpeedUp,
+    PlayerSpeedReset,
+    PlayerToggleStopAfterCurrent,
+    PlayerSetLoopPointA,
+    PlayerSetLoopPointB,
+    PlayerClearLoop,
+}
+
+impl KeyAction {
+    /// The command this action dispatches (shared by terminal keys and global hotkeys).
+    pub fn command(self) -> AppCommand {
+        match self {
+            Self::Quit => AppCommand::Quit,
+            Self::UiToggleHelp => AppCommand::UiToggleHelp,
+            Self::MenuOpen => AppCommand::MenuOpen,
+            Self::PlayerTogglePause => AppCommand::PlayerTogglePause,
+            Self::PlayerPrev => AppCommand::PlayerPrev,
+            Self::PlayerNext => AppCommand::PlayerNext,
+            Self::PlayerCycleMode => AppCommand::PlayerCycleMode,
+            Self::PlayerStop => AppCommand::PlayerStop,
+            Self::PlayerToggleLike => AppCommand::PlayerToggleLike,
+            Self::PlayerToggleMute => AppCommand::PlayerToggleMute,
+            Self::PlayerSpeedDown => AppCommand::PlayerSpeedDown,
+            Self::PlayerSpeedUp => AppCommand::PlayerSpeedUp,
+            Self::PlayerSpeedReset => AppCommand::PlayerSpeedReset,
+            Self::PlayerToggleStopAfterCurrent => AppCommand::PlayerToggleStopAfterCurrent,
+            Self::PlayerSetLoopPointA => AppCommand::PlayerSetLoopPointA,
+            Self::PlayerSetLoopPointB => AppCommand::PlayerSetLoopPointB,
+            Self::PlayerClearLoop => AppCommand::PlayerClearLoop,
+        }
+    }
+
+    /// Whether the key should be typed as text instead while a text input is focused.
+    pub fn yields_to_text_input(self) -> bool {
+        matches!(
+            self,
+            Self::PlayerToggleLike
+                | Self::PlayerCycleMode
+                | Self::PlayerToggleStopAfterCurrent
+                | Self::PlayerSpeedDown
+                | Self::PlayerSpeedUp
+                | Self::PlayerSpeedReset
+                | Self::PlayerSetLoopPointA
+                | Self::PlayerSetLoopPointB
+                | Self::PlayerClearLoop
+        )
+    }
+
+    /// Whether the action may be bound as a global hotkey (playback controls only).
+    pub fn allowed_globally(self) -> bool {
+        !matches!(self, Self::Quit | Self::UiToggleHelp | Self::MenuOpen)
+    }
 }
 
 /// Parse an action name string into a KeyAction.
@@ -31,6 +87,15 @@ pub fn action_from_str(s: &str) -> Option<KeyAction> {
         "PlayerNext" => Some(KeyAction::PlayerNext),
         "PlayerCycleMode" => Some(KeyAction::PlayerCycleMode),
         "PlayerStop" => Some(KeyAction::PlayerStop),
+        "PlayerToggleLike" => Some(KeyAction::PlayerToggleLike),
+        "PlayerToggleMute" => Some(KeyAction::PlayerToggleMute),
+        "PlayerSpeedDown" => Some(KeyAction::PlayerSpeedDown),
+        "PlayerSpeedUp" => Some(KeyAction::PlayerSpeedUp),
+        "PlayerSpeedReset" => Some(KeyAction::PlayerSpeedReset),
+        "PlayerToggleStopAfterCurrent" => Some(KeyAction::PlayerToggleStopAfterCurrent),
+        "PlayerSetLoopPointA" => Some(KeyAction::PlayerSetLoopPointA),
+        "PlayerSetLoopPointB" => Some(KeyAction::PlayerSetLoopPointB),
+        "PlayerClearLoop" => Some(KeyAction::PlayerClearLoop),
         _ => None,
     }
 }
@@ -62,6 +127,14 @@ impl KeyBindings {
         map.insert(KeyCode::Char('['), KeyAction::PlayerPrev);
         map.insert(KeyCode::Char(']'), KeyAction::PlayerNext);
         map.insert(KeyCode::Char('M'), KeyAction::PlayerCycleMode);
+        map.insert(KeyCode::Char('f'), KeyAction::PlayerToggleLike);
+        map.insert(KeyCode::Char('<'), KeyAction::PlayerSpeedDown);
+        map.insert(KeyCode::Char('>'), KeyAction::PlayerSpeedUp);
+        map.insert(KeyCode::Char('='), KeyAction::PlayerSpeedReset);
+        map.insert(KeyCode::Char('X'), KeyAction::PlayerToggleStopAfterCurrent);
+        map.insert(KeyCode::Char(','), KeyAction::PlayerSetLoopPointA);
+        map.insert(KeyCode::Char('.'), KeyAction::PlayerSetLoopPointB);
+        map.insert(KeyCode::Char(';'), KeyAction::PlayerClearLoop);
 
         Self { map }
     }
@@ -1,13 +1,21 @@
 pub mod app;
+pub mod audio_focus;
 pub mod audio_worker;
+pub mod cooldown;
 pub mod core;
+pub mod crash;
+pub mod data_paths;
 pub mod domain;
 pub mod error;
 pub mod features;
 pub mod keybindings;
 pub mod logging;
 pub mod messages;
+pub mod mpris;
 pub mod netease;
+pub mod notifications;
+pub mod play_history;
 pub mod player_state;
+pub mod reset;
 pub mod settings;
 pub mod ui;
@@ -1,16 +1,77 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{EnvFilter, fmt};
 
-pub struct LogGuard(#[allow(dead_code)] Option<WorkerGuard>);
+pub struct LogGuard {
+    _guard: Option<WorkerGuard>,
+    dir: PathBuf,
+}
+
+impl LogGuard {
+    /// 实际写入日志的目录（无法创建配置的目录时为临时目录）
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+/// 日志文件名；按天/小时滚动时追加日期后缀（`netease-ratui.log.2024-01-31`）
+pub const LOG_FILE_NAME: &str = "netease-ratui.log";
+
+/// 默认保留的日志文件数
+pub const DEFAULT_LOG_MAX_FILES: usize = 14;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogRotation {
+    #[default]
+    Daily,
+    Hourly,
+    /// 不滚动，始终写入同一个文件
+    Never,
+}
+
+impl LogRotation {
+    /// 解析设置中的 `log_rotation`（`daily` / `hourly` / `never`）
+    pub fn from_setting(s: &str) -> Self {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "hourly" => Self::Hourly,
+            "never" => Self::Never,
+            // 未知值按默认的每日滚动处理
+            _ => Self::Daily,
+        }
+    }
 
-#[derive(Debug, Clone, Default)]
+    fn to_appender(self) -> Rotation {
+        match self {
+            Self::Daily => Rotation::DAILY,
+            Self::Hourly => Rotation::HOURLY,
+            Self::Never => Rotation::NEVER,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct LogConfig {
     pub dir: Option<PathBuf>,
     pub filter: Option<String>,
+    pub rotation: LogRotation,
+    /// 最多保留的日志文件数，0 表示不清理
+    pub max_files: usize,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            filter: None,
+            rotation: LogRotation::default(),
+            max_files: DEFAULT_LOG_MAX_FILES,
+        }
+    }
 }
 
 pub fn init(data_dir: &Path, cfg: LogConfig) -> LogGuard {
@@ -22,7 +83,16 @@ pub fn init(data_dir: &Path, cfg: LogConfig) -> LogGuard {
     };
     let _ = fs::create_dir_all(&log_dir);
 
-    let file_appender = tracing_appender::rolling::daily(&log_dir, "netease-ratui.log");
+    let mut builder = RollingFileAppender::builder()
+        .rotation(cfg.rotation.to_appender())
+        .filename_prefix(LOG_FILE_NAME);
+    if cfg.max_files > 0 {
+        // 运行期间跨天滚动时由 tracing-appender 清理；启动时的清理见 cleanup_old_logs
+        builder = builder.max_log_files(cfg.max_files);
+    }
+    let file_appender = builder
+        .build(&log_dir)
+        .unwrap_or_else(|_| tracing_appender::rolling::daily(&log_dir, LOG_FILE_NAME));
     let (file_writer, guard) = tracing_appender::non_blocking(file_appender);
 
     let filter = match cfg.filter {
@@ -41,5 +111,158 @@ pub fn init(data_dir: &Path, cfg: LogConfig) -> LogGuard {
     let _ = subscriber.try_init();
     tracing::info!(log_dir = %log_dir.display(), "tracing 已初始化");
 
-    LogGuard(Some(guard))
+    // 当前日志文件已由 appender 创建，按修改时间它总是最新的一个
+    match cleanup_old_logs(&log_dir, cfg.max_files) {
+        Ok(0) => {}
+        Ok(n) => tracing::info!(removed = n, keep = cfg.max_files, "已清理旧日志"),
+        Err(e) => tracing::warn!(err = %e, "清理旧日志失败"),
+    }
+
+    LogGuard {
+        _guard: Some(guard),
+        dir: log_dir,
+    }
+}
+
+/// 是否为本应用写出的日志文件：`netease-ratui.log` 或 `netease-ratui.log.<日期>`
+fn is_app_log_file(name: &str) -> bool {
+    let Some(rest) = name.strip_prefix(LOG_FILE_NAME) else {
+        return false;
+    };
+    match rest.strip_prefix('.') {
+        None => rest.is_empty(),
+        Some(suffix) => {
+            !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit() || c == '-')
+        }
+    }
+}
+
+/// 删除日志目录中超出保留数量的旧日志（按修改时间保留最新的 `max_files` 个），返回删除数量
+///
+/// 只处理本应用的日志文件，目录中的其它文件不受影响；`max_files` 为 0 时不清理。
+pub fn cleanup_old_logs(log_dir: &Path, max_files: usize) -> std::io::Result<usize> {
+    if max_files == 0 {
+        return Ok(0);
+    }
+    let mut logs: Vec<(SystemTime, PathBuf)> = fs::read_dir(log_dir)?
+        .flatten()
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_file()))
+        .filter(|e| e.file_name().to_str().is_some_and(is_app_log_file))
+        .map(|e| {
+            let mtime = e
+                .metadata()
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (mtime, e.path())
+        })
+        .collect();
+    // 新的在前；修改时间相同时按文件名（日期）倒序
+    logs.sort_by(|a, b| b.cmp(a));
+
+    let mut removed = 0;
+    for (_, path) in logs.into_iter().skip(max_files) {
+        match fs::remove_file(&path) {
+            Ok(()) => removed += 1,
+            Err(e) => tracing::debug!(path = %path.display(), err = %e, "删除旧日志失败"),
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn touch(dir: &Path, name: &str, age_days: u64) {
+        let path = dir.join(name);
+        fs::write(&path, b"log").expect("write");
+        let mtime = SystemTime::now() - Duration::from_secs(age_days * 86_400);
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|f| f.set_modified(mtime))
+            .expect("set mtime");
+    }
+
+    fn names(dir: &Path) -> Vec<String> {
+        let mut v: Vec<String> = fs::read_dir(dir)
+            .expect("read_dir")
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        v.sort();
+        v
+    }
+
+    #[test]
+    fn only_app_log_files_match() {
+        assert!(is_app_log_file("netease-ratui.log"));
+        assert!(is_app_log_file("netease-ratui.log.2024-01-31"));
+        assert!(is_app_log_file("netease-ratui.log.2024-01-31-08"));
+        assert!(!is_app_log_file("netease-ratui.log."));
+        assert!(!is_app_log_file("netease-ratui.log.bak"));
+        assert!(!is_app_log_file("netease-ratui.logx"));
+        assert!(!is_app_log_file("other.log.2024-01-31"));
+        assert!(!is_app_log_file("player_state.json"));
+    }
+
+    #[test]
+    fn cleanup_keeps_newest_by_mtime_and_ignores_other_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        // 文件名日期与修改时间故意不一致：以修改时间为准
+        touch(dir.path(), "netease-ratui.log.2024-01-05", 1);
+        touch(dir.path(), "netease-ratui.log.2024-01-01", 2);
+        touch(dir.path(), "netease-ratui.log.2024-01-03", 3);
+        touch(dir.path(), "netease-ratui.log.2024-01-04", 4);
+        touch(dir.path(), "notes.txt", 30);
+        touch(dir.path(), "netease-ratui.log.bak", 30);
+        fs::create_dir(dir.path().join("netease-ratui.log.2023-12-01")).expect("mkdir");
+
+        let removed = cleanup_old_logs(dir.path(), 2).expect("cleanup");
+
+        assert_eq!(removed, 2);
+        assert_eq!(
+            names(dir.path()),
+            vec![
+                "netease-ratui.log.2023-12-01",
+                "netease-ratui.log.2024-01-01",
+                "netease-ratui.log.2024-01-05",
+                "netease-ratui.log.bak",
+                "notes.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn cleanup_boundary_and_disabled() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        for day in 1..=3 {
+            touch(
+                dir.path(),
+                &format!("netease-ratui.log.2024-01-0{day}"),
+                day,
+            );
+        }
+
+        // 恰好等于保留数：不删除
+        assert_eq!(cleanup_old_logs(dir.path(), 3).expect("cleanup"), 0);
+        // 0 表示不清理
+        assert_eq!(cleanup_old_logs(dir.path(), 0).expect("cleanup"), 0);
+        assert_eq!(names(dir.path()).len(), 3);
+
+        // 少一个：只删最旧的
+        assert_eq!(cleanup_old_logs(dir.path(), 2).expect("cleanup"), 1);
+        assert!(!dir.path().join("netease-ratui.log.2024-01-03").exists());
+
+        assert_eq!(cleanup_old_logs(dir.path(), 1).expect("cleanup"), 1);
+        assert_eq!(names(dir.path()), vec!["netease-ratui.log.2024-01-01"]);
+    }
+
+    #[test]
+    fn rotation_setting_parses_case_insensitively() {
+        assert_eq!(LogRotation::from_setting("Hourly"), LogRotation::Hourly);
+        assert_eq!(LogRotation::from_setting("never"), LogRotation::Never);
+        assert_eq!(LogRotation::from_setting("weekly"), LogRotation::Daily);
+    }
 }
@@ -1,20 +1,29 @@
 mod app;
+mod audio_focus;
 mod audio_worker;
+mod cooldown;
 mod core;
+mod crash;
+mod data_paths;
 mod domain;
 mod error;
 mod features;
 mod keybindings;
 mod logging;
 mod messages;
+mod mpris;
 mod netease;
+mod notifications;
+mod play_history;
 mod player_state;
+mod reset;
 mod settings;
 mod ui;
 
 use app::{App, AppSnapshot};
 use audio_worker::AudioBackend;
 use clap::Parser;
+use data_paths::DataPaths;
 use error::AppError;
 use netease::{NeteaseClient, NeteaseClientConfig};
 use std::env;
@@ -34,6 +43,25 @@ async fn main() -> Result<(), AppError> {
     if let Some(v) = cli.api_domain.clone() {
         cfg.api_domain = v;
     }
+    cfg.proxy = cli.proxy.clone().or_else(netease::proxy_from_env);
+    // 启动前校验，避免 actor 启动后才发现代理地址写错
+    if let Some(url) = cfg.proxy.as_deref() {
+        netease::parse_proxy(url)?;
+    }
+
+    if !cli.reset.is_empty() {
+        // 先读设置再重置：缓存目录可能由 settings.json 指定到别处
+        let settings = settings::load_settings(&cfg.data_dir);
+        let paths = DataPaths::new(&cfg.data_dir).with_audio_cache_dir(
+            data_paths::resolve_audio_cache_dir(
+                cli.cache_dir.as_deref(),
+                settings.audio_cache_dir.as_deref(),
+            ),
+        );
+        if !reset::run(&paths, &cli.reset, cli.yes)? {
+            return Ok(());
+        }
+    }
 
     let no_audio_env = env::var("NETEASE_NO_AUDIO")
         .ok()
@@ -45,14 +73,22 @@ async fn main() -> Result<(), AppError> {
         AudioBackend::Real
     };
 
-    let _log_guard = logging::init(
+    // https://no-color.org/：设置了非空的 NO_COLOR 即不输出颜色
+    let no_color = cli.no_color || env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+
+    // 日志初始化早于 actor，单独读取一次日志相关设置
+    let log_settings = settings::load_settings(&cfg.data_dir);
+    let log_guard = logging::init(
         &cfg.data_dir,
         logging::LogConfig {
             dir: cli.log_dir.clone(),
             filter: cli.log_filter.clone(),
+            rotation: logging::LogRotation::from_setting(&log_settings.log_rotation),
+            max_files: cli.log_retention.unwrap_or(log_settings.log_max_files),
         },
     );
     tracing::info!(data_dir = %cfg.data_dir.display(), "netease-ratui 启动");
+    crash::install(&cfg.data_dir);
 
     // 兼容旧环境变量（后续可考虑 deprecate）
     if cli.command.is_none() && env::var("NETEASE_SKIP_LOGIN").ok().as_deref() == Some("1") {
@@ -83,8 +119,15 @@ async fn main() -> Result<(), AppError> {
 
     match cli.command.unwrap_or(Command::Tui) {
         Command::Tui => {
-            let (tx, rx, app_actor) = core::spawn_app_actor(cfg, audio_backend);
-            run_tui(AppSnapshot::from_app(&App::default()), tx, rx).await?;
+            let (tx, rx, app_actor) =
+                core::spawn_app_actor(cfg, audio_backend, cli.cache_dir.clone());
+            if let Err(e) = run_tui(AppSnapshot::from_app(&App::default()), tx, rx, no_color).await
+            {
+                // 终端已由 TuiGuard 恢复，这里的输出不会被备用屏幕吞掉
+                eprintln!("netease-ratui 异常退出: {e}");
+                eprintln!("日志目录: {}", log_guard.dir().display());
+                return Err(e.into());
+            }
             app_actor
                 .await
                 .map_err(|e| AppError::Other(format!("App actor 退出失败: {e}")))?;
@@ -115,5 +158,17 @@ async fn main() -> Result<(), AppError> {
             );
             Ok(())
         }
+        Command::Stats { since, limit } => {
+            let window = play_history::parse_since(&since).map_err(AppError::Other)?;
+            let records = play_history::load_history(&cfg.data_dir)
+                .map_err(|e| AppError::Other(format!("读取播放历史失败: {e}")))?;
+            let since_epoch_ms = window.map(|w| {
+                let w = i64::try_from(w.as_millis()).unwrap_or(i64::MAX);
+                chrono::Utc::now().timestamp_millis().saturating_sub(w)
+            });
+            let summary = play_history::summarize(&records, since_epoch_ms, limit);
+            print!("{}", play_history::format_summary(&summary, &since));
+            Ok(())
+        }
     }
 }
@@ -14,16 +14,82 @@ pub enum AppCommand {
         focus: UiFocus,
     },
     UiToggleHelp,
+    /// 切换专注模式（仅本次运行有效）
+    UiToggleFocusMode,
+    /// 移动主体左/中栏分界线（百分比，正数向右）
+    UiPaneNudge {
+        delta: i16,
+    },
+    /// 终端尺寸变化（UI 上报宽度，用于判断右栏是否显示）
+    UiResized {
+        width: u16,
+    },
+    /// 切换「此队列禁用淡入淡出」
+    QueueToggleNoFade,
+    /// 队列面板：上下移动选中项（跳过折叠分组中的歌曲）
+    QueueMoveUp,
+    QueueMoveDown,
+    /// 队列面板：折叠 / 展开选中项所在的来源分组
+    QueueCollapse,
+    QueueExpand,
+    /// 队列面板：撤销最近一次播放队列的整体替换
+    QueueUndoReplace,
+    /// 队列页：把选中的歌曲在播放顺序中前移 / 后移一位
+    QueueReorderUp,
+    QueueReorderDown,
+    /// 队列页：从队列中移除选中的歌曲
+    QueueRemoveSelected,
+    /// 队列页：从选中的歌曲开始播放
+    QueuePlaySelected,
+    /// 搜索结果 / 歌单歌曲：把选中的歌曲插到当前歌曲之后播放
+    EnqueueNextSelected,
+    /// 搜索结果 / 歌单歌曲：把选中的歌曲追加到队尾
+    EnqueueLastSelected,
+    /// 恢复启动时搁置的旧播放队列
+    RestorePromptAccept,
+    /// 忽略启动时搁置的旧播放队列
+    RestorePromptDismiss,
+    /// 有下载进行中时按 q：打开退出确认弹窗
+    QuitConfirmOpen,
+    /// 退出确认弹窗：等待下载完成后退出
+    QuitConfirmWait,
+    /// 退出确认弹窗：取消退出
+    QuitConfirmCancel,
     LoginGenerateQr,
     LoginToggleCookieInput,
     LoginCookieInputChar {
         c: char,
     },
+    /// 一次性插入多个字符（粘贴 / 输入法上屏的连续字符）
+    LoginCookieInputString {
+        s: String,
+    },
     LoginCookieInputBackspace,
     LoginCookieSubmit,
+    /// 打开/关闭手机号登录表单
+    LoginTogglePhoneForm,
+    /// 手机号登录：在手机号与密码（验证码）输入框之间切换
+    LoginPhoneFieldNext,
+    /// 手机号登录：切换密码 / 短信验证码登录
+    LoginPhoneToggleCaptcha,
+    LoginPhoneInputChar {
+        c: char,
+    },
+    /// 一次性插入多个字符（粘贴 / 输入法上屏的连续字符）
+    LoginPhoneInputString {
+        s: String,
+    },
+    LoginPhoneInputBackspace,
+    /// 手机号登录：发送短信验证码
+    LoginPhoneSendCaptcha,
+    LoginPhoneSubmit,
     SearchInputChar {
         c: char,
     },
+    /// 一次性插入多个字符（粘贴 / 输入法上屏的连续字符）
+    SearchInputString {
+        s: String,
+    },
     SearchInputBackspace,
     SearchSubmit,
     SearchMoveUp,
@@ -35,6 +101,14 @@ pub enum AppCommand {
     SearchPageUp,
     SearchJumpTop,
     SearchJumpBottom,
+    /// 加载下一页搜索结果并追加到列表末尾
+    SearchLoadMore,
+    /// 切换搜索类型：歌曲 / 专辑 / 歌手
+    SearchCycleKind,
+    /// 打开选中的专辑（全部曲目）或歌手（热门歌曲）
+    SearchOpenSelected,
+    /// 从专辑/歌手的曲目返回上一级列表
+    SearchBack,
     SearchPlaySelected,
     PlaylistsMoveUp,
     PlaylistsMoveDown,
@@ -45,7 +119,13 @@ pub enum AppCommand {
     PlaylistsPageUp,
     PlaylistsJumpTop,
     PlaylistsJumpBottom,
+    /// 切换歌单归属筛选：全部 / 我的 / 收藏
+    PlaylistsCycleFilter,
+    /// 重新预加载歌单（预加载因接口错误暂停后由此恢复）
+    PlaylistsRetryPreload,
     PlaylistsOpenSelected,
+    /// 打开每日推荐（需登录），歌曲列表与播放队列同普通歌单
+    PlaylistsOpenRecommend,
     PlaylistTracksMoveUp,
     PlaylistTracksMoveDown,
     PlaylistTracksMoveTo {
@@ -55,12 +135,70 @@ pub enum AppCommand {
     PlaylistTracksPageUp,
     PlaylistTracksJumpTop,
     PlaylistTracksJumpBottom,
+    /// 选中正在播放的歌曲（在当前打开的歌单中时）
+    PlaylistTracksJumpToPlaying,
     PlaylistTracksPlaySelected,
+    /// 切换已打开歌单的显示顺序：原始顺序 / 最近添加优先
+    PlaylistTracksToggleOrder,
+    /// 循环切换已打开歌单的排序：原始顺序 → 按歌名 → 按歌手
+    PlaylistTracksCycleSort,
+    /// 从当前打开的（自己的）歌单中移除选中的歌曲
+    PlaylistTracksRemoveSelected,
+    /// 检查当前打开的（自己的）歌单中的不可用歌曲并查找可替换的版本
+    PlaylistTracksCheckUnavailable,
+    /// 歌单内搜索：打开输入框
+    PlaylistFilterOpen,
+    /// 歌单内搜索的关键词输入（即时筛选）
+    PlaylistFilterChar {
+        c: char,
+    },
+    PlaylistFilterString {
+        s: String,
+    },
+    PlaylistFilterBackspace,
+    /// 歌单内搜索：关闭输入框并保留筛选结果
+    PlaylistFilterSubmit,
+    /// 歌单内搜索：清除筛选
+    PlaylistFilterCancel,
+    /// 导出 CSV：`all` 为 false 时导出当前打开的歌单，为 true 时导出全部已加载的歌单
+    ExportCsv {
+        all: bool,
+    },
+    /// 标记/取消标记选中的搜索结果（用于批量添加到歌单）
+    SearchToggleMark,
+    /// 打开「添加到歌单」选择框，目标为选中的搜索结果
+    SearchAddSelectedToPlaylist,
+    /// 打开「添加到歌单」选择框，目标为全部已标记的歌曲
+    SearchAddMarkedToPlaylist,
+    /// 把选中的歌曲（搜索结果或歌单歌曲）下载到下载目录
+    DownloadSelected,
+    PlaylistPickerMoveUp,
+    PlaylistPickerMoveDown,
+    /// 选择框的筛选词输入
+    PlaylistPickerInputChar {
+        c: char,
+    },
+    PlaylistPickerInputString {
+        s: String,
+    },
+    PlaylistPickerInputBackspace,
+    PlaylistPickerConfirm,
+    PlaylistPickerCancel,
+    /// 不可用歌曲审阅框：移动选中项
+    UnavailableReviewMoveUp,
+    UnavailableReviewMoveDown,
+    /// 不可用歌曲审阅框：用找到的版本替换选中的歌曲
+    UnavailableReviewAccept,
+    /// 不可用歌曲审阅框：跳过选中的歌曲
+    UnavailableReviewSkip,
+    UnavailableReviewClose,
     Back,
     PlayerTogglePause,
     PlayerStop,
     PlayerPrev,
     PlayerNext,
+    /// 开启 / 退出私人 FM
+    FmToggle,
     PlayerSeekBackwardMs {
         ms: u64,
     },
@@ -72,11 +210,31 @@ pub enum AppCommand {
     },
     PlayerVolumeDown,
     PlayerVolumeUp,
+    /// 静音 / 取消静音（恢复静音前的音量）
+    PlayerToggleMute,
+    /// 设置音量（0.0～2.0），来自 MPRIS 等外部控制
+    PlayerSetVolume {
+        volume: f32,
+    },
+    /// 播放倍速 -0.05x / +0.05x / 恢复 1.0x
+    PlayerSpeedDown,
+    PlayerSpeedUp,
+    PlayerSpeedReset,
+    /// 当前歌曲播完后停止（再按一次取消）
+    PlayerToggleStopAfterCurrent,
+    /// A-B 循环：以当前播放位置设置 A / B 点，或取消循环
+    PlayerSetLoopPointA,
+    PlayerSetLoopPointB,
+    PlayerClearLoop,
     PlayerCycleMode,
+    /// 收藏/取消收藏当前播放歌曲
+    PlayerToggleLike,
     LyricsToggleFollow,
     LyricsMoveUp,
     LyricsMoveDown,
     LyricsGotoCurrent,
+    /// 循环切换歌词副行：原文 → 原文+翻译 → 原文+罗马音
+    LyricsCycleDisplay,
     LyricsOffsetAddMs {
         ms: i64,
     },
@@ -95,6 +253,36 @@ pub enum AppCommand {
     MenuSelect,
     MenuMoveUp,
     MenuMoveDown,
+    TasksToggle,
+    TasksMoveUp,
+    TasksMoveDown,
+    TasksCancelSelected,
+    NotificationsToggle,
+    NotificationsMoveUp,
+    NotificationsMoveDown,
+    /// 打开选中通知的详情面板
+    NotificationsOpenDetail,
+    NotificationsCloseDetail,
+    /// 详情面板滚动到第 offset 行（界面按当前宽度换行后算出并限制范围）
+    NotificationDetailScroll {
+        offset: usize,
+    },
+    /// 界面已把详情文本写入剪贴板（OSC 52）
+    NotificationDetailCopied,
+    /// 终端即将挂起（Ctrl+Z / SIGTSTP）：播放中时先暂停，界面等到暂停生效后再停止进程
+    TerminalSuspending,
+    /// 终端从挂起（Ctrl+Z / SIGTSTP）恢复；suspended_ms 为挂起时长
+    TerminalResumed {
+        suspended_ms: u64,
+    },
+    /// 终端失去（false）或重新获得（true）焦点；终端不支持焦点上报时不会发送
+    TerminalFocusChanged {
+        focused: bool,
+    },
+    /// 音频焦点监听：其它程序开始（true）或全部停止（false）播放声音
+    AudioFocusChanged {
+        others_playing: bool,
+    },
 }
 
 #[derive(Debug)]
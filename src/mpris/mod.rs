@@ -0,0 +1,389 @@
+//! MPRIS (D-Bus Media Player Remote Interfacing Specification) integration.
+//!
+//! With the `mpris` feature the player is exposed on the session bus as
+//! `org.mpris.MediaPlayer2.netease_ratui`, so desktop media keys, `playerctl` and
+//! shell media widgets can show and control playback. The server taps the
+//! `AppEvent::State` stream on its way to the UI and forwards incoming calls to the
+//! app actor as [`AppCommand`]s.
+//!
+//! This module owns the snapshot → MPRIS state mapping and the call → command
+//! mapping, so it is compiled (and tested) without the feature; only `server`
+//! talks D-Bus.
+
+#![cfg_attr(not(feature = "mpris"), allow(dead_code))]
+
+#[cfg(feature = "mpris")]
+mod server;
+
+#[cfg(feature = "mpris")]
+pub use server::start_mpris;
+
+use crate::app::{AppSnapshot, PlayerSnapshot};
+use crate::messages::app::AppCommand;
+
+/// 播放位置跳变超过该值（微秒）时视为 Seek，发出 `Seeked` 信号
+const SEEK_JUMP_US: i64 = 1_500_000;
+
+/// MPRIS `PlaybackStatus`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaybackStatus {
+    Playing,
+    Paused,
+    Stopped,
+}
+
+impl PlaybackStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Playing => "Playing",
+            Self::Paused => "Paused",
+            Self::Stopped => "Stopped",
+        }
+    }
+}
+
+/// 当前歌曲的元数据（`Metadata` 属性）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MprisTrack {
+    pub id: i64,
+    pub title: String,
+    pub artists: Vec<String>,
+    pub album: String,
+    pub length_us: Option<i64>,
+}
+
+impl MprisTrack {
+    /// `mpris:trackid`：每首歌一个对象路径，`SetPosition` 用它确认目标歌曲
+    pub fn object_path(&self) -> String {
+        track_object_path(self.id)
+    }
+}
+
+pub fn track_object_path(song_id: i64) -> String {
+    // 对象路径只允许 [A-Za-z0-9_]，负数 id（本地/私有歌曲）换成前缀
+    if song_id < 0 {
+        format!("/org/netease_ratui/track/n{}", song_id.unsigned_abs())
+    } else {
+        format!("/org/netease_ratui/track/{song_id}")
+    }
+}
+
+/// 对外公布的播放器状态，由每次 `AppEvent::State` 快照生成
+#[derive(Debug, Clone)]
+pub struct MprisState {
+    pub track: Option<MprisTrack>,
+    pub status: PlaybackStatus,
+    pub volume: f64,
+    pub rate: f64,
+    pub can_seek: bool,
+    /// 计时字段与 UI 相同，读取 `Position` 时按当前时间计算
+    player: PlayerSnapshot,
+}
+
+impl MprisState {
+    pub fn from_snapshot(app: &AppSnapshot) -> Self {
+        let player = &app.player;
+        let status = if player.now_playing.is_none() || player.play_started_at.is_none() {
+            PlaybackStatus::Stopped
+        } else if player.paused {
+            PlaybackStatus::Paused
+        } else {
+            PlaybackStatus::Playing
+        };
+        let track = player.now_playing.as_ref().map(|title| {
+            match app.queue_pos.and_then(|pos| app.queue.get(pos)) {
+                Some(song) => MprisTrack {
+                    id: song.id,
+                    title: song.display_name(app.song_name_mode).into_owned(),
+                    artists: song
+                        .artists
+                        .split('/')
+                        .map(|a| a.trim().to_owned())
+                        .filter(|a| !a.is_empty())
+                        .collect(),
+                    album: song.album.clone(),
+                    length_us: player.play_total_ms.or(song.duration_ms).map(ms_to_us),
+                },
+                // 队列之外的播放（理论上不会出现）只有标题
+                None => MprisTrack {
+                    id: 0,
+                    title: title.clone(),
+                    artists: Vec::new(),
+                    album: String::new(),
+                    length_us: player.play_total_ms.map(ms_to_us),
+                },
+            }
+        });
+        Self {
+            track,
+            status,
+            // 静音时报告 0，取消静音后恢复原音量
+            volume: if player.muted {
+                0.0
+            } else {
+                f64::from(player.volume)
+            },
+            rate: f64::from(player.play_speed),
+            can_seek: player.can_seek(),
+            player: player.clone(),
+        }
+    }
+
+    pub fn position_us(&self) -> i64 {
+        if self.status == PlaybackStatus::Stopped {
+            return 0;
+        }
+        ms_to_us(self.player.playback_elapsed_ms())
+    }
+
+    /// 与上一个状态相比，除进度外对外可见的属性是否变化
+    pub fn same_properties(&self, other: &Self) -> bool {
+        self.track == other.track
+            && self.status == other.status
+            && self.volume == other.volume
+            && self.rate == other.rate
+            && self.can_seek == other.can_seek
+    }
+
+    /// 新状态相对于按旧状态推算的位置是否发生了跳变（Seek）；返回新位置
+    ///
+    /// `elapsed_us` 为两次快照之间经过的时间。换歌或停止不算 Seek。
+    pub fn seeked_from(&self, prev: &Self, prev_position_us: i64, elapsed_us: i64) -> Option<i64> {
+        if self.status == PlaybackStatus::Stopped
+            || self.track.as_ref().map(|t| t.id) != prev.track.as_ref().map(|t| t.id)
+        {
+            return None;
+        }
+        let expected_us = if prev.status == PlaybackStatus::Playing {
+            prev_position_us + (elapsed_us as f64 * prev.rate) as i64
+        } else {
+            prev_position_us
+        };
+        let position_us = self.position_us();
+        ((position_us - expected_us).abs() > SEEK_JUMP_US).then_some(position_us)
+    }
+
+    /// 把一次 MPRIS 调用换成 app 命令；对当前状态无意义的调用（如播放中再 Play）返回 None
+    pub fn command(&self, request: MprisRequest) -> Option<AppCommand> {
+        let playing = self.status == PlaybackStatus::Playing;
+        let paused = self.status == PlaybackStatus::Paused;
+        match request {
+            MprisRequest::PlayPause if playing || paused => Some(AppCommand::PlayerTogglePause),
+            MprisRequest::Play if paused => Some(AppCommand::PlayerTogglePause),
+            MprisRequest::Pause if playing => Some(AppCommand::PlayerTogglePause),
+            MprisRequest::PlayPause | MprisRequest::Play | MprisRequest::Pause => None,
+            MprisRequest::Stop => {
+                (self.status != PlaybackStatus::Stopped).then_some(AppCommand::PlayerStop)
+            }
+            MprisRequest::Next => Some(AppCommand::PlayerNext),
+            MprisRequest::Previous => Some(AppCommand::PlayerPrev),
+            MprisRequest::Seek { offset_us } => {
+                if !self.can_seek || offset_us == 0 {
+                    return None;
+                }
+                let ms = offset_us.unsigned_abs() / 1_000;
+                Some(if offset_us > 0 {
+                    AppCommand::PlayerSeekForwardMs { ms }
+                } else {
+                    AppCommand::PlayerSeekBackwardMs { ms }
+                })
+            }
+            MprisRequest::SetPosition {
+                track_id,
+                position_us,
+            } => {
+                let track = self.track.as_ref()?;
+                // 规范要求：歌曲已变化或位置越界时忽略
+                if !self.can_seek
+                    || track.object_path() != track_id
+                    || position_us < 0
+                    || track.length_us.is_some_and(|len| position_us > len)
+                {
+                    return None;
+                }
+                Some(AppCommand::PlayerSeekAbsoluteMs {
+                    ms: position_us.unsigned_abs() / 1_000,
+                })
+            }
+            MprisRequest::SetVolume(volume) => Some(AppCommand::PlayerSetVolume {
+                volume: volume.clamp(0.0, 2.0) as f32,
+            }),
+        }
+    }
+}
+
+/// 来自 MPRIS 客户端的调用
+#[derive(Debug, Clone, PartialEq)]
+pub enum MprisRequest {
+    Play,
+    Pause,
+    PlayPause,
+    Stop,
+    Next,
+    Previous,
+    Seek { offset_us: i64 },
+    SetPosition { track_id: String, position_us: i64 },
+    SetVolume(f64),
+}
+
+fn ms_to_us(ms: u64) -> i64 {
+    i64::try_from(ms.saturating_mul(1_000)).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, PlayQueue, Song};
+    use std::time::{Duration, Instant};
+
+    fn playing_app() -> App {
+        let mut queue = PlayQueue::new(crate::app::PlayMode::Sequential);
+        queue.set_songs(
+            vec![
+                Song::titled(186016, "晴天", "周杰伦/ 五月天")
+                    .lasting_ms(269_000)
+                    .in_album("叶惠美"),
+            ],
+            Some(0),
+        );
+        App {
+            play_queue: queue,
+            now_playing: Some("晴天 - 周杰伦/ 五月天".to_owned()),
+            play_started_at: Some(Instant::now() - Duration::from_secs(10)),
+            play_total_ms: Some(270_000),
+            play_song_id: Some(186016),
+            volume: 0.8,
+            ..App::default()
+        }
+    }
+
+    fn state(app: &App) -> MprisState {
+        MprisState::from_snapshot(&AppSnapshot::from_app(app))
+    }
+
+    #[test]
+    fn maps_snapshot_to_metadata_and_status() {
+        let app = playing_app();
+        let s = state(&app);
+        assert_eq!(s.status, PlaybackStatus::Playing);
+        let track = s.track.as_ref().expect("track");
+        assert_eq!(track.title, "晴天");
+        assert_eq!(track.artists, vec!["周杰伦", "五月天"]);
+        assert_eq!(track.album, "叶惠美");
+        assert_eq!(track.length_us, Some(270_000_000));
+        assert_eq!(track.object_path(), "/org/netease_ratui/track/186016");
+        assert!((s.volume - 0.8).abs() < 1e-6);
+        assert!(s.position_us().abs_diff(10_000_000) < 100_000);
+
+        let mut paused = playing_app();
+        paused.paused = true;
+        paused.play_paused_at = Some(Instant::now());
+        assert_eq!(state(&paused).status, PlaybackStatus::Paused);
+
+        assert_eq!(state(&App::default()).status, PlaybackStatus::Stopped);
+        assert_eq!(state(&App::default()).position_us(), 0);
+    }
+
+    #[test]
+    fn play_and_pause_only_toggle_when_meaningful() {
+        let s = state(&playing_app());
+        assert!(matches!(
+            s.command(MprisRequest::Pause),
+            Some(AppCommand::PlayerTogglePause)
+        ));
+        assert!(s.command(MprisRequest::Play).is_none());
+        assert!(matches!(
+            s.command(MprisRequest::PlayPause),
+            Some(AppCommand::PlayerTogglePause)
+        ));
+
+        let stopped = state(&App::default());
+        assert!(stopped.command(MprisRequest::PlayPause).is_none());
+        assert!(stopped.command(MprisRequest::Stop).is_none());
+        assert!(matches!(
+            stopped.command(MprisRequest::Next),
+            Some(AppCommand::PlayerNext)
+        ));
+    }
+
+    #[test]
+    fn seek_calls_map_to_seek_commands() {
+        let s = state(&playing_app());
+        assert!(matches!(
+            s.command(MprisRequest::Seek {
+                offset_us: 5_000_000
+            }),
+            Some(AppCommand::PlayerSeekForwardMs { ms: 5_000 })
+        ));
+        assert!(matches!(
+            s.command(MprisRequest::Seek {
+                offset_us: -3_000_000
+            }),
+            Some(AppCommand::PlayerSeekBackwardMs { ms: 3_000 })
+        ));
+        assert!(matches!(
+            s.command(MprisRequest::SetPosition {
+                track_id: track_object_path(186016),
+                position_us: 60_000_000,
+            }),
+            Some(AppCommand::PlayerSeekAbsoluteMs { ms: 60_000 })
+        ));
+        // 歌曲已变化或越界时忽略
+        assert!(
+            s.command(MprisRequest::SetPosition {
+                track_id: track_object_path(1),
+                position_us: 60_000_000,
+            })
+            .is_none()
+        );
+        assert!(
+            s.command(MprisRequest::SetPosition {
+                track_id: track_object_path(186016),
+                position_us: 300_000_000,
+            })
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn volume_maps_to_app_volume() {
+        let s = state(&playing_app());
+        assert!(matches!(
+            s.command(MprisRequest::SetVolume(0.5)),
+            Some(AppCommand::PlayerSetVolume { volume }) if volume == 0.5
+        ));
+        assert!(matches!(
+            s.command(MprisRequest::SetVolume(-1.0)),
+            Some(AppCommand::PlayerSetVolume { volume }) if volume == 0.0
+        ));
+    }
+
+    #[test]
+    fn detects_seek_jumps_but_not_normal_progress() {
+        let app = playing_app();
+        let prev = state(&app);
+        let prev_pos = prev.position_us();
+
+        // 正常播放 2 秒
+        let mut later = app.clone();
+        later.play_started_at = later.play_started_at.map(|t| t - Duration::from_secs(2));
+        assert!(
+            state(&later)
+                .seeked_from(&prev, prev_pos, 2_000_000)
+                .is_none()
+        );
+
+        // 跳到 1 分钟
+        let mut jumped = app.clone();
+        jumped.set_playback_position(60_000);
+        let pos = state(&jumped)
+            .seeked_from(&prev, prev_pos, 0)
+            .expect("seeked");
+        assert!(pos.abs_diff(60_000_000) < 100_000);
+    }
+
+    #[test]
+    fn negative_song_ids_make_valid_object_paths() {
+        assert_eq!(track_object_path(-42), "/org/netease_ratui/track/n42");
+    }
+}
@@ -0,0 +1,319 @@
+//! D-Bus side of the MPRIS integration (`mpris` feature).
+//!
+//! [`start_mpris`] puts a forwarding task between the app actor and the UI: every
+//! event is passed through unchanged, and `AppEvent::State` snapshots additionally
+//! update the exported `org.mpris.MediaPlayer2.Player` properties. Without a session
+//! bus the task only logs a warning and keeps forwarding.
+
+use super::{MprisRequest, MprisState, PlaybackStatus};
+use crate::audio_worker::{MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED};
+use crate::messages::app::{AppCommand, AppEvent};
+use std::collections::HashMap;
+use std::time::Instant;
+use tokio::sync::mpsc;
+use zbus::object_server::{InterfaceRef, SignalEmitter};
+use zbus::zvariant::{ObjectPath, OwnedValue, Value};
+use zbus::{Connection, interface};
+
+const BUS_NAME: &str = "org.mpris.MediaPlayer2.netease_ratui";
+const OBJECT_PATH: &str = "/org/mpris/MediaPlayer2";
+
+/// Tap the actor → UI event stream; returns the receiver the UI should read from.
+pub fn start_mpris(
+    tx_cmd: mpsc::Sender<AppCommand>,
+    mut rx_evt: mpsc::Receiver<AppEvent>,
+) -> mpsc::Receiver<AppEvent> {
+    let (tx_ui, rx_ui) = mpsc::channel::<AppEvent>(64);
+    tokio::spawn(async move {
+        let mut server = match MprisServer::connect(tx_cmd).await {
+            Ok(server) => Some(server),
+            Err(e) => {
+                tracing::warn!("MPRIS 不可用（无法连接 D-Bus 会话总线）: {e}");
+                None
+            }
+        };
+        while let Some(evt) = rx_evt.recv().await {
+            if let (Some(s), AppEvent::State(snapshot)) = (server.as_mut(), &evt)
+                && let Err(e) = s.update(MprisState::from_snapshot(snapshot)).await
+            {
+                tracing::warn!("MPRIS 状态更新失败: {e}");
+            }
+            if tx_ui.send(evt).await.is_err() {
+                break;
+            }
+        }
+    });
+    rx_ui
+}
+
+struct MprisServer {
+    // 连接断开即从总线注销
+    _conn: Connection,
+    player: InterfaceRef<Player>,
+    /// 上次发布时的位置与时间，用于识别 Seek
+    last_position: Option<(i64, Instant)>,
+}
+
+impl MprisServer {
+    async fn connect(tx_cmd: mpsc::Sender<AppCommand>) -> zbus::Result<Self> {
+        let conn = zbus::connection::Builder::session()?
+            .name(BUS_NAME)?
+            .serve_at(OBJECT_PATH, Root)?
+            .serve_at(
+                OBJECT_PATH,
+                Player {
+                    state: None,
+                    tx_cmd,
+                },
+            )?
+            .build()
+            .await?;
+        let player = conn
+            .object_server()
+            .interface::<_, Player>(OBJECT_PATH)
+            .await?;
+        tracing::info!(bus_name = BUS_NAME, "MPRIS 已注册");
+        Ok(Self {
+            _conn: conn,
+            player,
+            last_position: None,
+        })
+    }
+
+    async fn update(&mut self, state: MprisState) -> zbus::Result<()> {
+        let emitter = self.player.signal_emitter();
+        let now = Instant::now();
+        let position_us = state.position_us();
+        let mut player = self.player.get_mut().await;
+
+        let seeked = match (&player.state, self.last_position) {
+            (Some(prev), Some((prev_pos, at))) => {
+                let elapsed_us = i64::try_from(at.elapsed().as_micros()).unwrap_or(i64::MAX);
+                state.seeked_from(prev, prev_pos, elapsed_us)
+            }
+            _ => None,
+        };
+        let changed = player
+            .state
+            .as_ref()
+            .is_none_or(|prev| !prev.same_properties(&state));
+        player.state = Some(state);
+        self.last_position = Some((position_us, now));
+
+        if changed {
+            player.playback_status_changed(emitter).await?;
+            player.metadata_changed(emitter).await?;
+            player.volume_changed(emitter).await?;
+            player.rate_changed(emitter).await?;
+            player.can_seek_changed(emitter).await?;
+        }
+        if let Some(position) = seeked {
+            Player::seeked(emitter, position).await?;
+        }
+        Ok(())
+    }
+}
+
+struct Root;
+
+#[interface(name = "org.mpris.MediaPlayer2")]
+impl Root {
+    fn raise(&self) {}
+
+    fn quit(&self) {}
+
+    #[zbus(property)]
+    fn can_quit(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn can_raise(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn has_track_list(&self) -> bool {
+        false
+    }
+
+    #[zbus(property)]
+    fn identity(&self) -> &str {
+        "netease-ratui"
+    }
+
+    #[zbus(property)]
+    fn supported_uri_schemes(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[zbus(property)]
+    fn supported_mime_types(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
+
+struct Player {
+    /// 尚未收到第一份快照时为 None（按已停止处理）
+    state: Option<MprisState>,
+    tx_cmd: mpsc::Sender<AppCommand>,
+}
+
+impl Player {
+    fn dispatch(&self, request: MprisRequest) {
+        let Some(cmd) = self.state.as_ref().and_then(|s| s.command(request.clone())) else {
+            tracing::debug!(?request, "MPRIS 调用被忽略");
+            return;
+        };
+        tracing::debug!(?request, command = ?cmd, "MPRIS 调用");
+        // 不阻塞发送：等待期间持有接口锁，转发任务更新状态时会等它，进而卡住 actor 的事件发送
+        match self.tx_cmd.try_send(cmd) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                tracing::warn!("命令队列已满，丢弃 MPRIS 调用");
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                tracing::warn!("App actor 已退出：MPRIS 调用未送达");
+            }
+        }
+    }
+
+    fn status(&self) -> PlaybackStatus {
+        self.state
+            .as_ref()
+            .map_or(PlaybackStatus::Stopped, |s| s.status)
+    }
+}
+
+#[interface(name = "org.mpris.MediaPlayer2.Player")]
+impl Player {
+    fn next(&self) {
+        self.dispatch(MprisRequest::Next);
+    }
+
+    fn previous(&self) {
+        self.dispatch(MprisRequest::Previous);
+    }
+
+    fn pause(&self) {
+        self.dispatch(MprisRequest::Pause);
+    }
+
+    fn play_pause(&self) {
+        self.dispatch(MprisRequest::PlayPause);
+    }
+
+    fn stop(&self) {
+        self.dispatch(MprisRequest::Stop);
+    }
+
+    fn play(&self) {
+        self.dispatch(MprisRequest::Play);
+    }
+
+    fn seek(&self, offset: i64) {
+        self.dispatch(MprisRequest::Seek { offset_us: offset });
+    }
+
+    fn set_position(&self, track_id: ObjectPath<'_>, position: i64) {
+        self.dispatch(MprisRequest::SetPosition {
+            track_id: track_id.to_string(),
+            position_us: position,
+        });
+    }
+
+    fn open_uri(&self, _uri: &str) {}
+
+    #[zbus(signal)]
+    async fn seeked(emitter: &SignalEmitter<'_>, position: i64) -> zbus::Result<()>;
+
+    #[zbus(property)]
+    fn playback_status(&self) -> &str {
+        self.status().as_str()
+    }
+
+    #[zbus(property)]
+    fn rate(&self) -> f64 {
+        self.state.as_ref().map_or(1.0, |s| s.rate)
+    }
+
+    #[zbus(property)]
+    fn minimum_rate(&self) -> f64 {
+        f64::from(MIN_PLAYBACK_SPEED)
+    }
+
+    #[zbus(property)]
+    fn maximum_rate(&self) -> f64 {
+        f64::from(MAX_PLAYBACK_SPEED)
+    }
+
+    #[zbus(property)]
+    fn metadata(&self) -> HashMap<String, OwnedValue> {
+        let mut map = HashMap::new();
+        let Some(track) = self.state.as_ref().and_then(|s| s.track.as_ref()) else {
+            return map;
+        };
+        let mut insert = |key: &str, value: Value<'_>| {
+            if let Ok(value) = OwnedValue::try_from(value) {
+                map.insert(key.to_owned(), value);
+            }
+        };
+        if let Ok(path) = ObjectPath::try_from(track.object_path()) {
+            insert("mpris:trackid", Value::from(path));
+        }
+        if let Some(length) = track.length_us {
+            insert("mpris:length", Value::from(length));
+        }
+        insert("xesam:title", Value::from(track.title.as_str()));
+        insert("xesam:artist", Value::from(track.artists.clone()));
+        if !track.album.is_empty() {
+            insert("xesam:album", Value::from(track.album.as_str()));
+        }
+        map
+    }
+
+    #[zbus(property)]
+    fn volume(&self) -> f64 {
+        self.state.as_ref().map_or(1.0, |s| s.volume)
+    }
+
+    #[zbus(property)]
+    fn set_volume(&mut self, volume: f64) {
+        self.dispatch(MprisRequest::SetVolume(volume));
+    }
+
+    #[zbus(property(emits_changed_signal = "false"))]
+    fn position(&self) -> i64 {
+        self.state.as_ref().map_or(0, MprisState::position_us)
+    }
+
+    #[zbus(property)]
+    fn can_go_next(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_go_previous(&self) -> bool {
+        true
+    }
+
+    #[zbus(property)]
+    fn can_play(&self) -> bool {
+        self.status() != PlaybackStatus::Stopped
+    }
+
+    #[zbus(property)]
+    fn can_pause(&self) -> bool {
+        self.status() != PlaybackStatus::Stopped
+    }
+
+    #[zbus(property)]
+    fn can_seek(&self) -> bool {
+        self.state.as_ref().is_some_and(|s| s.can_seek)
+    }
+
+    #[zbus(property(emits_changed_signal = "const"))]
+    fn can_control(&self) -> bool {
+        true
+    }
+}
@@ -1,10 +1,15 @@
-use crate::domain::model::{Account, LoginStatus, LyricLine, Playlist, Song, SongUrl};
-use crate::error::MessageError;
+use crate::cooldown::{CooldownHandle, host_of, is_rate_limited_code};
+use crate::domain::model::{
+    Account, Album, Artist, LoginStatus, LyricLine, Playlist, PlaylistTrackOp, Song, SongPreview,
+    SongUrl,
+};
+use crate::error::{MessageError, NeteaseError, NeteaseErrorVariant};
 use crate::netease::models::convert::ModelError;
 use crate::netease::models::{convert, dto};
 use crate::netease::{NeteaseClient, NeteaseClientConfig};
 
 use serde_json::Value;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
 async fn emit_error(
@@ -14,9 +19,22 @@ async fn emit_error(
     err: MessageError,
 ) {
     tracing::warn!(req_id, ctx, err = %err, "NeteaseActor 请求失败");
-    let _ = tx_evt
-        .send(NeteaseEvent::Error { req_id, error: err })
-        .await;
+    let evt = match err {
+        // 需要登录单独成事件：上层统一回到登录页，而不是当作普通错误提示
+        MessageError::Netease(NeteaseErrorVariant::NeedLogin) => NeteaseEvent::NeedLogin { req_id },
+        error => NeteaseEvent::Error { req_id, error },
+    };
+    let _ = tx_evt.send(evt).await;
+}
+
+/// 播放链接请求被风控（-460 等）或 403 拒绝
+fn is_rate_limited_response(resp: &Result<Value, NeteaseError>) -> bool {
+    match resp {
+        Ok(_) => false,
+        Err(NeteaseError::Api { code, .. }) => is_rate_limited_code(i64::from(*code)),
+        Err(NeteaseError::Reqwest(e)) => e.status() == Some(reqwest::StatusCode::FORBIDDEN),
+        Err(_) => false,
+    }
 }
 
 // 辅助函数：将 ModelError 转换为 MessageError
@@ -24,6 +42,24 @@ fn model_error_to_message(err: ModelError) -> MessageError {
     MessageError::other(err.to_string())
 }
 
+/// 手机号登录的凭据
+#[derive(Clone, PartialEq, Eq)]
+pub enum CellphoneCredential {
+    /// 密码 MD5（小写十六进制）
+    PasswordMd5(String),
+    /// 短信验证码
+    Captcha(String),
+}
+
+impl std::fmt::Debug for CellphoneCredential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PasswordMd5(_) => f.write_str("PasswordMd5(<已隐藏>)"),
+            Self::Captcha(_) => f.write_str("Captcha(<已隐藏>)"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum NeteaseCommand {
     Init {
@@ -55,12 +91,52 @@ pub enum NeteaseCommand {
         req_id: u64,
         ids: Vec<i64>,
     },
+    /// 单首歌曲详情（搜索结果预览）
+    SongPreview {
+        req_id: u64,
+        id: i64,
+    },
+    /// 批量歌曲详情（导出 CSV 时补全专辑、时长与可播放性）
+    SongPreviews {
+        req_id: u64,
+        ids: Vec<i64>,
+    },
     CloudSearchSongs {
         req_id: u64,
         keywords: String,
         limit: i64,
         offset: i64,
     },
+    CloudSearchAlbums {
+        req_id: u64,
+        keywords: String,
+        limit: i64,
+        offset: i64,
+    },
+    /// 专辑曲目
+    AlbumTracks {
+        req_id: u64,
+        album_id: i64,
+    },
+    CloudSearchArtists {
+        req_id: u64,
+        keywords: String,
+        limit: i64,
+        offset: i64,
+    },
+    /// 歌手热门歌曲
+    ArtistTopSongs {
+        req_id: u64,
+        artist_id: i64,
+    },
+    /// 每日推荐歌曲（需登录）
+    RecommendSongs {
+        req_id: u64,
+    },
+    /// 私人 FM 的下一批歌曲（需登录）
+    PersonalFm {
+        req_id: u64,
+    },
     SongUrl {
         req_id: u64,
         id: i64,
@@ -69,6 +145,8 @@ pub enum NeteaseCommand {
     Lyric {
         req_id: u64,
         song_id: i64,
+        /// 歌词行数上限（超出时截断）
+        max_lines: usize,
     },
     LogoutLocal {
         req_id: u64,
@@ -77,6 +155,50 @@ pub enum NeteaseCommand {
         req_id: u64,
         music_u: String,
     },
+    /// 向手机号发送登录验证码
+    LoginCaptchaSend {
+        req_id: u64,
+        phone: String,
+        country_code: String,
+    },
+    /// 校验短信验证码，通过后回传 `LoginCaptchaVerified`
+    LoginCaptchaVerify {
+        req_id: u64,
+        phone: String,
+        country_code: String,
+        captcha: String,
+    },
+    /// 手机号登录；成功后的 cookie 由客户端持久化
+    LoginCellphone {
+        req_id: u64,
+        phone: String,
+        country_code: String,
+        credential: CellphoneCredential,
+    },
+    LikeList {
+        req_id: u64,
+        uid: i64,
+    },
+    LikeSong {
+        req_id: u64,
+        song_id: i64,
+        like: bool,
+    },
+    /// 向自己的歌单添加/移除歌曲
+    PlaylistTracksManipulate {
+        req_id: u64,
+        op: PlaylistTrackOp,
+        playlist_id: i64,
+        track_ids: Vec<i64>,
+    },
+    /// 上报听歌记录；尽力而为，不回传事件
+    Scrobble {
+        song_id: i64,
+        source_id: Option<i64>,
+        time_secs: u64,
+    },
+    /// 刷新登录态（由 actor 内部每天调度）；失效时回传 `SessionExpired`
+    LoginRefresh,
 }
 
 #[derive(Debug)]
@@ -99,6 +221,8 @@ pub enum NeteaseEvent {
     Account {
         req_id: u64,
         account: Account,
+        /// MUSIC_U 的过期时间（Unix 秒）；会话 cookie 或未知时为 None
+        login_expires_at: Option<i64>,
     },
     Playlists {
         req_id: u64,
@@ -108,6 +232,8 @@ pub enum NeteaseEvent {
         req_id: u64,
         playlist_id: i64,
         ids: Vec<i64>,
+        /// 歌曲加入歌单的时间（id -> 毫秒时间戳）；接口未给出的歌曲不在其中
+        added_at: std::collections::HashMap<i64, i64>,
     },
     Songs {
         req_id: u64,
@@ -117,14 +243,51 @@ pub enum NeteaseEvent {
         req_id: u64,
         songs: Vec<Song>,
     },
+    SearchAlbums {
+        req_id: u64,
+        albums: Vec<Album>,
+    },
+    AlbumTracks {
+        req_id: u64,
+        album_id: i64,
+        songs: Vec<Song>,
+    },
+    SearchArtists {
+        req_id: u64,
+        artists: Vec<Artist>,
+    },
+    ArtistTopSongs {
+        req_id: u64,
+        artist_id: i64,
+        songs: Vec<Song>,
+    },
+    RecommendSongs {
+        req_id: u64,
+        songs: Vec<Song>,
+    },
+    PersonalFm {
+        req_id: u64,
+        songs: Vec<Song>,
+    },
+    SongPreview {
+        req_id: u64,
+        preview: SongPreview,
+    },
+    SongPreviews {
+        req_id: u64,
+        previews: Vec<SongPreview>,
+    },
     SongUrl {
         req_id: u64,
         song_url: SongUrl,
+        /// 请求时使用的音质（降级重试时低于设置的音质）
+        br: i64,
     },
-    /// 歌曲 URL 不可用（版权限制、VIP 专享等）
+    /// 歌曲在请求的音质下没有可用 URL（版权限制、VIP 专享等）
     SongUrlUnavailable {
         req_id: u64,
         id: i64,
+        br: i64,
     },
     Lyric {
         req_id: u64,
@@ -139,14 +302,74 @@ pub enum NeteaseEvent {
         success: bool,
         message: String,
     },
+    LoginCaptchaSent {
+        req_id: u64,
+    },
+    LoginCaptchaVerified {
+        req_id: u64,
+    },
+    /// 手机号登录成功
+    LoginCellphoneDone {
+        req_id: u64,
+        nickname: String,
+    },
+    /// 用户「我喜欢」的歌曲 id 列表
+    LikeList {
+        req_id: u64,
+        ids: Vec<i64>,
+    },
+    SongLiked {
+        req_id: u64,
+        song_id: i64,
+        liked: bool,
+    },
+    PlaylistTracksManipulated {
+        req_id: u64,
+        op: PlaylistTrackOp,
+        playlist_id: i64,
+        /// 实际添加/移除的歌曲（已在歌单中的歌曲不会再添加）
+        track_ids: Vec<i64>,
+    },
+    /// 接口返回 code 301：游客调用了需登录的接口，或登录已失效
+    NeedLogin {
+        req_id: u64,
+    },
+    /// 定期刷新登录态被拒绝，本地登录态已清除
+    SessionExpired,
+    /// 接口限流，后台解析播放链接暂停一段时间
+    RateLimited {
+        duration: Duration,
+    },
     Error {
         req_id: u64,
         error: MessageError,
     },
 }
 
+/// 刷新登录态的周期；启动时 MUSIC_U 已超过该时长未刷新则立即刷新
+const LOGIN_REFRESH_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+
+/// 按周期向 actor 发送 `LoginRefresh`；弱引用，所有外部 Sender 关闭后随之退出
+fn spawn_login_refresh(tx: mpsc::WeakSender<NeteaseCommand>, first_delay: Duration) {
+    tokio::spawn(async move {
+        tokio::time::sleep(first_delay).await;
+        loop {
+            let Some(sender) = tx.upgrade() else {
+                break;
+            };
+            if sender.send(NeteaseCommand::LoginRefresh).await.is_err() {
+                break;
+            }
+            drop(sender);
+            tokio::time::sleep(LOGIN_REFRESH_INTERVAL).await;
+        }
+    });
+}
+
+/// `cooldown` 与 TransferActor 共享：接口限流时推迟后台（低优先级）的播放链接解析
 pub fn spawn_netease_actor(
     cfg: NeteaseClientConfig,
+    cooldown: CooldownHandle,
 ) -> (
     mpsc::Sender<NeteaseCommand>,
     mpsc::Sender<NeteaseCommand>,
@@ -155,8 +378,12 @@ pub fn spawn_netease_actor(
     let (tx_hi, mut rx_hi) = mpsc::channel::<NeteaseCommand>(64);
     let (tx_lo, mut rx_lo) = mpsc::channel::<NeteaseCommand>(64);
     let (tx_evt, rx_evt) = mpsc::channel::<NeteaseEvent>(64);
+    // 弱引用：不阻止所有外部 Sender 关闭后 actor 退出
+    let tx_lo_deferred = tx_lo.downgrade();
+    let tx_refresh = tx_hi.downgrade();
+    let api_host = host_of(&cfg.api_domain);
 
-    tokio::spawn(async move {
+    let actor = tokio::spawn(async move {
         let mut client = match NeteaseClient::new(cfg) {
             Ok(c) => c,
             Err(e) => {
@@ -170,15 +397,38 @@ pub fn spawn_netease_actor(
                 return;
             }
         };
+        spawn_login_refresh(
+            tx_refresh,
+            client.login_refresh_delay(LOGIN_REFRESH_INTERVAL),
+        );
 
         loop {
-            let cmd = tokio::select! {
+            let (cmd, background) = tokio::select! {
                 biased;
-                Some(cmd) = rx_hi.recv() => cmd,
-                Some(cmd) = rx_lo.recv() => cmd,
+                Some(cmd) = rx_hi.recv() => (cmd, false),
+                Some(cmd) = rx_lo.recv() => (cmd, true),
                 else => break,
             };
 
+            // 接口冷却中：后台预取的播放链接推迟到冷却结束再重新入队
+            if background
+                && matches!(cmd, NeteaseCommand::SongUrl { .. })
+                && let Some(remaining) = api_host.as_deref().and_then(|h| cooldown.remaining(h))
+            {
+                tracing::debug!(
+                    remaining_secs = remaining.as_secs(),
+                    "接口冷却中，推迟后台解析播放链接"
+                );
+                let tx = tx_lo_deferred.clone();
+                tokio::spawn(async move {
+                    tokio::time::sleep(remaining).await;
+                    if let Some(tx) = tx.upgrade() {
+                        let _ = tx.send(cmd).await;
+                    }
+                });
+                continue;
+            }
+
             match cmd {
                 NeteaseCommand::Init { req_id } => {
                     let _ = tx_evt
@@ -248,7 +498,13 @@ pub fn spawn_netease_actor(
                 NeteaseCommand::UserAccount { req_id } => match client.user_account().await {
                     Ok(v) => match parse::<dto::UserAccountResp>(v).and_then(convert::to_account) {
                         Ok(account) => {
-                            let _ = tx_evt.send(NeteaseEvent::Account { req_id, account }).await;
+                            let _ = tx_evt
+                                .send(NeteaseEvent::Account {
+                                    req_id,
+                                    account,
+                                    login_expires_at: client.cookie_expiry("MUSIC_U"),
+                                })
+                                .await;
                         }
                         Err(e) => {
                             emit_error(
@@ -268,7 +524,7 @@ pub fn spawn_netease_actor(
                     match client.user_playlist(uid, 200, 0).await {
                         Ok(v) => match parse::<dto::UserPlaylistResp>(v) {
                             Ok(v) => {
-                                let playlists = convert::to_playlists(v);
+                                let playlists = convert::to_playlists(v, uid);
                                 let _ = tx_evt
                                     .send(NeteaseEvent::Playlists { req_id, playlists })
                                     .await;
@@ -294,12 +550,13 @@ pub fn spawn_netease_actor(
                 } => match client.playlist_detail(playlist_id).await {
                     Ok(v) => match parse::<dto::PlaylistDetailResp>(v) {
                         Ok(v) => {
-                            let ids = convert::to_playlist_track_ids(v);
+                            let (ids, added_at) = convert::to_playlist_track_ids(v);
                             let _ = tx_evt
                                 .send(NeteaseEvent::PlaylistTrackIds {
                                     req_id,
                                     playlist_id,
                                     ids,
+                                    added_at,
                                 })
                                 .await;
                         }
@@ -339,6 +596,58 @@ pub fn spawn_netease_actor(
                         }
                     }
                 }
+                NeteaseCommand::SongPreview { req_id, id } => {
+                    match client.song_detail_by_ids(&[id]).await {
+                        Ok(v) => match parse::<dto::SongDetailResp>(v).and_then(|v| {
+                            convert::to_song_previews(v)
+                                .into_iter()
+                                .find(|p| p.id == id)
+                                .ok_or(ModelError::Empty)
+                        }) {
+                            Ok(preview) => {
+                                let _ = tx_evt
+                                    .send(NeteaseEvent::SongPreview { req_id, preview })
+                                    .await;
+                            }
+                            Err(e) => {
+                                emit_error(
+                                    &tx_evt,
+                                    req_id,
+                                    "SongPreview(parse)",
+                                    model_error_to_message(e),
+                                )
+                                .await;
+                            }
+                        },
+                        Err(e) => {
+                            emit_error(&tx_evt, req_id, "SongPreview(request)", e.into()).await;
+                        }
+                    }
+                }
+                NeteaseCommand::SongPreviews { req_id, ids } => {
+                    match client.song_detail_by_ids(&ids).await {
+                        Ok(v) => match parse::<dto::SongDetailResp>(v) {
+                            Ok(resp) => {
+                                let previews = convert::to_song_previews(resp);
+                                let _ = tx_evt
+                                    .send(NeteaseEvent::SongPreviews { req_id, previews })
+                                    .await;
+                            }
+                            Err(e) => {
+                                emit_error(
+                                    &tx_evt,
+                                    req_id,
+                                    "SongPreviews(parse)",
+                                    model_error_to_message(e),
+                                )
+                                .await;
+                            }
+                        },
+                        Err(e) => {
+                            emit_error(&tx_evt, req_id, "SongPreviews(request)", e.into()).await;
+                        }
+                    }
+                }
                 NeteaseCommand::CloudSearchSongs {
                     req_id,
                     keywords,
@@ -366,24 +675,216 @@ pub fn spawn_netease_actor(
                         emit_error(&tx_evt, req_id, "CloudSearchSongs(request)", e.into()).await;
                     }
                 },
+                NeteaseCommand::CloudSearchAlbums {
+                    req_id,
+                    keywords,
+                    limit,
+                    offset,
+                } => match client.cloudsearch(&keywords, 10, limit, offset).await {
+                    Ok(v) => match parse::<dto::CloudSearchResp>(v) {
+                        Ok(v) => {
+                            let albums = convert::to_album_list_from_search(v);
+                            let _ = tx_evt
+                                .send(NeteaseEvent::SearchAlbums { req_id, albums })
+                                .await;
+                        }
+                        Err(e) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "CloudSearchAlbums(parse)",
+                                model_error_to_message(e),
+                            )
+                            .await;
+                        }
+                    },
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "CloudSearchAlbums(request)", e.into()).await;
+                    }
+                },
+                NeteaseCommand::CloudSearchArtists {
+                    req_id,
+                    keywords,
+                    limit,
+                    offset,
+                } => match client.cloudsearch(&keywords, 100, limit, offset).await {
+                    Ok(v) => match parse::<dto::CloudSearchResp>(v) {
+                        Ok(v) => {
+                            let artists = convert::to_artist_list_from_search(v);
+                            let _ = tx_evt
+                                .send(NeteaseEvent::SearchArtists { req_id, artists })
+                                .await;
+                        }
+                        Err(e) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "CloudSearchArtists(parse)",
+                                model_error_to_message(e),
+                            )
+                            .await;
+                        }
+                    },
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "CloudSearchArtists(request)", e.into()).await;
+                    }
+                },
+                NeteaseCommand::ArtistTopSongs { req_id, artist_id } => {
+                    match client.artist_top_songs(artist_id).await {
+                        Ok(v) => match parse::<dto::ArtistTopSongsResp>(v) {
+                            Ok(v) => {
+                                let songs = convert::to_song_list_from_artist_top(v);
+                                let _ = tx_evt
+                                    .send(NeteaseEvent::ArtistTopSongs {
+                                        req_id,
+                                        artist_id,
+                                        songs,
+                                    })
+                                    .await;
+                            }
+                            Err(e) => {
+                                emit_error(
+                                    &tx_evt,
+                                    req_id,
+                                    "ArtistTopSongs(parse)",
+                                    model_error_to_message(e),
+                                )
+                                .await;
+                            }
+                        },
+                        Err(e) => {
+                            emit_error(&tx_evt, req_id, "ArtistTopSongs(request)", e.into()).await;
+                        }
+                    }
+                }
+                NeteaseCommand::RecommendSongs { req_id } => match client.recommend_songs().await {
+                    Ok(v) => match parse::<dto::RecommendSongsResp>(v)
+                        .and_then(convert::to_song_list_from_recommend)
+                    {
+                        Ok(songs) => {
+                            let _ = tx_evt
+                                .send(NeteaseEvent::RecommendSongs { req_id, songs })
+                                .await;
+                        }
+                        // 保留业务 code，便于上层区分游客（301）
+                        Err(ModelError::BadCode(code)) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "RecommendSongs(code)",
+                                MessageError::from_netease(NeteaseError::Api {
+                                    code: code as i32,
+                                    msg: "每日推荐接口返回错误".to_owned(),
+                                }),
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "RecommendSongs(parse)",
+                                model_error_to_message(e),
+                            )
+                            .await;
+                        }
+                    },
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "RecommendSongs(request)", e.into()).await;
+                    }
+                },
+                NeteaseCommand::PersonalFm { req_id } => match client.personal_fm().await {
+                    Ok(v) => match parse::<dto::PersonalFmResp>(v)
+                        .and_then(convert::to_song_list_from_personal_fm)
+                    {
+                        Ok(songs) => {
+                            let _ = tx_evt
+                                .send(NeteaseEvent::PersonalFm { req_id, songs })
+                                .await;
+                        }
+                        Err(ModelError::BadCode(code)) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "PersonalFm(code)",
+                                MessageError::from_netease(NeteaseError::Api {
+                                    code: code as i32,
+                                    msg: "私人FM接口返回错误".to_owned(),
+                                }),
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "PersonalFm(parse)",
+                                model_error_to_message(e),
+                            )
+                            .await;
+                        }
+                    },
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "PersonalFm(request)", e.into()).await;
+                    }
+                },
+                NeteaseCommand::AlbumTracks { req_id, album_id } => {
+                    match client.album_detail(album_id).await {
+                        Ok(v) => match parse::<dto::AlbumDetailResp>(v) {
+                            Ok(v) => {
+                                let songs = convert::to_song_list_from_album(v);
+                                let _ = tx_evt
+                                    .send(NeteaseEvent::AlbumTracks {
+                                        req_id,
+                                        album_id,
+                                        songs,
+                                    })
+                                    .await;
+                            }
+                            Err(e) => {
+                                emit_error(
+                                    &tx_evt,
+                                    req_id,
+                                    "AlbumTracks(parse)",
+                                    model_error_to_message(e),
+                                )
+                                .await;
+                            }
+                        },
+                        Err(e) => {
+                            emit_error(&tx_evt, req_id, "AlbumTracks(request)", e.into()).await;
+                        }
+                    }
+                }
                 NeteaseCommand::SongUrl { req_id, id, br } => {
-                    match client.song_url(&[id], br).await {
+                    let resp = client.song_url(&[id], br).await;
+                    if let Some(host) = api_host.as_deref() {
+                        if !is_rate_limited_response(&resp) {
+                            if resp.is_ok() {
+                                cooldown.record_success(host);
+                            }
+                        } else if let Some(duration) = cooldown.record_failure(host) {
+                            tracing::warn!(host, secs = duration.as_secs(), "接口限流，进入冷却");
+                            let _ = tx_evt.send(NeteaseEvent::RateLimited { duration }).await;
+                        }
+                    }
+                    match resp {
                         Ok(v) => {
                             match parse::<dto::SongUrlResp>(v).and_then(convert::to_song_url) {
                                 Ok(song_url) => {
                                     let _ = tx_evt
-                                        .send(NeteaseEvent::SongUrl { req_id, song_url })
+                                        .send(NeteaseEvent::SongUrl {
+                                            req_id,
+                                            song_url,
+                                            br,
+                                        })
                                         .await;
                                 }
                                 Err(ModelError::MissingField("data[0].url")) => {
-                                    // 歌曲无可用 URL（版权限制等），发送特殊事件
-                                    tracing::warn!(
-                                        req_id,
-                                        song_id = id,
-                                        "歌曲无可用播放链接，自动跳过"
-                                    );
+                                    // 该音质下无可用 URL（版权限制等），由上层决定降级重试或跳过
+                                    tracing::warn!(req_id, song_id = id, br, "歌曲无可用播放链接");
                                     let _ = tx_evt
-                                        .send(NeteaseEvent::SongUrlUnavailable { req_id, id })
+                                        .send(NeteaseEvent::SongUrlUnavailable { req_id, id, br })
                                         .await;
                                 }
                                 Err(e) => {
@@ -402,10 +903,14 @@ pub fn spawn_netease_actor(
                         }
                     }
                 }
-                NeteaseCommand::Lyric { req_id, song_id } => match client.lyric(song_id).await {
+                NeteaseCommand::Lyric {
+                    req_id,
+                    song_id,
+                    max_lines,
+                } => match client.lyric(song_id).await {
                     Ok(v) => match parse::<dto::LyricResp>(v) {
                         Ok(v) => {
-                            let lyrics = convert::to_lyrics(v);
+                            let lyrics = convert::to_lyrics(v, max_lines);
                             let _ = tx_evt
                                 .send(NeteaseEvent::Lyric {
                                     req_id,
@@ -447,9 +952,211 @@ pub fn spawn_netease_actor(
                         }
                     }
                 }
+                NeteaseCommand::LoginCaptchaSend {
+                    req_id,
+                    phone,
+                    country_code,
+                } => match client.captcha_sent(&phone, &country_code).await {
+                    Ok(_) => {
+                        let _ = tx_evt.send(NeteaseEvent::LoginCaptchaSent { req_id }).await;
+                    }
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "LoginCaptchaSend", e.into()).await;
+                    }
+                },
+                NeteaseCommand::LoginCaptchaVerify {
+                    req_id,
+                    phone,
+                    country_code,
+                    captcha,
+                } => match client.captcha_verify(&phone, &captcha, &country_code).await {
+                    Ok(_) => {
+                        let _ = tx_evt
+                            .send(NeteaseEvent::LoginCaptchaVerified { req_id })
+                            .await;
+                    }
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "LoginCaptchaVerify", e.into()).await;
+                    }
+                },
+                NeteaseCommand::LoginCellphone {
+                    req_id,
+                    phone,
+                    country_code,
+                    credential,
+                } => {
+                    let result = match &credential {
+                        CellphoneCredential::PasswordMd5(md5) => {
+                            client.login_cellphone(&phone, md5, &country_code).await
+                        }
+                        CellphoneCredential::Captcha(captcha) => {
+                            client
+                                .login_cellphone_captcha(&phone, captcha, &country_code)
+                                .await
+                        }
+                    };
+                    match result {
+                        Ok(v) => {
+                            match parse::<dto::UserAccountResp>(v).and_then(convert::to_account) {
+                                Ok(account) => {
+                                    let _ = tx_evt
+                                        .send(NeteaseEvent::LoginCellphoneDone {
+                                            req_id,
+                                            nickname: account.nickname,
+                                        })
+                                        .await;
+                                }
+                                Err(e) => {
+                                    emit_error(
+                                        &tx_evt,
+                                        req_id,
+                                        "LoginCellphone(parse)",
+                                        model_error_to_message(e),
+                                    )
+                                    .await;
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            emit_error(&tx_evt, req_id, "LoginCellphone(request)", e.into()).await;
+                        }
+                    }
+                }
+                NeteaseCommand::LikeList { req_id, uid } => match client.likelist(uid).await {
+                    Ok(v) => match parse::<dto::LikeListResp>(v) {
+                        Ok(v) => {
+                            let ids = convert::to_liked_ids(v);
+                            let _ = tx_evt.send(NeteaseEvent::LikeList { req_id, ids }).await;
+                        }
+                        Err(e) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "LikeList(parse)",
+                                model_error_to_message(e),
+                            )
+                            .await;
+                        }
+                    },
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "LikeList(request)", e.into()).await;
+                    }
+                },
+                NeteaseCommand::LikeSong {
+                    req_id,
+                    song_id,
+                    like,
+                } => match client.like_song(song_id, like).await {
+                    Ok(v) => match parse::<dto::LikeResp>(v).and_then(convert::check_like) {
+                        Ok(()) => {
+                            let _ = tx_evt
+                                .send(NeteaseEvent::SongLiked {
+                                    req_id,
+                                    song_id,
+                                    liked: like,
+                                })
+                                .await;
+                        }
+                        Err(e) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "LikeSong(parse)",
+                                model_error_to_message(e),
+                            )
+                            .await;
+                        }
+                    },
+                    Err(e) => {
+                        emit_error(&tx_evt, req_id, "LikeSong(request)", e.into()).await;
+                    }
+                },
+                NeteaseCommand::PlaylistTracksManipulate {
+                    req_id,
+                    op,
+                    playlist_id,
+                    track_ids,
+                } => match client
+                    .playlist_tracks_manipulate(op, playlist_id, &track_ids)
+                    .await
+                {
+                    Ok(v) => match parse::<dto::PlaylistManipulateResp>(v)
+                        .and_then(|resp| convert::check_playlist_manipulate(resp, &track_ids))
+                    {
+                        Ok(changed) => {
+                            let _ = tx_evt
+                                .send(NeteaseEvent::PlaylistTracksManipulated {
+                                    req_id,
+                                    op,
+                                    playlist_id,
+                                    track_ids: changed,
+                                })
+                                .await;
+                        }
+                        // 保留业务 code，便于上层区分重复添加（502）
+                        Err(ModelError::BadCode(code)) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "PlaylistTracksManipulate(code)",
+                                MessageError::from_netease(NeteaseError::Api {
+                                    code: code as i32,
+                                    msg: "歌单操作接口返回错误".to_owned(),
+                                }),
+                            )
+                            .await;
+                        }
+                        Err(e) => {
+                            emit_error(
+                                &tx_evt,
+                                req_id,
+                                "PlaylistTracksManipulate(parse)",
+                                model_error_to_message(e),
+                            )
+                            .await;
+                        }
+                    },
+                    Err(e) => {
+                        emit_error(
+                            &tx_evt,
+                            req_id,
+                            "PlaylistTracksManipulate(request)",
+                            e.into(),
+                        )
+                        .await;
+                    }
+                },
+                NeteaseCommand::LoginRefresh if !client.is_logged_in() => {}
+                NeteaseCommand::LoginRefresh => match client.login_refresh().await {
+                    Ok(_) => tracing::info!(
+                        expires_at = ?client.cookie_expiry("MUSIC_U"),
+                        "登录态已刷新"
+                    ),
+                    Err(e) if e.is_auth_error() => {
+                        tracing::warn!(err = %e, "刷新登录态被拒绝，登录已失效");
+                        if let Err(e) = client.drop_login() {
+                            tracing::warn!(err = %e, "清除登录态失败");
+                        }
+                        let _ = tx_evt.send(NeteaseEvent::SessionExpired).await;
+                    }
+                    // 网络错误等：保留登录态，下个周期再试
+                    Err(e) => tracing::warn!(err = %e, "刷新登录态失败"),
+                },
+                NeteaseCommand::Scrobble {
+                    song_id,
+                    source_id,
+                    time_secs,
+                } => match client.scrobble(song_id, source_id, time_secs).await {
+                    Ok(v) => match v.get("code").and_then(Value::as_i64) {
+                        Some(200) => tracing::debug!(song_id, time_secs, "听歌记录已上报"),
+                        code => tracing::debug!(song_id, ?code, "听歌记录上报被拒绝"),
+                    },
+                    Err(e) => tracing::debug!(song_id, err = %e, "听歌记录上报失败"),
+                },
             }
         }
     });
+    crate::crash::supervise("NeteaseActor", actor);
 
     (tx_hi, tx_lo, rx_evt)
 }
@@ -1,3 +1,4 @@
+use super::config::CookieRecord;
 use super::types::OsProfile;
 use crate::netease::util;
 use rand::Rng;
@@ -46,6 +47,25 @@ pub fn wnmcid() -> String {
     format!("{s}.{}.01.0", now_millis())
 }
 
+/// 每次请求按设备信息合成的 cookie 字段；只用于构造请求头，不写入持久化的 cookie 罐
+pub const SYNTHETIC_COOKIE_KEYS: &[&str] = &[
+    "os",
+    "osver",
+    "appver",
+    "channel",
+    "deviceId",
+    "__remember_me",
+    "ntes_kaola_ad",
+];
+
+/// 从持久化的 cookie 罐中移除请求期合成的字段，返回是否有改动
+pub fn strip_synthetic_cookies(cookies: &mut HashMap<String, CookieRecord>) -> bool {
+    let before = cookies.len();
+    cookies.retain(|k, _| !SYNTHETIC_COOKIE_KEYS.contains(&k.as_str()));
+    cookies.len() != before
+}
+
+/// 基于持久化的 cookie 罐合成本次请求用的 cookie（返回副本，不修改传入的 cookie 罐）
 pub fn process_cookie_object(
     cookies: &HashMap<String, String>,
     device_id: &str,
@@ -185,12 +205,12 @@ pub fn build_eapi_header(
     header
 }
 
-pub fn generate_chain_id(cookies: &HashMap<String, String>) -> String {
+pub fn generate_chain_id(cookies: &HashMap<String, CookieRecord>) -> String {
     let version = "v1";
     let random_num: u32 = rand::thread_rng().gen_range(0..1_000_000);
     let device_id = cookies
         .get("sDeviceId")
-        .cloned()
+        .map(|c| c.value.clone())
         .unwrap_or_else(|| format!("unknown-{random_num}"));
     let platform = "web";
     let action = "login";
@@ -198,10 +218,187 @@ pub fn generate_chain_id(cookies: &HashMap<String, String>) -> String {
     format!("{version}_{device_id}_{platform}_{action}_{ts}")
 }
 
-pub fn update_cookies(cookies: &mut HashMap<String, String>, set_cookie_headers: &[String]) {
+/// 按 Set-Cookie 更新 cookie 罐；已过期（含 `Max-Age=0` 删除）的 cookie 直接移除
+pub fn update_cookies(
+    cookies: &mut HashMap<String, CookieRecord>,
+    set_cookie_headers: &[String],
+    now_secs: i64,
+) {
     for sc in set_cookie_headers {
-        if let Ok(c) = cookie::Cookie::parse(sc.to_owned()) {
-            cookies.insert(c.name().to_owned(), c.value().to_owned());
+        let Ok(c) = cookie::Cookie::parse(sc.to_owned()) else {
+            continue;
+        };
+        // Max-Age 优先于 Expires
+        let expires = match c.max_age() {
+            Some(age) => Some(now_secs.saturating_add(age.whole_seconds())),
+            None => c.expires_datetime().map(|t| t.unix_timestamp()),
+        };
+        let record = CookieRecord {
+            value: c.value().to_owned(),
+            domain: c
+                .domain()
+                .map(|d| d.trim_start_matches('.').to_ascii_lowercase())
+                .filter(|d| !d.is_empty()),
+            path: c.path().map(ToOwned::to_owned),
+            expires,
+        };
+        if record.is_expired(now_secs) {
+            cookies.remove(c.name());
+        } else {
+            cookies.insert(c.name().to_owned(), record);
         }
     }
 }
+
+/// 移除已过期的 cookie，返回是否有改动
+pub fn prune_expired(cookies: &mut HashMap<String, CookieRecord>, now_secs: i64) -> bool {
+    let before = cookies.len();
+    cookies.retain(|_, c| !c.is_expired(now_secs));
+    cookies.len() != before
+}
+
+fn domain_matches(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// RFC 6265 路径匹配：`/weapi` 匹配 `/weapi` 与 `/weapi/...`，不匹配 `/weapix`
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    match request_path.strip_prefix(cookie_path) {
+        Some(rest) => rest.is_empty() || cookie_path.ends_with('/') || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+/// 本次请求应携带的 cookie：按 URL 的主机与路径过滤，并跳过已过期的
+pub fn cookies_for_url(
+    cookies: &HashMap<String, CookieRecord>,
+    url: &str,
+    now_secs: i64,
+) -> HashMap<String, String> {
+    let parsed = reqwest::Url::parse(url).ok();
+    let host = parsed
+        .as_ref()
+        .and_then(|u| u.host_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let path = parsed.as_ref().map_or("/", |u| u.path());
+    cookies
+        .iter()
+        .filter(|(_, c)| !c.is_expired(now_secs))
+        .filter(|(_, c)| c.domain.as_deref().is_none_or(|d| domain_matches(&host, d)))
+        .filter(|(_, c)| c.path.as_deref().is_none_or(|p| path_matches(path, p)))
+        .map(|(k, c)| (k.clone(), c.value.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jar(records: &[(&str, CookieRecord)]) -> HashMap<String, CookieRecord> {
+        records
+            .iter()
+            .map(|(k, c)| ((*k).to_owned(), c.clone()))
+            .collect()
+    }
+
+    fn names(cookies: &HashMap<String, String>) -> Vec<&str> {
+        let mut names: Vec<&str> = cookies.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn outgoing_cookies_are_filtered_by_domain_and_path() {
+        let cookies = jar(&[
+            ("any", CookieRecord::session("1")),
+            (
+                "music",
+                CookieRecord {
+                    domain: Some("music.163.com".to_owned()),
+                    ..CookieRecord::session("2")
+                },
+            ),
+            (
+                "other",
+                CookieRecord {
+                    domain: Some("163.com.cn".to_owned()),
+                    ..CookieRecord::session("3")
+                },
+            ),
+            (
+                "weapi",
+                CookieRecord {
+                    path: Some("/weapi".to_owned()),
+                    ..CookieRecord::session("4")
+                },
+            ),
+        ]);
+
+        let sent = cookies_for_url(&cookies, "https://music.163.com/weapi/song/detail", 0);
+        assert_eq!(names(&sent), ["any", "music", "weapi"]);
+        // 子域名匹配父域的 Domain
+        let sent = cookies_for_url(&cookies, "https://interface.music.163.com/eapi/x", 0);
+        assert_eq!(names(&sent), ["any", "music"]);
+        // 路径前缀必须在 / 边界上
+        let sent = cookies_for_url(&cookies, "https://music.163.com/weapix", 0);
+        assert_eq!(names(&sent), ["any", "music"]);
+        let sent = cookies_for_url(&cookies, "https://evilmusic.163.com/weapi", 0);
+        assert_eq!(names(&sent), ["any", "weapi"]);
+    }
+
+    #[test]
+    fn expired_cookies_are_not_sent_and_pruned() {
+        let mut cookies = jar(&[
+            (
+                "old",
+                CookieRecord {
+                    expires: Some(100),
+                    ..CookieRecord::session("1")
+                },
+            ),
+            (
+                "fresh",
+                CookieRecord {
+                    expires: Some(200),
+                    ..CookieRecord::session("2")
+                },
+            ),
+        ]);
+        let sent = cookies_for_url(&cookies, "https://music.163.com/", 100);
+        assert_eq!(names(&sent), ["fresh"]);
+
+        assert!(prune_expired(&mut cookies, 150));
+        assert!(!prune_expired(&mut cookies, 150));
+        assert_eq!(cookies.keys().collect::<Vec<_>>(), ["fresh"]);
+    }
+
+    #[test]
+    fn set_cookie_attributes_are_recorded() {
+        let mut cookies = jar(&[("gone", CookieRecord::session("x"))]);
+        update_cookies(
+            &mut cookies,
+            &[
+                "MUSIC_U=u; Max-Age=3600; Expires=Thu, 01 Jan 2037 00:00:00 GMT; Domain=.Music.163.com; Path=/".to_owned(),
+                "__csrf=c; Expires=Thu, 01 Jan 2037 00:00:00 GMT".to_owned(),
+                "gone=; Max-Age=0".to_owned(),
+            ],
+            1_000,
+        );
+        assert_eq!(
+            cookies["MUSIC_U"],
+            CookieRecord {
+                value: "u".to_owned(),
+                domain: Some("music.163.com".to_owned()),
+                path: Some("/".to_owned()),
+                expires: Some(4_600),
+            },
+            "Max-Age 优先于 Expires"
+        );
+        assert_eq!(cookies["__csrf"].expires, Some(2_114_380_800));
+        assert!(!cookies.contains_key("gone"), "Max-Age=0 删除 cookie");
+    }
+}
@@ -3,16 +3,23 @@ mod cookie;
 mod error;
 mod types;
 
-pub use config::{ClientState, NeteaseClientConfig};
+pub use config::{
+    ClientState, CookieRecord, NeteaseClientConfig, parse_proxy, proxy_from_env, state_path,
+};
 pub use error::NeteaseError;
 pub use types::{QrPlatform, ValidateCookieResult};
 
+use crate::domain::model::PlaylistTrackOp;
 use crate::netease::crypto::{self, CryptoMode};
 use crate::netease::util;
-use cookie::{cookie_obj_to_string, create_header_cookie, process_cookie_object, update_cookies};
+use cookie::{
+    cookie_obj_to_string, cookies_for_url, create_header_cookie, now_secs, process_cookie_object,
+    prune_expired, strip_synthetic_cookies, update_cookies,
+};
 use reqwest::header::{CONTENT_TYPE, HeaderMap, HeaderValue, REFERER, SET_COOKIE, USER_AGENT};
 use serde_json::{Value, json};
 use std::fs;
+use std::time::Duration;
 use types::{UA_API_IPHONE, UA_LINUX, UA_WEAPI_PC};
 
 #[derive(Debug)]
@@ -26,10 +33,15 @@ impl NeteaseClient {
     pub fn new(cfg: NeteaseClientConfig) -> Result<Self, NeteaseError> {
         fs::create_dir_all(&cfg.data_dir).map_err(NeteaseError::Io)?;
 
-        let http = reqwest::Client::builder()
+        let mut builder = reqwest::Client::builder()
             .user_agent("netease-ratui")
-            .build()
-            .map_err(NeteaseError::Reqwest)?;
+            .timeout(cfg.timeout)
+            .connect_timeout(cfg.connect_timeout);
+        if let Some(url) = cfg.proxy.as_deref() {
+            builder = builder.proxy(parse_proxy(url)?);
+            tracing::info!("网易接口经由代理访问");
+        }
+        let http = builder.build().map_err(NeteaseError::Reqwest)?;
 
         let mut client = Self {
             http,
@@ -37,6 +49,17 @@ impl NeteaseClient {
             cfg,
         };
 
+        // 旧版本会把请求期合成的 cookie（os 等）写回 cookie 罐，加载时清理
+        if strip_synthetic_cookies(&mut client.state.cookies) {
+            tracing::info!("已清理持久化 cookie 中的请求期字段");
+            client.save_cleaned_state();
+        }
+
+        if prune_expired(&mut client.state.cookies, now_secs() as i64) {
+            tracing::info!("已移除过期的持久化 cookie");
+            client.save_cleaned_state();
+        }
+
         if client.state.device_id.is_none() {
             client.state.device_id = Some(util::generate_device_id());
             client.save_state()?;
@@ -53,10 +76,30 @@ impl NeteaseClient {
         config::save_state(&self.cfg.data_dir, &self.state)
     }
 
+    /// 写回加载时清理过的状态；失败（如数据目录只读）只记录日志，内存中的状态已清理
+    fn save_cleaned_state(&self) {
+        if let Err(e) = self.save_state() {
+            tracing::warn!(err = %e, "写回清理后的客户端状态失败");
+        }
+    }
+
     // ========== Auth Methods ==========
 
+    /// 未过期的 cookie 记录
+    fn live_cookie(&self, name: &str) -> Option<&CookieRecord> {
+        self.state
+            .cookies
+            .get(name)
+            .filter(|c| !c.is_expired(now_secs() as i64))
+    }
+
     pub fn is_logged_in(&self) -> bool {
-        self.state.cookies.contains_key("MUSIC_U")
+        self.live_cookie("MUSIC_U").is_some()
+    }
+
+    /// cookie 的过期时间（Unix 秒）；不存在、已过期或为会话 cookie 时为 None
+    pub fn cookie_expiry(&self, name: &str) -> Option<i64> {
+        self.live_cookie(name)?.expires
     }
 
     #[allow(dead_code)]
@@ -64,7 +107,7 @@ impl NeteaseClient {
         self.state
             .cookies
             .iter()
-            .map(|(k, v)| format!("{k}={v}"))
+            .map(|(k, c)| format!("{k}={}", c.value))
             .collect::<Vec<_>>()
             .join("; ")
     }
@@ -73,7 +116,7 @@ impl NeteaseClient {
         if self.is_logged_in() {
             return Ok(());
         }
-        if self.state.cookies.contains_key("MUSIC_A") {
+        if self.live_cookie("MUSIC_A").is_some() {
             return Ok(());
         }
         self.register_anonymous().await?;
@@ -83,7 +126,8 @@ impl NeteaseClient {
     pub async fn register_anonymous(&mut self) -> Result<Value, NeteaseError> {
         let device_id = self.device_id().to_owned();
         let username = util::build_anonymous_username(&device_id);
-        self.request(
+        // 不经过 request()：注册本身失败时不再触发重新注册
+        self.request_once(
             "/api/register/anonimous",
             json!({ "username": username }),
             CryptoMode::Weapi,
@@ -122,19 +166,126 @@ impl NeteaseClient {
         .await
     }
 
+    /// 手机号 + 密码登录；`password_md5` 为密码 MD5 的小写十六进制。成功后服务端下发 MUSIC_U
+    pub async fn login_cellphone(
+        &mut self,
+        phone: &str,
+        password_md5: &str,
+        country_code: &str,
+    ) -> Result<Value, NeteaseError> {
+        self.cellphone_login(phone, country_code, "password", password_md5)
+            .await
+    }
+
+    /// 手机号 + 短信验证码登录
+    pub async fn login_cellphone_captcha(
+        &mut self,
+        phone: &str,
+        captcha: &str,
+        country_code: &str,
+    ) -> Result<Value, NeteaseError> {
+        self.cellphone_login(phone, country_code, "captcha", captcha)
+            .await
+    }
+
+    async fn cellphone_login(
+        &mut self,
+        phone: &str,
+        country_code: &str,
+        credential_key: &str,
+        credential: &str,
+    ) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        let mut params = json!({
+            "type": "1",
+            "https": "true",
+            "phone": phone,
+            "countrycode": country_code,
+            "remember": "true",
+        });
+        params[credential_key] = json!(credential);
+        self.request("/api/login/cellphone", params, CryptoMode::Weapi)
+            .await
+    }
+
+    /// 向手机号发送登录验证码
+    pub async fn captcha_sent(
+        &mut self,
+        phone: &str,
+        country_code: &str,
+    ) -> Result<Value, NeteaseError> {
+        self.request(
+            "/api/sms/captcha/sent",
+            json!({ "cellphone": phone, "ctcode": country_code, "secrete": "music_middle" }),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
+    /// 校验短信验证码
+    pub async fn captcha_verify(
+        &mut self,
+        phone: &str,
+        captcha: &str,
+        country_code: &str,
+    ) -> Result<Value, NeteaseError> {
+        self.request(
+            "/api/sms/captcha/verify",
+            json!({ "cellphone": phone, "captcha": captcha, "ctcode": country_code }),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
     pub fn logout_local(&mut self) -> Result<(), NeteaseError> {
         self.state.cookies.clear();
+        self.state.cookie_saved_at = None;
         self.save_state()?;
         Ok(())
     }
 
+    /// 刷新登录态，延长 MUSIC_U 的有效期（服务端通常会下发新的 MUSIC_U）
+    pub async fn login_refresh(&mut self) -> Result<Value, NeteaseError> {
+        let v = self
+            .request("/api/login/token/refresh", json!({}), CryptoMode::Weapi)
+            .await?;
+        self.state.cookie_saved_at = Some(now_secs() as i64);
+        self.save_state()?;
+        Ok(v)
+    }
+
+    /// 距下一次刷新登录态还需等待的时间：MUSIC_U 写入时间未知或已超过 `interval` 时立即刷新；
+    /// 未登录时等待一个完整周期
+    pub fn login_refresh_delay(&self, interval: Duration) -> Duration {
+        self.login_refresh_delay_at(now_secs() as i64, interval)
+    }
+
+    fn login_refresh_delay_at(&self, now_secs: i64, interval: Duration) -> Duration {
+        if !self.is_logged_in() {
+            return interval;
+        }
+        let Some(saved_at) = self.state.cookie_saved_at else {
+            return Duration::ZERO;
+        };
+        let age = Duration::from_secs(now_secs.saturating_sub(saved_at).max(0) as u64);
+        interval.saturating_sub(age)
+    }
+
+    /// 登录态失效：丢弃 MUSIC_U，保留游客 cookie 与设备信息
+    pub fn drop_login(&mut self) -> Result<(), NeteaseError> {
+        self.state.cookies.remove("MUSIC_U");
+        self.state.cookie_saved_at = None;
+        self.save_state()
+    }
+
     pub async fn set_cookie_and_validate(
         &mut self,
         music_u: &str,
     ) -> Result<ValidateCookieResult, NeteaseError> {
         self.state
             .cookies
-            .insert("MUSIC_U".to_owned(), music_u.to_owned());
+            .insert("MUSIC_U".to_owned(), CookieRecord::session(music_u));
+        self.state.cookie_saved_at = Some(now_secs() as i64);
         self.save_state()?;
 
         match self.user_account().await {
@@ -153,8 +304,7 @@ impl NeteaseClient {
                 })
             }
             Err(e) => {
-                self.state.cookies.remove("MUSIC_U");
-                self.save_state()?;
+                self.drop_login()?;
                 Err(NeteaseError::CookieValidationFailed(format!(
                     "Cookie 验证失败: {e}"
                 )))
@@ -186,6 +336,46 @@ impl NeteaseClient {
         .await
     }
 
+    /// 专辑详情（含全部曲目）
+    pub async fn album_detail(&mut self, album_id: i64) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        self.request(
+            &format!("/api/v1/album/{album_id}"),
+            json!({}),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
+    /// 歌手热门歌曲（最多 50 首）
+    pub async fn artist_top_songs(&mut self, artist_id: i64) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        self.request(
+            "/api/artist/top/song",
+            json!({ "id": artist_id }),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
+    /// 每日推荐歌曲（需登录，游客返回 code 301）
+    pub async fn recommend_songs(&mut self) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        self.request(
+            "/api/v3/discovery/recommend/songs",
+            json!({}),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
+    /// 私人 FM：每次返回数首推荐歌曲（需登录，游客返回 code 301）
+    pub async fn personal_fm(&mut self) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        self.request("/api/v1/radio/get", json!({}), CryptoMode::Weapi)
+            .await
+    }
+
     pub async fn song_url(&mut self, ids: &[i64], br: i64) -> Result<Value, NeteaseError> {
         self.ensure_anonymous().await?;
         let ids_str = serde_json::to_string(ids).map_err(NeteaseError::Serde)?;
@@ -262,9 +452,112 @@ impl NeteaseClient {
             .await
     }
 
+    pub async fn likelist(&mut self, uid: i64) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        self.request(
+            "/api/song/like/get",
+            json!({ "uid": uid }),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
+    pub async fn like_song(&mut self, id: i64, like: bool) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        self.request(
+            "/api/radio/like",
+            json!({
+              "alg": "itembased",
+              "trackId": id,
+              "like": like,
+              "time": "3",
+            }),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
+    /// 向自己的歌单添加或移除歌曲
+    pub async fn playlist_tracks_manipulate(
+        &mut self,
+        op: PlaylistTrackOp,
+        playlist_id: i64,
+        track_ids: &[i64],
+    ) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        let params = playlist_manipulate_params(op, playlist_id, track_ids)?;
+        self.request("/api/playlist/manipulate/tracks", params, CryptoMode::Weapi)
+            .await
+    }
+
+    /// 上报一次听歌记录（进入听歌排行与推荐）；`source_id` 为来源歌单
+    pub async fn scrobble(
+        &mut self,
+        song_id: i64,
+        source_id: Option<i64>,
+        time_secs: u64,
+    ) -> Result<Value, NeteaseError> {
+        self.ensure_anonymous().await?;
+        let logs = weblog_play_logs(song_id, source_id, time_secs)?;
+        self.request(
+            "/api/feedback/weblog",
+            json!({ "logs": logs }),
+            CryptoMode::Weapi,
+        )
+        .await
+    }
+
     // ========== Request Methods ==========
 
+    /// 请求实际发往的 URL（cookie 按它的主机与路径过滤）
+    fn endpoint_url(&self, uri: &str, crypto: CryptoMode) -> String {
+        match crypto {
+            CryptoMode::Weapi => format!(
+                "{}/weapi/{}",
+                self.cfg.domain.trim_end_matches('/'),
+                uri.trim_start_matches("/api/"),
+            ),
+            CryptoMode::Linuxapi => format!(
+                "{}/api/linux/forward",
+                self.cfg.domain.trim_end_matches('/')
+            ),
+            CryptoMode::Eapi => format!(
+                "{}/eapi/{}",
+                self.cfg.api_domain.trim_end_matches('/'),
+                uri.trim_start_matches("/api/"),
+            ),
+        }
+    }
+
+    /// 发送请求；游客凭证（MUSIC_A）被服务端拒绝时重新注册游客身份并重试一次
     async fn request(
+        &mut self,
+        uri: &str,
+        data: Value,
+        crypto: CryptoMode,
+    ) -> Result<Value, NeteaseError> {
+        match self.request_once(uri, data.clone(), crypto).await {
+            Err(e) if self.anonymous_rejected(&e) => {
+                tracing::warn!(uri, err = %e, "游客凭证已失效，重新注册后重试");
+                self.state.cookies.remove("MUSIC_A");
+                self.save_state()?;
+                self.register_anonymous().await?;
+                self.request_once(uri, data, crypto).await
+            }
+            resp => resp,
+        }
+    }
+
+    /// 未登录且带着 MUSIC_A 时，接口返回凭证无效（301 / 50000004）
+    fn anonymous_rejected(&self, err: &NeteaseError) -> bool {
+        let rejected = matches!(
+            err,
+            NeteaseError::NeedLogin | NeteaseError::Api { code: 50000004, .. }
+        );
+        rejected && !self.is_logged_in() && self.state.cookies.contains_key("MUSIC_A")
+    }
+
+    async fn request_once(
         &mut self,
         uri: &str,
         mut data: Value,
@@ -280,7 +573,9 @@ impl NeteaseClient {
             HeaderValue::from_static("application/x-www-form-urlencoded"),
         );
 
-        let mut cookie = process_cookie_object(&self.state.cookies, self.device_id(), uri);
+        let url = self.endpoint_url(uri, crypto);
+        let jar = cookies_for_url(&self.state.cookies, &url, now_secs() as i64);
+        let mut cookie = process_cookie_object(&jar, self.device_id(), uri);
 
         let (url, form, _should_return_early) = match crypto {
             CryptoMode::Weapi => {
@@ -298,20 +593,11 @@ impl NeteaseClient {
                 let f = crypto::weapi(&data).map_err(|e| NeteaseError::Crypto(format!("{e}")))?;
                 cookie.insert("os".to_owned(), "pc".to_owned());
 
-                let url = format!(
-                    "{}/weapi/{}",
-                    self.cfg.domain.trim_end_matches('/'),
-                    uri.trim_start_matches("/api/"),
-                );
                 let form = vec![("params", f.params), ("encSecKey", f.enc_sec_key)];
                 (url, form, false)
             }
             CryptoMode::Linuxapi => {
                 headers.insert(USER_AGENT, HeaderValue::from_static(UA_LINUX));
-                let url = format!(
-                    "{}/api/linux/forward",
-                    self.cfg.domain.trim_end_matches('/')
-                );
                 let linux_obj = json!({
                     "method": "POST",
                     "url": format!("{}{}", self.cfg.domain.trim_end_matches('/'), uri),
@@ -332,18 +618,8 @@ impl NeteaseClient {
                     .ok_or(NeteaseError::BadInput("data 必须是 JSON object"))?
                     .insert("header".to_owned(), json!(header));
 
-                self.state.cookies.insert(
-                    "os".to_owned(),
-                    cookie.get("os").cloned().unwrap_or_else(|| "pc".to_owned()),
-                );
-
                 let f =
                     crypto::eapi(uri, &data).map_err(|e| NeteaseError::Crypto(format!("{e}")))?;
-                let url = format!(
-                    "{}/eapi/{}",
-                    self.cfg.api_domain.trim_end_matches('/'),
-                    uri.trim_start_matches("/api/"),
-                );
                 let form = vec![("params", f.params)];
 
                 headers.insert(
@@ -352,7 +628,7 @@ impl NeteaseClient {
                         NeteaseError::BadHeader(format!("Cookie(header cookie): {e}"))
                     })?,
                 );
-                return self.send(url, headers, form).await;
+                return self.send(uri, url, headers, form).await;
             }
         };
 
@@ -361,40 +637,44 @@ impl NeteaseClient {
             HeaderValue::from_str(&cookie_obj_to_string(&cookie))
                 .map_err(|e| NeteaseError::BadHeader(format!("Cookie: {e}")))?,
         );
-        self.send(url, headers, form).await
+        self.send(uri, url, headers, form).await
     }
 
+    /// 发送请求；连接失败、超时、连接中断或 5xx 时按指数退避重试
     async fn send(
         &mut self,
+        uri: &str,
         url: String,
         headers: HeaderMap,
         form: Vec<(&'static str, String)>,
     ) -> Result<Value, NeteaseError> {
-        let resp = match self
-            .http
-            .post(url.clone())
-            .headers(headers.clone())
-            .form(&form)
-            .send()
-            .await
-        {
-            Ok(r) => r,
-            Err(e) => {
-                if url.contains("https://interface.music.163.com/") {
-                    tracing::warn!(url = %url, err = %e, "请求失败，降级到 music.163.com");
-                    let fallback =
-                        url.replace("https://interface.music.163.com/", "https://music.163.com/");
-                    self.http
-                        .post(fallback)
-                        .headers(headers)
-                        .form(&form)
-                        .send()
-                        .await
-                        .map_err(NeteaseError::Reqwest)?
-                } else {
-                    return Err(NeteaseError::Reqwest(e));
+        let max_retries = if is_login_endpoint(uri) {
+            self.cfg.retries.min(1)
+        } else {
+            self.cfg.retries
+        };
+        let body = form_body(&form);
+        let mut attempt = 0;
+        let resp = loop {
+            let retry_reason = match self.post_once(&url, &headers, &body).await {
+                Ok(resp) if resp.status().is_server_error() && attempt < max_retries => {
+                    format!("HTTP {}", resp.status())
                 }
-            }
+                Ok(resp) => break resp,
+                Err(e) if is_transient(&e) && attempt < max_retries => e.to_string(),
+                Err(e) => return Err(NeteaseError::Reqwest(e)),
+            };
+            let delay = retry_delay(attempt, self.cfg.retry_backoff, self.cfg.retry_backoff_max);
+            attempt += 1;
+            tracing::warn!(
+                uri,
+                attempt,
+                max_retries,
+                delay_ms = delay.as_millis() as u64,
+                reason = %retry_reason,
+                "请求失败，稍后重试"
+            );
+            tokio::time::sleep(delay).await;
         };
 
         let set_cookies = resp
@@ -408,9 +688,772 @@ impl NeteaseClient {
         let body: Value = serde_json::from_slice(&bytes)
             .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&bytes).to_string()));
 
-        update_cookies(&mut self.state.cookies, &set_cookies);
+        update_cookies(&mut self.state.cookies, &set_cookies, now_secs() as i64);
+        if set_cookies
+            .iter()
+            .any(|c| c.trim_start().starts_with("MUSIC_U="))
+        {
+            self.state.cookie_saved_at = Some(now_secs() as i64);
+        }
         self.save_state()?;
 
-        Ok(body)
+        check_code(uri, body)
+    }
+
+    /// 发送一次；`interface.music.163.com` 不可达时降级到 `music.163.com`
+    async fn post_once(
+        &self,
+        url: &str,
+        headers: &HeaderMap,
+        body: &str,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        let post = |url: &str| {
+            self.http
+                .post(url)
+                .headers(headers.clone())
+                .body(body.to_owned())
+                .send()
+        };
+        match post(url).await {
+            Ok(r) => Ok(r),
+            Err(e) if url.contains("https://interface.music.163.com/") => {
+                tracing::warn!(url = %url, err = %e, "请求失败，降级到 music.163.com");
+                post(&url.replace("https://interface.music.163.com/", "https://music.163.com/"))
+                    .await
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// 表单请求体：每个值只做一次百分号编码（UTF-8），与上游 Node 实现的 `querystring.stringify` 一致
+///
+/// weapi 的 `params` 是 base64，其中的 `+`、`/`、`=` 必须编码为 `%2B`、`%2F`、`%3D`，
+/// 否则服务端会把 `+` 解成空格；eapi 的 hex 与 `encSecKey` 不含需要编码的字符。
+fn form_body(form: &[(&str, String)]) -> String {
+    form.iter()
+        .map(|(k, v)| format!("{}={}", urlencoding::encode(k), urlencoding::encode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// 歌单增删请求的参数：所有歌曲 id 拼成一个 `trackIds` 数组字符串，一次请求完成
+fn playlist_manipulate_params(
+    op: PlaylistTrackOp,
+    playlist_id: i64,
+    track_ids: &[i64],
+) -> Result<Value, NeteaseError> {
+    let ids_str = serde_json::to_string(track_ids).map_err(NeteaseError::Serde)?;
+    Ok(json!({
+      "op": op.as_str(),
+      "pid": playlist_id,
+      "trackIds": ids_str,
+      "imme": "true",
+    }))
+}
+
+/// 把响应中非 200 的业务 `code` 转成错误：301 为 [`NeteaseError::NeedLogin`]，其余为
+/// [`NeteaseError::Api`]（附带服务端的 `message`/`msg`）。没有 `code` 字段的响应原样返回；
+/// 扫码状态接口用 800~803 表示扫码进度，也原样返回
+fn check_code(uri: &str, body: Value) -> Result<Value, NeteaseError> {
+    if uri == "/api/login/qrcode/client/login" {
+        return Ok(body);
+    }
+    let Some(code) = body.get("code").and_then(Value::as_i64) else {
+        return Ok(body);
+    };
+    match code {
+        200 => Ok(body),
+        301 => Err(NeteaseError::NeedLogin),
+        code => {
+            let msg = ["message", "msg"]
+                .into_iter()
+                .find_map(|k| body.get(k).and_then(Value::as_str))
+                .unwrap_or_default()
+                .to_owned();
+            Err(NeteaseError::Api {
+                code: code as i32,
+                msg,
+            })
+        }
+    }
+}
+
+/// 登录/注册/短信接口不是幂等的（重复提交可能触发风控或重复发短信），最多重试一次
+fn is_login_endpoint(uri: &str) -> bool {
+    uri.starts_with("/api/login/")
+        || uri.starts_with("/api/register/")
+        || uri.starts_with("/api/sms/")
+}
+
+/// 可以重试的网络错误：连接失败、超时、连接被中断
+fn is_transient(e: &reqwest::Error) -> bool {
+    e.is_connect() || e.is_timeout() || e.is_request()
+}
+
+/// 第 `attempt` 次重试前的等待：`base * 2^attempt`，不超过 `max`
+fn retry_delay(attempt: u32, base: Duration, max: Duration) -> Duration {
+    base.saturating_mul(2u32.saturating_pow(attempt.min(16)))
+        .min(max)
+}
+
+/// weblog 的 `logs` 参数：一条 `play` 记录的 JSON 数组字符串
+fn weblog_play_logs(
+    song_id: i64,
+    source_id: Option<i64>,
+    time_secs: u64,
+) -> Result<String, NeteaseError> {
+    let logs = json!([{
+        "action": "play",
+        "json": {
+            "download": 0,
+            "end": "playend",
+            "id": song_id,
+            "sourceId": source_id.map(|id| id.to_string()).unwrap_or_default(),
+            "time": time_secs,
+            "type": "song",
+            "wifi": 0,
+            "source": "list",
+        },
+    }]);
+    serde_json::to_string(&logs).map_err(NeteaseError::Serde)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn weblog_payload_carries_song_source_and_time() {
+        let logs: Value =
+            serde_json::from_str(&weblog_play_logs(186016, Some(24381616), 42).expect("logs"))
+                .expect("json");
+        assert_eq!(logs[0]["action"], "play");
+        let entry = &logs[0]["json"];
+        assert_eq!(entry["id"], 186016);
+        assert_eq!(entry["sourceId"], "24381616");
+        assert_eq!(entry["time"], 42);
+        assert_eq!(entry["type"], "song");
+        assert_eq!(entry["end"], "playend");
+
+        let logs: Value =
+            serde_json::from_str(&weblog_play_logs(1, None, 30).expect("logs")).expect("json");
+        assert_eq!(logs[0]["json"]["sourceId"], "");
+    }
+
+    fn client_for(server_url: &str, data_dir: &std::path::Path) -> NeteaseClient {
+        client_with(server_url, data_dir, NeteaseClientConfig::default())
+    }
+
+    fn client_with(
+        server_url: &str,
+        data_dir: &std::path::Path,
+        cfg: NeteaseClientConfig,
+    ) -> NeteaseClient {
+        let mut client = NeteaseClient::new(NeteaseClientConfig {
+            domain: server_url.to_owned(),
+            api_domain: server_url.to_owned(),
+            data_dir: data_dir.to_path_buf(),
+            ..cfg
+        })
+        .expect("client");
+        client
+            .state
+            .cookies
+            .insert("MUSIC_U".to_owned(), CookieRecord::session("u"));
+        client
+    }
+
+    fn values(cookies: &HashMap<String, CookieRecord>) -> HashMap<String, String> {
+        cookies
+            .iter()
+            .map(|(k, c)| (k.clone(), c.value.clone()))
+            .collect()
+    }
+
+    fn saved_cookies(data_dir: &std::path::Path) -> HashMap<String, String> {
+        values(&config::load_state(data_dir).expect("state").cookies)
+    }
+
+    #[tokio::test]
+    async fn request_cycles_persist_only_set_cookie_values() {
+        let mut server = mockito::Server::new_async().await;
+        for path in [
+            "/weapi/nuser/account/get",
+            "/eapi/song/lyric",
+            "/api/linux/forward",
+        ] {
+            server
+                .mock("POST", path)
+                .with_status(200)
+                .with_header("set-cookie", "__csrf=abc; Path=/")
+                .with_body("{}")
+                .create_async()
+                .await;
+        }
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_for(&server.url(), dir.path());
+
+        client.user_account().await.expect("weapi");
+        client.lyric(1).await.expect("eapi");
+        client
+            .request("/api/song/detail", json!({}), CryptoMode::Linuxapi)
+            .await
+            .expect("linuxapi");
+
+        let expected = HashMap::from([
+            ("MUSIC_U".to_owned(), "u".to_owned()),
+            ("__csrf".to_owned(), "abc".to_owned()),
+        ]);
+        assert_eq!(values(&client.state.cookies), expected);
+        assert_eq!(saved_cookies(dir.path()), expected);
+    }
+
+    #[test]
+    fn loading_strips_synthetic_keys_from_saved_state() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let cookies = ["os", "appver", "channel", "osver", "MUSIC_U"]
+            .into_iter()
+            .map(|k| (k.to_owned(), CookieRecord::session("v")))
+            .collect();
+        config::save_state(
+            dir.path(),
+            &ClientState {
+                cookies,
+                device_id: Some("dev".to_owned()),
+                cookie_saved_at: None,
+            },
+        )
+        .expect("save");
+
+        let client = NeteaseClient::new(NeteaseClientConfig {
+            data_dir: dir.path().to_path_buf(),
+            ..NeteaseClientConfig::default()
+        })
+        .expect("client");
+
+        let expected = HashMap::from([("MUSIC_U".to_owned(), "v".to_owned())]);
+        assert_eq!(values(&client.state.cookies), expected);
+        assert_eq!(saved_cookies(dir.path()), expected, "清理结果应写回磁盘");
+    }
+
+    #[test]
+    fn invalid_proxy_is_rejected_when_creating_the_client() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let err = NeteaseClient::new(NeteaseClientConfig {
+            data_dir: dir.path().to_path_buf(),
+            proxy: Some("127.0.0.1:7890".to_owned()),
+            ..NeteaseClientConfig::default()
+        })
+        .expect_err("invalid proxy");
+        assert!(matches!(err, NeteaseError::InvalidProxy { .. }), "{err}");
+    }
+
+    #[tokio::test]
+    async fn expired_and_out_of_scope_cookies_are_not_sent() {
+        let mut server = mockito::Server::new_async().await;
+        let leaked = server
+            .mock("POST", "/weapi/nuser/account/get")
+            .match_header(
+                "cookie",
+                mockito::Matcher::Regex("scoped=|stale=".to_owned()),
+            )
+            .expect(0)
+            .create_async()
+            .await;
+        let mock = server
+            .mock("POST", "/weapi/nuser/account/get")
+            .match_header("cookie", mockito::Matcher::Regex("MUSIC_U=u".to_owned()))
+            .with_status(200)
+            .with_body("{}")
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_for(&server.url(), dir.path());
+        let now = now_secs() as i64;
+        client.state.cookies.insert(
+            "MUSIC_U".to_owned(),
+            CookieRecord {
+                expires: Some(now + 3_600),
+                ..CookieRecord::session("u")
+            },
+        );
+        client.state.cookies.insert(
+            "scoped".to_owned(),
+            CookieRecord {
+                path: Some("/eapi".to_owned()),
+                ..CookieRecord::session("1")
+            },
+        );
+        client.state.cookies.insert(
+            "stale".to_owned(),
+            CookieRecord {
+                expires: Some(now - 1),
+                ..CookieRecord::session("1")
+            },
+        );
+
+        client.user_account().await.expect("weapi");
+        leaked.assert_async().await;
+        mock.assert_async().await;
+        assert_eq!(client.cookie_expiry("MUSIC_U"), Some(now + 3_600));
+        assert_eq!(client.cookie_expiry("stale"), None);
+        assert!(client.is_logged_in());
+    }
+
+    /// 重试间隔缩短到毫秒级的配置
+    fn quick_retries(retries: u32) -> NeteaseClientConfig {
+        NeteaseClientConfig {
+            retries,
+            retry_backoff: Duration::from_millis(1),
+            retry_backoff_max: Duration::from_millis(5),
+            ..NeteaseClientConfig::default()
+        }
+    }
+
+    /// 只接受连接的 TCP 服务：`hang` 为 true 时连接一直挂着不回应，否则立即断开；返回地址与连接计数
+    fn silent_server(hang: bool) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}", listener.local_addr().expect("addr"));
+        let accepted = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = accepted.clone();
+        std::thread::spawn(move || {
+            let mut held = Vec::new();
+            for stream in listener.incoming().flatten() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                if hang {
+                    held.push(stream);
+                }
+            }
+        });
+        (url, accepted)
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap() {
+        let base = Duration::from_millis(250);
+        let max = Duration::from_millis(2_000);
+        let delays: Vec<u128> = (0..6)
+            .map(|n| retry_delay(n, base, max).as_millis())
+            .collect();
+        assert_eq!(delays, [250, 500, 1_000, 2_000, 2_000, 2_000]);
+        assert_eq!(retry_delay(u32::MAX, base, max), max);
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried_until_success() {
+        let mut server = mockito::Server::new_async().await;
+        let failing = server
+            .mock("POST", "/weapi/nuser/account/get")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("POST", "/weapi/nuser/account/get")
+            .with_status(200)
+            .with_body(r#"{"code":200}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_with(&server.url(), dir.path(), quick_retries(2));
+
+        let body = client.user_account().await.expect("retried");
+        assert_eq!(body["code"], 200);
+        failing.assert_async().await;
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn login_endpoints_are_retried_at_most_once() {
+        let mut server = mockito::Server::new_async().await;
+        let login = server
+            .mock("POST", "/eapi/login/qrcode/client/login")
+            .with_status(502)
+            .expect(2)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_with(&server.url(), dir.path(), quick_retries(5));
+
+        // 重试用完后返回最后一次响应，由调用方按 code 判断
+        client.login_qr_check("key").await.expect("response");
+        login.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn hung_requests_time_out_after_the_configured_retries() {
+        let (url, accepted) = silent_server(true);
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_with(
+            &url,
+            dir.path(),
+            NeteaseClientConfig {
+                timeout: Duration::from_millis(200),
+                ..quick_retries(1)
+            },
+        );
+
+        let started = std::time::Instant::now();
+        let err = client.user_account().await.expect_err("timeout");
+        assert!(
+            matches!(&err, NeteaseError::Reqwest(e) if e.is_timeout()),
+            "{err}"
+        );
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn dropped_connections_are_retried() {
+        let (url, accepted) = silent_server(false);
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_with(&url, dir.path(), quick_retries(2));
+
+        client.user_account().await.expect_err("connection closed");
+        assert_eq!(accepted.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn non_200_codes_become_typed_errors() {
+        let mut server = mockito::Server::new_async().await;
+        for (path, body) in [
+            (
+                "/weapi/nuser/account/get",
+                r#"{"code":301,"msg":"需要登录"}"#,
+            ),
+            (
+                "/weapi/v1/radio/get",
+                r#"{"code":-462,"message":"操作频繁，请稍候再试"}"#,
+            ),
+            ("/weapi/v3/discovery/recommend/songs", r#"{"code":400}"#),
+            (
+                "/eapi/login/qrcode/client/login",
+                r#"{"code":801,"message":"等待扫码"}"#,
+            ),
+        ] {
+            server
+                .mock("POST", path)
+                .with_status(200)
+                .with_body(body)
+                .create_async()
+                .await;
+        }
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_for(&server.url(), dir.path());
+
+        let err = client.user_account().await.expect_err("301");
+        assert!(matches!(err, NeteaseError::NeedLogin), "{err}");
+
+        let err = client.personal_fm().await.expect_err("-462");
+        assert!(
+            matches!(&err, NeteaseError::Api { code: -462, msg } if msg == "操作频繁，请稍候再试"),
+            "{err}"
+        );
+
+        let err = client.recommend_songs().await.expect_err("400");
+        assert!(
+            matches!(&err, NeteaseError::Api { code: 400, msg } if msg.is_empty()),
+            "{err}"
+        );
+
+        // 扫码进度码由调用方解析
+        let body = client.login_qr_check("key").await.expect("qr status");
+        assert_eq!(body["code"], 801);
+    }
+
+    /// 未登录、持有（服务端已作废的）游客 cookie 的客户端
+    fn guest_client(server_url: &str, data_dir: &std::path::Path) -> NeteaseClient {
+        let mut client = client_for(server_url, data_dir);
+        client.state.cookies.remove("MUSIC_U");
+        client
+            .state
+            .cookies
+            .insert("MUSIC_A".to_owned(), CookieRecord::session("stale"));
+        client
+    }
+
+    async fn mock_search(server: &mut mockito::Server, body: &str, hits: usize) -> mockito::Mock {
+        server
+            .mock("POST", "/eapi/cloudsearch/pc")
+            .with_status(200)
+            .with_body(body)
+            .expect(hits)
+            .create_async()
+            .await
+    }
+
+    async fn mock_register(server: &mut mockito::Server, body: &str, hits: usize) -> mockito::Mock {
+        server
+            .mock("POST", "/weapi/register/anonimous")
+            .with_status(200)
+            .with_header("set-cookie", "MUSIC_A=fresh; Path=/")
+            .with_body(body)
+            .expect(hits)
+            .create_async()
+            .await
+    }
+
+    #[test]
+    fn form_body_percent_encodes_base64_exactly_once() {
+        // 期望值为上游 Node 实现 querystring.stringify 的输出
+        let form = [
+            (
+                "params",
+                "QaJbeLmi2V6uDHcCX3CitSMi31KGSA8iscI2byB+dJrl/ZKtXB+hmrSU7/d1SAYs3xunCDEclk2ykV3/kPg1kedzOGHaVi3MLBKmDOvXjuE=".to_owned(),
+            ),
+            ("encSecKey", "5f476cc68f214f71".to_owned()),
+        ];
+        assert_eq!(
+            form_body(&form),
+            "params=QaJbeLmi2V6uDHcCX3CitSMi31KGSA8iscI2byB%2BdJrl%2FZKtXB%2BhmrSU7%2Fd1SAYs3xunCDEclk2ykV3%2FkPg1kedzOGHaVi3MLBKmDOvXjuE%3D&encSecKey=5f476cc68f214f71"
+        );
+        let hex = [("params", "2B5D64177AA6460F".to_owned())];
+        assert_eq!(form_body(&hex), "params=2B5D64177AA6460F");
+    }
+
+    /// 解析表单请求体（每个值解码一次）
+    fn parse_form(body: &[u8]) -> HashMap<String, String> {
+        std::str::from_utf8(body)
+            .expect("utf8")
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(k, v)| {
+                let decode = |s: &str| urlencoding::decode(s).expect("percent").into_owned();
+                (decode(k), decode(v))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn search_keywords_survive_the_wire_round_trip() {
+        for keyword in ["AC/DC", "Beyond+", "周杰伦 🎵", "a&b=c"] {
+            let mut server = mockito::Server::new_async().await;
+            let expected = keyword.to_owned();
+            let mock = server
+                .mock("POST", "/eapi/cloudsearch/pc")
+                .match_header("content-type", "application/x-www-form-urlencoded")
+                .match_request(move |req| {
+                    let form = parse_form(req.body().expect("body"));
+                    let Some(params) = form.get("params") else {
+                        return false;
+                    };
+                    let (uri, data) = crypto::eapi_req_decrypt(params).expect("decrypt");
+                    uri == "/api/cloudsearch/pc" && data["s"] == expected.as_str()
+                })
+                .with_status(200)
+                .with_body(r#"{"code":200}"#)
+                .create_async()
+                .await;
+            let dir = tempfile::tempdir().expect("tempdir");
+            let mut client = client_for(&server.url(), dir.path());
+
+            client
+                .cloudsearch(keyword, 1, 10, 0)
+                .await
+                .unwrap_or_else(|e| panic!("{keyword}: {e}"));
+            mock.assert_async().await;
+        }
+    }
+
+    #[tokio::test]
+    async fn rejected_anonymous_cookie_is_reregistered_and_retried_once() {
+        let mut server = mockito::Server::new_async().await;
+        let rejected = mock_search(&mut server, r#"{"code":50000004}"#, 1).await;
+        let ok = mock_search(&mut server, r#"{"code":200}"#, 1).await;
+        let register = mock_register(&mut server, r#"{"code":200}"#, 1).await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = guest_client(&server.url(), dir.path());
+
+        client.cloudsearch("a", 1, 10, 0).await.expect("retried");
+        rejected.assert_async().await;
+        ok.assert_async().await;
+        register.assert_async().await;
+        assert_eq!(saved_cookies(dir.path())["MUSIC_A"], "fresh");
+    }
+
+    #[tokio::test]
+    async fn anonymous_reregistration_does_not_loop() {
+        let mut server = mockito::Server::new_async().await;
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        // 重新注册后仍被拒绝：只重试一次
+        let search = mock_search(&mut server, r#"{"code":301}"#, 2).await;
+        let register = mock_register(&mut server, r#"{"code":200}"#, 1).await;
+        let mut client = guest_client(&server.url(), dir.path());
+        let err = client.cloudsearch("a", 1, 10, 0).await.expect_err("301");
+        assert!(matches!(err, NeteaseError::NeedLogin), "{err}");
+        search.assert_async().await;
+        register.assert_async().await;
+        server.reset();
+
+        // 注册本身失败：直接返回注册的错误，不再重试原请求
+        let search = mock_search(&mut server, r#"{"code":301}"#, 1).await;
+        let register = mock_register(&mut server, r#"{"code":-460,"message":"风控"}"#, 1).await;
+        let mut client = guest_client(&server.url(), dir.path());
+        let err = client
+            .cloudsearch("a", 1, 10, 0)
+            .await
+            .expect_err("register");
+        assert!(matches!(err, NeteaseError::Api { code: -460, .. }), "{err}");
+        search.assert_async().await;
+        register.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn logged_in_rejections_are_not_reregistered() {
+        let mut server = mockito::Server::new_async().await;
+        let search = mock_search(&mut server, r#"{"code":301}"#, 1).await;
+        let register = mock_register(&mut server, r#"{"code":200}"#, 0).await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_for(&server.url(), dir.path());
+
+        let err = client.cloudsearch("a", 1, 10, 0).await.expect_err("301");
+        assert!(matches!(err, NeteaseError::NeedLogin), "{err}");
+        search.assert_async().await;
+        register.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn login_refresh_records_the_new_cookie() {
+        let mut server = mockito::Server::new_async().await;
+        let refresh = server
+            .mock("POST", "/weapi/login/token/refresh")
+            .with_status(200)
+            .with_header("set-cookie", "MUSIC_U=renewed; Path=/; Max-Age=86400")
+            .with_body(r#"{"code":200}"#)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_for(&server.url(), dir.path());
+        assert_eq!(client.state.cookie_saved_at, None);
+
+        client.login_refresh().await.expect("refresh");
+        refresh.assert_async().await;
+        assert_eq!(saved_cookies(dir.path())["MUSIC_U"], "renewed");
+        let saved_at = config::load_state(dir.path())
+            .expect("state")
+            .cookie_saved_at
+            .expect("saved_at");
+        assert!((now_secs() as i64 - saved_at).abs() < 5);
+        assert!(client.cookie_expiry("MUSIC_U").is_some());
+    }
+
+    #[tokio::test]
+    async fn cellphone_login_persists_only_the_returned_cookie() {
+        let mut server = mockito::Server::new_async().await;
+        let login = server
+            .mock("POST", "/weapi/login/cellphone")
+            .with_status(200)
+            .with_header("set-cookie", "MUSIC_U=phone-login; Path=/; Max-Age=86400")
+            .with_body(r#"{"code":200,"account":{"id":7},"profile":{"nickname":"n"}}"#)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = guest_client(&server.url(), dir.path());
+        let password_md5 = "5f4dcc3b5aa765d61d8327deb882cf99";
+
+        client
+            .login_cellphone("13800000000", password_md5, "86")
+            .await
+            .expect("login");
+        login.assert_async().await;
+        assert!(client.is_logged_in());
+        assert_eq!(saved_cookies(dir.path())["MUSIC_U"], "phone-login");
+        let state_file = std::fs::read_dir(dir.path())
+            .expect("data dir")
+            .map(|e| std::fs::read_to_string(e.expect("entry").path()).unwrap_or_default())
+            .collect::<String>();
+        assert!(!state_file.contains(password_md5));
+        assert!(!state_file.contains("13800000000"));
+    }
+
+    #[tokio::test]
+    async fn wrong_cellphone_password_is_an_api_error() {
+        let mut server = mockito::Server::new_async().await;
+        let login = server
+            .mock("POST", "/weapi/login/cellphone")
+            .with_status(200)
+            .with_body(r#"{"code":502,"msg":"密码错误"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = guest_client(&server.url(), dir.path());
+
+        let err = client
+            .login_cellphone("13800000000", "bad", "86")
+            .await
+            .expect_err("502");
+        assert!(matches!(err, NeteaseError::Api { code: 502, ref msg } if msg == "密码错误"));
+        login.assert_async().await;
+        assert!(!client.is_logged_in());
+    }
+
+    #[test]
+    fn sms_endpoints_share_the_login_retry_limit() {
+        assert!(is_login_endpoint("/api/sms/captcha/sent"));
+        assert!(is_login_endpoint("/api/login/cellphone"));
+        assert!(!is_login_endpoint("/api/cloudsearch/pc"));
+    }
+
+    #[tokio::test]
+    async fn rejected_login_refresh_is_an_auth_error() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("POST", "/weapi/login/token/refresh")
+            .with_status(200)
+            .with_body(r#"{"code":301}"#)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_for(&server.url(), dir.path());
+
+        let err = client.login_refresh().await.expect_err("301");
+        assert!(err.is_auth_error(), "{err}");
+        assert_eq!(client.state.cookie_saved_at, None);
+
+        client.drop_login().expect("drop");
+        assert!(!client.is_logged_in());
+        assert!(!saved_cookies(dir.path()).contains_key("MUSIC_U"));
+    }
+
+    #[test]
+    fn login_refresh_is_due_once_the_cookie_is_a_day_old() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let mut client = client_for("http://127.0.0.1:9", dir.path());
+        let day = Duration::from_secs(24 * 3600);
+        let now = 1_700_000_000;
+
+        // 旧版本状态文件没有写入时间：立即刷新
+        assert_eq!(client.login_refresh_delay_at(now, day), Duration::ZERO);
+
+        client.state.cookie_saved_at = Some(now - 10 * 3600);
+        assert_eq!(
+            client.login_refresh_delay_at(now, day),
+            Duration::from_secs(14 * 3600)
+        );
+        client.state.cookie_saved_at = Some(now - 3 * 24 * 3600);
+        assert_eq!(client.login_refresh_delay_at(now, day), Duration::ZERO);
+
+        client.state.cookies.remove("MUSIC_U");
+        assert_eq!(client.login_refresh_delay_at(now, day), day);
+    }
+
+    #[test]
+    fn playlist_manipulate_joins_all_ids_into_one_request() {
+        let ids: Vec<i64> = (1..=200).map(|i| 1_000_000_000 + i).collect();
+        let params = playlist_manipulate_params(PlaylistTrackOp::Add, 42, &ids).expect("params");
+
+        assert_eq!(params["op"], "add");
+        assert_eq!(params["pid"], 42);
+        assert_eq!(params["imme"], "true");
+        let track_ids = params["trackIds"].as_str().expect("trackIds 为字符串");
+        assert!(track_ids.starts_with("[1000000001,1000000002,"));
+        let parsed: Vec<i64> = serde_json::from_str(track_ids).expect("数组");
+        assert_eq!(parsed, ids);
     }
 }
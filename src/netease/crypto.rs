@@ -115,8 +115,12 @@ fn random_base62_16() -> [u8; 16] {
 }
 
 pub fn weapi(data: &Value) -> Result<WeapiForm, CryptoError> {
+    weapi_with_secret(data, random_base62_16())
+}
+
+/// 用指定的随机密钥加密（测试中固定密钥以得到确定的密文）
+fn weapi_with_secret(data: &Value, sk: [u8; 16]) -> Result<WeapiForm, CryptoError> {
     let text = serde_json::to_string(data).map_err(CryptoError::BadJson)?;
-    let sk = random_base62_16();
 
     let p1 = aes_128_cbc_encrypt_base64(text.as_bytes(), PRESET_KEY.as_bytes(), IV.as_bytes())?;
     let params = aes_128_cbc_encrypt_base64(p1.as_bytes(), &sk, IV.as_bytes())?;
@@ -151,12 +155,34 @@ pub fn eapi(uri: &str, data: &Value) -> Result<EapiForm, CryptoError> {
     Ok(EapiForm { params })
 }
 
+/// 解密 eapi 请求的 `params`，返回（接口路径, 请求数据）；校验摘要
+pub fn eapi_req_decrypt(params_hex: &str) -> Result<(String, Value), CryptoError> {
+    let pt = aes_128_ecb_decrypt_hex(params_hex, EAPI_KEY.as_bytes())?;
+    let payload = String::from_utf8(pt).map_err(CryptoError::BadUtf8)?;
+    let mut parts = payload.splitn(3, "-36cd479b6b5-");
+    let (Some(uri), Some(text), Some(digest)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(CryptoError::BadPayload);
+    };
+    let mut hasher = Md5::new();
+    hasher.update(format!("nobody{uri}use{text}md5forencrypt").as_bytes());
+    if hex::encode(hasher.finalize()) != digest {
+        return Err(CryptoError::BadPayload);
+    }
+    let data = serde_json::from_str(text).map_err(CryptoError::BadJson)?;
+    Ok((uri.to_owned(), data))
+}
+
 pub fn eapi_res_decrypt_json(ct_hex_upper: &str) -> Result<Value, CryptoError> {
     let pt = aes_128_ecb_decrypt_hex(ct_hex_upper, EAPI_KEY.as_bytes())?;
     let s = String::from_utf8(pt).map_err(CryptoError::BadUtf8)?;
     serde_json::from_str(&s).map_err(CryptoError::BadJson)
 }
 
+/// 手机号登录提交的密码摘要：MD5 的小写十六进制（与上游 `CryptoJS.MD5(password)` 一致）
+pub fn password_md5(password: &str) -> String {
+    hex::encode(Md5::digest(password.as_bytes()))
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum CryptoError {
     #[error("AES 加密 padding 错误")]
@@ -171,4 +197,89 @@ pub enum CryptoError {
     BadUtf8(std::string::FromUtf8Error),
     #[error("JSON 解析失败: {0}")]
     BadJson(serde_json::Error),
+    #[error("eapi 请求格式或摘要不符")]
+    BadPayload,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// 固定的 weapi 随机密钥；下面的期望值由上游 Node 实现（crypto + querystring）以同一密钥生成
+    const SECRET: &[u8; 16] = b"aB3dE5gH7jK9mN1p";
+    const ENC_SEC_KEY: &str = "5f476cc68f214f71b9af74ccf0072b690884ccd87d24ea673e94f1ffd427d43cfabb2e00738fac722d6ce89338a13b1a09fc7459bc266471690df49fe8d62016b5699253c87dbe3cfb6a66cfd90059c608e4d26473e31129559bc001b8c45c6316d5138c406612285b51a806b0c48c12c2a1decc87e17138684cfae77481f721";
+
+    fn weapi_decrypt(params: &str, sk: &[u8]) -> Value {
+        let cbc_decrypt = |b64: &str, key: &[u8]| -> Vec<u8> {
+            let mut buf = base64::engine::general_purpose::STANDARD
+                .decode(b64)
+                .expect("base64");
+            Aes128CbcDec::new(key.into(), IV.as_bytes().into())
+                .decrypt_padded_mut::<Pkcs7>(&mut buf)
+                .expect("padding")
+                .to_vec()
+        };
+        let inner = String::from_utf8(cbc_decrypt(params, sk)).expect("utf8");
+        serde_json::from_slice(&cbc_decrypt(&inner, PRESET_KEY.as_bytes())).expect("json")
+    }
+
+    #[test]
+    fn weapi_matches_upstream_ciphertext_and_round_trips() {
+        let cases = [
+            (
+                "AC/DC",
+                "QaJbeLmi2V6uDHcCX3CitfFfhHSBZZndzdpgpGvtBDgUtXuWc70DXDQVwnaCJH1N",
+            ),
+            (
+                "Beyond+",
+                "QaJbeLmi2V6uDHcCX3CitU3iE0t6arfzrQdXwWRg1SNqStyeBigTEzirXcjgJAj9",
+            ),
+            (
+                "周杰伦 🎵",
+                "QaJbeLmi2V6uDHcCX3CitSMi31KGSA8iscI2byB+dJrl/ZKtXB+hmrSU7/d1SAYs3xunCDEclk2ykV3/kPg1kedzOGHaVi3MLBKmDOvXjuE=",
+            ),
+        ];
+        for (keyword, params) in cases {
+            let data = json!({ "csrf_token": "", "s": keyword });
+            let form = weapi_with_secret(&data, *SECRET).expect("weapi");
+            assert_eq!(form.params, params, "{keyword}");
+            assert_eq!(form.enc_sec_key, ENC_SEC_KEY);
+            assert_eq!(weapi_decrypt(&form.params, SECRET), data);
+        }
+    }
+
+    #[test]
+    fn eapi_matches_upstream_ciphertext_and_round_trips() {
+        let uri = "/api/cloudsearch/pc";
+        let data = json!({ "s": "Beyond+" });
+        let form = eapi(uri, &data).expect("eapi");
+        assert_eq!(
+            form.params,
+            "2B5D64177AA6460FBAA3DCB1285E28954BBB4F7556E09B0FB25750F12398BB50B02DD26AFAB2BD9687DE749C1DA71C9DE916324A5980D9A4EE3CDF7EF23CB39FFAF0EC3A459C67E6C135FE1A3B3E14EE37799E02AAACD93400A2931C9A89B6F7"
+        );
+        for keyword in ["AC/DC", "Beyond+", "周杰伦 🎵"] {
+            let data = json!({ "s": keyword });
+            let form = eapi(uri, &data).expect("eapi");
+            assert_eq!(
+                eapi_req_decrypt(&form.params).expect("decrypt"),
+                (uri.to_owned(), data)
+            );
+        }
+    }
+
+    #[test]
+    fn weapi_uses_a_fresh_secret_per_request() {
+        let data = json!({ "s": "x" });
+        let a = weapi(&data).expect("weapi");
+        let b = weapi(&data).expect("weapi");
+        assert_ne!(a.params, b.params);
+        assert_ne!(a.enc_sec_key, b.enc_sec_key);
+    }
+
+    #[test]
+    fn password_md5_is_lowercase_hex() {
+        assert_eq!(password_md5("password"), "5f4dcc3b5aa765d61d8327deb882cf99");
+        assert_eq!(password_md5("密码"), "a8105204604a0b11e916f3879aae3b0b");
+    }
 }
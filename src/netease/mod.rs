@@ -4,6 +4,7 @@ mod crypto;
 pub mod models;
 mod util;
 
-pub use client::{NeteaseClient, NeteaseClientConfig, QrPlatform};
+pub use client::{NeteaseClient, NeteaseClientConfig, QrPlatform, parse_proxy, proxy_from_env};
 #[allow(unused_imports)]
 pub use crypto::CryptoMode;
+pub use crypto::password_md5;
@@ -1,8 +1,12 @@
-use crate::domain::model::{Account, LoginStatus, LyricLine, Playlist, Song, SongUrl};
+use crate::domain::model::{
+    Account, Album, Artist, LoginStatus, LyricLine, Playlist, Song, SongAvailability, SongPreview,
+    SongUrl,
+};
 
 use super::dto::{
-    CloudSearchResp, LoginQrCheckResp, LoginQrKeyResp, LyricResp, PlaylistDetailResp,
-    SongDetailResp, SongUrlResp, UserAccountResp, UserPlaylistResp,
+    AlbumDetailResp, ArtistTopSongsResp, CloudSearchResp, LikeListResp, LikeResp, LoginQrCheckResp,
+    LoginQrKeyResp, LyricResp, PersonalFmResp, PlaylistDetailResp, PlaylistManipulateResp,
+    RecommendSongsResp, SongDetailResp, SongUrlResp, UserAccountResp, UserPlaylistResp,
 };
 
 #[derive(Debug, thiserror::Error)]
@@ -13,6 +17,8 @@ pub enum ModelError {
     BadJson(#[from] serde_json::Error),
     #[error("响应为空")]
     Empty,
+    #[error("接口返回错误 code={0}")]
+    BadCode(i64),
 }
 
 pub fn extract_unikey(resp: LoginQrKeyResp) -> Result<String, ModelError> {
@@ -42,14 +48,26 @@ pub fn to_account(resp: UserAccountResp) -> Result<Account, ModelError> {
     Ok(Account { uid, nickname })
 }
 
-pub fn to_playlists(resp: UserPlaylistResp) -> Vec<Playlist> {
+/// `uid` 为当前登录用户：接口标记 `subscribed` 或创建者不是自己的歌单视为收藏
+pub fn to_playlists(resp: UserPlaylistResp, uid: i64) -> Vec<Playlist> {
     resp.playlist
         .into_iter()
-        .map(|p| Playlist {
-            id: p.id,
-            name: p.name,
-            track_count: p.track_count,
-            special_type: p.special_type,
+        .map(|p| {
+            let subscribed = p.subscribed || p.creator.as_ref().is_some_and(|c| c.user_id != uid);
+            let updated_at_ms = p
+                .update_time
+                .into_iter()
+                .chain(p.track_update_time)
+                .filter(|t| *t > 0)
+                .max();
+            Playlist {
+                id: p.id,
+                name: p.name,
+                track_count: p.track_count,
+                special_type: p.special_type,
+                subscribed,
+                updated_at_ms,
+            }
         })
         .collect()
 }
@@ -61,65 +79,243 @@ pub fn to_song_list_from_search(resp: CloudSearchResp) -> Vec<Song> {
     result.songs.into_iter().map(to_song).collect()
 }
 
+pub fn to_album_list_from_search(resp: CloudSearchResp) -> Vec<Album> {
+    let Some(result) = resp.result else {
+        return vec![];
+    };
+    result
+        .albums
+        .into_iter()
+        .map(|a| {
+            let artists = if !a.artists.is_empty() {
+                a.artists
+            } else {
+                a.artist.into_iter().collect()
+            };
+            Album {
+                id: a.id,
+                name: a.name,
+                artists: artists
+                    .into_iter()
+                    .map(|a| a.name)
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                track_count: a.size,
+            }
+        })
+        .collect()
+}
+
+pub fn to_artist_list_from_search(resp: CloudSearchResp) -> Vec<Artist> {
+    let Some(result) = resp.result else {
+        return vec![];
+    };
+    result
+        .artists
+        .into_iter()
+        .map(|a| Artist {
+            id: a.id,
+            name: a.name,
+            music_size: a.music_size,
+            album_size: a.album_size,
+        })
+        .collect()
+}
+
+/// 歌手热门歌曲，最多保留 50 首
+pub fn to_song_list_from_artist_top(resp: ArtistTopSongsResp) -> Vec<Song> {
+    resp.songs.into_iter().take(50).map(to_song).collect()
+}
+
+/// 每日推荐歌曲；游客调用时接口返回 code 301
+pub fn to_song_list_from_recommend(resp: RecommendSongsResp) -> Result<Vec<Song>, ModelError> {
+    if resp.code != 200 {
+        return Err(ModelError::BadCode(resp.code));
+    }
+    Ok(resp
+        .data
+        .map(|d| d.daily_songs.into_iter().map(to_song).collect())
+        .unwrap_or_default())
+}
+
+/// 私人 FM 歌曲；游客调用时接口返回 code 301
+pub fn to_song_list_from_personal_fm(resp: PersonalFmResp) -> Result<Vec<Song>, ModelError> {
+    if resp.code != 200 {
+        return Err(ModelError::BadCode(resp.code));
+    }
+    Ok(resp.data.into_iter().map(to_song).collect())
+}
+
 pub fn to_song_list_from_detail(resp: SongDetailResp) -> Vec<Song> {
     resp.songs.into_iter().map(to_song).collect()
 }
 
+pub fn to_song_list_from_album(resp: AlbumDetailResp) -> Vec<Song> {
+    resp.songs.into_iter().map(to_song).collect()
+}
+
+/// 歌曲详情 → 搜索预览（可播放性综合 `fee` 与 `privileges[].st`）
+pub fn to_song_previews(resp: SongDetailResp) -> Vec<SongPreview> {
+    let blocked: std::collections::HashSet<i64> = resp
+        .privileges
+        .iter()
+        .filter(|p| p.st < 0)
+        .map(|p| p.id)
+        .collect();
+    resp.songs
+        .into_iter()
+        .map(|s| {
+            let availability = if blocked.contains(&s.id) || s.no_copyright_rcmd.is_some() {
+                SongAvailability::Unavailable
+            } else {
+                match s.fee {
+                    1 => SongAvailability::Vip,
+                    4 => SongAvailability::Paid,
+                    _ => SongAvailability::Free,
+                }
+            };
+            let publish_year = s
+                .publish_time
+                .filter(|&ms| ms > 0)
+                .and_then(chrono::DateTime::from_timestamp_millis)
+                .map(|t| chrono::Datelike::year(&t));
+            SongPreview {
+                id: s.id,
+                album: s.al.map(|al| al.name).unwrap_or_default(),
+                duration_ms: s.duration_ms,
+                publish_year,
+                availability,
+            }
+        })
+        .collect()
+}
+
 fn to_song(s: super::dto::SongInfo) -> Song {
     let artists = if !s.ar.is_empty() { s.ar } else { s.artists };
+    let artist_ids = artists.iter().map(|a| a.id).collect();
     let artists = artists
         .into_iter()
         .map(|a| a.name)
         .collect::<Vec<_>>()
         .join("/");
+    let (album, album_id, cover_url) =
+        s.al.map(|al| {
+            let cover = al.pic_url.filter(|u| !u.is_empty());
+            (al.name, Some(al.id).filter(|id| *id > 0), cover)
+        })
+        .unwrap_or_default();
+    let translated = s
+        .tns
+        .into_iter()
+        .map(|t| t.trim().to_owned())
+        .find(|t| !t.is_empty());
     Song {
         id: s.id,
         name: s.name,
         artists,
         duration_ms: s.duration_ms,
+        album,
+        album_id,
+        artist_ids,
+        translated,
+        cover_url,
+        track_no: s.no.filter(|n| *n > 0),
     }
 }
 
-pub fn to_playlist_track_ids(resp: PlaylistDetailResp) -> Vec<i64> {
-    resp.playlist
-        .map(|p| p.track_ids.into_iter().map(|t| t.id).collect())
-        .unwrap_or_default()
+/// 歌单的原始歌曲顺序，以及各歌曲加入歌单的时间（id -> 毫秒时间戳，缺失的不记录）
+pub fn to_playlist_track_ids(
+    resp: PlaylistDetailResp,
+) -> (Vec<i64>, std::collections::HashMap<i64, i64>) {
+    let track_ids = resp.playlist.map(|p| p.track_ids).unwrap_or_default();
+    let added_at = track_ids
+        .iter()
+        .filter_map(|t| Some((t.id(), t.added_at()?)))
+        .collect();
+    (track_ids.iter().map(|t| t.id()).collect(), added_at)
 }
 
 pub fn to_song_url(resp: SongUrlResp) -> Result<SongUrl, ModelError> {
     let it = resp.data.into_iter().next().ok_or(ModelError::Empty)?;
     let url = it.url.ok_or(ModelError::MissingField("data[0].url"))?;
-    Ok(SongUrl { id: it.id, url })
+    Ok(SongUrl {
+        id: it.id,
+        url,
+        size: it.size.filter(|s| *s > 0),
+        br: it.br.filter(|b| *b > 0),
+        gain_db: it.gain.filter(|g| g.is_finite() && *g != 0.0),
+    })
+}
+
+pub fn to_liked_ids(resp: LikeListResp) -> Vec<i64> {
+    resp.ids
+}
+
+/// 喜欢/取消喜欢接口只返回 code，非 200 视为失败
+pub fn check_like(resp: LikeResp) -> Result<(), ModelError> {
+    match resp.code {
+        200 => Ok(()),
+        code => Err(ModelError::BadCode(code)),
+    }
 }
 
-pub fn to_lyrics(resp: LyricResp) -> Vec<LyricLine> {
-    let original = resp
+/// 检查歌单增删结果，返回实际生效的歌曲 id（保持 `requested` 的顺序）
+///
+/// 返回中没有可解析的 `trackIds` 时视为请求的歌曲全部生效。
+pub fn check_playlist_manipulate(
+    resp: PlaylistManipulateResp,
+    requested: &[i64],
+) -> Result<Vec<i64>, ModelError> {
+    if resp.code != 200 {
+        return Err(ModelError::BadCode(resp.code));
+    }
+    let reported: Option<Vec<i64>> = match resp.track_ids {
+        Some(serde_json::Value::String(s)) => serde_json::from_str(&s).ok(),
+        Some(v @ serde_json::Value::Array(_)) => serde_json::from_value(v).ok(),
+        _ => None,
+    };
+    Ok(match reported {
+        Some(ids) => requested
+            .iter()
+            .copied()
+            .filter(|id| ids.contains(id))
+            .collect(),
+        None => requested.to_vec(),
+    })
+}
+
+/// 时间戳上限（24h），超出的时间戳会被截到该值
+pub const MAX_LYRIC_TIME_MS: u64 = 24 * 60 * 60 * 1000;
+/// 截断时追加的最后一行
+pub const LYRICS_TRUNCATED_TEXT: &str = "……歌词过长已截断";
+/// 与前后相邻行（按原文顺序）同时相差超过该值的时间戳视为异常值并丢弃
+const NON_MONOTONIC_TOLERANCE_MS: u64 = 10 * 60 * 1000;
+
+/// 转换歌词；最多保留 `max_lines` 行（含截断提示行），结果按时间升序
+pub fn to_lyrics(resp: LyricResp, max_lines: usize) -> Vec<LyricLine> {
+    let mut original = resp
         .lrc
         .map(|b| parse_lrc_original(&b.lyric))
         .unwrap_or_default();
+    truncate_lyrics(&mut original, max_lines);
     let translation = resp
         .tlyric
-        .map(|b| parse_lrc_translation(&b.lyric))
+        .map(|b| parse_lrc_secondary(&b.lyric))
+        .unwrap_or_default();
+    let roman = resp
+        .romalrc
+        .map(|b| parse_lrc_secondary(&b.lyric))
         .unwrap_or_default();
 
-    if translation.is_empty() {
+    if translation.is_empty() && roman.is_empty() {
         return original;
     }
 
-    let mut trans_map = std::collections::HashMap::<u64, String>::new();
-    for it in translation {
-        trans_map.entry(it.time_ms).or_insert(it.text);
+    for l in &mut original {
+        l.translation = translation.get(&l.time_ms).cloned();
+        l.roman = roman.get(&l.time_ms).cloned();
     }
-
     original
-        .into_iter()
-        .map(|mut l| {
-            if let Some(t) = trans_map.get(&l.time_ms) {
-                l.translation = Some(t.clone());
-            }
-            l
-        })
-        .collect()
 }
 
 fn parse_lrc_original(text: &str) -> Vec<LyricLine> {
@@ -132,21 +328,37 @@ fn parse_lrc_original(text: &str) -> Vec<LyricLine> {
             Some(LyricLine {
                 time_ms,
                 text: content,
-                translation: None,
+                ..Default::default()
             })
         })
         .collect()
 }
 
-fn parse_lrc_translation(text: &str) -> Vec<LyricLine> {
-    parse_lrc_text(text, true)
-        .into_iter()
-        .map(|(time_ms, content)| LyricLine {
-            time_ms,
-            text: content,
-            translation: None,
-        })
-        .collect()
+/// 翻译/罗马音按时间戳索引，与原文合并；同一时间戳只取第一行，空行忽略
+fn parse_lrc_secondary(text: &str) -> std::collections::HashMap<u64, String> {
+    let mut out = std::collections::HashMap::new();
+    for (time_ms, content) in parse_lrc_text(text, true) {
+        if !content.trim().is_empty() {
+            out.entry(time_ms).or_insert(content);
+        }
+    }
+    out
+}
+
+/// 超出上限时截断，并以提示行结尾
+fn truncate_lyrics(lines: &mut Vec<LyricLine>, max_lines: usize) {
+    let max_lines = max_lines.max(1);
+    if lines.len() <= max_lines {
+        return;
+    }
+    tracing::warn!(lines = lines.len(), max_lines, "歌词行数超出上限，已截断");
+    lines.truncate(max_lines - 1);
+    let time_ms = lines.last().map_or(0, |l| l.time_ms);
+    lines.push(LyricLine {
+        time_ms,
+        text: LYRICS_TRUNCATED_TEXT.to_owned(),
+        ..Default::default()
+    });
 }
 
 fn parse_lrc_text(text: &str, allow_empty_text: bool) -> Vec<(u64, String)> {
@@ -180,13 +392,49 @@ fn parse_lrc_text(text: &str, allow_empty_text: bool) -> Vec<(u64, String)> {
         }
     }
 
+    let mut out = drop_timestamp_outliers(out);
     out.sort_by_key(|(t, _)| *t);
     out
 }
 
+/// 丢弃按原文顺序与前后相邻行同时相差过大的时间戳（如夹在正常歌词中的 `[99:99.999]`）
+///
+/// 其余乱序（如合唱行的多个时间戳）交给排序处理。
+fn drop_timestamp_outliers(lines: Vec<(u64, String)>) -> Vec<(u64, String)> {
+    if lines.len() < 2 {
+        return lines;
+    }
+    let times: Vec<u64> = lines.iter().map(|(t, _)| *t).collect();
+    let far_above = |t: u64, other: Option<&u64>| {
+        other.is_none_or(|o| t > o.saturating_add(NON_MONOTONIC_TOLERANCE_MS))
+    };
+    let far_below = |t: u64, other: Option<&u64>| {
+        other.is_none_or(|o| t.saturating_add(NON_MONOTONIC_TOLERANCE_MS) < *o)
+    };
+    lines
+        .into_iter()
+        .enumerate()
+        .filter(|(i, (t, _))| {
+            let prev = i.checked_sub(1).and_then(|j| times.get(j));
+            let next = times.get(i + 1);
+            let outlier = (far_above(*t, prev) && far_above(*t, next))
+                || (far_below(*t, prev) && far_below(*t, next));
+            if outlier {
+                tracing::debug!(time_ms = t, "丢弃异常的歌词时间戳");
+            }
+            !outlier
+        })
+        .map(|(_, line)| line)
+        .collect()
+}
+
+/// 解析 `mm:ss.xx` / `mm:ss.xxx`，结果截到 [0, 24h]（负数视为 0）
 fn parse_lrc_timestamp_ms(tag: &str) -> Option<u64> {
-    // mm:ss.xx or mm:ss.xxx
     let (mm, rest) = tag.split_once(':')?;
+    let (negative, mm) = match mm.strip_prefix('-') {
+        Some(mm) => (true, mm),
+        None => (false, mm),
+    };
     let mm: u64 = mm.parse().ok()?;
     let (ss, frac) = rest.split_once('.').unwrap_or((rest, ""));
     let ss: u64 = ss.parse().ok()?;
@@ -206,12 +454,69 @@ fn parse_lrc_timestamp_ms(tag: &str) -> Option<u64> {
         2 => frac_val * 10,
         _ => frac_val,
     };
-    Some(mm * 60_000 + ss * 1_000 + frac_ms)
+    if negative {
+        return Some(0);
+    }
+    let ms = mm
+        .saturating_mul(60_000)
+        .saturating_add(ss.saturating_mul(1_000))
+        .saturating_add(frac_ms);
+    Some(ms.min(MAX_LYRIC_TIME_MS))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::settings::DEFAULT_LYRICS_MAX_LINES;
+
+    #[test]
+    fn test_check_like_rejects_non_200_code() {
+        assert!(check_like(LikeResp { code: 200 }).is_ok());
+        assert!(matches!(
+            check_like(LikeResp { code: 301 }),
+            Err(ModelError::BadCode(301))
+        ));
+    }
+
+    #[test]
+    fn check_playlist_manipulate_keeps_error_code() {
+        let resp = |code| PlaylistManipulateResp {
+            code,
+            track_ids: None,
+        };
+        assert_eq!(
+            check_playlist_manipulate(resp(200), &[1, 2]).unwrap(),
+            [1, 2]
+        );
+        assert!(matches!(
+            check_playlist_manipulate(resp(502), &[1]),
+            Err(ModelError::BadCode(502))
+        ));
+    }
+
+    #[test]
+    fn check_playlist_manipulate_reports_only_changed_ids() {
+        let resp = |track_ids| PlaylistManipulateResp {
+            code: 200,
+            track_ids: Some(track_ids),
+        };
+        let requested: Vec<i64> = (1..=10).collect();
+
+        // 第 3、7 首已在歌单中，接口只返回其余 8 首（字符串形式）
+        let changed =
+            check_playlist_manipulate(resp(serde_json::json!("[10,9,8,6,5,4,2,1]")), &requested)
+                .unwrap();
+        assert_eq!(changed, [1, 2, 4, 5, 6, 8, 9, 10]);
+
+        let changed =
+            check_playlist_manipulate(resp(serde_json::json!([4, 5])), &requested).unwrap();
+        assert_eq!(changed, [4, 5]);
+
+        // 无法解析时按全部成功处理
+        let changed =
+            check_playlist_manipulate(resp(serde_json::json!("oops")), &requested).unwrap();
+        assert_eq!(changed, requested);
+    }
 
     #[test]
     fn test_extract_unikey_from_top_level() {
@@ -317,8 +622,11 @@ mod tests {
             tlyric: Some(crate::netease::models::dto::LyricBlock {
                 lyric: "[00:01.00]Translated line\n[00:03.00]Only translation".to_owned(),
             }),
+            romalrc: Some(crate::netease::models::dto::LyricBlock {
+                lyric: "[00:02.00]se-kan-do rain\n[00:01.00] ".to_owned(),
+            }),
         };
-        let lyrics = to_lyrics(resp);
+        let lyrics = to_lyrics(resp, DEFAULT_LYRICS_MAX_LINES);
         assert_eq!(lyrics.len(), 2);
         assert_eq!(lyrics[0].time_ms, 1000);
         assert_eq!(lyrics[0].text, "Original line");
@@ -326,6 +634,9 @@ mod tests {
         assert_eq!(lyrics[1].time_ms, 2000);
         assert_eq!(lyrics[1].text, "Second line");
         assert_eq!(lyrics[1].translation, None);
+        // 罗马音与翻译各自按时间戳合并，空行视为没有
+        assert_eq!(lyrics[0].roman, None);
+        assert_eq!(lyrics[1].roman.as_deref(), Some("se-kan-do rain"));
     }
 
     #[test]
@@ -335,8 +646,9 @@ mod tests {
                 lyric: "[00:01.00]Original line".to_owned(),
             }),
             tlyric: None,
+            romalrc: None,
         };
-        let lyrics = to_lyrics(resp);
+        let lyrics = to_lyrics(resp, DEFAULT_LYRICS_MAX_LINES);
         assert_eq!(lyrics.len(), 1);
         assert_eq!(lyrics[0].text, "Original line");
         assert_eq!(lyrics[0].translation, None);
@@ -348,11 +660,47 @@ mod tests {
             data: vec![crate::netease::models::dto::SongUrlItem {
                 id: 12345,
                 url: Some("https://example.com/song.mp3".to_owned()),
+                size: Some(4_000_000),
+                br: Some(128_000),
+                gain: Some(-3.5),
             }],
         };
         let song_url = to_song_url(resp).unwrap();
         assert_eq!(song_url.id, 12345);
         assert_eq!(song_url.url, "https://example.com/song.mp3");
+        assert_eq!(song_url.size, Some(4_000_000));
+        assert_eq!(song_url.br, Some(128_000));
+        assert_eq!(song_url.gain_db, Some(-3.5));
+    }
+
+    #[test]
+    fn test_song_url_effective_br_detects_downgrade() {
+        let song_url = |br| SongUrl {
+            id: 1,
+            url: String::new(),
+            size: None,
+            br,
+            gain_db: None,
+        };
+        // 请求 320k，非会员只拿到 128k
+        assert_eq!(song_url(Some(128_000)).effective_br(320_000), 128_000);
+        assert_eq!(song_url(Some(320_000)).effective_br(320_000), 320_000);
+        // 无损文件的实际码率不固定，不视为降级
+        assert_eq!(song_url(Some(1_411_000)).effective_br(999_000), 999_000);
+        assert_eq!(song_url(Some(850_000)).effective_br(999_000), 999_000);
+        // 实际码率高于请求时仍按请求的音质
+        assert_eq!(song_url(Some(999_000)).effective_br(320_000), 320_000);
+        assert_eq!(song_url(None).effective_br(320_000), 320_000);
+    }
+
+    #[test]
+    fn test_fallback_br_walks_down_the_ladder() {
+        use crate::domain::model::fallback_br;
+        assert_eq!(fallback_br(999_000), Some(320_000));
+        assert_eq!(fallback_br(320_000), Some(128_000));
+        // 设置为 192k 时直接降到 128k
+        assert_eq!(fallback_br(192_000), Some(128_000));
+        assert_eq!(fallback_br(128_000), None);
     }
 
     #[test]
@@ -367,6 +715,9 @@ mod tests {
             data: vec![crate::netease::models::dto::SongUrlItem {
                 id: 12345,
                 url: None,
+                size: None,
+                br: None,
+                gain: None,
             }],
         };
         assert!(matches!(
@@ -384,20 +735,396 @@ mod tests {
                     name: "Favorite".to_owned(),
                     track_count: 100,
                     special_type: 0,
+                    subscribed: false,
+                    creator: None,
+                    update_time: None,
+                    track_update_time: None,
                 },
                 crate::netease::models::dto::PlaylistInfo {
                     id: 2,
                     name: "Liked".to_owned(),
                     track_count: 50,
                     special_type: 1,
+                    subscribed: false,
+                    creator: None,
+                    update_time: None,
+                    track_update_time: None,
                 },
             ],
         };
-        let playlists = to_playlists(resp);
+        let playlists = to_playlists(resp, 9);
         assert_eq!(playlists.len(), 2);
         assert_eq!(playlists[0].id, 1);
         assert_eq!(playlists[0].name, "Favorite");
         assert_eq!(playlists[0].track_count, 100);
         assert_eq!(playlists[1].special_type, 1);
+        assert!(!playlists[0].subscribed, "缺少创建者信息时视为自己的歌单");
+    }
+
+    #[test]
+    fn test_to_playlists_detects_ownership() {
+        let resp: UserPlaylistResp = serde_json::from_value(serde_json::json!({
+            "playlist": [
+                { "id": 1, "name": "我喜欢的音乐", "specialType": 5,
+                  "subscribed": false, "creator": { "userId": 42 } },
+                { "id": 2, "name": "别人的歌单", "subscribed": true,
+                  "creator": { "userId": 7 } },
+                // subscribed 缺失时按创建者判断
+                { "id": 3, "name": "未标记的收藏", "creator": { "userId": 7 } },
+                { "id": 4, "name": "自建", "creator": { "userId": 42 } }
+            ]
+        }))
+        .expect("parse");
+        let subscribed: Vec<_> = to_playlists(resp, 42)
+            .iter()
+            .map(|p| (p.id, p.subscribed))
+            .collect();
+        assert_eq!(
+            subscribed,
+            vec![(1, false), (2, true), (3, true), (4, false)]
+        );
+    }
+
+    #[test]
+    fn test_to_playlists_takes_latest_update_time() {
+        let resp: UserPlaylistResp = serde_json::from_value(serde_json::json!({
+            "playlist": [
+                { "id": 1, "name": "a", "updateTime": 1_700_000_000_000_i64,
+                  "trackUpdateTime": 1_700_000_500_000_i64 },
+                { "id": 2, "name": "b", "updateTime": 1_700_000_000_000_i64, "trackUpdateTime": 0 },
+                { "id": 3, "name": "c" }
+            ]
+        }))
+        .expect("parse");
+        let updated: Vec<_> = to_playlists(resp, 42)
+            .iter()
+            .map(|p| p.updated_at_ms)
+            .collect();
+        assert_eq!(
+            updated,
+            vec![Some(1_700_000_500_000), Some(1_700_000_000_000), None]
+        );
+    }
+
+    #[test]
+    fn test_to_song_list_from_detail_keeps_album_cover_and_track_no() {
+        let resp: SongDetailResp = serde_json::from_value(serde_json::json!({
+            "songs": [
+                {
+                    "id": 1,
+                    "name": "晴天",
+                    "dt": 269000,
+                    "ar": [{"id": 6452, "name": "周杰伦"}, {"id": 7, "name": "合唱"}],
+                    "al": {"id": 18905, "name": "叶惠美", "picUrl": "http://p1.music.126.net/a.jpg"},
+                    "no": 3
+                },
+                {"id": 2, "name": "无专辑", "artists": [{"name": "未知"}], "no": 0}
+            ]
+        }))
+        .expect("parse");
+        let songs = to_song_list_from_detail(resp);
+
+        assert_eq!(songs[0].artists, "周杰伦/合唱");
+        assert_eq!(songs[0].artist_ids, vec![6452, 7]);
+        assert_eq!(songs[0].album, "叶惠美");
+        assert_eq!(songs[0].album_id, Some(18905));
+        assert_eq!(
+            songs[0].cover_url.as_deref(),
+            Some("http://p1.music.126.net/a.jpg")
+        );
+        assert_eq!(songs[0].track_no, Some(3));
+        assert_eq!(songs[1].album, "");
+        assert_eq!(songs[1].cover_url, None);
+        assert_eq!(songs[1].track_no, None);
+        assert_eq!(songs[1].album_id, None);
+        assert_eq!(songs[1].artist_ids, vec![0]);
+    }
+
+    #[test]
+    fn test_to_song_keeps_original_name_and_first_translation() {
+        let resp: SongDetailResp = serde_json::from_value(serde_json::json!({
+            "songs": [
+                {"id": 1, "name": "밤편지", "tns": ["夜信", "夜晚的信"]},
+                {"id": 2, "name": "Blueming", "transNames": ["  "]},
+                {"id": 3, "name": "晴天"}
+            ]
+        }))
+        .expect("parse");
+        let songs = to_song_list_from_detail(resp);
+
+        assert_eq!(songs[0].name, "밤편지");
+        assert_eq!(songs[0].translated.as_deref(), Some("夜信"));
+        assert_eq!(songs[1].translated, None);
+        assert_eq!(songs[2].translated, None);
+    }
+
+    #[test]
+    fn test_to_album_list_from_search_joins_artists() {
+        let resp: CloudSearchResp = serde_json::from_value(serde_json::json!({
+            "result": {
+                "albums": [
+                    {"id": 18905, "name": "叶惠美", "size": 11,
+                     "artists": [{"id": 6452, "name": "周杰伦"}]},
+                    {"id": 2, "name": "合辑", "size": 3,
+                     "artists": [{"name": "A"}, {"name": "B"}]},
+                    {"id": 3, "name": "单主歌手", "artist": {"name": "C"}}
+                ]
+            }
+        }))
+        .expect("parse");
+        let albums = to_album_list_from_search(resp);
+
+        assert_eq!(albums.len(), 3);
+        assert_eq!(albums[0].name, "叶惠美");
+        assert_eq!(albums[0].artists, "周杰伦");
+        assert_eq!(albums[0].track_count, 11);
+        assert_eq!(albums[1].artists, "A/B");
+        assert_eq!(albums[2].artists, "C");
+        assert_eq!(albums[2].track_count, 0);
+    }
+
+    #[test]
+    fn test_to_artist_list_from_search() {
+        let resp: CloudSearchResp = serde_json::from_value(serde_json::json!({
+            "result": {
+                "artists": [
+                    {"id": 6452, "name": "周杰伦", "musicSize": 566, "albumSize": 39},
+                    {"id": 7, "name": "无统计"}
+                ]
+            }
+        }))
+        .unwrap();
+        let artists = to_artist_list_from_search(resp);
+        assert_eq!(artists.len(), 2);
+        assert_eq!(artists[0].name, "周杰伦");
+        assert_eq!((artists[0].music_size, artists[0].album_size), (566, 39));
+        assert_eq!((artists[1].music_size, artists[1].album_size), (0, 0));
+    }
+
+    #[test]
+    fn test_to_song_list_from_artist_top_keeps_at_most_50() {
+        let songs: Vec<_> = (1..=60)
+            .map(|id| serde_json::json!({"id": id, "name": format!("s{id}"), "ar": [{"id": 6452, "name": "周杰伦"}]}))
+            .collect();
+        let resp: ArtistTopSongsResp =
+            serde_json::from_value(serde_json::json!({ "songs": songs })).unwrap();
+        let songs = to_song_list_from_artist_top(resp);
+        assert_eq!(songs.len(), 50);
+        assert_eq!(songs[0].artists, "周杰伦");
+    }
+
+    #[test]
+    fn test_to_song_list_from_recommend() {
+        let resp: RecommendSongsResp = serde_json::from_value(serde_json::json!({
+            "code": 200,
+            "data": {"dailySongs": [
+                {"id": 1, "name": "晴天", "ar": [{"id": 6452, "name": "周杰伦"}]},
+                {"id": 2, "name": "七里香", "ar": [{"id": 6452, "name": "周杰伦"}]}
+            ]}
+        }))
+        .expect("parse");
+        let songs = to_song_list_from_recommend(resp).expect("songs");
+        assert_eq!(songs.len(), 2);
+        assert_eq!(songs[1].name, "七里香");
+
+        // 游客：code 301，无 data
+        let resp: RecommendSongsResp =
+            serde_json::from_value(serde_json::json!({"code": 301})).expect("parse");
+        assert!(matches!(
+            to_song_list_from_recommend(resp),
+            Err(ModelError::BadCode(301))
+        ));
+    }
+
+    #[test]
+    fn playlist_track_ids_accept_objects_and_plain_ids() {
+        let resp: PlaylistDetailResp = serde_json::from_value(serde_json::json!({
+            "playlist": {"trackIds": [
+                {"id": 3, "v": 12, "t": 0, "at": 1_700_000_300_000_i64, "uid": 1},
+                {"id": 1, "at": 0},
+                {"id": 2},
+                4
+            ]}
+        }))
+        .expect("parse");
+        let (ids, added_at) = to_playlist_track_ids(resp);
+        assert_eq!(ids, vec![3, 1, 2, 4]);
+        assert_eq!(
+            added_at,
+            std::collections::HashMap::from([(3, 1_700_000_300_000)])
+        );
+
+        let resp: PlaylistDetailResp =
+            serde_json::from_value(serde_json::json!({"playlist": {"trackIds": [5, 6]}}))
+                .expect("parse");
+        let (ids, added_at) = to_playlist_track_ids(resp);
+        assert_eq!(ids, vec![5, 6]);
+        assert!(added_at.is_empty());
+    }
+
+    #[test]
+    fn test_to_song_list_from_personal_fm() {
+        let resp: PersonalFmResp = serde_json::from_value(serde_json::json!({
+            "code": 200,
+            "data": [{
+                "id": 3,
+                "name": "稻香",
+                "duration": 223_000,
+                "artists": [{"id": 6452, "name": "周杰伦"}],
+                "album": {"id": 18896, "name": "魔杰座"}
+            }]
+        }))
+        .expect("parse");
+        let songs = to_song_list_from_personal_fm(resp).expect("songs");
+        assert_eq!(songs.len(), 1);
+        assert_eq!(songs[0].artists, "周杰伦");
+        assert_eq!(songs[0].album, "魔杰座");
+        assert_eq!(songs[0].duration_ms, Some(223_000));
+
+        let resp: PersonalFmResp =
+            serde_json::from_value(serde_json::json!({"code": 301})).expect("parse");
+        assert!(matches!(
+            to_song_list_from_personal_fm(resp),
+            Err(ModelError::BadCode(301))
+        ));
+    }
+
+    #[test]
+    fn test_to_song_list_from_album() {
+        let resp: AlbumDetailResp = serde_json::from_value(serde_json::json!({
+            "code": 200,
+            "album": {"id": 18905, "name": "叶惠美"},
+            "songs": [
+                {"id": 1, "name": "以父之名", "dt": 342000,
+                 "ar": [{"id": 6452, "name": "周杰伦"}], "al": {"id": 18905, "name": "叶惠美"}},
+                {"id": 2, "name": "晴天", "dt": 269000,
+                 "ar": [{"id": 6452, "name": "周杰伦"}], "al": {"id": 18905, "name": "叶惠美"}}
+            ]
+        }))
+        .expect("parse");
+        let songs = to_song_list_from_album(resp);
+
+        let names: Vec<_> = songs.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["以父之名", "晴天"]);
+        assert_eq!(songs[1].album_id, Some(18905));
+        assert_eq!(songs[1].duration_ms, Some(269_000));
+    }
+
+    #[test]
+    fn test_to_song_previews_reads_year_and_availability() {
+        let resp: SongDetailResp = serde_json::from_value(serde_json::json!({
+            "songs": [
+                {"id": 1, "name": "a", "dt": 269000, "al": {"id": 2, "name": "叶惠美"},
+                 "publishTime": 1059580800000i64, "fee": 8},
+                {"id": 2, "name": "b", "fee": 1, "publishTime": 0},
+                {"id": 3, "name": "c", "fee": 4},
+                {"id": 4, "name": "d", "fee": 0},
+                {"id": 5, "name": "e", "fee": 8, "noCopyrightRcmd": {"type": 1}}
+            ],
+            "privileges": [{"id": 4, "st": -200}, {"id": 1, "st": 0}]
+        }))
+        .expect("parse");
+        let previews = to_song_previews(resp);
+
+        assert_eq!(previews[0].album, "叶惠美");
+        assert_eq!(previews[0].duration_ms, Some(269_000));
+        assert_eq!(previews[0].publish_year, Some(2003));
+        assert_eq!(previews[0].availability, SongAvailability::Free);
+        assert_eq!(previews[1].publish_year, None);
+        assert_eq!(previews[1].availability, SongAvailability::Vip);
+        assert_eq!(previews[2].availability, SongAvailability::Paid);
+        assert_eq!(previews[3].availability, SongAvailability::Unavailable);
+        assert_eq!(previews[4].availability, SongAvailability::Unavailable);
+    }
+
+    #[test]
+    fn test_parse_lrc_timestamp_clamps_out_of_range() {
+        assert_eq!(parse_lrc_timestamp_ms("99:99.999"), Some(6_039_999));
+        assert_eq!(parse_lrc_timestamp_ms("-00:01.00"), Some(0));
+        assert_eq!(
+            parse_lrc_timestamp_ms("99999999999999:00.00"),
+            Some(MAX_LYRIC_TIME_MS)
+        );
+        assert_eq!(parse_lrc_timestamp_ms("ti:标题"), None);
+    }
+
+    #[test]
+    fn test_parse_lrc_text_drops_isolated_outlier_and_sorts_repeats() {
+        let text =
+            "[00:10.00]a\n[00:11.00]b\n[99:99.999]spam\n[00:12.00]c\n[00:13.00][00:02.00]chorus";
+        let result = parse_lrc_text(text, false);
+        let texts: Vec<&str> = result.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(texts, vec!["chorus", "a", "b", "c", "chorus"]);
+    }
+
+    fn lyric_resp(lrc: String) -> LyricResp {
+        LyricResp {
+            lrc: Some(crate::netease::models::dto::LyricBlock { lyric: lrc }),
+            tlyric: None,
+            romalrc: None,
+        }
+    }
+
+    #[test]
+    fn test_to_lyrics_truncates_oversized_payload() {
+        let lrc = (0..5000)
+            .map(|i| format!("[{:02}:{:02}.00]重复内容", i / 60, i % 60))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lyrics = to_lyrics(lyric_resp(lrc), 2000);
+        assert_eq!(lyrics.len(), 2000);
+        assert_eq!(lyrics[1998].text, "重复内容");
+        assert_eq!(lyrics[1999].text, LYRICS_TRUNCATED_TEXT);
+        assert!(lyrics.windows(2).all(|w| w[0].time_ms <= w[1].time_ms));
+    }
+
+    #[test]
+    fn test_to_lyrics_invariants_on_adversarial_input() {
+        use rand::rngs::StdRng;
+        use rand::{Rng, SeedableRng};
+
+        const TAGS: [&str; 8] = [
+            "[99:99.999]",
+            "[-01:00.00]",
+            "[00:00.00]",
+            "[ti:x]",
+            "[12345678901234567890:00]",
+            "[00:61.5]",
+            "[",
+            "[]",
+        ];
+        for seed in 0..64 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let max_lines = rng.gen_range(1..50);
+            let lines = rng.gen_range(0..200);
+            let lrc = (0..lines)
+                .map(|_| {
+                    let tag = if rng.gen_bool(0.3) {
+                        TAGS[rng.gen_range(0..TAGS.len())].to_owned()
+                    } else {
+                        let ms: u64 = rng.gen_range(0..400_000);
+                        format!(
+                            "[{:02}:{:02}.{:03}]",
+                            ms / 60_000,
+                            ms / 1000 % 60,
+                            ms % 1000
+                        )
+                    };
+                    format!("{tag}{}", "词".repeat(rng.gen_range(0..4)))
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let lyrics = to_lyrics(lyric_resp(lrc), max_lines);
+            assert!(lyrics.len() <= max_lines, "seed={seed}");
+            assert!(
+                lyrics.windows(2).all(|w| w[0].time_ms <= w[1].time_ms),
+                "seed={seed}"
+            );
+            assert!(
+                lyrics.iter().all(|l| l.time_ms <= MAX_LYRIC_TIME_MS),
+                "seed={seed}"
+            );
+        }
     }
 }
@@ -27,31 +27,140 @@ pub struct CloudSearchResp {
 pub struct CloudSearchResult {
     #[serde(default)]
     pub songs: Vec<SongInfo>,
+    /// 专辑搜索（type=10）结果
+    #[serde(default)]
+    pub albums: Vec<AlbumSearchInfo>,
+    /// 歌手搜索（type=100）结果
+    #[serde(default)]
+    pub artists: Vec<ArtistSearchInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtistSearchInfo {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub music_size: i64,
+    #[serde(default)]
+    pub album_size: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlbumSearchInfo {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub artists: Vec<ArtistInfo>,
+    /// 部分结果只有主歌手
+    #[serde(default)]
+    pub artist: Option<ArtistInfo>,
+    /// 曲目数
+    #[serde(default)]
+    pub size: i64,
+}
+
+/// `/api/artist/top/song` 返回的歌手热门歌曲（歌曲格式同 song/detail）
+#[derive(Debug, Deserialize)]
+pub struct ArtistTopSongsResp {
+    #[serde(default)]
+    pub songs: Vec<SongInfo>,
+}
+
+/// `/api/v3/discovery/recommend/songs` 返回的每日推荐（歌曲格式同 song/detail）
+#[derive(Debug, Deserialize)]
+pub struct RecommendSongsResp {
+    pub code: i64,
+    #[serde(default)]
+    pub data: Option<RecommendSongsData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendSongsData {
+    #[serde(default)]
+    pub daily_songs: Vec<SongInfo>,
+}
+
+/// `/api/v1/radio/get` 返回的私人 FM 歌曲（旧格式：`artists` / `album` / `duration`）
+#[derive(Debug, Deserialize)]
+pub struct PersonalFmResp {
+    pub code: i64,
+    #[serde(default)]
+    pub data: Vec<SongInfo>,
+}
+
+/// `/api/v1/album/{id}` 返回的专辑曲目（歌曲格式同 song/detail）
+#[derive(Debug, Deserialize)]
+pub struct AlbumDetailResp {
+    #[serde(default)]
+    pub songs: Vec<SongInfo>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SongDetailResp {
     #[serde(default)]
     pub songs: Vec<SongInfo>,
+    #[serde(default)]
+    pub privileges: Vec<PrivilegeInfo>,
+}
+
+/// song/detail 返回的播放权限；`st < 0` 表示无版权
+#[derive(Debug, Deserialize)]
+pub struct PrivilegeInfo {
+    pub id: i64,
+    #[serde(default)]
+    pub st: i64,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SongInfo {
     pub id: i64,
     pub name: String,
-    #[serde(rename = "dt", default)]
+    /// song/detail 返回 `dt`，私人 FM 等旧格式返回 `duration`
+    #[serde(rename = "dt", alias = "duration", default)]
     pub duration_ms: Option<u64>,
     #[serde(default)]
     pub ar: Vec<ArtistInfo>,
     #[serde(default)]
     pub artists: Vec<ArtistInfo>,
+    /// song/detail 返回 `al`，cloudsearch 旧格式返回 `album`
+    #[serde(default, alias = "album")]
+    pub al: Option<AlbumInfo>,
+    /// 译名；song/detail 返回 `tns`，cloudsearch 旧格式返回 `transNames`
+    #[serde(default, alias = "transNames")]
+    pub tns: Vec<String>,
+    /// 发行时间（毫秒时间戳）
+    #[serde(rename = "publishTime", default)]
+    pub publish_time: Option<i64>,
+    /// 专辑内曲目号，部分接口不返回
+    #[serde(default)]
+    pub no: Option<u32>,
+    /// 收费类型：1 VIP 专享，4 购买专辑，0/8 免费
+    #[serde(default)]
+    pub fee: i64,
+    /// 非空表示无版权（附带推荐替代版本）
+    #[serde(rename = "noCopyrightRcmd", default)]
+    pub no_copyright_rcmd: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ArtistInfo {
+    #[serde(default)]
+    pub id: i64,
     pub name: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct AlbumInfo {
+    #[serde(default)]
+    pub id: i64,
+    #[serde(default)]
+    pub name: String,
+    #[serde(rename = "picUrl", default)]
+    pub pic_url: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UserAccountResp {
     pub account: Option<AccountInfo>,
@@ -82,6 +191,22 @@ pub struct PlaylistInfo {
     pub track_count: i64,
     #[serde(rename = "specialType", default)]
     pub special_type: i64,
+    #[serde(default)]
+    pub subscribed: bool,
+    #[serde(default)]
+    pub creator: Option<PlaylistCreator>,
+    /// 歌单信息更新时间（毫秒时间戳）
+    #[serde(rename = "updateTime", default)]
+    pub update_time: Option<i64>,
+    /// 曲目变动时间（毫秒时间戳）
+    #[serde(rename = "trackUpdateTime", default)]
+    pub track_update_time: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlaylistCreator {
+    #[serde(rename = "userId", default)]
+    pub user_id: i64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -95,9 +220,31 @@ pub struct PlaylistDetail {
     pub track_ids: Vec<TrackId>,
 }
 
+/// `trackIds` 中的一项：通常是带 `at`（加入歌单的时间，毫秒时间戳）的对象，个别接口只给出 id
 #[derive(Debug, Deserialize)]
-pub struct TrackId {
-    pub id: i64,
+#[serde(untagged)]
+pub enum TrackId {
+    Plain(i64),
+    Entry {
+        id: i64,
+        #[serde(default)]
+        at: Option<i64>,
+    },
+}
+
+impl TrackId {
+    pub fn id(&self) -> i64 {
+        match *self {
+            Self::Plain(id) | Self::Entry { id, .. } => id,
+        }
+    }
+
+    pub fn added_at(&self) -> Option<i64> {
+        match *self {
+            Self::Plain(_) => None,
+            Self::Entry { at, .. } => at.filter(|&t| t > 0),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -110,12 +257,22 @@ pub struct SongUrlResp {
 pub struct SongUrlItem {
     pub id: i64,
     pub url: Option<String>,
+    #[serde(default)]
+    pub size: Option<u64>,
+    #[serde(default)]
+    pub br: Option<i64>,
+    /// 响度增益（dB），0 表示没有数据
+    #[serde(default)]
+    pub gain: Option<f32>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct LyricResp {
     pub lrc: Option<LyricBlock>,
     pub tlyric: Option<LyricBlock>,
+    /// 罗马音（日语、韩语歌曲）
+    #[serde(default)]
+    pub romalrc: Option<LyricBlock>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -123,3 +280,23 @@ pub struct LyricBlock {
     #[serde(default)]
     pub lyric: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct LikeListResp {
+    #[serde(default)]
+    pub ids: Vec<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LikeResp {
+    pub code: i64,
+}
+
+/// `/api/playlist/manipulate/tracks` 的返回
+#[derive(Debug, Deserialize)]
+pub struct PlaylistManipulateResp {
+    pub code: i64,
+    /// 实际添加/移除的歌曲 id，形如 `"[1,2]"` 的字符串（部分歌曲已在歌单中时只含其余歌曲）
+    #[serde(default, rename = "trackIds")]
+    pub track_ids: Option<serde_json::Value>,
+}
@@ -0,0 +1,100 @@
+//! Desktop notifications on track change (`notifications` feature).
+//!
+//! When the `notifications_enabled` setting is on, the core shows
+//! 「正在播放: {title}」 with the artists as body each time a new song starts.
+//! Delivery runs on a background task; failures are logged at debug level and never
+//! reach playback. Builds without the feature keep the setting but show nothing.
+
+use crate::app::App;
+use crate::domain::model::Song;
+
+/// 一条「正在播放」通知
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackNotice {
+    pub summary: String,
+    pub body: String,
+}
+
+impl TrackNotice {
+    /// 开关打开且不在专注模式时，为刚开始播放的歌曲生成通知
+    pub fn for_song(app: &App, song: &Song) -> Option<Self> {
+        if !app.notifications_enabled || app.focus_mode {
+            return None;
+        }
+        Some(Self {
+            summary: format!("正在播放: {}", song.display_name(app.song_name_mode)),
+            body: song.artists.clone(),
+        })
+    }
+}
+
+/// 在后台发送通知，不等待结果
+#[cfg(feature = "notifications")]
+pub fn show(notice: TrackNotice) {
+    tokio::spawn(async move {
+        let mut notification = notify_rust::Notification::new();
+        notification
+            .appname("netease-ratui")
+            .summary(&notice.summary)
+            .body(&notice.body);
+        if let Err(e) = deliver(notification).await {
+            tracing::debug!("桌面通知发送失败: {e}");
+        }
+    });
+}
+
+#[cfg(all(feature = "notifications", unix, not(target_os = "macos")))]
+async fn deliver(notification: notify_rust::Notification) -> Result<(), String> {
+    notification
+        .show_async()
+        .await
+        .map(drop)
+        .map_err(|e| e.to_string())
+}
+
+/// macOS / Windows 只有阻塞接口
+#[cfg(all(feature = "notifications", not(all(unix, not(target_os = "macos")))))]
+async fn deliver(notification: notify_rust::Notification) -> Result<(), String> {
+    tokio::task::spawn_blocking(move || notification.show().map(drop).map_err(|e| e.to_string()))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+/// Builds without the `notifications` feature cannot notify.
+#[cfg(not(feature = "notifications"))]
+pub fn show(notice: TrackNotice) {
+    tracing::trace!(summary = %notice.summary, "当前构建未启用 notifications 特性，跳过桌面通知");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::model::SongNameMode;
+
+    #[test]
+    fn notice_uses_display_name_and_artists() {
+        let app = App {
+            notifications_enabled: true,
+            song_name_mode: SongNameMode::TranslatedFirst,
+            ..Default::default()
+        };
+        let song = Song::titled(1, "晴天", "周杰伦").translated_as("Sunny Day");
+        assert_eq!(
+            TrackNotice::for_song(&app, &song),
+            Some(TrackNotice {
+                summary: "正在播放: Sunny Day".to_owned(),
+                body: "周杰伦".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn no_notice_when_disabled_or_in_focus_mode() {
+        let song = Song::titled(1, "晴天", "周杰伦");
+        let mut app = App::default();
+        assert_eq!(TrackNotice::for_song(&app, &song), None);
+        app.notifications_enabled = true;
+        app.focus_mode = true;
+        assert_eq!(TrackNotice::for_song(&app, &song), None);
+    }
+}
@@ -0,0 +1,7 @@
+//! 本地播放历史：每首歌播放结束（或被切走）时追加一条记录，供 `stats` 命令统计
+
+mod stats;
+mod store;
+
+pub use stats::{format_summary, parse_since, summarize};
+pub use store::{PlayRecord, PlaySession, append_record, history_path, load_history};
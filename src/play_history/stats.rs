@@ -0,0 +1,296 @@
+use super::store::PlayRecord;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 单个歌手/专辑的统计
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsEntry {
+    pub name: String,
+    pub listened_ms: u64,
+    pub plays: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsSummary {
+    pub plays: usize,
+    pub listened_ms: u64,
+    /// 平均完播率（没有时长信息的记录不参与）
+    pub avg_completion: Option<f32>,
+    pub top_artists: Vec<StatsEntry>,
+    pub top_albums: Vec<StatsEntry>,
+}
+
+/// 优先按 id 聚合，旧记录没有 id 时按名字
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Id(i64),
+    Name(String),
+}
+
+#[derive(Default)]
+struct Tally {
+    entries: HashMap<Key, StatsEntry>,
+}
+
+impl Tally {
+    fn add(&mut self, key: Key, name: &str, listened_ms: u64) {
+        let entry = self.entries.entry(key).or_insert_with(|| StatsEntry {
+            name: name.to_owned(),
+            listened_ms: 0,
+            plays: 0,
+        });
+        entry.listened_ms += listened_ms;
+        entry.plays += 1;
+    }
+
+    /// 按收听时长降序，其次按播放次数、名字
+    fn top(self, limit: usize) -> Vec<StatsEntry> {
+        let mut entries: Vec<_> = self.entries.into_values().collect();
+        entries.sort_by(|a, b| {
+            b.listened_ms
+                .cmp(&a.listened_ms)
+                .then(b.plays.cmp(&a.plays))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        entries.truncate(limit);
+        entries
+    }
+}
+
+/// 统计 `since_epoch_ms` 之后的记录（None 表示全部）
+///
+/// 多位歌手合作的歌曲，收听时长分别计入每位歌手。
+pub fn summarize(
+    records: &[PlayRecord],
+    since_epoch_ms: Option<i64>,
+    limit: usize,
+) -> StatsSummary {
+    let mut summary = StatsSummary::default();
+    let mut artists = Tally::default();
+    let mut albums = Tally::default();
+    let mut completion_sum = 0.0f64;
+    let mut completion_count = 0usize;
+
+    for rec in records
+        .iter()
+        .filter(|r| since_epoch_ms.is_none_or(|since| r.played_at_epoch_ms >= since))
+    {
+        summary.plays += 1;
+        summary.listened_ms += rec.played_ms;
+        if let Some(c) = rec.completion {
+            completion_sum += f64::from(c);
+            completion_count += 1;
+        }
+
+        let names: Vec<&str> = rec
+            .artists
+            .split('/')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .collect();
+        let ids_match = rec.artist_ids.len() == names.len();
+        for (i, name) in names.iter().enumerate() {
+            let key = match rec.artist_ids.get(i) {
+                Some(id) if ids_match && *id > 0 => Key::Id(*id),
+                _ => Key::Name((*name).to_owned()),
+            };
+            artists.add(key, name, rec.played_ms);
+        }
+
+        if !rec.album.is_empty() {
+            let key = rec
+                .album_id
+                .map_or_else(|| Key::Name(rec.album.clone()), Key::Id);
+            albums.add(key, &rec.album, rec.played_ms);
+        }
+    }
+
+    summary.avg_completion =
+        (completion_count > 0).then(|| (completion_sum / completion_count as f64) as f32);
+    summary.top_artists = artists.top(limit);
+    summary.top_albums = albums.top(limit);
+    summary
+}
+
+/// 解析 `--since`：`30d`、`12h`、`2w`、`90m`；`all` 表示全部
+pub fn parse_since(s: &str) -> Result<Option<Duration>, String> {
+    let s = s.trim();
+    if s.eq_ignore_ascii_case("all") {
+        return Ok(None);
+    }
+    let invalid = || format!("无效的时间范围: {s}（示例：30d、12h、2w、all）");
+    let unit = s.chars().last().ok_or_else(invalid)?;
+    let n: u64 = s[..s.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    let secs = match unit {
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        'w' => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    Ok(Some(Duration::from_secs(n.saturating_mul(secs))))
+}
+
+fn hours_label(ms: u64) -> String {
+    format!("{:.1} 小时", ms as f64 / 3_600_000.0)
+}
+
+/// 命令行输出
+pub fn format_summary(summary: &StatsSummary, since_label: &str) -> String {
+    let mut out = format!(
+        "统计范围: {since_label}\n播放 {} 次，共 {}",
+        summary.plays,
+        hours_label(summary.listened_ms)
+    );
+    if let Some(c) = summary.avg_completion {
+        out.push_str(&format!("，平均完播率 {:.0}%", c * 100.0));
+    }
+    out.push('\n');
+
+    for (title, entries) in [
+        ("歌手", &summary.top_artists),
+        ("专辑", &summary.top_albums),
+    ] {
+        out.push_str(&format!("\n收听最多的{title}:\n"));
+        if entries.is_empty() {
+            out.push_str("  （暂无数据）\n");
+        }
+        for (i, e) in entries.iter().enumerate() {
+            out.push_str(&format!(
+                "  {:>2}. {}  {}，{} 次\n",
+                i + 1,
+                e.name,
+                hours_label(e.listened_ms),
+                e.plays
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::play_history::store::CURRENT_VERSION;
+
+    const HOUR: u64 = 3_600_000;
+
+    fn rec(
+        at: i64,
+        artists: &str,
+        artist_ids: Vec<i64>,
+        album: (&str, Option<i64>),
+        played_ms: u64,
+    ) -> PlayRecord {
+        PlayRecord {
+            version: CURRENT_VERSION,
+            played_at_epoch_ms: at,
+            song_id: 1,
+            title: "t".to_owned(),
+            artists: artists.to_owned(),
+            album: album.0.to_owned(),
+            album_id: album.1,
+            artist_ids,
+            duration_ms: Some(played_ms * 2),
+            played_ms,
+            br: None,
+            completion: Some(0.5),
+        }
+    }
+
+    #[test]
+    fn ranks_artists_and_albums_by_listening_time() {
+        let records = vec![
+            rec(10, "周杰伦", vec![1], ("叶惠美", Some(100)), HOUR),
+            rec(20, "周杰伦/费玉清", vec![1, 2], ("叶惠美", Some(100)), HOUR),
+            rec(30, "费玉清", vec![2], ("", None), HOUR / 2),
+            // 旧记录：没有 id，按名字聚合
+            rec(40, "陈奕迅", vec![], ("U87", None), 3 * HOUR),
+        ];
+        let s = summarize(&records, None, 10);
+
+        assert_eq!(s.plays, 4);
+        assert_eq!(s.listened_ms, HOUR * 11 / 2);
+        assert_eq!(s.avg_completion, Some(0.5));
+        let artists: Vec<_> = s
+            .top_artists
+            .iter()
+            .map(|e| (e.name.as_str(), e.listened_ms, e.plays))
+            .collect();
+        assert_eq!(
+            artists,
+            vec![
+                ("陈奕迅", 3 * HOUR, 1),
+                ("周杰伦", 2 * HOUR, 2),
+                ("费玉清", HOUR * 3 / 2, 2),
+            ]
+        );
+        let albums: Vec<_> = s.top_albums.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(albums, vec!["U87", "叶惠美"], "空专辑不参与统计");
+    }
+
+    #[test]
+    fn since_and_limit_filter_results() {
+        let records = vec![
+            rec(10, "A", vec![1], ("X", Some(1)), HOUR),
+            rec(20, "B", vec![2], ("Y", Some(2)), HOUR),
+            rec(30, "C", vec![3], ("Z", Some(3)), HOUR * 2),
+        ];
+        let s = summarize(&records, Some(20), 1);
+        assert_eq!(s.plays, 2);
+        assert_eq!(s.top_artists.len(), 1);
+        assert_eq!(s.top_artists[0].name, "C");
+
+        assert_eq!(summarize(&[], None, 10), StatsSummary::default());
+    }
+
+    #[test]
+    fn mismatched_artist_ids_fall_back_to_names() {
+        let records = vec![
+            rec(10, "A/B", vec![1], ("", None), HOUR),
+            rec(20, "A", vec![1], ("", None), HOUR),
+        ];
+        let s = summarize(&records, None, 10);
+        assert_eq!(s.top_artists.len(), 3, "id 与名字数量不一致时按名字聚合");
+    }
+
+    #[test]
+    fn parse_since_units() {
+        assert_eq!(
+            parse_since("30d"),
+            Ok(Some(Duration::from_secs(30 * 86_400)))
+        );
+        assert_eq!(
+            parse_since("12h"),
+            Ok(Some(Duration::from_secs(12 * 3_600)))
+        );
+        assert_eq!(
+            parse_since("2w"),
+            Ok(Some(Duration::from_secs(14 * 86_400)))
+        );
+        assert_eq!(parse_since("90m"), Ok(Some(Duration::from_secs(90 * 60))));
+        assert_eq!(parse_since("ALL"), Ok(None));
+        assert!(parse_since("").is_err());
+        assert!(parse_since("d").is_err());
+        assert!(parse_since("30x").is_err());
+        assert!(parse_since("三十天").is_err());
+    }
+
+    #[test]
+    fn format_summary_lists_entries() {
+        let s = summarize(
+            &[rec(10, "周杰伦", vec![1], ("叶惠美", Some(1)), HOUR)],
+            None,
+            10,
+        );
+        let out = format_summary(&s, "30d");
+        assert!(
+            out.contains("播放 1 次，共 1.0 小时，平均完播率 50%"),
+            "{out}"
+        );
+        assert!(out.contains(" 1. 周杰伦  1.0 小时，1 次"), "{out}");
+        assert!(format_summary(&StatsSummary::default(), "all").contains("暂无数据"));
+    }
+}
@@ -0,0 +1,212 @@
+use crate::domain::model::Song;
+use crate::error::PlayHistoryError;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// 当前记录格式版本；更新版本写入的记录读取时跳过
+pub const CURRENT_VERSION: u8 = 1;
+const HISTORY_FILE: &str = "play_history.jsonl";
+
+/// 播放不足该时长（且不足一半）的歌曲视为跳过，不记录
+const MIN_PLAYED_MS: u64 = 30_000;
+
+/// 一条播放记录（文件中每行一条 JSON）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayRecord {
+    pub version: u8,
+    pub played_at_epoch_ms: i64,
+    pub song_id: i64,
+    pub title: String,
+    pub artists: String,
+    pub album: String,
+    pub album_id: Option<i64>,
+    pub artist_ids: Vec<i64>,
+    pub duration_ms: Option<u64>,
+    /// 实际播放时长
+    pub played_ms: u64,
+    /// 实际播放的音质（服务端可能返回低于请求的音质）
+    pub br: Option<i64>,
+    /// 完播率：played_ms / duration_ms，取值 0.0..=1.0
+    pub completion: Option<f32>,
+}
+
+impl PlayRecord {
+    pub fn new(
+        song: &Song,
+        played_ms: u64,
+        duration_ms: Option<u64>,
+        br: Option<i64>,
+        played_at_epoch_ms: i64,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            played_at_epoch_ms,
+            song_id: song.id,
+            title: song.name.clone(),
+            artists: song.artists.clone(),
+            album: song.album.clone(),
+            album_id: song.album_id,
+            artist_ids: song.artist_ids.clone(),
+            duration_ms,
+            played_ms,
+            br,
+            completion: completion_ratio(played_ms, duration_ms),
+        }
+    }
+}
+
+fn completion_ratio(played_ms: u64, duration_ms: Option<u64>) -> Option<f32> {
+    duration_ms
+        .filter(|d| *d > 0)
+        .map(|d| (played_ms as f64 / d as f64).clamp(0.0, 1.0) as f32)
+}
+
+/// 正在播放的歌曲；切歌/播放结束时结算为一条记录
+#[derive(Debug, Clone)]
+pub struct PlaySession {
+    pub song: Song,
+    pub br: Option<i64>,
+    pub started_at_epoch_ms: i64,
+}
+
+impl PlaySession {
+    /// 播放太短（跳过）时返回 None
+    pub fn finish(self, played_ms: u64, duration_ms: Option<u64>) -> Option<PlayRecord> {
+        let duration_ms = duration_ms.or(self.song.duration_ms);
+        let threshold = duration_ms.map_or(MIN_PLAYED_MS, |d| MIN_PLAYED_MS.min(d / 2));
+        if played_ms == 0 || played_ms < threshold {
+            return None;
+        }
+        Some(PlayRecord::new(
+            &self.song,
+            played_ms,
+            duration_ms,
+            self.br,
+            self.started_at_epoch_ms,
+        ))
+    }
+}
+
+pub fn history_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(HISTORY_FILE)
+}
+
+/// 追加一条记录
+pub fn append_record(data_dir: &Path, record: &PlayRecord) -> Result<(), PlayHistoryError> {
+    fs::create_dir_all(data_dir)?;
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_path(data_dir))?;
+    file.write_all(&line)?;
+    Ok(())
+}
+
+/// 读取全部记录（只读，不修改文件）
+///
+/// 无法解析的行与来自更新版本的记录会被跳过。
+pub fn load_history(data_dir: &Path) -> Result<Vec<PlayRecord>, PlayHistoryError> {
+    let file = match fs::File::open(history_path(data_dir)) {
+        Ok(f) => f,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut records = Vec::new();
+    for (idx, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<PlayRecord>(&line) {
+            Ok(rec) if rec.version > CURRENT_VERSION => {
+                tracing::warn!(
+                    line = idx + 1,
+                    version = rec.version,
+                    "跳过更新版本的播放记录"
+                );
+            }
+            Ok(rec) => records.push(rec),
+            Err(e) => {
+                tracing::warn!(line = idx + 1, err = %e, "跳过无法解析的播放记录");
+            }
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> PlaySession {
+        PlaySession {
+            song: Song {
+                album_id: Some(18905),
+                artist_ids: vec![6452],
+                ..Song::titled(1, "晴天", "周杰伦")
+                    .lasting_ms(200_000)
+                    .in_album("叶惠美")
+            },
+            br: Some(320_000),
+            started_at_epoch_ms: 1_700_000_000_000,
+        }
+    }
+
+    #[test]
+    fn session_skips_short_plays_and_computes_completion() {
+        assert_eq!(session().finish(10_000, None), None, "跳过的歌曲不记录");
+        assert_eq!(session().finish(0, Some(20_000)), None);
+
+        let rec = session().finish(150_000, None).expect("应记录");
+        assert_eq!(rec.version, CURRENT_VERSION);
+        assert_eq!(rec.album, "叶惠美");
+        assert_eq!(rec.artist_ids, vec![6452]);
+        assert_eq!(rec.br, Some(320_000));
+        assert_eq!(rec.completion, Some(0.75));
+
+        // 短于一分钟的歌曲放完一半即记录
+        let rec = session().finish(25_000, Some(40_000)).expect("短歌曲");
+        assert_eq!(rec.duration_ms, Some(40_000));
+        assert_eq!(
+            session().finish(300_000, None).unwrap().completion,
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn append_and_load_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(load_history(dir.path()).expect("空").is_empty());
+
+        let a = session().finish(200_000, None).unwrap();
+        let b = session().finish(60_000, None).unwrap();
+        append_record(dir.path(), &a).expect("append");
+        append_record(dir.path(), &b).expect("append");
+
+        assert_eq!(load_history(dir.path()).expect("load"), vec![a, b]);
+    }
+
+    #[test]
+    fn load_skips_unreadable_lines_without_touching_the_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = history_path(dir.path());
+        let rec = session().finish(200_000, None).unwrap();
+        let mut future = serde_json::to_value(&rec).expect("json");
+        future["version"] = (CURRENT_VERSION + 1).into();
+        let content = [
+            serde_json::to_string(&rec).expect("json"),
+            "not json".to_owned(),
+            future.to_string(),
+        ]
+        .join("\n");
+        fs::write(&path, &content).expect("write");
+
+        assert_eq!(load_history(dir.path()).expect("load"), vec![rec]);
+        assert_eq!(fs::read_to_string(&path).expect("read"), content);
+    }
+}
@@ -1,6 +1,13 @@
+mod queue;
 mod store;
 
-pub use store::{apply_snapshot_to_app, load_player_state_async, save_player_state_async};
+pub use queue::{
+    QueueFile, QueueSaveDebounce, load_queue_async, queue_path, resolve_newest, save_queue_async,
+};
+pub use store::{
+    AppStateSnapshot, apply_snapshot_to_app, load_player_state_async, restore_queue_and_progress,
+    save_player_state_async, state_path,
+};
 
 // 重新导出 PlayerStateError 从统一错误模块
 pub use crate::error::PlayerStateError;
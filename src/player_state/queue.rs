@@ -0,0 +1,310 @@
+//! 播放队列的独立自动保存（queue.json）
+//!
+//! 完整快照（player_state.json）每 30 秒才保存一次，崩溃时最近的队列修改会丢失。
+//! 队列每次变化后 2 秒内单独写入 queue.json；启动时与完整快照比较保存时间，较新的队列生效。
+
+use super::store::{AppStateSnapshot, PlayQueueState, PlaybackProgress, app_to_snapshot};
+use crate::app::App;
+use crate::error::PlayerStateError;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const QUEUE_VERSION: u8 = 1;
+const QUEUE_FILE: &str = "queue.json";
+
+/// 队列变化后最迟多久写盘
+pub const QUEUE_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueFile {
+    pub version: u8,
+    pub saved_at_epoch_ms: i64,
+    pub queue: PlayQueueState,
+}
+
+impl QueueFile {
+    pub fn from_app(app: &App) -> Self {
+        Self {
+            version: QUEUE_VERSION,
+            saved_at_epoch_ms: chrono::Utc::now().timestamp_millis(),
+            queue: PlayQueueState::from_queue(app.user_queue(), app.play_mode),
+        }
+    }
+}
+
+pub fn queue_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(QUEUE_FILE)
+}
+
+pub async fn load_queue_async(data_dir: &Path) -> Result<QueueFile, PlayerStateError> {
+    let bytes = tokio::fs::read(queue_path(data_dir))
+        .await
+        .map_err(PlayerStateError::Io)?;
+    let file: QueueFile = serde_json::from_slice(&bytes).map_err(PlayerStateError::Serde)?;
+    if file.version > QUEUE_VERSION {
+        return Err(PlayerStateError::IncompatibleVersion {
+            expected: QUEUE_VERSION,
+            found: file.version,
+        });
+    }
+    Ok(file)
+}
+
+pub async fn save_queue_async(data_dir: &Path, file: QueueFile) -> Result<(), PlayerStateError> {
+    tokio::fs::create_dir_all(data_dir)
+        .await
+        .map_err(PlayerStateError::Io)?;
+    let bytes = serde_json::to_vec(&file).map_err(PlayerStateError::Serde)?;
+    super::store::write_atomic_async(&queue_path(data_dir), bytes).await
+}
+
+/// 合并启动时读到的完整快照与队列文件，保存时间较新者的队列生效
+///
+/// 完整快照缺失或损坏时，仍可单独从队列文件恢复队列。
+pub fn resolve_newest(
+    loaded: Result<AppStateSnapshot, PlayerStateError>,
+    queue: Option<QueueFile>,
+) -> Result<AppStateSnapshot, PlayerStateError> {
+    let Some(queue) = queue else {
+        return loaded;
+    };
+    match loaded {
+        Ok(mut snapshot) => {
+            if queue.saved_at_epoch_ms > snapshot.saved_at_epoch_ms {
+                tracing::info!(
+                    snapshot_saved_at = snapshot.saved_at_epoch_ms,
+                    queue_saved_at = queue.saved_at_epoch_ms,
+                    "🎵 [StateRestore] queue.json 较新，使用其中的播放队列"
+                );
+                replace_queue(&mut snapshot, queue);
+            }
+            Ok(snapshot)
+        }
+        Err(e) => {
+            if !matches!(&e, PlayerStateError::Io(io) if io.kind() == std::io::ErrorKind::NotFound)
+            {
+                tracing::warn!("加载状态失败: {}, 仅从 queue.json 恢复队列", e);
+            }
+            let mut snapshot = app_to_snapshot(&App::default());
+            replace_queue(&mut snapshot, queue);
+            Ok(snapshot)
+        }
+    }
+}
+
+/// 用队列文件替换快照中的队列；当前歌曲变了时旧的播放进度不再有效
+fn replace_queue(snapshot: &mut AppStateSnapshot, queue: QueueFile) {
+    let current = queue.queue.current_song_id();
+    if snapshot.player.play_song_id != current {
+        let total_ms = queue
+            .queue
+            .cursor
+            .and_then(|pos| queue.queue.order.get(pos))
+            .and_then(|&idx| queue.queue.songs.get(idx))
+            .and_then(|s| s.duration_ms);
+        snapshot.player.play_song_id = current;
+        snapshot.player.progress = PlaybackProgress {
+            started_at_epoch_ms: None,
+            total_ms,
+            paused: true,
+            paused_at_epoch_ms: None,
+            paused_accum_ms: 0,
+        };
+        snapshot.saved_at_epoch_ms = queue.saved_at_epoch_ms;
+    }
+    snapshot.player.play_queue = queue.queue;
+}
+
+/// 队列自动保存的防抖：首次变化后等待 [`QUEUE_SAVE_DELAY`] 再写，期间的修改合并为一次写入
+///
+/// 计时从第一次未保存的修改开始，持续修改不会无限推迟写入。
+#[derive(Debug, Default)]
+pub struct QueueSaveDebounce {
+    revision: u64,
+    dirty_since: Option<Instant>,
+}
+
+impl QueueSaveDebounce {
+    pub fn new(revision: u64) -> Self {
+        Self {
+            revision,
+            dirty_since: None,
+        }
+    }
+
+    /// 传入队列当前的修改序号
+    pub fn observe(&mut self, revision: u64, now: Instant) {
+        if revision != self.revision {
+            self.revision = revision;
+            self.dirty_since.get_or_insert(now);
+        }
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.dirty_since.map(|t| t + QUEUE_SAVE_DELAY)
+    }
+
+    /// 到期时返回 true 并清除待写标记
+    pub fn take_due(&mut self, now: Instant) -> bool {
+        if self.deadline().is_some_and(|d| now >= d) {
+            self.dirty_since = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::PlayMode;
+    use crate::domain::model::Song;
+
+    fn queue_file(ids: &[i64], cursor: usize, saved_at: i64) -> QueueFile {
+        let mut app = App {
+            play_mode: PlayMode::Sequential,
+            ..Default::default()
+        };
+        app.play_queue.set_mode(PlayMode::Sequential);
+        app.play_queue.set_songs(
+            ids.iter()
+                .copied()
+                .map(|id| Song::fixture(id).lasting_ms(id as u64 * 1_000))
+                .collect(),
+            Some(cursor),
+        );
+        let mut file = QueueFile::from_app(&app);
+        file.saved_at_epoch_ms = saved_at;
+        file
+    }
+
+    fn snapshot(ids: &[i64], cursor: usize, saved_at: i64) -> AppStateSnapshot {
+        let mut app = App {
+            volume: 0.3,
+            ..Default::default()
+        };
+        app.play_queue.set_songs(
+            ids.iter()
+                .copied()
+                .map(|id| Song::fixture(id).lasting_ms(id as u64 * 1_000))
+                .collect(),
+            Some(cursor),
+        );
+        app.play_song_id = Some(ids[cursor]);
+        let mut snapshot = app_to_snapshot(&app);
+        snapshot.player.progress.started_at_epoch_ms = Some(saved_at - 5_000);
+        snapshot.saved_at_epoch_ms = saved_at;
+        snapshot
+    }
+
+    fn song_ids(s: &AppStateSnapshot) -> Vec<i64> {
+        s.player.play_queue.songs.iter().map(|s| s.id).collect()
+    }
+
+    #[test]
+    fn newer_queue_file_replaces_snapshot_queue() {
+        let resolved = resolve_newest(
+            Ok(snapshot(&[1, 2], 0, 1_000)),
+            Some(queue_file(&[7, 8, 9], 1, 2_000)),
+        )
+        .expect("resolve");
+        assert_eq!(song_ids(&resolved), vec![7, 8, 9]);
+        assert_eq!(resolved.player.play_queue.mode, "Sequential");
+        assert_eq!(resolved.player.play_song_id, Some(8));
+        assert_eq!(resolved.player.progress.started_at_epoch_ms, None);
+        assert_eq!(resolved.player.progress.total_ms, Some(8_000));
+        assert_eq!(resolved.player.volume, 0.3, "其余状态来自完整快照");
+    }
+
+    #[test]
+    fn older_queue_file_is_ignored() {
+        let resolved = resolve_newest(
+            Ok(snapshot(&[1, 2], 1, 2_000)),
+            Some(queue_file(&[7, 8], 0, 1_000)),
+        )
+        .expect("resolve");
+        assert_eq!(song_ids(&resolved), vec![1, 2]);
+        assert_eq!(resolved.player.play_song_id, Some(2));
+        assert_eq!(resolved.saved_at_epoch_ms, 2_000);
+    }
+
+    #[test]
+    fn same_current_song_keeps_progress() {
+        let resolved = resolve_newest(
+            Ok(snapshot(&[1, 2], 1, 1_000)),
+            Some(queue_file(&[2, 3], 0, 2_000)),
+        )
+        .expect("resolve");
+        assert_eq!(song_ids(&resolved), vec![2, 3]);
+        assert_eq!(resolved.player.progress.started_at_epoch_ms, Some(-4_000));
+        assert_eq!(resolved.saved_at_epoch_ms, 1_000);
+    }
+
+    #[test]
+    fn queue_file_alone_restores_queue() {
+        let missing = Err(PlayerStateError::Io(std::io::Error::from(
+            std::io::ErrorKind::NotFound,
+        )));
+        let resolved =
+            resolve_newest(missing, Some(queue_file(&[4, 5], 1, 3_000))).expect("resolve");
+        assert_eq!(song_ids(&resolved), vec![4, 5]);
+        assert_eq!(resolved.player.play_song_id, Some(5));
+
+        let missing = Err(PlayerStateError::Io(std::io::Error::from(
+            std::io::ErrorKind::NotFound,
+        )));
+        assert!(resolve_newest(missing, None).is_err());
+    }
+
+    #[test]
+    fn rapid_mutations_are_coalesced_into_few_writes() {
+        let t0 = Instant::now();
+        let mut debounce = QueueSaveDebounce::new(0);
+        let mut writes = 0;
+        // 5 秒内每 100ms 修改一次
+        for i in 1..=50u64 {
+            let now = t0 + Duration::from_millis(i * 100);
+            debounce.observe(i, now);
+            if debounce.take_due(now) {
+                writes += 1;
+            }
+        }
+        assert!((1..=3).contains(&writes), "writes={writes}");
+
+        // 最后一次修改仍会在 2 秒内写入
+        let last = t0 + Duration::from_millis(5_000);
+        let deadline = debounce.deadline().expect("仍有未保存的修改");
+        assert!(deadline <= last + QUEUE_SAVE_DELAY);
+        assert!(debounce.take_due(deadline));
+        assert_eq!(debounce.deadline(), None);
+
+        // 序号未变不触发写入
+        debounce.observe(50, deadline);
+        assert_eq!(debounce.deadline(), None);
+    }
+
+    #[tokio::test]
+    async fn save_and_load_roundtrip() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        save_queue_async(dir.path(), queue_file(&[1, 2, 3], 2, 42))
+            .await
+            .expect("save");
+        let loaded = load_queue_async(dir.path()).await.expect("load");
+        assert_eq!(loaded.version, QUEUE_VERSION);
+        assert_eq!(loaded.saved_at_epoch_ms, 42);
+        assert_eq!(loaded.queue.current_song_id(), Some(3));
+
+        tokio::fs::write(
+            queue_path(dir.path()),
+            br#"{"version":99,"saved_at_epoch_ms":1,"queue":{"songs":[],"order":[],"cursor":null,"mode":"ListLoop"}}"#,
+        )
+        .await
+        .expect("write");
+        assert!(matches!(
+            load_queue_async(dir.path()).await,
+            Err(PlayerStateError::IncompatibleVersion { found: 99, .. })
+        ));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::app::state::{App, PlayMode};
+use crate::app::state::{App, PlayMode, View, playback_wall_ms};
 use crate::app::{PlayQueue, PlaylistPreload};
 use crate::domain::model::{Playlist, Song};
 use crate::error::PlayerStateError;
@@ -12,13 +12,26 @@ const CURRENT_VERSION: u8 = 3;
 const STATE_FILE: &str = "player_state.json";
 
 /// 轻量级歌曲信息（用于序列化）
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SongLite {
     pub id: i64,
     pub name: String,
     pub artists: String,
     #[serde(default)]
     pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub album: String,
+    #[serde(default)]
+    pub album_id: Option<i64>,
+    #[serde(default)]
+    pub artist_ids: Vec<i64>,
+    /// 译名；`name` 保存原名，显示方式切换后无需重新拉取
+    #[serde(default)]
+    pub translated: Option<String>,
+    #[serde(default)]
+    pub cover_url: Option<String>,
+    #[serde(default)]
+    pub track_no: Option<u32>,
 }
 
 impl From<&Song> for SongLite {
@@ -28,6 +41,12 @@ impl From<&Song> for SongLite {
             name: song.name.clone(),
             artists: song.artists.clone(),
             duration_ms: song.duration_ms,
+            album: song.album.clone(),
+            album_id: song.album_id,
+            artist_ids: song.artist_ids.clone(),
+            translated: song.translated.clone(),
+            cover_url: song.cover_url.clone(),
+            track_no: song.track_no,
         }
     }
 }
@@ -39,6 +58,39 @@ pub struct PlayQueueState {
     pub order: Vec<usize>,
     pub cursor: Option<usize>,
     pub mode: String,
+    /// 队列来源（如 `playlist:<id>`）
+    #[serde(default)]
+    pub origin: Option<String>,
+    /// 每首歌的来源（与 `songs` 对应）；都与 `origin` 相同时为空
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entry_origins: Vec<Option<String>>,
+}
+
+impl PlayQueueState {
+    pub fn from_queue(queue: &PlayQueue, mode: PlayMode) -> Self {
+        Self {
+            songs: queue.songs().iter().map(SongLite::from).collect(),
+            order: queue.order().to_vec(),
+            cursor: queue.cursor_pos(),
+            mode: play_mode_to_string(mode),
+            origin: queue.origin().map(str::to_owned),
+            entry_origins: if queue.entry_origins_uniform() {
+                Vec::new()
+            } else {
+                (0..queue.songs().len())
+                    .map(|i| queue.entry_origin(i).map(|o| o.to_string()))
+                    .collect()
+            },
+        }
+    }
+
+    /// 游标所指歌曲的 id
+    pub fn current_song_id(&self) -> Option<i64> {
+        self.cursor
+            .and_then(|pos| self.order.get(pos))
+            .and_then(|&idx| self.songs.get(idx))
+            .map(|s| s.id)
+    }
 }
 
 /// 播放进度（使用时间戳替代 Instant）
@@ -70,6 +122,10 @@ pub struct PlaylistLite {
     pub name: String,
     pub track_count: i64,
     pub special_type: i64,
+    #[serde(default)]
+    pub subscribed: bool,
+    #[serde(default)]
+    pub updated_at_ms: Option<i64>,
 }
 
 impl From<&Playlist> for PlaylistLite {
@@ -79,6 +135,8 @@ impl From<&Playlist> for PlaylistLite {
             name: playlist.name.clone(),
             track_count: playlist.track_count,
             special_type: playlist.special_type,
+            subscribed: playlist.subscribed,
+            updated_at_ms: playlist.updated_at_ms,
         }
     }
 }
@@ -92,11 +150,14 @@ pub struct AppStateSnapshot {
     pub playlists_selected: usize,
     #[serde(default)]
     pub playlist_preloads: HashMap<i64, PlaylistPreload>,
+    /// 保存时所在页面（登录页不记录）
+    #[serde(default)]
+    pub last_view: Option<String>,
     pub saved_at_epoch_ms: i64,
 }
 
 /// 将 App 转换为持久化格式
-fn app_to_snapshot(app: &App) -> AppStateSnapshot {
+pub(super) fn app_to_snapshot(app: &App) -> AppStateSnapshot {
     let now = chrono::Utc::now().timestamp_millis();
 
     // 计算播放进度
@@ -126,12 +187,7 @@ fn app_to_snapshot(app: &App) -> AppStateSnapshot {
     };
 
     // 转换播放队列
-    let play_queue = PlayQueueState {
-        songs: app.play_queue.songs().iter().map(SongLite::from).collect(),
-        order: app.play_queue.order().to_vec(),
-        cursor: app.play_queue.cursor_pos(),
-        mode: play_mode_to_string(app.play_mode),
-    };
+    let play_queue = PlayQueueState::from_queue(app.user_queue(), app.play_mode);
 
     // 转换歌单
     let playlists: Vec<PlaylistLite> = app.playlists.iter().map(PlaylistLite::from).collect();
@@ -192,14 +248,18 @@ fn app_to_snapshot(app: &App) -> AppStateSnapshot {
         playlists,
         playlists_selected: app.playlists_selected,
         playlist_preloads,
+        last_view: view_to_string(app.view),
         saved_at_epoch_ms: now,
     }
 }
 
 /// 从持久化格式恢复到 App
+///
+/// `restore_queue` 为 false 时跳过播放队列与播放进度，其余状态（音量、歌单等）照常恢复。
 pub fn apply_snapshot_to_app(
     snapshot: &AppStateSnapshot,
     app: &mut App,
+    restore_queue: bool,
 ) -> Result<(), PlayerStateError> {
     // 检查版本兼容性（支持版本 1、2 和 3）
     if snapshot.version > CURRENT_VERSION {
@@ -233,6 +293,27 @@ pub fn apply_snapshot_to_app(
         tracing::info!("🎵 [StateRestore] 版本 < 3, playlist_preloads 初始化为空");
     }
 
+    app.last_view = snapshot.last_view.as_deref().and_then(view_from_string);
+    app.volume = snapshot.player.volume;
+    app.play_br = snapshot.player.play_br;
+    app.crossfade_ms = snapshot.player.crossfade_ms;
+    app.play_mode = play_mode_from_string(&snapshot.player.play_queue.mode);
+
+    if restore_queue {
+        restore_queue_and_progress(snapshot, app);
+    } else {
+        app.play_queue = PlayQueue::new(app.play_mode);
+        tracing::info!("🎵 [StateRestore] 跳过播放队列与进度");
+    }
+
+    // 恢复歌单（只恢复基本信息，不恢复歌曲详情）
+    restore_playlists(snapshot, app, use_default_special_type);
+
+    Ok(())
+}
+
+/// 恢复播放进度与播放队列
+pub fn restore_queue_and_progress(snapshot: &AppStateSnapshot, app: &mut App) {
     let now_epoch_ms = chrono::Utc::now().timestamp_millis();
     let restore_now = Instant::now();
     let time_since_save_ms = now_epoch_ms
@@ -285,7 +366,9 @@ pub fn apply_snapshot_to_app(
     );
 
     let pos_ms_u64 = u64::try_from(pos_ms_i64).unwrap_or(u64::MAX);
-    let total_offset_ms_u64 = pos_ms_u64.saturating_add(paused_accum_ms_u64);
+    // 运行中接受恢复提示时可能已改过倍速：计时起点按当前倍速反推
+    let total_offset_ms_u64 =
+        playback_wall_ms(pos_ms_u64, app.play_speed).saturating_add(paused_accum_ms_u64);
     let started_at = restore_now
         .checked_sub(Duration::from_millis(total_offset_ms_u64))
         .or_else(|| {
@@ -306,10 +389,6 @@ pub fn apply_snapshot_to_app(
 
     // 恢复播放器状态
     app.play_song_id = snapshot.player.play_song_id;
-    app.volume = snapshot.player.volume;
-    app.play_br = snapshot.player.play_br;
-    app.crossfade_ms = snapshot.player.crossfade_ms;
-    app.play_mode = play_mode_from_string(&snapshot.player.play_queue.mode);
 
     // 恢复播放队列
     let songs: Vec<Song> = snapshot
@@ -322,6 +401,12 @@ pub fn apply_snapshot_to_app(
             name: lite.name.clone(),
             artists: lite.artists.clone(),
             duration_ms: lite.duration_ms,
+            album: lite.album.clone(),
+            album_id: lite.album_id,
+            artist_ids: lite.artist_ids.clone(),
+            translated: lite.translated.clone(),
+            cover_url: lite.cover_url.clone(),
+            track_no: lite.track_no,
         })
         .collect();
 
@@ -337,8 +422,14 @@ pub fn apply_snapshot_to_app(
             "🎵 [StateRestore] 保存的播放队列顺序无效，已回退到自然顺序"
         );
     }
+    app.play_queue
+        .restore_entry_origins(&snapshot.player.play_queue.entry_origins);
+    if let Some(origin) = &snapshot.player.play_queue.origin {
+        app.play_queue.set_origin(origin.clone());
+    }
+}
 
-    // 恢复歌单（只恢复基本信息，不恢复歌曲详情）
+fn restore_playlists(snapshot: &AppStateSnapshot, app: &mut App, use_default_special_type: bool) {
     app.playlists = snapshot
         .playlists
         .iter()
@@ -351,6 +442,8 @@ pub fn apply_snapshot_to_app(
             } else {
                 lite.special_type
             },
+            subscribed: lite.subscribed,
+            updated_at_ms: lite.updated_at_ms,
         })
         .collect();
 
@@ -386,8 +479,6 @@ pub fn apply_snapshot_to_app(
     } else {
         0
     };
-
-    Ok(())
 }
 
 /// 加载播放器状态
@@ -473,8 +564,6 @@ pub async fn save_player_state_async(data_dir: &Path, app: App) -> Result<(), Pl
         .map_err(PlayerStateError::Io)?;
 
     let path = state_path(data_dir);
-    let tmp_path = path.with_extension("json.tmp");
-
     let snapshot = app_to_snapshot(&app);
     let base_pos_ms = snapshot
         .player
@@ -496,19 +585,27 @@ pub async fn save_player_state_async(data_dir: &Path, app: App) -> Result<(), Pl
         "🎵 [StateSaveDbg] snapshot"
     );
     let bytes = serde_json::to_vec_pretty(&snapshot).map_err(PlayerStateError::Serde)?;
+    write_atomic_async(&path, bytes).await
+}
 
+/// 先写临时文件再 rename，避免写到一半崩溃时留下损坏的状态文件
+pub(super) async fn write_atomic_async(
+    path: &Path,
+    bytes: Vec<u8>,
+) -> Result<(), PlayerStateError> {
+    let tmp_path = path.with_extension("json.tmp");
     tokio::fs::write(&tmp_path, bytes)
         .await
         .map_err(PlayerStateError::Io)?;
 
     // 尽量保持原子写入语义（Windows 上 rename 不能覆盖已存在目标）
-    match tokio::fs::rename(&tmp_path, &path).await {
+    match tokio::fs::rename(&tmp_path, path).await {
         Ok(()) => Ok(()),
         Err(e) => {
             // 如果目标已存在，尝试删除后再 rename（Windows 上常见）
             tracing::debug!(err = %e, "player_state rename failed, retrying with remove_file");
-            let _ = tokio::fs::remove_file(&path).await;
-            match tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(path).await;
+            match tokio::fs::rename(&tmp_path, path).await {
                 Ok(()) => Ok(()),
                 Err(e2) => {
                     let _ = tokio::fs::remove_file(&tmp_path).await;
@@ -519,7 +616,7 @@ pub async fn save_player_state_async(data_dir: &Path, app: App) -> Result<(), Pl
     }
 }
 
-fn state_path(data_dir: &Path) -> PathBuf {
+pub fn state_path(data_dir: &Path) -> PathBuf {
     data_dir.join(STATE_FILE)
 }
 
@@ -542,6 +639,29 @@ fn play_mode_from_string(s: &str) -> PlayMode {
     }
 }
 
+fn view_to_string(v: View) -> Option<String> {
+    let s = match v {
+        View::Login => return None,
+        View::Playlists => "Playlists",
+        View::Search => "Search",
+        View::Lyrics => "Lyrics",
+        View::Settings => "Settings",
+        View::Queue => "Queue",
+    };
+    Some(s.to_owned())
+}
+
+fn view_from_string(s: &str) -> Option<View> {
+    match s {
+        "Playlists" => Some(View::Playlists),
+        "Search" => Some(View::Search),
+        "Lyrics" => Some(View::Lyrics),
+        "Settings" => Some(View::Settings),
+        "Queue" => Some(View::Queue),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -553,6 +673,7 @@ mod tests {
             name: name.to_string(),
             artists: artists.to_string(),
             duration_ms: None,
+            ..Default::default()
         }
     }
 
@@ -595,10 +716,13 @@ mod tests {
             name: "Test Playlist".to_string(),
             track_count: 100,
             special_type: 0,
+            subscribed: false,
+            updated_at_ms: Some(1_700_000_000_000),
         };
 
         let lite = PlaylistLite::from(&playlist);
         assert_eq!(lite.id, 456);
+        assert_eq!(lite.updated_at_ms, Some(1_700_000_000_000));
         assert_eq!(lite.name, "Test Playlist");
         assert_eq!(lite.track_count, 100);
         assert_eq!(lite.special_type, 0);
@@ -623,6 +747,8 @@ mod tests {
                     order: vec![],
                     cursor: None,
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 1.0,
                 play_br: 320000,
@@ -631,11 +757,12 @@ mod tests {
             playlists: vec![],
             playlists_selected: 0,
             playlist_preloads: std::collections::HashMap::new(),
+            last_view: None,
             saved_at_epoch_ms: i64::MIN,
         };
 
         let mut app = App::default();
-        let result = apply_snapshot_to_app(&snapshot, &mut app);
+        let result = apply_snapshot_to_app(&snapshot, &mut app, true);
         assert!(result.is_ok());
         assert!(app.play_started_at.is_some());
     }
@@ -660,6 +787,8 @@ mod tests {
                     order: vec![],
                     cursor: None,
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 1.0,
                 play_br: 320000,
@@ -668,11 +797,12 @@ mod tests {
             playlists: vec![],
             playlists_selected: 0,
             playlist_preloads: std::collections::HashMap::new(),
+            last_view: None,
             saved_at_epoch_ms: now + 5_000,
         };
 
         let mut app = App::default();
-        let result = apply_snapshot_to_app(&snapshot, &mut app);
+        let result = apply_snapshot_to_app(&snapshot, &mut app, true);
         assert!(result.is_ok());
         assert!(app.play_started_at.is_some());
     }
@@ -702,6 +832,8 @@ mod tests {
                     order: vec![],
                     cursor: None,
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 1.0,
                 play_br: 320000,
@@ -710,11 +842,12 @@ mod tests {
             playlists: vec![],
             playlists_selected: 0,
             playlist_preloads: std::collections::HashMap::new(),
+            last_view: None,
             saved_at_epoch_ms: saved_at,
         };
 
         let mut app = App::default();
-        apply_snapshot_to_app(&snapshot, &mut app).unwrap();
+        apply_snapshot_to_app(&snapshot, &mut app, true).unwrap();
 
         // Restore always pauses, so `play_paused_at` must be set to freeze the elapsed time.
         assert!(app.paused);
@@ -749,6 +882,8 @@ mod tests {
                     order: vec![],
                     cursor: None,
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 1.0,
                 play_br: 320000,
@@ -757,11 +892,12 @@ mod tests {
             playlists: vec![],
             playlists_selected: 0,
             playlist_preloads: std::collections::HashMap::new(),
+            last_view: None,
             saved_at_epoch_ms: saved_at,
         };
 
         let mut app = App::default();
-        apply_snapshot_to_app(&snapshot, &mut app).unwrap();
+        apply_snapshot_to_app(&snapshot, &mut app, true).unwrap();
 
         // paused-at-save should never advance.
         assert_eq!(app_playback_elapsed_ms(&app), 10_000);
@@ -808,6 +944,8 @@ mod tests {
                     order: vec![],
                     cursor: None,
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 0.5,
                 play_br: 320000,
@@ -816,11 +954,12 @@ mod tests {
             playlists: vec![],
             playlists_selected: 0,
             playlist_preloads: HashMap::new(),
+            last_view: None,
             saved_at_epoch_ms: 0,
         };
 
         let mut app = App::default();
-        let result = apply_snapshot_to_app(&snapshot, &mut app);
+        let result = apply_snapshot_to_app(&snapshot, &mut app, true);
         assert!(result.is_err());
         match result {
             Err(PlayerStateError::IncompatibleVersion { expected, found }) => {
@@ -851,10 +990,18 @@ mod tests {
                         name: "Test Song".to_string(),
                         artists: "Test Artist".to_string(),
                         duration_ms: Some(180000),
+                        album: "Test Album".to_string(),
+                        album_id: Some(9),
+                        artist_ids: vec![42],
+                        translated: Some("测试歌曲".to_string()),
+                        cover_url: None,
+                        track_no: None,
                     }],
                     order: vec![0],
                     cursor: Some(0),
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 0.7,
                 play_br: 320000,
@@ -865,14 +1012,17 @@ mod tests {
                 name: "My Playlist".to_string(),
                 track_count: 50,
                 special_type: 0,
+                subscribed: false,
+                updated_at_ms: None,
             }],
             playlists_selected: 0,
             playlist_preloads: HashMap::new(),
+            last_view: None,
             saved_at_epoch_ms: chrono::Utc::now().timestamp_millis(),
         };
 
         let mut app = App::default();
-        let result = apply_snapshot_to_app(&snapshot, &mut app);
+        let result = apply_snapshot_to_app(&snapshot, &mut app, true);
         assert!(result.is_ok());
 
         // 验证恢复的状态
@@ -886,6 +1036,12 @@ mod tests {
         assert_eq!(app.playlists.len(), 1);
         assert_eq!(app.playlists[0].id, 1);
         assert_eq!(app.play_mode, PlayMode::ListLoop);
+        let song = &app.play_queue.songs()[0];
+        assert_eq!(song.album, "Test Album");
+        assert_eq!(song.album_id, Some(9));
+        assert_eq!(song.artist_ids, vec![42]);
+        assert_eq!(song.name, "Test Song");
+        assert_eq!(song.translated.as_deref(), Some("测试歌曲"));
     }
 
     #[test]
@@ -909,23 +1065,28 @@ mod tests {
                             name: "Song 1".to_string(),
                             artists: "Artist".to_string(),
                             duration_ms: None,
+                            ..Default::default()
                         },
                         SongLite {
                             id: 2,
                             name: "Song 2".to_string(),
                             artists: "Artist".to_string(),
                             duration_ms: None,
+                            ..Default::default()
                         },
                         SongLite {
                             id: 3,
                             name: "Song 3".to_string(),
                             artists: "Artist".to_string(),
                             duration_ms: None,
+                            ..Default::default()
                         },
                     ],
                     order: vec![2, 0, 1],
                     cursor: None,
                     mode: "Shuffle".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 0.7,
                 play_br: 320000,
@@ -934,11 +1095,12 @@ mod tests {
             playlists: vec![],
             playlists_selected: 0,
             playlist_preloads: HashMap::new(),
+            last_view: None,
             saved_at_epoch_ms: chrono::Utc::now().timestamp_millis(),
         };
 
         let mut app = App::default();
-        apply_snapshot_to_app(&snapshot, &mut app).unwrap();
+        apply_snapshot_to_app(&snapshot, &mut app, true).unwrap();
 
         assert_eq!(app.play_mode, PlayMode::Shuffle);
         assert_eq!(app.play_queue.order(), &[2, 0, 1]);
@@ -992,6 +1154,11 @@ mod tests {
         let preload = PlaylistPreload {
             status: PreloadStatus::Completed,
             songs: vec![song(101, "Preloaded Song", "Test Artist")],
+            missing_count: 0,
+            stats: None,
+            completed_at_ms: Some(1_700_000_000_000),
+            added_at: HashMap::from([(101, 1_690_000_000_000)]),
+            track_ids_fingerprint: None,
         };
 
         // 验证 PlaylistPreload 可以序列化和反序列化
@@ -1005,6 +1172,13 @@ mod tests {
         }
         assert_eq!(deserialized.songs.len(), 1);
         assert_eq!(deserialized.songs[0].id, 101);
+        assert_eq!(deserialized.completed_at_ms, Some(1_700_000_000_000));
+        assert_eq!(deserialized.added_at, preload.added_at);
+
+        // 旧版本缓存没有 added_at
+        let legacy: PlaylistPreload =
+            serde_json::from_str(r#"{"status":"Completed","songs":[]}"#).expect("旧格式");
+        assert!(legacy.added_at.is_empty());
     }
 
     #[test]
@@ -1029,6 +1203,8 @@ mod tests {
                     order: vec![],
                     cursor: None,
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 0.5,
                 play_br: 320000,
@@ -1039,6 +1215,8 @@ mod tests {
                 name: "Test Playlist".to_string(),
                 track_count: 10,
                 special_type: 0,
+                subscribed: false,
+                updated_at_ms: None,
             }],
             playlists_selected: 0,
             playlist_preloads: vec![(
@@ -1046,10 +1224,16 @@ mod tests {
                 PlaylistPreload {
                     status: PreloadStatus::Completed,
                     songs: vec![song(201, "Cached Song", "Cached Artist")],
+                    missing_count: 0,
+                    stats: None,
+                    completed_at_ms: None,
+                    added_at: HashMap::new(),
+                    track_ids_fingerprint: None,
                 },
             )]
             .into_iter()
             .collect(),
+            last_view: None,
             saved_at_epoch_ms: chrono::Utc::now().timestamp_millis(),
         };
 
@@ -1089,6 +1273,8 @@ mod tests {
                     order: vec![],
                     cursor: None,
                     mode: "ListLoop".to_string(),
+                    origin: None,
+                    entry_origins: Vec::new(),
                 },
                 volume: 0.5,
                 play_br: 320000,
@@ -1099,6 +1285,8 @@ mod tests {
                 name: "My Playlist".to_string(),
                 track_count: 50,
                 special_type: 5,
+                subscribed: false,
+                updated_at_ms: None,
             }],
             playlists_selected: 0,
             playlist_preloads: vec![(
@@ -1109,15 +1297,21 @@ mod tests {
                         song(301, "Song A", "Artist A"),
                         song(302, "Song B", "Artist B"),
                     ],
+                    missing_count: 0,
+                    stats: None,
+                    completed_at_ms: None,
+                    added_at: HashMap::new(),
+                    track_ids_fingerprint: None,
                 },
             )]
             .into_iter()
             .collect(),
+            last_view: None,
             saved_at_epoch_ms: chrono::Utc::now().timestamp_millis(),
         };
 
         let mut app = App::default();
-        let result = apply_snapshot_to_app(&snapshot, &mut app);
+        let result = apply_snapshot_to_app(&snapshot, &mut app, true);
 
         assert!(result.is_ok(), "apply_snapshot_to_app 应该成功");
 
@@ -1138,4 +1332,56 @@ mod tests {
             _ => panic!("期望 Completed 状态"),
         }
     }
+
+    #[test]
+    fn test_queue_entry_origins_roundtrip() {
+        let mut app = App::default();
+        app.play_queue
+            .set_songs(vec![song(1, "A", "X"), song(2, "B", "X")], Some(0));
+        app.play_queue.set_origin("album:1");
+        assert!(
+            app_to_snapshot(&app)
+                .player
+                .play_queue
+                .entry_origins
+                .is_empty(),
+            "来源一致时不单独保存"
+        );
+
+        app.play_queue
+            .extend_from([song(3, "C", "Y")], Some("playlist:7"));
+        let snapshot = app_to_snapshot(&app);
+        assert_eq!(snapshot.player.play_queue.entry_origins.len(), 3);
+
+        let mut restored = App::default();
+        restore_queue_and_progress(&snapshot, &mut restored);
+        let origins: Vec<_> = (0..3)
+            .map(|i| restored.play_queue.entry_origin(i).map(|o| o.to_string()))
+            .collect();
+        assert_eq!(
+            origins,
+            [
+                Some("album:1".to_owned()),
+                Some("album:1".to_owned()),
+                Some("playlist:7".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_view_roundtrip_skips_login() {
+        let mut app = App {
+            view: View::Lyrics,
+            ..Default::default()
+        };
+        let snapshot = app_to_snapshot(&app);
+        assert_eq!(snapshot.last_view.as_deref(), Some("Lyrics"));
+
+        let mut restored = App::default();
+        apply_snapshot_to_app(&snapshot, &mut restored, true).unwrap();
+        assert_eq!(restored.last_view, Some(View::Lyrics));
+
+        app.view = View::Login;
+        assert_eq!(app_to_snapshot(&app).last_view, None, "登录页不应记录");
+    }
 }
@@ -0,0 +1,341 @@
+//! 启动参数 `--reset`：按领域清理数据目录中的持久化状态
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use crate::data_paths::DataPaths;
+use crate::error::AppError;
+
+/// 可重置的状态领域
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ResetDomain {
+    /// 登录 cookie（需要重新登录）
+    Cookies,
+    /// 应用设置
+    Settings,
+    /// 播放状态（队列、进度、歌单预加载）
+    PlayerState,
+    /// 仅清除播放状态中的歌单预加载
+    Preloads,
+    /// 本地播放历史（`stats` 命令的数据来源）
+    PlayHistory,
+    /// 崩溃报告（含最近的命令记录）
+    CrashReports,
+    /// 音频缓存
+    AudioCache,
+    /// 以上全部
+    All,
+}
+
+/// 单个清理动作
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResetAction {
+    RemoveFile(PathBuf),
+    RemoveDir(PathBuf),
+    /// 保留播放状态文件，仅清空其中的 `playlist_preloads`
+    ClearPreloads(PathBuf),
+    /// 数据目录之外的音频缓存目录：只删除缓存文件与索引，目录本身和其它文件保留
+    ClearCacheFiles(PathBuf),
+}
+
+/// 包含 cookies 或 all 时需要确认（会丢失登录状态）
+pub fn needs_confirmation(domains: &[ResetDomain]) -> bool {
+    domains
+        .iter()
+        .any(|d| matches!(d, ResetDomain::Cookies | ResetDomain::All))
+}
+
+/// 将领域映射为清理动作（去重，按固定顺序）
+pub fn plan(paths: &DataPaths, domains: &[ResetDomain]) -> Vec<ResetAction> {
+    let has = |d: ResetDomain| domains.contains(&ResetDomain::All) || domains.contains(&d);
+
+    let mut actions = Vec::new();
+    if has(ResetDomain::Cookies) {
+        actions.push(ResetAction::RemoveFile(paths.cookies()));
+    }
+    if has(ResetDomain::Settings) {
+        actions.push(ResetAction::RemoveFile(paths.settings()));
+    }
+    if has(ResetDomain::PlayerState) {
+        actions.push(ResetAction::RemoveFile(paths.player_state()));
+        actions.push(ResetAction::RemoveFile(paths.queue()));
+    } else if has(ResetDomain::Preloads) {
+        actions.push(ResetAction::ClearPreloads(paths.player_state()));
+    }
+    if has(ResetDomain::PlayHistory) {
+        actions.push(ResetAction::RemoveFile(paths.play_history()));
+    }
+    if has(ResetDomain::CrashReports) {
+        actions.push(ResetAction::RemoveDir(paths.crash_reports_dir()));
+    }
+    if has(ResetDomain::AudioCache) {
+        // 默认位置可能还留有移动前的旧缓存，一并删除
+        actions.push(ResetAction::RemoveDir(paths.default_audio_cache_dir()));
+        if paths.audio_cache_overridden() {
+            actions.push(ResetAction::ClearCacheFiles(paths.audio_cache_dir()));
+        }
+    }
+    actions
+}
+
+/// 需要确认时询问用户；`assume_yes` 对应 `--yes`
+pub fn confirm(
+    domains: &[ResetDomain],
+    assume_yes: bool,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<bool> {
+    if assume_yes || !needs_confirmation(domains) {
+        return Ok(true);
+    }
+    write!(output, "即将清除登录状态等数据，确认继续？[y/N] ")?;
+    output.flush()?;
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(matches!(
+        line.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+/// 执行清理动作，返回实际发生的变更描述（不存在的文件跳过）
+pub fn execute(paths: &DataPaths, actions: &[ResetAction]) -> Result<Vec<String>, AppError> {
+    let mut done = Vec::new();
+    for action in actions {
+        match action {
+            ResetAction::RemoveFile(p) => {
+                paths.ensure_within(p)?;
+                if p.is_file() {
+                    fs::remove_file(p)?;
+                    done.push(format!("已删除 {}", p.display()));
+                }
+            }
+            ResetAction::RemoveDir(p) => {
+                paths.ensure_within(p)?;
+                if p.is_dir() {
+                    fs::remove_dir_all(p)?;
+                    done.push(format!("已删除目录 {}", p.display()));
+                }
+            }
+            ResetAction::ClearPreloads(p) => {
+                paths.ensure_within(p)?;
+                if !p.is_file() {
+                    continue;
+                }
+                let mut snapshot: serde_json::Value = serde_json::from_slice(&fs::read(p)?)?;
+                let cleared = snapshot
+                    .as_object_mut()
+                    .and_then(|obj| obj.get_mut("playlist_preloads"))
+                    .and_then(|v| v.as_object_mut())
+                    .filter(|m| !m.is_empty())
+                    .map(|m| {
+                        let n = m.len();
+                        m.clear();
+                        n
+                    });
+                if let Some(n) = cleared {
+                    fs::write(p, serde_json::to_vec_pretty(&snapshot)?)?;
+                    done.push(format!("已清空 {} 中的 {n} 个歌单预加载", p.display()));
+                }
+            }
+            // 用户指定的目录，不要求位于数据目录内
+            ResetAction::ClearCacheFiles(dir) => {
+                let Ok(rd) = fs::read_dir(dir) else {
+                    continue;
+                };
+                let mut removed = 0usize;
+                for ent in rd.flatten() {
+                    let p = ent.path();
+                    let is_cache_file = p.is_file()
+                        && (p.file_name().is_some_and(|n| n == "index.json")
+                            || p.extension()
+                                .is_some_and(|ext| ext == "bin" || ext == "tmp"));
+                    if is_cache_file {
+                        fs::remove_file(&p)?;
+                        removed += 1;
+                    }
+                }
+                if removed > 0 {
+                    done.push(format!(
+                        "已删除 {} 中的 {removed} 个缓存文件",
+                        dir.display()
+                    ));
+                }
+            }
+        }
+    }
+    Ok(done)
+}
+
+/// 处理 `--reset`：确认、执行并打印结果；返回 false 表示用户取消
+pub fn run(paths: &DataPaths, domains: &[ResetDomain], assume_yes: bool) -> Result<bool, AppError> {
+    let stdin = io::stdin();
+    if !confirm(domains, assume_yes, &mut stdin.lock(), &mut io::stdout())? {
+        println!("已取消重置");
+        return Ok(false);
+    }
+
+    let actions = plan(paths, domains);
+    let done = execute(paths, &actions)?;
+    if done.is_empty() {
+        println!("没有需要清理的数据（{}）", paths.root().display());
+    }
+    for line in &done {
+        println!("{line}");
+        tracing::info!("[Reset] {line}");
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn domains_map_to_expected_paths() {
+        let paths = DataPaths::new("/data");
+        assert_eq!(
+            plan(&paths, &[ResetDomain::Cookies]),
+            vec![ResetAction::RemoveFile(paths.cookies())]
+        );
+        assert_eq!(
+            plan(&paths, &[ResetDomain::PlayHistory]),
+            vec![ResetAction::RemoveFile(paths.play_history())]
+        );
+        assert_eq!(
+            plan(&paths, &[ResetDomain::CrashReports]),
+            vec![ResetAction::RemoveDir(paths.crash_reports_dir())]
+        );
+        assert_eq!(
+            plan(&paths, &[ResetDomain::Preloads, ResetDomain::AudioCache]),
+            vec![
+                ResetAction::ClearPreloads(paths.player_state()),
+                ResetAction::RemoveDir(paths.audio_cache_dir()),
+            ]
+        );
+        // player-state 已包含预加载，不重复处理
+        assert_eq!(
+            plan(&paths, &[ResetDomain::Preloads, ResetDomain::PlayerState]),
+            vec![
+                ResetAction::RemoveFile(paths.player_state()),
+                ResetAction::RemoveFile(paths.queue()),
+            ]
+        );
+        assert_eq!(
+            plan(&paths, &[ResetDomain::All]),
+            vec![
+                ResetAction::RemoveFile(paths.cookies()),
+                ResetAction::RemoveFile(paths.settings()),
+                ResetAction::RemoveFile(paths.player_state()),
+                ResetAction::RemoveFile(paths.queue()),
+                ResetAction::RemoveFile(paths.play_history()),
+                ResetAction::RemoveDir(paths.crash_reports_dir()),
+                ResetAction::RemoveDir(paths.audio_cache_dir()),
+            ]
+        );
+    }
+
+    #[test]
+    fn confirmation_required_only_for_cookies_or_all() {
+        assert!(!needs_confirmation(&[
+            ResetDomain::Settings,
+            ResetDomain::AudioCache
+        ]));
+        assert!(needs_confirmation(&[ResetDomain::Cookies]));
+        assert!(needs_confirmation(&[ResetDomain::All]));
+
+        let mut out = Vec::new();
+        let ok = confirm(
+            &[ResetDomain::Settings],
+            false,
+            &mut Cursor::new(""),
+            &mut out,
+        )
+        .unwrap();
+        assert!(ok);
+        assert!(out.is_empty(), "无需确认时不应提示");
+
+        let ok = confirm(&[ResetDomain::All], true, &mut Cursor::new(""), &mut out).unwrap();
+        assert!(ok, "--yes 跳过确认");
+
+        let ok = confirm(&[ResetDomain::All], false, &mut Cursor::new(""), &mut out).unwrap();
+        assert!(!ok, "无输入（非交互）视为拒绝");
+
+        let ok = confirm(
+            &[ResetDomain::Cookies],
+            false,
+            &mut Cursor::new("y\n"),
+            &mut out,
+        )
+        .unwrap();
+        assert!(ok);
+    }
+
+    #[test]
+    fn execute_removes_files_and_clears_preloads() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = DataPaths::new(dir.path());
+        fs::write(paths.settings(), "{}").unwrap();
+        fs::write(
+            paths.player_state(),
+            r#"{"version":3,"playlist_preloads":{"1":{}},"volume":0.5}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(paths.audio_cache_dir()).unwrap();
+        fs::write(paths.audio_cache_dir().join("index.json"), "{}").unwrap();
+
+        let actions = plan(
+            &paths,
+            &[
+                ResetDomain::Settings,
+                ResetDomain::Preloads,
+                ResetDomain::AudioCache,
+                ResetDomain::Cookies,
+            ],
+        );
+        let done = execute(&paths, &actions).unwrap();
+
+        assert_eq!(done.len(), 3, "不存在的 cookie 文件应跳过: {done:?}");
+        assert!(!paths.settings().exists());
+        assert!(!paths.audio_cache_dir().exists());
+        let state: serde_json::Value =
+            serde_json::from_slice(&fs::read(paths.player_state()).unwrap()).unwrap();
+        assert_eq!(state["playlist_preloads"], serde_json::json!({}));
+        assert_eq!(state["volume"], serde_json::json!(0.5));
+    }
+
+    #[test]
+    fn external_audio_cache_keeps_the_directory_and_other_files() {
+        let data = tempfile::tempdir().expect("tempdir");
+        let hdd = tempfile::tempdir().expect("tempdir");
+        let paths =
+            DataPaths::new(data.path()).with_audio_cache_dir(Some(hdd.path().to_path_buf()));
+        assert_eq!(
+            plan(&paths, &[ResetDomain::AudioCache]),
+            vec![
+                ResetAction::RemoveDir(paths.default_audio_cache_dir()),
+                ResetAction::ClearCacheFiles(hdd.path().to_path_buf()),
+            ]
+        );
+
+        for name in [
+            "1_320000.bin",
+            "2_999000.bin.3.tmp",
+            "index.json",
+            "notes.txt",
+        ] {
+            fs::write(hdd.path().join(name), "x").unwrap();
+        }
+        let done = execute(&paths, &plan(&paths, &[ResetDomain::AudioCache])).unwrap();
+
+        assert_eq!(done.len(), 1, "{done:?}");
+        let left: Vec<_> = fs::read_dir(hdd.path())
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name())
+            .collect();
+        assert_eq!(left, ["notes.txt"]);
+    }
+}
@@ -0,0 +1,79 @@
+//! 按歌曲保存的歌词 offset
+//!
+//! 不同歌曲的歌词时间轴误差不同，单一的全局 offset 不够用。这里按歌曲 id 记录 offset，
+//! 最近使用的排在最后；超过 [`MAX_LYRICS_OFFSETS`] 条时淘汰最久未使用的，避免设置文件无限增长。
+
+use serde::{Deserialize, Serialize};
+
+/// 最多保存的歌曲数
+pub const MAX_LYRICS_OFFSETS: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SongLyricsOffset {
+    pub song_id: i64,
+    pub offset_ms: i64,
+}
+
+/// 歌曲 id → 歌词 offset（按最近使用排序的 LRU）
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LyricsOffsets(Vec<SongLyricsOffset>);
+
+impl LyricsOffsets {
+    /// 查询歌曲的 offset（不改变使用顺序）
+    pub fn get(&self, song_id: i64) -> Option<i64> {
+        self.0
+            .iter()
+            .find(|e| e.song_id == song_id)
+            .map(|e| e.offset_ms)
+    }
+
+    /// 查询并标记为最近使用（歌词加载时调用）
+    pub fn touch(&mut self, song_id: i64) -> Option<i64> {
+        let pos = self.0.iter().position(|e| e.song_id == song_id)?;
+        let entry = self.0.remove(pos);
+        self.0.push(entry);
+        Some(entry.offset_ms)
+    }
+
+    /// 设置歌曲的 offset 并标记为最近使用，超出上限时淘汰最久未使用的
+    pub fn set(&mut self, song_id: i64, offset_ms: i64) {
+        self.0.retain(|e| e.song_id != song_id);
+        self.0.push(SongLyricsOffset { song_id, offset_ms });
+        let excess = self.0.len().saturating_sub(MAX_LYRICS_OFFSETS);
+        self.0.drain(..excess);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_replaces_existing_entry_and_marks_recent() {
+        let mut offsets = LyricsOffsets::default();
+        offsets.set(1, 200);
+        offsets.set(2, -100);
+        offsets.set(1, 400);
+        assert_eq!(offsets.0.len(), 2);
+        assert_eq!(offsets.get(1), Some(400));
+        assert_eq!(offsets.get(3), None);
+        assert_eq!(offsets.0.last().map(|e| e.song_id), Some(1));
+    }
+
+    #[test]
+    fn pruning_evicts_least_recently_used() {
+        let mut offsets = LyricsOffsets::default();
+        for id in 0..MAX_LYRICS_OFFSETS as i64 {
+            offsets.set(id, id);
+        }
+        // 加载过歌词的歌曲不会被优先淘汰
+        assert_eq!(offsets.touch(0), Some(0));
+        offsets.set(10_000, 50);
+
+        assert_eq!(offsets.0.len(), MAX_LYRICS_OFFSETS);
+        assert_eq!(offsets.get(0), Some(0));
+        assert_eq!(offsets.get(1), None, "最久未使用的被淘汰");
+        assert_eq!(offsets.get(10_000), Some(50));
+    }
+}
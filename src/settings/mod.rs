@@ -1,5 +1,12 @@
+pub mod lyrics_offsets;
 pub mod store;
+pub mod validate;
+
+pub use lyrics_offsets::LyricsOffsets;
 
 pub use store::{
-    AppSettings, load_settings, play_mode_from_string, play_mode_to_string, save_settings,
+    AppSettings, DEFAULT_LYRICS_MAX_LINES, audio_focus_policy_from_string,
+    audio_focus_policy_to_string, load_settings, play_mode_from_string, play_mode_to_string,
+    save_settings, settings_path, song_name_mode_from_string, song_name_mode_to_string,
+    startup_view_from_string, startup_view_to_string,
 };
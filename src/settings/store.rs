@@ -1,23 +1,103 @@
-use crate::app::PlayMode;
+use super::LyricsOffsets;
+use super::validate;
+use crate::app::{AudioFocusPolicy, PlayMode, SongNameMode, StartupView};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 歌词行数上限的默认值
+pub const DEFAULT_LYRICS_MAX_LINES: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AppSettings {
     // 播放器设置
     pub volume: f32,
+    /// `Alt+↑/↓` 每次调整的音量（0.01～0.25）
+    #[serde(default = "default_volume_step")]
+    pub volume_step: f32,
     pub br: i64,
     pub play_mode: String,
     pub lyrics_offset_ms: i64,
+    /// 按歌曲保存的歌词 offset（覆盖全局 `lyrics_offset_ms`），最多保留最近 500 首
+    #[serde(default)]
+    pub lyrics_song_offsets: LyricsOffsets,
+    /// 单首歌词的行数上限，超出时截断（防止异常的超大歌词）
+    #[serde(default = "default_lyrics_max_lines")]
+    pub lyrics_max_lines: usize,
+    /// 在底部状态栏显示当前歌词行
+    #[serde(default = "default_status_lyrics")]
+    pub status_lyrics: bool,
     #[serde(default = "default_crossfade_ms")]
     pub crossfade_ms: u64,
+    /// 音量均衡：按接口给出的响度增益调整每首歌的音量
+    #[serde(default)]
+    pub normalize_volume: bool,
+    /// 播放倍速（0.5～2.0，变速同时变调）
+    #[serde(default = "default_playback_speed")]
+    pub playback_speed: f32,
+    /// 低音/高音增益（dB，-12～12）
+    #[serde(default)]
+    pub eq_bass_db: i8,
+    #[serde(default)]
+    pub eq_treble_db: i8,
+    #[serde(default = "default_seek_step_small_ms")]
+    pub seek_step_small_ms: u64,
+    #[serde(default = "default_seek_step_large_ms")]
+    pub seek_step_large_ms: u64,
+    /// Ctrl+Z 挂起后恢复时保持暂停（false 则自动继续播放）
+    #[serde(default)]
+    pub pause_on_suspend: bool,
+    /// 终端失去焦点时自动暂停，重新获得焦点时继续（需终端支持焦点事件上报）
+    #[serde(default)]
+    pub pause_on_focus_lost: bool,
+    /// 其它程序开始播放声音时：`Ignore` / `Duck`（降低音量）/ `Pause`；需以 `audio-focus` 特性构建
+    #[serde(default = "default_audio_focus_policy")]
+    pub audio_focus_policy: String,
+    /// `Duck` 策略降低音量的百分比（10～90）
+    #[serde(default = "default_audio_focus_duck_percent")]
+    pub audio_focus_duck_percent: u8,
+    /// 监听系统全局快捷键（媒体键），终端无焦点时也生效；需以 `global-hotkeys` 特性构建
+    #[serde(default)]
+    pub global_hotkeys: bool,
+    /// 新歌开始播放时显示桌面通知；需以 `notifications` 特性构建
+    #[serde(default)]
+    pub notifications_enabled: bool,
+    /// 停止播放后空闲多少秒关闭音频输出并释放解码缓冲，下次播放时重新打开；0 表示一直保留
+    #[serde(default = "default_audio_idle_release_secs")]
+    pub audio_idle_release_secs: u64,
+
+    // 启动设置
+    #[serde(default = "default_startup_view")]
+    pub startup_view: String,
+    /// 启动时恢复上次的播放队列（false 时仍恢复音量、歌单等其它状态）
+    #[serde(default = "default_restore_queue_on_startup")]
+    pub restore_queue_on_startup: bool,
+    /// 启动时恢复的歌曲加载后直接继续播放（false 时停在暂停状态，按空格继续）
+    #[serde(default)]
+    pub resume_on_start: bool,
+    /// 播放状态超过该时长（小时）时，启动后先询问是否恢复队列；0 表示总是直接恢复
+    #[serde(default = "default_stale_state_prompt_hours")]
+    pub stale_state_prompt_hours: u64,
+
+    // 界面设置
+    /// 主体三栏宽度百分比 `[左, 中, 右]`
+    #[serde(default = "default_pane_ratios")]
+    pub pane_ratios: [u16; 3],
+    /// 歌曲名语言：`Original` / `TranslatedFirst` / `Both`
+    #[serde(default = "default_song_name_mode")]
+    pub song_name_mode: String,
+    /// 单色显示：不输出颜色，用粗体/反色与文本标记区分焦点和选中（`NO_COLOR` 或 `--no-color` 时总是单色）
+    #[serde(default)]
+    pub no_color: bool,
 
     // 缓存/预加载设置
     #[serde(default = "default_preload_count")]
     pub preload_count: usize,
     #[serde(default = "default_audio_cache_max_mb")]
     pub audio_cache_max_mb: usize,
+    /// 音频缓存目录（支持 `~/` 开头）；未设置时为数据目录下的 `audio_cache/`，`--cache-dir` 优先
+    #[serde(default)]
+    pub audio_cache_dir: Option<PathBuf>,
     #[serde(default = "default_download_concurrency")]
     pub download_concurrency: Option<usize>,
     #[serde(default = "default_http_timeout_secs")]
@@ -30,31 +110,79 @@ pub struct AppSettings {
     pub download_retry_backoff_ms: u64,
     #[serde(default = "default_download_retry_backoff_max_ms")]
     pub download_retry_backoff_max_ms: u64,
+    /// `D` 下载歌曲的保存目录（支持 `~/` 开头）；未设置时使用系统音乐目录
+    #[serde(default)]
+    pub download_dir: Option<PathBuf>,
+
+    // 日志设置
+    /// 日志滚动周期：`daily` / `hourly` / `never`
+    #[serde(default = "default_log_rotation")]
+    pub log_rotation: String,
+    /// 最多保留的日志文件数，0 表示不清理
+    #[serde(default = "default_log_max_files")]
+    pub log_max_files: usize,
 }
 
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
             volume: 1.0,
+            volume_step: default_volume_step(),
             br: 999_000,
             play_mode: "ListLoop".to_owned(),
             lyrics_offset_ms: 0,
+            lyrics_song_offsets: LyricsOffsets::default(),
+            lyrics_max_lines: DEFAULT_LYRICS_MAX_LINES,
+            status_lyrics: default_status_lyrics(),
             crossfade_ms: 300,
+            normalize_volume: false,
+            playback_speed: default_playback_speed(),
+            eq_bass_db: 0,
+            eq_treble_db: 0,
+            seek_step_small_ms: 5_000,
+            seek_step_large_ms: 30_000,
+            pause_on_suspend: false,
+            pause_on_focus_lost: false,
+            audio_focus_policy: default_audio_focus_policy(),
+            audio_focus_duck_percent: default_audio_focus_duck_percent(),
+            global_hotkeys: false,
+            notifications_enabled: false,
+            audio_idle_release_secs: default_audio_idle_release_secs(),
+
+            startup_view: "Playlists".to_owned(),
+            restore_queue_on_startup: true,
+            resume_on_start: false,
+            stale_state_prompt_hours: 48,
+
+            pane_ratios: [20, 47, 33],
+            song_name_mode: default_song_name_mode(),
+            no_color: false,
 
             // 缓存/预加载默认值
             preload_count: 5,
             audio_cache_max_mb: 2048,
+            audio_cache_dir: None,
             download_concurrency: None, // None 表示自动检测
             http_timeout_secs: 30,
             http_connect_timeout_secs: 10,
             download_retries: 2,
             download_retry_backoff_ms: 250,
             download_retry_backoff_max_ms: 2000,
+            download_dir: None,
+
+            log_rotation: "daily".to_owned(),
+            log_max_files: crate::logging::DEFAULT_LOG_MAX_FILES,
         }
     }
 }
 
 // 默认值函数（用于 serde default）
+fn default_log_rotation() -> String {
+    "daily".to_owned()
+}
+fn default_log_max_files() -> usize {
+    crate::logging::DEFAULT_LOG_MAX_FILES
+}
 fn default_preload_count() -> usize {
     5
 }
@@ -79,16 +207,68 @@ fn default_download_retry_backoff_ms() -> u64 {
 fn default_download_retry_backoff_max_ms() -> u64 {
     2000
 }
+fn default_lyrics_max_lines() -> usize {
+    DEFAULT_LYRICS_MAX_LINES
+}
+fn default_status_lyrics() -> bool {
+    true
+}
+
+fn default_volume_step() -> f32 {
+    0.05
+}
+fn default_playback_speed() -> f32 {
+    1.0
+}
 fn default_crossfade_ms() -> u64 {
     300
 }
+fn default_seek_step_small_ms() -> u64 {
+    5_000
+}
+fn default_seek_step_large_ms() -> u64 {
+    30_000
+}
+fn default_audio_idle_release_secs() -> u64 {
+    60
+}
+fn default_audio_focus_policy() -> String {
+    "Ignore".to_owned()
+}
+fn default_audio_focus_duck_percent() -> u8 {
+    50
+}
+fn default_startup_view() -> String {
+    "Playlists".to_owned()
+}
+fn default_restore_queue_on_startup() -> bool {
+    true
+}
+fn default_stale_state_prompt_hours() -> u64 {
+    48
+}
+fn default_pane_ratios() -> [u16; 3] {
+    [20, 47, 33]
+}
+fn default_song_name_mode() -> String {
+    "Original".to_owned()
+}
 
 pub fn load_settings(data_dir: &Path) -> AppSettings {
     let p = settings_path(data_dir);
     let Ok(bytes) = fs::read(&p) else {
         return AppSettings::default();
     };
-    serde_json::from_slice(&bytes).unwrap_or_default()
+    let Ok(mut value) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return AppSettings::default();
+    };
+    let mut adjustments = validate::sanitize_json(&mut value);
+    let Ok(mut settings) = serde_json::from_value::<AppSettings>(value) else {
+        return AppSettings::default();
+    };
+    adjustments.extend(settings.normalize());
+    validate::warn_adjustments(&adjustments);
+    settings
 }
 
 pub fn save_settings(data_dir: &Path, s: &AppSettings) -> std::io::Result<()> {
@@ -123,6 +303,59 @@ pub fn play_mode_from_string(s: &str) -> PlayMode {
     }
 }
 
-fn settings_path(data_dir: &Path) -> PathBuf {
+pub fn startup_view_to_string(v: StartupView) -> String {
+    match v {
+        StartupView::Last => "Last",
+        StartupView::Playlists => "Playlists",
+        StartupView::Search => "Search",
+        StartupView::NowPlaying => "NowPlaying",
+    }
+    .to_owned()
+}
+
+pub fn startup_view_from_string(s: &str) -> StartupView {
+    match s {
+        "Last" => StartupView::Last,
+        "Search" => StartupView::Search,
+        "NowPlaying" => StartupView::NowPlaying,
+        _ => StartupView::Playlists,
+    }
+}
+
+pub fn song_name_mode_to_string(m: SongNameMode) -> String {
+    match m {
+        SongNameMode::Original => "Original",
+        SongNameMode::TranslatedFirst => "TranslatedFirst",
+        SongNameMode::Both => "Both",
+    }
+    .to_owned()
+}
+
+pub fn song_name_mode_from_string(s: &str) -> SongNameMode {
+    match s {
+        "TranslatedFirst" => SongNameMode::TranslatedFirst,
+        "Both" => SongNameMode::Both,
+        _ => SongNameMode::Original,
+    }
+}
+
+pub fn audio_focus_policy_to_string(p: AudioFocusPolicy) -> String {
+    match p {
+        AudioFocusPolicy::Ignore => "Ignore",
+        AudioFocusPolicy::Duck => "Duck",
+        AudioFocusPolicy::Pause => "Pause",
+    }
+    .to_owned()
+}
+
+pub fn audio_focus_policy_from_string(s: &str) -> AudioFocusPolicy {
+    match s {
+        "Duck" => AudioFocusPolicy::Duck,
+        "Pause" => AudioFocusPolicy::Pause,
+        _ => AudioFocusPolicy::Ignore,
+    }
+}
+
+pub fn settings_path(data_dir: &Path) -> PathBuf {
     data_dir.join("settings.json")
 }
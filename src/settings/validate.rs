@@ -0,0 +1,500 @@
+//! 读取 settings.json 后的校验与修正
+//!
+//! 手工编辑的设置文件可能包含越界的数值（音量 9.5、预加载 100000 个歌单）或未知的枚举字符串。
+//! [`AppSettings::normalize`] 把每个字段限制到可用范围内，并返回修改记录；调用方用
+//! [`warn_adjustments`] 一次性记录所有被修改的字段。
+
+use super::store::AppSettings;
+use crate::audio_worker::{EQ_MAX_DB, EQ_MIN_DB, MAX_PLAYBACK_SPEED, MIN_PLAYBACK_SPEED};
+use serde_json::Value;
+
+/// 可选的音质档位（加载时把其它值吸附到最近的一档）
+pub const BR_OPTIONS: [i64; 4] = [128_000, 192_000, 320_000, 999_000];
+
+/// 一个被修正的字段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Adjustment {
+    pub field: &'static str,
+    pub from: String,
+    pub to: String,
+}
+
+/// 设置文件中应为非负整数的字段；负数在反序列化前改为 0（否则整个文件都会读取失败）
+const UNSIGNED_FIELDS: [&str; 16] = [
+    "lyrics_max_lines",
+    "crossfade_ms",
+    "seek_step_small_ms",
+    "seek_step_large_ms",
+    "audio_focus_duck_percent",
+    "audio_idle_release_secs",
+    "stale_state_prompt_hours",
+    "preload_count",
+    "audio_cache_max_mb",
+    "download_concurrency",
+    "http_timeout_secs",
+    "http_connect_timeout_secs",
+    "download_retries",
+    "download_retry_backoff_ms",
+    "download_retry_backoff_max_ms",
+    "log_max_files",
+];
+
+/// 反序列化前的修正：非负整数字段中的负数改为 0
+pub fn sanitize_json(value: &mut Value) -> Vec<Adjustment> {
+    let mut adjustments = Vec::new();
+    let Some(obj) = value.as_object_mut() else {
+        return adjustments;
+    };
+    for field in UNSIGNED_FIELDS {
+        if let Some(v) = obj.get_mut(field)
+            && v.as_i64().is_some_and(|n| n < 0)
+        {
+            adjustments.push(Adjustment {
+                field,
+                from: v.to_string(),
+                to: "0".to_owned(),
+            });
+            *v = Value::from(0);
+        }
+    }
+    adjustments
+}
+
+/// 把 `value` 限制到 `[min, max]`，越界时记录修改
+fn clamp<T>(adjustments: &mut Vec<Adjustment>, field: &'static str, value: &mut T, min: T, max: T)
+where
+    T: PartialOrd + Copy + ToString,
+{
+    let clamped = if *value < min {
+        min
+    } else if *value > max {
+        max
+    } else {
+        return;
+    };
+    adjustments.push(Adjustment {
+        field,
+        from: value.to_string(),
+        to: clamped.to_string(),
+    });
+    *value = clamped;
+}
+
+/// 字符串不在 `allowed` 中时改为 `fallback`
+fn one_of(
+    adjustments: &mut Vec<Adjustment>,
+    field: &'static str,
+    value: &mut String,
+    allowed: &[&str],
+    fallback: &str,
+) {
+    if allowed.contains(&value.as_str()) {
+        return;
+    }
+    adjustments.push(Adjustment {
+        field,
+        from: format!("{value:?}"),
+        to: format!("{fallback:?}"),
+    });
+    *value = fallback.to_owned();
+}
+
+/// 与 `br` 最接近的可选音质
+pub fn nearest_br(br: i64) -> i64 {
+    BR_OPTIONS
+        .iter()
+        .copied()
+        .min_by_key(|option| option.abs_diff(br))
+        .unwrap_or(999_000)
+}
+
+impl AppSettings {
+    /// 把各字段修正到可用范围内，返回修改记录（没有修改时为空）
+    pub fn normalize(&mut self) -> Vec<Adjustment> {
+        let mut adj = Vec::new();
+
+        if !self.volume.is_finite() {
+            adj.push(Adjustment {
+                field: "volume",
+                from: self.volume.to_string(),
+                to: "1".to_owned(),
+            });
+            self.volume = 1.0;
+        }
+        clamp(&mut adj, "volume", &mut self.volume, 0.0, 2.0);
+        if !self.volume_step.is_finite() {
+            adj.push(Adjustment {
+                field: "volume_step",
+                from: self.volume_step.to_string(),
+                to: "0.05".to_owned(),
+            });
+            self.volume_step = 0.05;
+        }
+        clamp(&mut adj, "volume_step", &mut self.volume_step, 0.01, 0.25);
+
+        let br = nearest_br(self.br);
+        if br != self.br {
+            adj.push(Adjustment {
+                field: "br",
+                from: self.br.to_string(),
+                to: br.to_string(),
+            });
+            self.br = br;
+        }
+
+        one_of(
+            &mut adj,
+            "play_mode",
+            &mut self.play_mode,
+            &["Sequential", "ListLoop", "SingleLoop", "Shuffle"],
+            "ListLoop",
+        );
+        clamp(
+            &mut adj,
+            "lyrics_max_lines",
+            &mut self.lyrics_max_lines,
+            1,
+            100_000,
+        );
+        clamp(&mut adj, "crossfade_ms", &mut self.crossfade_ms, 0, 2_000);
+        if !self.playback_speed.is_finite() {
+            adj.push(Adjustment {
+                field: "playback_speed",
+                from: self.playback_speed.to_string(),
+                to: "1".to_owned(),
+            });
+            self.playback_speed = 1.0;
+        }
+        clamp(
+            &mut adj,
+            "playback_speed",
+            &mut self.playback_speed,
+            MIN_PLAYBACK_SPEED,
+            MAX_PLAYBACK_SPEED,
+        );
+        clamp(
+            &mut adj,
+            "eq_bass_db",
+            &mut self.eq_bass_db,
+            EQ_MIN_DB,
+            EQ_MAX_DB,
+        );
+        clamp(
+            &mut adj,
+            "eq_treble_db",
+            &mut self.eq_treble_db,
+            EQ_MIN_DB,
+            EQ_MAX_DB,
+        );
+        clamp(
+            &mut adj,
+            "audio_focus_duck_percent",
+            &mut self.audio_focus_duck_percent,
+            10,
+            90,
+        );
+        clamp(
+            &mut adj,
+            "audio_idle_release_secs",
+            &mut self.audio_idle_release_secs,
+            0,
+            86_400,
+        );
+        one_of(
+            &mut adj,
+            "audio_focus_policy",
+            &mut self.audio_focus_policy,
+            &["Ignore", "Duck", "Pause"],
+            "Ignore",
+        );
+        one_of(
+            &mut adj,
+            "startup_view",
+            &mut self.startup_view,
+            &["Last", "Playlists", "Search", "NowPlaying"],
+            "Playlists",
+        );
+        one_of(
+            &mut adj,
+            "song_name_mode",
+            &mut self.song_name_mode,
+            &["Original", "TranslatedFirst", "Both"],
+            "Original",
+        );
+
+        clamp(&mut adj, "preload_count", &mut self.preload_count, 0, 50);
+        clamp(
+            &mut adj,
+            "audio_cache_max_mb",
+            &mut self.audio_cache_max_mb,
+            64,
+            65_536,
+        );
+        if let Some(n) = self.download_concurrency.as_mut() {
+            clamp(&mut adj, "download_concurrency", n, 1, 64);
+        }
+        clamp(
+            &mut adj,
+            "http_timeout_secs",
+            &mut self.http_timeout_secs,
+            1,
+            300,
+        );
+        clamp(
+            &mut adj,
+            "http_connect_timeout_secs",
+            &mut self.http_connect_timeout_secs,
+            1,
+            300,
+        );
+        clamp(
+            &mut adj,
+            "download_retries",
+            &mut self.download_retries,
+            0,
+            10,
+        );
+        clamp(
+            &mut adj,
+            "download_retry_backoff_ms",
+            &mut self.download_retry_backoff_ms,
+            0,
+            60_000,
+        );
+        let backoff_min = self.download_retry_backoff_ms;
+        clamp(
+            &mut adj,
+            "download_retry_backoff_max_ms",
+            &mut self.download_retry_backoff_max_ms,
+            backoff_min,
+            60_000,
+        );
+
+        adj
+    }
+}
+
+/// 修改记录的汇总文本，如 `volume: 9.5 -> 2, br: 12345 -> 128000`
+pub fn describe_adjustments(adjustments: &[Adjustment]) -> Option<String> {
+    if adjustments.is_empty() {
+        return None;
+    }
+    Some(
+        adjustments
+            .iter()
+            .map(|a| format!("{}: {} -> {}", a.field, a.from, a.to))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// 用一条警告记录所有被修正的字段
+pub fn warn_adjustments(adjustments: &[Adjustment]) {
+    if let Some(summary) = describe_adjustments(adjustments) {
+        tracing::warn!(
+            count = adjustments.len(),
+            "设置文件中有超出范围的值，已修正: {summary}"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adjusted(f: impl FnOnce(&mut AppSettings)) -> (AppSettings, Vec<Adjustment>) {
+        let mut s = AppSettings::default();
+        f(&mut s);
+        let adj = s.normalize();
+        (s, adj)
+    }
+
+    #[test]
+    fn defaults_need_no_adjustment() {
+        assert!(AppSettings::default().normalize().is_empty());
+    }
+
+    #[test]
+    fn numeric_fields_are_clamped() {
+        type Case = (
+            &'static str,
+            fn(&mut AppSettings),
+            fn(&AppSettings) -> String,
+            &'static str,
+        );
+        let cases: [Case; 17] = [
+            ("volume", |s| s.volume = 9.5, |s| s.volume.to_string(), "2"),
+            ("volume", |s| s.volume = -1.0, |s| s.volume.to_string(), "0"),
+            (
+                "volume",
+                |s| s.volume = f32::NAN,
+                |s| s.volume.to_string(),
+                "1",
+            ),
+            (
+                "playback_speed",
+                |s| s.playback_speed = 3.0,
+                |s| s.playback_speed.to_string(),
+                "2",
+            ),
+            (
+                "eq_bass_db",
+                |s| s.eq_bass_db = -40,
+                |s| s.eq_bass_db.to_string(),
+                "-12",
+            ),
+            (
+                "volume_step",
+                |s| s.volume_step = 0.0,
+                |s| s.volume_step.to_string(),
+                "0.01",
+            ),
+            (
+                "volume_step",
+                |s| s.volume_step = f32::INFINITY,
+                |s| s.volume_step.to_string(),
+                "0.05",
+            ),
+            (
+                "crossfade_ms",
+                |s| s.crossfade_ms = 9_000,
+                |s| s.crossfade_ms.to_string(),
+                "2000",
+            ),
+            (
+                "preload_count",
+                |s| s.preload_count = 100_000,
+                |s| s.preload_count.to_string(),
+                "50",
+            ),
+            (
+                "audio_cache_max_mb",
+                |s| s.audio_cache_max_mb = 1,
+                |s| s.audio_cache_max_mb.to_string(),
+                "64",
+            ),
+            (
+                "audio_cache_max_mb",
+                |s| s.audio_cache_max_mb = 1 << 20,
+                |s| s.audio_cache_max_mb.to_string(),
+                "65536",
+            ),
+            (
+                "download_retries",
+                |s| s.download_retries = 99,
+                |s| s.download_retries.to_string(),
+                "10",
+            ),
+            (
+                "http_timeout_secs",
+                |s| s.http_timeout_secs = 0,
+                |s| s.http_timeout_secs.to_string(),
+                "1",
+            ),
+            (
+                "http_connect_timeout_secs",
+                |s| s.http_connect_timeout_secs = 3_600,
+                |s| s.http_connect_timeout_secs.to_string(),
+                "300",
+            ),
+            (
+                "download_concurrency",
+                |s| s.download_concurrency = Some(0),
+                |s| format!("{:?}", s.download_concurrency),
+                "Some(1)",
+            ),
+            (
+                "download_retry_backoff_max_ms",
+                |s| {
+                    s.download_retry_backoff_ms = 800;
+                    s.download_retry_backoff_max_ms = 100
+                },
+                |s| s.download_retry_backoff_max_ms.to_string(),
+                "800",
+            ),
+            (
+                "audio_focus_duck_percent",
+                |s| s.audio_focus_duck_percent = 100,
+                |s| s.audio_focus_duck_percent.to_string(),
+                "90",
+            ),
+        ];
+        for (field, set, get, expected) in cases {
+            let (s, adj) = adjusted(set);
+            assert_eq!(get(&s), expected, "{field}");
+            assert_eq!(adj.len(), 1, "{field}: {adj:?}");
+            assert_eq!(adj[0].field, field);
+        }
+    }
+
+    #[test]
+    fn br_snaps_to_nearest_option() {
+        for (br, expected) in [
+            (12_345, 128_000),
+            (170_000, 192_000),
+            (300_000, 320_000),
+            (700_000, 999_000),
+            (5_000_000, 999_000),
+        ] {
+            let (s, adj) = adjusted(|s| s.br = br);
+            assert_eq!(s.br, expected, "br {br}");
+            assert_eq!(adj.len(), 1);
+        }
+        assert!(adjusted(|s| s.br = 320_000).1.is_empty());
+    }
+
+    #[test]
+    fn unknown_strings_fall_back_to_defaults() {
+        let (s, adj) = adjusted(|s| {
+            s.play_mode = "Random".to_owned();
+            s.startup_view = "Home".to_owned();
+            s.song_name_mode = "".to_owned();
+            s.audio_focus_policy = "Mute".to_owned();
+        });
+        assert_eq!(s.play_mode, "ListLoop");
+        assert_eq!(s.startup_view, "Playlists");
+        assert_eq!(s.song_name_mode, "Original");
+        assert_eq!(s.audio_focus_policy, "Ignore");
+        let fields: Vec<_> = adj.iter().map(|a| a.field).collect();
+        assert_eq!(
+            fields,
+            [
+                "play_mode",
+                "audio_focus_policy",
+                "startup_view",
+                "song_name_mode"
+            ]
+        );
+    }
+
+    #[test]
+    fn negative_unsigned_values_become_zero_before_parsing() {
+        let mut value = serde_json::json!({
+            "volume": 0.5,
+            "download_retries": -3,
+            "preload_count": -1,
+            "lyrics_offset_ms": -200,
+        });
+        let adj = sanitize_json(&mut value);
+        assert_eq!(value["download_retries"], 0);
+        assert_eq!(value["preload_count"], 0);
+        // 有符号字段不受影响
+        assert_eq!(value["lyrics_offset_ms"], -200);
+        let fields: Vec<_> = adj.iter().map(|a| a.field).collect();
+        assert_eq!(fields, ["preload_count", "download_retries"]);
+    }
+
+    #[test]
+    fn adjustments_are_summarized_in_one_message() {
+        let (_, adj) = adjusted(|s| {
+            s.volume = 9.5;
+            s.br = 12_345;
+            s.preload_count = 100_000;
+        });
+        assert_eq!(
+            describe_adjustments(&adj).as_deref(),
+            Some("volume: 9.5 -> 2, br: 12345 -> 128000, preload_count: 100000 -> 50")
+        );
+        assert_eq!(describe_adjustments(&[]), None);
+    }
+}
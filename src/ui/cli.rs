@@ -1,3 +1,4 @@
+use crate::reset::ResetDomain;
 use clap::{Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -23,6 +24,14 @@ pub struct Cli {
     #[arg(long, env = "RUST_LOG")]
     pub log_filter: Option<String>,
 
+    /// 最多保留的日志文件数（覆盖 settings.json 的 `log_max_files`，0 表示不清理）
+    #[arg(long)]
+    pub log_retention: Option<usize>,
+
+    /// 覆盖音频缓存目录（默认 `{data_dir}/audio_cache`，优先于 settings.json 的 `audio_cache_dir`）
+    #[arg(long, env = "NETEASE_CACHE_DIR")]
+    pub cache_dir: Option<PathBuf>,
+
     /// 覆盖网易 domain（默认 https://music.163.com）
     #[arg(long, env = "NETEASE_DOMAIN")]
     pub domain: Option<String>,
@@ -31,9 +40,26 @@ pub struct Cli {
     #[arg(long, env = "NETEASE_API_DOMAIN")]
     pub api_domain: Option<String>,
 
+    /// HTTP/HTTPS/SOCKS5 代理（如 `socks5://127.0.0.1:1080`），网易接口与歌曲下载都经由该代理；
+    /// 未指定时使用环境变量 `HTTPS_PROXY`
+    #[arg(long)]
+    pub proxy: Option<String>,
+
     /// 禁用音频输出（无声模式/CI 可用，亦支持 NETEASE_NO_AUDIO=1）
     #[arg(long)]
     pub no_audio: bool,
+
+    /// 单色显示，不输出颜色（亦支持 NO_COLOR 环境变量）
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// 启动前重置指定数据（逗号分隔：cookies,settings,player-state,preloads,play-history,crash-reports,audio-cache,all）
+    #[arg(long, value_enum, value_delimiter = ',')]
+    pub reset: Vec<ResetDomain>,
+
+    /// 跳过 `--reset` 的确认提示
+    #[arg(long, requires = "reset")]
+    pub yes: bool,
 }
 
 #[derive(Debug, Subcommand)]
@@ -52,4 +78,15 @@ pub enum Command {
 
     /// 打印二维码登录相关信息（便于排查接口返回）
     QrKey,
+
+    /// 统计本地播放历史：收听最多的歌手与专辑
+    Stats {
+        /// 时间范围（如 30d、12h、2w；all 表示全部）
+        #[arg(long, default_value = "30d")]
+        since: String,
+
+        /// 每个榜单显示的条数
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
 }
@@ -8,13 +8,20 @@ mod login_view;
 mod lyrics_view;
 mod menu;
 mod mouse;
+mod notifications_view;
 mod overlays;
 mod panels;
 mod player_status;
 mod playlists_view;
+mod queue_view;
 mod search_view;
 mod settings_view;
+#[cfg(test)]
+mod snapshot_tests;
 mod styles;
+mod suspend;
+mod tasks_view;
+mod tick;
 mod toast;
 mod utils;
 mod views;
@@ -26,10 +33,13 @@ use std::io;
 use tokio::sync::mpsc;
 
 /// 主 TUI 入口点 - 从 main.rs 调用
+///
+/// `no_color` 为 true（`--no-color` / `NO_COLOR`）时总是单色显示，否则跟随设置项。
 pub async fn run_tui(
     app: AppSnapshot,
     tx: mpsc::Sender<AppCommand>,
     rx: mpsc::Receiver<AppEvent>,
+    no_color: bool,
 ) -> io::Result<()> {
-    event_loop::run_tui_internal(app, tx, rx).await
+    event_loop::run_tui_internal(app, tx, rx, no_color).await
 }
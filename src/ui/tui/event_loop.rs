@@ -1,40 +1,77 @@
 use super::guard::TuiGuard;
-use super::keyboard::handle_key;
+use super::keyboard::{batchable_char, handle_key, handle_paste, send_text_input};
 use super::mouse::handle_mouse;
+use super::styles::Theme;
+use super::suspend;
+use super::tick::{self, Ticker};
 use super::views::draw_ui;
 use crate::app::{AppSnapshot, Toast};
 use crate::messages::app::{AppCommand, AppEvent};
-use crossterm::event::{self, Event};
+use crossterm::event::{self, Event, KeyEventKind};
 use ratatui::{Terminal, backend::CrosstermBackend};
 use std::io;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TryRecvError;
+
+/// 连续字符的合并窗口：输入法上屏时字符往往在几毫秒内连续到达
+const CHAR_BATCH_WINDOW: Duration = Duration::from_millis(4);
+/// 单次合并的最大字符数，避免长时间占用事件循环
+const CHAR_BATCH_MAX: usize = 256;
+/// 挂起前等待暂停生效的最长时间
+#[cfg(unix)]
+const SUSPEND_PAUSE_TIMEOUT: Duration = Duration::from_millis(500);
 
 pub(super) async fn run_tui_internal(
     mut app: AppSnapshot,
     tx: mpsc::Sender<AppCommand>,
     mut rx: mpsc::Receiver<AppEvent>,
+    no_color: bool,
 ) -> io::Result<()> {
     let _guard = TuiGuard::enter()?;
     let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
+    #[cfg(unix)]
+    let mut signals = suspend::listen_signals()?;
 
     let _ = tx.send(AppCommand::Bootstrap).await;
+    if let Ok((width, _)) = crossterm::terminal::size() {
+        let _ = tx.send(AppCommand::UiResized { width }).await;
+    }
 
-    let tick_rate = Duration::from_millis(200);
     let mut last_tick = Instant::now();
+    let mut ticker = Ticker::new();
+    // 自上次重绘以来是否有状态/输入变化
+    let mut dirty = true;
+    // 终端是否有焦点；不支持焦点上报的终端不会发送 FocusLost，始终为 true
+    let mut ui_focused = true;
 
     loop {
-        while let Ok(evt) = rx.try_recv() {
-            match evt {
-                AppEvent::State(s) => app = *s,
-                AppEvent::Toast(s) => {
-                    app.toast = Some(Toast::info(s));
-                }
-                AppEvent::Error(e) => {
-                    app.toast = Some(Toast::error(format!("错误: {e}")));
+        #[cfg(unix)]
+        while let Ok(sig) = signals.try_recv() {
+            match sig {
+                suspend::SuspendSignal::Stop => {
+                    pause_before_suspend(&mut app, &tx, &mut rx).await?;
+                    suspend::suspend(&mut terminal, &tx).await?
                 }
+                suspend::SuspendSignal::Continue => suspend::resume(&mut terminal)?,
             }
+            dirty = true;
+        }
+
+        // 核心任务意外退出时按键会被静默丢弃，界面看起来像卡死：直接报错退出
+        if poll_core(&mut app, &tx, &mut rx)? {
+            dirty = true;
+        }
+
+        // 等待中的下载已结束，或等待超时
+        if app.exit_requested
+            || app
+                .quit_confirm
+                .is_some_and(|c| c.wait_expired(Instant::now()))
+        {
+            let _ = tx.send(AppCommand::Quit).await;
+            break;
         }
 
         // 检查 Toast 是否过期
@@ -42,21 +79,61 @@ pub(super) async fn run_tui_internal(
             && toast.is_expired()
         {
             app.toast = None;
+            dirty = true;
         }
 
-        terminal.draw(|f| draw_ui(f, &app))?;
+        if ticker.should_draw(&app, dirty, ui_focused) {
+            let theme = Theme::resolve(no_color || app.no_color);
+            terminal.draw(|f| draw_ui(f, &app, theme))?;
+            dirty = false;
+        }
 
+        let tick_rate = tick::tick_rate(&app, ui_focused);
         let timeout = tick_rate.saturating_sub(last_tick.elapsed());
         if event::poll(timeout)? {
-            match event::read()? {
-                Event::Key(key) if handle_key(&app, key, &tx).await => {
-                    break;
+            dirty = true;
+            let evt = event::read()?;
+            if suspend::is_suspend_key(&evt) {
+                #[cfg(unix)]
+                {
+                    pause_before_suspend(&mut app, &tx, &mut rx).await?;
+                    suspend::suspend(&mut terminal, &tx).await?;
+                }
+                continue;
+            }
+            if let Event::FocusGained | Event::FocusLost = evt {
+                ui_focused = matches!(evt, Event::FocusGained);
+                if ui_focused {
+                    // 失焦期间没有重绘，清屏后完整重绘
+                    terminal.clear()?;
                 }
-                Event::Key(_) => {}
-                Event::Mouse(mouse) => {
-                    handle_mouse(&app, mouse, &tx).await;
+                let _ = tx
+                    .send(AppCommand::TerminalFocusChanged {
+                        focused: ui_focused,
+                    })
+                    .await;
+                continue;
+            }
+            let first_char = match &evt {
+                Event::Key(key) => batchable_char(&app, key),
+                _ => None,
+            };
+            if let Some(c) = first_char {
+                let (text, rest) = collect_char_burst(&app, c, || {
+                    if event::poll(CHAR_BATCH_WINDOW)? {
+                        event::read().map(Some)
+                    } else {
+                        Ok(None)
+                    }
+                })?;
+                send_text_input(&app, text, &tx).await;
+                if let Some(evt) = rest
+                    && handle_event(&app, evt, &tx).await
+                {
+                    break;
                 }
-                _ => {}
+            } else if handle_event(&app, evt, &tx).await {
+                break;
             }
         }
 
@@ -67,3 +144,216 @@ pub(super) async fn run_tui_internal(
 
     Ok(())
 }
+
+/// 应用核心推送的事件；返回是否有变化
+///
+/// 核心任务已退出（命令通道或事件通道关闭）时返回错误，由调用方退出 TUI。
+fn poll_core(
+    app: &mut AppSnapshot,
+    tx: &mpsc::Sender<AppCommand>,
+    rx: &mut mpsc::Receiver<AppEvent>,
+) -> io::Result<bool> {
+    let mut changed = false;
+    loop {
+        let evt = match rx.try_recv() {
+            Ok(evt) => evt,
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => return Err(core_gone()),
+        };
+        changed = true;
+        apply_core_event(app, evt);
+    }
+    if tx.is_closed() {
+        return Err(core_gone());
+    }
+    Ok(changed)
+}
+
+fn apply_core_event(app: &mut AppSnapshot, evt: AppEvent) {
+    match evt {
+        AppEvent::State(s) => *app = *s,
+        AppEvent::Toast(s) => {
+            if let Some(toast) = Toast::info(s).filter_for_focus_mode(app.focus_mode) {
+                app.toast = Some(toast);
+            }
+        }
+        AppEvent::Error(e) => {
+            app.toast = Some(Toast::error(format!("错误: {e}")));
+        }
+    }
+}
+
+/// 播放中时先让核心暂停，等到暂停生效（或超时）后再挂起进程
+#[cfg(unix)]
+async fn pause_before_suspend(
+    app: &mut AppSnapshot,
+    tx: &mpsc::Sender<AppCommand>,
+    rx: &mut mpsc::Receiver<AppEvent>,
+) -> io::Result<()> {
+    if app.player.play_started_at.is_none() || app.player.paused {
+        return Ok(());
+    }
+    let _ = tx.send(AppCommand::TerminalSuspending).await;
+    let deadline = tokio::time::Instant::now() + SUSPEND_PAUSE_TIMEOUT;
+    while !app.player.paused {
+        match tokio::time::timeout_at(deadline, rx.recv()).await {
+            Ok(Some(evt)) => apply_core_event(app, evt),
+            Ok(None) => return Err(core_gone()),
+            Err(_) => {
+                tracing::warn!("[Tui] 挂起前等待暂停超时");
+                break;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn core_gone() -> io::Error {
+    tracing::error!("核心任务已意外退出，TUI 无法继续响应");
+    io::Error::other("后台核心任务意外退出，界面无法继续响应")
+}
+
+/// 处理单个终端事件；返回 true 表示退出
+async fn handle_event(app: &AppSnapshot, evt: Event, tx: &mpsc::Sender<AppCommand>) -> bool {
+    match evt {
+        Event::Key(key) => handle_key(app, key, tx).await,
+        Event::Mouse(mouse) => {
+            handle_mouse(app, mouse, tx).await;
+            false
+        }
+        Event::Paste(text) => {
+            handle_paste(app, &text, tx).await;
+            false
+        }
+        Event::Resize(width, _) => {
+            let _ = tx.send(AppCommand::UiResized { width }).await;
+            false
+        }
+        _ => false,
+    }
+}
+
+/// 从 `next` 中持续读取紧随其后的输入字符，合并为一段文本
+///
+/// 返回合并后的文本，以及打断合并的第一个非输入事件（需要调用方继续处理）。
+fn collect_char_burst<F>(
+    app: &AppSnapshot,
+    first: char,
+    mut next: F,
+) -> io::Result<(String, Option<Event>)>
+where
+    F: FnMut() -> io::Result<Option<Event>>,
+{
+    let mut text = String::from(first);
+    let mut count = 1;
+    while count < CHAR_BATCH_MAX {
+        let Some(evt) = next()? else {
+            break;
+        };
+        match &evt {
+            Event::Key(key) if matches!(key.kind, KeyEventKind::Release) => {}
+            Event::Key(key) => match batchable_char(app, key) {
+                Some(c) => {
+                    text.push(c);
+                    count += 1;
+                }
+                None => return Ok((text, Some(evt))),
+            },
+            _ => return Ok((text, Some(evt))),
+        }
+    }
+    Ok((text, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{collect_char_burst, poll_core};
+    use crate::app::{App, AppSnapshot, UiFocus, View};
+    use crate::messages::app::{AppCommand, AppEvent};
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+    use std::collections::VecDeque;
+    use tokio::sync::mpsc;
+
+    fn search_input_snapshot() -> AppSnapshot {
+        AppSnapshot::from_app(&App {
+            view: View::Search,
+            ui_focus: UiFocus::HeaderSearch,
+            logged_in: true,
+            ..Default::default()
+        })
+    }
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn burst_of_chars_is_merged_in_order() {
+        let app = search_input_snapshot();
+        let mut queue: VecDeque<Event> =
+            "杰伦 晴天".chars().map(|c| key(KeyCode::Char(c))).collect();
+        let mut release = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+        release.kind = KeyEventKind::Release;
+        queue.insert(1, Event::Key(release));
+
+        let (text, rest) = collect_char_burst(&app, '周', || Ok(queue.pop_front())).expect("burst");
+
+        assert_eq!(text, "周杰伦 晴天");
+        assert!(rest.is_none());
+    }
+
+    #[test]
+    fn burst_stops_at_non_input_event() {
+        let app = search_input_snapshot();
+        let mut queue: VecDeque<Event> = VecDeque::from([
+            key(KeyCode::Char('b')),
+            key(KeyCode::Enter),
+            key(KeyCode::Char('c')),
+        ]);
+
+        let (text, rest) = collect_char_burst(&app, 'a', || Ok(queue.pop_front())).expect("burst");
+
+        assert_eq!(text, "ab");
+        assert!(matches!(rest, Some(Event::Key(k)) if k.code == KeyCode::Enter));
+        assert_eq!(queue.len(), 1, "打断事件之后的事件不应被读取");
+    }
+
+    #[test]
+    fn core_events_are_applied_while_core_is_alive() {
+        let (tx, _rx_cmd) = mpsc::channel::<AppCommand>(4);
+        let (tx_evt, mut rx) = mpsc::channel::<AppEvent>(4);
+        let mut app = search_input_snapshot();
+
+        assert!(!poll_core(&mut app, &tx, &mut rx).expect("alive"));
+        tx_evt
+            .try_send(AppEvent::Toast("你好".to_owned()))
+            .expect("send");
+        assert!(poll_core(&mut app, &tx, &mut rx).expect("alive"));
+        assert!(app.toast.is_some());
+    }
+
+    #[test]
+    fn dropped_core_receiver_ends_the_loop_with_an_error() {
+        let (tx, rx_cmd) = mpsc::channel::<AppCommand>(4);
+        let (_tx_evt, mut rx) = mpsc::channel::<AppEvent>(4);
+        let mut app = search_input_snapshot();
+
+        // 会话中途核心任务退出（例如 panic）：命令接收端被丢弃
+        drop(rx_cmd);
+        assert!(poll_core(&mut app, &tx, &mut rx).is_err());
+    }
+
+    #[test]
+    fn closed_event_stream_ends_the_loop_after_draining() {
+        let (tx, _rx_cmd) = mpsc::channel::<AppCommand>(4);
+        let (tx_evt, mut rx) = mpsc::channel::<AppEvent>(4);
+        let mut app = search_input_snapshot();
+
+        tx_evt
+            .try_send(AppEvent::Toast("最后一条".to_owned()))
+            .expect("send");
+        drop(tx_evt);
+        assert!(poll_core(&mut app, &tx, &mut rx).is_err());
+        assert!(app.toast.is_some(), "退出前仍应用已收到的事件");
+    }
+}
@@ -1,38 +1,98 @@
 use crossterm::{
     cursor,
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-pub struct TuiGuard;
+/// 终端当前是否处于 TUI 模式（raw mode + 备用屏幕 + 鼠标捕获）
+static ACTIVE: AtomicBool = AtomicBool::new(false);
 
-impl TuiGuard {
-    pub fn enter() -> io::Result<Self> {
-        enable_raw_mode()?;
-        let mut stdout = io::stdout();
+/// 进入 TUI 模式；已经处于 TUI 模式时直接返回
+///
+/// 启动与挂起恢复（SIGCONT）共用。
+pub fn setup_terminal() -> io::Result<()> {
+    if ACTIVE.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    let mut stdout = io::stdout();
+    let result = enable_raw_mode().and_then(|()| {
         execute!(
             stdout,
             EnterAlternateScreen,
             cursor::Hide,
-            EnableMouseCapture
+            EnableMouseCapture,
+            EnableBracketedPaste,
+            EnableFocusChange
         )?;
-        stdout.flush()?;
+        stdout.flush()
+    });
+    if result.is_err() {
+        // 半途失败时尽量把终端还原
+        let _ = restore_terminal();
+    }
+    result
+}
+
+/// 退出 TUI 模式；未处于 TUI 模式时不做任何事，可以重复调用
+///
+/// 退出与挂起（Ctrl+Z / SIGTSTP）共用。
+pub fn restore_terminal() -> io::Result<()> {
+    restore_with(&mut io::stdout())
+}
+
+fn restore_with(out: &mut impl Write) -> io::Result<()> {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    // 每一步都要执行，返回遇到的第一个错误
+    let raw = disable_raw_mode();
+    let screen = execute!(
+        out,
+        cursor::Show,
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        DisableFocusChange
+    );
+    let flush = out.flush();
+    raw.and(screen).and(flush)
+}
+
+pub struct TuiGuard;
+
+impl TuiGuard {
+    pub fn enter() -> io::Result<Self> {
+        setup_terminal()?;
         Ok(Self)
     }
 }
 
 impl Drop for TuiGuard {
     fn drop(&mut self) {
-        let _ = disable_raw_mode();
-        let mut stdout = io::stdout();
-        let _ = execute!(
-            stdout,
-            cursor::Show,
-            LeaveAlternateScreen,
-            DisableMouseCapture
-        );
-        let _ = stdout.flush();
+        let _ = restore_terminal();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_is_idempotent() {
+        ACTIVE.store(true, Ordering::SeqCst);
+        let mut first = Vec::new();
+        restore_with(&mut first).expect("首次还原");
+        assert!(!first.is_empty(), "首次还原应输出离开备用屏幕等控制序列");
+
+        let mut second = Vec::new();
+        restore_with(&mut second).expect("重复还原不应报错");
+        assert!(second.is_empty(), "重复还原不应再次写入终端");
+        assert!(restore_terminal().is_ok());
     }
 }
@@ -1,18 +1,17 @@
 use super::layout::HeaderLayout;
-use crate::app::{AppSnapshot, UiFocus, tab_configs, tab_index_for_view};
+use super::styles::Theme;
+use crate::app::{AppSnapshot, UiFocus, focus_order, tab_configs, tab_index_for_view};
 use ratatui::{
     Frame,
-    style::{Color, Style},
     text::Line,
     widgets::{Paragraph, Tabs},
 };
 
-pub(super) fn draw_header(f: &mut Frame, layout: &HeaderLayout, app: &AppSnapshot) {
+pub(super) fn draw_header(f: &mut Frame, layout: &HeaderLayout, app: &AppSnapshot, theme: Theme) {
     let configs = tab_configs(app.logged_in);
     let titles: Vec<Line> = configs
         .iter()
-        .enumerate()
-        .map(|(i, c)| Line::from(format!("{}[F{}]", c.title, i + 1)))
+        .map(|c| Line::from(format!("{}[F{}]", c.title, c.fkey)))
         .collect();
     let selected = tab_index_for_view(app.view, app.logged_in).unwrap_or(0);
 
@@ -20,8 +19,8 @@ pub(super) fn draw_header(f: &mut Frame, layout: &HeaderLayout, app: &AppSnapsho
         .select(selected)
         .divider("|")
         .padding(" ", " ")
-        .style(Style::default().fg(Color::Gray))
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .style(theme.focus(false))
+        .highlight_style(theme.highlight());
     f.render_widget(tabs, layout.tabs);
 
     let search_hint = if app.search_input.is_empty() {
@@ -29,10 +28,11 @@ pub(super) fn draw_header(f: &mut Frame, layout: &HeaderLayout, app: &AppSnapsho
     } else {
         format!("Search[1]: {}", app.search_input)
     };
-    let search_style = if matches!(app.ui_focus, UiFocus::HeaderSearch) {
-        Style::default().fg(Color::Yellow)
+    // 只有搜索页能在搜索框输入，其它页面变暗
+    let search_style = if focus_order(app.view).contains(&UiFocus::HeaderSearch) {
+        theme.focus(matches!(app.ui_focus, UiFocus::HeaderSearch))
     } else {
-        Style::default().fg(Color::Gray)
+        theme.dimmed()
     };
     f.render_widget(
         Paragraph::new(search_hint).style(search_style),
@@ -46,11 +46,12 @@ pub(super) fn draw_header(f: &mut Frame, layout: &HeaderLayout, app: &AppSnapsho
         UiFocus::BodyRight => "Right",
     };
     let status = format!(
-        "View:{}|Focus:{}|Login:{}|Help:{}",
+        "View:{}|Focus:{}|Login:{}|Help:{}{}",
         configs.get(selected).map(|c| c.title).unwrap_or(""),
         focus_label,
         if app.logged_in { "Yes" } else { "No" },
-        if app.help_visible { "On" } else { "Off" }
+        if app.help_visible { "On" } else { "Off" },
+        if app.focus_mode { "|专注" } else { "" }
     );
     f.render_widget(Paragraph::new(status), layout.status);
 }
@@ -1,10 +1,17 @@
-use super::utils::is_unauth_login_page;
-use crate::app::{AppSnapshot, AppViewSnapshot, PlaylistMode, UiFocus, View};
+use super::notifications_view::{detail_scroll_bounds, osc52_copy_sequence};
+use super::utils::{canvas_rect, is_unauth_login_page};
+use crate::app::{AppSnapshot, AppViewSnapshot, PlaylistMode, UiFocus, View, tab_configs};
 use crate::keybindings::KeyAction;
 use crate::messages::app::AppCommand;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use ratatui::prelude::Rect;
+use std::io::{self, Write};
 use tokio::sync::mpsc;
 
+/// 每次调整栏宽的步长（百分比）
+const PANE_NUDGE_PCT: i16 = 2;
+
 pub(super) async fn handle_key(
     app: &AppSnapshot,
     key: KeyEvent,
@@ -17,6 +24,38 @@ pub(super) async fn handle_key(
         return false;
     }
 
+    // 旧播放状态确认弹窗：捕获所有按键，直到作出选择
+    if app.restore_prompt.is_some() {
+        match key.code {
+            KeyCode::Char('r' | 'R') => {
+                let _ = tx.send(AppCommand::RestorePromptAccept).await;
+            }
+            KeyCode::Char('i' | 'I') | KeyCode::Esc => {
+                let _ = tx.send(AppCommand::RestorePromptDismiss).await;
+            }
+            _ => {}
+        }
+        return false;
+    }
+
+    // 退出确认弹窗：y / 再按一次 q 立即退出，w 等待下载完成后退出
+    if app.quit_confirm.is_some() {
+        match key.code {
+            KeyCode::Char('y' | 'Y' | 'q') => {
+                let _ = tx.send(AppCommand::Quit).await;
+                return true;
+            }
+            KeyCode::Char('w' | 'W') => {
+                let _ = tx.send(AppCommand::QuitConfirmWait).await;
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => {
+                let _ = tx.send(AppCommand::QuitConfirmCancel).await;
+            }
+            _ => {}
+        }
+        return false;
+    }
+
     if app.help_visible {
         match key.code {
             KeyCode::Esc => {
@@ -56,50 +95,211 @@ pub(super) async fn handle_key(
         return false;
     }
 
-    // Configurable global keybindings (Quit, Help, Menu, PlayerPrev/Next, CycleMode)
-    // These are resolved via the keybindings HashMap instead of hardcoded match branches.
-    if key.modifiers == KeyModifiers::NONE
-        && let Some(action) = app.keybindings.resolve(key.code)
-    {
-        match action {
-            KeyAction::Quit => {
-                let _ = tx.send(AppCommand::Quit).await;
-                return true;
+    // 「添加到歌单」选择框：打开时捕获所有按键，输入的文字用于筛选歌单
+    if app.playlist_picker.is_some() {
+        let cmd = match key.code {
+            KeyCode::Esc => Some(AppCommand::PlaylistPickerCancel),
+            KeyCode::Enter => Some(AppCommand::PlaylistPickerConfirm),
+            KeyCode::Up => Some(AppCommand::PlaylistPickerMoveUp),
+            KeyCode::Down => Some(AppCommand::PlaylistPickerMoveDown),
+            KeyCode::Backspace => Some(AppCommand::PlaylistPickerInputBackspace),
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Some(AppCommand::PlaylistPickerInputChar { c })
             }
-            KeyAction::UiToggleHelp => {
-                let _ = tx.send(AppCommand::UiToggleHelp).await;
-                return false;
+            _ => None,
+        };
+        if let Some(cmd) = cmd {
+            let _ = tx.send(cmd).await;
+        }
+        return false;
+    }
+
+    // 不可用歌曲审阅框：打开时捕获所有按键
+    if app.unavailable_review.is_some() {
+        let cmd = match key.code {
+            KeyCode::Esc => Some(AppCommand::UnavailableReviewClose),
+            KeyCode::Enter => Some(AppCommand::UnavailableReviewAccept),
+            KeyCode::Char('s') => Some(AppCommand::UnavailableReviewSkip),
+            KeyCode::Up | KeyCode::Char('k') => Some(AppCommand::UnavailableReviewMoveUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(AppCommand::UnavailableReviewMoveDown),
+            _ => None,
+        };
+        if let Some(cmd) = cmd {
+            let _ = tx.send(cmd).await;
+        }
+        return false;
+    }
+
+    // 手机号登录表单：捕获输入与编辑按键，密码中的字母不会触发全局快捷键；
+    // F1-F4、Ctrl+Tab 等其他按键照常处理
+    if phone_login_focused(app) {
+        let cmd = match (key.code, key.modifiers) {
+            (KeyCode::Esc, _) => Some(AppCommand::LoginTogglePhoneForm),
+            (KeyCode::Enter, _) => Some(AppCommand::LoginPhoneSubmit),
+            (KeyCode::Backspace, _) => Some(AppCommand::LoginPhoneInputBackspace),
+            (KeyCode::Tab | KeyCode::BackTab | KeyCode::Up | KeyCode::Down, m)
+                if !m.contains(KeyModifiers::CONTROL) =>
+            {
+                Some(AppCommand::LoginPhoneFieldNext)
             }
-            KeyAction::MenuOpen => {
-                let _ = tx.send(AppCommand::MenuOpen).await;
-                return false;
+            (KeyCode::Char('t'), m) if m.contains(KeyModifiers::CONTROL) => {
+                Some(AppCommand::LoginPhoneToggleCaptcha)
             }
-            KeyAction::PlayerPrev => {
-                let _ = tx.send(AppCommand::PlayerPrev).await;
-                return false;
+            (KeyCode::Char('s'), m) if m.contains(KeyModifiers::CONTROL) => {
+                Some(AppCommand::LoginPhoneSendCaptcha)
+            }
+            (KeyCode::Char(c), m) if !m.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                Some(AppCommand::LoginPhoneInputChar { c })
+            }
+            _ => None,
+        };
+        if let Some(cmd) = cmd {
+            let _ = tx.send(cmd).await;
+            return false;
+        }
+    }
+
+    // Background tasks overlay: captures all keys when visible
+    if app.tasks_visible {
+        match key.code {
+            KeyCode::Esc | KeyCode::F(6) => {
+                let _ = tx.send(AppCommand::TasksToggle).await;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                let _ = tx.send(AppCommand::TasksMoveUp).await;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let _ = tx.send(AppCommand::TasksMoveDown).await;
+            }
+            KeyCode::Char('x') | KeyCode::Delete => {
+                let _ = tx.send(AppCommand::TasksCancelSelected).await;
             }
-            KeyAction::PlayerNext => {
-                let _ = tx.send(AppCommand::PlayerNext).await;
+            _ => {}
+        }
+        return false;
+    }
+
+    // 通知详情：滚动 / 复制，Esc 回到通知历史
+    if app.notification_detail.is_some() {
+        let (max_scroll, page) = terminal::size()
+            .ok()
+            .and_then(|(cols, rows)| canvas_rect(Rect::new(0, 0, cols, rows)))
+            .and_then(|canvas| detail_scroll_bounds(app, canvas))
+            .unwrap_or((usize::MAX, 10));
+        let scroll = app.notification_detail_scroll.min(max_scroll);
+        let offset = match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+                let _ = tx.send(AppCommand::NotificationsCloseDetail).await;
                 return false;
             }
-            KeyAction::PlayerCycleMode => {
-                let _ = tx.send(AppCommand::PlayerCycleMode).await;
+            KeyCode::Char('c') => {
+                copy_notification_detail(app, tx).await;
                 return false;
             }
-            KeyAction::PlayerStop => {
-                // Ctrl+s is the default; if user binds a plain key, handle it here
-                let _ = tx.send(AppCommand::PlayerStop).await;
+            KeyCode::Up | KeyCode::Char('k') => scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => scroll.saturating_add(1),
+            KeyCode::PageUp => scroll.saturating_sub(page),
+            KeyCode::PageDown | KeyCode::Char(' ') => scroll.saturating_add(page),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => max_scroll,
+            _ => return false,
+        };
+        let offset = offset.min(max_scroll);
+        if offset != app.notification_detail_scroll {
+            let _ = tx
+                .send(AppCommand::NotificationDetailScroll { offset })
+                .await;
+        }
+        return false;
+    }
+
+    // 通知历史：捕获所有按键
+    if app.notifications_visible {
+        let cmd = match key.code {
+            KeyCode::Esc | KeyCode::F(7) => Some(AppCommand::NotificationsToggle),
+            KeyCode::Enter => Some(AppCommand::NotificationsOpenDetail),
+            KeyCode::Up | KeyCode::Char('k') => Some(AppCommand::NotificationsMoveUp),
+            KeyCode::Down | KeyCode::Char('j') => Some(AppCommand::NotificationsMoveDown),
+            _ => None,
+        };
+        if let Some(cmd) = cmd {
+            let _ = tx.send(cmd).await;
+        }
+        return false;
+    }
+
+    // 歌单内搜索输入框：打开时捕获所有按键，输入的文字即时筛选歌曲；↑/↓ 在结果中移动
+    if playlist_filter_editing(app) {
+        let cmd = match key.code {
+            KeyCode::Esc => Some(AppCommand::PlaylistFilterCancel),
+            KeyCode::Enter => Some(AppCommand::PlaylistFilterSubmit),
+            KeyCode::Backspace => Some(AppCommand::PlaylistFilterBackspace),
+            KeyCode::Up => Some(AppCommand::PlaylistTracksMoveUp),
+            KeyCode::Down => Some(AppCommand::PlaylistTracksMoveDown),
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                Some(AppCommand::PlaylistFilterChar { c })
+            }
+            _ => None,
+        };
+        if let Some(cmd) = cmd {
+            let _ = tx.send(cmd).await;
+        }
+        return false;
+    }
+
+    // 歌单列表：f 切换归属筛选（优先于全局的 f 收藏）
+    if key.code == KeyCode::Char('f')
+        && key.modifiers == KeyModifiers::NONE
+        && playlist_list_focused(app)
+    {
+        let _ = tx.send(AppCommand::PlaylistsCycleFilter).await;
+        return false;
+    }
+
+    // 歌单列表：r 重新预加载（预加载因接口错误暂停后恢复）
+    if key.code == KeyCode::Char('r')
+        && key.modifiers == KeyModifiers::NONE
+        && playlist_list_focused(app)
+    {
+        let _ = tx.send(AppCommand::PlaylistsRetryPreload).await;
+        return false;
+    }
+
+    // 歌单列表：d 打开每日推荐
+    if key.code == KeyCode::Char('d')
+        && key.modifiers == KeyModifiers::NONE
+        && playlist_list_focused(app)
+    {
+        let _ = tx.send(AppCommand::PlaylistsOpenRecommend).await;
+        return false;
+    }
+
+    // Configurable global keybindings (Quit, Help, Menu, PlayerPrev/Next, CycleMode)
+    // These are resolved via the keybindings HashMap instead of hardcoded match branches.
+    if let Some(action) = global_action(app, &key) {
+        match action {
+            // 有下载进行中：先弹窗确认
+            KeyAction::Quit if app.quit_blockers > 0 => {
+                let _ = tx.send(AppCommand::QuitConfirmOpen).await;
                 return false;
             }
-            KeyAction::PlayerTogglePause => {
-                // Space key has special handling below for search input
-                if key.code == KeyCode::Char(' ') {
-                    // Will be handled in the global player controls section below
-                    // which checks for search input focus
-                } else {
-                    let _ = tx.send(AppCommand::PlayerTogglePause).await;
-                    return false;
-                }
+            KeyAction::Quit => {
+                let _ = tx.send(action.command()).await;
+                return true;
+            }
+            // Space key has special handling below for search input
+            KeyAction::PlayerTogglePause if key.code == KeyCode::Char(' ') => {}
+            _ => {
+                let _ = tx.send(action.command()).await;
+                return false;
             }
         }
     }
@@ -140,6 +340,36 @@ pub(super) async fn handle_key(
             let _ = tx.send(AppCommand::TabTo { index }).await;
             return false;
         }
+        KeyEvent {
+            code: KeyCode::F(5),
+            ..
+        } if !unauth_login_page => {
+            let _ = tx.send(AppCommand::FmToggle).await;
+            return false;
+        }
+        KeyEvent {
+            code: KeyCode::F(6),
+            ..
+        } => {
+            let _ = tx.send(AppCommand::TasksToggle).await;
+            return false;
+        }
+        KeyEvent {
+            code: KeyCode::F(7),
+            ..
+        } => {
+            let _ = tx.send(AppCommand::NotificationsToggle).await;
+            return false;
+        }
+        KeyEvent {
+            code: KeyCode::F(8),
+            ..
+        } => {
+            if let Some(index) = tab_configs(app.logged_in).iter().position(|c| c.fkey == 8) {
+                let _ = tx.send(AppCommand::TabTo { index }).await;
+            }
+            return false;
+        }
         _ => {}
     }
 
@@ -205,14 +435,30 @@ pub(super) async fn handle_key(
             let _ = tx.send(AppCommand::PlayerStop).await;
             return false;
         }
+        (KeyCode::Char('f'), m) if m.contains(KeyModifiers::CONTROL) => {
+            let _ = tx.send(AppCommand::UiToggleFocusMode).await;
+            return false;
+        }
+        // Ctrl+Shift+←/→：移动左/中栏分界线
+        (KeyCode::Left | KeyCode::Right, m)
+            if m.contains(KeyModifiers::CONTROL) && m.contains(KeyModifiers::SHIFT) =>
+        {
+            let delta = if key.code == KeyCode::Left {
+                -PANE_NUDGE_PCT
+            } else {
+                PANE_NUDGE_PCT
+            };
+            let _ = tx.send(AppCommand::UiPaneNudge { delta }).await;
+            return false;
+        }
         (KeyCode::Left, m) if m.contains(KeyModifiers::CONTROL) => {
-            let _ = tx
-                .send(AppCommand::PlayerSeekBackwardMs { ms: 5_000 })
-                .await;
+            let ms = app.player.seek_step_small_ms;
+            let _ = tx.send(AppCommand::PlayerSeekBackwardMs { ms }).await;
             return false;
         }
         (KeyCode::Right, m) if m.contains(KeyModifiers::CONTROL) => {
-            let _ = tx.send(AppCommand::PlayerSeekForwardMs { ms: 5_000 }).await;
+            let ms = app.player.seek_step_small_ms;
+            let _ = tx.send(AppCommand::PlayerSeekForwardMs { ms }).await;
             return false;
         }
         (KeyCode::Up, m) if m.contains(KeyModifiers::ALT) => {
@@ -223,6 +469,10 @@ pub(super) async fn handle_key(
             let _ = tx.send(AppCommand::PlayerVolumeDown).await;
             return false;
         }
+        (KeyCode::Char('m' | 'M'), m) if m.contains(KeyModifiers::ALT) => {
+            let _ = tx.send(AppCommand::PlayerToggleMute).await;
+            return false;
+        }
         (KeyCode::Left, m) if m.contains(KeyModifiers::ALT) && matches!(app.view, View::Lyrics) => {
             let ms = if m.contains(KeyModifiers::SHIFT) {
                 -50
@@ -251,6 +501,24 @@ pub(super) async fn handle_key(
     } else {
         app.ui_focus
     };
+
+    // 右栏（队列预览）：x 切换此队列的淡入淡出；u 撤销队列替换；
+    // ↑/↓ 移动选中项，←/→ 折叠 / 展开所在的来源分组
+    if focus == UiFocus::BodyRight {
+        let cmd = match key.code {
+            KeyCode::Char('x') => Some(AppCommand::QueueToggleNoFade),
+            KeyCode::Char('u') => Some(AppCommand::QueueUndoReplace),
+            KeyCode::Up => Some(AppCommand::QueueMoveUp),
+            KeyCode::Down => Some(AppCommand::QueueMoveDown),
+            KeyCode::Left => Some(AppCommand::QueueCollapse),
+            KeyCode::Right => Some(AppCommand::QueueExpand),
+            _ => None,
+        };
+        if let Some(cmd) = cmd {
+            let _ = tx.send(cmd).await;
+            return false;
+        }
+    }
     match app.view {
         View::Login => {
             if focus != UiFocus::BodyCenter {
@@ -287,14 +555,19 @@ pub(super) async fn handle_key(
                     KeyCode::Char('c') => {
                         let _ = tx.send(AppCommand::LoginToggleCookieInput).await;
                     }
+                    KeyCode::Char('p') => {
+                        let _ = tx.send(AppCommand::LoginTogglePhoneForm).await;
+                    }
                     _ => {}
                 }
             }
         }
         View::Playlists => {
-            let playlist_mode = match &app.view_state {
-                AppViewSnapshot::Playlists(state) => state.playlist_mode,
-                _ => PlaylistMode::List,
+            let (playlist_mode, filter_active) = match &app.view_state {
+                AppViewSnapshot::Playlists(state) => {
+                    (state.playlist_mode, !state.playlist_filter_input.is_empty())
+                }
+                _ => (PlaylistMode::List, false),
             };
             if matches!(key.code, KeyCode::Char('b')) {
                 let _ = tx.send(AppCommand::Back).await;
@@ -335,6 +608,39 @@ pub(super) async fn handle_key(
                     KeyCode::Char('p') if matches!(playlist_mode, PlaylistMode::Tracks) => {
                         let _ = tx.send(AppCommand::PlaylistTracksPlaySelected).await;
                     }
+                    KeyCode::Char('d') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::PlaylistTracksRemoveSelected).await;
+                    }
+                    KeyCode::Char('e') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::ExportCsv { all: false }).await;
+                    }
+                    KeyCode::Char('U') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::PlaylistTracksCheckUnavailable).await;
+                    }
+                    KeyCode::Char('D') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::DownloadSelected).await;
+                    }
+                    KeyCode::Char('t') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::PlaylistTracksToggleOrder).await;
+                    }
+                    KeyCode::Char('S') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::PlaylistTracksCycleSort).await;
+                    }
+                    KeyCode::Char('/') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::PlaylistFilterOpen).await;
+                    }
+                    KeyCode::Esc if filter_active => {
+                        let _ = tx.send(AppCommand::PlaylistFilterCancel).await;
+                    }
+                    KeyCode::Char('z') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::PlaylistTracksJumpToPlaying).await;
+                    }
+                    KeyCode::Char('N') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::EnqueueNextSelected).await;
+                    }
+                    KeyCode::Char('E') if matches!(playlist_mode, PlaylistMode::Tracks) => {
+                        let _ = tx.send(AppCommand::EnqueueLastSelected).await;
+                    }
                     KeyCode::Up => match playlist_mode {
                         PlaylistMode::List => {
                             let _ = tx.send(AppCommand::PlaylistsMoveUp).await;
@@ -422,6 +728,36 @@ pub(super) async fn handle_key(
             (UiFocus::BodyCenter, KeyCode::End) => {
                 let _ = tx.send(AppCommand::SearchJumpBottom).await;
             }
+            (UiFocus::BodyCenter, KeyCode::Char('n')) => {
+                let _ = tx.send(AppCommand::SearchLoadMore).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('t')) => {
+                let _ = tx.send(AppCommand::SearchCycleKind).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('a')) => {
+                let _ = tx.send(AppCommand::SearchAddSelectedToPlaylist).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('v')) => {
+                let _ = tx.send(AppCommand::SearchToggleMark).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('A')) => {
+                let _ = tx.send(AppCommand::SearchAddMarkedToPlaylist).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('D')) => {
+                let _ = tx.send(AppCommand::DownloadSelected).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('N')) => {
+                let _ = tx.send(AppCommand::EnqueueNextSelected).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('E')) => {
+                let _ = tx.send(AppCommand::EnqueueLastSelected).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Enter) => {
+                let _ = tx.send(AppCommand::SearchOpenSelected).await;
+            }
+            (UiFocus::BodyCenter, KeyCode::Char('b')) => {
+                let _ = tx.send(AppCommand::SearchBack).await;
+            }
             _ => {}
         },
         View::Lyrics => {
@@ -435,12 +771,24 @@ pub(super) async fn handle_key(
                 KeyCode::Char('g') => {
                     let _ = tx.send(AppCommand::LyricsGotoCurrent).await;
                 }
+                KeyCode::Char('t') => {
+                    let _ = tx.send(AppCommand::LyricsCycleDisplay).await;
+                }
                 KeyCode::Up => {
                     let _ = tx.send(AppCommand::LyricsMoveUp).await;
                 }
                 KeyCode::Down => {
                     let _ = tx.send(AppCommand::LyricsMoveDown).await;
                 }
+                // PageUp/PageDown：长 Seek
+                KeyCode::PageUp => {
+                    let ms = app.player.seek_step_large_ms;
+                    let _ = tx.send(AppCommand::PlayerSeekBackwardMs { ms }).await;
+                }
+                KeyCode::PageDown => {
+                    let ms = app.player.seek_step_large_ms;
+                    let _ = tx.send(AppCommand::PlayerSeekForwardMs { ms }).await;
+                }
                 _ => {}
             }
         }
@@ -503,51 +851,557 @@ pub(super) async fn handle_key(
                 _ => {}
             }
         }
+        View::Queue => {
+            if focus != UiFocus::BodyCenter {
+                return false;
+            }
+            let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+            let cmd = match key.code {
+                KeyCode::Up if shift => AppCommand::QueueReorderUp,
+                KeyCode::Down if shift => AppCommand::QueueReorderDown,
+                KeyCode::Char('K') => AppCommand::QueueReorderUp,
+                KeyCode::Char('J') => AppCommand::QueueReorderDown,
+                KeyCode::Up => AppCommand::QueueMoveUp,
+                KeyCode::Down => AppCommand::QueueMoveDown,
+                KeyCode::Char('d') | KeyCode::Delete => AppCommand::QueueRemoveSelected,
+                KeyCode::Enter | KeyCode::Char('p') => AppCommand::QueuePlaySelected,
+                KeyCode::Char('u') => AppCommand::QueueUndoReplace,
+                _ => return false,
+            };
+            let _ = tx.send(cmd).await;
+        }
     }
 
     false
 }
 
+/// 把正在查看的通知全文通过 OSC 52 写入剪贴板（终端不支持时无效果）
+async fn copy_notification_detail(app: &AppSnapshot, tx: &mpsc::Sender<AppCommand>) {
+    let Some(n) = app
+        .notification_detail
+        .and_then(|i| app.notifications.get(i))
+    else {
+        return;
+    };
+    let mut out = io::stdout();
+    let written = out
+        .write_all(osc52_copy_sequence(&n.message).as_bytes())
+        .and_then(|()| out.flush());
+    match written {
+        Ok(()) => {
+            let _ = tx.send(AppCommand::NotificationDetailCopied).await;
+        }
+        Err(e) => tracing::warn!("写入剪贴板失败: {e}"),
+    }
+}
+
+/// 文本输入目标（用于合并连续字符与处理粘贴）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum TextInputTarget {
+    Search,
+    LoginCookie,
+    LoginPhone,
+    PlaylistPicker,
+    PlaylistFilter,
+}
+
+/// 当前焦点是否处于文本输入框
+pub(super) fn text_input_target(app: &AppSnapshot) -> Option<TextInputTarget> {
+    if app.help_visible
+        || app.menu_visible
+        || app.tasks_visible
+        || app.notifications_visible
+        || app.quit_confirm.is_some()
+    {
+        return None;
+    }
+    if app.playlist_picker.is_some() {
+        return Some(TextInputTarget::PlaylistPicker);
+    }
+    match &app.view_state {
+        AppViewSnapshot::Search(_)
+            if matches!(app.view, View::Search) && app.ui_focus == UiFocus::HeaderSearch =>
+        {
+            Some(TextInputTarget::Search)
+        }
+        AppViewSnapshot::Login(state)
+            if state.login_cookie_input_visible
+                && (is_unauth_login_page(app) || app.ui_focus == UiFocus::BodyCenter) =>
+        {
+            Some(TextInputTarget::LoginCookie)
+        }
+        AppViewSnapshot::Login(_) if phone_login_focused(app) => Some(TextInputTarget::LoginPhone),
+        AppViewSnapshot::Playlists(_) if playlist_filter_editing(app) => {
+            Some(TextInputTarget::PlaylistFilter)
+        }
+        _ => None,
+    }
+}
+
+/// 按键绑定的全局动作；文本输入框中让位给输入的动作（如 f、M、X、倍速、A-B 循环）不生效
+fn global_action(app: &AppSnapshot, key: &KeyEvent) -> Option<KeyAction> {
+    // 大写字母（如默认的 M、X）由终端连同 SHIFT 一起报告
+    let modifiers = match key.code {
+        KeyCode::Char(c) if c.is_uppercase() => key.modifiers - KeyModifiers::SHIFT,
+        _ => key.modifiers,
+    };
+    if modifiers != KeyModifiers::NONE {
+        return None;
+    }
+    let action = app.keybindings.resolve(key.code)?;
+    (!action.yields_to_text_input() || text_input_target(app).is_none()).then_some(action)
+}
+
+/// 手机号登录表单已打开且处于焦点
+fn phone_login_focused(app: &AppSnapshot) -> bool {
+    matches!(app.view, View::Login)
+        && matches!(&app.view_state, AppViewSnapshot::Login(state) if state.login_phone_form.is_some())
+        && (is_unauth_login_page(app) || app.ui_focus == UiFocus::BodyCenter)
+}
+
+/// 歌单内搜索输入框已打开
+fn playlist_filter_editing(app: &AppSnapshot) -> bool {
+    matches!(app.view, View::Playlists)
+        && matches!(&app.view_state, AppViewSnapshot::Playlists(state) if state.playlist_filter_editing)
+}
+
+/// 焦点在歌单列表上（左栏，或列表模式下的中栏）
+fn playlist_list_focused(app: &AppSnapshot) -> bool {
+    let AppViewSnapshot::Playlists(state) = &app.view_state else {
+        return false;
+    };
+    matches!(app.view, View::Playlists)
+        && (app.ui_focus == UiFocus::BodyLeft
+            || (app.ui_focus == UiFocus::BodyCenter
+                && matches!(state.playlist_mode, PlaylistMode::List)))
+}
+
+/// 若按键会被当作文本输入（而非快捷键），返回对应字符
+///
+/// 与 `handle_key` 的判定保持一致：已绑定全局动作的按键不视为输入（空格及 f 等让位给输入的键除外）；
+/// 手机号登录表单捕获全部字符，不受此限制。
+pub(super) fn batchable_char(app: &AppSnapshot, key: &KeyEvent) -> Option<char> {
+    if matches!(key.kind, KeyEventKind::Release) {
+        return None;
+    }
+    let KeyCode::Char(c) = key.code else {
+        return None;
+    };
+    if key
+        .modifiers
+        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    {
+        return None;
+    }
+    let target = text_input_target(app)?;
+    if c != ' ' && target != TextInputTarget::LoginPhone && global_action(app, key).is_some() {
+        return None;
+    }
+    Some(c)
+}
+
+pub(super) fn text_input_command(target: TextInputTarget, text: String) -> AppCommand {
+    let mut chars = text.chars();
+    match (target, chars.next(), chars.next()) {
+        (TextInputTarget::Search, Some(c), None) => AppCommand::SearchInputChar { c },
+        (TextInputTarget::LoginCookie, Some(c), None) => AppCommand::LoginCookieInputChar { c },
+        (TextInputTarget::LoginPhone, Some(c), None) => AppCommand::LoginPhoneInputChar { c },
+        (TextInputTarget::PlaylistPicker, Some(c), None) => {
+            AppCommand::PlaylistPickerInputChar { c }
+        }
+        (TextInputTarget::PlaylistFilter, Some(c), None) => AppCommand::PlaylistFilterChar { c },
+        (TextInputTarget::Search, _, _) => AppCommand::SearchInputString { s: text },
+        (TextInputTarget::LoginCookie, _, _) => AppCommand::LoginCookieInputString { s: text },
+        (TextInputTarget::LoginPhone, _, _) => AppCommand::LoginPhoneInputString { s: text },
+        (TextInputTarget::PlaylistPicker, _, _) => {
+            AppCommand::PlaylistPickerInputString { s: text }
+        }
+        (TextInputTarget::PlaylistFilter, _, _) => AppCommand::PlaylistFilterString { s: text },
+    }
+}
+
+/// 发送一段合并后的文本输入
+pub(super) async fn send_text_input(
+    app: &AppSnapshot,
+    text: String,
+    tx: &mpsc::Sender<AppCommand>,
+) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(target) = text_input_target(app) {
+        let _ = tx.send(text_input_command(target, text)).await;
+    }
+}
+
+/// 处理 bracketed paste：换行折叠为空格，丢弃其他控制字符
+pub(super) async fn handle_paste(app: &AppSnapshot, text: &str, tx: &mpsc::Sender<AppCommand>) {
+    let cleaned: String = text
+        .trim_end_matches(['\r', '\n'])
+        .chars()
+        .filter_map(|c| match c {
+            '\r' | '\n' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect();
+    send_text_input(app, cleaned, tx).await;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::app::{App, PlaylistMode};
 
     #[tokio::test]
-    async fn tab_release_is_ignored() {
-        let app = AppSnapshot::from_app(&App::default());
+    async fn ctrl_arrows_use_seek_steps_from_snapshot() {
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            seek_step_small_ms: 3_000,
+            seek_step_large_ms: 45_000,
+            ..Default::default()
+        });
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
 
-        let key = KeyEvent {
-            code: KeyCode::Tab,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Release,
-            state: crossterm::event::KeyEventState::NONE,
-        };
+        handle_key(
+            &app,
+            KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL),
+            &tx,
+        )
+        .await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlayerSeekForwardMs { ms: 3_000 })
+        ));
+
+        // 歌词页 PageUp/PageDown 使用大步长
+        let lyrics = AppSnapshot::from_app(&App {
+            view: View::Lyrics,
+            ui_focus: UiFocus::BodyCenter,
+            logged_in: true,
+            seek_step_large_ms: 45_000,
+            ..Default::default()
+        });
+        handle_key(
+            &lyrics,
+            KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE),
+            &tx,
+        )
+        .await;
+        handle_key(
+            &lyrics,
+            KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE),
+            &tx,
+        )
+        .await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlayerSeekBackwardMs { ms: 45_000 })
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlayerSeekForwardMs { ms: 45_000 })
+        ));
+    }
 
-        let should_quit = handle_key(&app, key, &tx).await;
-        assert!(!should_quit);
-        assert!(rx.try_recv().is_err());
+    #[tokio::test]
+    async fn ctrl_shift_arrows_nudge_pane_split() {
+        let app = AppSnapshot::from_app(&App {
+            view: View::Lyrics,
+            logged_in: true,
+            ..Default::default()
+        });
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let ctrl_shift = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+
+        handle_key(&app, KeyEvent::new(KeyCode::Left, ctrl_shift), &tx).await;
+        handle_key(&app, KeyEvent::new(KeyCode::Right, ctrl_shift), &tx).await;
+
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::UiPaneNudge { delta: -2 })
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::UiPaneNudge { delta: 2 })
+        ));
+        assert!(rx.try_recv().is_err(), "不应同时触发 Seek 或歌词 offset");
     }
 
     #[tokio::test]
-    async fn tab_press_sends_focus_next_once() {
-        let app = App {
+    async fn x_in_right_pane_toggles_queue_no_fade() {
+        let mut app = App {
             view: View::Playlists,
             logged_in: true,
+            ui_focus: UiFocus::BodyRight,
             ..Default::default()
         };
-        let app = AppSnapshot::from_app(&app);
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
 
-        let key = KeyEvent {
-            code: KeyCode::Tab,
-            modifiers: KeyModifiers::NONE,
-            kind: KeyEventKind::Press,
-            state: crossterm::event::KeyEventState::NONE,
-        };
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('x')),
+            &tx,
+        )
+        .await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueToggleNoFade)));
+
+        app.ui_focus = UiFocus::BodyCenter;
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('x')),
+            &tx,
+        )
+        .await;
+        assert!(rx.try_recv().is_err(), "其它栏不响应");
+    }
 
-        let should_quit = handle_key(&app, key, &tx).await;
+    #[tokio::test]
+    async fn u_in_right_pane_undoes_queue_replacement() {
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyRight,
+            ..Default::default()
+        };
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('u')),
+            &tx,
+        )
+        .await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueUndoReplace)));
+
+        app.ui_focus = UiFocus::BodyCenter;
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('u')),
+            &tx,
+        )
+        .await;
+        assert!(rx.try_recv().is_err(), "其它栏不响应");
+    }
+
+    #[tokio::test]
+    async fn shift_n_and_e_enqueue_selected_song() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let search = App {
+            view: View::Search,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        };
+        let tracks = App {
+            view: View::Playlists,
+            playlist_mode: PlaylistMode::Tracks,
+            ..search.clone()
+        };
+        for app in [search, tracks] {
+            let snapshot = AppSnapshot::from_app(&app);
+            handle_key(&snapshot, press_key(KeyCode::Char('N')), &tx).await;
+            assert!(matches!(rx.try_recv(), Ok(AppCommand::EnqueueNextSelected)));
+            handle_key(&snapshot, press_key(KeyCode::Char('E')), &tx).await;
+            assert!(matches!(rx.try_recv(), Ok(AppCommand::EnqueueLastSelected)));
+        }
+    }
+
+    #[tokio::test]
+    async fn z_in_playlist_tracks_jumps_to_playing_song() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        };
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('z')),
+            &tx,
+        )
+        .await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistTracksJumpToPlaying)
+        ));
+
+        app.playlist_mode = PlaylistMode::List;
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('z')),
+            &tx,
+        )
+        .await;
+        assert!(rx.try_recv().is_err(), "歌单列表中不响应");
+    }
+
+    #[tokio::test]
+    async fn shift_s_in_playlist_tracks_cycles_sort() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        };
+        let shift_s = KeyEvent::new(KeyCode::Char('S'), KeyModifiers::SHIFT);
+        handle_key(&AppSnapshot::from_app(&app), shift_s, &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistTracksCycleSort)
+        ));
+    }
+
+    #[tokio::test]
+    async fn shift_x_toggles_stop_after_current() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            logged_in: true,
+            ..Default::default()
+        });
+        let shift_x = KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT);
+        handle_key(&app, shift_x, &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlayerToggleStopAfterCurrent)
+        ));
+    }
+
+    #[tokio::test]
+    async fn slash_opens_track_filter_and_input_captures_keys() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        };
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('/')),
+            &tx,
+        )
+        .await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::PlaylistFilterOpen)));
+
+        // 输入框打开时，已绑定全局动作的字符（如 q）也作为输入
+        app.playlist_filter_editing = true;
+        let snapshot = AppSnapshot::from_app(&app);
+        assert_eq!(
+            text_input_target(&snapshot),
+            Some(TextInputTarget::PlaylistFilter)
+        );
+        let quit = handle_key(&snapshot, press_key(KeyCode::Char('q')), &tx).await;
+        assert!(!quit);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistFilterChar { c: 'q' })
+        ));
+        handle_key(&snapshot, press_key(KeyCode::Enter), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistFilterSubmit)
+        ));
+
+        // 确认后 Esc 清除筛选
+        app.playlist_filter_editing = false;
+        app.playlist_filter_input = "q".to_owned();
+        handle_key(&AppSnapshot::from_app(&app), press_key(KeyCode::Esc), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistFilterCancel)
+        ));
+    }
+
+    #[tokio::test]
+    async fn queue_view_keys_edit_the_queue() {
+        let app = App {
+            view: View::Queue,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        };
+        let snapshot = AppSnapshot::from_app(&app);
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        let shift_up = KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT);
+        handle_key(&snapshot, shift_up, &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueReorderUp)));
+        handle_key(&snapshot, press_key(KeyCode::Char('J')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueReorderDown)));
+        handle_key(&snapshot, press_key(KeyCode::Down), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueMoveDown)));
+        handle_key(&snapshot, press_key(KeyCode::Delete), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueRemoveSelected)));
+        handle_key(&snapshot, press_key(KeyCode::Enter), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueuePlaySelected)));
+
+        // F8 切到队列页（登录前后都是第 5 个页签）
+        handle_key(&snapshot, press_key(KeyCode::F(8)), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::TabTo { index: 4 })));
+    }
+
+    #[tokio::test]
+    async fn arrows_in_right_pane_navigate_queue_sections() {
+        let app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyRight,
+            ..Default::default()
+        };
+        let snapshot = AppSnapshot::from_app(&app);
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        for code in [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right] {
+            handle_key(&snapshot, press_key(code), &tx).await;
+        }
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueMoveUp)));
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueMoveDown)));
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueCollapse)));
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QueueExpand)));
+    }
+
+    #[tokio::test]
+    async fn tab_release_is_ignored() {
+        let app = AppSnapshot::from_app(&App::default());
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        let key = KeyEvent {
+            code: KeyCode::Tab,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Release,
+            state: crossterm::event::KeyEventState::NONE,
+        };
+
+        let should_quit = handle_key(&app, key, &tx).await;
+        assert!(!should_quit);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn tab_press_sends_focus_next_once() {
+        let app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ..Default::default()
+        };
+        let app = AppSnapshot::from_app(&app);
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        let key = KeyEvent {
+            code: KeyCode::Tab,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: crossterm::event::KeyEventState::NONE,
+        };
+
+        let should_quit = handle_key(&app, key, &tx).await;
         assert!(!should_quit);
         assert!(matches!(rx.try_recv(), Ok(AppCommand::UiFocusNext)));
         assert!(rx.try_recv().is_err());
@@ -570,6 +1424,48 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[tokio::test]
+    async fn phone_login_form_captures_global_keys_as_input() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let closed = AppSnapshot::from_app(&App::default());
+        handle_key(&closed, press_key(KeyCode::Char('p')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::LoginTogglePhoneForm)
+        ));
+
+        let open = AppSnapshot::from_app(&App {
+            login_phone_form: Some(Default::default()),
+            ..Default::default()
+        });
+        // q 默认绑定退出，表单中作为输入
+        assert!(!handle_key(&open, press_key(KeyCode::Char('q')), &tx).await);
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::LoginPhoneInputChar { c: 'q' })
+        ));
+        assert_eq!(
+            batchable_char(&open, &press_key(KeyCode::Char('q'))),
+            Some('q')
+        );
+        handle_key(&open, press_key(KeyCode::Tab), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::LoginPhoneFieldNext)));
+        let ctrl_s = KeyEvent {
+            modifiers: KeyModifiers::CONTROL,
+            ..press_key(KeyCode::Char('s'))
+        };
+        handle_key(&open, ctrl_s, &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::LoginPhoneSendCaptcha)
+        ));
+        handle_key(&open, press_key(KeyCode::Esc), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::LoginTogglePhoneForm)
+        ));
+    }
+
     #[tokio::test]
     async fn ctrl_tab_still_switches_tabs_on_unauth_login_page() {
         let app = AppSnapshot::from_app(&App::default());
@@ -1432,6 +2328,72 @@ mod tests {
         assert!(rx.try_recv().is_err(), "不应发送其他命令");
     }
 
+    #[tokio::test]
+    async fn n_loads_more_search_results_only_from_results_pane() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let results = AppSnapshot::from_app(&App {
+            view: View::Search,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        });
+        handle_key(&results, press_key(KeyCode::Char('n')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::SearchLoadMore)));
+
+        // 搜索框中 n 是普通输入
+        let input = AppSnapshot::from_app(&App {
+            view: View::Search,
+            ui_focus: UiFocus::HeaderSearch,
+            ..Default::default()
+        });
+        handle_key(&input, press_key(KeyCode::Char('n')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::SearchInputChar { c: 'n' })
+        ));
+    }
+
+    #[tokio::test]
+    async fn album_keys_in_search_results_pane() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let results = AppSnapshot::from_app(&App {
+            view: View::Search,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        });
+        handle_key(&results, press_key(KeyCode::Char('t')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::SearchCycleKind)));
+        handle_key(&results, press_key(KeyCode::Enter), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::SearchOpenSelected)));
+        handle_key(&results, press_key(KeyCode::Char('b')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::SearchBack)));
+    }
+
+    #[tokio::test]
+    async fn t_in_lyrics_view_cycles_display() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Lyrics,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('t')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::LyricsCycleDisplay)));
+    }
+
+    #[tokio::test]
+    async fn ctrl_f_toggles_focus_mode_from_any_view() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Lyrics,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        });
+        let key = KeyEvent::new(KeyCode::Char('f'), KeyModifiers::CONTROL);
+        handle_key(&app, key, &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::UiToggleFocusMode)));
+        assert!(rx.try_recv().is_err());
+    }
+
     /// PageDown 在歌单页 BodyLeft 发送 PlaylistsPageDown
     #[tokio::test]
     async fn page_down_in_playlists_left_sends_playlists_page_down() {
@@ -1693,4 +2655,582 @@ mod tests {
         );
         assert!(rx2.try_recv().is_err(), "不应发送其他命令");
     }
+
+    #[tokio::test]
+    async fn f6_sends_tasks_toggle() {
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ..Default::default()
+        });
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        let should_quit = handle_key(&app, press_key(KeyCode::F(6)), &tx).await;
+        assert!(!should_quit);
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::TasksToggle)));
+    }
+
+    #[tokio::test]
+    async fn tasks_overlay_captures_keys() {
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            tasks_visible: true,
+            ..Default::default()
+        });
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        handle_key(&app, press_key(KeyCode::Char('x')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::TasksCancelSelected)));
+
+        handle_key(&app, press_key(KeyCode::Down), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::TasksMoveDown)));
+
+        // q 不应穿透为退出
+        let should_quit = handle_key(&app, press_key(KeyCode::Char('q')), &tx).await;
+        assert!(!should_quit);
+        assert!(rx.try_recv().is_err());
+
+        handle_key(&app, press_key(KeyCode::Esc), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::TasksToggle)));
+    }
+
+    #[tokio::test]
+    async fn notification_history_opens_detail_and_scrolls() {
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            notifications_visible: true,
+            ..Default::default()
+        };
+        app.record_notification(crate::app::ToastLevel::Error, &"长".repeat(2_000));
+        let snapshot = AppSnapshot::from_app(&app);
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        handle_key(&snapshot, press_key(KeyCode::Enter), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::NotificationsOpenDetail)
+        ));
+        let should_quit = handle_key(&snapshot, press_key(KeyCode::Char('q')), &tx).await;
+        assert!(!should_quit);
+        assert!(rx.try_recv().is_err());
+
+        app.notification_detail = Some(0);
+        let snapshot = AppSnapshot::from_app(&app);
+        handle_key(&snapshot, press_key(KeyCode::Down), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::NotificationDetailScroll { offset: 1 })
+        ));
+        // 已在顶部，再向上不发送
+        handle_key(&snapshot, press_key(KeyCode::Up), &tx).await;
+        assert!(rx.try_recv().is_err());
+        handle_key(&snapshot, press_key(KeyCode::Esc), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::NotificationsCloseDetail)
+        ));
+    }
+
+    #[tokio::test]
+    async fn restore_prompt_captures_keys() {
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            restore_prompt: Some("发现 3 天前的播放状态".to_owned()),
+            ..Default::default()
+        });
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        handle_key(&app, press_key(KeyCode::Char('r')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::RestorePromptAccept)));
+
+        handle_key(&app, press_key(KeyCode::Char('i')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::RestorePromptDismiss)
+        ));
+
+        handle_key(&app, press_key(KeyCode::Esc), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::RestorePromptDismiss)
+        ));
+
+        let should_quit = handle_key(&app, press_key(KeyCode::Char('q')), &tx).await;
+        assert!(!should_quit);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn quit_with_running_download_asks_for_confirmation() {
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ..Default::default()
+        };
+        app.tasks.register(
+            crate::app::TaskKind::PlaylistPreload { playlist_id: 1 },
+            "歌单",
+        );
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        // 只有预加载：直接退出
+        let should_quit = handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('q')),
+            &tx,
+        )
+        .await;
+        assert!(should_quit);
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::Quit)));
+
+        app.tasks
+            .register(crate::app::TaskKind::AudioDownload { song_id: 1 }, "A");
+        let should_quit = handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('q')),
+            &tx,
+        )
+        .await;
+        assert!(!should_quit, "有下载进行中时不应直接退出");
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QuitConfirmOpen)));
+    }
+
+    #[tokio::test]
+    async fn q_during_csv_export_opens_quit_confirm() {
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ..Default::default()
+        };
+        app.tasks.register(crate::app::TaskKind::CsvExport, "歌单");
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        let should_quit = handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('q')),
+            &tx,
+        )
+        .await;
+        assert!(!should_quit, "导出进行中时不应直接退出");
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QuitConfirmOpen)));
+    }
+
+    #[tokio::test]
+    async fn quit_confirm_captures_keys_and_second_q_forces_quit() {
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            quit_confirm: Some(crate::app::QuitConfirm {
+                downloads: 2,
+                wait_deadline: None,
+            }),
+            ..Default::default()
+        };
+        app.tasks
+            .register(crate::app::TaskKind::AudioDownload { song_id: 1 }, "A");
+        let app = AppSnapshot::from_app(&app);
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        assert!(!handle_key(&app, press_key(KeyCode::Char('w')), &tx).await);
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QuitConfirmWait)));
+        assert!(!handle_key(&app, press_key(KeyCode::Char('n')), &tx).await);
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::QuitConfirmCancel)));
+        assert!(!handle_key(&app, press_key(KeyCode::Char(' ')), &tx).await);
+        assert!(rx.try_recv().is_err(), "弹窗应捕获其他按键");
+
+        for c in ['q', 'y'] {
+            assert!(handle_key(&app, press_key(KeyCode::Char(c)), &tx).await);
+            assert!(matches!(rx.try_recv(), Ok(AppCommand::Quit)));
+        }
+    }
+
+    fn search_input_snapshot() -> AppSnapshot {
+        AppSnapshot::from_app(&App {
+            view: View::Search,
+            ui_focus: UiFocus::HeaderSearch,
+            logged_in: true,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn batchable_char_skips_bound_keys_and_modifiers() {
+        let app = search_input_snapshot();
+        assert_eq!(
+            batchable_char(&app, &press_key(KeyCode::Char('周'))),
+            Some('周')
+        );
+        assert_eq!(
+            batchable_char(&app, &press_key(KeyCode::Char(' '))),
+            Some(' ')
+        );
+        // q 绑定为退出，不参与合并
+        assert_eq!(batchable_char(&app, &press_key(KeyCode::Char('q'))), None);
+
+        let ctrl = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(batchable_char(&app, &ctrl), None);
+
+        let playlists = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            batchable_char(&playlists, &press_key(KeyCode::Char('a'))),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn playback_keys_are_typed_in_text_inputs() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let input = search_input_snapshot();
+        let results = AppSnapshot::from_app(&App {
+            view: View::Search,
+            ui_focus: UiFocus::BodyCenter,
+            logged_in: true,
+            ..Default::default()
+        });
+        for c in ['f', 'M', 'X', '<', '>', '=', ',', '.', ';'] {
+            let key = if c.is_uppercase() {
+                KeyEvent::new(KeyCode::Char(c), KeyModifiers::SHIFT)
+            } else {
+                press_key(KeyCode::Char(c))
+            };
+            assert_eq!(batchable_char(&input, &key), Some(c));
+            handle_key(&input, key, &tx).await;
+            assert!(
+                matches!(rx.try_recv(), Ok(AppCommand::SearchInputChar { c: typed }) if typed == c),
+                "搜索框中 {c} 应作为输入"
+            );
+            // 离开输入框后仍是全局快捷键
+            handle_key(&results, key, &tx).await;
+            let action = results
+                .keybindings
+                .resolve(KeyCode::Char(c))
+                .expect("已绑定");
+            let cmd = rx.try_recv().expect("应发送全局命令");
+            assert_eq!(format!("{cmd:?}"), format!("{:?}", action.command()));
+        }
+    }
+
+    #[test]
+    fn text_input_command_uses_char_for_single_codepoint() {
+        assert!(matches!(
+            text_input_command(TextInputTarget::Search, "a".to_owned()),
+            AppCommand::SearchInputChar { c: 'a' }
+        ));
+        assert!(matches!(
+            text_input_command(TextInputTarget::Search, "晴天".to_owned()),
+            AppCommand::SearchInputString { ref s } if s == "晴天"
+        ));
+        assert!(matches!(
+            text_input_command(TextInputTarget::LoginCookie, "abc".to_owned()),
+            AppCommand::LoginCookieInputString { ref s } if s == "abc"
+        ));
+    }
+
+    #[tokio::test]
+    async fn paste_in_search_sends_single_string_command() {
+        let app = search_input_snapshot();
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        handle_paste(&app, "周杰伦\n晴天\n", &tx).await;
+
+        match rx.try_recv() {
+            Ok(AppCommand::SearchInputString { s }) => assert_eq!(s, "周杰伦 晴天"),
+            other => panic!("expected SearchInputString, got {other:?}"),
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn paste_outside_text_input_is_ignored() {
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ..Default::default()
+        });
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+
+        handle_paste(&app, "hello", &tx).await;
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn f_cycles_playlist_filter_only_on_playlist_list() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        for (focus, mode) in [
+            (UiFocus::BodyLeft, PlaylistMode::List),
+            (UiFocus::BodyLeft, PlaylistMode::Tracks),
+            (UiFocus::BodyCenter, PlaylistMode::List),
+        ] {
+            let app = AppSnapshot::from_app(&App {
+                view: View::Playlists,
+                logged_in: true,
+                ui_focus: focus,
+                playlist_mode: mode,
+                ..Default::default()
+            });
+            handle_key(&app, press_key(KeyCode::Char('f')), &tx).await;
+            assert!(
+                matches!(rx.try_recv(), Ok(AppCommand::PlaylistsCycleFilter)),
+                "{focus:?} {mode:?}"
+            );
+        }
+
+        // 歌曲列表中 f 仍是收藏
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('f')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::PlayerToggleLike)));
+    }
+
+    #[tokio::test]
+    async fn r_retries_preload_only_on_playlist_list() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyLeft,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('r')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistsRetryPreload)
+        ));
+
+        let app = AppSnapshot::from_app(&App {
+            view: View::Search,
+            logged_in: true,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('r')), &tx).await;
+        assert!(!matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistsRetryPreload)
+        ));
+    }
+
+    #[tokio::test]
+    async fn d_opens_daily_recommend_on_playlist_list() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('d')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistsOpenRecommend)
+        ));
+    }
+
+    #[tokio::test]
+    async fn a_on_search_results_opens_playlist_picker() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Search,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('a')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::SearchAddSelectedToPlaylist)
+        ));
+    }
+
+    #[tokio::test]
+    async fn d_on_playlist_tracks_removes_selected() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('d')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistTracksRemoveSelected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn e_on_playlist_tracks_exports_csv() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('e')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::ExportCsv { all: false })
+        ));
+    }
+
+    #[tokio::test]
+    async fn t_on_playlist_tracks_toggles_order() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('t')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistTracksToggleOrder)
+        ));
+    }
+
+    #[tokio::test]
+    async fn unavailable_review_captures_keys() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_mode: PlaylistMode::Tracks,
+            ..Default::default()
+        };
+        handle_key(
+            &AppSnapshot::from_app(&app),
+            press_key(KeyCode::Char('U')),
+            &tx,
+        )
+        .await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistTracksCheckUnavailable)
+        ));
+
+        app.unavailable_review = Some(crate::app::UnavailableReview::default());
+        let app = AppSnapshot::from_app(&app);
+        for code in [
+            KeyCode::Enter,
+            KeyCode::Char('s'),
+            KeyCode::Down,
+            KeyCode::Char('q'),
+            KeyCode::Esc,
+        ] {
+            handle_key(&app, press_key(code), &tx).await;
+        }
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::UnavailableReviewAccept)
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::UnavailableReviewSkip)
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::UnavailableReviewMoveDown)
+        ));
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::UnavailableReviewClose)
+        ));
+        assert!(rx.try_recv().is_err(), "q 不退出");
+    }
+
+    #[tokio::test]
+    async fn playlist_picker_captures_keys() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Search,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            playlist_picker: Some(crate::app::PlaylistPicker::default()),
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Down), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistPickerMoveDown)
+        ));
+        // 字母（包括 q 与 j/k）输入到筛选框，不退出也不移动
+        handle_key(&app, press_key(KeyCode::Char('q')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistPickerInputChar { c: 'q' })
+        ));
+        handle_key(&app, press_key(KeyCode::Backspace), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistPickerInputBackspace)
+        ));
+        handle_key(&app, press_key(KeyCode::Esc), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::PlaylistPickerCancel)
+        ));
+        assert_eq!(
+            text_input_target(&app),
+            Some(TextInputTarget::PlaylistPicker)
+        );
+        assert!(matches!(
+            text_input_command(TextInputTarget::PlaylistPicker, "通勤".to_owned()),
+            AppCommand::PlaylistPickerInputString { s } if s == "通勤"
+        ));
+    }
+
+    #[tokio::test]
+    async fn v_marks_and_shift_a_adds_marked_search_results() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Search,
+            logged_in: true,
+            ui_focus: UiFocus::BodyCenter,
+            ..Default::default()
+        });
+        handle_key(&app, press_key(KeyCode::Char('v')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::SearchToggleMark)));
+        handle_key(&app, press_key(KeyCode::Char('A')), &tx).await;
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::SearchAddMarkedToPlaylist)
+        ));
+    }
+
+    #[tokio::test]
+    async fn alt_m_toggles_mute_from_search_input() {
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        let app = AppSnapshot::from_app(&App {
+            view: View::Search,
+            logged_in: true,
+            ui_focus: UiFocus::HeaderSearch,
+            ..Default::default()
+        });
+        let alt_m = KeyEvent {
+            modifiers: KeyModifiers::ALT,
+            ..press_key(KeyCode::Char('m'))
+        };
+        handle_key(&app, alt_m, &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::PlayerToggleMute)));
+        // 不带 Alt 的 m 仍是默认的菜单键
+        handle_key(&app, press_key(KeyCode::Char('m')), &tx).await;
+        assert!(matches!(rx.try_recv(), Ok(AppCommand::MenuOpen)));
+    }
 }
@@ -1,8 +1,12 @@
+use crate::app::PaneRatios;
+use crate::app::pane::right_pane_visible;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
 const HEADER_HEIGHT: u16 = 3;
 const FOOTER_HEIGHT: u16 = 3;
 const TOAST_HEIGHT: u16 = 3;
+/// 达到该宽度后左右栏不再按比例变宽，多出的宽度全部给中栏
+const WIDE_BODY_WIDTH: u16 = 180;
 
 pub(super) struct CanvasLayout {
     pub header: Rect,
@@ -20,7 +24,8 @@ pub(super) struct HeaderLayout {
 pub(super) struct BodyLayout {
     pub left: Rect,
     pub center: Rect,
-    pub right: Rect,
+    /// 窄终端下隐藏
+    pub right: Option<Rect>,
 }
 
 pub(super) struct RightLayout {
@@ -67,20 +72,39 @@ pub(super) fn split_header(header: Rect) -> HeaderLayout {
     }
 }
 
-pub(super) fn split_body(body: Rect) -> BodyLayout {
+/// 按宽度断点计算三栏宽度 `[左, 中, 右]`
+///
+/// - 窄（< `NARROW_BODY_WIDTH`）：隐藏右栏，左/中按比例分配全部宽度
+/// - 常规：三栏都按比例分配
+/// - 宽（>= `WIDE_BODY_WIDTH`）：左右栏按 `WIDE_BODY_WIDTH` 计算后固定，中栏占据剩余宽度
+pub(super) fn body_widths(width: u16, ratios: PaneRatios) -> [u16; 3] {
+    let pct = |base: u16, p: u16| (u32::from(base) * u32::from(p) / 100) as u16;
+    if !right_pane_visible(width) {
+        let sum = u32::from(ratios.left + ratios.center).max(1);
+        let left = (u32::from(width) * u32::from(ratios.left) / sum) as u16;
+        return [left, width - left, 0];
+    }
+    let base = width.min(WIDE_BODY_WIDTH);
+    let left = pct(base, ratios.left);
+    let right = pct(base, ratios.right());
+    [left, width - left - right, right]
+}
+
+pub(super) fn split_body(body: Rect, ratios: PaneRatios) -> BodyLayout {
+    let [left, center, right] = body_widths(body.width, ratios);
     let cols = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Length(24),
-            Constraint::Length(58),
-            Constraint::Length(40),
+            Constraint::Length(left),
+            Constraint::Length(center),
+            Constraint::Length(right),
         ])
         .split(body);
 
     BodyLayout {
         left: cols[0],
         center: cols[1],
-        right: cols[2],
+        right: (right > 0).then_some(cols[2]),
     }
 }
 
@@ -95,3 +119,53 @@ pub(super) fn split_right(right: Rect) -> RightLayout {
         now: rows[1],
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_ratios_keep_classic_columns_at_122() {
+        assert_eq!(body_widths(122, PaneRatios::default()), [24, 58, 40]);
+    }
+
+    #[test]
+    fn narrow_width_hides_right_pane() {
+        let widths = body_widths(100, PaneRatios::default());
+        assert_eq!(widths[2], 0);
+        assert_eq!(widths[0] + widths[1], 100);
+        assert_eq!(widths[0], 29, "左/中按 20:47 分配");
+
+        let body = Rect::new(0, 0, 100, 20);
+        assert!(split_body(body, PaneRatios::default()).right.is_none());
+        assert!(
+            split_body(Rect::new(0, 0, 122, 20), PaneRatios::default())
+                .right
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn wide_width_grows_center_only() {
+        let at_cap = body_widths(WIDE_BODY_WIDTH, PaneRatios::default());
+        let ultrawide = body_widths(300, PaneRatios::default());
+        assert_eq!(at_cap, [36, 85, 59]);
+        assert_eq!(ultrawide[0], at_cap[0]);
+        assert_eq!(ultrawide[2], at_cap[2]);
+        assert_eq!(ultrawide[1], 300 - 36 - 59);
+    }
+
+    #[test]
+    fn widths_always_fill_body() {
+        let mut ratios = PaneRatios::default();
+        ratios.nudge(10);
+        for width in [100, 119, 120, 122, 150, 179, 180, 240, 400] {
+            for r in [PaneRatios::default(), ratios] {
+                let w = body_widths(width, r);
+                assert_eq!(w.iter().sum::<u16>(), width, "width={width}");
+                assert!(w[1] >= w[0], "中栏不窄于左栏 width={width}");
+            }
+        }
+        assert_eq!(body_widths(150, ratios), [45, 56, 49]);
+    }
+}
@@ -1,4 +1,6 @@
-use crate::app::LoginSnapshot;
+use super::styles::Theme;
+use crate::app::{LoginSnapshot, PhoneLoginField, PhoneLoginSnapshot};
+use crate::features::login::qr_remaining_text;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout},
@@ -13,7 +15,13 @@ pub(super) fn draw_login(
     state: &LoginSnapshot,
     logged_in: bool,
     full_page: bool,
+    theme: Theme,
 ) {
+    if let Some(form) = &state.login_phone_form {
+        draw_phone_form(f, area, &state.login_status, form, theme);
+        return;
+    }
+
     if full_page {
         draw_login_full_page(f, area, state, logged_in);
         return;
@@ -22,6 +30,19 @@ pub(super) fn draw_login(
     draw_login_compact(f, area, state, logged_in);
 }
 
+/// 状态文本；显示二维码时附上剩余有效期
+fn status_with_qr_expiry(state: &LoginSnapshot) -> String {
+    match (state.login_qr_ascii.is_some(), state.login_qr_created_at) {
+        (true, Some(created_at)) => {
+            match qr_remaining_text(created_at, std::time::Instant::now()) {
+                Some(left) => format!("{}（二维码剩余 {left}）", state.login_status),
+                None => format!("{}（二维码已过期）", state.login_status),
+            }
+        }
+        _ => state.login_status.clone(),
+    }
+}
+
 fn draw_login_full_page(f: &mut Frame, area: Rect, state: &LoginSnapshot, logged_in: bool) {
     if state.login_cookie_input_visible {
         let chunks = Layout::default()
@@ -75,7 +96,7 @@ fn draw_login_full_page(f: &mut Frame, area: Rect, state: &LoginSnapshot, logged
     let qr_hint = if state.login_qr_ascii.is_some() {
         ""
     } else {
-        "\n\n按 l 生成二维码\n按 c 使用 Cookie 登录"
+        "\n\n按 l 生成二维码\n按 c 使用 Cookie 登录\n按 p 使用手机号登录"
     };
     let qr_display = format!(
         "{}{}",
@@ -95,12 +116,13 @@ fn draw_login_full_page(f: &mut Frame, area: Rect, state: &LoginSnapshot, logged
         "状态:\n{}\n\n已登录: {}\n\n快捷键:\n\
         l - 生成二维码\n\
         c - Cookie 登录\n\
+        p - 手机号登录\n\
         F1-F4 / Ctrl+Tab - 切换页面\n\
         ? - 帮助\n\
         q - 退出\n\n\
         Cookie 登录：\n浏览器登录 music.163.com\n后按 c 输入 MUSIC_U\n\n\
         URL:\n{}",
-        state.login_status,
+        status_with_qr_expiry(state),
         if logged_in {
             "是"
         } else {
@@ -156,7 +178,7 @@ fn draw_login_compact(f: &mut Frame, area: Rect, state: &LoginSnapshot, logged_i
     let qr_hint = if state.login_qr_ascii.is_some() {
         ""
     } else {
-        "\n\n按 l 生成二维码，或按 c 使用 Cookie 登录"
+        "\n\n按 l 生成二维码，按 c 使用 Cookie 登录，按 p 使用手机号登录"
     };
     let qr_display = format!(
         "{}{}",
@@ -178,12 +200,12 @@ fn draw_login_compact(f: &mut Frame, area: Rect, state: &LoginSnapshot, logged_i
         URL: {}\n\
         \n\
         快捷键:\n\
-        l - 生成二维码 | c - Cookie 登录\n\
+        l - 生成二维码 | c - Cookie 登录 | p - 手机号登录\n\
         Ctrl+Tab - 切换页面 | q - 退出\n\
         \n\
         Cookie 登录：浏览器登录 music.163.com\n\
         后按 c，输入 MUSIC_U 值即可",
-        state.login_status,
+        status_with_qr_expiry(state),
         if logged_in {
             "是"
         } else {
@@ -195,3 +217,57 @@ fn draw_login_compact(f: &mut Frame, area: Rect, state: &LoginSnapshot, logged_i
         Paragraph::new(info).block(Block::default().borders(Borders::ALL).title("操作说明[3]"));
     f.render_widget(info_block, chunks[1]);
 }
+
+/// 手机号登录表单：状态、手机号、密码（掩码）或验证码、快捷键说明
+fn draw_phone_form(
+    f: &mut Frame,
+    area: Rect,
+    status: &str,
+    form: &PhoneLoginSnapshot,
+    theme: Theme,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(3),
+        ])
+        .split(area);
+
+    let hint = Paragraph::new(format!("状态: {status}"))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("手机号登录[3]"),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(hint, chunks[0]);
+
+    let phone = Paragraph::new(form.phone.as_str()).block(theme.panel(
+        "手机号（其他地区以 +区号 开头）",
+        form.field == PhoneLoginField::Phone,
+    ));
+    f.render_widget(phone, chunks[1]);
+
+    let (secret_title, other_mode) = if form.captcha {
+        ("短信验证码", "密码")
+    } else {
+        ("密码", "验证码")
+    };
+    let secret = Paragraph::new(form.secret.as_str())
+        .block(theme.panel(secret_title, form.field == PhoneLoginField::Secret));
+    f.render_widget(secret, chunks[2]);
+
+    let help = format!(
+        "快捷键：Enter 登录 | Tab/↑/↓ 切换输入框 | Esc 返回\n\
+        Ctrl+T 改用{other_mode}登录 | Ctrl+S 发送短信验证码\n\
+        \n\
+        登录成功后只保存服务端返回的 Cookie，不保存密码"
+    );
+    let help_block = Paragraph::new(help)
+        .block(Block::default().borders(Borders::ALL).title("帮助"))
+        .wrap(Wrap { trim: false });
+    f.render_widget(help_block, chunks[3]);
+}
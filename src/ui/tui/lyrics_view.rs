@@ -1,13 +1,13 @@
-use super::styles::focus_style;
-use super::utils::{apply_lyrics_offset, current_lyric_index, playback_time_ms};
+use super::styles::Theme;
+use super::utils::{lyric_index_at, playback_time_ms};
 use super::widgets::list_state;
 use crate::app::{LyricsSnapshot, PlayerSnapshot};
 use ratatui::{
     Frame,
     prelude::Rect,
-    style::{Color, Style},
+    style::{Modifier, Style},
     text::{Line, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, List, ListItem, Paragraph, Wrap},
 };
 
 pub(super) fn draw_lyrics(
@@ -16,28 +16,18 @@ pub(super) fn draw_lyrics(
     state: &LyricsSnapshot,
     player: &PlayerSnapshot,
     active: bool,
+    theme: Theme,
 ) {
-    let border = focus_style(active);
     if state.lyrics.is_empty() {
         let block = Paragraph::new(state.lyrics_status.as_str())
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("歌词[3]")
-                    .border_style(border),
-            )
+            .block(lyrics_panel("歌词[3]", player, active, theme))
             .wrap(Wrap { trim: false });
         f.render_widget(block, area);
         return;
     }
 
-    let (elapsed_ms, _) = playback_time_ms(player);
     let selected = if state.lyrics_follow {
-        current_lyric_index(
-            &state.lyrics,
-            apply_lyrics_offset(elapsed_ms, state.lyrics_offset_ms),
-        )
-        .unwrap_or(0)
+        highlighted_lyric(state, player)
     } else {
         state
             .lyrics_selected
@@ -49,10 +39,11 @@ pub(super) fn draw_lyrics(
         .iter()
         .map(|l| {
             let mut lines = vec![Line::from(l.text.as_str())];
-            if let Some(t) = l.translation.as_deref()
-                && !t.trim().is_empty()
-            {
-                lines.push(Line::from(format!("  {t}")));
+            if let Some(t) = state.lyrics_display.secondary(l) {
+                lines.push(
+                    Line::from(format!("  {t}"))
+                        .style(Style::default().add_modifier(Modifier::DIM)),
+                );
             }
             ListItem::new(Text::from(lines).centered())
         })
@@ -62,13 +53,31 @@ pub(super) fn draw_lyrics(
     let scroll_padding = 5.min(area.height.saturating_sub(2) as usize / 2);
 
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("歌词[3]（自动滚动）")
-                .border_style(border),
-        )
+        .block(lyrics_panel("歌词[3]（自动滚动）", player, active, theme))
         .scroll_padding(scroll_padding)
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .highlight_style(theme.current())
+        .highlight_symbol(theme.current_symbol());
     f.render_stateful_widget(list, area, &mut list_state(selected));
 }
+
+/// 歌词面板；底边右侧显示「下一首」（专注模式隐藏歌名时不显示）
+fn lyrics_panel<'a>(
+    title: &'a str,
+    player: &PlayerSnapshot,
+    active: bool,
+    theme: Theme,
+) -> Block<'a> {
+    let block = theme.panel(title, active);
+    match player.up_next.as_deref() {
+        Some(next) if !player.mask_title => {
+            block.title_bottom(Line::from(format!(" 下一首: {next} ")).right_aligned())
+        }
+        _ => block,
+    }
+}
+
+/// 按当前播放进度（含 offset）定位的歌词行
+pub(super) fn highlighted_lyric(state: &LyricsSnapshot, player: &PlayerSnapshot) -> usize {
+    let (elapsed_ms, _) = playback_time_ms(player);
+    lyric_index_at(&state.lyrics, elapsed_ms, state.lyrics_offset_ms).unwrap_or(0)
+}
@@ -1,15 +1,16 @@
 use ratatui::{
     Frame,
     prelude::Rect,
-    style::{Color, Modifier, Style},
+    style::Color,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState},
 };
 
-use crate::app::AppSnapshot;
+use super::styles::Theme;
+use crate::app::{AppSnapshot, PlaylistPicker, SongNameMode, UnavailableReview};
 
 /// Draw the action menu overlay centered on the canvas area.
-pub(super) fn draw_menu_overlay(f: &mut Frame, area: Rect, app: &AppSnapshot) {
+pub(super) fn draw_menu_overlay(f: &mut Frame, area: Rect, app: &AppSnapshot, theme: Theme) {
     let width = area.width.saturating_sub(4).min(40);
     let item_count = app.menu_items.len() as u16;
     let height = item_count
@@ -25,11 +26,9 @@ pub(super) fn draw_menu_overlay(f: &mut Frame, area: Rect, app: &AppSnapshot) {
         .enumerate()
         .map(|(i, item)| {
             let style = if i == app.menu_selected {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
+                theme.selected_item()
             } else {
-                Style::default().fg(Color::White)
+                theme.fg(Color::White)
             };
             let prefix = if i == app.menu_selected { " > " } else { "   " };
             ListItem::new(Line::from(Span::styled(format!("{prefix}{item}"), style)))
@@ -40,7 +39,7 @@ pub(super) fn draw_menu_overlay(f: &mut Frame, area: Rect, app: &AppSnapshot) {
         Block::default()
             .borders(Borders::ALL)
             .title("操作菜单")
-            .style(Style::default().fg(Color::Cyan)),
+            .style(theme.fg(Color::Cyan)),
     );
 
     let mut state = ListState::default();
@@ -49,6 +48,137 @@ pub(super) fn draw_menu_overlay(f: &mut Frame, area: Rect, app: &AppSnapshot) {
     f.render_stateful_widget(list, popup, &mut state);
 }
 
+/// 「添加到歌单」选择框：输入文字按歌单名模糊筛选
+pub(super) fn draw_playlist_picker(
+    f: &mut Frame,
+    area: Rect,
+    picker: &PlaylistPicker,
+    name_mode: SongNameMode,
+    theme: Theme,
+) {
+    let visible = picker.visible();
+    let width = area.width.saturating_sub(4).min(48);
+    let height = (visible.len().max(1) as u16)
+        .saturating_add(2)
+        .min(area.height.saturating_sub(4));
+    let popup = centered_rect(area, width, height);
+
+    f.render_widget(Clear, popup);
+
+    let mut items: Vec<ListItem> = visible
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let selected = i == picker.selected;
+            let style = if selected {
+                theme.selected_item()
+            } else {
+                theme.fg(Color::White)
+            };
+            let prefix = if selected { " > " } else { "   " };
+            ListItem::new(Line::from(Span::styled(
+                format!("{prefix}{} ({} 首)", p.name, p.track_count),
+                style,
+            )))
+        })
+        .collect();
+    if items.is_empty() {
+        items.push(ListItem::new(Line::from(Span::styled(
+            "   没有匹配的歌单",
+            theme.fg(Color::DarkGray),
+        ))));
+    }
+
+    let target = match picker.songs.as_slice() {
+        [song] => format!("「{}」", song.display_name(name_mode)),
+        songs => format!(" {} 首", songs.len()),
+    };
+    let filter = if picker.filter.is_empty() {
+        " 输入筛选 ".to_owned()
+    } else {
+        format!(" 筛选: {} ", picker.filter)
+    };
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("添加{target}到歌单（Enter 确认 / Esc 取消）"))
+            .title_bottom(Line::from(filter).right_aligned())
+            .style(theme.fg(Color::Cyan)),
+    );
+
+    let mut state = ListState::default();
+    state.select((!visible.is_empty()).then_some(picker.selected));
+
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// 不可用歌曲审阅框：每行为「原歌曲 → 找到的版本（专辑）」
+pub(super) fn draw_unavailable_review(
+    f: &mut Frame,
+    area: Rect,
+    review: &UnavailableReview,
+    name_mode: SongNameMode,
+    theme: Theme,
+) {
+    let width = area.width.saturating_sub(4).min(72);
+    let height = (review.items.len().max(1) as u16)
+        .saturating_add(2)
+        .min(area.height.saturating_sub(4));
+    let popup = centered_rect(area, width, height);
+
+    f.render_widget(Clear, popup);
+
+    let items: Vec<ListItem> = review
+        .items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let selected = i == review.selected;
+            let style = if selected {
+                theme.selected_item()
+            } else {
+                theme.fg(Color::White)
+            };
+            let prefix = if selected { " > " } else { "   " };
+            let album = if item.candidate.album.is_empty() {
+                String::new()
+            } else {
+                format!("（{}）", item.candidate.album)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{prefix}{} → ", item.original.display_title(name_mode)),
+                    style,
+                ),
+                Span::styled(
+                    format!("{}{album}", item.candidate.display_title(name_mode)),
+                    if selected {
+                        style
+                    } else {
+                        theme.fg(Color::Green)
+                    },
+                ),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                "替换「{}」中的不可用歌曲（Enter 替换 / s 跳过 / Esc 关闭）",
+                review.playlist_name
+            ))
+            .title_bottom(Line::from(format!(" 剩余 {} 首 ", review.items.len())).right_aligned())
+            .style(theme.fg(Color::Cyan)),
+    );
+
+    let mut state = ListState::default();
+    state.select(Some(review.selected));
+
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
 fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
     let x = area.x + area.width.saturating_sub(width) / 2;
     let y = area.y + area.height.saturating_sub(height) / 2;
@@ -88,7 +218,7 @@ mod tests {
         let mut terminal = ratatui::Terminal::new(backend).unwrap();
         terminal
             .draw(|f| {
-                draw_menu_overlay(f, f.area(), &snapshot);
+                draw_menu_overlay(f, f.area(), &snapshot, Theme::Color);
             })
             .unwrap();
     }
@@ -1,3 +1,5 @@
+use super::layout::body_widths;
+use super::playlists_view::TRACKS_HEADER_HEIGHT;
 use super::utils::{canvas_rect, is_unauth_login_page};
 use crate::app::{AppSnapshot, AppViewSnapshot, PlaylistMode, View, tab_configs};
 use crate::messages::app::AppCommand;
@@ -14,8 +16,6 @@ use unicode_width::UnicodeWidthStr;
 const HEADER_HEIGHT: u16 = 3;
 const FOOTER_HEIGHT: u16 = 3;
 const TOAST_HEIGHT: u16 = 3;
-const LEFT_WIDTH: u16 = 24;
-const CENTER_WIDTH: u16 = 58;
 
 /// Double-click time window in milliseconds.
 const DOUBLE_CLICK_WINDOW_MS: u64 = 500;
@@ -83,7 +83,12 @@ pub(super) async fn handle_mouse(
     mouse: MouseEvent,
     tx: &mpsc::Sender<AppCommand>,
 ) {
-    if app.help_visible {
+    if app.help_visible
+        || app.tasks_visible
+        || app.notifications_visible
+        || app.restore_prompt.is_some()
+        || app.quit_confirm.is_some()
+    {
         return;
     }
     let Ok((cols, rows)) = terminal::size() else {
@@ -109,8 +114,13 @@ async fn handle_mouse_with_canvas(
     canvas: &Rect,
     tx: &mpsc::Sender<AppCommand>,
 ) {
-    // Help overlay blocks all mouse events
-    if app.help_visible {
+    // Help / tasks / notifications overlay / restore prompt / quit confirm blocks all mouse events
+    if app.help_visible
+        || app.tasks_visible
+        || app.notifications_visible
+        || app.restore_prompt.is_some()
+        || app.quit_confirm.is_some()
+    {
         return;
     }
 
@@ -176,11 +186,12 @@ async fn handle_mouse_with_canvas(
         return;
     }
 
-    // Determine which body panel was clicked
-    if column < LEFT_WIDTH {
+    // Determine which body panel was clicked (column widths follow the adaptive layout)
+    let [left_width, center_width, _] = body_widths(canvas.width, app.pane_ratios);
+    if column < left_width {
         // Left panel click
         handle_left_panel_click(app, row - body_start, tx).await;
-    } else if column < LEFT_WIDTH + CENTER_WIDTH {
+    } else if column < left_width + center_width {
         // Center panel click
         handle_center_panel_click(app, row - body_start, tx).await;
     } else {
@@ -232,8 +243,12 @@ async fn handle_center_panel_click(app: &AppSnapshot, row: u16, tx: &mpsc::Sende
         (View::Playlists, AppViewSnapshot::Playlists(state)) => {
             match state.playlist_mode {
                 PlaylistMode::Tracks => {
+                    // 列表上方有歌单名与统计行
                     let count = state.playlist_tracks.len();
-                    if let Some(index) = row_to_item_index(row, count) {
+                    if let Some(index) = row
+                        .checked_sub(TRACKS_HEADER_HEIGHT)
+                        .and_then(|row| row_to_item_index(row, count))
+                    {
                         let is_double = DOUBLE_CLICK
                             .with(|dc| dc.borrow_mut().check_and_update(Panel::Center, index));
                         if is_double {
@@ -252,7 +267,7 @@ async fn handle_center_panel_click(app: &AppSnapshot, row: u16, tx: &mpsc::Sende
             }
         }
         (View::Search, AppViewSnapshot::Search(state)) => {
-            let count = state.search_results.len();
+            let count = state.list_len();
             if let Some(index) = row_to_item_index(row, count) {
                 let is_double =
                     DOUBLE_CLICK.with(|dc| dc.borrow_mut().check_and_update(Panel::Center, index));
@@ -323,7 +338,12 @@ mod tests {
     use crate::app::{App, Song};
     use crate::audio_worker::{AudioBufferState, AudioStreamHint};
     use crate::domain::model::Playlist;
-    use crate::ui::tui::utils::{MIN_CANVAS_HEIGHT, MIN_CANVAS_WIDTH};
+    use crate::ui::tui::utils::MIN_CANVAS_HEIGHT;
+
+    /// 测试画布宽度：默认比例下三栏为 24 / 58 / 40
+    const CANVAS_WIDTH: u16 = 122;
+    const LEFT_WIDTH: u16 = 24;
+    const CENTER_WIDTH: u16 = 58;
 
     fn song(id: i64, name: &str, artists: &str) -> Song {
         Song {
@@ -331,6 +351,7 @@ mod tests {
             name: name.to_owned(),
             artists: artists.to_owned(),
             duration_ms: None,
+            ..Default::default()
         }
     }
 
@@ -339,7 +360,7 @@ mod tests {
         Rect {
             x: 0,
             y: 0,
-            width: MIN_CANVAS_WIDTH,
+            width: CANVAS_WIDTH,
             height: MIN_CANVAS_HEIGHT,
         }
     }
@@ -381,18 +402,24 @@ mod tests {
                 name: "我喜欢的音乐".to_owned(),
                 track_count: 100,
                 special_type: 5,
+                subscribed: false,
+                updated_at_ms: None,
             },
             Playlist {
                 id: 2,
                 name: "歌单B".to_owned(),
                 track_count: 50,
                 special_type: 0,
+                subscribed: false,
+                updated_at_ms: None,
             },
             Playlist {
                 id: 3,
                 name: "歌单C".to_owned(),
                 track_count: 30,
                 special_type: 0,
+                subscribed: false,
+                updated_at_ms: None,
             },
         ];
         app.playlists_selected = 0;
@@ -438,9 +465,15 @@ mod tests {
 
         // Click on center panel (col 24+), row within body
         // Body starts at row 3, center panel starts at col 24
-        // Within panel, row 0 = top border, row 1 = item 0, row 2 = item 1, ...
-        // Click on item 3 (Song D): row = HEADER_HEIGHT + 4 = 7
-        let mouse = make_mouse_event(LEFT_WIDTH + 10, HEADER_HEIGHT + 4, 0, 0);
+        // Within panel, row 0 = top border, rows 1-2 = playlist name and stats,
+        // row 3 = item 0, row 4 = item 1, ...
+        // Click on item 3 (Song D): row = HEADER_HEIGHT + TRACKS_HEADER_HEIGHT + 4
+        let mouse = make_mouse_event(
+            LEFT_WIDTH + 10,
+            HEADER_HEIGHT + TRACKS_HEADER_HEIGHT + 4,
+            0,
+            0,
+        );
         run_mouse(&snapshot, mouse, &tx).await;
 
         let cmd = rx.try_recv().expect("应发送 PlaylistTracksMoveTo 命令");
@@ -452,6 +485,26 @@ mod tests {
         assert!(rx.try_recv().is_err(), "不应发送其他命令");
     }
 
+    /// 歌曲列表上方的歌单名与统计行不对应任何歌曲
+    #[tokio::test]
+    async fn click_playlist_tracks_header_does_not_select() {
+        let mut app = App {
+            view: View::Playlists,
+            logged_in: true,
+            ..Default::default()
+        };
+        app.playlist_mode = PlaylistMode::Tracks;
+        app.playlist_tracks = vec![song(1, "Song A", "Artist A")];
+        let snapshot = AppSnapshot::from_app(&app);
+
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        for row in 1..=TRACKS_HEADER_HEIGHT {
+            let mouse = make_mouse_event(LEFT_WIDTH + 10, HEADER_HEIGHT + row, 0, 0);
+            run_mouse(&snapshot, mouse, &tx).await;
+        }
+        assert!(rx.try_recv().is_err(), "点击标题区不应发送命令");
+    }
+
     /// VAL-MOUSE-003: 左键单击搜索结果选中
     #[tokio::test]
     async fn click_search_result_selects_item() {
@@ -543,6 +596,32 @@ mod tests {
         assert!(rx.try_recv().is_err(), "右侧面板点击不应发送命令");
     }
 
+    /// 窄终端隐藏右栏后，原右栏位置属于中栏
+    #[tokio::test]
+    async fn narrow_canvas_center_extends_over_hidden_right_pane() {
+        let mut app = App {
+            view: View::Search,
+            logged_in: true,
+            ..Default::default()
+        };
+        app.search_results = vec![song(1, "Result A", "Artist A")];
+        let snapshot = AppSnapshot::from_app(&app);
+        let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
+        reset_dc();
+
+        let canvas = Rect {
+            width: 100,
+            ..test_canvas()
+        };
+        let mouse = make_mouse_event(95, HEADER_HEIGHT + 1, 0, 0);
+        handle_mouse_with_canvas(&snapshot, mouse, &canvas, &tx).await;
+
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppCommand::SearchMoveTo { index: 0 })
+        ));
+    }
+
     /// Click in footer area without a playing song should not trigger anything
     #[tokio::test]
     async fn click_footer_does_not_trigger() {
@@ -596,6 +675,8 @@ mod tests {
             name: "test".to_owned(),
             track_count: 10,
             special_type: 0,
+            subscribed: false,
+            updated_at_ms: None,
         }];
         let snapshot = AppSnapshot::from_app(&app);
 
@@ -630,9 +711,14 @@ mod tests {
         ];
         let snapshot = AppSnapshot::from_app(&app);
 
-        // First click: selects item 1 (Song B) — row 2 within panel (row 0 = border, row 1 = item 0, row 2 = item 1)
+        // First click: selects item 1 (Song B) — two rows below the tracks header
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
-        let mouse1 = make_mouse_event(LEFT_WIDTH + 10, HEADER_HEIGHT + 2, 0, 0);
+        let mouse1 = make_mouse_event(
+            LEFT_WIDTH + 10,
+            HEADER_HEIGHT + TRACKS_HEADER_HEIGHT + 2,
+            0,
+            0,
+        );
         run_mouse_keep_dc(&snapshot, mouse1, &tx).await;
 
         let cmd = rx.try_recv().expect("第一次点击应发送选中命令");
@@ -644,7 +730,12 @@ mod tests {
         assert!(rx.try_recv().is_err(), "第一次点击不应发送其他命令");
 
         // Second click on same item: triggers play
-        let mouse2 = make_mouse_event(LEFT_WIDTH + 10, HEADER_HEIGHT + 2, 0, 0);
+        let mouse2 = make_mouse_event(
+            LEFT_WIDTH + 10,
+            HEADER_HEIGHT + TRACKS_HEADER_HEIGHT + 2,
+            0,
+            0,
+        );
         run_mouse_keep_dc(&snapshot, mouse2, &tx).await;
 
         let cmd = rx.try_recv().expect("双击应发送播放命令");
@@ -715,6 +806,8 @@ mod tests {
             name: "歌单A".to_owned(),
             track_count: 10,
             special_type: 0,
+            subscribed: false,
+            updated_at_ms: None,
         }];
         app.playlist_tracks = vec![song(1, "Song A", "Artist A"), song(2, "Song B", "Artist B")];
         let snapshot = AppSnapshot::from_app(&app);
@@ -734,7 +827,12 @@ mod tests {
         assert!(rx.try_recv().is_err(), "不应发送其他命令");
 
         // Second click: center panel (same item index 0 but different panel)
-        let mouse2 = make_mouse_event(LEFT_WIDTH + 10, HEADER_HEIGHT + 1, 0, 0);
+        let mouse2 = make_mouse_event(
+            LEFT_WIDTH + 10,
+            HEADER_HEIGHT + TRACKS_HEADER_HEIGHT + 1,
+            0,
+            0,
+        );
         run_mouse_keep_dc(&snapshot, mouse2, &tx).await;
 
         // Should just be a single click select, NOT a play
@@ -805,12 +903,16 @@ mod tests {
                 name: "歌单A".to_owned(),
                 track_count: 10,
                 special_type: 0,
+                subscribed: false,
+                updated_at_ms: None,
             },
             Playlist {
                 id: 2,
                 name: "歌单B".to_owned(),
                 track_count: 20,
                 special_type: 0,
+                subscribed: false,
+                updated_at_ms: None,
             },
         ];
         let snapshot = AppSnapshot::from_app(&app);
@@ -859,7 +961,7 @@ mod tests {
         // Click outside canvas (far right, past canvas width)
         let outside_mouse = MouseEvent {
             kind: MouseEventKind::Down(MouseButton::Left),
-            column: MIN_CANVAS_WIDTH + 10,
+            column: CANVAS_WIDTH + 10,
             row: HEADER_HEIGHT + 1,
             modifiers: crossterm::event::KeyModifiers::NONE,
         };
@@ -925,15 +1027,14 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
 
         // Click at 1/4 of canvas width in the footer row
-        let quarter_col = MIN_CANVAS_WIDTH / 4;
+        let quarter_col = CANVAS_WIDTH / 4;
         let footer_row = MIN_CANVAS_HEIGHT - FOOTER_HEIGHT;
         let mouse = make_mouse_event(quarter_col, footer_row, 0, 0);
         run_mouse(&snapshot, mouse, &tx).await;
 
         let cmd = rx.try_recv().expect("应发送 PlayerSeekAbsoluteMs 命令");
         if let AppCommand::PlayerSeekAbsoluteMs { ms } = cmd {
-            let expected =
-                (240_000.0 * (quarter_col as f64 / MIN_CANVAS_WIDTH as f64)).round() as u64;
+            let expected = (240_000.0 * (quarter_col as f64 / CANVAS_WIDTH as f64)).round() as u64;
             assert!(
                 (ms as i64 - expected as i64).unsigned_abs() <= 1000,
                 "seek 目标时间应 ≈ {expected}ms（±1000ms），实际 {ms}ms"
@@ -958,7 +1059,7 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
 
         // Click at 3/4 of canvas width in the footer row
-        let three_quarter_col = (MIN_CANVAS_WIDTH * 3) / 4;
+        let three_quarter_col = (CANVAS_WIDTH * 3) / 4;
         let footer_row = MIN_CANVAS_HEIGHT - FOOTER_HEIGHT;
         let mouse = make_mouse_event(three_quarter_col, footer_row, 0, 0);
         run_mouse(&snapshot, mouse, &tx).await;
@@ -966,7 +1067,7 @@ mod tests {
         let cmd = rx.try_recv().expect("应发送 PlayerSeekAbsoluteMs 命令");
         if let AppCommand::PlayerSeekAbsoluteMs { ms } = cmd {
             let expected =
-                (240_000.0 * (three_quarter_col as f64 / MIN_CANVAS_WIDTH as f64)).round() as u64;
+                (240_000.0 * (three_quarter_col as f64 / CANVAS_WIDTH as f64)).round() as u64;
             assert!(
                 (ms as i64 - expected as i64).unsigned_abs() <= 1000,
                 "seek 目标时间应 ≈ {expected}ms（±1000ms），实际 {ms}ms"
@@ -1017,7 +1118,7 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
 
         let footer_row = MIN_CANVAS_HEIGHT - FOOTER_HEIGHT;
-        let mouse = make_mouse_event(MIN_CANVAS_WIDTH - 1, footer_row, 0, 0);
+        let mouse = make_mouse_event(CANVAS_WIDTH - 1, footer_row, 0, 0);
         run_mouse(&snapshot, mouse, &tx).await;
 
         let cmd = rx.try_recv().expect("应发送 PlayerSeekAbsoluteMs 命令");
@@ -1046,7 +1147,7 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
 
         let footer_row = MIN_CANVAS_HEIGHT - FOOTER_HEIGHT;
-        let mouse = make_mouse_event(MIN_CANVAS_WIDTH / 2, footer_row, 0, 0);
+        let mouse = make_mouse_event(CANVAS_WIDTH / 2, footer_row, 0, 0);
         run_mouse(&snapshot, mouse, &tx).await;
 
         assert!(rx.try_recv().is_err(), "无播放时页脚点击不应发送命令");
@@ -1071,7 +1172,7 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<AppCommand>(8);
 
         let footer_row = MIN_CANVAS_HEIGHT - FOOTER_HEIGHT;
-        let mouse = make_mouse_event(MIN_CANVAS_WIDTH / 2, footer_row, 0, 0);
+        let mouse = make_mouse_event(CANVAS_WIDTH / 2, footer_row, 0, 0);
         run_mouse(&snapshot, mouse, &tx).await;
 
         assert!(
@@ -1096,7 +1197,7 @@ mod tests {
 
         let scroll_mouse = MouseEvent {
             kind: MouseEventKind::ScrollUp,
-            column: MIN_CANVAS_WIDTH / 2,
+            column: CANVAS_WIDTH / 2,
             row: MIN_CANVAS_HEIGHT / 2,
             modifiers: crossterm::event::KeyModifiers::NONE,
         };
@@ -1125,7 +1226,7 @@ mod tests {
 
         let scroll_mouse = MouseEvent {
             kind: MouseEventKind::ScrollDown,
-            column: MIN_CANVAS_WIDTH / 2,
+            column: CANVAS_WIDTH / 2,
             row: MIN_CANVAS_HEIGHT / 2,
             modifiers: crossterm::event::KeyModifiers::NONE,
         };
@@ -1151,7 +1252,7 @@ mod tests {
 
         let scroll_mouse = MouseEvent {
             kind: MouseEventKind::ScrollUp,
-            column: MIN_CANVAS_WIDTH / 2,
+            column: CANVAS_WIDTH / 2,
             row: MIN_CANVAS_HEIGHT / 2,
             modifiers: crossterm::event::KeyModifiers::NONE,
         };
@@ -1220,7 +1321,7 @@ mod tests {
         // Try scroll
         let scroll_mouse = MouseEvent {
             kind: MouseEventKind::ScrollUp,
-            column: MIN_CANVAS_WIDTH / 2,
+            column: CANVAS_WIDTH / 2,
             row: MIN_CANVAS_HEIGHT / 2,
             modifiers: crossterm::event::KeyModifiers::NONE,
         };
@@ -1228,7 +1329,7 @@ mod tests {
 
         // Try footer click
         let footer_row = MIN_CANVAS_HEIGHT - FOOTER_HEIGHT;
-        let footer_mouse = make_mouse_event(MIN_CANVAS_WIDTH / 2, footer_row, 0, 0);
+        let footer_mouse = make_mouse_event(CANVAS_WIDTH / 2, footer_row, 0, 0);
         run_mouse(&snapshot, footer_mouse, &tx).await;
 
         assert!(rx.try_recv().is_err(), "帮助弹窗可见时鼠标不应发送任何命令");
@@ -1251,7 +1352,7 @@ mod tests {
         // Click outside canvas (beyond canvas width)
         let outside_click = MouseEvent {
             kind: MouseEventKind::Down(MouseButton::Left),
-            column: MIN_CANVAS_WIDTH + 10,
+            column: CANVAS_WIDTH + 10,
             row: HEADER_HEIGHT + 1,
             modifiers: crossterm::event::KeyModifiers::NONE,
         };
@@ -1260,7 +1361,7 @@ mod tests {
         // Scroll outside canvas
         let outside_scroll = MouseEvent {
             kind: MouseEventKind::ScrollUp,
-            column: MIN_CANVAS_WIDTH + 10,
+            column: CANVAS_WIDTH + 10,
             row: HEADER_HEIGHT + 1,
             modifiers: crossterm::event::KeyModifiers::NONE,
         };
@@ -0,0 +1,267 @@
+use base64::Engine;
+use ratatui::{
+    Frame,
+    prelude::Rect,
+    style::Color,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+};
+use std::time::Instant;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use super::styles::Theme;
+use super::utils::{fmt_ago, truncate_to_width};
+use crate::app::{AppSnapshot, ToastLevel};
+
+/// Draw the notification history overlay (newest first).
+pub(super) fn draw_notifications_overlay(
+    f: &mut Frame,
+    area: Rect,
+    app: &AppSnapshot,
+    theme: Theme,
+) {
+    let width = area.width.saturating_sub(4).min(90);
+    let rows = (app.notifications.len().max(1) as u16).saturating_add(3); // borders + hint line
+    let height = rows.min(area.height.saturating_sub(4));
+    let popup = centered_rect(area, width, height);
+
+    f.render_widget(Clear, popup);
+
+    let now = Instant::now();
+    // 边框 + 前缀 + 时间列
+    let text_width = usize::from(width).saturating_sub(2 + 3 + 12);
+    let mut items: Vec<ListItem> = if app.notifications.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "   暂无通知",
+            theme.fg(Color::Gray),
+        )))]
+    } else {
+        app.notifications
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let selected = i == app.notifications_selected;
+                let style = if selected {
+                    theme.selected_item()
+                } else {
+                    theme.fg(level_color(n.level))
+                };
+                let prefix = if selected { " > " } else { "   " };
+                // 列表只显示首行，完整内容在详情中查看
+                let first_line = n.message.lines().next().unwrap_or_default();
+                ListItem::new(Line::from(Span::styled(
+                    format!(
+                        "{prefix}{:<10}  {}",
+                        fmt_ago(now.saturating_duration_since(n.at)),
+                        truncate_to_width(first_line, text_width)
+                    ),
+                    style,
+                )))
+            })
+            .collect()
+    };
+    items.push(ListItem::new(Line::from(Span::styled(
+        "   ↑↓ 选择 | Enter 查看详情 | F7/Esc 关闭",
+        theme.fg(Color::DarkGray),
+    ))));
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("通知历史")
+            .style(theme.fg(Color::Cyan)),
+    );
+
+    let mut state = ListState::default();
+    if !app.notifications.is_empty() {
+        state.select(Some(app.notifications_selected));
+    }
+
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+/// Draw the full text of the selected notification, wrapped and scrollable.
+pub(super) fn draw_notification_detail(f: &mut Frame, area: Rect, app: &AppSnapshot, theme: Theme) {
+    let Some(n) = app
+        .notification_detail
+        .and_then(|i| app.notifications.get(i))
+    else {
+        return;
+    };
+    let popup = detail_rect(area);
+    let (text_width, body_height) = detail_body_size(popup);
+    let lines = wrap_text(&n.message, text_width);
+    let max_scroll = lines.len().saturating_sub(body_height);
+    let scroll = app.notification_detail_scroll.min(max_scroll);
+
+    f.render_widget(Clear, popup);
+
+    let style = theme.fg(level_color(n.level));
+    let mut body: Vec<Line> = lines
+        .into_iter()
+        .skip(scroll)
+        .take(body_height)
+        .map(|l| Line::from(Span::styled(l, style)))
+        .collect();
+    body.resize(body_height, Line::from(""));
+    body.push(Line::from(Span::styled(
+        "↑↓/PgUp/PgDn 滚动 | c 复制 | Esc 返回",
+        theme.fg(Color::DarkGray),
+    )));
+
+    let title = if max_scroll > 0 {
+        format!("通知详情（{}/{}）", scroll + 1, max_scroll + 1)
+    } else {
+        "通知详情".to_owned()
+    };
+    let paragraph = Paragraph::new(body).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(theme.fg(Color::Cyan)),
+    );
+    f.render_widget(paragraph, popup);
+}
+
+/// 详情面板的最大滚动行数与一页的行数（按键处理据此限制滚动范围）
+pub(super) fn detail_scroll_bounds(app: &AppSnapshot, canvas: Rect) -> Option<(usize, usize)> {
+    let n = app
+        .notification_detail
+        .and_then(|i| app.notifications.get(i))?;
+    let (text_width, body_height) = detail_body_size(detail_rect(canvas));
+    let lines = wrap_text(&n.message, text_width).len();
+    Some((lines.saturating_sub(body_height), body_height.max(1)))
+}
+
+/// OSC 52 转义序列：终端（含 SSH 会话中的本地终端）把文本写入系统剪贴板
+pub(super) fn osc52_copy_sequence(text: &str) -> String {
+    format!(
+        "\x1b]52;c;{}\x07",
+        base64::engine::general_purpose::STANDARD.encode(text)
+    )
+}
+
+/// 按显示宽度换行：优先在空格处断行，放不下的长串（URL、无空格的中文）按字符硬断
+pub(super) fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let paragraph = paragraph.trim_end_matches('\r');
+        let mut line = String::new();
+        let mut used = 0;
+        for token in paragraph.split_inclusive(' ') {
+            let visible = token.trim_end_matches(' ').width();
+            if used + visible <= width {
+                line.push_str(token);
+                used += token.width();
+                continue;
+            }
+            if visible <= width {
+                lines.push(line.trim_end().to_owned());
+                line = token.to_owned();
+                used = token.width();
+                continue;
+            }
+            for c in token.chars() {
+                let w = c.width().unwrap_or(0);
+                if used + w > width && used > 0 {
+                    lines.push(line.trim_end().to_owned());
+                    line.clear();
+                    used = 0;
+                }
+                line.push(c);
+                used += w;
+            }
+        }
+        lines.push(line.trim_end().to_owned());
+    }
+    lines
+}
+
+fn detail_rect(area: Rect) -> Rect {
+    let width = area.width.saturating_sub(8).min(100);
+    let height = area.height.saturating_sub(4);
+    centered_rect(area, width, height)
+}
+
+/// 正文的宽度与行数（扣除边框与提示行）
+fn detail_body_size(popup: Rect) -> (usize, usize) {
+    (
+        usize::from(popup.width.saturating_sub(2)),
+        usize::from(popup.height.saturating_sub(3)),
+    )
+}
+
+fn level_color(level: ToastLevel) -> Color {
+    match level {
+        ToastLevel::Error => Color::Red,
+        ToastLevel::Warning => Color::Yellow,
+        ToastLevel::Info => Color::White,
+    }
+}
+
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, notifications::MAX_MESSAGE_BYTES};
+
+    #[test]
+    fn wraps_at_spaces_when_words_fit() {
+        assert_eq!(wrap_text("hello world foo", 11), vec!["hello world", "foo"]);
+        assert_eq!(wrap_text("a\n\nb", 10), vec!["a", "", "b"]);
+    }
+
+    #[test]
+    fn hard_breaks_long_unbroken_tokens() {
+        let url = format!("https://music.163.com/api/{}", "x".repeat(60));
+        let text = format!("请求失败: {url}");
+        let lines = wrap_text(&text, 20);
+        assert!(lines.iter().all(|l| l.width() <= 20), "{lines:?}");
+        // URL 紧接在前文之后按字符断开，内容原样保留
+        assert_eq!(lines.concat(), text);
+        assert!(lines.len() >= 5);
+    }
+
+    #[test]
+    fn hard_breaks_by_display_width_for_wide_chars() {
+        let lines = wrap_text("错误错误错误", 5);
+        assert_eq!(lines, vec!["错误", "错误", "错误"]);
+        // 宽度比单个字符还窄时每行一个字符，不会死循环
+        assert_eq!(wrap_text("错误", 1), vec!["错", "误"]);
+    }
+
+    #[test]
+    fn detail_scroll_is_bounded_by_wrapped_length() {
+        let mut app = App {
+            notifications_visible: true,
+            ..Default::default()
+        };
+        app.record_notification(ToastLevel::Error, &"y".repeat(MAX_MESSAGE_BYTES * 2));
+        app.notification_detail = Some(0);
+        let snapshot = AppSnapshot::from_app(&app);
+        let canvas = Rect::new(0, 0, 100, 29);
+
+        let (max_scroll, page) = detail_scroll_bounds(&snapshot, canvas).unwrap();
+        // 存储的消息已截断到 8KB 左右，按 90 列换行
+        let stored = &snapshot.notifications[0].message;
+        assert!(stored.len() < MAX_MESSAGE_BYTES + 64);
+        assert_eq!(page, 22);
+        assert_eq!(max_scroll, wrap_text(stored, 90).len() - page);
+    }
+
+    #[test]
+    fn osc52_sequence_encodes_text() {
+        assert_eq!(osc52_copy_sequence("hi"), "\x1b]52;c;aGk=\x07");
+    }
+}
@@ -1,5 +1,7 @@
+use crate::app::QuitConfirm;
 use ratatui::{
     Frame,
+    layout::Alignment,
     prelude::Rect,
     text::{Line, Text},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
@@ -7,7 +9,7 @@ use ratatui::{
 
 pub(super) fn draw_help_overlay(f: &mut Frame, area: Rect) {
     let width = area.width.saturating_sub(4).min(90);
-    let height = area.height.saturating_sub(4).min(18);
+    let height = area.height.saturating_sub(4).min(22);
     let popup = centered_rect(area, width, height);
 
     f.render_widget(Clear, popup);
@@ -17,13 +19,34 @@ pub(super) fn draw_help_overlay(f: &mut Frame, area: Rect) {
         Line::from(""),
         Line::from("F1-F4: Switch view"),
         Line::from("1-4: Switch focus (Alt+1-4 in search)"),
-        Line::from("Tab / Shift+Tab: Focus cycle"),
+        Line::from("Tab / Shift+Tab: Focus cycle (dimmed panes are skipped)"),
         Line::from("Enter: Confirm / Open"),
         Line::from("Space: Play / Pause"),
         Line::from("[ / ]: Prev / Next"),
-        Line::from("Ctrl+←/→: Seek"),
-        Line::from("Alt+↑/↓: Volume"),
+        Line::from("Ctrl+←/→: Seek (PgUp/PgDn in lyrics view: long seek)"),
+        Line::from("Ctrl+Shift+←/→: Resize left/center panes"),
+        Line::from("Alt+↑/↓ / Alt+M: Volume / Mute"),
+        Line::from("< / > / =: Speed -/+ 0.05x / reset"),
         Line::from("M: Play mode"),
+        Line::from("X: Stop after current song"),
+        Line::from(", / . / ;: Set loop point A / B / clear A-B loop"),
+        Line::from("f: Like / Unlike current song"),
+        Line::from("x (queue pane [4]): No crossfade for this queue"),
+        Line::from("←/→ (queue pane [4]): Collapse / expand source section"),
+        Line::from("f (playlist list): Filter all / mine / subscribed"),
+        Line::from("t (lyrics view): Original / +translation / +romanization"),
+        Line::from("t (playlist tracks): Original order / recently added first"),
+        Line::from("S (playlist tracks): Sort original / by name / by artist"),
+        Line::from("z (playlist tracks): Jump to the playing song"),
+        Line::from("/ (playlist tracks): Filter by name / artist (Esc: clear)"),
+        Line::from("D (search results / playlist tracks): Download to music folder"),
+        Line::from("N / E (search results / playlist tracks): Play next / Add to queue end"),
+        Line::from("F5: Personal FM on/off ([ disabled, ] next FM song)"),
+        Line::from("F6: Background tasks"),
+        Line::from("F7: Notification history (Enter: full message, c: copy)"),
+        Line::from("F8: Play queue (Shift+↑/↓ reorder, d remove, Enter play)"),
+        Line::from("Ctrl+F: Focus mode (hide toasts, session only)"),
+        Line::from("Ctrl+Z: Suspend (fg to resume)"),
         Line::from("? / Esc: Close help"),
     ];
     let help = Paragraph::new(Text::from(lines))
@@ -32,6 +55,37 @@ pub(super) fn draw_help_overlay(f: &mut Frame, area: Rect) {
     f.render_widget(help, popup);
 }
 
+/// 启动时发现旧播放状态的确认弹窗
+pub(super) fn draw_restore_prompt(f: &mut Frame, area: Rect, message: &str) {
+    let width = area.width.saturating_sub(4).min(60);
+    let popup = centered_rect(area, width, 5);
+
+    f.render_widget(Clear, popup);
+
+    let prompt = Paragraph::new(Text::from(vec![Line::from(""), Line::from(message)]))
+        .block(Block::default().borders(Borders::ALL).title("恢复播放状态"))
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false });
+    f.render_widget(prompt, popup);
+}
+
+/// 有下载进行中时的退出确认弹窗
+pub(super) fn draw_quit_confirm(f: &mut Frame, area: Rect, confirm: &QuitConfirm) {
+    let width = area.width.saturating_sub(4).min(64);
+    let popup = centered_rect(area, width, 5);
+
+    f.render_widget(Clear, popup);
+
+    let prompt = Paragraph::new(Text::from(vec![
+        Line::from(""),
+        Line::from(confirm.message()),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title("退出"))
+    .alignment(Alignment::Center)
+    .wrap(Wrap { trim: false });
+    f.render_widget(prompt, popup);
+}
+
 fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
     let x = area.x + area.width.saturating_sub(width) / 2;
     let y = area.y + area.height.saturating_sub(height) / 2;
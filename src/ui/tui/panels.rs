@@ -1,22 +1,27 @@
 use super::playlists_view::draw_playlist_list;
-use super::styles::focus_style;
+use super::styles::Theme;
 use super::utils::{
-    apply_lyrics_offset, br_label, current_lyric_index, fmt_offset, playback_time_ms,
+    br_label, failure_mark, fmt_ago, fmt_mmss, fmt_offset, fmt_relative_now, lyric_index_at,
+    playback_time_ms, quality_span,
 };
+use crate::app::queue_groups::{QueueLayout, QueueRow};
 use crate::app::{
-    AppSnapshot, AppViewSnapshot, PlayerSnapshot, UiFocus, tab_configs, tab_index_for_view,
+    AppSnapshot, AppViewSnapshot, PlayerSnapshot, SearchSnapshot, UiFocus, focus_order,
+    tab_configs, tab_index_for_view,
 };
+use crate::domain::model::SongAvailability;
+use crate::features::player::playback::play_mode_label;
 use ratatui::{
     Frame,
     prelude::Rect,
-    text::{Line, Text},
-    widgets::{Block, Borders, Paragraph},
+    text::{Line, Span, Text},
+    widgets::Paragraph,
 };
 
-pub(super) fn draw_left_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
+pub(super) fn draw_left_panel(f: &mut Frame, area: Rect, app: &AppSnapshot, theme: Theme) {
     match &app.view_state {
         AppViewSnapshot::Playlists(state) => {
-            draw_playlist_list(f, area, state, app.ui_focus == UiFocus::BodyLeft);
+            draw_playlist_list(f, area, state, app.ui_focus == UiFocus::BodyLeft, theme);
         }
         AppViewSnapshot::Search(state) => {
             draw_left_info(
@@ -26,9 +31,10 @@ pub(super) fn draw_left_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
                 vec![
                     Line::from(format!("关键词: {}", app.search_input)),
                     Line::from(state.search_status.as_str()),
-                    Line::from(format!("结果: {}", state.search_results.len())),
+                    Line::from(format!("结果: {}", state.list_len())),
                 ],
-                app.ui_focus == UiFocus::BodyLeft,
+                app,
+                theme,
             );
         }
         AppViewSnapshot::Lyrics(state) => {
@@ -47,15 +53,18 @@ pub(super) fn draw_left_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
                     )),
                     Line::from(format!("offset: {}", fmt_offset(state.lyrics_offset_ms))),
                     Line::from(format!("行数: {}", state.lyrics.len())),
+                    Line::from(format!("显示: {}", state.lyrics_display.label())),
                 ],
-                app.ui_focus == UiFocus::BodyLeft,
+                app,
+                theme,
             );
         }
         AppViewSnapshot::Settings(state) => {
-            let categories = vec![("播放", 0), ("歌词", 1), ("缓存", 2), ("账号", 3)];
-            let lines: Vec<Line> = categories
-                .into_iter()
-                .map(|(label, idx)| {
+            let lines: Vec<Line> = state
+                .settings_groups
+                .iter()
+                .enumerate()
+                .map(|(idx, label)| {
                     let mark = if idx == state.settings_group_selected {
                         ">"
                     } else {
@@ -64,12 +73,21 @@ pub(super) fn draw_left_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
                     Line::from(format!("{mark}{label}"))
                 })
                 .collect();
+            draw_left_info(f, area, "设置分组", lines, app, theme);
+        }
+        AppViewSnapshot::Queue(state) => {
             draw_left_info(
                 f,
                 area,
-                "设置分组",
-                lines,
-                app.ui_focus == UiFocus::BodyLeft,
+                "队列",
+                vec![
+                    Line::from(state.queue_status.as_str()),
+                    Line::from(format!("模式: {}", play_mode_label(app.player.play_mode))),
+                    Line::from("Shift+↑↓ 调整顺序"),
+                    Line::from("d 移除 · Enter 播放"),
+                ],
+                app,
+                theme,
             );
         }
         AppViewSnapshot::Login(state) => {
@@ -82,27 +100,39 @@ pub(super) fn draw_left_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
                     Line::from("l 生成二维码"),
                     Line::from("c Cookie 登录"),
                 ],
-                app.ui_focus == UiFocus::BodyLeft,
+                app,
+                theme,
             );
         }
     }
 }
 
-fn draw_left_info(f: &mut Frame, area: Rect, title: &str, lines: Vec<Line>, active: bool) {
-    let style = focus_style(active);
-    let panel = Paragraph::new(Text::from(lines))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(format!("{}[2]", title))
-                .style(style),
-        )
-        .style(style);
+/// 左栏信息面板；当前页面左栏不可聚焦时变暗，标题也不显示 `[2]`
+fn draw_left_info(
+    f: &mut Frame,
+    area: Rect,
+    title: &str,
+    lines: Vec<Line>,
+    app: &AppSnapshot,
+    theme: Theme,
+) {
+    let panel = if focus_order(app.view).contains(&UiFocus::BodyLeft) {
+        let active = app.ui_focus == UiFocus::BodyLeft;
+        let style = theme.focus(active);
+        Paragraph::new(Text::from(lines))
+            .block(theme.panel(format!("{}[2]", title), active).style(style))
+            .style(style)
+    } else {
+        let style = theme.dimmed();
+        Paragraph::new(Text::from(lines))
+            .block(theme.panel(title, false).border_style(style).style(style))
+            .style(style)
+    };
     f.render_widget(panel, area);
 }
 
 #[allow(dead_code)]
-pub(super) fn draw_nav_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
+pub(super) fn draw_nav_panel(f: &mut Frame, area: Rect, app: &AppSnapshot, theme: Theme) {
     let configs = tab_configs(app.logged_in);
     let selected = tab_index_for_view(app.view, app.logged_in).unwrap_or(0);
     let lines: Vec<Line> = configs
@@ -117,19 +147,21 @@ pub(super) fn draw_nav_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
         })
         .collect();
 
-    let style = focus_style(app.ui_focus == UiFocus::BodyLeft);
+    let active = app.ui_focus == UiFocus::BodyLeft;
+    let style = theme.focus(active);
     let panel = Paragraph::new(Text::from(lines))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("导航")
-                .style(style),
-        )
+        .block(theme.panel("导航", active).style(style))
         .style(style);
     f.render_widget(panel, area);
 }
 
-pub(super) fn draw_now_panel(f: &mut Frame, area: Rect, player: &PlayerSnapshot, focus: UiFocus) {
+pub(super) fn draw_now_panel(
+    f: &mut Frame,
+    area: Rect,
+    player: &PlayerSnapshot,
+    focus: UiFocus,
+    theme: Theme,
+) {
     let now = player.now_playing.as_deref().unwrap_or("-");
     let mode = match player.play_mode {
         crate::app::PlayMode::Sequential => "顺序",
@@ -148,24 +180,28 @@ pub(super) fn draw_now_panel(f: &mut Frame, area: Rect, player: &PlayerSnapshot,
                 "不可用"
             }
         )),
-        Line::from(format!("模式:{mode}")),
-        Line::from(format!("音量:{:.0}%", player.volume * 100.0)),
-        Line::from(format!("音质:{}", br_label(player.play_br))),
+        Line::from(if player.stop_after_current {
+            format!("模式:{mode}(播完停止)")
+        } else {
+            format!("模式:{mode}")
+        }),
+        Line::from(if player.muted {
+            format!("音量:静音({:.0}%)", player.volume * 100.0)
+        } else {
+            format!("音量:{:.0}%", player.volume * 100.0)
+        }),
+        Line::from(vec![Span::raw("音质:"), quality_span(player, theme)]),
     ];
 
-    let style = focus_style(focus == UiFocus::BodyRight);
+    let active = focus == UiFocus::BodyRight;
+    let style = theme.focus(active);
     let panel = Paragraph::new(Text::from(lines))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Now[4]")
-                .style(style),
-        )
+        .block(theme.panel("Now[4]", active).style(style))
         .style(style);
     f.render_widget(panel, area);
 }
 
-pub(super) fn draw_context_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
+pub(super) fn draw_context_panel(f: &mut Frame, area: Rect, app: &AppSnapshot, theme: Theme) {
     let inner_height = area.height.saturating_sub(2) as usize;
     let (title, lines) = match &app.view_state {
         AppViewSnapshot::Login(state) => (
@@ -201,35 +237,61 @@ pub(super) fn draw_context_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
                 if let Some(p) = state.playlists.get(state.playlists_selected) {
                     lines.push(Line::from(format!("歌单: {}", p.name)));
                     lines.push(Line::from(format!("曲目: {}", p.track_count)));
+                    if let Some(at) = p.updated_at_ms {
+                        lines.push(Line::from(format!("更新: {}", fmt_relative_now(at))));
+                    }
                 }
             } else if let Some(s) = state.playlist_tracks.get(state.playlist_tracks_selected) {
-                lines.push(Line::from(format!("歌曲: {}", s.name)));
+                lines.push(Line::from(format!(
+                    "歌曲: {}",
+                    s.display_name(app.song_name_mode)
+                )));
                 lines.push(Line::from(format!("艺人: {}", s.artists)));
+                lines.extend(failure_line(app, s.id));
             }
             let queue_max_lines = inner_height.saturating_sub(lines.len());
-            lines.extend(queue_preview_lines(app, queue_max_lines));
+            lines.extend(queue_preview_lines(app, queue_max_lines, theme));
             ("歌单", lines)
         }
         AppViewSnapshot::Search(state) => {
             let mut lines = vec![
                 Line::from(format!("关键词: {}", app.search_input)),
                 Line::from(state.search_status.as_str()),
-                Line::from(format!("结果: {}", state.search_results.len())),
+                Line::from(format!("结果: {}", state.list_len())),
                 Line::from(format!(
                     "选中: {}",
-                    if state.search_results.is_empty() {
+                    if state.list_len() == 0 {
                         0
                     } else {
                         state.search_selected + 1
                     }
                 )),
             ];
-            if let Some(s) = state.search_results.get(state.search_selected) {
-                lines.push(Line::from(format!("歌曲: {}", s.name)));
+            if state.showing_albums() {
+                if let Some(a) = state.search_albums.get(state.search_selected) {
+                    lines.push(Line::from(format!("专辑: {}", a.name)));
+                    lines.push(Line::from(format!("艺人: {}", a.artists)));
+                    lines.push(Line::from(format!("曲目: {} 首", a.track_count)));
+                }
+            } else if state.showing_artists() {
+                if let Some(a) = state.search_artists.get(state.search_selected) {
+                    lines.push(Line::from(format!("歌手: {}", a.name)));
+                    lines.push(Line::from(format!("单曲: {} 首", a.music_size)));
+                    lines.push(Line::from(format!("专辑: {} 张", a.album_size)));
+                }
+            } else if let Some(s) = state.search_results.get(state.search_selected) {
+                lines.push(Line::from(format!(
+                    "歌曲: {}",
+                    s.display_name(app.song_name_mode)
+                )));
                 lines.push(Line::from(format!("艺人: {}", s.artists)));
+                lines.extend(failure_line(app, s.id));
+                if matches!(app.ui_focus, UiFocus::BodyCenter | UiFocus::BodyRight) {
+                    lines.extend(search_preview_lines(state));
+                }
             }
             let queue_max_lines = inner_height.saturating_sub(lines.len());
-            lines.extend(queue_preview_lines(app, queue_max_lines));
+            lines.extend(queue_preview_lines(app, queue_max_lines, theme));
             ("搜索", lines)
         }
         AppViewSnapshot::Lyrics(state) => {
@@ -248,11 +310,8 @@ pub(super) fn draw_context_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
             ];
             if !state.lyrics.is_empty() {
                 let (elapsed_ms, _) = playback_time_ms(&app.player);
-                let idx = current_lyric_index(
-                    &state.lyrics,
-                    apply_lyrics_offset(elapsed_ms, state.lyrics_offset_ms),
-                )
-                .unwrap_or(0);
+                let idx =
+                    lyric_index_at(&state.lyrics, elapsed_ms, state.lyrics_offset_ms).unwrap_or(0);
                 if let Some(line) = state.lyrics.get(idx) {
                     lines.push(Line::from(format!("当前: {}", line.text)));
                 }
@@ -279,40 +338,166 @@ pub(super) fn draw_context_panel(f: &mut Frame, area: Rect, app: &AppSnapshot) {
                 Line::from(format!("淡入淡出: {}ms", state.crossfade_ms)),
             ],
         ),
+        AppViewSnapshot::Queue(state) => {
+            let mut lines = vec![Line::from(state.queue_status.as_str())];
+            if app.queue_no_fade {
+                lines.push(Line::from("此队列禁用淡入淡出"));
+            }
+            let selected = app.queue_selected.or(app.queue_pos);
+            if let Some(s) = selected.and_then(|pos| app.queue.get(pos)) {
+                lines.push(Line::from(format!(
+                    "歌曲: {}",
+                    s.display_name(app.song_name_mode)
+                )));
+                lines.push(Line::from(format!("艺人: {}", s.artists)));
+                if !s.album.is_empty() {
+                    lines.push(Line::from(format!("专辑: {}", s.album)));
+                }
+                lines.extend(failure_line(app, s.id));
+            }
+            ("队列", lines)
+        }
     };
 
-    let style = focus_style(app.ui_focus == UiFocus::BodyRight);
+    let active = app.ui_focus == UiFocus::BodyRight;
+    let style = theme.focus(active);
     let panel = Paragraph::new(Text::from(lines))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(format!("{}[4]", title))
-                .style(style),
-        )
+        .block(theme.panel(format!("{}[4]", title), active).style(style))
         .style(style);
     f.render_widget(panel, area);
 }
 
-fn queue_preview_lines(app: &AppSnapshot, max_lines: usize) -> Vec<Line<'_>> {
+/// 选中歌曲的播放失败记录（本次运行内没有失败时为空）
+fn failure_line(app: &AppSnapshot, song_id: i64) -> Option<Line<'static>> {
+    let f = app.song_failures.get(song_id)?;
+    Some(Line::from(format!(
+        "失败: {} 次 · {} · {}",
+        f.count,
+        f.kind.label(),
+        fmt_ago(f.last_at.elapsed())
+    )))
+}
+
+/// 选中搜索结果的详情预览（停留片刻后加载）
+fn search_preview_lines(state: &SearchSnapshot) -> Vec<Line<'static>> {
+    let Some(p) = &state.preview else {
+        return vec![Line::from("详情: 加载中…")];
+    };
+    let availability = match p.availability {
+        SongAvailability::Free => "可播放",
+        SongAvailability::Vip => "VIP",
+        SongAvailability::Paid => "需购买专辑",
+        SongAvailability::Unavailable => "无版权",
+    };
+    let cached = match state.preview_cached {
+        Some(true) => " · 已缓存",
+        _ => "",
+    };
+    vec![
+        Line::from(format!(
+            "专辑: {}",
+            if p.album.is_empty() { "-" } else { &p.album }
+        )),
+        Line::from(format!(
+            "时长: {} | 发行: {}",
+            p.duration_ms.map_or_else(|| "--:--".to_owned(), fmt_mmss),
+            p.publish_year
+                .map_or_else(|| "未知".to_owned(), |y| y.to_string())
+        )),
+        Line::from(format!("状态: {availability}{cached}")),
+    ]
+}
+
+fn queue_preview_lines(app: &AppSnapshot, max_lines: usize, theme: Theme) -> Vec<Line<'_>> {
     if max_lines == 0 {
         return Vec::new();
     }
     if app.queue.is_empty() {
-        return vec![Line::from("队列: 空")];
+        return vec![Line::from(if app.fm_active {
+            "私人FM: 加载中"
+        } else {
+            "队列: 空"
+        })];
     }
 
     let total = app.queue.len();
-    let start = app.queue_pos.unwrap_or(0).min(total.saturating_sub(1));
+    let playing = app.queue_pos.unwrap_or(0).min(total.saturating_sub(1));
     let mut lines = Vec::with_capacity(max_lines);
-    lines.push(Line::from(format!("队列:{}/{}", start + 1, total)));
-    let max_songs = max_lines.saturating_sub(1);
-    for (i, song) in app.queue.iter().skip(start).take(max_songs).enumerate() {
-        let idx = start + i + 1;
-        let marker = if i == 0 { ">" } else { " " };
-        lines.push(Line::from(format!(
-            "{marker}{}.{}-{}",
-            idx, song.name, song.artists
-        )));
+    let no_fade = if app.queue_no_fade {
+        " 无淡入淡出"
+    } else {
+        ""
+    };
+    let label = if app.fm_active { "私人FM" } else { "队列" };
+    lines.push(Line::from(format!(
+        "{label}:{}/{}{no_fade}",
+        playing + 1,
+        total
+    )));
+
+    // 右栏获得焦点后显示选中项，列表从选中项开始；否则从正在播放的歌曲开始
+    let selected = (app.ui_focus == UiFocus::BodyRight)
+        .then_some(app.queue_selected)
+        .flatten()
+        .map(|pos| pos.min(total - 1));
+    let anchor = selected.unwrap_or(playing);
+    let layout = QueueLayout::new(&app.queue_groups, Some(playing));
+    let grouped = !app.queue_groups.is_empty();
+    let max_rows = max_lines.saturating_sub(1);
+    let (rows, start): (Vec<QueueRow>, usize) = if grouped {
+        let anchor_row = layout.row_of(anchor).unwrap_or(0);
+        // 尽量从所在分组的标题开始显示
+        let header_row = layout
+            .group_of(anchor)
+            .and_then(|g| layout.rows().iter().position(|&r| r == QueueRow::Header(g)))
+            .unwrap_or(anchor_row);
+        let start = if anchor_row - header_row < max_rows {
+            header_row
+        } else {
+            anchor_row
+        };
+        (layout.rows().to_vec(), start)
+    } else {
+        ((0..total).map(QueueRow::Entry).collect(), anchor)
+    };
+    let selected_row = selected.and_then(|pos| {
+        if grouped {
+            layout.row_of(pos)
+        } else {
+            Some(pos)
+        }
+    });
+
+    for (row_idx, row) in rows.iter().enumerate().skip(start).take(max_rows) {
+        let line = match *row {
+            QueueRow::Header(g) => {
+                let group = &app.queue_groups[g];
+                let fold = if group.collapsed { "▸" } else { "▾" };
+                Line::from(format!("{fold} {}", group.header()))
+            }
+            QueueRow::Entry(pos) => {
+                let Some(song) = app.queue.get(pos) else {
+                    continue;
+                };
+                let marker = if pos == playing {
+                    theme.playing_mark()
+                } else {
+                    " "
+                };
+                Line::from(format!(
+                    "{marker}{}.{}-{}{}",
+                    pos + 1,
+                    song.display_name(app.song_name_mode),
+                    song.artists,
+                    failure_mark(&app.song_failures, song.id)
+                ))
+            }
+        };
+        if selected_row == Some(row_idx) {
+            lines.push(line.style(theme.highlight()));
+        } else {
+            lines.push(line);
+        }
     }
     lines
 }
@@ -1,17 +1,64 @@
-use super::utils::{br_label, fmt_mmss, playback_time_ms};
-use super::widgets::progress_bar_text;
+use super::styles::Theme;
+use super::utils::{fmt_mmss, lyric_index_at, playback_time_ms, quality_span, truncate_to_width};
+use super::widgets::{
+    buffered_ratio, lyric_section_starts, mark_loop_points, progress_cells, progress_spans,
+};
 use crate::app::{PlayMode, PlayerSnapshot};
 use ratatui::{
     Frame,
     prelude::Rect,
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::Paragraph,
 };
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+/// 暂停超过该时长后状态栏歌词留空
+const STATUS_LYRIC_PAUSE_GRACE: Duration = Duration::from_secs(3);
+
+/// 进度条最大宽度（格）；终端较窄时按剩余宽度收缩
+const PROGRESS_MAX_WIDTH: usize = 48;
+
+/// 状态栏要显示的当前歌词行（与歌词页高亮同一行）
+///
+/// 以下情况返回 None：设置关闭或没有歌词、专注模式隐藏歌名、未在播放、暂停超过
+/// [`STATUS_LYRIC_PAUSE_GRACE`]、当前行为空（间奏）。
+fn status_lyric(player: &PlayerSnapshot, now: Instant) -> Option<&str> {
+    if player.mask_title {
+        return None;
+    }
+    let lyrics = player.status_lyrics.as_ref()?;
+    player.play_started_at?;
+    if player.paused
+        && player
+            .play_paused_at
+            .is_none_or(|at| now.saturating_duration_since(at) > STATUS_LYRIC_PAUSE_GRACE)
+    {
+        return None;
+    }
+    let (elapsed_ms, _) = playback_time_ms(player);
+    let idx = lyric_index_at(&lyrics.lines, elapsed_ms, lyrics.offset_ms)?;
+    let text = lyrics.lines[idx].text.trim();
+    (!text.is_empty()).then_some(text)
+}
 
-pub(super) fn draw_footer(f: &mut Frame, area: Rect, player: &PlayerSnapshot, view_status: &str) {
-    let now = player.now_playing.as_deref().unwrap_or("-");
+pub(super) fn draw_footer(
+    f: &mut Frame,
+    area: Rect,
+    player: &PlayerSnapshot,
+    view_status: &str,
+    theme: Theme,
+) {
+    let now = match player.now_playing.as_deref() {
+        Some(_) if player.mask_title => "♪ 播放中",
+        Some(title) => title,
+        None => "-",
+    };
+    let up_next = match player.up_next.as_deref() {
+        Some(next) if !player.mask_title => format!(" | 下一首: {next}"),
+        _ => String::new(),
+    };
     let (elapsed_ms, total_ms) = playback_time_ms(player);
-    let progress = progress_bar_text(elapsed_ms, total_ms, 24);
     let time_text = format!(
         "{} / {}{}",
         fmt_mmss(elapsed_ms),
@@ -31,18 +78,273 @@ pub(super) fn draw_footer(f: &mut Frame, area: Rect, player: &PlayerSnapshot, vi
         "Ctrl+Left/Right Seek(缓存完成后可用)"
     };
 
+    let heart = match player.liked {
+        Some(true) => "♥ ",
+        Some(false) => "♡ ",
+        None => "",
+    };
+
+    // 倍速只在不是 1.0x 时显示
+    let speed_text = if player.play_speed == 1.0 {
+        String::new()
+    } else {
+        format!(" | 倍速: {:.2}x", player.play_speed)
+    };
+    let volume_pct = player.volume.clamp(0.0, 2.0) * 100.0;
+    let volume_text = if player.muted {
+        format!("静音({volume_pct:.0}%)")
+    } else {
+        format!("{volume_pct:.0}%")
+    };
+    let stop_text = if player.stop_after_current {
+        "(播完停止)"
+    } else {
+        ""
+    };
+    let status = format!(
+        "时间: {time_text} | 模式: {mode_text}{stop_text}{speed_text} | 音量: {volume_text} | 音质: "
+    );
+    let quality = quality_span(player, theme);
+    let progress_prefix = " | 进度: [";
+    let bar_width = usize::from(area.width)
+        .saturating_sub(status.width() + quality.width() + progress_prefix.width() + 1)
+        .min(PROGRESS_MAX_WIDTH);
+    let markers = player
+        .track_lyrics
+        .as_ref()
+        .map(|l| lyric_section_starts(&l.lines, l.offset_ms))
+        .unwrap_or_default();
+    let mut cells = progress_cells(
+        bar_width,
+        elapsed_ms,
+        total_ms,
+        &markers,
+        buffered_ratio(player.play_stream_hint.as_ref()),
+    );
+    mark_loop_points(&mut cells, total_ms, player.loop_a_ms, player.loop_b_ms);
+    let mut status_line = vec![Span::raw(status), quality, Span::raw(progress_prefix)];
+    status_line.extend(progress_spans(&cells, theme));
+    status_line.push(Span::raw("]"));
+
     let lines = vec![
-        Line::from(format!("提示:{view_status}|Now:{now}")),
-        Line::from(format!(
-            "时间: {time_text} | 模式: {mode_text} | 音量: {:.0}% | 音质: {} | {progress}",
-            (player.volume.clamp(0.0, 2.0) * 100.0),
-            br_label(player.play_br),
-        )),
-        Line::from(format!(
-            "1-4 切换页 | Tab 焦点 | q 退出 | ? 帮助 | 空格 播放/暂停 | [/] 上一首/下一首 | {seek_hint} | Alt+Up/Down 音量 | M 模式"
-        )),
+        Line::from(format!("提示:{view_status}|Now:{heart}{now}{up_next}")),
+        Line::from(status_line),
+        // 有歌词时用当前歌词行替换快捷键提示
+        match status_lyric(player, Instant::now()) {
+            Some(lyric) => Line::from(format!(
+                "♪ {}",
+                truncate_to_width(lyric, usize::from(area.width).saturating_sub(2))
+            )),
+            None => Line::from(format!(
+                "1-4 切换页 | Tab 焦点 | q 退出 | ? 帮助 | 空格 播放/暂停 | [/] 上一首/下一首 | {seek_hint} | Alt+Up/Down 音量 | Alt+M 静音 | M 模式 | f 收藏"
+            )),
+        },
     ];
 
     let footer = Paragraph::new(Text::from(lines));
     f.render_widget(footer, area);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, AppSnapshot};
+    use crate::domain::model::LyricLine;
+
+    fn playing_app(started_secs_ago: u64) -> (App, Instant) {
+        let now = Instant::now();
+        let mut app = App {
+            play_song_id: Some(1),
+            lyrics_song_id: Some(1),
+            play_started_at: Some(now - Duration::from_secs(started_secs_ago)),
+            ..App::default()
+        };
+        app.lyrics = vec![
+            LyricLine {
+                time_ms: 1_000,
+                text: "第一句".to_owned(),
+                translation: None,
+                roman: None,
+            },
+            LyricLine {
+                time_ms: 5_000,
+                text: "  ".to_owned(),
+                translation: None,
+                roman: None,
+            },
+            LyricLine {
+                time_ms: 9_000,
+                text: "第二句".to_owned(),
+                translation: None,
+                roman: None,
+            },
+        ]
+        .into();
+        (app, now)
+    }
+
+    fn lyric_of(app: &App, now: Instant) -> Option<String> {
+        let snapshot = AppSnapshot::from_app(app);
+        status_lyric(&snapshot.player, now).map(str::to_owned)
+    }
+
+    #[test]
+    fn shows_line_highlighted_by_lyrics_view() {
+        let (app, now) = playing_app(2);
+        assert_eq!(lyric_of(&app, now).as_deref(), Some("第一句"));
+
+        // 空行（间奏）留空
+        let (app, now) = playing_app(6);
+        assert_eq!(lyric_of(&app, now), None);
+
+        // offset 与歌词页一致
+        let (mut app, now) = playing_app(6);
+        app.lyrics_offset_ms = 4_000;
+        assert_eq!(lyric_of(&app, now).as_deref(), Some("第二句"));
+    }
+
+    #[test]
+    fn blank_when_disabled_stale_or_paused_too_long() {
+        let (mut app, now) = playing_app(2);
+        app.status_lyrics = false;
+        assert_eq!(lyric_of(&app, now), None);
+
+        // 歌词属于上一首歌
+        let (mut app, now) = playing_app(2);
+        app.lyrics_song_id = Some(2);
+        assert_eq!(lyric_of(&app, now), None);
+
+        let (mut app, now) = playing_app(2);
+        app.paused = true;
+        app.play_paused_at = Some(now - Duration::from_secs(1));
+        assert_eq!(lyric_of(&app, now).as_deref(), Some("第一句"));
+        app.play_paused_at = Some(now - Duration::from_secs(4));
+        assert_eq!(lyric_of(&app, now), None);
+
+        let (mut app, now) = playing_app(2);
+        app.play_started_at = None;
+        assert_eq!(lyric_of(&app, now), None);
+    }
+
+    #[test]
+    fn focus_mode_masks_title_and_lyric_when_enabled() {
+        let (mut app, now) = playing_app(2);
+        app.now_playing = Some("晴天 - 周杰伦".to_owned());
+        let render = |app: &App| {
+            let snapshot = AppSnapshot::from_app(app);
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(120, 3))
+                .expect("terminal");
+            terminal
+                .draw(|f| draw_footer(f, f.area(), &snapshot.player, "", Theme::Color))
+                .expect("draw");
+            let buffer = terminal.backend().buffer().clone();
+            buffer
+                .content()
+                .iter()
+                .map(|c| c.symbol())
+                .collect::<String>()
+        };
+
+        assert!(render(&app).contains("晴"));
+
+        // 只开专注模式：歌名照常显示
+        app.focus_mode = true;
+        assert!(render(&app).contains("晴"));
+        assert_eq!(lyric_of(&app, now).as_deref(), Some("第一句"));
+
+        app.focus_mask_titles = true;
+        let text = render(&app);
+        assert!(!text.contains("晴"));
+        assert!(text.contains("Now:♪"));
+        assert_eq!(lyric_of(&app, now), None);
+    }
+
+    #[test]
+    fn shows_speed_only_when_not_normal() {
+        let (mut app, _) = playing_app(2);
+        let render = |app: &App| {
+            let snapshot = AppSnapshot::from_app(app);
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 3))
+                .expect("terminal");
+            terminal
+                .draw(|f| draw_footer(f, f.area(), &snapshot.player, "", Theme::Color))
+                .expect("draw");
+            let buffer = terminal.backend().buffer().clone();
+            buffer
+                .content()
+                .iter()
+                .map(|c| c.symbol())
+                .collect::<String>()
+        };
+
+        assert!(!render(&app).contains("x |"));
+        app.play_speed = 1.25;
+        assert!(render(&app).contains("1.25x"));
+    }
+
+    #[test]
+    fn shows_mute_next_to_remembered_volume() {
+        let (mut app, _) = playing_app(2);
+        app.volume = 0.8;
+        let render = |app: &App| {
+            let snapshot = AppSnapshot::from_app(app);
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(160, 3))
+                .expect("terminal");
+            terminal
+                .draw(|f| draw_footer(f, f.area(), &snapshot.player, "", Theme::Color))
+                .expect("draw");
+            let buffer = terminal.backend().buffer().clone();
+            buffer
+                .content()
+                .iter()
+                .map(|c| c.symbol())
+                .collect::<String>()
+        };
+
+        let text = render(&app);
+        assert!(text.contains("80%") && !text.contains("静"));
+        app.muted = true;
+        let text = render(&app);
+        assert!(text.contains("静") && text.contains("(80%)"));
+    }
+
+    #[test]
+    fn progress_bar_marks_lyric_sections_and_shrinks_to_fit() {
+        let (mut app, _) = playing_app(2);
+        app.play_total_ms = Some(100_000);
+        app.lyrics = vec![LyricLine {
+            time_ms: 50_000,
+            text: "副歌".to_owned(),
+            translation: None,
+            roman: None,
+        }]
+        .into();
+        let snapshot = AppSnapshot::from_app(&app);
+        let render = |width| {
+            let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(width, 3))
+                .expect("terminal");
+            terminal
+                .draw(|f| draw_footer(f, f.area(), &snapshot.player, "", Theme::Color))
+                .expect("draw");
+            let buffer = terminal.backend().buffer().clone();
+            (0..width)
+                .map(|x| buffer[(x, 1)].symbol().to_owned())
+                .collect::<String>()
+        };
+
+        let wide = render(140);
+        let bar = &wide[wide.find('[').expect("进度条") + 1..wide.rfind(']').expect("进度条")];
+        assert_eq!(bar.chars().count(), PROGRESS_MAX_WIDTH);
+        assert_eq!(
+            bar.find('|'),
+            Some(PROGRESS_MAX_WIDTH / 2),
+            "50s 处的段落标记"
+        );
+
+        let narrow = render(100);
+        let bar =
+            &narrow[narrow.find('[').expect("进度条") + 1..narrow.rfind(']').expect("进度条")];
+        assert!(bar.chars().count() < PROGRESS_MAX_WIDTH);
+        assert!(bar.contains('|'));
+    }
+}
@@ -1,20 +1,28 @@
-use super::styles::focus_style;
-use crate::app::{PlaylistMode, PlaylistsSnapshot};
+use super::styles::Theme;
+use super::utils::{failure_mark, fmt_relative_now};
+use crate::app::{
+    PlaylistFilter, PlaylistMode, PlaylistStats, PlaylistsSnapshot, SongFailures, SongNameMode,
+    TrackOrder,
+};
 use ratatui::{
     Frame,
+    layout::{Constraint, Direction, Layout},
     prelude::Rect,
-    style::{Color, Style},
-    text::{Line, Text},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+    widgets::{List, ListItem, Paragraph, Wrap},
 };
 
+/// 歌曲模式下列表上方的标题区行数（歌单名 + 统计）；鼠标点击换算行号时需扣除
+pub(super) const TRACKS_HEADER_HEIGHT: u16 = 2;
+
 pub(super) fn draw_playlist_list(
     f: &mut Frame,
     area: Rect,
     state: &PlaylistsSnapshot,
     active: bool,
+    theme: Theme,
 ) {
-    let border = focus_style(active);
     let items: Vec<ListItem> = state
         .playlists
         .iter()
@@ -22,27 +30,34 @@ pub(super) fn draw_playlist_list(
         .map(|(i, p)| {
             let mark = if p.special_type == 5 || p.name.contains("我喜欢") {
                 " ♥"
+            } else if p.subscribed {
+                " ☆"
             } else {
                 ""
             };
-            ListItem::new(Line::from(format!(
-                "{}. {}({}首){}",
-                i + 1,
-                p.name,
-                p.track_count,
-                mark
-            )))
+            let count = match state.playlist_missing.get(&p.id) {
+                Some(&missing) => track_count_label(p.track_count.max(0) as usize, missing),
+                None => format!("{}首", p.track_count),
+            };
+            let mut line = Line::from(format!("{}. {}({}){}", i + 1, p.name, count, mark));
+            if let Some(at) = p.updated_at_ms {
+                line.push_span(Span::styled(
+                    format!(" · {}", fmt_relative_now(at)),
+                    theme.fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(line)
         })
         .collect();
 
+    let title = if state.playlist_filter == PlaylistFilter::All {
+        "歌单[2]".to_owned()
+    } else {
+        format!("歌单[2] · {}", state.playlist_filter.label())
+    };
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("歌单[2]")
-                .border_style(border),
-        )
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .block(theme.panel(title, active))
+        .highlight_style(theme.highlight());
 
     let mut st = ratatui::widgets::ListState::default();
     if !state.playlists.is_empty() {
@@ -55,23 +70,116 @@ pub(super) fn draw_playlist_list(
     f.render_stateful_widget(list, area, &mut st);
 }
 
-pub(super) fn draw_playlists(f: &mut Frame, area: Rect, state: &PlaylistsSnapshot, active: bool) {
-    let border = focus_style(active);
+pub(super) fn draw_playlists(
+    f: &mut Frame,
+    area: Rect,
+    state: &PlaylistsSnapshot,
+    failures: &SongFailures,
+    name_mode: SongNameMode,
+    active: bool,
+    theme: Theme,
+) {
     if matches!(state.playlist_mode, PlaylistMode::Tracks) {
         let items: Vec<ListItem> = state
             .playlist_tracks
             .iter()
             .enumerate()
-            .map(|(i, s)| ListItem::new(Line::from(format!("{}. {}-{}", i + 1, s.name, s.artists))))
+            .map(|(i, s)| {
+                // 已在音频缓存中的歌曲（可离线播放）
+                let cached = if state.playlist_tracks_cached.contains(&s.id) {
+                    " ⬇"
+                } else {
+                    ""
+                };
+                let playing = state.play_song_id == Some(s.id);
+                let line = Line::from(format!(
+                    "{}{}. {}-{}{cached}{}",
+                    if playing { "▶ " } else { "" },
+                    // 歌单内搜索时仍显示在完整歌单中的序号
+                    state.playlist_tracks_index.get(i).copied().unwrap_or(i) + 1,
+                    s.display_name(name_mode),
+                    s.artists,
+                    failure_mark(failures, s.id)
+                ));
+                ListItem::new(if playing {
+                    line.style(theme.playing_row())
+                } else {
+                    line
+                })
+            })
             .collect();
-        let list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("歌曲[3](↑↓选择 p 播放 b 返回)")
-                    .border_style(border),
+        let title = if state.playlist_tracks_missing > 0 {
+            format!(
+                "歌曲[3] {}(↑↓选择 p 播放 / 搜索 z 定位 t 排序 b 返回)",
+                track_count_label(
+                    state.playlist_tracks_total + state.playlist_tracks_missing,
+                    state.playlist_tracks_missing
+                )
             )
-            .highlight_style(Style::default().fg(Color::Yellow));
+        } else {
+            "歌曲[3](↑↓选择 p 播放 / 搜索 z 定位 t 排序 b 返回)".to_owned()
+        };
+        let block = theme.panel(title, active);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(TRACKS_HEADER_HEIGHT), Constraint::Min(0)])
+            .split(inner);
+
+        let mut title_line = Line::styled(
+            state.playlist_tracks_name.as_str(),
+            Style::default().add_modifier(Modifier::BOLD),
+        );
+        if state.playlist_tracks_order != TrackOrder::Canonical {
+            title_line.push_span(Span::styled(
+                format!("  排序: {}", state.playlist_tracks_order.label()),
+                theme.fg(Color::Cyan),
+            ));
+        }
+        let freshness = freshness_label(
+            state.playlist_tracks_updated_at,
+            state.playlist_tracks_cached_at,
+        );
+        if !freshness.is_empty() {
+            title_line.push_span(Span::styled(
+                format!("  {freshness}"),
+                theme.fg(Color::DarkGray),
+            ));
+        }
+        // 歌单内搜索时第二行显示关键词（代替统计），不改变标题区高度
+        let second_line = if state.playlist_filter_editing {
+            Line::styled(
+                format!(
+                    "搜索: {}▏（Enter 确认 Esc 清除）",
+                    state.playlist_filter_input
+                ),
+                theme.fg(Color::Yellow),
+            )
+        } else if !state.playlist_filter_input.is_empty() {
+            Line::styled(
+                format!(
+                    "搜索: {}（{}/{} 首，/ 修改 Esc 清除）",
+                    state.playlist_filter_input,
+                    state.playlist_tracks.len(),
+                    state.playlist_tracks_total
+                ),
+                theme.fg(Color::Yellow),
+            )
+        } else {
+            Line::styled(
+                state
+                    .playlist_tracks_stats
+                    .as_ref()
+                    .map(stats_label)
+                    .unwrap_or_default(),
+                theme.fg(Color::DarkGray),
+            )
+        };
+        let header = Paragraph::new(Text::from(vec![title_line, second_line]));
+        f.render_widget(header, chunks[0]);
+
+        let list = List::new(items).highlight_style(theme.highlight());
 
         let mut st = ratatui::widgets::ListState::default();
         if !state.playlist_tracks.is_empty() {
@@ -81,11 +189,22 @@ pub(super) fn draw_playlists(f: &mut Frame, area: Rect, state: &PlaylistsSnapsho
                     .min(state.playlist_tracks.len().saturating_sub(1)),
             ));
         }
-        f.render_stateful_widget(list, area, &mut st);
+        f.render_stateful_widget(list, chunks[1], &mut st);
     } else {
         let selected = state.playlists.get(state.playlists_selected);
         let hint = if let Some(p) = selected {
-            format!("选中:{}({}首)\n回车打开歌单", p.name, p.track_count)
+            let freshness = freshness_label(
+                p.updated_at_ms,
+                state.playlist_cached_at.get(&p.id).copied(),
+            );
+            if freshness.is_empty() {
+                format!("选中:{}({}首)\n回车打开歌单", p.name, p.track_count)
+            } else {
+                format!(
+                    "选中:{}({}首)\n{freshness}\n回车打开歌单",
+                    p.name, p.track_count
+                )
+            }
         } else {
             "暂无歌单，等待登录后加载".to_owned()
         };
@@ -95,13 +214,59 @@ pub(super) fn draw_playlists(f: &mut Frame, area: Rect, state: &PlaylistsSnapsho
             Line::from(hint),
         ]);
         let panel = Paragraph::new(text)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title("歌单详情[3]")
-                    .border_style(border),
-            )
+            .block(theme.panel("歌单详情[3]", active))
             .wrap(Wrap { trim: false });
         f.render_widget(panel, area);
     }
 }
+
+/// 歌单新旧，如 "更新于 3 天前 · 预加载于 刚刚"；两者都未知时为空
+fn freshness_label(updated_at: Option<i64>, cached_at: Option<i64>) -> String {
+    let mut parts = Vec::new();
+    if let Some(at) = updated_at {
+        parts.push(format!("更新于 {}", fmt_relative_now(at)));
+    }
+    if let Some(at) = cached_at {
+        parts.push(format!("预加载于 {}", fmt_relative_now(at)));
+    }
+    parts.join(" · ")
+}
+
+/// 歌曲数展示，如 "312 首，5 首不可用"
+fn track_count_label(total: usize, missing: usize) -> String {
+    if missing > 0 {
+        format!("{total} 首，{missing} 首不可用")
+    } else {
+        format!("{total}首")
+    }
+}
+
+/// 歌单统计行，如 "1小时23分 · 12 位歌手 · 周杰伦 10 / 林俊杰 5 / 陈奕迅 3"
+fn stats_label(stats: &PlaylistStats) -> String {
+    let mut parts = vec![duration_label(stats.total_ms)];
+    if stats.unknown_duration > 0 {
+        parts[0].push_str(&format!("（{} 首时长未知）", stats.unknown_duration));
+    }
+    if stats.artist_count > 0 {
+        parts.push(format!("{} 位歌手", stats.artist_count));
+        parts.push(
+            stats
+                .top_artists
+                .iter()
+                .map(|(name, count)| format!("{name} {count}"))
+                .collect::<Vec<_>>()
+                .join(" / "),
+        );
+    }
+    parts.join(" · ")
+}
+
+/// 总时长，如 "1小时23分"、"45分"
+fn duration_label(ms: u64) -> String {
+    let minutes = ms / 60_000;
+    if minutes >= 60 {
+        format!("{}小时{}分", minutes / 60, minutes % 60)
+    } else {
+        format!("{minutes}分")
+    }
+}
@@ -0,0 +1,56 @@
+use super::styles::Theme;
+use super::utils::{failure_mark, fmt_mmss};
+use super::widgets::list_state;
+use crate::app::AppSnapshot;
+use ratatui::{
+    Frame,
+    prelude::Rect,
+    text::Line,
+    widgets::{List, ListItem, Paragraph},
+};
+
+const TITLE: &str = "队列[3]（↑↓选择 Shift+↑↓调整顺序 d 移除 Enter 播放）";
+
+pub(super) fn draw_queue(f: &mut Frame, area: Rect, app: &AppSnapshot, active: bool, theme: Theme) {
+    if app.queue.is_empty() {
+        let empty = Paragraph::new("队列为空").block(theme.panel(TITLE, active));
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let total = app.queue.len();
+    let items: Vec<ListItem> = app
+        .queue
+        .iter()
+        .enumerate()
+        .map(|(pos, song)| {
+            let marker = if app.queue_pos == Some(pos) {
+                theme.playing_mark()
+            } else {
+                " "
+            };
+            let duration = song
+                .duration_ms
+                .map(|ms| format!(" [{}]", fmt_mmss(ms)))
+                .unwrap_or_default();
+            ListItem::new(Line::from(format!(
+                "{marker}{}. {} - {}{duration}{}",
+                pos + 1,
+                song.display_name(app.song_name_mode),
+                song.artists,
+                failure_mark(&app.song_failures, song.id)
+            )))
+        })
+        .collect();
+
+    // 未选择时选中正在播放的歌曲
+    let selected = app
+        .queue_selected
+        .or(app.queue_pos)
+        .unwrap_or(0)
+        .min(total - 1);
+    let list = List::new(items)
+        .block(theme.panel(TITLE, active))
+        .highlight_style(theme.highlight());
+    f.render_stateful_widget(list, area, &mut list_state(selected));
+}
@@ -1,33 +1,133 @@
-use super::styles::focus_style;
+use super::styles::Theme;
+use super::utils::failure_mark;
 use super::widgets::list_state;
-use crate::app::SearchSnapshot;
+use crate::app::{SearchOpened, SearchSnapshot, SongFailures, SongNameMode};
 use ratatui::{
     Frame,
     prelude::Rect,
-    style::{Color, Style},
     text::Line,
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{List, ListItem},
 };
 
-pub(super) fn draw_search(f: &mut Frame, area: Rect, state: &SearchSnapshot, active: bool) {
-    let border = focus_style(active);
+pub(super) fn draw_search(
+    f: &mut Frame,
+    area: Rect,
+    state: &SearchSnapshot,
+    failures: &SongFailures,
+    name_mode: SongNameMode,
+    active: bool,
+    theme: Theme,
+) {
+    if state.showing_albums() {
+        let items = state
+            .search_albums
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let line = format!(
+                    "{}. {} - {}（{} 首）",
+                    i + 1,
+                    a.name,
+                    a.artists,
+                    a.track_count
+                );
+                ListItem::new(Line::from(line))
+            })
+            .collect::<Vec<_>>();
+        draw_list(
+            f,
+            area,
+            items,
+            "专辑[3](↑↓选择 Enter 打开)",
+            active,
+            theme,
+            state,
+        );
+        return;
+    }
+    if state.showing_artists() {
+        let items = state
+            .search_artists
+            .iter()
+            .enumerate()
+            .map(|(i, a)| {
+                let line = format!(
+                    "{}. {}（{} 首 · {} 张专辑）",
+                    i + 1,
+                    a.name,
+                    a.music_size,
+                    a.album_size
+                );
+                ListItem::new(Line::from(line))
+            })
+            .collect::<Vec<_>>();
+        draw_list(
+            f,
+            area,
+            items,
+            "歌手[3](↑↓选择 Enter 打开)",
+            active,
+            theme,
+            state,
+        );
+        return;
+    }
+
     let items = state
         .search_results
         .iter()
         .enumerate()
         .map(|(i, s)| {
-            let line = format!("{}. {}-{}({})", s.id, s.name, s.artists, i + 1);
-            ListItem::new(Line::from(line))
+            let playing = state.play_song_id == Some(s.id);
+            let line = Line::from(format!(
+                "{}{}{}. {}-{}({}){}",
+                if playing { "▶ " } else { "" },
+                if state.search_marked.contains(&s.id) {
+                    "✓ "
+                } else {
+                    ""
+                },
+                s.id,
+                s.display_name(name_mode),
+                s.artists,
+                i + 1,
+                failure_mark(failures, s.id)
+            ));
+            ListItem::new(if playing {
+                line.style(theme.playing_row())
+            } else {
+                line
+            })
         })
         .collect::<Vec<_>>();
 
+    let mut title = match &state.search_opened {
+        Some(SearchOpened::Album(album)) => {
+            format!("专辑: {} - {}[3](p 播放 b 返回)", album.name, album.artists)
+        }
+        Some(SearchOpened::Artist(artist)) => format!("歌手: {}[3](p 播放 b 返回)", artist.name),
+        None => "结果[3](↑↓选择)".to_owned(),
+    };
+    if !state.search_marked.is_empty() {
+        title.push_str(&format!(
+            " 已标记 {} 首(A 添加到歌单)",
+            state.search_marked.len()
+        ));
+    }
+    draw_list(f, area, items, &title, active, theme, state);
+}
+
+fn draw_list(
+    f: &mut Frame,
+    area: Rect,
+    items: Vec<ListItem>,
+    title: &str,
+    active: bool,
+    theme: Theme,
+    state: &SearchSnapshot,
+) {
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("结果[3](↑↓选择)")
-                .border_style(border),
-        )
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .block(theme.panel(title, active))
+        .highlight_style(theme.highlight());
     f.render_stateful_widget(list, area, &mut list_state(state.search_selected));
 }
@@ -1,80 +1,39 @@
-use super::styles::focus_style;
-use super::utils::{br_label, fmt_offset, play_mode_label};
+use super::styles::Theme;
 use super::widgets::list_state;
-use crate::app::{PlayerSnapshot, SettingsSnapshot};
+use crate::app::SettingsSnapshot;
 use ratatui::{
     Frame,
     prelude::Rect,
-    style::{Color, Style},
     text::Line,
-    widgets::{Block, Borders, List, ListItem},
+    widgets::{List, ListItem},
 };
 
 pub(super) fn draw_settings(
     f: &mut Frame,
     area: Rect,
     state: &SettingsSnapshot,
-    player: &PlayerSnapshot,
-    logged_in: bool,
     active: bool,
+    theme: Theme,
 ) {
-    let border = focus_style(active);
+    // 设置项文本由注册表生成（见 features::settings::registry）
+    let items: Vec<ListItem> = state
+        .settings_items
+        .iter()
+        .map(|label| ListItem::new(Line::from(label.as_str())))
+        .collect();
 
-    // 根据分组生成设置项
-    let items = match state.settings_group_selected {
-        0 => vec![
-            // 播放
-            ListItem::new(Line::from(format!("音质: {}", br_label(player.play_br)))),
-            ListItem::new(Line::from(format!("音量: {:.0}%", player.volume * 100.0))),
-            ListItem::new(Line::from(format!(
-                "播放模式: {}",
-                play_mode_label(player.play_mode)
-            ))),
-        ],
-        1 => vec![
-            // 歌词
-            ListItem::new(Line::from(format!(
-                "歌词 offset: {}",
-                fmt_offset(state.lyrics_offset_ms)
-            ))),
-        ],
-        2 => vec![
-            // 缓存
-            ListItem::new(Line::from(format!(
-                "淡入淡出: {}",
-                if state.crossfade_ms == 0 {
-                    "关闭".to_owned()
-                } else {
-                    format!("{}ms", state.crossfade_ms)
-                }
-            ))),
-            ListItem::new(Line::from("清除音频缓存".to_owned())),
-        ],
-        3 => vec![
-            // 账号
-            ListItem::new(Line::from(if logged_in {
-                "退出登录".to_owned()
-            } else {
-                "退出登录（未登录）".to_owned()
-            })),
-        ],
-        _ => vec![],
-    };
-
-    let group_names = ["播放", "歌词", "缓存", "账号"];
     let title = format!(
         "设置[3]（↑↓选择 ←→调整 Enter 操作）- {}",
-        group_names[state.settings_group_selected]
+        state
+            .settings_groups
+            .get(state.settings_group_selected)
+            .copied()
+            .unwrap_or_default()
     );
 
     let list = List::new(items)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .border_style(border),
-        )
-        .highlight_style(Style::default().fg(Color::Yellow));
+        .block(theme.panel(title, active))
+        .highlight_style(theme.highlight());
 
     f.render_stateful_widget(list, area, &mut list_state(state.settings_selected));
 }
@@ -0,0 +1,288 @@
+//! 主要页面的渲染快照测试（TestBackend + 纯文本 golden 文件）
+//!
+//! 每个页面在 80x24、120x40、40x12 下单独渲染页面本身（`draw_*` 占满整个终端），
+//! 另在 120x40 下渲染完整界面（`draw_ui`，含 header/侧栏/状态栏）。
+//! 渲染结果与 `src/ui/tui/snapshots/*.txt` 逐行比较，行尾空白不计。
+//!
+//! 界面有意改动后更新快照：
+//!
+//! ```bash
+//! UPDATE_SNAPSHOTS=1 cargo test --lib ui::tui::snapshot_tests
+//! git diff src/ui/tui/snapshots   # 确认改动符合预期后一并提交
+//! ```
+
+mod fixtures;
+
+use super::login_view::draw_login;
+use super::lyrics_view::draw_lyrics;
+use super::playlists_view::draw_playlists;
+use super::queue_view::draw_queue;
+use super::search_view::draw_search;
+use super::settings_view::draw_settings;
+use super::styles::Theme;
+use super::views::draw_ui;
+use crate::app::{AppSnapshot, AppViewSnapshot};
+use ratatui::style::{Color, Modifier};
+use ratatui::{Frame, Terminal, backend::TestBackend, buffer::Buffer};
+use std::path::PathBuf;
+use unicode_width::UnicodeWidthStr;
+
+const VIEW_SIZES: [(u16, u16); 3] = [(80, 24), (120, 40), (40, 12)];
+const FULL_SIZE: (u16, u16) = (120, 40);
+
+/// 只渲染当前页面本身，占满整个终端
+fn draw_view(f: &mut Frame, app: &AppSnapshot, theme: Theme) {
+    let area = f.area();
+    match &app.view_state {
+        AppViewSnapshot::Login(state) => {
+            draw_login(f, area, state, app.logged_in, !app.logged_in, theme)
+        }
+        AppViewSnapshot::Playlists(state) => draw_playlists(
+            f,
+            area,
+            state,
+            &app.song_failures,
+            app.song_name_mode,
+            true,
+            theme,
+        ),
+        AppViewSnapshot::Search(state) => draw_search(
+            f,
+            area,
+            state,
+            &app.song_failures,
+            app.song_name_mode,
+            true,
+            theme,
+        ),
+        AppViewSnapshot::Lyrics(state) => draw_lyrics(f, area, state, &app.player, true, theme),
+        AppViewSnapshot::Settings(state) => draw_settings(f, area, state, true, theme),
+        AppViewSnapshot::Queue(_) => draw_queue(f, area, app, true, theme),
+    }
+}
+
+fn render(size: (u16, u16), draw: impl FnOnce(&mut Frame)) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(size.0, size.1)).expect("terminal");
+    terminal.draw(draw).expect("draw");
+    terminal.backend().buffer().clone()
+}
+
+/// 按显示宽度输出每行文本：宽字符后被占位的单元格跳过，行尾空白与末尾空行去掉
+fn buffer_to_text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    let mut out = String::new();
+    for row in buffer.content().chunks(width) {
+        let mut line = String::new();
+        let mut skip = 0;
+        for cell in row {
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let symbol = cell.symbol();
+            line.push_str(symbol);
+            skip = symbol.width().saturating_sub(1);
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    // 末尾空行也去掉（与 end-of-file-fixer 一致）
+    let len = out.trim_end_matches('\n').len();
+    out.truncate(len);
+    out.push('\n');
+    out
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/tui/snapshots")
+        .join(format!("{name}.txt"))
+}
+
+fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(path.parent().expect("snapshot dir")).expect("create dir");
+        std::fs::write(&path, actual).expect("write snapshot");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!(
+            "缺少快照 {}；运行 UPDATE_SNAPSHOTS=1 cargo test --lib ui::tui::snapshot_tests 生成\n{actual}",
+            path.display()
+        )
+    });
+    if expected == actual {
+        return;
+    }
+
+    let mut diff = String::new();
+    let expected_lines: Vec<&str> = expected.lines().collect();
+    let actual_lines: Vec<&str> = actual.lines().collect();
+    for i in 0..expected_lines.len().max(actual_lines.len()) {
+        let (e, a) = (expected_lines.get(i), actual_lines.get(i));
+        if e != a {
+            diff.push_str(&format!(
+                "{:>3} - {}\n    + {}\n",
+                i + 1,
+                e.unwrap_or(&""),
+                a.unwrap_or(&"")
+            ));
+        }
+    }
+    panic!(
+        "快照 {} 不一致（有意改动时用 UPDATE_SNAPSHOTS=1 更新）：\n{diff}\n实际渲染：\n{actual}",
+        path.display()
+    );
+}
+
+fn check_view(name: &str, app: &AppSnapshot) {
+    for size in VIEW_SIZES {
+        let buffer = render(size, |f| draw_view(f, app, Theme::Color));
+        assert_snapshot(
+            &format!("{name}_{}x{}", size.0, size.1),
+            &buffer_to_text(&buffer),
+        );
+    }
+    let buffer = render(FULL_SIZE, |f| draw_ui(f, app, Theme::Color));
+    assert_snapshot(
+        &format!("full_{name}_{}x{}", FULL_SIZE.0, FULL_SIZE.1),
+        &buffer_to_text(&buffer),
+    );
+}
+
+#[test]
+fn login_view_snapshots() {
+    check_view("login", &fixtures::login());
+}
+
+#[test]
+fn playlists_view_snapshots() {
+    check_view("playlists", &fixtures::playlists());
+    check_view("playlist_tracks", &fixtures::playlist_tracks());
+    check_view(
+        "playlist_tracks_filtered",
+        &fixtures::playlist_tracks_filtered(),
+    );
+}
+
+#[test]
+fn search_view_snapshots() {
+    check_view("search", &fixtures::search());
+}
+
+#[test]
+fn lyrics_view_snapshots() {
+    let app = fixtures::lyrics();
+    check_view("lyrics", &app);
+
+    // 纯文本快照不含颜色：另行确认高亮落在选中的歌词行上
+    let buffer = render((80, 24), |f| draw_view(f, &app, Theme::Color));
+    let text = buffer_to_text(&buffer);
+    let row = text
+        .lines()
+        .position(|line| line.contains("童年的荡秋千"))
+        .expect("highlighted line rendered");
+    let highlighted = (0..buffer.area.width)
+        .filter(|&x| buffer[(x, row as u16)].fg == Color::Yellow)
+        .count();
+    assert!(highlighted > 0, "选中的歌词行应高亮");
+}
+
+#[test]
+fn settings_view_snapshots() {
+    check_view("settings", &fixtures::settings());
+}
+
+#[test]
+fn queue_view_snapshots() {
+    check_view("queue", &fixtures::queue());
+}
+
+#[test]
+fn full_layout_below_minimum_size_shows_resize_prompt() {
+    let buffer = render((80, 24), |f| {
+        draw_ui(f, &fixtures::playlists(), Theme::Color)
+    });
+    assert_snapshot("full_resize_prompt_80x24", &buffer_to_text(&buffer));
+}
+
+/// 单色模式下没有任何单元格带颜色
+fn assert_no_color(buffer: &Buffer) {
+    let colored = buffer
+        .content()
+        .iter()
+        .filter(|c| c.fg != Color::Reset || c.bg != Color::Reset)
+        .count();
+    assert_eq!(colored, 0, "单色模式不应输出颜色");
+}
+
+/// 包含 `needle` 的行中是否有带 `modifier` 的单元格
+fn row_has_modifier(buffer: &Buffer, needle: &str, modifier: Modifier) -> bool {
+    let text = buffer_to_text(buffer);
+    let row = text
+        .lines()
+        .position(|line| line.contains(needle))
+        .unwrap_or_else(|| panic!("未渲染: {needle}"));
+    (0..buffer.area.width).any(|x| buffer[(x, row as u16)].modifier.contains(modifier))
+}
+
+#[test]
+fn monochrome_lyrics_marks_current_line_and_focus() {
+    let buffer = render(FULL_SIZE, |f| {
+        draw_ui(f, &fixtures::lyrics(), Theme::Monochrome)
+    });
+    assert_no_color(&buffer);
+
+    let text = buffer_to_text(&buffer);
+    assert!(
+        text.lines()
+            .any(|line| line.contains('▶') && line.contains("童年的荡秋千")),
+        "当前歌词行应带 ▶ 标记:\n{text}"
+    );
+    assert_eq!(
+        text.matches("[*]").count(),
+        1,
+        "只有焦点面板带 [*]:\n{text}"
+    );
+    assert!(row_has_modifier(&buffer, "童年的荡秋千", Modifier::BOLD));
+}
+
+#[test]
+fn monochrome_playlist_marks_selection_and_playing_queue_entry() {
+    let buffer = render(FULL_SIZE, |f| {
+        draw_ui(f, &fixtures::playlist_tracks_playing(), Theme::Monochrome)
+    });
+    assert_no_color(&buffer);
+
+    let text = buffer_to_text(&buffer);
+    assert!(
+        text.contains("▶2.富士山下"),
+        "队列中正在播放的一项应带 ▶:\n{text}"
+    );
+    assert!(!text.contains(">2.富士山下"));
+    assert!(
+        text.contains("▶ 2. 富士山下"),
+        "歌曲列表中正在播放的一行应带 ▶:\n{text}"
+    );
+    assert!(row_has_modifier(&buffer, "▶ 2. 富士山下", Modifier::BOLD));
+    assert!(text.contains("[*]"), "焦点面板应带 [*]:\n{text}");
+    assert!(
+        row_has_modifier(&buffer, "3. Viva La Vida", Modifier::REVERSED),
+        "选中行应反色显示"
+    );
+}
+
+#[test]
+fn monochrome_settings_and_search_use_reversed_selection() {
+    for (app, selected) in [
+        (fixtures::settings(), "状态栏歌词"),
+        (fixtures::search(), "富士山下"),
+    ] {
+        let buffer = render((80, 24), |f| draw_view(f, &app, Theme::Monochrome));
+        assert_no_color(&buffer);
+        assert!(row_has_modifier(&buffer, selected, Modifier::REVERSED));
+        assert!(buffer_to_text(&buffer).contains("[*]"));
+    }
+}
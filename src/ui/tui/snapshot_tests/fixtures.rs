@@ -0,0 +1,166 @@
+//! 快照测试用的 App 状态：直接构造 `App` 再生成 `AppSnapshot`，不依赖 core/actor
+//!
+//! 固定所有与时间相关的字段（未开始播放），保证渲染结果稳定。
+
+use crate::app::{App, AppSnapshot, PlaylistMode, PlaylistStats, UiFocus, View};
+use crate::domain::model::{LyricLine, Playlist, Song};
+use std::sync::Arc;
+
+fn songs() -> Vec<Song> {
+    vec![
+        Song::titled(1, "晴天", "周杰伦").lasting_ms(269_000),
+        Song::titled(2, "富士山下", "陈奕迅").lasting_ms(259_000),
+        Song::titled(3, "Viva La Vida", "Coldplay").lasting_ms(242_000),
+        Song::titled(4, "夜空中最亮的星（Live 版）", "逃跑计划").lasting_ms(252_000),
+        Song::titled(5, "海阔天空", "Beyond").lasting_ms(326_000),
+        Song::titled(6, "Bohemian Rhapsody", "Queen").lasting_ms(355_000),
+        Song::titled(7, "一丝不挂", "陈奕迅").lasting_ms(243_000),
+        Song::titled(8, "告白气球", "周杰伦").lasting_ms(215_000),
+    ]
+}
+
+/// 已登录、停留在指定页面的基础状态
+fn logged_in(view: View) -> App {
+    App {
+        view,
+        logged_in: true,
+        ui_focus: UiFocus::BodyCenter,
+        account_uid: Some(42),
+        account_nickname: Some("测试用户".to_owned()),
+        ..Default::default()
+    }
+}
+
+/// 未登录的登录页：尚未生成二维码（显示占位提示）
+pub(super) fn login() -> AppSnapshot {
+    AppSnapshot::from_app(&App::default())
+}
+
+/// 歌单列表：含超长中文歌单名
+pub(super) fn playlists() -> AppSnapshot {
+    AppSnapshot::from_app(&playlists_app())
+}
+
+fn playlists_app() -> App {
+    let mut app = logged_in(View::Playlists);
+    app.playlists = vec![
+        Playlist {
+            id: 1,
+            name: "测试用户喜欢的音乐".to_owned(),
+            track_count: 1024,
+            special_type: 5,
+            subscribed: false,
+            updated_at_ms: None,
+        },
+        Playlist {
+            id: 2,
+            name: "深夜循环｜那些在凌晨三点的出租车上反复听到泪流满面的华语老歌合集".to_owned(),
+            track_count: 388,
+            ..Default::default()
+        },
+        Playlist {
+            id: 3,
+            name: "Workout Mix 2024".to_owned(),
+            track_count: 57,
+            ..Default::default()
+        },
+        Playlist {
+            id: 4,
+            name: "【收藏】日系城市流行 City Pop 精选：竹内玛莉亚、山下达郎、大贯妙子".to_owned(),
+            track_count: 120,
+            subscribed: true,
+            ..Default::default()
+        },
+    ];
+    app.playlists_selected = 1;
+    app.playlists_status = "歌单: 4 个（我的 3 · 收藏 1）".to_owned();
+    app
+}
+
+/// 已打开的歌单：歌曲列表与统计头
+pub(super) fn playlist_tracks() -> AppSnapshot {
+    let mut app = playlists_app();
+    let tracks = songs();
+    app.playlist_mode = PlaylistMode::Tracks;
+    app.playlist_tracks_stats = Some(PlaylistStats::compute(&tracks));
+    app.playlist_tracks = tracks;
+    app.playlist_tracks_selected = 2;
+    app.playlists_status = "歌曲: 8 首（p 播放）".to_owned();
+    AppSnapshot::from_app(&app)
+}
+
+/// 歌单内搜索「陈奕迅」：输入框打开，选中第 2 个匹配（原第 7 首）
+pub(super) fn playlist_tracks_filtered() -> AppSnapshot {
+    let mut app = playlists_app();
+    app.playlist_mode = PlaylistMode::Tracks;
+    app.playlist_tracks = songs();
+    app.playlist_tracks_selected = 6;
+    app.playlist_filter_input = "陈奕迅".to_owned();
+    app.playlist_filter_editing = true;
+    app.playlists_status = "匹配 2/8 首（Esc 清除筛选）".to_owned();
+    AppSnapshot::from_app(&app)
+}
+
+/// 已打开的歌单，并以它为播放队列、正在播放第 2 首
+pub(super) fn playlist_tracks_playing() -> AppSnapshot {
+    let mut app = playlists_app();
+    app.playlist_mode = PlaylistMode::Tracks;
+    app.playlist_tracks = songs();
+    app.playlist_tracks_selected = 2;
+    app.play_queue.set_songs(songs(), Some(1));
+    app.play_song_id = Some(2);
+    AppSnapshot::from_app(&app)
+}
+
+/// 搜索结果：选中第 2 条，第 5 条正在播放
+pub(super) fn search() -> AppSnapshot {
+    let mut app = logged_in(View::Search);
+    app.search_input = "陈奕迅".to_owned();
+    app.search_results = songs();
+    app.search_selected = 1;
+    app.search_status = "搜索到 8 首".to_owned();
+    app.play_song_id = Some(5);
+    AppSnapshot::from_app(&app)
+}
+
+/// 歌词：手动滚动模式下高亮第 3 行（不随播放进度变化）
+pub(super) fn lyrics() -> AppSnapshot {
+    let mut app = logged_in(View::Lyrics);
+    app.lyrics = Arc::from(
+        [
+            (0, "故事的小黄花", None),
+            (5_000, "从出生那年就飘着", None),
+            (10_000, "童年的荡秋千", Some("The swing of childhood")),
+            (15_000, "随记忆一直晃到现在", None),
+            (20_000, "Re So So Si Do Si La", None),
+            (25_000, "So La Si Si Si Si La Si La So", None),
+        ]
+        .map(|(time_ms, text, translation)| LyricLine {
+            time_ms,
+            text: text.to_owned(),
+            translation: translation.map(str::to_owned),
+            roman: None,
+        }),
+    );
+    app.lyrics_follow = false;
+    app.lyrics_selected = 2;
+    app.lyrics_status = "歌词: 6 行".to_owned();
+    AppSnapshot::from_app(&app)
+}
+
+/// 设置页：选中「歌词」分组
+pub(super) fn settings() -> AppSnapshot {
+    let mut app = logged_in(View::Settings);
+    app.settings_group_selected = 1;
+    app.settings_selected = 1;
+    app.lyrics_offset_ms = 250;
+    AppSnapshot::from_app(&app)
+}
+
+/// 队列页：播放第 2 首，选中第 4 首
+pub(super) fn queue() -> AppSnapshot {
+    let mut app = logged_in(View::Queue);
+    app.play_queue.set_songs(songs(), Some(1));
+    app.queue_selected = Some(3);
+    AppSnapshot::from_app(&app)
+}
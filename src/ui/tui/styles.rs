@@ -1,9 +1,159 @@
-use ratatui::style::{Color, Style};
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, BorderType, Borders},
+};
 
-pub(super) fn focus_style(active: bool) -> Style {
-    if active {
-        Style::default().fg(Color::Yellow)
-    } else {
-        Style::default().fg(Color::Gray)
+/// 界面配色
+///
+/// 单色模式（`NO_COLOR`、`--no-color` 或设置项）不输出任何颜色，焦点与选中改用
+/// 粗体/下划线/反色，并补充文本标记（当前行 `▶`、焦点面板 `[*]`），保证不依赖颜色也能分辨。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum Theme {
+    #[default]
+    Color,
+    Monochrome,
+}
+
+impl Theme {
+    pub(super) fn resolve(no_color: bool) -> Self {
+        if no_color {
+            Theme::Monochrome
+        } else {
+            Theme::Color
+        }
+    }
+
+    /// 纯装饰性的前景色；单色模式下忽略
+    pub(super) fn fg(self, color: Color) -> Style {
+        match self {
+            Theme::Color => Style::default().fg(color),
+            Theme::Monochrome => Style::default(),
+        }
+    }
+
+    /// 提示性警告（如音质被服务端降级）
+    pub(super) fn warning(self) -> Style {
+        match self {
+            Theme::Color => Style::default().fg(Color::LightRed),
+            Theme::Monochrome => Style::default().add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// 面板边框与焦点输入框
+    pub(super) fn focus(self, active: bool) -> Style {
+        match (self, active) {
+            (Theme::Color, true) => Style::default().fg(Color::Yellow),
+            (Theme::Color, false) => Style::default().fg(Color::Gray),
+            (Theme::Monochrome, true) => Style::default().add_modifier(Modifier::BOLD),
+            (Theme::Monochrome, false) => Style::default(),
+        }
+    }
+
+    /// 当前页面不可聚焦的面板（只展示信息）
+    pub(super) fn dimmed(self) -> Style {
+        match self {
+            Theme::Color => Style::default().fg(Color::DarkGray),
+            Theme::Monochrome => Style::default().add_modifier(Modifier::DIM),
+        }
+    }
+
+    /// 带边框的面板；单色模式下焦点面板用粗边框，并在右上角标出 `[*]`
+    pub(super) fn panel<'a>(self, title: impl Into<Line<'a>>, active: bool) -> Block<'a> {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(self.focus(active));
+        if self == Theme::Monochrome && active {
+            block
+                .border_type(BorderType::Thick)
+                .title(Line::from("[*]").right_aligned())
+        } else {
+            block
+        }
+    }
+
+    /// 列表中的选中行（光标）
+    pub(super) fn highlight(self) -> Style {
+        match self {
+            Theme::Color => Style::default().fg(Color::Yellow),
+            Theme::Monochrome => Style::default().add_modifier(Modifier::REVERSED),
+        }
+    }
+
+    /// 弹窗中的选中项（已有 `>` 前缀）
+    pub(super) fn selected_item(self) -> Style {
+        match self {
+            Theme::Color => Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            Theme::Monochrome => Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+        }
+    }
+
+    /// 正在播放的行（当前歌词）
+    pub(super) fn current(self) -> Style {
+        match self {
+            Theme::Color => Style::default().fg(Color::Yellow),
+            Theme::Monochrome => {
+                Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+            }
+        }
+    }
+
+    /// 当前行的前缀标记；彩色模式下歌词靠颜色区分，不加标记
+    pub(super) fn current_symbol(self) -> &'static str {
+        match self {
+            Theme::Color => "",
+            Theme::Monochrome => "▶ ",
+        }
+    }
+
+    /// 歌单 / 搜索列表中正在播放的一行（另有 `▶` 前缀）
+    pub(super) fn playing_row(self) -> Style {
+        match self {
+            Theme::Color => Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+            Theme::Monochrome => Style::default().add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// 队列中正在播放的一项
+    pub(super) fn playing_mark(self) -> &'static str {
+        match self {
+            Theme::Color => ">",
+            Theme::Monochrome => "▶",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monochrome_styles_carry_no_color() {
+        let theme = Theme::Monochrome;
+        for style in [
+            theme.fg(Color::Cyan),
+            theme.warning(),
+            theme.focus(true),
+            theme.focus(false),
+            theme.highlight(),
+            theme.selected_item(),
+            theme.current(),
+        ] {
+            assert_eq!(style.fg, None, "{style:?}");
+            assert_eq!(style.bg, None, "{style:?}");
+        }
+        assert!(theme.highlight().add_modifier.contains(Modifier::REVERSED));
+        assert!(theme.focus(true).add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn resolve_picks_monochrome_when_no_color() {
+        assert_eq!(Theme::resolve(true), Theme::Monochrome);
+        assert_eq!(Theme::resolve(false), Theme::Color);
     }
 }
@@ -0,0 +1,115 @@
+//! 终端挂起（Ctrl+Z / SIGTSTP）与恢复（SIGCONT）
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+
+/// raw mode 下 Ctrl+Z 不会触发 SIGTSTP，而是作为按键事件送达
+pub(super) fn is_suspend_key(evt: &Event) -> bool {
+    matches!(
+        evt,
+        Event::Key(key)
+            if !matches!(key.kind, KeyEventKind::Release)
+                && key.modifiers.contains(KeyModifiers::CONTROL)
+                && matches!(key.code, KeyCode::Char('z' | 'Z'))
+    )
+}
+
+#[cfg(unix)]
+pub(super) use unix::{SuspendSignal, listen_signals, resume, suspend};
+
+#[cfg(unix)]
+mod unix {
+    use super::super::guard::{restore_terminal, setup_terminal};
+    use crate::messages::app::AppCommand;
+    use ratatui::{Terminal, backend::Backend};
+    use std::io;
+    use std::time::Instant;
+    use tokio::signal::unix::{SignalKind, signal};
+    use tokio::sync::mpsc;
+
+    /// 来自外部的作业控制信号（如 `kill -TSTP`、`fg`）
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum SuspendSignal {
+        Stop,
+        Continue,
+    }
+
+    /// 监听 SIGTSTP / SIGCONT，转发给事件循环
+    pub fn listen_signals() -> io::Result<mpsc::UnboundedReceiver<SuspendSignal>> {
+        let mut tstp = signal(SignalKind::from_raw(libc::SIGTSTP))?;
+        let mut cont = signal(SignalKind::from_raw(libc::SIGCONT))?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            loop {
+                let sig = tokio::select! {
+                    Some(()) = tstp.recv() => SuspendSignal::Stop,
+                    Some(()) = cont.recv() => SuspendSignal::Continue,
+                    else => break,
+                };
+                if tx.send(sig).is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(rx)
+    }
+
+    /// 还原终端后停止进程；恢复（fg）后重新进入 TUI 并强制完整重绘
+    pub async fn suspend<B>(
+        terminal: &mut Terminal<B>,
+        tx: &mpsc::Sender<AppCommand>,
+    ) -> io::Result<()>
+    where
+        B: Backend<Error = io::Error>,
+    {
+        tracing::info!("[Tui] 挂起进程");
+        restore_terminal()?;
+        let started = Instant::now();
+        // SIGTSTP 已被上面的监听接管，这里用无法捕获的 SIGSTOP 交还给 shell
+        // SAFETY: raise 只向当前进程发送信号，无内存安全前提
+        unsafe {
+            libc::raise(libc::SIGSTOP);
+        }
+        let suspended_ms = started.elapsed().as_millis() as u64;
+        tracing::info!(suspended_ms, "[Tui] 进程已恢复");
+
+        resume(terminal)?;
+        let _ = tx.send(AppCommand::TerminalResumed { suspended_ms }).await;
+        Ok(())
+    }
+
+    /// 重新进入 TUI；ratatui 的缓冲区仍是挂起前的内容，需要清屏才会完整重绘
+    pub fn resume<B>(terminal: &mut Terminal<B>) -> io::Result<()>
+    where
+        B: Backend<Error = io::Error>,
+    {
+        setup_terminal()?;
+        terminal.clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_suspend_key;
+    use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+    #[test]
+    fn only_ctrl_z_press_suspends() {
+        let key = |code, modifiers| Event::Key(KeyEvent::new(code, modifiers));
+        assert!(is_suspend_key(&key(
+            KeyCode::Char('z'),
+            KeyModifiers::CONTROL
+        )));
+        assert!(!is_suspend_key(&key(
+            KeyCode::Char('z'),
+            KeyModifiers::NONE
+        )));
+        assert!(!is_suspend_key(&key(
+            KeyCode::Char('s'),
+            KeyModifiers::CONTROL
+        )));
+
+        let mut release = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::CONTROL);
+        release.kind = KeyEventKind::Release;
+        assert!(!is_suspend_key(&Event::Key(release)));
+    }
+}
@@ -0,0 +1,147 @@
+use ratatui::{
+    Frame,
+    prelude::Rect,
+    style::Color,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+};
+use std::time::Instant;
+
+use super::styles::Theme;
+use super::utils::fmt_mmss;
+use crate::app::{AppSnapshot, BackgroundTask, TaskStatus};
+
+/// Draw the background tasks overlay centered on the canvas area.
+pub(super) fn draw_tasks_overlay(f: &mut Frame, area: Rect, app: &AppSnapshot, theme: Theme) {
+    let width = area.width.saturating_sub(4).min(90);
+    let rows = (app.tasks.len().max(1) as u16).saturating_add(3); // borders + hint line
+    let height = rows.min(area.height.saturating_sub(4));
+    let popup = centered_rect(area, width, height);
+
+    f.render_widget(Clear, popup);
+
+    let now = Instant::now();
+    let mut items: Vec<ListItem> = if app.tasks.is_empty() {
+        vec![ListItem::new(Line::from(Span::styled(
+            "   暂无后台任务",
+            theme.fg(Color::Gray),
+        )))]
+    } else {
+        app.tasks
+            .iter()
+            .enumerate()
+            .map(|(i, task)| {
+                let selected = i == app.tasks_selected;
+                let style = if selected {
+                    theme.selected_item()
+                } else {
+                    theme.fg(status_color(&task.status))
+                };
+                let prefix = if selected { " > " } else { "   " };
+                ListItem::new(Line::from(Span::styled(
+                    format!("{prefix}{}", format_task_row(task, now)),
+                    style,
+                )))
+            })
+            .collect()
+    };
+    items.push(ListItem::new(Line::from(Span::styled(
+        "   ↑↓ 选择 | x 取消 | F6/Esc 关闭",
+        theme.fg(Color::DarkGray),
+    ))));
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("后台任务")
+            .style(theme.fg(Color::Cyan)),
+    );
+
+    let mut state = ListState::default();
+    if !app.tasks.is_empty() {
+        state.select(Some(app.tasks_selected));
+    }
+
+    f.render_stateful_widget(list, popup, &mut state);
+}
+
+fn status_color(status: &TaskStatus) -> Color {
+    match status {
+        TaskStatus::Running => Color::White,
+        TaskStatus::Completed => Color::Green,
+        TaskStatus::Failed(_) => Color::Red,
+        TaskStatus::Cancelled => Color::Gray,
+    }
+}
+
+fn format_task_row(task: &BackgroundTask, now: Instant) -> String {
+    let progress = match task.total {
+        Some(total) if total > 0 => format!(
+            "{}/{} {:>3}%",
+            task.done,
+            total,
+            task.done.saturating_mul(100) / total
+        ),
+        _ if task.done > 0 => task.done.to_string(),
+        _ => "-".to_owned(),
+    };
+    let status = match &task.status {
+        TaskStatus::Running => "进行中".to_owned(),
+        TaskStatus::Completed => "完成".to_owned(),
+        TaskStatus::Failed(msg) => format!("失败: {msg}"),
+        TaskStatus::Cancelled => "已取消".to_owned(),
+    };
+    let elapsed_ms = now.saturating_duration_since(task.started_at).as_millis() as u64;
+    format!(
+        "[{}] {} | {progress} | {} 前开始 | {status}",
+        task.kind.label(),
+        task.title,
+        fmt_mmss(elapsed_ms)
+    )
+}
+
+fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    Rect {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{App, TaskKind};
+
+    #[test]
+    fn tasks_overlay_renders_registered_task() {
+        let mut app = App {
+            tasks_visible: true,
+            ..Default::default()
+        };
+        let id = app
+            .tasks
+            .register(TaskKind::PlaylistPreload { playlist_id: 1 }, "我喜欢的音乐");
+        app.tasks.update_progress(id, 50, Some(200));
+        let snapshot = AppSnapshot::from_app(&app);
+
+        let backend = ratatui::backend::TestBackend::new(100, 24);
+        let mut terminal = ratatui::Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| draw_tasks_overlay(f, f.area(), &snapshot, Theme::Color))
+            .unwrap();
+
+        let rendered = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect::<String>();
+        assert!(rendered.contains("50/200"));
+        assert!(rendered.contains(" 25%"));
+    }
+}
@@ -0,0 +1,187 @@
+//! 事件循环的刷新节奏：歌词页播放时提高刷新率，保证高亮行及时切换
+//!
+//! 终端失去焦点（需终端支持焦点上报）时完全停止重绘，也不再使用高刷新率；
+//! 不支持焦点上报的终端始终视为有焦点。
+
+use super::lyrics_view::highlighted_lyric;
+use crate::app::{AppSnapshot, AppViewSnapshot, View};
+use std::time::{Duration, Instant};
+
+/// 常规刷新间隔
+pub(super) const TICK_RATE: Duration = Duration::from_millis(200);
+/// 歌词页播放中的刷新间隔（约 30Hz）
+pub(super) const LYRICS_TICK_RATE: Duration = Duration::from_millis(33);
+
+/// 只有终端有焦点、在歌词页且正在播放（未暂停）时才需要高刷新率
+pub(super) fn tick_rate_for(view: View, playing: bool, paused: bool, focused: bool) -> Duration {
+    if focused && matches!(view, View::Lyrics) && playing && !paused {
+        LYRICS_TICK_RATE
+    } else {
+        TICK_RATE
+    }
+}
+
+pub(super) fn tick_rate(app: &AppSnapshot, focused: bool) -> Duration {
+    tick_rate_for(
+        app.view,
+        app.player.play_started_at.is_some(),
+        app.player.paused,
+        focused,
+    )
+}
+
+/// 当前跟随播放进度的高亮歌词行；不在歌词页或锁定滚动时为 None
+///
+/// 高刷新率下每个 tick 只比较这一项（二分查找），变化时才重绘。
+pub(super) fn lyric_highlight(app: &AppSnapshot) -> Option<usize> {
+    match &app.view_state {
+        AppViewSnapshot::Lyrics(state) if state.lyrics_follow && !state.lyrics.is_empty() => {
+            Some(highlighted_lyric(state, &app.player))
+        }
+        _ => None,
+    }
+}
+
+/// 刷新节奏与重绘判断
+pub(super) struct Ticker {
+    highlight: Option<usize>,
+    last_draw: Option<Instant>,
+}
+
+impl Ticker {
+    pub(super) fn new() -> Self {
+        Self {
+            highlight: None,
+            last_draw: None,
+        }
+    }
+
+    /// 是否需要重绘：终端无焦点时从不重绘；常规节奏下每个 tick 都重绘；
+    /// 高刷新率下仅在状态变化、高亮行变化或距上次重绘超过常规间隔时重绘
+    pub(super) fn should_draw(&mut self, app: &AppSnapshot, dirty: bool, focused: bool) -> bool {
+        if !focused {
+            return false;
+        }
+        let highlight = lyric_highlight(app);
+        let due = self.last_draw.is_none_or(|t| t.elapsed() >= TICK_RATE);
+        let draw = dirty || due || tick_rate(app, true) == TICK_RATE || highlight != self.highlight;
+        if draw {
+            self.highlight = highlight;
+            self.last_draw = Some(Instant::now());
+        }
+        draw
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::domain::model::LyricLine;
+
+    #[test]
+    fn tick_rate_over_view_and_playback_state() {
+        let views = [
+            View::Login,
+            View::Playlists,
+            View::Search,
+            View::Lyrics,
+            View::Settings,
+            View::Queue,
+        ];
+        for view in views {
+            for playing in [true, false] {
+                for paused in [true, false] {
+                    for focused in [true, false] {
+                        let expected = if focused && view == View::Lyrics && playing && !paused {
+                            LYRICS_TICK_RATE
+                        } else {
+                            TICK_RATE
+                        };
+                        assert_eq!(
+                            tick_rate_for(view, playing, paused, focused),
+                            expected,
+                            "view={view:?} playing={playing} paused={paused} focused={focused}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn fast_tick_redraws_only_on_highlight_change_or_dirty() {
+        let mut app = App {
+            view: View::Lyrics,
+            play_started_at: Some(Instant::now()),
+            ..Default::default()
+        };
+        app.lyrics = [0, 1_000, 600_000]
+            .into_iter()
+            .map(|time_ms| LyricLine {
+                time_ms,
+                text: format!("{time_ms}"),
+                translation: None,
+                roman: None,
+            })
+            .collect();
+        let snapshot = AppSnapshot::from_app(&app);
+        assert_eq!(tick_rate(&snapshot, true), LYRICS_TICK_RATE);
+
+        let mut ticker = Ticker::new();
+        assert!(ticker.should_draw(&snapshot, false, true), "首帧必须绘制");
+        assert!(
+            !ticker.should_draw(&snapshot, false, true),
+            "高亮行未变化时跳过重绘"
+        );
+        assert!(ticker.should_draw(&snapshot, true, true), "状态变化时重绘");
+
+        // 进度越过下一行歌词的时间点
+        app.play_started_at = Instant::now().checked_sub(Duration::from_millis(1_500));
+        let snapshot = AppSnapshot::from_app(&app);
+        assert!(
+            ticker.should_draw(&snapshot, false, true),
+            "高亮行变化时重绘"
+        );
+        assert!(!ticker.should_draw(&snapshot, false, true));
+
+        app.paused = true;
+        app.play_paused_at = Some(Instant::now());
+        let snapshot = AppSnapshot::from_app(&app);
+        assert_eq!(tick_rate(&snapshot, true), TICK_RATE);
+        assert!(
+            ticker.should_draw(&snapshot, false, true),
+            "常规节奏下每个 tick 都重绘"
+        );
+    }
+
+    #[test]
+    fn unfocused_terminal_skips_redraws_until_focus_returns() {
+        for (playing, paused) in [(true, false), (true, true), (false, false)] {
+            let app = App {
+                view: View::Lyrics,
+                play_started_at: playing.then(Instant::now),
+                paused,
+                play_paused_at: paused.then(Instant::now),
+                lyrics: [LyricLine {
+                    time_ms: 0,
+                    text: "a".to_owned(),
+                    translation: None,
+                    roman: None,
+                }]
+                .into_iter()
+                .collect(),
+                ..Default::default()
+            };
+            let snapshot = AppSnapshot::from_app(&app);
+            let case = format!("playing={playing} paused={paused}");
+            assert_eq!(tick_rate(&snapshot, false), TICK_RATE, "{case}");
+
+            let mut ticker = Ticker::new();
+            assert!(!ticker.should_draw(&snapshot, true, false), "{case}");
+            assert!(!ticker.should_draw(&snapshot, false, false), "{case}");
+            // 重新获得焦点时事件循环标记 dirty，立即完整重绘
+            assert!(ticker.should_draw(&snapshot, true, true), "{case}");
+        }
+    }
+}
@@ -1,27 +1,25 @@
+use super::styles::Theme;
 use crate::app::{Toast, ToastLevel};
 use ratatui::{
     Frame,
     prelude::Rect,
-    style::{Color, Style},
+    style::Color,
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
 /// 绘制 Toast 通知
-pub fn draw_toast(f: &mut Frame, area: Rect, toast: &Toast) {
+pub fn draw_toast(f: &mut Frame, area: Rect, toast: &Toast, theme: Theme) {
     let (icon, color) = match toast.level {
         ToastLevel::Error => ("❌", Color::Red),
         ToastLevel::Warning => ("⚠️ ", Color::Yellow),
         ToastLevel::Info => ("ℹ️ ", Color::Gray),
     };
 
-    let paragraph = Paragraph::new(format!("{} {}", icon, &toast.message))
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .style(Style::default().fg(color)),
-        )
+    let style = theme.fg(color);
+    let paragraph = Paragraph::new(format!("{} {}", icon, toast.message))
+        .block(Block::default().borders(Borders::ALL).style(style))
         .wrap(Wrap { trim: true })
-        .style(Style::default().fg(color));
+        .style(style);
 
     f.render_widget(paragraph, area);
 }
@@ -77,4 +75,12 @@ mod tests {
         assert_eq!(toast.level, ToastLevel::Warning);
         assert_eq!(toast.level.duration_ms(), Some(5000));
     }
+
+    #[test]
+    fn focus_mode_only_lets_errors_through() {
+        assert!(Toast::info("已收藏").filter_for_focus_mode(true).is_none());
+        assert!(Toast::warning("慢").filter_for_focus_mode(true).is_none());
+        assert!(Toast::error("失败").filter_for_focus_mode(true).is_some());
+        assert!(Toast::info("已收藏").filter_for_focus_mode(false).is_some());
+    }
 }
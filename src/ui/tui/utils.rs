@@ -1,7 +1,12 @@
-use crate::app::{AppSnapshot, AppViewSnapshot, PlayMode, PlayerSnapshot, View};
+use super::styles::Theme;
+use crate::app::{AppSnapshot, AppViewSnapshot, PlayerSnapshot, SongFailures, View};
 use ratatui::layout::Rect;
+use ratatui::text::Span;
+use std::borrow::Cow;
+use std::time::Duration;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
-pub(super) const MIN_CANVAS_WIDTH: u16 = 122;
+pub(super) const MIN_CANVAS_WIDTH: u16 = 100;
 pub(super) const MIN_CANVAS_HEIGHT: u16 = 29;
 
 pub(super) fn is_unauth_login_page(app: &AppSnapshot) -> bool {
@@ -10,17 +15,17 @@ pub(super) fn is_unauth_login_page(app: &AppSnapshot) -> bool {
         && matches!(app.view_state, AppViewSnapshot::Login(_))
 }
 
+/// 画布占满终端宽度（主体三栏按宽度断点分配），高度固定并垂直居中
 pub(super) fn canvas_rect(area: Rect) -> Option<Rect> {
     if area.width < MIN_CANVAS_WIDTH || area.height < MIN_CANVAS_HEIGHT {
         return None;
     }
 
-    let x = area.x + (area.width - MIN_CANVAS_WIDTH) / 2;
     let y = area.y + (area.height - MIN_CANVAS_HEIGHT) / 2;
     Some(Rect {
-        x,
+        x: area.x,
         y,
-        width: MIN_CANVAS_WIDTH,
+        width: area.width,
         height: MIN_CANVAS_HEIGHT,
     })
 }
@@ -47,11 +52,42 @@ pub(super) fn current_lyric_index(
     }
 }
 
+/// 按播放进度（含 offset）定位当前歌词行；歌词页高亮与状态栏歌词共用
+pub(super) fn lyric_index_at(
+    lines: &[crate::domain::model::LyricLine],
+    elapsed_ms: u64,
+    offset_ms: i64,
+) -> Option<usize> {
+    current_lyric_index(lines, apply_lyrics_offset(elapsed_ms, offset_ms))
+}
+
+/// 按显示宽度截断（中文等宽字符算 2 列），超出时以「…」结尾
+pub(super) fn truncate_to_width(text: &str, max_width: usize) -> Cow<'_, str> {
+    if text.width() <= max_width {
+        return Cow::Borrowed(text);
+    }
+    if max_width == 0 {
+        return Cow::Borrowed("");
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > max_width - 1 {
+            break;
+        }
+        used += w;
+        out.push(c);
+    }
+    out.push('…');
+    Cow::Owned(out)
+}
+
 pub(super) fn apply_lyrics_offset(elapsed_ms: u64, offset_ms: i64) -> u64 {
     if offset_ms >= 0 {
-        elapsed_ms.saturating_add(offset_ms as u64)
+        elapsed_ms.saturating_add(offset_ms.unsigned_abs())
     } else {
-        elapsed_ms.saturating_sub((-offset_ms) as u64)
+        elapsed_ms.saturating_sub(offset_ms.unsigned_abs())
     }
 }
 
@@ -72,18 +108,146 @@ pub(super) fn br_label(br: i64) -> &'static str {
     }
 }
 
-pub(super) fn play_mode_label(m: PlayMode) -> &'static str {
-    match m {
-        PlayMode::Sequential => "顺序",
-        PlayMode::ListLoop => "列表循环",
-        PlayMode::SingleLoop => "单曲循环",
-        PlayMode::Shuffle => "随机",
+/// 音质显示：服务端降级时显示实际音质并用警告色标出（如「128k ↓」）
+pub(super) fn quality_span(player: &PlayerSnapshot, theme: Theme) -> Span<'static> {
+    match player.downgraded_br() {
+        Some(actual) => Span::styled(format!("{} ↓", br_label(actual)), theme.warning()),
+        None => Span::raw(br_label(player.play_br)),
+    }
+}
+
+/// 多次播放失败的歌曲在列表行尾显示的标记
+pub(super) fn failure_mark(failures: &SongFailures, song_id: i64) -> &'static str {
+    if failures.is_marked(song_id) {
+        " ⚠"
+    } else {
+        ""
+    }
+}
+
+/// 距今时长，如「12秒前」「3分钟前」
+pub(super) fn fmt_ago(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs < 60 {
+        format!("{secs}秒前")
+    } else if secs < 3600 {
+        format!("{}分钟前", secs / 60)
+    } else {
+        format!("{}小时前", secs / 3600)
+    }
+}
+
+/// 时间戳（毫秒）相对 `now_ms` 的友好描述，如「刚刚」「3 小时前」「昨天」「2 周前」
+///
+/// 时间戳晚于当前时间（本机时钟偏差）时视为「刚刚」。
+pub(super) fn fmt_relative_time(ts_ms: i64, now_ms: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+
+    let secs = now_ms.saturating_sub(ts_ms) / 1000;
+    if secs < MINUTE {
+        "刚刚".to_owned()
+    } else if secs < HOUR {
+        format!("{} 分钟前", secs / MINUTE)
+    } else if secs < DAY {
+        format!("{} 小时前", secs / HOUR)
+    } else if secs < 2 * DAY {
+        "昨天".to_owned()
+    } else if secs < 7 * DAY {
+        format!("{} 天前", secs / DAY)
+    } else if secs < 30 * DAY {
+        format!("{} 周前", secs / (7 * DAY))
+    } else if secs < 365 * DAY {
+        format!("{} 个月前", secs / (30 * DAY))
+    } else {
+        format!("{} 年前", secs / (365 * DAY))
     }
 }
 
+/// 以当前时间渲染 [`fmt_relative_time`]
+pub(super) fn fmt_relative_now(ts_ms: i64) -> String {
+    fmt_relative_time(ts_ms, chrono::Utc::now().timestamp_millis())
+}
+
 pub(super) fn fmt_mmss(ms: u64) -> String {
     let total_sec = ms / 1000;
     let m = total_sec / 60;
     let s = total_sec % 60;
     format!("{m:02}:{s:02}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_lyrics_offset, fmt_relative_time, lyric_index_at, truncate_to_width};
+    use crate::domain::model::LyricLine;
+
+    fn line(time_ms: u64, text: &str) -> LyricLine {
+        LyricLine {
+            time_ms,
+            text: text.to_owned(),
+            translation: None,
+            roman: None,
+        }
+    }
+
+    #[test]
+    fn lyrics_offset_saturates_at_extremes() {
+        assert_eq!(apply_lyrics_offset(1_000, -200), 800);
+        assert_eq!(apply_lyrics_offset(1_000, i64::MIN), 0);
+        assert_eq!(apply_lyrics_offset(u64::MAX, i64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn lyric_index_applies_offset_before_lookup() {
+        let lines = [line(1_000, "a"), line(2_000, "b"), line(3_000, "c")];
+        assert_eq!(lyric_index_at(&lines, 500, 0), Some(0));
+        assert_eq!(lyric_index_at(&lines, 2_000, 0), Some(1));
+        assert_eq!(lyric_index_at(&lines, 2_500, 600), Some(2));
+        assert_eq!(lyric_index_at(&lines, 3_100, -200), Some(1));
+        assert_eq!(lyric_index_at(&[], 1_000, 0), None);
+    }
+
+    #[test]
+    fn truncate_respects_display_width() {
+        assert_eq!(truncate_to_width("hello", 5), "hello");
+        assert_eq!(truncate_to_width("hello world", 6), "hello…");
+        // 中文每字 2 列：5 列只能放 2 个字 + 省略号
+        assert_eq!(truncate_to_width("你好世界", 5), "你好…");
+        assert_eq!(truncate_to_width("你好世界", 8), "你好世界");
+        assert_eq!(truncate_to_width("你好", 0), "");
+    }
+
+    #[test]
+    fn relative_time_boundaries() {
+        const NOW: i64 = 1_700_000_000_000;
+        const SEC: i64 = 1_000;
+        const MIN: i64 = 60 * SEC;
+        const HOUR: i64 = 60 * MIN;
+        const DAY: i64 = 24 * HOUR;
+        let ago = |ms: i64| fmt_relative_time(NOW - ms, NOW);
+
+        assert_eq!(ago(0), "刚刚");
+        assert_eq!(ago(59 * SEC), "刚刚");
+        assert_eq!(ago(MIN), "1 分钟前");
+        assert_eq!(ago(HOUR - 1), "59 分钟前");
+        assert_eq!(ago(HOUR), "1 小时前");
+        assert_eq!(ago(DAY - 1), "23 小时前");
+        assert_eq!(ago(DAY), "昨天");
+        assert_eq!(ago(2 * DAY - 1), "昨天");
+        assert_eq!(ago(2 * DAY), "2 天前");
+        assert_eq!(ago(7 * DAY - 1), "6 天前");
+        assert_eq!(ago(7 * DAY), "1 周前");
+        assert_eq!(ago(30 * DAY - 1), "4 周前");
+        assert_eq!(ago(30 * DAY), "1 个月前");
+        assert_eq!(ago(365 * DAY - 1), "12 个月前");
+        assert_eq!(ago(365 * DAY), "1 年前");
+        assert_eq!(ago(3 * 365 * DAY), "3 年前");
+    }
+
+    #[test]
+    fn relative_time_in_future_is_just_now() {
+        assert_eq!(fmt_relative_time(2_000_000, 1_000_000), "刚刚");
+        assert_eq!(fmt_relative_time(i64::MAX, i64::MIN), "刚刚");
+    }
+}
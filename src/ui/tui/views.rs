@@ -2,13 +2,17 @@ use super::header::draw_header;
 use super::layout::{split_body, split_canvas, split_header, split_right};
 use super::login_view::draw_login;
 use super::lyrics_view::draw_lyrics;
-use super::menu::draw_menu_overlay;
-use super::overlays::draw_help_overlay;
+use super::menu::{draw_menu_overlay, draw_playlist_picker, draw_unavailable_review};
+use super::notifications_view::{draw_notification_detail, draw_notifications_overlay};
+use super::overlays::{draw_help_overlay, draw_quit_confirm, draw_restore_prompt};
 use super::panels::{draw_context_panel, draw_left_panel, draw_now_panel};
 use super::player_status::draw_footer;
 use super::playlists_view::draw_playlists;
+use super::queue_view::draw_queue;
 use super::search_view::draw_search;
 use super::settings_view::draw_settings;
+use super::styles::Theme;
+use super::tasks_view::draw_tasks_overlay;
 use super::toast::draw_toast;
 use super::utils::{MIN_CANVAS_HEIGHT, MIN_CANVAS_WIDTH, canvas_rect, is_unauth_login_page};
 use crate::app::{AppSnapshot, AppViewSnapshot, UiFocus, View};
@@ -18,7 +22,7 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
 };
 
-pub(super) fn draw_ui(f: &mut Frame, app: &AppSnapshot) {
+pub(super) fn draw_ui(f: &mut Frame, app: &AppSnapshot, theme: Theme) {
     let size = f.area();
     let Some(canvas) = canvas_rect(size) else {
         draw_resize_prompt(f, size);
@@ -29,11 +33,11 @@ pub(super) fn draw_ui(f: &mut Frame, app: &AppSnapshot) {
         let canvas_layout = split_canvas(canvas);
 
         if let AppViewSnapshot::Login(state) = &app.view_state {
-            draw_login(f, canvas, state, app.logged_in, true);
+            draw_login(f, canvas, state, app.logged_in, true, theme);
         }
 
         if let Some(toast) = &app.toast {
-            draw_toast(f, canvas_layout.toast, toast);
+            draw_toast(f, canvas_layout.toast, toast, theme);
         }
 
         if app.help_visible {
@@ -41,51 +45,82 @@ pub(super) fn draw_ui(f: &mut Frame, app: &AppSnapshot) {
         }
 
         if app.menu_visible {
-            draw_menu_overlay(f, canvas, app);
+            draw_menu_overlay(f, canvas, app, theme);
+        }
+
+        if let Some(message) = &app.restore_prompt {
+            draw_restore_prompt(f, canvas, message);
+        }
+
+        if let Some(confirm) = &app.quit_confirm {
+            draw_quit_confirm(f, canvas, confirm);
         }
         return;
     }
 
     let canvas_layout = split_canvas(canvas);
     let header_layout = split_header(canvas_layout.header);
-    let body_layout = split_body(canvas_layout.body);
-    let right_layout = split_right(body_layout.right);
+    let body_layout = split_body(canvas_layout.body, app.pane_ratios);
 
-    draw_header(f, &header_layout, app);
-    draw_left_panel(f, body_layout.left, app);
-    draw_context_panel(f, right_layout.context, app);
-    draw_now_panel(f, right_layout.now, &app.player, app.ui_focus);
+    draw_header(f, &header_layout, app, theme);
+    draw_left_panel(f, body_layout.left, app, theme);
+    if let Some(right) = body_layout.right {
+        let right_layout = split_right(right);
+        draw_context_panel(f, right_layout.context, app, theme);
+        draw_now_panel(f, right_layout.now, &app.player, app.ui_focus, theme);
+    }
 
-    let center_active = app.ui_focus == UiFocus::BodyCenter;
+    // 右栏隐藏时，尚未同步的右栏焦点按中栏处理
+    let center_active = app.ui_focus == UiFocus::BodyCenter
+        || (app.ui_focus == UiFocus::BodyRight && body_layout.right.is_none());
     match (&app.view, &app.view_state) {
         (View::Login, AppViewSnapshot::Login(state)) => {
-            draw_login(f, body_layout.center, state, app.logged_in, false);
+            draw_login(f, body_layout.center, state, app.logged_in, false, theme);
         }
         (View::Playlists, AppViewSnapshot::Playlists(state)) => {
-            draw_playlists(f, body_layout.center, state, center_active);
+            draw_playlists(
+                f,
+                body_layout.center,
+                state,
+                &app.song_failures,
+                app.song_name_mode,
+                center_active,
+                theme,
+            );
         }
         (View::Search, AppViewSnapshot::Search(state)) => {
-            draw_search(f, body_layout.center, state, center_active);
+            draw_search(
+                f,
+                body_layout.center,
+                state,
+                &app.song_failures,
+                app.song_name_mode,
+                center_active,
+                theme,
+            );
         }
         (View::Lyrics, AppViewSnapshot::Lyrics(state)) => {
-            draw_lyrics(f, body_layout.center, state, &app.player, center_active);
-        }
-        (View::Settings, AppViewSnapshot::Settings(state)) => {
-            draw_settings(
+            draw_lyrics(
                 f,
                 body_layout.center,
                 state,
                 &app.player,
-                app.logged_in,
                 center_active,
+                theme,
             );
         }
+        (View::Settings, AppViewSnapshot::Settings(state)) => {
+            draw_settings(f, body_layout.center, state, center_active, theme);
+        }
+        (View::Queue, AppViewSnapshot::Queue(_)) => {
+            draw_queue(f, body_layout.center, app, center_active, theme);
+        }
         _ => {}
     }
 
     // 绘制 Toast（如果有）
     if let Some(toast) = &app.toast {
-        draw_toast(f, canvas_layout.toast, toast);
+        draw_toast(f, canvas_layout.toast, toast, theme);
     }
 
     let view_status = match &app.view_state {
@@ -94,15 +129,41 @@ pub(super) fn draw_ui(f: &mut Frame, app: &AppSnapshot) {
         AppViewSnapshot::Search(state) => state.search_status.as_str(),
         AppViewSnapshot::Lyrics(state) => state.lyrics_status.as_str(),
         AppViewSnapshot::Settings(state) => state.settings_status.as_str(),
+        AppViewSnapshot::Queue(state) => state.queue_status.as_str(),
     };
-    draw_footer(f, canvas_layout.footer, &app.player, view_status);
+    draw_footer(f, canvas_layout.footer, &app.player, view_status, theme);
 
     if app.help_visible {
         draw_help_overlay(f, canvas);
     }
 
     if app.menu_visible {
-        draw_menu_overlay(f, canvas, app);
+        draw_menu_overlay(f, canvas, app, theme);
+    }
+
+    if app.tasks_visible {
+        draw_tasks_overlay(f, canvas, app, theme);
+    }
+
+    if app.notifications_visible {
+        draw_notifications_overlay(f, canvas, app, theme);
+        draw_notification_detail(f, canvas, app, theme);
+    }
+
+    if let Some(picker) = &app.playlist_picker {
+        draw_playlist_picker(f, canvas, picker, app.song_name_mode, theme);
+    }
+
+    if let Some(review) = &app.unavailable_review {
+        draw_unavailable_review(f, canvas, review, app.song_name_mode, theme);
+    }
+
+    if let Some(message) = &app.restore_prompt {
+        draw_restore_prompt(f, canvas, message);
+    }
+
+    if let Some(confirm) = &app.quit_confirm {
+        draw_quit_confirm(f, canvas, confirm);
     }
 }
 
@@ -122,13 +183,15 @@ fn draw_resize_prompt(f: &mut Frame, area: ratatui::layout::Rect) {
 
 #[cfg(test)]
 mod tests {
-    use super::draw_ui;
-    use crate::app::{App, AppSnapshot};
+    use super::{Theme, draw_ui};
+    use crate::app::{App, AppSnapshot, PhoneLoginField, PhoneLoginForm};
 
     fn render_to_string(snapshot: &AppSnapshot) -> String {
         let backend = ratatui::backend::TestBackend::new(122, 29);
         let mut terminal = ratatui::Terminal::new(backend).expect("terminal");
-        terminal.draw(|f| draw_ui(f, snapshot)).expect("draw");
+        terminal
+            .draw(|f| draw_ui(f, snapshot, Theme::Color))
+            .expect("draw");
 
         let backend = terminal.backend();
         let buffer = backend.buffer();
@@ -175,4 +238,23 @@ mod tests {
             "未登录专页不应渲染常规 header"
         );
     }
+
+    #[test]
+    fn unauth_phone_login_page_masks_the_password() {
+        let app = App {
+            login_phone_form: Some(PhoneLoginForm {
+                phone: "13800000000".to_owned(),
+                secret: "hunter2".to_owned(),
+                field: PhoneLoginField::Secret,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let snapshot = AppSnapshot::from_app(&app);
+
+        let rendered = render_to_string(&snapshot);
+        assert!(rendered.contains("13800000000"));
+        assert!(rendered.contains("•••••••"));
+        assert!(!rendered.contains("hunter2"));
+    }
 }
@@ -1,32 +1,31 @@
 use ratatui::widgets::ListState;
 
+mod progress;
+
+pub(super) use progress::{
+    buffered_ratio, lyric_section_starts, mark_loop_points, progress_cells, progress_spans,
+};
+
 pub(super) fn list_state(selected: usize) -> ListState {
     let mut st = ListState::default();
     st.select(Some(selected));
     st
 }
 
-/// 生成文本进度条，如 `进度: [######------------------]`
-///
-/// - `elapsed_ms`: 已播放毫秒数
-/// - `total_ms`: 歌曲总时长（毫秒），`None` 表示无歌曲
-/// - `width`: 进度条内部宽度（方括号内的字符数）
-pub(super) fn progress_bar_text(elapsed_ms: u64, total_ms: Option<u64>, width: usize) -> String {
-    let Some(total_ms) = total_ms.filter(|t| *t > 0) else {
-        // 无歌曲或总时长为 0：全部填充 '-'
-        let bar = "-".repeat(width);
-        return format!("进度: [{bar}]");
-    };
-
-    let ratio = (elapsed_ms.min(total_ms) as f64) / (total_ms as f64);
-    let filled = ((ratio * width as f64).round() as usize).min(width);
-    let bar = "#".repeat(filled) + &"-".repeat(width - filled);
-    format!("进度: [{bar}]")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::tui::styles::Theme;
+
+    /// 无标记、无缓冲时的进度条文本，如 `进度: [######------------------]`
+    fn progress_bar_text(elapsed_ms: u64, total_ms: Option<u64>, width: usize) -> String {
+        let cells = progress_cells(width, elapsed_ms, total_ms, &[], None);
+        let bar: String = progress_spans(&cells, Theme::Color)
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect();
+        format!("进度: [{bar}]")
+    }
 
     /// VAL-PROGRESS-001: 播放中进度条显示填充区域
     /// progress_bar_text(60000, Some(240000), 24) → 6 个 # 和 18 个 -
@@ -0,0 +1,329 @@
+//! 底部状态栏进度条
+//!
+//! Gauge 只能画单一填充，这里逐格计算：已播放 / 已缓冲 / 未缓冲三段，
+//! 再叠加刻度标记（歌词段落起点等）与 A-B 循环点。计算部分都是纯函数，渲染只做样式映射。
+
+use crate::audio_worker::{AudioPlaybackMode, AudioStreamHint};
+use crate::domain::model::LyricLine;
+use crate::ui::tui::styles::Theme;
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+/// 相邻两句歌词间隔达到该时长时，后一句视为新段落的起点
+pub(crate) const LYRIC_SECTION_GAP_MS: u64 = 30_000;
+
+/// 进度条的一格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ProgressCell {
+    Played,
+    Buffered,
+    Empty,
+    /// 刻度标记；`played` 表示所在位置已播放
+    Marker {
+        played: bool,
+    },
+    /// A-B 循环点，显示为 `A` / `B`
+    LoopPoint(char),
+}
+
+impl ProgressCell {
+    fn symbol(self) -> char {
+        match self {
+            ProgressCell::Played => '#',
+            ProgressCell::Buffered => '=',
+            ProgressCell::Empty => '-',
+            ProgressCell::Marker { .. } => '|',
+            ProgressCell::LoopPoint(label) => label,
+        }
+    }
+
+    fn style(self, theme: Theme) -> Style {
+        match self {
+            ProgressCell::Played => Style::default(),
+            ProgressCell::Buffered => theme.fg(Color::Gray),
+            ProgressCell::Empty => theme.fg(Color::DarkGray),
+            ProgressCell::Marker { played: true } => theme.fg(Color::Yellow),
+            ProgressCell::Marker { played: false } => theme.fg(Color::LightYellow),
+            ProgressCell::LoopPoint(_) => theme.fg(Color::Cyan),
+        }
+    }
+}
+
+/// `value / total` 对应的格数（四舍五入，不超过 `width`）
+fn cells_for(value: u64, total: u64, width: usize) -> usize {
+    let ratio = value.min(total) as f64 / total as f64;
+    ((ratio * width as f64).round() as usize).min(width)
+}
+
+/// 逐格计算进度条
+///
+/// - `total_ms` 为 `None` 或 0 时整条为空，标记与缓冲都不显示
+/// - `buffered_ratio`：已下载比例（0.0..=1.0），低于已播放部分时按已播放处理
+/// - 标记落在所在时间向下取整的格子；超出总时长的标记忽略，同一格的多个标记合并
+pub(crate) fn progress_cells(
+    width: usize,
+    elapsed_ms: u64,
+    total_ms: Option<u64>,
+    markers_ms: &[u64],
+    buffered_ratio: Option<f64>,
+) -> Vec<ProgressCell> {
+    let Some(total_ms) = total_ms.filter(|t| *t > 0) else {
+        return vec![ProgressCell::Empty; width];
+    };
+
+    let played = cells_for(elapsed_ms, total_ms, width);
+    let buffered = buffered_ratio
+        .map(|r| ((r.clamp(0.0, 1.0) * width as f64).round() as usize).min(width))
+        .unwrap_or(0)
+        .max(played);
+
+    let mut cells: Vec<ProgressCell> = (0..width)
+        .map(|i| {
+            if i < played {
+                ProgressCell::Played
+            } else if i < buffered {
+                ProgressCell::Buffered
+            } else {
+                ProgressCell::Empty
+            }
+        })
+        .collect();
+
+    for &marker in markers_ms.iter().filter(|m| **m < total_ms) {
+        let idx = ((marker as u128 * width as u128) / total_ms as u128) as usize;
+        if let Some(cell) = cells.get_mut(idx) {
+            *cell = ProgressCell::Marker {
+                played: idx < played,
+            };
+        }
+    }
+    cells
+}
+
+/// 在进度条上标出 A-B 循环点（覆盖同一格的其它内容；A、B 落在同一格时显示 B）
+pub(crate) fn mark_loop_points(
+    cells: &mut [ProgressCell],
+    total_ms: Option<u64>,
+    loop_a_ms: Option<u64>,
+    loop_b_ms: Option<u64>,
+) {
+    let Some(total_ms) = total_ms.filter(|t| *t > 0) else {
+        return;
+    };
+    let width = cells.len();
+    for (label, at) in [('A', loop_a_ms), ('B', loop_b_ms)] {
+        let Some(at) = at.filter(|at| *at < total_ms) else {
+            continue;
+        };
+        let idx = ((at as u128 * width as u128) / total_ms as u128) as usize;
+        if let Some(cell) = cells.get_mut(idx) {
+            *cell = ProgressCell::LoopPoint(label);
+        }
+    }
+}
+
+/// 把格子按类型合并为带样式的片段
+pub(crate) fn progress_spans(cells: &[ProgressCell], theme: Theme) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut run = String::new();
+    let mut run_kind: Option<ProgressCell> = None;
+    for &cell in cells {
+        if let Some(kind) = run_kind.filter(|k| *k != cell) {
+            spans.push(Span::styled(std::mem::take(&mut run), kind.style(theme)));
+        }
+        run_kind = Some(cell);
+        run.push(cell.symbol());
+    }
+    if let Some(kind) = run_kind {
+        spans.push(Span::styled(run, kind.style(theme)));
+    }
+    spans
+}
+
+/// 已下载比例：本地缓存文件视为全部可用；总大小未知时不显示缓冲段
+pub(crate) fn buffered_ratio(hint: Option<&AudioStreamHint>) -> Option<f64> {
+    let hint = hint?;
+    if matches!(hint.mode, AudioPlaybackMode::CachedFile) {
+        return Some(1.0);
+    }
+    let total = hint.total_bytes.filter(|t| *t > 0)?;
+    Some(hint.buffered_bytes.unwrap_or(0) as f64 / total as f64)
+}
+
+/// 歌词段落起点（播放时间，已按 offset 换算）
+///
+/// 只看非空歌词行：与上一句（第一句则与 0）间隔达到 [`LYRIC_SECTION_GAP_MS`] 的行算一个段落起点。
+pub(crate) fn lyric_section_starts(lines: &[LyricLine], offset_ms: i64) -> Vec<u64> {
+    let mut prev_ms = 0;
+    let mut starts = Vec::new();
+    for line in lines.iter().filter(|l| !l.text.trim().is_empty()) {
+        if line.time_ms.saturating_sub(prev_ms) >= LYRIC_SECTION_GAP_MS {
+            // 歌词时间 = 播放时间 + offset
+            let at = if offset_ms >= 0 {
+                line.time_ms.saturating_sub(offset_ms.unsigned_abs())
+            } else {
+                line.time_ms.saturating_add(offset_ms.unsigned_abs())
+            };
+            starts.push(at);
+        }
+        prev_ms = line.time_ms;
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio_worker::AudioBufferState;
+
+    fn text(cells: &[ProgressCell]) -> String {
+        cells.iter().map(|c| c.symbol()).collect()
+    }
+
+    fn bar(
+        width: usize,
+        elapsed_ms: u64,
+        total_ms: Option<u64>,
+        markers_ms: &[u64],
+        buffered: Option<f64>,
+    ) -> String {
+        text(&progress_cells(
+            width, elapsed_ms, total_ms, markers_ms, buffered,
+        ))
+    }
+
+    fn line(time_ms: u64, text: &str) -> LyricLine {
+        LyricLine {
+            time_ms,
+            text: text.to_owned(),
+            translation: None,
+            roman: None,
+        }
+    }
+
+    #[test]
+    fn empty_when_no_song_or_zero_duration() {
+        assert_eq!(bar(8, 0, None, &[1_000], Some(0.5)), "--------");
+        assert_eq!(bar(8, 5_000, Some(0), &[], Some(1.0)), "--------");
+        assert_eq!(bar(0, 5_000, Some(10_000), &[1_000], Some(1.0)), "");
+    }
+
+    #[test]
+    fn played_cells_round_to_nearest_boundary() {
+        // 10 格，每格 1s：2.49s → 2 格，2.5s → 3 格
+        assert_eq!(bar(10, 2_490, Some(10_000), &[], None), "##--------");
+        assert_eq!(bar(10, 2_500, Some(10_000), &[], None), "###-------");
+        assert_eq!(bar(10, 0, Some(10_000), &[], None), "----------");
+        assert_eq!(bar(10, 9_950, Some(10_000), &[], None), "##########");
+        // 超出总时长按满格处理
+        assert_eq!(bar(10, 15_000, Some(10_000), &[], None), "##########");
+    }
+
+    #[test]
+    fn buffered_region_follows_played_region() {
+        assert_eq!(bar(10, 2_000, Some(10_000), &[], Some(0.6)), "##====----");
+        // 缓冲比例低于已播放：不出现缓冲段
+        assert_eq!(bar(10, 5_000, Some(10_000), &[], Some(0.2)), "#####-----");
+        // 越界比例被钳制
+        assert_eq!(bar(4, 0, Some(10_000), &[], Some(1.7)), "====");
+        assert_eq!(bar(4, 0, Some(10_000), &[], Some(-0.3)), "----");
+    }
+
+    #[test]
+    fn markers_floor_into_their_cell() {
+        // 每格 1s：2.999s 仍在第 2 格（下标 2），3.0s 进入下标 3
+        assert_eq!(bar(10, 0, Some(10_000), &[2_999], None), "--|-------");
+        assert_eq!(bar(10, 0, Some(10_000), &[3_000], None), "---|------");
+        assert_eq!(bar(10, 0, Some(10_000), &[0], None), "|---------");
+        assert_eq!(bar(10, 0, Some(10_000), &[9_999], None), "---------|");
+    }
+
+    #[test]
+    fn markers_outside_track_are_ignored() {
+        assert_eq!(
+            bar(10, 0, Some(10_000), &[10_000, 60_000], None),
+            "----------"
+        );
+    }
+
+    #[test]
+    fn colliding_markers_share_one_cell() {
+        let cells = progress_cells(10, 0, Some(10_000), &[3_100, 3_900, 3_500], None);
+        assert_eq!(text(&cells), "---|------");
+        assert_eq!(
+            cells
+                .iter()
+                .filter(|c| matches!(c, ProgressCell::Marker { .. }))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn markers_record_whether_they_are_played() {
+        let cells = progress_cells(10, 5_000, Some(10_000), &[1_000, 7_000], Some(0.8));
+        assert_eq!(text(&cells), "#|###==|--");
+        assert_eq!(cells[1], ProgressCell::Marker { played: true });
+        assert_eq!(cells[7], ProgressCell::Marker { played: false });
+    }
+
+    #[test]
+    fn loop_points_overlay_the_bar() {
+        let mut cells = progress_cells(10, 5_000, Some(10_000), &[3_000], None);
+        mark_loop_points(&mut cells, Some(10_000), Some(3_500), Some(8_000));
+        assert_eq!(text(&cells), "###A#---B-");
+        // 未知时长或超出总时长时不标
+        let mut cells = progress_cells(4, 0, None, &[], None);
+        mark_loop_points(&mut cells, None, Some(0), None);
+        assert_eq!(text(&cells), "----");
+        let mut cells = progress_cells(4, 0, Some(4_000), &[], None);
+        mark_loop_points(&mut cells, Some(4_000), Some(1_000), Some(9_000));
+        assert_eq!(text(&cells), "-A--");
+    }
+
+    #[test]
+    fn spans_merge_runs_of_the_same_cell() {
+        let cells = progress_cells(10, 2_000, Some(10_000), &[5_000], Some(0.7));
+        let spans = progress_spans(&cells, Theme::Color);
+        let parts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(parts, ["##", "===", "|", "=", "---"]);
+        assert!(progress_spans(&[], Theme::Color).is_empty());
+        assert!(
+            progress_spans(&cells, Theme::Monochrome)
+                .iter()
+                .all(|s| s.style.fg.is_none())
+        );
+    }
+
+    #[test]
+    fn buffered_ratio_from_stream_hint() {
+        assert_eq!(buffered_ratio(None), None);
+        assert_eq!(
+            buffered_ratio(Some(&AudioStreamHint::cached_file(None))),
+            Some(1.0)
+        );
+        let hint = AudioStreamHint::progressive(AudioBufferState::Ready, false, 256, Some(1024));
+        assert_eq!(buffered_ratio(Some(&hint)), Some(0.25));
+        let unknown = AudioStreamHint::progressive(AudioBufferState::Ready, false, 256, None);
+        assert_eq!(buffered_ratio(Some(&unknown)), None);
+    }
+
+    #[test]
+    fn lyric_sections_start_after_long_gaps() {
+        let lines = [
+            line(32_000, "第一段"),
+            line(40_000, "第二句"),
+            line(45_000, ""),
+            line(80_000, "第二段"),
+            line(95_000, "第三句"),
+        ];
+        // 前奏 32s 也算一个间隔；空行不打断间隔计算
+        assert_eq!(lyric_section_starts(&lines, 0), [32_000, 80_000]);
+        // 歌词时间 = 播放时间 + offset
+        assert_eq!(lyric_section_starts(&lines, 2_000), [30_000, 78_000]);
+        assert_eq!(lyric_section_starts(&lines, -2_000), [34_000, 82_000]);
+        assert!(lyric_section_starts(&[line(5_000, "a"), line(20_000, "b")], 0).is_empty());
+    }
+}
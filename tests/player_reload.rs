@@ -1,4 +1,4 @@
-use netease_ratui::core::prelude::audio::{AudioBufferState, AudioStreamHint};
+use netease_ratui::core::prelude::audio::{AudioBufferState, AudioStreamHint, TrackTags};
 /// 测试播放器重启后自动恢复播放功能
 ///
 /// 这个测试套件验证以下场景：
@@ -64,10 +64,43 @@ fn test_audio_event_all_variants() {
         },
         AudioEvent::Stopped,
         AudioEvent::Ended { play_id: 789 },
+        AudioEvent::Position {
+            play_id: 456,
+            position_ms: 42_000,
+        },
         AudioEvent::CacheCleared {
             files: 10,
             bytes: 1024 * 1024,
         },
+        AudioEvent::CacheEvicted {
+            files: 2,
+            bytes: 512 * 1024,
+        },
+        AudioEvent::CacheStats {
+            files: 30,
+            bytes: 90 * 1024 * 1024,
+        },
+        AudioEvent::CachedSongs {
+            song_ids: vec![1, 3],
+        },
+        AudioEvent::Exported {
+            song_id: 123,
+            path: std::path::PathBuf::from("/music/Artist - Test Song.flac"),
+        },
+        AudioEvent::ExportFailed {
+            song_id: 123,
+            message: "disk full".to_string(),
+        },
+        AudioEvent::CacheMigrateProgress { done: 5, total: 8 },
+        AudioEvent::CacheMigrated {
+            files: 7,
+            bytes: 70 * 1024 * 1024,
+            failed: 1,
+            cancelled: false,
+        },
+        AudioEvent::DownloadCooldown {
+            duration: std::time::Duration::from_secs(120),
+        },
         AudioEvent::Error(netease_ratui::error::MessageError::Other(
             "Test error".to_string(),
         )),
@@ -75,7 +108,7 @@ fn test_audio_event_all_variants() {
     ];
 
     // 验证事件数量
-    assert_eq!(events.len(), 10, "应该有 10 个事件变体");
+    assert_eq!(events.len(), 19, "应该有 19 个事件变体");
 
     // 对每个事件进行有意义的验证
     for event in events {
@@ -121,10 +154,53 @@ fn test_audio_event_all_variants() {
             AudioEvent::Ended { play_id } => {
                 assert_eq!(play_id, 789);
             }
+            AudioEvent::Position {
+                play_id,
+                position_ms,
+            } => {
+                assert_eq!(play_id, 456);
+                assert_eq!(position_ms, 42_000);
+            }
             AudioEvent::CacheCleared { files, bytes } => {
                 assert_eq!(files, 10);
                 assert_eq!(bytes, 1024 * 1024);
             }
+            AudioEvent::CacheEvicted { files, bytes } => {
+                assert_eq!(files, 2);
+                assert_eq!(bytes, 512 * 1024);
+            }
+            AudioEvent::CacheStats { files, bytes } => {
+                assert_eq!(files, 30);
+                assert_eq!(bytes, 90 * 1024 * 1024);
+            }
+            AudioEvent::CachedSongs { song_ids } => {
+                assert_eq!(song_ids, vec![1, 3]);
+            }
+            AudioEvent::Exported { song_id, path } => {
+                assert_eq!(song_id, 123);
+                assert_eq!(path.extension().and_then(|e| e.to_str()), Some("flac"));
+            }
+            AudioEvent::ExportFailed { song_id, message } => {
+                assert_eq!(song_id, 123);
+                assert_eq!(message, "disk full");
+            }
+            AudioEvent::CacheMigrateProgress { done, total } => {
+                assert_eq!((done, total), (5, 8));
+            }
+            AudioEvent::CacheMigrated {
+                files,
+                bytes,
+                failed,
+                cancelled,
+            } => {
+                assert_eq!(files, 7);
+                assert_eq!(bytes, 70 * 1024 * 1024);
+                assert_eq!(failed, 1);
+                assert!(!cancelled);
+            }
+            AudioEvent::DownloadCooldown { duration } => {
+                assert_eq!(duration.as_secs(), 120);
+            }
             AudioEvent::Error(err) => {
                 assert!(matches!(err, netease_ratui::error::MessageError::Other(_)));
                 assert_eq!(err.to_string(), "Test error");
@@ -142,28 +218,68 @@ fn test_audio_command_all_variants() {
     let commands = vec![
         AudioCommand::PlayTrack {
             id: 123,
-            br: 320000,
+            br: 128000,
+            requested_br: 320000,
             url: "http://example.com/audio.mp3".to_string(),
+            expected_bytes: Some(4_000_000),
             title: "Test Song".to_string(),
             duration_ms: Some(180000),
+            no_fade: true,
+            gain_db: Some(-4.5),
         },
         AudioCommand::TogglePause,
         AudioCommand::Stop,
         AudioCommand::SeekToMs(60000),
         AudioCommand::SetVolume(0.8),
+        AudioCommand::SetVolumeNormalization(true),
+        AudioCommand::SetPlaybackSpeed(1.25),
+        AudioCommand::SetEqualizer {
+            bass_db: 3,
+            treble_db: -2,
+        },
         AudioCommand::SetCrossfadeMs(300),
         AudioCommand::ClearCache,
         AudioCommand::SetCacheBr(320000),
-        AudioCommand::PrefetchAudio {
+        AudioCommand::QueueNext {
             id: 456,
             br: 320000,
+            requested_br: 320000,
             url: "http://example.com/audio2.mp3".to_string(),
+            expected_bytes: None,
             title: "Test Song 2".to_string(),
+            duration_ms: None,
+            no_fade: false,
+            gain_db: None,
         },
+        AudioCommand::ClearQueued,
+        AudioCommand::QueryCacheStats,
+        AudioCommand::QueryCachedSongs {
+            song_ids: vec![1, 2, 3],
+            br: 320000,
+        },
+        AudioCommand::ExportTrack {
+            id: 123,
+            br: 320000,
+            url: "http://example.com/audio.mp3".to_string(),
+            expected_bytes: Some(4_000_000),
+            tags: TrackTags {
+                title: "Test Song".to_string(),
+                artists: vec!["Artist".to_string()],
+                album: "Test Album".to_string(),
+                track_no: Some(1),
+                cover_url: None,
+            },
+            dest: std::path::PathBuf::from("/music/Artist - Test Song"),
+        },
+        AudioCommand::CancelExport { id: 123 },
+        AudioCommand::MigrateCache {
+            from: std::path::PathBuf::from("/data/netease/audio_cache"),
+        },
+        AudioCommand::CancelCacheMigration,
     ];
 
     // 验证命令数量
-    assert_eq!(commands.len(), 9, "应该有 9 个命令变体");
+    assert_eq!(commands.len(), 19, "应该有 19 个命令变体");
 
     // 对每个命令进行有意义的验证
     for cmd in commands {
@@ -171,15 +287,23 @@ fn test_audio_command_all_variants() {
             AudioCommand::PlayTrack {
                 id,
                 br,
+                requested_br,
                 url,
+                expected_bytes,
                 title,
                 duration_ms,
+                no_fade,
+                gain_db,
             } => {
                 assert_eq!(id, 123);
-                assert_eq!(br, 320000);
+                assert_eq!(br, 128000);
+                assert_eq!(requested_br, 320000);
                 assert_eq!(url, "http://example.com/audio.mp3");
+                assert_eq!(expected_bytes, Some(4_000_000));
                 assert_eq!(title, "Test Song");
                 assert_eq!(duration_ms, Some(180000));
+                assert!(no_fade);
+                assert_eq!(gain_db, Some(-4.5));
             }
             AudioCommand::TogglePause => {
                 // TogglePause 没有字段，只需匹配成功
@@ -193,6 +317,15 @@ fn test_audio_command_all_variants() {
             AudioCommand::SetVolume(vol) => {
                 assert_eq!(vol, 0.8);
             }
+            AudioCommand::SetVolumeNormalization(on) => {
+                assert!(on);
+            }
+            AudioCommand::SetPlaybackSpeed(speed) => {
+                assert_eq!(speed, 1.25);
+            }
+            AudioCommand::SetEqualizer { bass_db, treble_db } => {
+                assert_eq!((bass_db, treble_db), (3, -2));
+            }
             AudioCommand::SetCrossfadeMs(ms) => {
                 assert_eq!(ms, 300);
             }
@@ -202,11 +335,64 @@ fn test_audio_command_all_variants() {
             AudioCommand::SetCacheBr(br) => {
                 assert_eq!(br, 320000);
             }
-            AudioCommand::PrefetchAudio { id, br, url, title } => {
+            AudioCommand::QueueNext {
+                id,
+                br,
+                requested_br,
+                url,
+                expected_bytes,
+                title,
+                duration_ms,
+                no_fade,
+                gain_db,
+            } => {
                 assert_eq!(id, 456);
                 assert_eq!(br, 320000);
+                assert_eq!(requested_br, 320000);
                 assert_eq!(url, "http://example.com/audio2.mp3");
+                assert_eq!(expected_bytes, None);
                 assert_eq!(title, "Test Song 2");
+                assert_eq!(duration_ms, None);
+                assert!(!no_fade);
+                assert_eq!(gain_db, None);
+            }
+            AudioCommand::ClearQueued => {
+                // ClearQueued 没有字段，只需匹配成功
+            }
+            AudioCommand::QueryCacheStats => {
+                // QueryCacheStats 没有字段，只需匹配成功
+            }
+            AudioCommand::QueryCachedSongs { song_ids, br } => {
+                assert_eq!(song_ids, vec![1, 2, 3]);
+                assert_eq!(br, 320000);
+            }
+            AudioCommand::ExportTrack {
+                id,
+                br,
+                url,
+                expected_bytes,
+                tags,
+                dest,
+            } => {
+                assert_eq!(id, 123);
+                assert_eq!(br, 320000);
+                assert_eq!(url, "http://example.com/audio.mp3");
+                assert_eq!(expected_bytes, Some(4_000_000));
+                assert_eq!(tags.title, "Test Song");
+                assert_eq!(tags.artists, ["Artist"]);
+                assert_eq!(
+                    dest.file_name().and_then(|n| n.to_str()),
+                    Some("Artist - Test Song")
+                );
+            }
+            AudioCommand::CancelExport { id } => {
+                assert_eq!(id, 123);
+            }
+            AudioCommand::MigrateCache { from } => {
+                assert!(from.ends_with("audio_cache"));
+            }
+            AudioCommand::CancelCacheMigration => {
+                // CancelCacheMigration 没有字段，只需匹配成功
             }
         }
     }
@@ -14,7 +14,7 @@ async fn quit_waits_for_final_state_save() {
         ..Default::default()
     };
 
-    let (tx, _rx, app_actor) = spawn_app_actor(cfg, AudioBackend::Null);
+    let (tx, _rx, app_actor) = spawn_app_actor(cfg, AudioBackend::Null, None);
     tx.send(AppCommand::Quit).await.expect("send quit");
     drop(tx);
 
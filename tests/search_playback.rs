@@ -1,6 +1,6 @@
 use netease_ratui::app::App;
 use netease_ratui::core::CoreEffects;
-use netease_ratui::core::infra::{RequestKey, RequestTracker};
+use netease_ratui::core::infra::{IdAllocator, NextSongCacheManager, RequestKey, RequestTracker};
 use netease_ratui::domain::model::Song;
 use netease_ratui::features::search::handle_search_command;
 use netease_ratui::messages::app::AppCommand;
@@ -14,17 +14,19 @@ async fn search_play_selected_uses_song_url_request() {
         artists: "Artist".to_owned(),
         ..Default::default()
     });
-    let mut req_id = 1u64;
+    let req_ids = IdAllocator::default();
     let mut tracker = RequestTracker::new();
     let mut titles = std::collections::HashMap::new();
+    let mut next_song_cache = NextSongCacheManager::default();
     let mut effects = CoreEffects::default();
 
     handle_search_command(
         AppCommand::SearchPlaySelected,
         &mut app,
-        &mut req_id,
+        &req_ids,
         &mut tracker,
         &mut titles,
+        &mut next_song_cache,
         &mut effects,
     )
     .await;
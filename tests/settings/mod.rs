@@ -1,4 +1,5 @@
-use netease_ratui::settings::{AppSettings, load_settings, save_settings};
+use netease_ratui::settings::lyrics_offsets::MAX_LYRICS_OFFSETS;
+use netease_ratui::settings::{AppSettings, LyricsOffsets, load_settings, save_settings};
 use std::fs;
 
 #[test]
@@ -8,39 +9,99 @@ fn settings_roundtrip() {
 
     let s = AppSettings {
         volume: 0.42,
+        volume_step: 0.02,
         br: 320_000,
         play_mode: "Shuffle".to_owned(),
         lyrics_offset_ms: -200,
+        lyrics_song_offsets: LyricsOffsets::default(),
+        lyrics_max_lines: 500,
+        status_lyrics: false,
         crossfade_ms: 350,
+        normalize_volume: true,
+        playback_speed: 1.25,
+        eq_bass_db: 4,
+        eq_treble_db: -3,
+        seek_step_small_ms: 10_000,
+        seek_step_large_ms: 60_000,
+        pause_on_suspend: true,
+        pause_on_focus_lost: true,
+        audio_focus_policy: "Duck".to_owned(),
+        audio_focus_duck_percent: 30,
+        global_hotkeys: true,
+        notifications_enabled: true,
+        audio_idle_release_secs: 0,
+        startup_view: "Search".to_owned(),
+        restore_queue_on_startup: false,
+        resume_on_start: true,
+        stale_state_prompt_hours: 0,
+        pane_ratios: [25, 45, 30],
+        song_name_mode: "Both".to_owned(),
+        no_color: true,
 
         // 新增字段
         preload_count: 10,
         audio_cache_max_mb: 4096,
+        audio_cache_dir: Some(std::path::PathBuf::from("/hdd/cache")),
         download_concurrency: Some(4),
         http_timeout_secs: 60,
         http_connect_timeout_secs: 15,
         download_retries: 3,
         download_retry_backoff_ms: 500,
         download_retry_backoff_max_ms: 5000,
+        download_dir: Some(std::path::PathBuf::from("/music")),
+        log_rotation: "hourly".to_owned(),
+        log_max_files: 3,
     };
     save_settings(data_dir, &s).expect("save_settings");
 
     let loaded = load_settings(data_dir);
     assert!((loaded.volume - 0.42).abs() < f32::EPSILON);
+    assert!((loaded.volume_step - 0.02).abs() < f32::EPSILON);
     assert_eq!(loaded.br, 320_000);
     assert_eq!(loaded.play_mode, "Shuffle");
     assert_eq!(loaded.lyrics_offset_ms, -200);
+    assert_eq!(loaded.lyrics_max_lines, 500);
+    assert!(!loaded.status_lyrics);
     assert_eq!(loaded.crossfade_ms, 350);
+    assert!(loaded.normalize_volume);
+    assert!((loaded.playback_speed - 1.25).abs() < f32::EPSILON);
+    assert_eq!((loaded.eq_bass_db, loaded.eq_treble_db), (4, -3));
+    assert_eq!(loaded.seek_step_small_ms, 10_000);
+    assert_eq!(loaded.seek_step_large_ms, 60_000);
+    assert!(loaded.pause_on_suspend);
+    assert!(loaded.pause_on_focus_lost);
+    assert_eq!(loaded.audio_focus_policy, "Duck");
+    assert_eq!(loaded.audio_focus_duck_percent, 30);
+    assert!(loaded.global_hotkeys);
+    assert!(loaded.notifications_enabled);
+    assert_eq!(loaded.audio_idle_release_secs, 0);
+    assert_eq!(loaded.startup_view, "Search");
+    assert!(!loaded.restore_queue_on_startup);
+    assert!(loaded.resume_on_start);
+    assert_eq!(loaded.stale_state_prompt_hours, 0);
+    assert_eq!(loaded.pane_ratios, [25, 45, 30]);
+    assert_eq!(loaded.song_name_mode, "Both");
+    assert!(loaded.no_color);
 
     // 验证新增字段
     assert_eq!(loaded.preload_count, 10);
     assert_eq!(loaded.audio_cache_max_mb, 4096);
+    assert_eq!(
+        loaded.audio_cache_dir,
+        Some(std::path::PathBuf::from("/hdd/cache"))
+    );
     assert_eq!(loaded.download_concurrency, Some(4));
     assert_eq!(loaded.http_timeout_secs, 60);
     assert_eq!(loaded.http_connect_timeout_secs, 15);
     assert_eq!(loaded.download_retries, 3);
     assert_eq!(loaded.download_retry_backoff_ms, 500);
     assert_eq!(loaded.download_retry_backoff_max_ms, 5000);
+    assert_eq!(
+        loaded.download_dir,
+        Some(std::path::PathBuf::from("/music"))
+    );
+    assert_eq!(loaded.log_rotation, "hourly");
+    assert_eq!(loaded.log_max_files, 3);
 }
 
 #[test]
@@ -56,17 +117,34 @@ fn settings_default_values() {
     assert_eq!(loaded.br, 999_000);
     assert_eq!(loaded.play_mode, "ListLoop");
     assert_eq!(loaded.lyrics_offset_ms, 0);
+    assert_eq!(loaded.lyrics_max_lines, 2000);
+    assert!(loaded.status_lyrics);
     assert_eq!(loaded.crossfade_ms, 300);
+    assert_eq!(loaded.seek_step_small_ms, 5_000);
+    assert_eq!(loaded.seek_step_large_ms, 30_000);
+    assert!(!loaded.pause_on_suspend);
+    assert!(!loaded.pause_on_focus_lost);
+    assert!(!loaded.global_hotkeys);
+    assert!(!loaded.notifications_enabled);
+    assert_eq!(loaded.startup_view, "Playlists");
+    assert!(loaded.restore_queue_on_startup);
+    assert!(!loaded.resume_on_start);
+    assert_eq!(loaded.stale_state_prompt_hours, 48);
+    assert_eq!(loaded.pane_ratios, [20, 47, 33]);
 
     // 新增字段默认值
     assert_eq!(loaded.preload_count, 5);
     assert_eq!(loaded.audio_cache_max_mb, 2048);
+    assert_eq!(loaded.audio_cache_dir, None);
     assert_eq!(loaded.download_concurrency, None);
     assert_eq!(loaded.http_timeout_secs, 30);
     assert_eq!(loaded.http_connect_timeout_secs, 10);
     assert_eq!(loaded.download_retries, 2);
     assert_eq!(loaded.download_retry_backoff_ms, 250);
     assert_eq!(loaded.download_retry_backoff_max_ms, 2000);
+    assert_eq!(loaded.download_dir, None);
+    assert_eq!(loaded.log_rotation, "daily");
+    assert_eq!(loaded.log_max_files, 14);
 }
 
 #[test]
@@ -79,3 +157,81 @@ fn settings_corrupt_file_falls_back_to_default() {
     let loaded = load_settings(data_dir);
     assert_eq!(loaded.br, AppSettings::default().br);
 }
+
+#[test]
+fn per_song_lyrics_offsets_roundtrip() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let data_dir = dir.path();
+
+    let mut s = AppSettings::default();
+    s.lyrics_song_offsets.set(186016, 350);
+    s.lyrics_song_offsets.set(5257138, -120);
+    save_settings(data_dir, &s).expect("save_settings");
+
+    let loaded = load_settings(data_dir);
+    assert_eq!(loaded.lyrics_song_offsets, s.lyrics_song_offsets);
+    assert_eq!(loaded.lyrics_song_offsets.get(186016), Some(350));
+    assert_eq!(loaded.lyrics_song_offsets.get(5257138), Some(-120));
+    assert_eq!(loaded.lyrics_offset_ms, 0, "全局 offset 不受影响");
+}
+
+#[test]
+fn per_song_lyrics_offsets_are_pruned_before_saving() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let data_dir = dir.path();
+
+    let mut s = AppSettings::default();
+    for id in 0..(MAX_LYRICS_OFFSETS as i64 + 20) {
+        s.lyrics_song_offsets.set(id, 100);
+    }
+    save_settings(data_dir, &s).expect("save_settings");
+
+    let loaded = load_settings(data_dir);
+    assert_eq!(loaded.lyrics_song_offsets.get(19), None);
+    assert_eq!(loaded.lyrics_song_offsets.get(20), Some(100));
+    assert_eq!(
+        loaded
+            .lyrics_song_offsets
+            .get(MAX_LYRICS_OFFSETS as i64 + 19),
+        Some(100)
+    );
+}
+
+#[test]
+fn settings_without_per_song_offsets_still_load() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let data_dir = dir.path();
+    fs::create_dir_all(data_dir).expect("create_dir_all");
+    fs::write(
+        data_dir.join("settings.json"),
+        br#"{"volume":0.5,"br":320000,"play_mode":"ListLoop","lyrics_offset_ms":150}"#,
+    )
+    .expect("write");
+
+    let loaded = load_settings(data_dir);
+    assert_eq!(loaded.lyrics_offset_ms, 150);
+    assert_eq!(loaded.lyrics_song_offsets, LyricsOffsets::default());
+}
+
+#[test]
+fn hand_edited_out_of_range_values_are_clamped_on_load() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    let data_dir = dir.path();
+    fs::create_dir_all(data_dir).expect("create_dir_all");
+    fs::write(
+        data_dir.join("settings.json"),
+        br#"{"volume":9.5,"br":12345,"play_mode":"Random","lyrics_offset_ms":-150,
+            "preload_count":100000,"download_retries":-3,"http_timeout_secs":0}"#,
+    )
+    .expect("write");
+
+    // 负数的重试次数不再让整个文件回退为默认值
+    let loaded = load_settings(data_dir);
+    assert_eq!(loaded.volume, 2.0);
+    assert_eq!(loaded.br, 128_000);
+    assert_eq!(loaded.play_mode, "ListLoop");
+    assert_eq!(loaded.lyrics_offset_ms, -150);
+    assert_eq!(loaded.preload_count, 50);
+    assert_eq!(loaded.download_retries, 0);
+    assert_eq!(loaded.http_timeout_secs, 1);
+}